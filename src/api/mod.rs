@@ -0,0 +1,7 @@
+//! HTTP API for external monitors and dashboards
+
+pub mod status;
+pub mod server;
+
+pub use status::*;
+pub use server::*;
@@ -0,0 +1,128 @@
+//! Minimal HTTP/1.1 server for the health, status and dashboard endpoints
+//!
+//! `GET /health` returns the health check, with 503 while unhealthy, and
+//! `GET /status` the full status report. `GET /` serves the dashboard page,
+//! which loads `/dashboard` and then follows `/events`, a server-sent event
+//! stream with one dashboard report per monitoring cycle. Every other
+//! response closes the connection.
+
+use anyhow::{Context, Result};
+use serde_json::json;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, info, warn};
+use crate::api::StatusBoard;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// Request heads beyond this are cut off; the endpoints take no body
+const MAX_REQUEST_BYTES: usize = 8192;
+/// Comment line sent on an idle event stream so proxies keep it open
+const EVENT_KEEPALIVE: Duration = Duration::from_secs(15);
+
+const DASHBOARD_HTML: &str = include_str!("dashboard.html");
+
+/// Bind `bind_addr` and serve the API in the background, returning the bound address
+pub async fn spawn_status_server(bind_addr: &str, board: StatusBoard) -> Result<SocketAddr> {
+    let listener = TcpListener::bind(bind_addr).await
+        .with_context(|| format!("Failed to bind status API on {}", bind_addr))?;
+    let local_addr = listener.local_addr()?;
+    info!("🌐 Status API and dashboard listening on http://{}", local_addr);
+
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    let board = board.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_connection(stream, &board).await {
+                            debug!("Status API request from {} failed: {}", peer, e);
+                        }
+                    });
+                }
+                Err(e) => {
+                    warn!("Status API accept failed: {}", e);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        }
+    });
+    Ok(local_addr)
+}
+
+async fn handle_connection(mut stream: TcpStream, board: &StatusBoard) -> Result<()> {
+    let mut head = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") && head.len() < MAX_REQUEST_BYTES {
+        let read = tokio::time::timeout(REQUEST_TIMEOUT, stream.read(&mut chunk)).await
+            .context("Timed out reading request")??;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&chunk[..read]);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+    if method == "GET" && path == "/events" {
+        return stream_events(stream, board).await;
+    }
+
+    let (status, content_type, body) = route(method, path, board).await;
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, content_type, body.len(), body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Status line, content type and body for a request
+async fn route(method: &str, path: &str, board: &StatusBoard) -> (&'static str, &'static str, String) {
+    const JSON: &str = "application/json";
+    if method != "GET" {
+        return ("405 Method Not Allowed", JSON, json!({ "error": "only GET is supported" }).to_string());
+    }
+    let body = match path {
+        "/" => return ("200 OK", "text/html; charset=utf-8", DASHBOARD_HTML.to_string()),
+        "/health" => board.health().map(|health| (serde_json::to_string(&health), health.is_healthy())),
+        "/status" => board.latest().await.map(|report| (serde_json::to_string(&report), true)),
+        "/monitoring" => board.latest().await.map(|report| (serde_json::to_string(&report.monitoring), true)),
+        "/dashboard" => board.dashboard().await.map(|report| (serde_json::to_string(&report), true)),
+        _ => return ("404 Not Found", JSON, json!({ "error": format!("unknown endpoint {}", path) }).to_string()),
+    };
+
+    match body {
+        None => ("503 Service Unavailable", JSON, json!({ "status": "starting" }).to_string()),
+        Some((Ok(body), true)) => ("200 OK", JSON, body),
+        Some((Ok(body), false)) => ("503 Service Unavailable", JSON, body),
+        Some((Err(e), _)) => ("500 Internal Server Error", JSON, json!({ "error": e.to_string() }).to_string()),
+    }
+}
+
+/// Send the dashboard report now and after every published cycle until the client disconnects
+async fn stream_events(mut stream: TcpStream, board: &StatusBoard) -> Result<()> {
+    let mut updates = board.subscribe();
+    stream.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n"
+    ).await?;
+
+    loop {
+        if let Some(report) = board.dashboard().await {
+            let event = format!("data: {}\n\n", serde_json::to_string(&report)?);
+            stream.write_all(event.as_bytes()).await?;
+        }
+        loop {
+            match tokio::time::timeout(EVENT_KEEPALIVE, updates.changed()).await {
+                Ok(Ok(())) => break,
+                Ok(Err(_)) => return Ok(()),
+                Err(_) => stream.write_all(b": keepalive\n\n").await?,
+            }
+        }
+    }
+}
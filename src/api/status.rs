@@ -0,0 +1,89 @@
+//! Latest status report shared between the monitoring loop and the API
+
+use std::sync::Arc;
+use tokio::sync::{watch, RwLock};
+use crate::types::{DashboardReport, HealthStatus, RecentActivity, StatusReport};
+
+/// The monitoring loop publishes a report after each cycle and the health
+/// task a health check on its interval; API requests read the latest ones
+/// and never wait on the loop
+#[derive(Clone)]
+pub struct StatusBoard {
+    latest: Arc<RwLock<Option<StatusReport>>>,
+    activity: Arc<RwLock<RecentActivity>>,
+    /// Bumped on every published report, for streaming clients
+    updates: Arc<watch::Sender<u64>>,
+    /// Newest health check, from either publisher
+    health: Arc<watch::Sender<Option<HealthStatus>>>,
+}
+
+impl Default for StatusBoard {
+    fn default() -> Self {
+        Self {
+            latest: Arc::new(RwLock::new(None)),
+            activity: Arc::new(RwLock::new(RecentActivity::default())),
+            updates: Arc::new(watch::channel(0).0),
+            health: Arc::new(watch::channel(None).0),
+        }
+    }
+}
+
+impl StatusBoard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn publish(&self, report: StatusReport) {
+        let health = report.health.clone();
+        *self.latest.write().await = Some(report);
+        self.health.send_replace(Some(health));
+        self.updates.send_modify(|version| *version += 1);
+    }
+
+    /// Record a health check made between reports
+    pub fn publish_health(&self, health: HealthStatus) {
+        self.health.send_replace(Some(health));
+    }
+
+    /// Replace the dashboard's recent activity; served with the next report
+    pub async fn publish_activity(&self, activity: RecentActivity) {
+        *self.activity.write().await = activity;
+    }
+
+    /// None until the first cycle completes. Carries the newest health
+    /// check, which may be younger than the report.
+    pub async fn latest(&self) -> Option<StatusReport> {
+        let mut report = self.latest_report().await?;
+        if let Some(health) = self.health() {
+            report.health = health;
+        }
+        Some(report)
+    }
+
+    /// The report as the loop published it
+    pub(crate) async fn latest_report(&self) -> Option<StatusReport> {
+        self.latest.read().await.clone()
+    }
+
+    /// None until the first health check or report
+    pub fn health(&self) -> Option<HealthStatus> {
+        self.health.borrow().clone()
+    }
+
+    /// Changes with every health check and report
+    pub fn subscribe_health(&self) -> watch::Receiver<Option<HealthStatus>> {
+        self.health.subscribe()
+    }
+
+    pub async fn dashboard(&self) -> Option<DashboardReport> {
+        Some(DashboardReport {
+            status: self.latest().await?,
+            activity: self.activity.read().await.clone(),
+        })
+    }
+
+    /// Changes each time a report is published
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.updates.subscribe()
+    }
+}
@@ -0,0 +1,145 @@
+//! Per-pool DEX/CEX basis statistics
+//!
+//! [`BasisTracker`] keeps each pool's recent basis (DEX price minus CEX
+//! price, in USD) and scores the current one against the window's mean and
+//! standard deviation. A pool whose DEX persistently trades a little away
+//! from the CEX then only signals arbitrage when the gap moves beyond its
+//! usual range, rather than whenever it clears a fixed percentage.
+
+use rust_decimal::prelude::*;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// Default window of basis samples the statistics cover
+pub const DEFAULT_BASIS_WINDOW_SECS: u64 = 3600;
+
+/// Earlier samples a pool needs before its z-score gates opportunities
+pub const MIN_BASIS_SAMPLES: usize = 30;
+
+/// Standard deviations of basis noise an auto-calibrated price-difference
+/// threshold covers
+pub const BASIS_NOISE_MULTIPLE: Decimal = Decimal::TWO;
+
+/// The current basis of a pool against its recent history
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BasisStats {
+    /// DEX price minus CEX price
+    pub basis: Decimal,
+    /// Mean and standard deviation of the earlier samples in the window
+    pub mean: Decimal,
+    pub stdev: Decimal,
+    pub samples: usize,
+    /// Standard deviations of the basis from the mean; None below
+    /// `MIN_BASIS_SAMPLES` or when the basis hasn't moved
+    pub z_score: Option<Decimal>,
+    /// Correlation of consecutive DEX and CEX price changes in the window
+    pub correlation: Option<f64>,
+}
+
+impl BasisStats {
+    /// Whether the basis is far enough from its mean to trade: always with a
+    /// zero threshold or while the z-score is unknown
+    pub fn signals(&self, z_threshold: Decimal) -> bool {
+        z_threshold <= Decimal::ZERO || self.z_score.is_none_or(|z| z.abs() >= z_threshold)
+    }
+
+    /// `floor` percent, or `BASIS_NOISE_MULTIPLE` standard deviations of the
+    /// basis as a percentage of `price` when that is wider. Until the pool
+    /// has `MIN_BASIS_SAMPLES` the floor alone applies.
+    pub fn calibrated_min_diff_pct(&self, floor: Decimal, price: Decimal) -> Decimal {
+        if self.samples < MIN_BASIS_SAMPLES || price <= Decimal::ZERO {
+            return floor;
+        }
+        floor.max(BASIS_NOISE_MULTIPLE * self.stdev / price * Decimal::ONE_HUNDRED)
+    }
+}
+
+struct BasisSample {
+    seen_at: Instant,
+    dex: f64,
+    cex: f64,
+}
+
+#[derive(Default)]
+pub struct BasisTracker {
+    pools: RwLock<HashMap<String, VecDeque<BasisSample>>>,
+}
+
+impl BasisTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Score `pool`'s basis against the samples of the last `window`, then
+    /// record it
+    pub async fn record(&self, pool: &str, dex_price: Decimal, cex_price: Decimal, window: Duration) -> BasisStats {
+        self.record_at(pool, dex_price, cex_price, window, Instant::now()).await
+    }
+
+    pub async fn record_at(
+        &self,
+        pool: &str,
+        dex_price: Decimal,
+        cex_price: Decimal,
+        window: Duration,
+        now: Instant,
+    ) -> BasisStats {
+        let mut pools = self.pools.write().await;
+        let samples = pools.entry(pool.to_string()).or_default();
+        while samples.front().is_some_and(|s| now.saturating_duration_since(s.seen_at) > window) {
+            samples.pop_front();
+        }
+
+        let basis = dex_price - cex_price;
+        let history: Vec<f64> = samples.iter().map(|s| s.dex - s.cex).collect();
+        let count = history.len() as f64;
+        let mean = if history.is_empty() { 0.0 } else { history.iter().sum::<f64>() / count };
+        let stdev = if history.is_empty() {
+            0.0
+        } else {
+            (history.iter().map(|b| (b - mean).powi(2)).sum::<f64>() / count).sqrt()
+        };
+        let z_score = (history.len() >= MIN_BASIS_SAMPLES && stdev > 0.0)
+            .then(|| (basis.to_f64().unwrap_or_default() - mean) / stdev)
+            .and_then(Decimal::from_f64);
+
+        samples.push_back(BasisSample {
+            seen_at: now,
+            dex: dex_price.to_f64().unwrap_or_default(),
+            cex: cex_price.to_f64().unwrap_or_default(),
+        });
+
+        BasisStats {
+            basis,
+            mean: Decimal::from_f64(mean).unwrap_or_default(),
+            stdev: Decimal::from_f64(stdev).unwrap_or_default(),
+            samples: history.len(),
+            z_score,
+            correlation: price_change_correlation(samples),
+        }
+    }
+}
+
+/// Pearson correlation of consecutive DEX and CEX price changes
+fn price_change_correlation(samples: &VecDeque<BasisSample>) -> Option<f64> {
+    let changes: Vec<(f64, f64)> = samples.iter().zip(samples.iter().skip(1))
+        .map(|(a, b)| (b.dex - a.dex, b.cex - a.cex))
+        .collect();
+    if changes.len() < 2 {
+        return None;
+    }
+    let count = changes.len() as f64;
+    let (dex_mean, cex_mean) = (
+        changes.iter().map(|(d, _)| d).sum::<f64>() / count,
+        changes.iter().map(|(_, c)| c).sum::<f64>() / count,
+    );
+    let covariance: f64 = changes.iter().map(|(d, c)| (d - dex_mean) * (c - cex_mean)).sum();
+    let dex_variance: f64 = changes.iter().map(|(d, _)| (d - dex_mean).powi(2)).sum();
+    let cex_variance: f64 = changes.iter().map(|(_, c)| (c - cex_mean).powi(2)).sum();
+    if dex_variance <= 0.0 || cex_variance <= 0.0 {
+        return None;
+    }
+    Some(covariance / (dex_variance * cex_variance).sqrt())
+}
@@ -3,18 +3,38 @@
 use chrono::Utc;
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
-use crate::types::{ArbitrageOpportunity, ValidationResult};
+use crate::{
+    arbitrage::FeeModel,
+    pools::{PoolQuote, PoolReserves},
+    types::{ArbitrageOpportunity, OpportunityStage, OrderBook, PriceBasis, ValidationResult},
+};
 
+/// Price difference below which no opportunity is reported, in percent
+pub const DEFAULT_MIN_PRICE_DIFF_PCT: Decimal = dec!(0.05);
+
+/// Detect an arbitrage opportunity from the DEX mid price once it is at
+/// least `min_price_diff_pct` away from the CEX price.
+///
+/// The P&L is computed from the execution price for the trade size (curve
+/// and fee included): the pool's own quote when available, otherwise one
+/// derived from `reserves`, and the mid price only when neither is known.
+/// Net profit also pays `fees`: the CEX fee, the transfer cost and, on the
+/// mid-price fallback, the DEX pool fee.
 pub fn calculate_arbitrage(
     pool_name: &str,
     dex_price: Decimal,
     cex_price: Decimal,
     trade_size: Decimal,
+    quote: Option<&PoolQuote>,
+    reserves: Option<&PoolReserves>,
+    gas_cost_usd: Decimal,
+    fees: &FeeModel,
+    min_price_diff_pct: Decimal,
 ) -> Option<ArbitrageOpportunity> {
     let price_diff = dex_price - cex_price;
     let price_diff_pct = (price_diff.abs() / cex_price) * dec!(100);
     
-    if price_diff_pct < dec!(0.05) {
+    if price_diff_pct < min_price_diff_pct {
         return None;
     }
     
@@ -24,9 +44,25 @@ pub fn calculate_arbitrage(
         "Buy on Binance → Sell on Aerodrome"
     };
     
-    let gross_profit_usd = trade_size * price_diff.abs();
-    let gas_cost_usd = dec!(0.02);
-    let net_profit_usd = gross_profit_usd - gas_cost_usd;
+    let reserve_quote = match quote {
+        Some(_) => None,
+        None => reserves.and_then(|r| r.quote(trade_size)),
+    };
+    let execution_price = quote.or(reserve_quote.as_ref()).map(|q| {
+        if dex_price < cex_price { q.buy_price } else { q.sell_price }
+    });
+    
+    let gross_profit_usd = match execution_price {
+        Some(price) if dex_price < cex_price => trade_size * (cex_price - price),
+        Some(price) => trade_size * (price - cex_price),
+        None => trade_size * price_diff.abs(),
+    };
+    let cex_fee_usd = fees.cex_fee_usd(trade_size, cex_price);
+    let dex_fee_usd = match execution_price {
+        Some(_) => Decimal::ZERO,
+        None => fees.dex_fee_usd(trade_size, dex_price),
+    };
+    let net_profit_usd = gross_profit_usd - gas_cost_usd - cex_fee_usd - dex_fee_usd - fees.transfer_cost_usd;
     let roi_pct = (net_profit_usd / (trade_size * cex_price)) * dec!(100);
     
     Some(ArbitrageOpportunity {
@@ -35,15 +71,43 @@ pub fn calculate_arbitrage(
         pool: pool_name.to_string(),
         direction: direction.to_string(),
         dex_price,
+        quoted_dex_price: execution_price,
         cex_price,
+        price_basis: PriceBasis::default(),
+        fair_values: None,
         price_diff_pct,
+        cex_fill_price: None,
         size_eth: trade_size,
         gross_profit_usd,
         gas_cost_usd,
+        cex_fee_usd,
+        dex_fee_usd,
+        transfer_cost_usd: fees.transfer_cost_usd,
         net_profit_usd,
         roi_pct,
         validation_checks: ValidationResult::default(),
         volatility_assessment: None,
         execution_simulation: None,
+        stage: OpportunityStage::Detected,
     })
 }
+
+/// Price the CEX leg at the order book's average fill for the trade size
+/// instead of the reference price, adjusting gross and net profit. Leaves
+/// the opportunity unchanged when the book is too thin to fill it.
+pub fn apply_cex_depth(opportunity: &mut ArbitrageOpportunity, book: &OrderBook) {
+    // Buying on the DEX sells on the CEX, into the bids
+    let buy_on_cex = !opportunity.direction.contains("Buy on Aerodrome");
+    let Some(fill_price) = book.average_fill_price(buy_on_cex, opportunity.size_eth) else {
+        return;
+    };
+    let shortfall_usd = if buy_on_cex {
+        opportunity.size_eth * (fill_price - opportunity.cex_price)
+    } else {
+        opportunity.size_eth * (opportunity.cex_price - fill_price)
+    };
+    opportunity.cex_fill_price = Some(fill_price);
+    opportunity.gross_profit_usd -= shortfall_usd;
+    opportunity.net_profit_usd -= shortfall_usd;
+    opportunity.roi_pct = opportunity.net_profit_usd / (opportunity.size_eth * opportunity.cex_price) * dec!(100);
+}
@@ -0,0 +1,88 @@
+//! Deferral of marginal opportunities during gas spikes
+//!
+//! An opportunity whose profit is eaten by a gas spike, but which would clear
+//! `min_profit_usd` at baseline gas, is parked per pool for a short TTL. The
+//! pool is re-evaluated with fresh prices on later cycles; the deferral either
+//! is re-evaluated once gas falls under its ceiling, or expires.
+
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use crate::types::ArbitrageOpportunity;
+
+#[derive(Debug, Clone)]
+pub struct DeferredOpportunity {
+    pub opportunity: ArbitrageOpportunity,
+    /// Highest swap gas cost at which the opportunity still clears min profit
+    pub gas_ceiling_usd: Decimal,
+    /// When the pool was first deferred, kept across re-deferrals
+    pub deferred_at: Instant,
+}
+
+/// Gas ceiling for `opportunity` if it is unprofitable only because of a gas spike
+pub fn deferral_ceiling(
+    opportunity: &ArbitrageOpportunity,
+    gas_baseline_usd: Decimal,
+    min_profit_usd: Decimal,
+    spike_multiplier: Decimal,
+) -> Option<Decimal> {
+    let fees_usd = opportunity.cex_fee_usd + opportunity.dex_fee_usd + opportunity.transfer_cost_usd;
+    let gas_ceiling_usd = opportunity.gross_profit_usd - fees_usd - min_profit_usd;
+    let spiking = opportunity.gas_cost_usd >= gas_baseline_usd * spike_multiplier;
+    let blocked_by_gas = opportunity.gas_cost_usd > gas_ceiling_usd;
+    let viable_at_baseline = gas_baseline_usd <= gas_ceiling_usd;
+
+    (spiking && blocked_by_gas && viable_at_baseline).then_some(gas_ceiling_usd)
+}
+
+pub struct DeferralQueue {
+    entries: RwLock<HashMap<String, DeferredOpportunity>>,
+    ttl: Duration,
+}
+
+impl DeferralQueue {
+    pub fn new(ttl_secs: u64) -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            ttl: Duration::from_secs(ttl_secs),
+        }
+    }
+
+    /// Park `opportunity` for its pool. `previous` carries the original
+    /// deferral time when a re-evaluated pool is deferred again, so the TTL
+    /// is not extended. Returns true for a newly deferred pool.
+    pub async fn defer(
+        &self,
+        opportunity: ArbitrageOpportunity,
+        gas_ceiling_usd: Decimal,
+        previous: Option<&DeferredOpportunity>,
+    ) -> bool {
+        let deferred_at = previous.map_or_else(Instant::now, |p| p.deferred_at);
+        if deferred_at.elapsed() > self.ttl {
+            return false;
+        }
+
+        self.entries.write().await.insert(opportunity.pool.clone(), DeferredOpportunity {
+            opportunity,
+            gas_ceiling_usd,
+            deferred_at,
+        });
+        previous.is_none()
+    }
+
+    /// Remove the pool's deferral for re-evaluation against fresh prices
+    pub async fn take(&self, pool: &str) -> Option<DeferredOpportunity> {
+        self.entries.write().await.remove(pool)
+    }
+
+    /// Drop and return deferrals older than the TTL
+    pub async fn purge_expired(&self) -> Vec<DeferredOpportunity> {
+        let mut entries = self.entries.write().await;
+        let expired: Vec<String> = entries.iter()
+            .filter(|(_, d)| d.deferred_at.elapsed() > self.ttl)
+            .map(|(pool, _)| pool.clone())
+            .collect();
+        expired.iter().filter_map(|pool| entries.remove(pool)).collect()
+    }
+}
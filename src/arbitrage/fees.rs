@@ -0,0 +1,47 @@
+//! Trading fees and transfer costs of an arbitrage round trip
+
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use serde::Serialize;
+use crate::config::Config;
+
+/// Costs charged on top of the price difference, as fractions of notional
+/// except the flat transfer cost
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FeeModel {
+    /// Binance fee on the CEX leg, maker or taker
+    pub cex_fee_rate: Decimal,
+    /// Pool fee on the DEX leg, only charged when no quote already includes it
+    pub dex_fee_rate: Decimal,
+    /// Bridge/withdrawal cost to move inventory back between venues
+    pub transfer_cost_usd: Decimal,
+}
+
+impl FeeModel {
+    /// No fees, for the raw price difference alone
+    pub fn zero() -> Self {
+        Self { cex_fee_rate: Decimal::ZERO, dex_fee_rate: Decimal::ZERO, transfer_cost_usd: Decimal::ZERO }
+    }
+
+    /// Configured fees for a pool whose default fee rate is `pool_fee_rate`
+    pub fn from_config(config: &Config, pool_fee_rate: Decimal) -> Self {
+        let cex_fee_bps = if config.binance_maker_orders {
+            config.binance_maker_fee_bps
+        } else {
+            config.binance_taker_fee_bps
+        };
+        Self {
+            cex_fee_rate: cex_fee_bps / dec!(10000),
+            dex_fee_rate: config.dex_fee_bps.map(|bps| bps / dec!(10000)).unwrap_or(pool_fee_rate),
+            transfer_cost_usd: config.transfer_cost_usd,
+        }
+    }
+
+    pub fn cex_fee_usd(&self, size: Decimal, cex_price: Decimal) -> Decimal {
+        size * cex_price * self.cex_fee_rate
+    }
+
+    pub fn dex_fee_usd(&self, size: Decimal, dex_price: Decimal) -> Decimal {
+        size * dex_price * self.dex_fee_rate
+    }
+}
@@ -0,0 +1,182 @@
+//! Opportunity lifecycles across cycles
+//!
+//! A spread seen in consecutive cycles on the same pool and direction is one
+//! opportunity. The tracker follows it from detection through validation and
+//! execution to settlement, and hands back each finished lifecycle to persist:
+//! how long the spread lasted, whether it reached execution, and whether it
+//! was still profitable once executed.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use crate::execution::ExecutionResult;
+use crate::types::{
+    ArbitrageOpportunity, ExecutionStatus, LifecycleEnd, OpportunityLifecycle, OpportunityStage,
+};
+
+/// An opportunity as one cycle left it
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpportunitySighting {
+    pub opportunity_id: String,
+    pub direction: String,
+    pub timestamp: DateTime<Utc>,
+    pub net_profit_usd: Decimal,
+    pub stage: OpportunityStage,
+    /// Submitted to the execution queue this cycle
+    pub queued: bool,
+}
+
+impl OpportunitySighting {
+    pub fn new(opportunity: &ArbitrageOpportunity, queued: bool) -> Self {
+        Self {
+            opportunity_id: opportunity.id.clone(),
+            direction: opportunity.direction.clone(),
+            timestamp: opportunity.timestamp,
+            net_profit_usd: opportunity.net_profit_usd,
+            stage: opportunity.stage,
+            queued,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct LifecycleTracker {
+    /// Unfinished lifecycles by id
+    lifecycles: HashMap<String, OpportunityLifecycle>,
+    /// Lifecycle whose spread was seen last cycle, by pool and direction
+    open: HashMap<(String, String), String>,
+}
+
+impl LifecycleTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lifecycles still open or waiting for an execution result
+    pub fn len(&self) -> usize {
+        self.lifecycles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lifecycles.is_empty()
+    }
+
+    /// Merge one cycle's sightings on `pool`. Spreads not seen again are
+    /// finished as closed, unless an execution for them is still pending.
+    pub fn observe(
+        &mut self,
+        pool: &str,
+        sightings: &[OpportunitySighting],
+        now: DateTime<Utc>,
+    ) -> Vec<OpportunityLifecycle> {
+        for sighting in sightings {
+            let key = (pool.to_string(), sighting.direction.clone());
+            let id = self.open.entry(key).or_insert_with(|| sighting.opportunity_id.clone()).clone();
+            let lifecycle = self.lifecycles.entry(id).or_insert_with(|| OpportunityLifecycle {
+                id: sighting.opportunity_id.clone(),
+                pool: pool.to_string(),
+                direction: sighting.direction.clone(),
+                stage: OpportunityStage::Detected,
+                end: LifecycleEnd::Closed,
+                detected_at: sighting.timestamp,
+                validated_at: None,
+                executed_at: None,
+                settled_at: None,
+                last_seen_at: sighting.timestamp,
+                ended_at: sighting.timestamp,
+                sightings: 0,
+                persisted_ms: 0,
+                detected_net_profit_usd: sighting.net_profit_usd,
+                peak_net_profit_usd: sighting.net_profit_usd,
+                last_net_profit_usd: sighting.net_profit_usd,
+                queued_opportunity_id: None,
+                execution_id: None,
+                execution_profit_usd: None,
+                profitable_at_execution: None,
+            });
+            lifecycle.sightings += 1;
+            lifecycle.last_seen_at = sighting.timestamp;
+            lifecycle.last_net_profit_usd = sighting.net_profit_usd;
+            lifecycle.peak_net_profit_usd = lifecycle.peak_net_profit_usd.max(sighting.net_profit_usd);
+            if sighting.stage >= OpportunityStage::Validated && lifecycle.stage.can_advance_to(OpportunityStage::Validated) {
+                lifecycle.stage = OpportunityStage::Validated;
+                lifecycle.validated_at = Some(sighting.timestamp);
+            }
+            if sighting.queued && lifecycle.queued_opportunity_id.is_none() {
+                lifecycle.queued_opportunity_id = Some(sighting.opportunity_id.clone());
+            }
+        }
+
+        let gone: Vec<_> = self.open.keys()
+            .filter(|(open_pool, direction)| open_pool == pool && !sightings.iter().any(|s| &s.direction == direction))
+            .cloned()
+            .collect();
+        let mut finished = Vec::new();
+        for key in gone {
+            let Some(id) = self.open.remove(&key) else { continue };
+            let awaiting_execution = self.lifecycles.get(&id).is_some_and(|l| l.queued_opportunity_id.is_some());
+            if awaiting_execution {
+                continue;
+            }
+            if let Some(lifecycle) = self.lifecycles.remove(&id) {
+                finished.push(finish(lifecycle, LifecycleEnd::Closed, now));
+            }
+        }
+        finished
+    }
+
+    /// Finish the lifecycle whose detection `opportunity_id` was queued.
+    /// None if no tracked lifecycle queued it.
+    pub fn record_execution(
+        &mut self,
+        opportunity_id: &str,
+        result: &ExecutionResult,
+        now: DateTime<Utc>,
+    ) -> Option<OpportunityLifecycle> {
+        let id = self.lifecycles.values()
+            .find(|l| l.queued_opportunity_id.as_deref() == Some(opportunity_id))?
+            .id.clone();
+        let mut lifecycle = self.lifecycles.remove(&id)?;
+        // A spread still open after its execution starts a new lifecycle
+        self.open.retain(|_, open| *open != id);
+
+        let end = match result {
+            ExecutionResult::Completed(execution) => {
+                let profit = execution.actual_profit_usd.unwrap_or(execution.expected_profit_usd);
+                lifecycle.stage = OpportunityStage::Executed;
+                lifecycle.executed_at = Some(execution.timestamp);
+                lifecycle.execution_id = Some(execution.id.clone());
+                lifecycle.execution_profit_usd = Some(profit);
+                lifecycle.profitable_at_execution = Some(profit > Decimal::ZERO);
+                match execution.status {
+                    ExecutionStatus::Success | ExecutionStatus::Simulated => {
+                        lifecycle.stage = OpportunityStage::Settled;
+                        lifecycle.settled_at = Some(now);
+                        LifecycleEnd::Settled
+                    }
+                    ExecutionStatus::Failed => LifecycleEnd::Failed,
+                }
+            }
+            ExecutionResult::Failed(_) => LifecycleEnd::Failed,
+            ExecutionResult::Expired | ExecutionResult::NotLeader => LifecycleEnd::Expired,
+        };
+        Some(finish(lifecycle, end, now))
+    }
+
+    /// Finish every lifecycle as interrupted, oldest first, on shutdown
+    pub fn drain(&mut self, now: DateTime<Utc>) -> Vec<OpportunityLifecycle> {
+        self.open.clear();
+        let mut finished: Vec<_> = self.lifecycles.drain()
+            .map(|(_, lifecycle)| finish(lifecycle, LifecycleEnd::Interrupted, now))
+            .collect();
+        finished.sort_by_key(|l| l.detected_at);
+        finished
+    }
+}
+
+fn finish(mut lifecycle: OpportunityLifecycle, end: LifecycleEnd, now: DateTime<Utc>) -> OpportunityLifecycle {
+    lifecycle.end = end;
+    lifecycle.ended_at = now;
+    lifecycle.persisted_ms = (lifecycle.last_seen_at - lifecycle.detected_at).num_milliseconds();
+    lifecycle
+}
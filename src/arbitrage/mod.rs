@@ -1,5 +1,13 @@
 //! Arbitrage opportunity detection and calculation
 
+pub mod basis;
 pub mod calculator;
+pub mod deferral;
+pub mod fees;
+pub mod lifecycle;
 
+pub use basis::*;
 pub use calculator::*;
+pub use deferral::*;
+pub use fees::*;
+pub use lifecycle::*;
@@ -0,0 +1,159 @@
+//! Loading recorded price points from JSONL output files
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+use tracing::{debug, info};
+use crate::types::{FairValueQuotes, PriceBasis};
+
+/// A DEX/CEX price observation for one pool
+#[derive(Debug, Clone)]
+pub struct PricePoint {
+    pub timestamp: DateTime<Utc>,
+    pub pool: String,
+    pub dex_price: Decimal,
+    pub cex_price: Decimal,
+    /// Pool reserves, recorded on market making signals only
+    pub weth_reserves: Option<Decimal>,
+    pub usd_reserves: Option<Decimal>,
+    /// Basis `cex_price` was recorded on (CEX last price for older records)
+    pub price_basis: PriceBasis,
+    /// Every basis's price, on records made since bases became selectable
+    pub fair_values: Option<FairValueQuotes>,
+}
+
+impl PricePoint {
+    /// This observation with `cex_price` replaced by its price on `basis`
+    pub fn on_basis(&self, basis: PriceBasis) -> Option<PricePoint> {
+        let price = match &self.fair_values {
+            Some(fair_values) => fair_values.price(basis)?,
+            None if basis == self.price_basis => self.cex_price,
+            None => return None,
+        };
+        Some(PricePoint { cex_price: price, price_basis: basis, ..self.clone() })
+    }
+}
+
+/// Re-price points on `basis`, dropping those that did not record it
+pub fn rebase_points(points: &[PricePoint], basis: PriceBasis) -> Vec<PricePoint> {
+    points.iter().filter_map(|p| p.on_basis(basis)).collect()
+}
+
+/// Points that recorded every basis, so each basis is replayed on the same observations
+pub fn points_with_all_bases(points: &[PricePoint]) -> Vec<PricePoint> {
+    points.iter()
+        .filter(|p| PriceBasis::ALL.iter().all(|basis| p.on_basis(*basis).is_some()))
+        .cloned()
+        .collect()
+}
+
+fn decimal_field(record: &Value, field: &str) -> Option<Decimal> {
+    match &record[field] {
+        Value::String(s) => Decimal::from_str(s).ok(),
+        Value::Number(n) => Decimal::from_str(&n.to_string()).ok(),
+        _ => None,
+    }
+}
+
+/// Extract a price point from an opportunity or market making signal record
+fn parse_record(record: &Value) -> Option<PricePoint> {
+    let timestamp = record["timestamp"].as_str()
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())?
+        .with_timezone(&Utc);
+    let pool = record["pool"].as_str()?.to_string();
+
+    // Opportunities record dex/cex prices, signals record pool/fair value prices
+    let dex_price = decimal_field(record, "dex_price")
+        .or_else(|| decimal_field(record, "current_pool_price"))?;
+    let cex_price = decimal_field(record, "cex_price")
+        .or_else(|| decimal_field(record, "fair_value_price"))?;
+
+    let depth = &record["market_conditions"]["liquidity_depth"];
+    let weth_reserves = decimal_field(depth, "weth_reserves");
+    let usd_reserves = decimal_field(depth, "usd_reserves");
+
+    let price_basis = serde_json::from_value(record["price_basis"].clone()).unwrap_or_default();
+    let fair_values = serde_json::from_value(record["fair_values"].clone()).ok();
+
+    Some(PricePoint {
+        timestamp,
+        pool,
+        dex_price,
+        cex_price,
+        weth_reserves,
+        usd_reserves,
+        price_basis,
+        fair_values,
+    })
+}
+
+fn load_directory(dir: &Path, seen: &mut HashSet<String>, points: &mut Vec<PricePoint>) -> Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+            continue;
+        }
+        load_file(&path, seen, points)?;
+    }
+    Ok(())
+}
+
+fn load_file(path: &Path, seen: &mut HashSet<String>, points: &mut Vec<PricePoint>) -> Result<()> {
+    let file = fs::File::open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut skipped = 0;
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        let Ok(record) = serde_json::from_str::<Value>(&line) else {
+            skipped += 1;
+            continue;
+        };
+        if let Some(id) = record["id"].as_str() {
+            if !seen.insert(id.to_string()) {
+                continue;
+            }
+        }
+        match parse_record(&record) {
+            Some(point) => points.push(point),
+            None => skipped += 1,
+        }
+    }
+    if skipped > 0 {
+        debug!("Skipped {} unparseable records in {}", skipped, path.display());
+    }
+    Ok(())
+}
+
+/// Load and time-order every price point recorded under `output_dir`
+pub fn load_price_points(output_dir: &str) -> Result<Vec<PricePoint>> {
+    let base = Path::new(output_dir);
+    let mut seen = HashSet::new();
+    let mut points = Vec::new();
+
+    load_directory(&base.join("market_making"), &mut seen, &mut points)?;
+    load_directory(&base.join("opportunities"), &mut seen, &mut points)?;
+
+    points.sort_by_key(|p| p.timestamp);
+    info!("📂 Loaded {} recorded price points from {}", points.len(), output_dir);
+    Ok(points)
+}
+
+/// Load and time-order the price points in one recorded JSONL file
+pub fn load_price_points_from_file(path: &Path) -> Result<Vec<PricePoint>> {
+    let mut points = Vec::new();
+    load_file(path, &mut HashSet::new(), &mut points)?;
+
+    points.sort_by_key(|p| p.timestamp);
+    info!("📂 Loaded {} recorded price points from {}", points.len(), path.display());
+    Ok(points)
+}
@@ -0,0 +1,190 @@
+//! Backtest engine replaying recorded prices through arbitrage and market making logic
+
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use serde::Serialize;
+use std::collections::HashMap;
+use tracing::info;
+use crate::{
+    arbitrage::{calculate_arbitrage, FeeModel},
+    backtest::{compare_lp_vs_arbitrage, rebase_points, LpComparison, PricePoint, DEFAULT_LP_CAPITAL_USD},
+    config::Config,
+    network::FALLBACK_GAS_COST_USD,
+    pools::VOLATILE_POOL_FEE,
+    types::{PriceBasis, VolatilityEstimator},
+    volatility::MultiTimeframeVolatilityCalculator,
+};
+
+/// Tunable parameters for a backtest run
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestConfig {
+    pub trade_size_eth: Decimal,
+    pub min_profit_usd: Decimal,
+    pub min_price_diff_pct: Decimal,
+    pub gas_cost_usd: Decimal,
+    /// Configured fees, with the volatile pool fee unless DEX_FEE_BPS overrides it
+    pub fees: FeeModel,
+    pub slippage_bps: u32,
+    pub base_spread_bps: u32,
+    pub volatility_threshold: Decimal,
+    pub mm_order_size_eth: Decimal,
+    pub max_position_size_eth: Decimal,
+    pub lp_capital_usd: Decimal,
+    /// Replay on this basis instead of each record's own, skipping records without it
+    pub price_basis: Option<PriceBasis>,
+    pub volatility_estimator: VolatilityEstimator,
+    pub ewma_lambda: Decimal,
+}
+
+impl BacktestConfig {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            trade_size_eth: config.trade_size_eth,
+            min_profit_usd: config.min_profit_usd,
+            min_price_diff_pct: config.min_price_diff_pct,
+            gas_cost_usd: FALLBACK_GAS_COST_USD,
+            fees: FeeModel::from_config(config, VOLATILE_POOL_FEE),
+            slippage_bps: config.slippage_tolerance_bps,
+            base_spread_bps: config.base_spread_bps,
+            volatility_threshold: config.volatility_threshold,
+            mm_order_size_eth: config.max_position_size_eth * dec!(0.1),
+            max_position_size_eth: config.max_position_size_eth,
+            lp_capital_usd: DEFAULT_LP_CAPITAL_USD,
+            price_basis: None,
+            volatility_estimator: config.volatility_estimator,
+            ewma_lambda: config.ewma_lambda,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PoolBacktestResult {
+    pub price_points: u64,
+    pub arbitrage_opportunities: u64,
+    pub arbitrage_trades: u64,
+    pub arbitrage_pnl_usd: Decimal,
+    pub mm_fills: u64,
+    pub mm_inventory_eth: Decimal,
+    pub mm_cash_usd: Decimal,
+    pub mm_pnl_usd: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestReport {
+    pub generated_at: DateTime<Utc>,
+    pub config: BacktestConfig,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub price_points: u64,
+    pub arbitrage_trades: u64,
+    pub arbitrage_pnl_usd: Decimal,
+    pub mm_fills: u64,
+    pub mm_pnl_usd: Decimal,
+    pub total_pnl_usd: Decimal,
+    pub max_drawdown_usd: Decimal,
+    pub pools: HashMap<String, PoolBacktestResult>,
+    pub lp_vs_arbitrage: Vec<LpComparison>,
+}
+
+/// Replay price points in time order and produce a P&L report
+pub async fn run_backtest(points: &[PricePoint], config: &BacktestConfig) -> BacktestReport {
+    let rebased;
+    let points = match config.price_basis {
+        Some(basis) => {
+            rebased = rebase_points(points, basis);
+            &rebased[..]
+        }
+        None => points,
+    };
+
+    let volatility = MultiTimeframeVolatilityCalculator::new()
+        .with_estimator(config.volatility_estimator, config.ewma_lambda.to_f64().unwrap_or_default());
+    let mut pools: HashMap<String, PoolBacktestResult> = HashMap::new();
+    let mut last_cex_price = dec!(0);
+    let mut peak_pnl = dec!(0);
+    let mut max_drawdown_usd = dec!(0);
+
+    for point in points {
+        volatility.add_price_at(point.timestamp.into(), point.cex_price).await;
+        let metrics = volatility.get_volatility_metrics().await;
+        last_cex_price = point.cex_price;
+
+        let pool = pools.entry(point.pool.clone()).or_default();
+        pool.price_points += 1;
+
+        // Arbitrage leg
+        if let Some(opportunity) = calculate_arbitrage(
+            &point.pool,
+            point.dex_price,
+            point.cex_price,
+            config.trade_size_eth,
+            None,
+            None,
+            config.gas_cost_usd,
+            &config.fees,
+            config.min_price_diff_pct,
+        ) {
+            pool.arbitrage_opportunities += 1;
+            if opportunity.net_profit_usd >= config.min_profit_usd
+                && metrics.short_term_volatility < config.volatility_threshold
+            {
+                let slippage_usd = config.trade_size_eth * point.cex_price
+                    * Decimal::from(config.slippage_bps) / dec!(10000);
+                pool.arbitrage_trades += 1;
+                pool.arbitrage_pnl_usd += opportunity.net_profit_usd - slippage_usd;
+            }
+        }
+
+        // Market making leg: quotes around fair value, filled when the pool crosses them
+        let spread_bps = Decimal::from(config.base_spread_bps) * metrics.recommended_adjustments.spread_multiplier;
+        let half_spread = point.cex_price * spread_bps / dec!(20000);
+        let bid = point.cex_price - half_spread;
+        let ask = point.cex_price + half_spread;
+        let order_size = config.mm_order_size_eth * metrics.recommended_adjustments.position_size_factor;
+
+        if point.dex_price < bid && pool.mm_inventory_eth + order_size <= config.max_position_size_eth {
+            pool.mm_inventory_eth += order_size;
+            pool.mm_cash_usd -= order_size * bid;
+            pool.mm_fills += 1;
+        } else if point.dex_price > ask && pool.mm_inventory_eth - order_size >= -config.max_position_size_eth {
+            pool.mm_inventory_eth -= order_size;
+            pool.mm_cash_usd += order_size * ask;
+            pool.mm_fills += 1;
+        }
+        pool.mm_pnl_usd = pool.mm_cash_usd + pool.mm_inventory_eth * point.cex_price;
+
+        // Drawdown on combined running P&L
+        let total: Decimal = pools.values().map(|p| p.arbitrage_pnl_usd + p.mm_pnl_usd).sum();
+        peak_pnl = peak_pnl.max(total);
+        max_drawdown_usd = max_drawdown_usd.max(peak_pnl - total);
+    }
+
+    // Mark remaining inventory at the last observed fair value
+    for pool in pools.values_mut() {
+        pool.mm_pnl_usd = pool.mm_cash_usd + pool.mm_inventory_eth * last_cex_price;
+    }
+
+    let arbitrage_pnl_usd: Decimal = pools.values().map(|p| p.arbitrage_pnl_usd).sum();
+    let mm_pnl_usd: Decimal = pools.values().map(|p| p.mm_pnl_usd).sum();
+    let lp_vs_arbitrage = compare_lp_vs_arbitrage(points, &pools, config);
+
+    let report = BacktestReport {
+        generated_at: Utc::now(),
+        config: config.clone(),
+        start: points.first().map(|p| p.timestamp),
+        end: points.last().map(|p| p.timestamp),
+        price_points: points.len() as u64,
+        arbitrage_trades: pools.values().map(|p| p.arbitrage_trades).sum(),
+        arbitrage_pnl_usd,
+        mm_fills: pools.values().map(|p| p.mm_fills).sum(),
+        mm_pnl_usd,
+        total_pnl_usd: arbitrage_pnl_usd + mm_pnl_usd,
+        max_drawdown_usd,
+        pools,
+        lp_vs_arbitrage,
+    };
+
+    info!("🧪 Backtest complete: {} points, P&L ${:.2}", report.price_points, report.total_pnl_usd);
+    report
+}
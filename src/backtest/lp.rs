@@ -0,0 +1,151 @@
+//! Per-pool comparison of passive LP returns against arbitrage on recorded flows
+//!
+//! Swap volume is estimated from WETH reserve changes between consecutive
+//! recorded signals, so it is a lower bound: swaps that net out between two
+//! polls are invisible. Divergence loss uses the constant-product formula for
+//! every pool, which overstates it for stable (sAMM) pools.
+
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use serde::Serialize;
+use std::collections::HashMap;
+use crate::{
+    backtest::{BacktestConfig, PoolBacktestResult, PricePoint},
+    pools::{STABLE_POOL_FEE, VOLATILE_POOL_FEE},
+};
+
+/// Capital assumed deposited as liquidity in each pool
+pub const DEFAULT_LP_CAPITAL_USD: Decimal = dec!(10000);
+
+/// Return difference (percentage points) below which neither side is preferred
+pub const VENUE_BIAS_BAND_PCT: Decimal = dec!(0.05);
+
+/// Whether a venue's recorded flows favoured providing or taking liquidity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum VenueBias {
+    Provide,
+    Take,
+    Neutral,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LpComparison {
+    pub pool: String,
+    /// Observations with recorded reserves
+    pub reserve_samples: u64,
+    pub fee_rate: Decimal,
+    pub estimated_volume_usd: Decimal,
+    pub avg_tvl_usd: Decimal,
+    pub lp_capital_usd: Decimal,
+    pub lp_fees_usd: Decimal,
+    /// Value lost against holding the deposit, from the start/end price move
+    pub divergence_loss_usd: Decimal,
+    pub lp_pnl_usd: Decimal,
+    pub lp_return_pct: Decimal,
+    /// Trade-size inventory held on both venues
+    pub arbitrage_capital_usd: Decimal,
+    pub arbitrage_pnl_usd: Decimal,
+    pub arbitrage_return_pct: Decimal,
+    pub bias: VenueBias,
+}
+
+fn pool_fee_rate(pool: &str) -> Decimal {
+    if pool.starts_with("sAMM") { STABLE_POOL_FEE } else { VOLATILE_POOL_FEE }
+}
+
+/// Constant-product LP value relative to holding, minus one (always ≤ 0)
+fn divergence_loss_ratio(start_price: Decimal, end_price: Decimal) -> Decimal {
+    let (Some(start), Some(end)) = (start_price.to_f64(), end_price.to_f64()) else {
+        return dec!(0);
+    };
+    if start <= 0.0 || end <= 0.0 {
+        return dec!(0);
+    }
+    let r = end / start;
+    Decimal::from_f64(2.0 * r.sqrt() / (1.0 + r) - 1.0).unwrap_or_default()
+}
+
+fn return_pct(pnl: Decimal, capital: Decimal) -> Decimal {
+    if capital > dec!(0) { pnl / capital * dec!(100) } else { dec!(0) }
+}
+
+/// Compare what each pool's recorded flows paid an LP against the backtest's arbitrage P&L
+pub fn compare_lp_vs_arbitrage(
+    points: &[PricePoint],
+    results: &HashMap<String, PoolBacktestResult>,
+    config: &BacktestConfig,
+) -> Vec<LpComparison> {
+    let mut by_pool: HashMap<&str, Vec<&PricePoint>> = HashMap::new();
+    for point in points {
+        by_pool.entry(point.pool.as_str()).or_default().push(point);
+    }
+
+    let mut comparisons: Vec<LpComparison> = by_pool.into_iter()
+        .map(|(pool, points)| {
+            let fee_rate = pool_fee_rate(pool);
+            let capital = config.lp_capital_usd;
+            let sampled: Vec<(&PricePoint, Decimal, Decimal)> = points.iter()
+                .filter_map(|p| Some((*p, p.weth_reserves?, p.usd_reserves?)))
+                .collect();
+
+            let mut volume_usd = dec!(0);
+            let mut lp_fees_usd = dec!(0);
+            let mut tvl_sum = dec!(0);
+            for (i, (point, weth, usd)) in sampled.iter().enumerate() {
+                let tvl = *weth * point.dex_price + *usd;
+                tvl_sum += tvl;
+                if i == 0 || tvl <= dec!(0) {
+                    continue;
+                }
+                let swapped_usd = (*weth - sampled[i - 1].1).abs() * point.dex_price;
+                volume_usd += swapped_usd;
+                lp_fees_usd += swapped_usd * fee_rate * (capital / tvl).min(dec!(1));
+            }
+
+            let divergence_loss_usd = match (sampled.first(), sampled.last()) {
+                (Some((first, ..)), Some((last, ..))) => {
+                    -capital * divergence_loss_ratio(first.dex_price, last.dex_price)
+                }
+                _ => dec!(0),
+            };
+            let lp_pnl_usd = lp_fees_usd - divergence_loss_usd;
+
+            let avg_cex_price = points.iter().map(|p| p.cex_price).sum::<Decimal>()
+                / Decimal::from(points.len());
+            let arbitrage_capital_usd = config.trade_size_eth * avg_cex_price * dec!(2);
+            let arbitrage_pnl_usd = results.get(pool).map(|r| r.arbitrage_pnl_usd).unwrap_or_default();
+
+            let lp_return_pct = return_pct(lp_pnl_usd, capital);
+            let arbitrage_return_pct = return_pct(arbitrage_pnl_usd, arbitrage_capital_usd);
+            let bias = if sampled.len() < 2
+                || (lp_return_pct - arbitrage_return_pct).abs() < VENUE_BIAS_BAND_PCT
+            {
+                VenueBias::Neutral
+            } else if lp_return_pct > arbitrage_return_pct {
+                VenueBias::Provide
+            } else {
+                VenueBias::Take
+            };
+
+            LpComparison {
+                pool: pool.to_string(),
+                reserve_samples: sampled.len() as u64,
+                fee_rate,
+                estimated_volume_usd: volume_usd,
+                avg_tvl_usd: if sampled.is_empty() { dec!(0) } else { tvl_sum / Decimal::from(sampled.len()) },
+                lp_capital_usd: capital,
+                lp_fees_usd,
+                divergence_loss_usd,
+                lp_pnl_usd,
+                lp_return_pct,
+                arbitrage_capital_usd,
+                arbitrage_pnl_usd,
+                arbitrage_return_pct,
+                bias,
+            }
+        })
+        .collect();
+
+    comparisons.sort_by(|a, b| a.pool.cmp(&b.pool));
+    comparisons
+}
@@ -0,0 +1,11 @@
+//! Backtesting over recorded JSONL data
+
+pub mod data;
+pub mod engine;
+pub mod lp;
+pub mod race;
+
+pub use data::*;
+pub use engine::*;
+pub use lp::*;
+pub use race::*;
@@ -0,0 +1,258 @@
+//! Latency race research mode
+//!
+//! Two strategy configurations replay the same recorded feed. Each decides
+//! on the prices it sees, but fills at the first recorded price at least its
+//! decision latency later, and the faster strategy takes a contested
+//! opportunity. A latency sweep reports how much edge each millisecond costs.
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use serde::Serialize;
+use std::collections::HashMap;
+use tracing::info;
+use crate::{
+    arbitrage::calculate_arbitrage,
+    backtest::{BacktestConfig, PricePoint},
+};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RaceStrategy {
+    pub name: String,
+    pub config: BacktestConfig,
+    pub latency_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RaceResult {
+    pub name: String,
+    pub latency_ms: u64,
+    pub signals: u64,
+    pub wins: u64,
+    pub lost_races: u64,
+    pub missed_fills: u64,
+    pub pnl_usd: Decimal,
+    /// P&L had every signal filled at decision-time prices
+    pub decision_pnl_usd: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencySweepPoint {
+    pub latency_ms: u64,
+    pub trades: u64,
+    pub pnl_usd: Decimal,
+    pub edge_lost_usd: Decimal,
+    pub edge_cost_per_ms_usd: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyRaceReport {
+    pub generated_at: DateTime<Utc>,
+    pub price_points: u64,
+    pub contested_opportunities: u64,
+    pub strategies: Vec<RaceResult>,
+    pub sweeps: HashMap<String, Vec<LatencySweepPoint>>,
+}
+
+/// A strategy's intent to trade one recorded opportunity
+struct Signal {
+    buy_on_dex: bool,
+    decision_pnl_usd: Decimal,
+    fill_time: Option<DateTime<Utc>>,
+    fill_pnl_usd: Option<Decimal>,
+}
+
+/// Index of point timestamps per pool, in time order
+fn pool_timelines(points: &[PricePoint]) -> HashMap<&str, Vec<usize>> {
+    let mut timelines: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, point) in points.iter().enumerate() {
+        timelines.entry(point.pool.as_str()).or_default().push(i);
+    }
+    timelines
+}
+
+/// Realized P&L of trading at `point` in a direction chosen earlier
+fn fill_pnl(point: &PricePoint, buy_on_dex: bool, config: &BacktestConfig) -> Decimal {
+    let spread = if buy_on_dex {
+        point.cex_price - point.dex_price
+    } else {
+        point.dex_price - point.cex_price
+    };
+    let slippage_usd = config.trade_size_eth * point.cex_price
+        * Decimal::from(config.slippage_bps) / dec!(10000);
+    let fees_usd = config.fees.cex_fee_usd(config.trade_size_eth, point.cex_price)
+        + config.fees.dex_fee_usd(config.trade_size_eth, point.dex_price)
+        + config.fees.transfer_cost_usd;
+    config.trade_size_eth * spread - config.gas_cost_usd - fees_usd - slippage_usd
+}
+
+fn evaluate(
+    points: &[PricePoint],
+    timeline: &[usize],
+    position: usize,
+    strategy: &RaceStrategy,
+) -> Option<Signal> {
+    let point = &points[timeline[position]];
+    let config = &strategy.config;
+    let opportunity = calculate_arbitrage(
+        &point.pool,
+        point.dex_price,
+        point.cex_price,
+        config.trade_size_eth,
+        None,
+        None,
+        config.gas_cost_usd,
+        &config.fees,
+        config.min_price_diff_pct,
+    )?;
+    if opportunity.net_profit_usd < config.min_profit_usd {
+        return None;
+    }
+
+    let buy_on_dex = point.dex_price < point.cex_price;
+    let ready_at = point.timestamp + Duration::milliseconds(strategy.latency_ms as i64);
+    let fill = timeline[position..].iter()
+        .map(|&i| &points[i])
+        .find(|p| p.timestamp >= ready_at);
+
+    Some(Signal {
+        buy_on_dex,
+        decision_pnl_usd: fill_pnl(point, buy_on_dex, config),
+        fill_time: fill.map(|p| p.timestamp),
+        fill_pnl_usd: fill.map(|p| fill_pnl(p, buy_on_dex, config)),
+    })
+}
+
+fn record_fill(result: &mut RaceResult, signal: &Signal, share: Decimal) {
+    match signal.fill_pnl_usd {
+        Some(pnl) => {
+            result.wins += 1;
+            result.pnl_usd += pnl * share;
+        }
+        None => result.missed_fills += 1,
+    }
+}
+
+/// Race two strategies on the same feed. The earlier fill takes a contested
+/// opportunity; simultaneous fills split it.
+pub fn run_latency_race(points: &[PricePoint], a: &RaceStrategy, b: &RaceStrategy) -> (RaceResult, RaceResult, u64) {
+    let mut result_a = RaceResult { name: a.name.clone(), latency_ms: a.latency_ms, ..Default::default() };
+    let mut result_b = RaceResult { name: b.name.clone(), latency_ms: b.latency_ms, ..Default::default() };
+    let mut contested = 0;
+
+    for timeline in pool_timelines(points).values() {
+        for position in 0..timeline.len() {
+            let signal_a = evaluate(points, timeline, position, a);
+            let signal_b = evaluate(points, timeline, position, b);
+
+            for (result, signal) in [(&mut result_a, &signal_a), (&mut result_b, &signal_b)] {
+                if let Some(signal) = signal {
+                    result.signals += 1;
+                    result.decision_pnl_usd += signal.decision_pnl_usd;
+                }
+            }
+
+            match (&signal_a, &signal_b) {
+                (Some(sa), Some(sb)) if sa.buy_on_dex == sb.buy_on_dex => {
+                    contested += 1;
+                    match (sa.fill_time, sb.fill_time) {
+                        (Some(ta), Some(tb)) if ta == tb => {
+                            record_fill(&mut result_a, sa, dec!(0.5));
+                            record_fill(&mut result_b, sb, dec!(0.5));
+                        }
+                        (Some(ta), Some(tb)) if ta < tb => {
+                            record_fill(&mut result_a, sa, dec!(1));
+                            result_b.lost_races += 1;
+                        }
+                        (Some(_), Some(_)) | (None, Some(_)) => {
+                            record_fill(&mut result_b, sb, dec!(1));
+                            result_a.lost_races += 1;
+                        }
+                        (Some(_), None) => {
+                            record_fill(&mut result_a, sa, dec!(1));
+                            result_b.missed_fills += 1;
+                        }
+                        (None, None) => {
+                            result_a.missed_fills += 1;
+                            result_b.missed_fills += 1;
+                        }
+                    }
+                }
+                _ => {
+                    if let Some(sa) = &signal_a {
+                        record_fill(&mut result_a, sa, dec!(1));
+                    }
+                    if let Some(sb) = &signal_b {
+                        record_fill(&mut result_b, sb, dec!(1));
+                    }
+                }
+            }
+        }
+    }
+
+    (result_a, result_b, contested)
+}
+
+/// Uncontested P&L of one strategy across decision latencies
+pub fn latency_sweep(points: &[PricePoint], strategy: &RaceStrategy, latencies_ms: &[u64]) -> Vec<LatencySweepPoint> {
+    let timelines = pool_timelines(points);
+
+    latencies_ms.iter().map(|&latency_ms| {
+        let probe = RaceStrategy { latency_ms, ..strategy.clone() };
+        let mut trades = 0;
+        let mut pnl_usd = dec!(0);
+        let mut decision_pnl_usd = dec!(0);
+        for timeline in timelines.values() {
+            for position in 0..timeline.len() {
+                if let Some(signal) = evaluate(points, timeline, position, &probe) {
+                    decision_pnl_usd += signal.decision_pnl_usd;
+                    if let Some(pnl) = signal.fill_pnl_usd {
+                        trades += 1;
+                        pnl_usd += pnl;
+                    }
+                }
+            }
+        }
+
+        // Edge lost is measured against filling at decision-time prices
+        let edge_lost_usd = decision_pnl_usd - pnl_usd;
+        LatencySweepPoint {
+            latency_ms,
+            trades,
+            pnl_usd,
+            edge_lost_usd,
+            edge_cost_per_ms_usd: if latency_ms > 0 {
+                edge_lost_usd / Decimal::from(latency_ms)
+            } else {
+                dec!(0)
+            },
+        }
+    }).collect()
+}
+
+pub fn run_latency_race_report(
+    points: &[PricePoint],
+    a: &RaceStrategy,
+    b: &RaceStrategy,
+    sweep_latencies_ms: &[u64],
+) -> LatencyRaceReport {
+    let (result_a, result_b, contested_opportunities) = run_latency_race(points, a, b);
+    let sweeps = [a, b].iter()
+        .map(|s| (s.name.clone(), latency_sweep(points, s, sweep_latencies_ms)))
+        .collect();
+
+    info!(
+        "🏁 Latency race: {} ({}ms) ${:.2} vs {} ({}ms) ${:.2}, {} contested",
+        result_a.name, result_a.latency_ms, result_a.pnl_usd,
+        result_b.name, result_b.latency_ms, result_b.pnl_usd,
+        contested_opportunities
+    );
+
+    LatencyRaceReport {
+        generated_at: Utc::now(),
+        price_points: points.len() as u64,
+        contested_opportunities,
+        strategies: vec![result_a, result_b],
+        sweeps,
+    }
+}
@@ -0,0 +1,1079 @@
+//! One monitoring cycle: fetch the CEX reference, then process every pool
+//! concurrently for arbitrage and market-making signals
+
+use crate::*;
+use anyhow::Result;
+use std::sync::Arc;
+use tokio::task::{JoinError, JoinSet};
+use std::time::{Duration, Instant};
+use std::collections::HashMap;
+use tracing::{info, warn, error, debug};
+use crate::errors::RecoveryAction;
+use super::session::*;
+
+/// Run a single monitoring cycle
+pub(crate) async fn run_monitoring_cycle(
+    provider: &Arc<ConcreteProvider>,
+    trade_execution_engine: &Arc<execution::TradeExecutionEngine>,
+    market_making_engine: &Arc<market_making::MarketMakingEngine>,
+    execution_queue: &execution::ExecutionQueue,
+    price_source: &dyn network::PriceSource,
+    gas_oracle: &network::GasOracle,
+    price_oracle: Option<&network::PriceOracle>,
+    stable_oracle: &network::StablePriceOracle,
+    deferral_queue: &Arc<arbitrage::DeferralQueue>,
+    pool_health: &Arc<pools::PoolHealthTracker>,
+    dex_twaps: &Arc<pools::TwapTracker>,
+    basis_tracker: &Arc<arbitrage::BasisTracker>,
+    portfolio: Option<&portfolio::PortfolioTracker>,
+    risk_hooks: &Arc<risk::RiskHooks>,
+    strategies: &Arc<strategy::Strategies>,
+    valid_pools: &[PoolInfo],
+    config: &Config,
+    breakers: &Arc<errors::CircuitBreakerRegistry>,
+    error_recovery: &Arc<errors::ErrorRecovery>,
+    state: &mut MonitoringState,
+    start_time: Instant,
+) -> Result<()> {
+    // Without the RPC there is nothing to monitor
+    if !breakers.rpc.can_proceed().await {
+        warn!("⚡ RPC circuit breaker is OPEN, waiting for cooldown...");
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        return Ok(());
+    }
+    
+    let mut pool_successes = 0;
+    let mut pool_failures = 0;
+    
+    // Pools whose breaker lets them through this cycle
+    let mut active_pools = Vec::with_capacity(valid_pools.len());
+    for pool in valid_pools {
+        if breakers.pool(&pool.name).await.can_proceed().await {
+            active_pools.push(pool);
+        } else {
+            debug!("⚡ Skipping {}: pool circuit breaker is OPEN", pool.name);
+            pool_failures += 1;
+        }
+    }
+    
+    // Read the CEX and every pool's DEX price together, so they are compared as one snapshot.
+    // While the CEX breaker is open, pools are still monitored against the last known price
+    // but nothing executes.
+    state.cex_degraded = !breakers.cex.can_proceed().await;
+    let mut snapshot = fetch_price_snapshot(
+        provider,
+        price_source,
+        &active_pools,
+        state.cex_degraded,
+        config.max_concurrent_pools,
+    ).await;
+    let cex_price = match snapshot.cex_reference.take() {
+        None => match state.last_known_cex_price {
+            Some(price) => {
+                debug!("⚡ CEX circuit breaker is OPEN, monitoring on last known price ${:.2}", price);
+                price
+            }
+            None => {
+                warn!("⚡ CEX circuit breaker is OPEN with no known price, waiting for cooldown...");
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                return Ok(());
+            }
+        },
+        Some(Ok(price)) => {
+            state.cex_last_update = snapshot.cex_sampled_at.get(network::ETH_USD_CEX_SYMBOL).copied();
+            state.last_known_cex_price = Some(price);
+            state.consecutive_cex_failures = 0;
+            if config.paper_trading {
+                state.paper_portfolio
+                    .get_or_insert_with(|| portfolio::PaperPortfolio::new(config.paper_starting_weth, config.paper_starting_usdc, price))
+                    .mark(price, snapshot.taken_at);
+            }
+            breakers.cex.record_success().await;
+            price
+        }
+        Some(Err(e)) => {
+            state.consecutive_cex_failures += 1;
+            *state.error_counts.entry("cex_price".to_string()).or_insert(0) += 1;
+        
+            // Use error recovery strategy
+            let recovery_action = error_recovery.handle_error(&e, "CEX price fetch").await;
+            return handle_cex_error_recovery(recovery_action, state, &breakers.cex, e).await;
+        }
+    };
+    
+    // Get volatility metrics
+    let volatility_metrics = market_making_engine.get_volatility_metrics().await;
+    
+    // Refresh gas fees and price one swap in USD
+    if let Err(e) = gas_oracle.refresh(provider.as_ref()).await {
+        warn!("Gas oracle refresh failed, using last estimate: {}", e);
+        *state.error_counts.entry("gas_oracle".to_string()).or_insert(0) += 1;
+    }
+    let gas_cost_usd = gas_oracle.estimate_swap_cost_usd(cex_price).await;
+    let gas_baseline_usd = gas_oracle.baseline_swap_cost_usd(cex_price).await
+        .unwrap_or(gas_cost_usd);
+    
+    // Oracle bound on WETH/USD prices; after a failed read the last answer applies until it is stale
+    let oracle_price = match price_oracle {
+        Some(oracle) => {
+            if let Err(e) = oracle.refresh(provider.as_ref()).await {
+                warn!("Oracle refresh failed: {}", e);
+                *state.error_counts.entry("oracle".to_string()).or_insert(0) += 1;
+            }
+            oracle.fresh_price().await
+        }
+        None => None,
+    };
+    
+    // USD prices of non-stable quote assets; pools quoted in an asset without a fresh price are skipped
+    let stable_failures = stable_oracle.refresh(provider.as_ref()).await;
+    if stable_failures > 0 {
+        *state.error_counts.entry("stable_oracle".to_string()).or_insert(0) += stable_failures as u32;
+    }
+    let stable_prices = stable_oracle.prices().await;
+    
+    // Drop deferred opportunities whose gas never normalized within the TTL
+    for expired in deferral_queue.purge_expired().await {
+        info!("⌛ Deferred opportunity on {} expired (gas ceiling ${:.4}, now ${:.4})",
+            expired.opportunity.pool, expired.gas_ceiling_usd, gas_cost_usd);
+        state.expired_deferrals += 1;
+    }
+    
+    // Feed real wallet/CEX inventory into market making
+    if let Some(portfolio) = portfolio {
+        match portfolio.refresh_if_stale(provider.as_ref()).await {
+            Ok(snapshot) => {
+                // Without a wallet (testnet CEX only) market making keeps its simulated inventory
+                if let Some(inventory) = snapshot.inventory() {
+                    market_making_engine.set_inventory(inventory.weth, inventory.usd).await;
+                    state.initial_inventory.get_or_insert(portfolio::InventoryMark {
+                        weth: inventory.weth,
+                        usd: inventory.usd,
+                        price: cex_price,
+                    });
+                }
+            }
+            Err(e) => {
+                warn!("Portfolio refresh failed, keeping last inventory: {}", e);
+                *state.error_counts.entry("portfolio".to_string()).or_insert(0) += 1;
+            }
+        }
+    }
+    
+    // The last known reference price was sampled at its last successful read
+    if state.cex_degraded {
+        if let Some(sampled_at) = state.cex_last_update {
+            snapshot.cex_sampled_at.insert(network::ETH_USD_CEX_SYMBOL.to_string(), sampled_at);
+        }
+    }
+    
+    // Prices for pairs whose CEX symbol is not the ETH/USD reference
+    let mut pair_cex_prices: HashMap<String, rust_decimal::Decimal> = HashMap::new();
+    for (symbol, result) in std::mem::take(&mut snapshot.pair_cex_prices) {
+        match result {
+            Ok(price) => {
+                pair_cex_prices.insert(symbol, price);
+            }
+            Err(e) => {
+                warn!("Failed to fetch {} price: {}", symbol, e);
+                *state.error_counts.entry(format!("cex_price_{}", symbol)).or_insert(0) += 1;
+            }
+        }
+    }
+    
+    if config.record_prices {
+        let samples: Vec<storage::PriceSample> = std::iter::once((network::ETH_USD_CEX_SYMBOL, cex_price))
+            .chain(pair_cex_prices.iter().map(|(symbol, price)| (symbol.as_str(), *price)))
+            .map(|(symbol, price)| storage::PriceSample {
+                timestamp: snapshot.taken_at,
+                ..storage::PriceSample::new(storage::PriceSource::Cex, symbol, price)
+            })
+            .collect();
+        if let Err(e) = storage::save_price_samples(&samples) {
+            warn!("Failed to save CEX prices: {}", e);
+        }
+    }
+    
+    // Best bid/ask for the mid and microprice bases; pools fall back to last prices without them
+    let mut cex_books: HashMap<String, BookTicker> = HashMap::new();
+    let book_symbols = std::iter::once(network::ETH_USD_CEX_SYMBOL)
+        .chain(active_pools.iter().map(|pool| pool.pair.cex_symbol.as_str()));
+    for symbol in book_symbols {
+        if cex_books.contains_key(symbol) {
+            continue;
+        }
+        match price_source.book_ticker(symbol).await {
+            Ok(book) => {
+                cex_books.insert(symbol.to_string(), book);
+            }
+            Err(e) => {
+                debug!("Failed to fetch {} book ticker: {}", symbol, e);
+                *state.error_counts.entry(format!("cex_book_{}", symbol)).or_insert(0) += 1;
+            }
+        }
+    }
+    
+    // Order book depth to price the CEX leg at its realizable fill
+    let mut cex_depths: HashMap<String, OrderBook> = HashMap::new();
+    if config.cex_depth_levels > 0 {
+        for pool in &active_pools {
+            let symbol = pool.pair.cex_symbol.as_str();
+            if cex_depths.contains_key(symbol) {
+                continue;
+            }
+            match price_source.order_book(symbol, config.cex_depth_levels).await {
+                Ok(book) => {
+                    cex_depths.insert(symbol.to_string(), book);
+                }
+                Err(e) => {
+                    debug!("Failed to fetch {} order book: {}", symbol, e);
+                    *state.error_counts.entry(format!("cex_depth_{}", symbol)).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+    
+    // Process healthiest pools first
+    let mut prioritized_pools = Vec::with_capacity(active_pools.len());
+    for pool in active_pools {
+        let score = pool_health.score(&pool.name).await
+            .map(|h| h.overall_score)
+            .unwrap_or_default();
+        prioritized_pools.push((score, pool));
+    }
+    prioritized_pools.sort_by(|a, b| b.0.cmp(&a.0));
+    
+    // Process all pools, up to MAX_CONCURRENT_POOLS at a time
+    let ctx = Arc::new(PoolCycleContext {
+        provider: provider.clone(),
+        trade_execution_engine: trade_execution_engine.clone(),
+        market_making_engine: market_making_engine.clone(),
+        execution_queue: execution_queue.clone(),
+        deferral_queue: deferral_queue.clone(),
+        pool_health: pool_health.clone(),
+        dex_twaps: dex_twaps.clone(),
+        basis_tracker: basis_tracker.clone(),
+        risk_hooks: risk_hooks.clone(),
+        strategies: strategies.clone(),
+        eth_usd: cex_price,
+        gas_cost_usd,
+        gas_baseline_usd,
+        oracle_price,
+        stable_prices,
+        volatility_metrics,
+        config: config.clone(),
+        is_leader: state.is_leader,
+        cex_degraded: state.cex_degraded,
+        snapshot_at: snapshot.taken_at,
+    });
+    let mut running = JoinSet::new();
+    let mut pool_tasks = HashMap::new();
+    for (_, pool) in prioritized_pools {
+        let Some(cex_quotes) = pool_cex_quotes(pool, cex_price, &pair_cex_prices, &cex_books) else {
+            debug!("Skipping {}: no {} price this cycle", pool.name, pool.pair.cex_symbol);
+            pool_failures += 1;
+            continue;
+        };
+        let Some(dex_sample) = snapshot.dex_prices.remove(&pool.name) else {
+            warn!("Skipping {}: its DEX price read did not finish", pool.name);
+            pool_failures += 1;
+            continue;
+        };
+        while running.len() >= config.max_concurrent_pools {
+            let Some(joined) = running.join_next_with_id().await else { break };
+            if finish_pool_task(joined, &mut pool_tasks, breakers, state).await {
+                pool_successes += 1;
+            } else {
+                pool_failures += 1;
+            }
+        }
+        let prices = PoolPrices {
+            skew: snapshot.cex_sampled_at.get(&pool.pair.cex_symbol).map(|cex_sampled_at| {
+                dex_sample.sampled_at.max(*cex_sampled_at) - dex_sample.sampled_at.min(*cex_sampled_at)
+            }),
+            dex_quote_price: dex_sample.price,
+            cex_quotes,
+            cex_depth: pool_cex_depth(pool, cex_price, &cex_depths),
+        };
+        let ctx = ctx.clone();
+        let task_pool = pool.clone();
+        // A panic fails this pool only; its JoinError is traced back by task id
+        let task = running.spawn(async move {
+            let mut tally = PoolTally::default();
+            let result = process_single_pool(&ctx, &task_pool, prices, &mut tally).await;
+            (tally, result)
+        });
+        pool_tasks.insert(task.id(), pool.name.clone());
+    }
+    while let Some(joined) = running.join_next_with_id().await {
+        if finish_pool_task(joined, &mut pool_tasks, breakers, state).await {
+            pool_successes += 1;
+        } else {
+            pool_failures += 1;
+        }
+    }
+    
+    state.dex_last_update = Some(Instant::now());
+    if pool_successes > 0 {
+        breakers.rpc.record_success().await;
+    }
+    
+    if pool_failures > 0 {
+        debug!("Pool processing: {} successful, {} failed", pool_successes, pool_failures);
+    }
+    if config.enable_market_making {
+        state.quote_book = Some(market_making_engine.quote_book_report(cex_price).await);
+        state.stress_tests = market_making_engine.latest_stress_tests().await;
+    }
+    if pools::POOL_STATE_CACHE.is_enabled() {
+        let cache = pools::POOL_STATE_CACHE.stats();
+        debug!("Reserve cache: {} hits, {} misses", cache.hits, cache.misses);
+    }
+    
+    // Print periodic statistics
+    if should_print_statistics(state, config, start_time, Instant::now()) {
+        utils::print_session_stats(
+            start_time,
+            state.total_opportunities,
+            state.profitable_opportunities,
+            state.total_potential_profit,
+            state.total_market_making_signals,
+            state.total_executions,
+            state.successful_executions,
+            &state.error_counts,
+            &breakers.state().await,
+        ).await;
+        mark_statistics_printed(state, Instant::now());
+    }
+    
+    // Check for too many errors
+    let total_errors: u32 = state.error_counts.values().sum();
+    if total_errors > 1000 {
+        error!("Too many total errors ({}), consider restarting", total_errors);
+        warn!("Error breakdown: {:?}", state.error_counts);
+    }
+    
+    Ok(())
+}
+
+/// Merge a finished pool task into the session and update the breakers;
+/// true if the pool was processed without error. `pool_tasks` names the pool
+/// each spawned task id belongs to.
+pub async fn finish_pool_task(
+    joined: Result<(tokio::task::Id, (PoolTally, Result<()>)), JoinError>,
+    pool_tasks: &mut HashMap<tokio::task::Id, String>,
+    breakers: &errors::CircuitBreakerRegistry,
+    state: &mut MonitoringState,
+) -> bool {
+    let (id, (mut tally, result)) = match joined {
+        Ok(finished) => finished,
+        Err(e) => {
+            let pool = pool_tasks.remove(&e.id()).unwrap_or_else(|| "unknown".to_string());
+            if e.is_panic() {
+                // What the pool counted before the panic is dropped with it
+                record_panic(state, &format!("pool {}", pool), e.into_panic().as_ref());
+            } else {
+                error!("Pool task for {} failed: {}", pool, e);
+                *state.error_counts.entry("pool_task".to_string()).or_insert(0) += 1;
+            }
+            breakers.pool(&pool).await.record_error().await;
+            return false;
+        }
+    };
+    let pool = pool_tasks.remove(&id).unwrap_or_else(|| "unknown".to_string());
+    if let Some(sightings) = tally.sightings.take() {
+        let closed = state.lifecycles.observe(&pool, &sightings, chrono::Utc::now());
+        if !closed.is_empty() {
+            record_lifecycles(state, &closed);
+        }
+    }
+    state.absorb(&pool, tally);
+    
+    let pool_breaker = breakers.pool(&pool).await;
+    let Err(e) = result else {
+        pool_breaker.record_success().await;
+        return true;
+    };
+    *state.error_counts.entry(format!("pool_{}", pool)).or_insert(0) += 1;
+    
+    match e.downcast_ref::<BotError>() {
+        Some(BotError::InsufficientLiquidity { .. }) => {
+            debug!("Pool {} has insufficient liquidity", pool);
+        }
+        Some(BotError::Network { .. }) => {
+            warn!("Network error for pool {}: {}", pool, e);
+            if breakers.rpc.record_error().await {
+                error!("RPC circuit breaker activated due to network errors");
+            }
+        }
+        Some(BotError::Contract { .. }) => {
+            warn!("Contract error for pool {}: {}", pool, e);
+            if pool_breaker.record_error().await {
+                error!("Circuit breaker for {} activated due to contract errors", pool);
+            }
+        }
+        _ => {
+            error!("Error processing pool {}: {}", pool, e);
+            pool_breaker.record_error().await;
+        }
+    }
+    false
+}
+
+/// USD prices of a pool's base token on the CEX, from its pair symbol's last price and book
+fn pool_cex_quotes(
+    pool: &PoolInfo,
+    eth_usd: rust_decimal::Decimal,
+    pair_cex_prices: &HashMap<String, rust_decimal::Decimal>,
+    cex_books: &HashMap<String, BookTicker>,
+) -> Option<CexQuotes> {
+    let symbol_price = if pool.pair.cex_symbol == network::ETH_USD_CEX_SYMBOL {
+        eth_usd
+    } else {
+        *pair_cex_prices.get(&pool.pair.cex_symbol)?
+    };
+    let quote_usd = pool.pair.cex_quote_usd(eth_usd)?;
+    let book = cex_books.get(&pool.pair.cex_symbol).map(|book| BookTicker {
+        bid_price: book.bid_price * quote_usd,
+        bid_qty: book.bid_qty,
+        ask_price: book.ask_price * quote_usd,
+        ask_qty: book.ask_qty,
+    });
+    Some(CexQuotes { last: symbol_price * quote_usd, book })
+}
+
+/// A pool's CEX order book with prices in USD
+fn pool_cex_depth(
+    pool: &PoolInfo,
+    eth_usd: rust_decimal::Decimal,
+    cex_depths: &HashMap<String, OrderBook>,
+) -> Option<OrderBook> {
+    let quote_usd = pool.pair.cex_quote_usd(eth_usd)?;
+    cex_depths.get(&pool.pair.cex_symbol).map(|book| book.scaled(quote_usd))
+}
+
+/// Handle CEX price error recovery
+async fn handle_cex_error_recovery(
+    recovery_action: errors::RecoveryAction,
+    state: &mut MonitoringState,
+    cex_breaker: &errors::CircuitBreaker,
+    error: BotError,
+) -> Result<()> {
+
+    
+    match recovery_action {
+        RecoveryAction::Retry { delay } => {
+            warn!("CEX error (attempt {}): {}. Retrying in {:?}",
+                state.consecutive_cex_failures, error, delay);
+            tokio::time::sleep(delay).await;
+        }
+        RecoveryAction::Skip { .. } => {
+            if let Some(fallback_price) = state.last_known_cex_price {
+                if state.consecutive_cex_failures <= 3 {
+                    warn!("Using last known CEX price: ${:.2} (age: {:?})",
+                        fallback_price,
+                        state.cex_last_update.map(|t| t.elapsed()).unwrap_or(Duration::MAX)
+                    );
+                    return Ok(());
+                }
+            }
+            error!("Too many CEX failures, activating CEX circuit breaker");
+            cex_breaker.record_error().await;
+        }
+        RecoveryAction::Shutdown { reason } => {
+            return Err(anyhow::anyhow!("Critical error - shutting down: {}", reason));
+        }
+        _ => {
+            error!("Unhandled CEX error: {}", error);
+        }
+    }
+    
+    Ok(())
+}
+
+/// Shared inputs of a cycle's pool tasks
+struct PoolCycleContext {
+    provider: Arc<ConcreteProvider>,
+    trade_execution_engine: Arc<execution::TradeExecutionEngine>,
+    market_making_engine: Arc<market_making::MarketMakingEngine>,
+    execution_queue: execution::ExecutionQueue,
+    deferral_queue: Arc<arbitrage::DeferralQueue>,
+    pool_health: Arc<pools::PoolHealthTracker>,
+    dex_twaps: Arc<pools::TwapTracker>,
+    basis_tracker: Arc<arbitrage::BasisTracker>,
+    risk_hooks: Arc<risk::RiskHooks>,
+    strategies: Arc<strategy::Strategies>,
+    /// ETH/USD reference price of the cycle
+    eth_usd: rust_decimal::Decimal,
+    gas_cost_usd: rust_decimal::Decimal,
+    gas_baseline_usd: rust_decimal::Decimal,
+    oracle_price: Option<rust_decimal::Decimal>,
+    /// USD prices of the `STABLE_PRICES` quote assets
+    stable_prices: StablePrices,
+    volatility_metrics: VolatilityMetrics,
+    config: Config,
+    is_leader: bool,
+    cex_degraded: bool,
+    /// Timestamp shared by every price read in the cycle's snapshot
+    snapshot_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// CEX and DEX prices read concurrently at the start of a cycle
+struct PriceSnapshot {
+    taken_at: chrono::DateTime<chrono::Utc>,
+    /// ETH/USD reference; None while the CEX breaker is open
+    cex_reference: Option<BotResult<rust_decimal::Decimal>>,
+    /// Prices of pair symbols other than the reference
+    pair_cex_prices: HashMap<String, BotResult<rust_decimal::Decimal>>,
+    /// When each CEX symbol's price was read
+    cex_sampled_at: HashMap<String, Instant>,
+    /// Each pool's DEX price by pool name
+    dex_prices: HashMap<String, DexSample>,
+}
+
+/// A pool's DEX price in quote tokens and when it was read
+struct DexSample {
+    price: BotResult<rust_decimal::Decimal>,
+    sampled_at: Instant,
+}
+
+/// One pool's prices from the cycle's snapshot
+struct PoolPrices {
+    dex_quote_price: BotResult<rust_decimal::Decimal>,
+    cex_quotes: CexQuotes,
+    cex_depth: Option<OrderBook>,
+    /// Time between the pool's DEX and CEX reads, when the CEX read time is known
+    skew: Option<Duration>,
+}
+
+/// Read the CEX reference and pair prices while every pool's DEX price is
+/// read, up to `max_concurrent_pools` at a time
+async fn fetch_price_snapshot(
+    provider: &Arc<ConcreteProvider>,
+    price_source: &dyn network::PriceSource,
+    active_pools: &[&PoolInfo],
+    cex_degraded: bool,
+    max_concurrent_pools: usize,
+) -> PriceSnapshot {
+    let cex = async {
+        let mut cex_sampled_at = HashMap::new();
+        let cex_reference = if cex_degraded {
+            None
+        } else {
+            let result = price_source.price(network::ETH_USD_CEX_SYMBOL).await;
+            cex_sampled_at.insert(network::ETH_USD_CEX_SYMBOL.to_string(), Instant::now());
+            Some(result)
+        };
+        let mut pair_cex_prices = HashMap::new();
+        for pool in active_pools {
+            let symbol = &pool.pair.cex_symbol;
+            if symbol == network::ETH_USD_CEX_SYMBOL || pair_cex_prices.contains_key(symbol) {
+                continue;
+            }
+            pair_cex_prices.insert(symbol.clone(), price_source.price(symbol).await);
+            cex_sampled_at.insert(symbol.clone(), Instant::now());
+        }
+        (cex_reference, pair_cex_prices, cex_sampled_at)
+    };
+    
+    let dex = async {
+        let mut dex_prices = HashMap::new();
+        let mut running = JoinSet::new();
+        let mut pending = active_pools.iter();
+        loop {
+            while running.len() < max_concurrent_pools {
+                let Some(pool) = pending.next() else { break };
+                let provider = provider.clone();
+                let pool = (*pool).clone();
+                running.spawn(async move {
+                    let price = pools::calculate_pool_price_safe_with_retry(&provider, &pool).await;
+                    (pool.name, DexSample { price, sampled_at: Instant::now() })
+                });
+            }
+            match running.join_next().await {
+                Some(Ok((name, sample))) => {
+                    dex_prices.insert(name, sample);
+                }
+                Some(Err(e)) => error!("DEX price read panicked: {}", e),
+                None => break,
+            }
+        }
+        dex_prices
+    };
+    
+    let ((cex_reference, pair_cex_prices, cex_sampled_at), dex_prices) = tokio::join!(cex, dex);
+    PriceSnapshot {
+        taken_at: chrono::Utc::now(),
+        cex_reference,
+        pair_cex_prices,
+        cex_sampled_at,
+        dex_prices,
+    }
+}
+
+/// Process a single pool for arbitrage and market making opportunities
+async fn process_single_pool(
+    ctx: &PoolCycleContext,
+    pool: &PoolInfo,
+    prices: PoolPrices,
+    tally: &mut PoolTally,
+) -> Result<()> {
+    let PoolCycleContext {
+        provider,
+        trade_execution_engine,
+        market_making_engine,
+        execution_queue,
+        deferral_queue,
+        pool_health,
+        dex_twaps,
+        basis_tracker,
+        risk_hooks,
+        volatility_metrics,
+        config,
+        ..
+    } = ctx;
+    let (eth_usd, gas_cost_usd, gas_baseline_usd, oracle_price) =
+        (ctx.eth_usd, ctx.gas_cost_usd, ctx.gas_baseline_usd, ctx.oracle_price);
+    let PoolPrices { dex_quote_price, cex_quotes, cex_depth, skew: price_skew } = prices;
+    
+    // DEX price in quote tokens from the snapshot, then in USD to compare with the CEX
+    let dex_quote_price = dex_quote_price
+        .map_err(|e| anyhow::anyhow!("Failed to calculate DEX price: {}", e))?;
+    let quote_usd = pool.pair.quote_usd(eth_usd, &ctx.stable_prices)
+        .ok_or_else(|| anyhow::anyhow!("No USD reference for {}", pool.pair.quote_symbol))?;
+    if quote_usd <= rust_decimal::Decimal::ZERO {
+        return Err(anyhow::anyhow!("USD reference for {} is {}, skipping {}", pool.pair.quote_symbol, quote_usd, pool.name));
+    }
+    let dex_price = dex_quote_price * quote_usd;
+    let cex_price = cex_quotes.last;
+    tally.dex_price = Some(dex_price);
+    
+    // Fair value on the configured basis (CEX last price until the basis is available)
+    let dex_twap = dex_twaps.record(&pool.name, dex_price, Duration::from_secs(config.twap_window_secs)).await;
+    let fair_values = FairValueQuotes::new(cex_quotes, dex_twap);
+    let (price_basis, fair_value) = fair_values.resolve(config.price_basis);
+    // An empty or zeroed book ticker resolves a CEX basis to zero; every ratio below divides by it
+    if fair_value <= rust_decimal::Decimal::ZERO {
+        return Err(anyhow::anyhow!("{} fair value is {}, skipping {}", price_basis, fair_value, pool.name));
+    }
+    
+    let price_diff_pct = ((dex_price - fair_value).abs() / fair_value) * rust_decimal_macros::dec!(100);
+    let basis = basis_tracker.record(&pool.name, dex_price, fair_value, Duration::from_secs(config.basis_window_secs)).await;
+    
+    info!(
+        "💹 {} | DEX: ${:.4} | {}: ${:.4} | Diff: {:.3}% | Vol: {:.2}%",
+        pool.name, dex_price, price_basis, fair_value, price_diff_pct,
+        volatility_metrics.short_term_volatility
+    );
+    
+    // Volatility and market making track ETH/USD only
+    if pool.pair.is_weth_usd() {
+        market_making_engine.update_price_history(cex_price).await;
+    }
+    // Every pool's DEX and basis volatility can widen its own spreads and slippage
+    market_making_engine.update_dex_price_history(&pool.name, dex_price, cex_price).await;
+    let volatility_metrics = &volatility_metrics.for_pool(market_making_engine.pool_volatility(&pool.name).await);
+    
+    // Analyze liquidity depth and update the pool health score
+    let (liquidity_depth, health) = match pools::analyze_liquidity_depth(
+        provider.as_ref(),
+        pool,
+        fair_value / quote_usd,
+        quote_usd,
+    ).await {
+        Ok(mut liquidity_depth) => {
+            let health = pool_health.record(&pool.name, &liquidity_depth, price_diff_pct).await;
+            liquidity_depth.health_score = health.overall_score;
+            liquidity_depth.depth_quality = pools::depth_quality_for_score(health.overall_score);
+            debug!("🩺 {} health score: {:.1}", pool.name, health.overall_score);
+            (Some(liquidity_depth), Some(health))
+        }
+        Err(e) => {
+            warn!("Failed to analyze liquidity depth for {}: {}", pool.name, e);
+            (None, None)
+        }
+    };
+    tally.price_snapshot = Some(PoolPriceSnapshot {
+        timestamp: ctx.snapshot_at,
+        dex_price,
+        fair_value,
+        price_basis,
+        diff_pct: price_diff_pct,
+        health_score: health.as_ref().map(|h| h.overall_score),
+    });
+    
+    let risk_ctx = risk::RiskContext {
+        pool,
+        dex_price,
+        cex_price,
+        gas_cost_usd,
+        volatility: volatility_metrics,
+        pool_health: health.as_ref(),
+    };
+    
+    // Custom sizers may resize the configured trade, or skip it with zero
+    let trade_size_eth = risk_hooks
+        .size(risk::SizingTarget::Arbitrage, config.trade_size_eth, &risk_ctx)
+        .min(config::MAX_TRADE_SIZE_ETH);
+    // Other base tokens trade the same USD notional as the configured ETH size
+    let trade_size = if pool.pair.is_weth_base() {
+        trade_size_eth
+    } else {
+        trade_size_eth * eth_usd / cex_price
+    };
+    
+    // Quote execution prices for the trade size
+    let quote = if trade_size > rust_decimal_macros::dec!(0) {
+        match pools::quote_trade_prices(
+            provider.as_ref(),
+            pool,
+            trade_size,
+            dex_quote_price,
+        ).await {
+            Ok(quote) => Some(quote.scaled(quote_usd)),
+            Err(e) => {
+                debug!("Quoter unavailable for {}, using mid price: {}", pool.name, e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    
+    // Reserves price the trade off-chain when the pool's quoter is unavailable
+    let reserves = liquidity_depth.as_ref().map(|depth| {
+        pools::PoolReserves::new(pool, depth.weth_reserves, depth.usd_reserves, quote_usd)
+    });
+    
+    let mut trace = DecisionTrace::new(&pool.name, DecisionInputs {
+        dex_price,
+        cex_price,
+        fair_value_price: fair_value,
+        price_basis,
+        price_diff_pct,
+        quoted_buy_price: quote.as_ref().map(|q| q.buy_price),
+        quoted_sell_price: quote.as_ref().map(|q| q.sell_price),
+        gas_cost_usd,
+        net_profit_usd: None,
+        short_term_volatility: volatility_metrics.short_term_volatility,
+        health_score: health.as_ref().map(|h| h.overall_score),
+        liquidity_usd: liquidity_depth.as_ref().map(|d| d.total_liquidity_usd),
+    });
+    
+    if config.record_prices {
+        let sample = storage::PriceSample {
+            timestamp: ctx.snapshot_at,
+            ..storage::PriceSample::new(storage::PriceSource::Dex, &pool.name, dex_price)
+        };
+        if let Err(e) = storage::save_price_samples(&[sample]) {
+            warn!("Failed to save DEX price: {}", e);
+        }
+    }
+    
+    if config.record_spreads {
+        let execution_quote = quote.clone().or_else(|| reserves.and_then(|r| r.quote(trade_size)));
+        let observation = storage::SpreadObservation {
+            timestamp: ctx.snapshot_at,
+            pool: pool.name.clone(),
+            spread_bps: (dex_price - fair_value) / fair_value * rust_decimal_macros::dec!(10000),
+            edge_bps: execution_quote.map(|q| {
+                (fair_value - q.buy_price).max(q.sell_price - fair_value) / fair_value * rust_decimal_macros::dec!(10000)
+            }),
+            liquidity_usd: liquidity_depth.as_ref().map(|d| d.total_liquidity_usd),
+            health_score: health.as_ref().map(|h| h.overall_score),
+        };
+        if let Err(e) = storage::save_spread_observations(&[observation]) {
+            warn!("Failed to save spread observation: {}", e);
+        }
+    }
+    
+    // Every registered strategy sees the same pool and market state
+    if trade_size <= rust_decimal_macros::dec!(0) {
+        trace.check("position_sizer", Some(trade_size), None, false);
+    }
+    let min_price_diff_pct = config.min_price_diff_pct_for(Some(&basis), fair_value);
+    trace.check("price_diff_pct", Some(price_diff_pct), Some(min_price_diff_pct), price_diff_pct >= min_price_diff_pct);
+    if let Some(z_score) = basis.z_score.filter(|_| config.basis_z_threshold > rust_decimal_macros::dec!(0)) {
+        trace.check("basis_z_score", Some(z_score.abs()), Some(config.basis_z_threshold), basis.signals(config.basis_z_threshold));
+    }
+    let pool_state = strategy::PoolState {
+        pool,
+        dex_price,
+        quote_usd,
+        cex_price,
+        fair_values: &fair_values,
+        price_basis,
+        fair_value,
+        price_diff_pct,
+        trade_size,
+        quote: quote.as_ref(),
+        reserves: reserves.as_ref(),
+        liquidity_depth: liquidity_depth.as_ref(),
+        health: health.as_ref(),
+        basis: Some(&basis),
+    };
+    let market_state = strategy::MarketState {
+        eth_usd,
+        gas_cost_usd,
+        gas_baseline_usd,
+        oracle_price,
+        volatility: volatility_metrics,
+        config,
+    };
+    let mut opportunities = Vec::new();
+    let mut signals = Vec::new();
+    for action in ctx.strategies.evaluate(&pool_state, &market_state).await {
+        match action {
+            strategy::Action::Arbitrage(opportunity) => opportunities.push(opportunity),
+            strategy::Action::MarketMaking(signal) => signals.push(signal),
+            strategy::Action::QuoteFill(fill) => {
+                info!("🧾 {} {:?} {:.4} WETH @ ${:.2} filled (spread capture ${:.4})",
+                    pool.name, fill.quote.side, fill.quote.size_eth, fill.quote.price, fill.spread_capture_usd);
+                if let Err(e) = storage::save_quote_fill(&fill) {
+                    error!("Failed to save quote fill: {}", e);
+                    *tally.error_counts.entry("save_quote_fill".to_string()).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+    trace.check("opportunity_detected", None, None, !opportunities.is_empty());
+    
+    // DEX and CEX prices are separate snapshots; too far apart, the spread may only be the delay
+    let skewed = price_skew.filter(|skew| config.max_price_skew_ms > 0 && skew.as_millis() > u128::from(config.max_price_skew_ms));
+    if let Some(skew) = skewed {
+        let skew_ms = skew.as_millis() as u64;
+        trace.check("price_skew", Some(skew_ms.into()), Some(config.max_price_skew_ms.into()), false);
+        if !opportunities.is_empty() {
+            info!("⏱️  Skipping arbitrage on {}: DEX and CEX prices sampled {}ms apart (max {}ms)",
+                pool.name, skew_ms, config.max_price_skew_ms);
+            trace.outcome = DecisionOutcome::Rejected;
+            opportunities.clear();
+        }
+    }
+    tally.sightings = skewed.is_none().then(Vec::new);
+    
+    // A deferred opportunity is re-evaluated against this cycle's prices and gas;
+    // it stays parked while the prices are too far apart to judge it
+    let deferred = match skewed {
+        Some(_) => None,
+        None => deferral_queue.take(&pool.name).await,
+    };
+    if deferred.is_some() && opportunities.is_empty() {
+        info!("🗑️  Deferred opportunity on {} dropped: spread closed", pool.name);
+    }
+    
+    for mut opportunity in opportunities {
+        tally.opportunities += 1;
+        opportunity.volatility_assessment = Some(volatility_metrics.clone());
+        if let Some(book) = &cex_depth {
+            arbitrage::apply_cex_depth(&mut opportunity, book);
+        }
+        trace.inputs.net_profit_usd = Some(opportunity.net_profit_usd);
+        let mut queued = false;
+        
+        let gas_ceiling_usd = config.gas_deferral_enabled.then(|| arbitrage::deferral_ceiling(
+            &opportunity,
+            gas_baseline_usd,
+            config.min_profit_usd,
+            config.gas_spike_multiplier,
+        )).flatten();
+        if let (Some(deferred), None) = (&deferred, gas_ceiling_usd) {
+            info!("♻️  Re-evaluating deferred opportunity on {} (gas ${:.4}, ceiling ${:.4})",
+                pool.name, gas_cost_usd, deferred.gas_ceiling_usd);
+            tally.reevaluated_deferrals += 1;
+        }
+        
+        // Park marginal opportunities during gas spikes instead of dropping them
+        if let Some(gas_ceiling_usd) = gas_ceiling_usd {
+            trace.check("gas_ceiling", Some(gas_cost_usd), Some(gas_ceiling_usd), false);
+            trace.outcome = DecisionOutcome::Deferred;
+            if deferral_queue.defer(opportunity.clone(), gas_ceiling_usd, deferred.as_ref()).await {
+                tally.deferred_opportunities += 1;
+                info!("⏸️  Deferred opportunity on {}: gas ${:.4} over ceiling ${:.4} (baseline ${:.4})",
+                    pool.name, gas_cost_usd, gas_ceiling_usd, gas_baseline_usd);
+            }
+        } else if config.enable_safety_checks {
+            // The pool's on-chain TWAP is only read when there is an opportunity to check
+            let dex_twap_price = if config.max_twap_deviation_pct > rust_decimal_macros::dec!(0) {
+                match pools::get_observation_twap(provider.as_ref(), pool).await {
+                    Ok(twap) => Some(twap * quote_usd),
+                    Err(e) => {
+                        debug!("On-chain TWAP unavailable for {}: {}", pool.name, e);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            opportunity.validation_checks = validation::validate_opportunity_with_volatility(
+                &opportunity,
+                pool,
+                provider.as_ref(),
+                volatility_metrics,
+                health.as_ref(),
+                oracle_price,
+                dex_twap_price,
+                cex_depth.as_ref(),
+            ).await;
+            record_validation_checks(&mut trace, &opportunity, volatility_metrics, config);
+            for verdict in risk_hooks.validate(&opportunity, &risk_ctx) {
+                trace.check(&verdict.validator, None, None, verdict.rejection.is_none());
+                if let Some(reason) = verdict.rejection {
+                    opportunity.validation_checks.warnings.push(format!("{}: {}", verdict.validator, reason));
+                    opportunity.validation_checks.all_passed = false;
+                }
+            }
+            trace.check(
+                "min_profit",
+                Some(opportunity.net_profit_usd),
+                Some(config.min_profit_usd),
+                opportunity.net_profit_usd >= config.min_profit_usd,
+            );
+            
+            if !opportunity.validation_checks.all_passed {
+                trace.outcome = DecisionOutcome::Rejected;
+                warn!("Arbitrage opportunity failed validation: {:?}", 
+                    opportunity.validation_checks.warnings);
+            } else if opportunity.net_profit_usd < config.min_profit_usd {
+                trace.outcome = DecisionOutcome::BelowMinProfit;
+            } else {
+                opportunity.advance(OpportunityStage::Validated);
+                trace.outcome = DecisionOutcome::Recorded;
+                tally.profitable_opportunities += 1;
+                tally.potential_profit += opportunity.net_profit_usd;
+                
+                utils::print_arbitrage_opportunity(&opportunity, volatility_metrics);
+                
+                // Screen new tokens for taxes, blacklists and honeypots before the first trade
+                let token_check = if config.enable_trade_execution && ctx.is_leader {
+                    trade_execution_engine.token_registry.ensure_tradable(provider.as_ref(), pool).await
+                } else {
+                    Ok(())
+                };
+                let risk_check = if config.enable_trade_execution && ctx.is_leader {
+                    trade_execution_engine.risk_check(&opportunity).await
+                } else {
+                    Ok(())
+                };
+
+                // Execute trade simulation if enabled (leader replica only)
+                if config.enable_trade_execution && !ctx.is_leader {
+                    trace.check("execution_leader", None, None, false);
+                    debug!("Skipping execution for {}: not the leader replica", opportunity.id);
+                } else if let Err(reason) = token_check {
+                    trace.check("token_safety", None, None, false);
+                    warn!("☣️  Not executing on {}: {}", pool.name, reason);
+                } else if config.enable_trade_execution && ctx.cex_degraded {
+                    trace.check("cex_breaker", None, None, false);
+                    warn!("⚡ Not executing on {}: CEX circuit breaker is open, price is stale", pool.name);
+                } else if let Err(reason) = risk_check {
+                    trace.check("risk_manager", None, None, false);
+                    warn!("🛑 Not executing on {}: {}", pool.name, reason);
+                } else if config.enable_trade_execution && storage::is_dry_run() {
+                    let priority = execution::opportunity_priority(&opportunity, volatility_metrics, config.min_profit_usd);
+                    trace.check("dry_run", None, None, false);
+                    info!("🧪 Dry run: would queue {:?}-priority execution {}: {} {} {} at DEX ${:.4} vs ${:.4}, net ${:.2}",
+                        priority, opportunity.id, opportunity.direction, opportunity.size_eth, pool.name,
+                        opportunity.dex_price, opportunity.cex_price, opportunity.net_profit_usd);
+                } else if config.enable_trade_execution {
+                    let priority = execution::opportunity_priority(&opportunity, volatility_metrics, config.min_profit_usd);
+                    let job = execution::ExecutionJob::new(opportunity.clone(), pool.clone(), volatility_metrics.clone(), priority.clone());
+                    if execution_queue.submit(job) {
+                        queued = true;
+                        trace.outcome = DecisionOutcome::Queued;
+                        info!("📬 Queued execution for {} ({:?} priority)", pool.name, priority);
+                    } else {
+                        trace.check("execution_queue", None, None, false);
+                        debug!("Not queuing {}: an execution for the pool is already queued or running", pool.name);
+                    }
+                }
+                
+                if let Err(e) = storage::save_opportunity(&opportunity) {
+                    error!("Failed to save arbitrage opportunity: {}", e);
+                    *tally.error_counts.entry("save_opportunity".to_string()).or_insert(0) += 1;
+                }
+                tally.opportunity = Some(opportunity.clone());
+            }
+        } else {
+            // Opportunities are only acted on after validation
+            trace.check("safety_checks_enabled", None, None, false);
+            trace.outcome = DecisionOutcome::Rejected;
+        }
+        if let Some(sightings) = tally.sightings.as_mut() {
+            sightings.push(arbitrage::OpportunitySighting::new(&opportunity, queued));
+        }
+    }
+    
+    for mut signal in signals {
+        signal.position_size_eth = risk_hooks.size(
+            risk::SizingTarget::MarketMaking,
+            signal.position_size_eth,
+            &risk_ctx,
+        );
+        market_making_engine.place_quotes(&signal).await;
+        tally.market_making_signals += 1;
+        trace.market_making = Some(MarketMakingDecision {
+            strategy_type: signal.strategy.strategy_type.clone(),
+            effective_spread_bps: signal.effective_spread_bps,
+            position_size_eth: signal.position_size_eth,
+            execution_priority: signal.execution_priority.clone(),
+        });
+        utils::print_market_making_signal(&signal);
+        
+        if let Err(e) = storage::save_market_making_signal(&signal) {
+            error!("Failed to save market making signal: {}", e);
+            *tally.error_counts.entry("save_market_making_signal".to_string()).or_insert(0) += 1;
+        }
+    }
+    
+    if config.record_decisions {
+        if let Err(e) = storage::save_decision_trace(&trace) {
+            error!("Failed to save decision trace: {}", e);
+            *tally.error_counts.entry("save_decision_trace".to_string()).or_insert(0) += 1;
+        }
+    }
+    
+    Ok(())
+}
+
+/// Record the validation rules evaluated for an opportunity in its decision trace
+fn record_validation_checks(
+    trace: &mut DecisionTrace,
+    opportunity: &ArbitrageOpportunity,
+    volatility_metrics: &VolatilityMetrics,
+    config: &Config,
+) {
+    let checks = &opportunity.validation_checks;
+    trace.check(
+        "pool_health",
+        trace.inputs.health_score,
+        Some(pools::MIN_POOL_HEALTH_SCORE),
+        checks.pool_health_acceptable,
+    );
+    trace.check(
+        "price_sanity",
+        Some(opportunity.price_diff_pct),
+        Some(config::MAX_PRICE_DEVIATION_PCT),
+        checks.price_sanity,
+    );
+    trace.check("oracle", None, Some(config.max_oracle_deviation_pct), checks.oracle_consistent);
+    trace.check("twap_manipulation", None, Some(config.max_twap_deviation_pct), !checks.manipulation_suspected);
+    // Volatility only warns, so it never rejects on its own
+    trace.checks.push(RuleCheck {
+        rule: "volatility".to_string(),
+        value: Some(volatility_metrics.short_term_volatility),
+        threshold: Some(config.volatility_threshold),
+        passed: checks.volatility_acceptable,
+    });
+    trace.check("liquidity", trace.inputs.liquidity_usd, None, checks.liquidity_check);
+    trace.check("cex_liquidity", None, None, checks.cex_liquidity);
+    trace.check("gas_economics", Some(opportunity.net_profit_usd), Some(rust_decimal_macros::dec!(0)), checks.gas_economics);
+    trace.check(
+        "slippage",
+        None,
+        Some(rust_decimal::Decimal::from(config::MAX_SLIPPAGE_BPS)),
+        checks.slippage_acceptable,
+    );
+    // Catches rules without a dedicated flag (e.g. trade size vs pool liquidity)
+    trace.check("all_validations", None, None, checks.all_passed);
+}
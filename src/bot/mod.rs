@@ -0,0 +1,8 @@
+//! Embeddable bot: [`Bot`] wires the components from a [`Config`](crate::Config)
+//! and runs the monitoring loop until its [`BotHandle`] shuts it down
+
+pub mod runner;
+pub mod cycle;
+pub mod session;
+
+pub use runner::*;
@@ -0,0 +1,643 @@
+//! Bot lifecycle: component setup, the main monitoring loop and the
+//! shutdown artifacts, run on a background task behind [`BotHandle`]
+
+use crate::*;
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, warn, error, debug};
+use alloy::providers::Provider;
+use tokio::task::JoinHandle;
+use futures::FutureExt;
+use std::panic::AssertUnwindSafe;
+use crate::types::ControlEvent;
+use super::cycle::run_monitoring_cycle;
+use super::session::*;
+
+/// How often old logs and outputs are compressed and pruned
+const RETENTION_SWEEP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// A configured bot, not yet running.
+///
+/// ```ignore
+/// let handle = Bot::new(config).with_dry_run(true).run();
+/// handle.shutdown();
+/// handle.wait().await?;
+/// ```
+pub struct Bot {
+    config: Config,
+    dry_run: bool,
+    risk_hooks: risk::RiskHooks,
+    strategies: strategy::Strategies,
+    price_source: Option<Arc<dyn network::PriceSource>>,
+}
+
+impl Bot {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            dry_run: false,
+            risk_hooks: risk::RiskHooks::new(),
+            strategies: strategy::Strategies::new(),
+            price_source: None,
+        }
+    }
+
+    /// Log records and executions instead of writing or sending them
+    /// (in addition to `config.dry_run`)
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Custom validators and sizers shared with every pool task
+    pub fn with_risk_hooks(mut self, risk_hooks: risk::RiskHooks) -> Self {
+        self.risk_hooks = risk_hooks;
+        self
+    }
+
+    /// Custom strategy evaluated for every pool after the built-in
+    /// arbitrage and market-making strategies
+    pub fn with_strategy(mut self, strategy: impl strategy::Strategy + 'static) -> Self {
+        self.strategies = self.strategies.with_strategy(strategy);
+        self
+    }
+
+    /// CEX prices from `source` instead of the configured `CEX_SOURCE`
+    pub fn with_price_source(mut self, source: impl network::PriceSource + 'static) -> Self {
+        self.price_source = Some(Arc::new(source));
+        self
+    }
+
+    /// Start the bot on a tokio task. Must be called within a tokio runtime.
+    /// Installs the bot's config as the process-wide [`CONFIG`](crate::CONFIG)
+    /// and applies its rate limits.
+    pub fn run(self) -> BotHandle {
+        storage::set_dry_run(self.dry_run || self.config.dry_run);
+        CONFIG.replace(self.config.clone());
+        network::configure_rate_limits(&self.config);
+        let (control, control_rx) = control::control_channel();
+        let status = api::StatusBoard::new();
+        let task = tokio::spawn(run_bot(
+            self.config,
+            control.clone(),
+            control_rx,
+            status.clone(),
+            self.risk_hooks,
+            self.strategies,
+            self.price_source,
+        ));
+        BotHandle { control, status, task }
+    }
+}
+
+/// A running bot
+pub struct BotHandle {
+    control: control::ControlSender,
+    status: api::StatusBoard,
+    task: JoinHandle<Result<()>>,
+}
+
+impl BotHandle {
+    /// Stop after the current cycle is preempted; the bot still writes its
+    /// shutdown summary and state before [`BotHandle::wait`] returns
+    pub fn shutdown(&self) {
+        self.control.kill("shutdown requested");
+    }
+
+    /// Sender for pause, resume and other control events
+    pub fn control(&self) -> control::ControlSender {
+        self.control.clone()
+    }
+
+    /// Status and activity reports published after each cycle
+    pub fn status(&self) -> &api::StatusBoard {
+        &self.status
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Wait for the bot to stop, returning its startup or fatal error
+    pub async fn wait(self) -> Result<()> {
+        self.task.await.map_err(|e| anyhow::anyhow!("Bot task failed: {}", e))?
+    }
+}
+
+/// Set up the components and run the monitoring loop until a kill switch
+async fn run_bot(
+    mut config: Config,
+    control_tx: control::ControlSender,
+    mut control_rx: control::ControlReceiver,
+    status_board: api::StatusBoard,
+    risk_hooks: risk::RiskHooks,
+    custom_strategies: strategy::Strategies,
+    price_source: Option<Arc<dyn network::PriceSource>>,
+) -> Result<()> {
+    // Validate configuration
+    cli::check_config(&config)?;
+    
+    // Supplement JSONL output with SQLite when configured; a dry run doesn't
+    // open (or create and migrate) the database
+    match &config.sqlite_path {
+        Some(sqlite_path) if !storage::is_dry_run() => storage::init_database(sqlite_path)?,
+        _ => storage::close_database(),
+    }
+    
+    // Initialize components
+    let breakers = Arc::new(
+        errors::CircuitBreakerRegistry::from_config(&config)
+            .with_control(control_tx.clone())
+    );
+    let error_recovery = Arc::new(errors::ErrorRecovery::from_config(&config));
+    
+    // Setup network providers
+    let provider = network::setup_mainnet_provider(&config).await?;
+    let risk_manager = Arc::new(
+        risk::RiskManager::from_config(&config)
+            .with_control(control_tx.clone())
+    );
+    let trade_execution_engine = Arc::new(
+        execution::TradeExecutionEngine::new(&config).await?
+            .with_risk_manager(risk_manager.clone())
+    );
+    // The lease is renewed in the background so long cycles and executions never let it lapse
+    let mut leader = control::LeaderElection::from_config(&config).map(|leader| {
+        info!("🗳️  Leader election enabled as {} ({}s lease)", leader.instance_id(), leader.lease_duration().as_secs());
+        leader.spawn_renewal()
+    });
+    let (execution_queue, mut execution_results) = execution::spawn_execution_queue(
+        trade_execution_engine.clone(),
+        config.max_concurrent_executions,
+        Duration::from_secs(config.execution_queue_max_age_secs),
+        leader.as_ref().map(|leader| leader.flag()),
+    );
+    let market_making_engine = Arc::new(
+        market_making::MarketMakingEngine::new()
+            .with_risk_manager(risk_manager.clone())
+    );
+    let reconciler = network::ReconciliationClient::from_config(&config)?;
+    let price_source = match price_source {
+        Some(source) => source,
+        None => network::cex_price_source(&config)?,
+    };
+    info!("💱 CEX prices from {}", price_source.name());
+    let gas_oracle = network::GasOracle::new();
+    let price_oracle = network::PriceOracle::from_config(&config);
+    if let Some(oracle) = &price_oracle {
+        info!("🔮 Oracle cross-check enabled: feed {:?}, max deviation {}%", oracle.feed, config.max_oracle_deviation_pct);
+    }
+    let stable_oracle = network::StablePriceOracle::from_config(&config);
+    if !stable_oracle.is_empty() {
+        info!("🔮 USD prices for other quote assets: {}", config.stable_prices.keys().cloned().collect::<Vec<_>>().join(", "));
+    }
+    let deferral_queue = Arc::new(arbitrage::DeferralQueue::new(config.gas_deferral_ttl_secs));
+    let pool_health = Arc::new(pools::PoolHealthTracker::new());
+    let dex_twaps = Arc::new(pools::TwapTracker::new());
+    let basis_tracker = Arc::new(arbitrage::BasisTracker::new());
+    let portfolio = portfolio::PortfolioTracker::from_config(&config)?;
+    let risk_hooks = Arc::new(risk_hooks);
+    let strategies = Arc::new(
+        strategy::Strategies::new()
+            .with_strategy(strategy::ArbitrageStrategy)
+            .with_strategy(strategy::MarketMakingStrategy::new(market_making_engine.clone(), provider.clone()))
+            .with_strategies(custom_strategies)
+    );
+    info!("🧠 Strategies: {}", strategies.names().join(", "));
+    
+    // Test Sepolia connection if trade execution enabled
+    if config.enable_trade_execution {
+        if let Some(sepolia_provider) = &trade_execution_engine.sepolia_provider {
+            info!("🔗 Testing connection to Base Sepolia...");
+            let sepolia_block = network::retry_with_backoff(
+                || async {
+                    sepolia_provider.get_block_number().await
+                        .map_err(|e| anyhow::anyhow!("Failed to get Sepolia block number: {}", e))
+                },
+                &network::RetryConfig::default(),
+                "Base Sepolia connection",
+            ).await.map_err(|e| {
+                error!("Failed to connect to Base Sepolia after multiple attempts");
+                anyhow::anyhow!("Network connection failed: {}", e)
+            })?;
+            info!("✅ Connected to Base Sepolia at block {}", sepolia_block);
+        }
+    }
+    
+    // Initialize and validate the static pools, then add factory-discovered ones
+    let mut pool_discovery = pools::PoolDiscovery::from_config(&config);
+    let mut base_pools = match pools::initialize_pools_with_cache(&provider, &config).await {
+        Ok(pools) => pools,
+        Err(e) if pool_discovery.is_some() => {
+            warn!("Static pool validation failed, relying on discovery: {}", e);
+            Vec::new()
+        }
+        Err(e) => return Err(e),
+    };
+    if let Some(discovery) = pool_discovery.as_mut() {
+        if let Err(e) = discovery.refresh(&provider, &config).await {
+            warn!("Pool discovery failed: {}", e);
+        }
+    }
+    let mut valid_pools = pools::merge_pools(&base_pools, discovered_pools(&pool_discovery));
+    
+    if valid_pools.is_empty() {
+        return Err(anyhow::anyhow!("No valid pools found after validation"));
+    }
+    
+    info!("✅ Initialized {} valid pools", valid_pools.len());
+    
+    if config.enable_market_making {
+        info!("\n🎯 Market Making Engine initialized with volatility adaptation");
+        info!("   Multi-timeframe volatility tracking: 5min, 30min, 1hour");
+        info!("   Dynamic spread adjustment based on volatility");
+        info!("   Position sizing adapts to market conditions");
+    }
+    
+    if config.enable_trade_execution {
+        info!("\n🚀 Trade Execution Engine initialized");
+        match (config.execution_network.as_str(), &config.private_key) {
+            ("mainnet", _) => info!("   Mode: Live swaps on Base mainnet"),
+            (_, Some(_)) => info!("   Mode: Swaps on Base Sepolia testnet"),
+            (_, None) => info!("   Mode: Simulation ({}), no PRIVATE_KEY for Base Sepolia", config.simulation_mode),
+        }
+    }
+    
+    // Setup monitoring state
+    let start_time = Instant::now();
+    let mut monitoring_state = MonitoringState::new();
+    
+    // Resume counters, volatility windows and last prices from the previous run
+    let mut volatility_history = storage::HistorySnapshot::default();
+    match storage::load_bot_state() {
+        Ok(Some(saved)) => {
+            monitoring_state.restore(&saved);
+            info!("♻️  Restored state saved at {} ({} volatility samples)",
+                saved.saved_at, saved.volatility_history.samples.len());
+            volatility_history = saved.volatility_history;
+        }
+        Ok(None) => {}
+        Err(e) => warn!("Ignoring unreadable bot state: {}", e),
+    }
+    // Prices recorded after the last save (or without one) fill the rest of the windows
+    let window_start = chrono::Utc::now() - chrono::Duration::seconds(volatility::LONG_TERM_SECS as i64);
+    match storage::recorded_cex_history(std::path::Path::new(storage::PRICES_DIR), network::ETH_USD_CEX_SYMBOL, window_start) {
+        Ok(recorded) => {
+            let added = volatility_history.append_newer(recorded);
+            if added > 0 {
+                info!("♻️  Rebuilt {} volatility samples from recorded prices", added);
+            }
+        }
+        Err(e) => warn!("Failed to read recorded prices: {}", e),
+    }
+    market_making_engine.restore_volatility_history(&volatility_history).await;
+    
+    // Lifetime totals count from the restored counters on
+    let lifetime = match storage::load_lifetime_stats() {
+        Ok(lifetime) => lifetime.unwrap_or_default(),
+        Err(e) => {
+            warn!("Ignoring unreadable lifetime statistics: {}", e);
+            LifetimeStats::default()
+        }
+    };
+    monitoring_state.stats = storage::SessionStatsRecorder::new(lifetime, &monitoring_state.counters(), chrono::Utc::now());
+    let mut last_state_save = Instant::now();
+    let mut last_retention_sweep: Option<Instant> = None;
+    
+    // Serve /health and /status from the report published after each cycle
+    if let Some(bind_addr) = &config.api_bind_addr {
+        api::spawn_status_server(bind_addr, status_board.clone()).await?;
+    }
+    
+    // Health checks run on their own schedule, however long a cycle takes
+    let health_monitor = (config.health_check_interval_secs > 0).then(|| utils::spawn_health_monitor(
+        status_board.clone(),
+        breakers.clone(),
+        start_time,
+        Duration::from_secs(config.health_check_interval_secs),
+    ));
+    
+    // Reload runtime-safe settings on SIGHUP or config file change
+    config::spawn_config_watcher(config.config_file.clone(), control_tx.clone());
+    
+    info!("\n🚀 Starting main monitoring loop...\n");
+    
+    let mut trigger = network::CycleTrigger::from_config(&config);
+    
+    let mut paused = false;
+    let mut reload_requested = false;
+    let mut checkpointer = storage::Checkpointer::new(config.checkpoint_interval_secs);
+    
+    // Main monitoring loop. Control events are polled first (biased) and also
+    // raced against the running cycle: a kill switch or pause preempts it at its
+    // next await point, other events are handled once it finishes.
+    loop {
+        tokio::select! {
+            biased;
+            Some(event) = control_rx.recv() => {
+                if !handle_control_event(event, &mut paused, &mut reload_requested) {
+                    break;
+                }
+            }
+            Some(outcome) = execution_results.recv() => {
+                record_execution_outcome(&mut monitoring_state, outcome, reconciler.as_ref()).await;
+            }
+            block = trigger.next() => {
+                if let Some(block) = block {
+                    debug!("⛓️  Cycle for block {}", block);
+                    pools::POOL_STATE_CACHE.advance_block(block);
+                }
+                let mut pools_changed = false;
+                if std::mem::take(&mut reload_requested) {
+                    reload_config(&mut config, &provider, &mut base_pools).await;
+                    pools_changed = true;
+                }
+                // Re-query the factory periodically, and after a reload for the new pool list
+                if let Some(discovery) = pool_discovery.as_mut().filter(|d| pools_changed || d.due()) {
+                    match discovery.refresh(&provider, &config).await {
+                        Ok(changed) => pools_changed |= changed,
+                        Err(e) => warn!("Pool discovery refresh failed: {}", e),
+                    }
+                }
+                if pools_changed {
+                    valid_pools = pools::merge_pools(&base_pools, discovered_pools(&pool_discovery));
+                    info!("🏊 Monitoring {} pools", valid_pools.len());
+                }
+                if paused {
+                    publish_status(&status_board, &mut monitoring_state, &market_making_engine, &breakers, start_time, paused).await;
+                    continue;
+                }
+                // All replicas monitor; only the lease holder executes
+                monitoring_state.is_leader = leader.as_ref().is_none_or(|l| l.is_leader());
+                // Only a kill switch or pause preempts the cycle; other events
+                // arriving meanwhile wait until it finishes
+                let mut deferred_events = Vec::new();
+                let cycle_end = {
+                    let cycle = AssertUnwindSafe(run_monitoring_cycle(
+                        &provider,
+                        &trade_execution_engine,
+                        &market_making_engine,
+                        &execution_queue,
+                        price_source.as_ref(),
+                        &gas_oracle,
+                        price_oracle.as_ref(),
+                        &stable_oracle,
+                        &deferral_queue,
+                        &pool_health,
+                        &dex_twaps,
+                        &basis_tracker,
+                        portfolio.as_ref(),
+                        &risk_hooks,
+                        &strategies,
+                        &valid_pools,
+                        &config,
+                        &breakers,
+                        &error_recovery,
+                        &mut monitoring_state,
+                        start_time,
+                    )).catch_unwind();
+                    tokio::pin!(cycle);
+                    loop {
+                        tokio::select! {
+                            biased;
+                            Some(event) = control_rx.recv() => {
+                                if event.preempts_cycle() {
+                                    break Err(event);
+                                }
+                                deferred_events.push(event);
+                            }
+                            result = &mut cycle => break Ok(result),
+                        }
+                    }
+                };
+                if !deferred_events.into_iter().all(|event| handle_control_event(event, &mut paused, &mut reload_requested)) {
+                    break;
+                }
+                match cycle_end {
+                    Err(event) => {
+                        warn!("⏹️  Control event preempted in-flight monitoring cycle");
+                        if !handle_control_event(event, &mut paused, &mut reload_requested) {
+                            break;
+                        }
+                    }
+                    Ok(result) => {
+                        match result {
+                            Ok(Ok(())) => {}
+                            Ok(Err(e)) => {
+                                error!("Monitoring cycle error: {}", e);
+                                if breakers.rpc.record_error().await {
+                                    error!("RPC circuit breaker activated due to monitoring errors");
+                                }
+                            }
+                            // The cycle is abandoned where it panicked; the next one starts fresh
+                            Err(panic) => {
+                                record_panic(&mut monitoring_state, "monitoring cycle", panic.as_ref());
+                            }
+                        }
+                        if panic_limit_reached(&monitoring_state, &config) {
+                            error!("🛑 {} panics caught (MAX_PANICS = {}), shutting down", monitoring_state.panics, config.max_panics);
+                            control_tx.kill("too many panics");
+                        }
+                    }
+                }
+                
+                // Poll faster while the market moves, slower while it is quiet
+                let volatility = market_making_engine.get_volatility_metrics().await;
+                let poll_interval = network::adaptive_poll_interval(&volatility, &config);
+                if trigger.set_poll_interval(poll_interval) {
+                    info!("⏱️  Polling every {:?} ({:?} volatility)", poll_interval, volatility.impact_assessment);
+                }
+                
+                if config.state_save_interval_secs > 0
+                    && last_state_save.elapsed() >= Duration::from_secs(config.state_save_interval_secs)
+                {
+                    let bot_state = monitoring_state.bot_state(market_making_engine.volatility_history().await);
+                    if let Err(e) = storage::save_bot_state(&bot_state) {
+                        warn!("Failed to save bot state: {}", e);
+                    }
+                    record_session_stats(&mut monitoring_state, start_time, chrono::Utc::now().date_naive());
+                    last_state_save = Instant::now();
+                }
+                
+                // Compress and prune old logs and outputs off the loop
+                if !storage::is_dry_run()
+                    && last_retention_sweep.is_none_or(|last| last.elapsed() >= RETENTION_SWEEP_INTERVAL)
+                {
+                    let policy = storage::RetentionPolicy::from_config(&config);
+                    if policy.is_enabled() {
+                        tokio::task::spawn_blocking(move || {
+                            if let Err(e) = storage::sweep_output(&policy) {
+                                warn!("Output retention sweep failed: {}", e);
+                            }
+                        });
+                    }
+                    last_retention_sweep = Some(Instant::now());
+                }
+                
+                if let Some(kind) = checkpointer.due() {
+                    let closed_day = checkpointer.current_day();
+                    let session = session_summary(&monitoring_state, start_time, None);
+                    if let Some(paper) = &session.paper_portfolio {
+                        if let Err(e) = storage::save_paper_portfolio_report(paper) {
+                            warn!("Failed to save paper portfolio snapshot: {}", e);
+                        }
+                    }
+                    if let Err(e) = checkpointer.run(kind, session) {
+                        error!("Storage checkpoint failed: {}", e);
+                        *monitoring_state.error_counts.entry("checkpoint".to_string()).or_insert(0) += 1;
+                    }
+                    if matches!(kind, CheckpointKind::EndOfDay | CheckpointKind::EndOfEpoch) {
+                        record_session_stats(&mut monitoring_state, start_time, closed_day);
+                        write_daily_report(closed_day);
+                    }
+                }
+                
+                publish_status(&status_board, &mut monitoring_state, &market_making_engine, &breakers, start_time, paused).await;
+            }
+        }
+    }
+    
+    // Let trades already sent finish and book them before the state is saved
+    execution_queue.close();
+    let drain_deadline = tokio::time::Instant::now() + config.longest_execution();
+    loop {
+        match tokio::time::timeout_at(drain_deadline, execution_results.recv()).await {
+            Ok(Some(outcome)) => record_execution_outcome(&mut monitoring_state, outcome, reconciler.as_ref()).await,
+            Ok(None) => break,
+            Err(_) => {
+                warn!("⏳ Executions still running after {:?}, shutting down without their results", config.longest_execution());
+                break;
+            }
+        }
+    }
+    
+    if let Some(leader) = leader.as_mut() {
+        leader.release().await;
+    }
+    if let Some(health_monitor) = health_monitor {
+        health_monitor.abort();
+    }
+    
+    // Spreads still open and executions still pending end with the session
+    let interrupted = monitoring_state.lifecycles.drain(chrono::Utc::now());
+    if !interrupted.is_empty() {
+        record_lifecycles(&mut monitoring_state, &interrupted);
+    }
+    
+    record_session_stats(&mut monitoring_state, start_time, chrono::Utc::now().date_naive());
+    
+    // Value end-of-session inventory and print final statistics
+    let inventory = session_inventory_report(
+        portfolio.as_ref(),
+        &market_making_engine,
+        &monitoring_state,
+        &config,
+    ).await;
+    print_final_statistics(start_time, &monitoring_state, inventory.as_ref());
+    
+    let summary = session_summary(&monitoring_state, start_time, inventory);
+    if let Err(e) = checkpointer.run(CheckpointKind::Shutdown, summary.clone()) {
+        error!("Shutdown storage checkpoint failed: {}", e);
+    }
+    if let Err(e) = storage::save_session_summary(&summary) {
+        error!("Failed to save session summary: {}", e);
+    }
+    
+    let bot_state = monitoring_state.bot_state(market_making_engine.volatility_history().await);
+    match storage::save_bot_state(&bot_state) {
+        Ok(()) => info!("💾 Saved bot state to {}", storage::STATE_PATH),
+        Err(e) => error!("Failed to save bot state: {}", e),
+    }
+    
+    Ok(())
+}
+
+/// Build and save the report for a day closed by the UTC rollover
+fn write_daily_report(day: chrono::NaiveDate) {
+    match storage::build_daily_report(std::path::Path::new("output"), day)
+        .and_then(|report| storage::save_daily_report(&report))
+    {
+        Ok((_, markdown_file)) => info!("📅 Daily report for {} written to {}", day, markdown_file),
+        Err(e) => error!("Failed to write daily report for {}: {}", day, e),
+    }
+}
+
+/// Apply a control-plane event, returning false when the bot must stop
+fn handle_control_event(event: ControlEvent, paused: &mut bool, reload_requested: &mut bool) -> bool {
+    match event {
+        ControlEvent::Pause { reason } => {
+            warn!("⏸️  Monitoring paused: {}", reason);
+            *paused = true;
+        }
+        ControlEvent::Resume => {
+            info!("▶️  Monitoring resumed");
+            *paused = false;
+        }
+        ControlEvent::KillSwitch { reason } => {
+            error!("🛑 Kill switch engaged: {}", reason);
+            info!("Shutdown signal received, exiting main loop...");
+            return false;
+        }
+        ControlEvent::BreakerTripped { reason } => {
+            warn!("⚡ Circuit breaker tripped: {}", reason);
+        }
+        ControlEvent::ExecutionHalted { reason } => {
+            warn!("🛑 Execution halted, monitoring continues: {}", reason);
+        }
+        ControlEvent::ReloadConfig { reason } => {
+            info!("🔄 Config reload requested: {}", reason);
+            *reload_requested = true;
+        }
+    }
+    true
+}
+
+fn discovered_pools(pool_discovery: &Option<pools::PoolDiscovery>) -> &[PoolInfo] {
+    pool_discovery.as_ref().map_or(&[], |d| d.pools())
+}
+
+/// Re-read config and apply runtime-safe changes, keeping engine state
+/// (volatility history, pool health) intact. Invalid config is ignored.
+async fn reload_config(
+    config: &mut Config,
+    provider: &Arc<ConcreteProvider>,
+    base_pools: &mut Vec<PoolInfo>,
+) {
+    let new_config = match Config::try_load() {
+        Ok(new_config) => new_config,
+        Err(e) => {
+            error!("❌ Config reload rejected, keeping current settings: {}", e);
+            return;
+        }
+    };
+    
+    let mut reloaded = config.clone();
+    let changed = reloaded.apply_reloadable(&new_config);
+    if changed.is_empty() {
+        info!("🔄 Config reloaded, no runtime settings changed");
+        return;
+    }
+    
+    // A changed pool list is validated before it replaces the current pools
+    if changed.iter().any(|field| ["enabled_pools", "custom_pools", "cex_symbols"].contains(field)) {
+        match pools::initialize_and_validate_pools(provider, &reloaded).await {
+            Ok(pools) if !pools.is_empty() => {
+                info!("🏊 Pool list reloaded: {} pools", pools.len());
+                *base_pools = pools;
+            }
+            Ok(_) => {
+                error!("❌ Config reload rejected: no valid pools in the new pool list");
+                return;
+            }
+            Err(e) => {
+                error!("❌ Config reload rejected: pool validation failed: {}", e);
+                return;
+            }
+        }
+    }
+    
+    info!("🔄 Config reloaded, updated: {}", changed.join(", "));
+    CONFIG.replace(reloaded.clone());
+    *config = reloaded;
+}
@@ -0,0 +1,391 @@
+//! Session bookkeeping on the [`MonitoringState`]: executions, the paper
+//! ledger, lifetime statistics, status reports and the shutdown summary
+
+use crate::*;
+use std::sync::Arc;
+use std::time::Instant;
+use std::collections::HashMap;
+use tracing::{info, warn, error, debug};
+
+/// Snapshot of the session statistics for checkpoints and the shutdown artifact
+pub(crate) fn session_summary(
+    state: &MonitoringState,
+    start_time: Instant,
+    inventory: Option<portfolio::InventoryReport>,
+) -> SessionSummary {
+    SessionSummary {
+        timestamp: chrono::Utc::now(),
+        runtime_secs: start_time.elapsed().as_secs(),
+        total_opportunities: state.total_opportunities,
+        profitable_opportunities: state.profitable_opportunities,
+        total_potential_profit_usd: state.total_potential_profit,
+        total_market_making_signals: state.total_market_making_signals,
+        total_executions: state.total_executions,
+        successful_executions: state.successful_executions,
+        deferred_opportunities: state.deferred_opportunities,
+        reevaluated_deferrals: state.reevaluated_deferrals,
+        expired_deferrals: state.expired_deferrals,
+        error_counts: state.error_counts.clone(),
+        inventory,
+        paper_portfolio: state.paper_portfolio.as_ref()
+            .zip(state.last_known_cex_price)
+            .map(|(paper, price)| paper.report(price)),
+        market_making_quotes: state.quote_book.clone(),
+        stress_tests: state.stress_tests.clone(),
+    }
+}
+
+/// Apply an execution's fills to the paper ledger and record the new valuation
+pub(crate) fn book_paper_execution(state: &mut MonitoringState, execution: &TradeExecution, opportunity: &ArbitrageOpportunity) {
+    let price = state.last_known_cex_price.unwrap_or(opportunity.cex_price);
+    let Some(paper) = state.paper_portfolio.as_mut() else {
+        return;
+    };
+    if !paper.apply_execution(execution, opportunity) {
+        return;
+    }
+    let report = paper.report(price);
+    info!("📒 Paper portfolio: {:.4} WETH + ${:.2} = ${:.2} (realized ${:.2}, unrealized ${:.2})",
+        report.weth, report.usdc, report.value_usd, report.realized_pnl_usd, report.unrealized_pnl_usd);
+    if let Err(e) = storage::save_paper_portfolio_report(&report) {
+        warn!("Failed to save paper portfolio snapshot: {}", e);
+        *state.error_counts.entry("save_paper_portfolio".to_string()).or_insert(0) += 1;
+    }
+}
+
+/// Book a finished queued execution into the session statistics, storage
+/// and the reconciliation service
+pub(crate) async fn record_execution_outcome(
+    state: &mut MonitoringState,
+    outcome: execution::ExecutionOutcome,
+    reconciler: Option<&network::ReconciliationClient>,
+) {
+    let execution::ExecutionOutcome { job, waited, result } = outcome;
+    if let Some(lifecycle) = state.lifecycles.record_execution(&job.opportunity.id, &result, chrono::Utc::now()) {
+        record_lifecycles(state, &[lifecycle]);
+    }
+    let execution = match result {
+        execution::ExecutionResult::Completed(execution) => execution,
+        execution::ExecutionResult::Failed(e) => {
+            error!("Trade execution simulation failed for {}: {}", job.pool.name, e);
+            *state.error_counts.entry("execution_simulation".to_string()).or_insert(0) += 1;
+            return;
+        }
+        execution::ExecutionResult::Expired => {
+            info!("⌛ Dropped queued execution for {} after {:.1}s: opportunity too old",
+                job.pool.name, waited.as_secs_f64());
+            *state.error_counts.entry("execution_expired".to_string()).or_insert(0) += 1;
+            return;
+        }
+        execution::ExecutionResult::NotLeader => {
+            info!("🔁 Dropped queued execution for {}: leadership lost", job.pool.name);
+            *state.error_counts.entry("execution_not_leader".to_string()).or_insert(0) += 1;
+            return;
+        }
+    };
+    debug!("Execution for {} started after {:.1}s in queue", job.pool.name, waited.as_secs_f64());
+    
+    state.total_executions += 1;
+    if matches!(execution.status, ExecutionStatus::Success | ExecutionStatus::Simulated) {
+        state.successful_executions += 1;
+    }
+    utils::print_trade_execution(&execution);
+    if job.pool.pair.is_weth_usd() {
+        book_paper_execution(state, &execution, &job.opportunity);
+    }
+    
+    state.activity.record_execution(&execution);
+    if let Err(e) = storage::save_trade_execution(&execution) {
+        error!("Failed to save trade execution: {}", e);
+        *state.error_counts.entry("save_execution".to_string()).or_insert(0) += 1;
+    }
+    
+    if let Some(reconciler) = reconciler {
+        match reconciler.reconcile(&execution).await {
+            Ok(ack) => {
+                if let Err(e) = storage::save_reconciliation_ack(&ack) {
+                    error!("Failed to save reconciliation ack: {}", e);
+                }
+            }
+            Err(e) => {
+                // Rejected executions are not recorded as reconciled
+                let cause = std::error::Error::source(&e);
+                let kind = if cause.is_some_and(|c| c.is::<network::ReconciliationRejected>()) {
+                    "reconciliation_rejected"
+                } else {
+                    "reconciliation"
+                };
+                match cause {
+                    Some(cause) => warn!("Failed to reconcile execution {}: {}: {}", execution.id, e, cause),
+                    None => warn!("Failed to reconcile execution {}: {}", execution.id, e),
+                }
+                *state.error_counts.entry(kind.to_string()).or_insert(0) += 1;
+            }
+        }
+    }
+}
+
+/// Persist finished opportunity lifecycles
+pub(crate) fn record_lifecycles(state: &mut MonitoringState, lifecycles: &[OpportunityLifecycle]) {
+    for lifecycle in lifecycles {
+        debug!("Opportunity {} on {} ended {:?} at {:?} after {}ms ({} sightings)",
+            lifecycle.id, lifecycle.pool, lifecycle.end, lifecycle.stage, lifecycle.persisted_ms, lifecycle.sightings);
+    }
+    if let Err(e) = storage::save_opportunity_lifecycles(lifecycles) {
+        error!("Failed to save opportunity lifecycles: {}", e);
+        *state.error_counts.entry("save_lifecycle".to_string()).or_insert(0) += 1;
+    }
+}
+
+/// Fold the session's counters into the lifetime statistics for `day` and save them
+pub(crate) fn record_session_stats(state: &mut MonitoringState, start_time: Instant, day: chrono::NaiveDate) {
+    let counters = state.counters();
+    state.stats.record(day, &counters, &state.error_counts, start_time.elapsed().as_secs());
+    if let Err(e) = storage::save_lifetime_stats(state.stats.lifetime()) {
+        warn!("Failed to save lifetime statistics: {}", e);
+        *state.error_counts.entry("save_lifetime_stats".to_string()).or_insert(0) += 1;
+    }
+}
+
+/// Count a panic caught in `scope` as the `panic` error class
+pub fn record_panic(state: &mut MonitoringState, scope: &str, panic: &(dyn std::any::Any + Send)) {
+    let message = panic.downcast_ref::<&str>().map(|s| s.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    state.panics += 1;
+    *state.error_counts.entry("panic".to_string()).or_insert(0) += 1;
+    error!("💥 Panic in {} caught ({} this session): {}", scope, state.panics, message);
+}
+
+/// The session caught `MAX_PANICS` panics and must shut down
+pub fn panic_limit_reached(state: &MonitoringState, config: &Config) -> bool {
+    config.max_panics > 0 && state.panics >= config.max_panics
+}
+
+/// Refresh the report served by the status API
+pub(crate) async fn publish_status(
+    status_board: &api::StatusBoard,
+    state: &mut MonitoringState,
+    market_making_engine: &market_making::MarketMakingEngine,
+    breakers: &Arc<errors::CircuitBreakerRegistry>,
+    start_time: Instant,
+    paused: bool,
+) {
+    let volatility = market_making_engine.get_volatility_metrics().await;
+    state.activity.record_volatility(state.last_known_cex_price, &volatility);
+    status_board.publish_activity(state.activity.clone()).await;
+    
+    let health = utils::run_health_check(
+        &state.dex_last_update,
+        &state.cex_last_update,
+        breakers,
+        start_time,
+    ).await;
+    status_board.publish(StatusReport {
+        timestamp: chrono::Utc::now(),
+        paused,
+        is_leader: state.is_leader,
+        health,
+        volatility,
+        circuit_breakers: breakers.state().await,
+        session: session_summary(state, start_time, None),
+        monitoring: state.snapshot(),
+    }).await;
+}
+
+/// What processing one pool adds to the session. Pools run concurrently,
+/// so each task fills its own tally and the cycle merges them into the state.
+#[derive(Default)]
+pub struct PoolTally {
+    pub(crate) dex_price: Option<rust_decimal::Decimal>,
+    pub(crate) price_snapshot: Option<PoolPriceSnapshot>,
+    pub(crate) opportunities: u64,
+    pub(crate) profitable_opportunities: u64,
+    pub(crate) potential_profit: rust_decimal::Decimal,
+    pub(crate) deferred_opportunities: u64,
+    pub(crate) reevaluated_deferrals: u64,
+    pub(crate) market_making_signals: u64,
+    /// Profitable opportunity for the dashboard
+    pub(crate) opportunity: Option<ArbitrageOpportunity>,
+    /// Opportunities as this cycle left them; None when the spread was not judged
+    pub(crate) sightings: Option<Vec<arbitrage::OpportunitySighting>>,
+    pub(crate) error_counts: HashMap<String, u32>,
+}
+
+impl MonitoringState {
+    /// Add a pool's tally from this cycle
+    pub(crate) fn absorb(&mut self, pool: &str, tally: PoolTally) {
+        if let Some(price) = tally.dex_price {
+            self.last_dex_prices.insert(pool.to_string(), price);
+        }
+        if let Some(snapshot) = tally.price_snapshot {
+            self.activity.record_pool_price(pool, snapshot);
+        }
+        if let Some(opportunity) = &tally.opportunity {
+            self.activity.record_opportunity(opportunity);
+        }
+        self.total_opportunities += tally.opportunities;
+        self.profitable_opportunities += tally.profitable_opportunities;
+        self.total_potential_profit += tally.potential_profit;
+        self.deferred_opportunities += tally.deferred_opportunities;
+        self.reevaluated_deferrals += tally.reevaluated_deferrals;
+        self.total_market_making_signals += tally.market_making_signals;
+        for (kind, count) in tally.error_counts {
+            *self.error_counts.entry(kind).or_insert(0) += count;
+        }
+    }
+}
+
+/// Print statistics once `STATS_INTERVAL_SECS` passed since the last print
+/// (or the start), or when a count passed a multiple of its threshold since then
+pub fn should_print_statistics(state: &MonitoringState, config: &Config, start_time: Instant, now: Instant) -> bool {
+    let since = state.stats_printed_at.unwrap_or(start_time);
+    if config.stats_interval_secs > 0
+        && now.saturating_duration_since(since) >= std::time::Duration::from_secs(config.stats_interval_secs)
+    {
+        return true;
+    }
+    let crossed = |count: u64, printed: u64, every: u64| every > 0 && count / every > printed / every;
+    let printed = &state.stats_printed_counters;
+    crossed(state.total_opportunities, printed.total_opportunities, config.stats_every_opportunities)
+        || crossed(state.total_market_making_signals, printed.total_market_making_signals, config.stats_every_signals)
+        || crossed(state.total_executions, printed.total_executions, config.stats_every_executions)
+}
+
+/// Note that the statistics were printed at `now`
+pub fn mark_statistics_printed(state: &mut MonitoringState, now: Instant) {
+    state.stats_printed_at = Some(now);
+    state.stats_printed_counters = state.counters();
+}
+
+/// Value the latest tracked inventory, falling back to the last market-making signal
+pub(crate) async fn session_inventory_report(
+    portfolio: Option<&portfolio::PortfolioTracker>,
+    market_making_engine: &market_making::MarketMakingEngine,
+    state: &MonitoringState,
+    config: &Config,
+) -> Option<portfolio::InventoryReport> {
+    let tracked = match (portfolio, state.last_known_cex_price) {
+        (Some(portfolio), Some(price)) => portfolio.latest().await
+            .and_then(|snapshot| snapshot.inventory().cloned())
+            .map(|inventory| (inventory, price)),
+        _ => None,
+    };
+    let (source, end) = match tracked {
+        Some((inventory, price)) => (InventorySource::Portfolio, portfolio::InventoryMark {
+            weth: inventory.weth,
+            usd: inventory.usd,
+            price,
+        }),
+        None => {
+            let (analysis, price) = market_making_engine.latest_inventory().await?;
+            (analysis.source, portfolio::InventoryMark {
+                weth: analysis.current_weth_balance,
+                usd: analysis.current_usd_balance,
+                price,
+            })
+        }
+    };
+    
+    // Simulated balances have no real starting point to measure P&L against
+    let start = match source {
+        InventorySource::Portfolio => state.initial_inventory.clone(),
+        InventorySource::Simulated => None,
+    };
+    
+    Some(portfolio::value_inventory(
+        source,
+        start,
+        end,
+        config.inventory_target_ratio,
+        config.rebalance_threshold,
+    ))
+}
+
+/// Print final statistics on shutdown
+pub(crate) fn print_final_statistics(
+    start_time: Instant,
+    state: &MonitoringState,
+    inventory: Option<&portfolio::InventoryReport>,
+) {
+    info!("\n🛑 Shutting down gracefully...");
+    info!("Final statistics:");
+    info!("   Total runtime: {:?}", start_time.elapsed());
+    info!("   Arbitrage opportunities found: {}", state.total_opportunities);
+    info!("   Profitable arbitrage opportunities: {}", state.profitable_opportunities);
+    info!("   Total potential arbitrage profit: ${:.2}", state.total_potential_profit);
+    info!("   Market making signals generated: {}", state.total_market_making_signals);
+    info!("   Trade executions simulated: {}", state.total_executions);
+    info!("   Successful executions: {}", state.successful_executions);
+    info!("   Gas-spike deferrals: {} (re-evaluated: {}, expired: {})",
+        state.deferred_opportunities, state.reevaluated_deferrals, state.expired_deferrals);
+    info!("   Total errors: {:?}", state.error_counts);
+    
+    let lifetime = state.stats.lifetime();
+    if let Some(first_started_at) = lifetime.first_started_at {
+        let counters = &lifetime.counters;
+        info!("Lifetime ({} sessions since {}):", lifetime.sessions, first_started_at.format("%Y-%m-%d %H:%M UTC"));
+        info!("   Runtime: {:?}", std::time::Duration::from_secs(lifetime.runtime_secs));
+        info!("   Opportunities: {} ({} profitable, ${:.2} potential) | Market making signals: {}",
+            counters.total_opportunities, counters.profitable_opportunities,
+            counters.total_potential_profit_usd, counters.total_market_making_signals);
+        info!("   Executions: {} ({} successful) | Errors: {}",
+            counters.total_executions, counters.successful_executions, lifetime.error_counts.values().sum::<u64>());
+        if let Some(today) = state.stats.day(chrono::Utc::now().date_naive()) {
+            info!("   Today: {} sessions, {} opportunities, {} executions, ${:.2} potential",
+                today.sessions, today.counters.total_opportunities, today.counters.total_executions,
+                today.counters.total_potential_profit_usd);
+        }
+    }
+    
+    if let Some(paper) = state.paper_portfolio.as_ref().zip(state.last_known_cex_price).map(|(p, price)| p.report(price)) {
+        info!("Paper portfolio:");
+        info!("   Balances: {:.4} WETH + ${:.2} = ${:.2} (started at ${:.2})",
+            paper.weth, paper.usdc, paper.value_usd, paper.start_value_usd);
+        info!("   P&L: ${:.2} (realized ${:.2}, unrealized ${:.2}, fees ${:.2}, {} trades)",
+            paper.total_pnl_usd, paper.realized_pnl_usd, paper.unrealized_pnl_usd, paper.fees_usd, paper.trades);
+        info!("   Max drawdown: ${:.2} | Sharpe: {}",
+            paper.max_drawdown_usd, paper.sharpe_ratio.map_or("n/a".to_string(), |s| format!("{:.2}", s)));
+    }
+    
+    if let Some(quotes) = &state.quote_book {
+        info!("Simulated market-making quotes:");
+        info!("   Placed: {} | Cancelled: {} | Open: {}", quotes.placed, quotes.cancelled, quotes.open_quotes);
+        info!("   Fills: {} bids, {} asks, {:.4} WETH | Spread capture: ${:.2}",
+            quotes.bid_fills, quotes.ask_fills, quotes.filled_eth, quotes.spread_capture_usd);
+        info!("   Position: {:.4} WETH, ${:.2} cash | P&L: ${:.2}", quotes.position_eth, quotes.cash_usd, quotes.pnl_usd);
+    }
+    
+    if !state.stress_tests.is_empty() {
+        info!("Stress tests (last signal):");
+        for test in &state.stress_tests {
+            info!("   {:?}: ${:.2} ({:.2}%)", test.scenario, test.pnl_usd, test.pnl_pct);
+        }
+    }
+    
+    let Some(inventory) = inventory else {
+        info!("   Inventory: no balances tracked this session");
+        return;
+    };
+    info!("Inventory ({:?}):", inventory.source);
+    info!("   End balances: {:.4} WETH + ${:.2} @ ${:.2}",
+        inventory.end.weth, inventory.end.usd, inventory.end.price);
+    info!("   End value: ${:.2}", inventory.end_value_usd);
+    match (&inventory.start, inventory.unrealized_pnl_usd) {
+        (Some(start), Some(pnl)) => {
+            info!("   Start value: ${:.2} ({:.4} WETH + ${:.2} @ ${:.2})",
+                start.value_usd(), start.weth, start.usd, start.price);
+            info!("   Unrealized P&L: ${:.2} (ETH price move: ${:.2})",
+                pnl, inventory.price_move_pnl_usd.unwrap_or_default());
+        }
+        _ => info!("   Unrealized P&L: n/a (no starting balances)"),
+    }
+    info!("   WETH ratio: {:.1}% (target {:.1}%)",
+        inventory.weth_ratio * rust_decimal_macros::dec!(100),
+        inventory.target_weth_ratio * rust_decimal_macros::dec!(100));
+    match inventory.hedge_status {
+        portfolio::HedgeStatus::Hedged => info!("   Hedge status: hedged"),
+        portfolio::HedgeStatus::OverExposed => warn!("   Hedge status: over-exposed, sell {:.4} WETH to rebalance", inventory.hedge_amount_eth),
+        portfolio::HedgeStatus::UnderExposed => warn!("   Hedge status: under-exposed, buy {:.4} WETH to rebalance", inventory.hedge_amount_eth),
+    }
+}
@@ -0,0 +1,88 @@
+//! Subcommand parsing
+
+use anyhow::Result;
+
+pub const USAGE: &str = "\
+Usage: aero-arb-mm-bot [COMMAND] [OPTIONS]
+
+Commands:
+  run [--dry-run]      Start live monitoring (the default); --dry-run logs
+                       what would be saved or executed instead of doing it
+  validate-config      Check the config file and environment, then exit
+  pools list           Print the pools that would be monitored
+  backtest [OPTIONS]   Backtest recorded data in --data-dir (default output)
+  replay FILE [OPTIONS] Backtest one recorded JSONL file
+  race [OPTIONS]       Race two strategy configurations on recorded data
+  economics --pool NAME [OPTIONS]  Unit economics of a hypothetical trade
+  report [--date YYYY-MM-DD]       Summarize one day's outputs
+  candles [OPTIONS]    Build OHLC candles from recorded prices
+  heatmap [OPTIONS]    Aggregate recorded spreads by hour and pool
+  lifecycles [OPTIONS] Fill rate and profit decay of recorded opportunities
+  attribution [OPTIONS] Realized P&L by pool, direction, hour and volatility
+  export [OPTIONS]     Convert a day's JSONL outputs to CSV or Parquet
+  help                 Print this message";
+
+/// A subcommand and the option arguments that follow it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command<'a> {
+    Run { dry_run: bool },
+    Help,
+    ValidateConfig,
+    PoolsList,
+    Backtest(&'a [String]),
+    Replay { file: &'a str, args: &'a [String] },
+    Race(&'a [String]),
+    Economics(&'a [String]),
+    Heatmap(&'a [String]),
+    Lifecycles(&'a [String]),
+    Attribution(&'a [String]),
+    Export(&'a [String]),
+    Report(&'a [String]),
+    Candles(&'a [String]),
+}
+
+impl<'a> Command<'a> {
+    /// Parse the arguments after the program name; none runs live monitoring
+    pub fn parse(args: &'a [String]) -> Result<Self> {
+        let Some((name, rest)) = args.split_first() else {
+            return Ok(Command::Run { dry_run: false });
+        };
+        let no_options = |command: Command<'a>| {
+            if rest.is_empty() {
+                Ok(command)
+            } else {
+                Err(anyhow::anyhow!("{} takes no options, got {}", name, rest[0]))
+            }
+        };
+        match name.as_str() {
+            "run" | "--dry-run" => {
+                let flags = if name == "run" { rest } else { args };
+                match flags {
+                    [] => Ok(Command::Run { dry_run: false }),
+                    [flag] if flag == "--dry-run" => Ok(Command::Run { dry_run: true }),
+                    _ => Err(anyhow::anyhow!("Usage: run [--dry-run]")),
+                }
+            }
+            "help" | "--help" | "-h" => Ok(Command::Help),
+            "validate-config" => no_options(Command::ValidateConfig),
+            "pools" => match rest {
+                [action] if action == "list" => Ok(Command::PoolsList),
+                _ => Err(anyhow::anyhow!("Usage: pools list")),
+            },
+            "backtest" => Ok(Command::Backtest(rest)),
+            "replay" => match rest.split_first() {
+                Some((file, args)) if !file.starts_with("--") => Ok(Command::Replay { file: file.as_str(), args }),
+                _ => Err(anyhow::anyhow!("Usage: replay FILE [backtest options]")),
+            },
+            "race" => Ok(Command::Race(rest)),
+            "economics" => Ok(Command::Economics(rest)),
+            "heatmap" => Ok(Command::Heatmap(rest)),
+            "lifecycles" => Ok(Command::Lifecycles(rest)),
+            "attribution" => Ok(Command::Attribution(rest)),
+            "export" => Ok(Command::Export(rest)),
+            "report" => Ok(Command::Report(rest)),
+            "candles" => Ok(Command::Candles(rest)),
+            other => Err(anyhow::anyhow!("Unknown command: {}\n\n{}", other, USAGE)),
+        }
+    }
+}
@@ -0,0 +1,612 @@
+//! Offline and one-shot subcommands
+//!
+//! Each command takes the arguments after its name as `--flag value` pairs
+//! and runs to completion without starting live monitoring.
+
+use anyhow::Result;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{info, warn};
+use crate::{
+    backtest,
+    config::{self, Config, CONFIG},
+    execution,
+    network,
+    pools,
+    storage,
+    types::{PoolInfo, PoolKind, PriceBasis},
+    volatility,
+    ConcreteProvider,
+};
+use super::Command;
+
+/// Backtest options shared by `backtest` and `replay`
+struct BacktestOptions {
+    config: backtest::BacktestConfig,
+    data_dir: Option<String>,
+    compare_bases: bool,
+}
+
+fn parse_backtest_options(config: &Config, command: &str, args: &[String]) -> Result<BacktestOptions> {
+    let mut backtest_config = backtest::BacktestConfig::from_config(config);
+    let mut data_dir = None;
+    let mut compare_bases = false;
+    let mut threshold_given = false;
+
+    for pair in args.chunks(2) {
+        let [flag, value] = pair else {
+            return Err(anyhow::anyhow!("Missing value for {}", pair[0]));
+        };
+        match flag.as_str() {
+            "--data-dir" => data_dir = Some(value.clone()),
+            "--trade-size" => backtest_config.trade_size_eth = value.parse()?,
+            "--min-profit" => backtest_config.min_profit_usd = value.parse()?,
+            "--gas-cost" => backtest_config.gas_cost_usd = value.parse()?,
+            "--slippage-bps" => backtest_config.slippage_bps = value.parse()?,
+            "--spread-bps" => backtest_config.base_spread_bps = value.parse()?,
+            "--volatility-threshold" => {
+                backtest_config.volatility_threshold = value.parse()?;
+                threshold_given = true;
+            }
+            "--volatility-estimator" => backtest_config.volatility_estimator = value.parse().map_err(anyhow::Error::msg)?,
+            "--max-position" => backtest_config.max_position_size_eth = value.parse()?,
+            "--lp-capital" => backtest_config.lp_capital_usd = value.parse()?,
+            "--price-basis" if value == "all" => compare_bases = true,
+            "--price-basis" => backtest_config.price_basis = Some(value.parse().map_err(anyhow::Error::msg)?),
+            other => return Err(anyhow::anyhow!("Unknown {} option: {}", command, other)),
+        }
+    }
+    // Thresholds are on the estimator's scale
+    if !threshold_given && backtest_config.volatility_estimator != config.volatility_estimator {
+        backtest_config.volatility_threshold = backtest_config.volatility_estimator.default_threshold();
+    }
+
+    Ok(BacktestOptions { config: backtest_config, data_dir, compare_bases })
+}
+
+/// Replay recorded data offline: `backtest [--data-dir DIR] [--trade-size ETH]
+/// [--min-profit USD] [--gas-cost USD] [--slippage-bps N] [--spread-bps N]
+/// [--volatility-threshold PCT] [--max-position ETH] [--lp-capital USD]
+/// [--price-basis BASIS|all]`
+pub async fn run_backtest_command(config: &Config, args: &[String]) -> Result<()> {
+    let options = parse_backtest_options(config, "backtest", args)?;
+    let data_dir = options.data_dir.clone().unwrap_or_else(|| "output".to_string());
+
+    info!("🧪 Backtesting recorded data in {}", data_dir);
+    let points = backtest::load_price_points(&data_dir)?;
+    if points.is_empty() {
+        warn!("No recorded price points found in {}", data_dir);
+        return Ok(());
+    }
+    report_backtest(&points, &options).await
+}
+
+/// Backtest a single recorded JSONL file: `replay FILE [backtest options]`
+pub async fn run_replay_command(config: &Config, file: &str, args: &[String]) -> Result<()> {
+    let options = parse_backtest_options(config, "replay", args)?;
+    if options.data_dir.is_some() {
+        return Err(anyhow::anyhow!("replay reads FILE; use backtest --data-dir for a directory"));
+    }
+
+    info!("🔁 Replaying {}", file);
+    let points = backtest::load_price_points_from_file(Path::new(file))?;
+    if points.is_empty() {
+        warn!("No recorded price points found in {}", file);
+        return Ok(());
+    }
+    report_backtest(&points, &options).await
+}
+
+async fn report_backtest(points: &[backtest::PricePoint], options: &BacktestOptions) -> Result<()> {
+    if options.compare_bases {
+        return compare_price_bases(points, &options.config).await;
+    }
+
+    let report = backtest::run_backtest(points, &options.config).await;
+    info!("📊 Backtest Results:");
+    info!("   Period: {:?} → {:?}", report.start, report.end);
+    info!("   Arbitrage: {} trades, ${:.2}", report.arbitrage_trades, report.arbitrage_pnl_usd);
+    info!("   Market Making: {} fills, ${:.2}", report.mm_fills, report.mm_pnl_usd);
+    info!("   Total P&L: ${:.2} (max drawdown ${:.2})", report.total_pnl_usd, report.max_drawdown_usd);
+    info!("   LP vs arbitrage (${} LP capital):", options.config.lp_capital_usd);
+    for comparison in &report.lp_vs_arbitrage {
+        info!("      {}: LP ${:.2} ({:.3}%) vs arb ${:.2} ({:.3}%) → {:?}",
+              comparison.pool, comparison.lp_pnl_usd, comparison.lp_return_pct,
+              comparison.arbitrage_pnl_usd, comparison.arbitrage_return_pct, comparison.bias);
+    }
+
+    let filename = storage::save_backtest_report(&report)?;
+    info!("   Report: {}", filename);
+    Ok(())
+}
+
+/// Backtest every price basis on the observations that recorded all of them
+async fn compare_price_bases(points: &[backtest::PricePoint], base: &backtest::BacktestConfig) -> Result<()> {
+    let common = backtest::points_with_all_bases(points);
+    info!("📊 Price basis comparison on {} of {} points that recorded every basis:", common.len(), points.len());
+    if common.is_empty() {
+        warn!("No recorded points carry every basis; record with this version first");
+        return Ok(());
+    }
+
+    for basis in PriceBasis::ALL {
+        let config = backtest::BacktestConfig { price_basis: Some(basis), ..base.clone() };
+        let report = backtest::run_backtest(&common, &config).await;
+        let filename = storage::save_backtest_report(&report)?;
+        info!("   {:>14}: arb {} trades ${:.2}, MM {} fills ${:.2}, total ${:.2} (max drawdown ${:.2}) → {}",
+            basis.to_string(), report.arbitrage_trades, report.arbitrage_pnl_usd,
+            report.mm_fills, report.mm_pnl_usd, report.total_pnl_usd, report.max_drawdown_usd, filename);
+    }
+    Ok(())
+}
+
+/// Race two strategy configurations on recorded data: `race [--data-dir DIR]
+/// [--latency-a MS] [--latency-b MS] [--min-profit-a USD] [--min-profit-b USD]
+/// [--trade-size-a ETH] [--trade-size-b ETH] [--sweep MS,MS,...]`
+pub fn run_race_command(config: &Config, args: &[String]) -> Result<()> {
+    let base = backtest::BacktestConfig::from_config(config);
+    let mut a = backtest::RaceStrategy { name: "A".to_string(), config: base.clone(), latency_ms: 50 };
+    let mut b = backtest::RaceStrategy { name: "B".to_string(), config: base, latency_ms: 500 };
+    let mut sweep: Vec<u64> = vec![0, 50, 100, 250, 500, 1000, 2000, 5000];
+    let mut data_dir = "output".to_string();
+
+    for pair in args.chunks(2) {
+        let [flag, value] = pair else {
+            return Err(anyhow::anyhow!("Missing value for {}", pair[0]));
+        };
+        match flag.as_str() {
+            "--data-dir" => data_dir = value.clone(),
+            "--latency-a" => a.latency_ms = value.parse()?,
+            "--latency-b" => b.latency_ms = value.parse()?,
+            "--min-profit-a" => a.config.min_profit_usd = value.parse()?,
+            "--min-profit-b" => b.config.min_profit_usd = value.parse()?,
+            "--trade-size-a" => a.config.trade_size_eth = value.parse()?,
+            "--trade-size-b" => b.config.trade_size_eth = value.parse()?,
+            "--sweep" => {
+                sweep = value.split(',')
+                    .map(|ms| ms.trim().parse())
+                    .collect::<std::result::Result<_, _>>()?;
+            }
+            other => return Err(anyhow::anyhow!("Unknown race option: {}", other)),
+        }
+    }
+
+    let points = backtest::load_price_points(&data_dir)?;
+    if points.is_empty() {
+        warn!("No recorded price points found in {}", data_dir);
+        return Ok(());
+    }
+
+    let report = backtest::run_latency_race_report(&points, &a, &b, &sweep);
+    info!("🏁 Latency Race Results ({} contested opportunities):", report.contested_opportunities);
+    for result in &report.strategies {
+        info!("   {} @ {}ms: {} wins, {} lost races, {} missed, P&L ${:.2} (decision-time ${:.2})",
+            result.name, result.latency_ms, result.wins, result.lost_races,
+            result.missed_fills, result.pnl_usd, result.decision_pnl_usd);
+    }
+    for (name, points) in &report.sweeps {
+        info!("   Latency sweep for {}:", name);
+        for point in points {
+            info!("      {:>5}ms: {} trades, P&L ${:.2}, edge lost ${:.2} (${:.4}/ms)",
+                point.latency_ms, point.trades, point.pnl_usd,
+                point.edge_lost_usd, point.edge_cost_per_ms_usd);
+        }
+    }
+
+    let filename = storage::save_latency_race_report(&report)?;
+    info!("   Report: {}", filename);
+    Ok(())
+}
+
+/// Summarize one day's JSONL outputs: `report [--date YYYY-MM-DD] [--data-dir DIR]`.
+/// The date defaults to today (UTC).
+pub fn run_report_command(args: &[String]) -> Result<()> {
+    let mut date = chrono::Utc::now().date_naive();
+    let mut data_dir = "output".to_string();
+
+    for pair in args.chunks(2) {
+        let [flag, value] = pair else {
+            return Err(anyhow::anyhow!("Missing value for {}", pair[0]));
+        };
+        match flag.as_str() {
+            "--date" => date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .map_err(|e| anyhow::anyhow!("Invalid --date {}: {}", value, e))?,
+            "--data-dir" => data_dir = value.clone(),
+            other => return Err(anyhow::anyhow!("Unknown report option: {}", other)),
+        }
+    }
+
+    let report = storage::build_daily_report(Path::new(&data_dir), date)?;
+    info!("📅 Daily report for {}", report.date);
+    info!("   Opportunities: {} (potential ${:.2})", report.opportunities, report.potential_profit_usd);
+    info!("   MM signals: {}", report.market_making_signals);
+    if let Some(rate) = report.execution_success_rate_pct {
+        info!("   Executions: {} ({:.1}% successful), realized P&L ${:.2}",
+            report.executions, rate, report.realized_pnl_usd);
+    }
+    if let (Some(best), worst) = (&report.best_pool, &report.worst_pool) {
+        info!("   Best pool: {}{}", best, worst.as_ref().map(|w| format!(", worst: {}", w)).unwrap_or_default());
+    }
+
+    let (json_file, markdown_file) = storage::save_daily_report(&report)?;
+    info!("   Report: {} / {}", json_file, markdown_file);
+    Ok(())
+}
+
+/// Build OHLC candles from recorded prices: `candles [--interval 1m|5m|1h]
+/// [--data-dir DIR]`. Builds every interval unless one is given.
+pub fn run_candles_command(args: &[String]) -> Result<()> {
+    let mut intervals = storage::CandleInterval::ALL.to_vec();
+    let mut data_dir = storage::PRICES_DIR.to_string();
+
+    for pair in args.chunks(2) {
+        let [flag, value] = pair else {
+            return Err(anyhow::anyhow!("Missing value for {}", pair[0]));
+        };
+        match flag.as_str() {
+            "--interval" => intervals = vec![value.parse().map_err(anyhow::Error::msg)?],
+            "--data-dir" => data_dir = value.clone(),
+            other => return Err(anyhow::anyhow!("Unknown candles option: {}", other)),
+        }
+    }
+
+    let samples = storage::load_price_samples(Path::new(&data_dir))?;
+    if samples.is_empty() {
+        warn!("No recorded prices found in {}", data_dir);
+        return Ok(());
+    }
+    info!("🕯️  Building candles from {} prices ({:?} → {:?})",
+        samples.len(), samples.first().map(|s| s.timestamp), samples.last().map(|s| s.timestamp));
+
+    for interval in intervals {
+        let candles = storage::build_candles(&samples, interval);
+        let mut markets: BTreeMap<(storage::PriceSource, &str), Vec<storage::Candle>> = BTreeMap::new();
+        for candle in &candles {
+            markets.entry((candle.source, candle.market.as_str())).or_default().push(candle.clone());
+        }
+        for ((source, market), market_candles) in &markets {
+            let volatility = volatility::candle_volatility_percentage(market_candles)
+                .map_or("n/a".to_string(), |v| format!("{:.3}%", v));
+            info!("   {} {} {}: {} candles, volatility {}", interval, source, market, market_candles.len(), volatility);
+        }
+        let filename = storage::save_candles_in(Path::new(storage::CANDLES_DIR), interval, &candles)?;
+        info!("   Candles: {}", filename);
+    }
+    Ok(())
+}
+
+/// Aggregate recorded spread observations into hour-of-day × pool cells:
+/// `heatmap [--data-dir DIR]`
+pub fn run_heatmap_command(args: &[String]) -> Result<()> {
+    let mut data_dir = storage::SPREADS_DIR.to_string();
+
+    for pair in args.chunks(2) {
+        let [flag, value] = pair else {
+            return Err(anyhow::anyhow!("Missing value for {}", pair[0]));
+        };
+        match flag.as_str() {
+            "--data-dir" => data_dir = value.clone(),
+            other => return Err(anyhow::anyhow!("Unknown heatmap option: {}", other)),
+        }
+    }
+
+    let observations = storage::load_spread_observations(Path::new(&data_dir))?;
+    if observations.is_empty() {
+        warn!("No spread observations found in {}", data_dir);
+        return Ok(());
+    }
+
+    let heatmap = storage::build_spread_heatmap(&observations);
+    info!("🗺️  Spread heatmap: {} observations, {:?} → {:?}", heatmap.observations, heatmap.start, heatmap.end);
+    let bps = |value: Option<Decimal>| value.map_or("n/a".to_string(), |v| format!("{:.1}", v));
+    for cell in &heatmap.cells {
+        info!("   {} {:02}h: {} samples, |spread| {:.1} bps (max {:.1}), edge {} bps, {}% positive",
+            cell.pool, cell.hour, cell.samples, cell.mean_abs_spread_bps, cell.max_abs_spread_bps,
+            bps(cell.mean_edge_bps), bps(cell.positive_edge_pct));
+    }
+
+    let filename = storage::save_spread_heatmap(&heatmap)?;
+    info!("   Report: {}", filename);
+    Ok(())
+}
+
+/// Summarize recorded opportunity lifecycles, overall and per pool:
+/// `lifecycles [--data-dir DIR]`
+pub fn run_lifecycles_command(args: &[String]) -> Result<()> {
+    let mut data_dir = storage::LIFECYCLES_DIR.to_string();
+
+    for pair in args.chunks(2) {
+        let [flag, value] = pair else {
+            return Err(anyhow::anyhow!("Missing value for {}", pair[0]));
+        };
+        match flag.as_str() {
+            "--data-dir" => data_dir = value.clone(),
+            other => return Err(anyhow::anyhow!("Unknown lifecycles option: {}", other)),
+        }
+    }
+
+    let lifecycles = storage::load_opportunity_lifecycles(Path::new(&data_dir))?;
+    if lifecycles.is_empty() {
+        warn!("No opportunity lifecycles found in {}", data_dir);
+        return Ok(());
+    }
+
+    let report = storage::build_lifecycle_report(&lifecycles);
+    info!("⏳ Opportunity lifecycles: {} recorded, {:?} → {:?}", report.overall.lifecycles, report.start, report.end);
+    let optional = |value: Option<Decimal>, dp: u32| value.map_or("n/a".to_string(), |v| v.round_dp(dp).to_string());
+    let summaries = std::iter::once(("all pools", &report.overall))
+        .chain(report.pools.iter().map(|(pool, summary)| (pool.as_str(), summary)));
+    for (name, summary) in summaries {
+        info!("   {}: {} detected, {} validated, {} executed ({} still profitable), {} settled; fill rate {}%, persisted {}ms, profit decay ${}",
+            name, summary.lifecycles, summary.validated, summary.executed, summary.profitable_at_execution,
+            summary.settled, optional(summary.fill_rate_pct, 1), optional(summary.mean_persisted_ms, 0),
+            optional(summary.mean_profit_decay_usd, 4));
+    }
+
+    let filename = storage::save_lifecycle_report(&report)?;
+    info!("   Report: {}", filename);
+    Ok(())
+}
+
+/// Break stored executions' P&L down by pool, direction, hour and volatility
+/// regime: `attribution [--since YYYY-MM-DD] [--data-dir DIR]`
+pub fn run_attribution_command(args: &[String]) -> Result<()> {
+    let mut since = None;
+    let mut data_dir = "output".to_string();
+
+    for pair in args.chunks(2) {
+        let [flag, value] = pair else {
+            return Err(anyhow::anyhow!("Missing value for {}", pair[0]));
+        };
+        match flag.as_str() {
+            "--since" => since = Some(chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .map_err(|e| anyhow::anyhow!("Invalid --since {}: {}", value, e))?),
+            "--data-dir" => data_dir = value.clone(),
+            other => return Err(anyhow::anyhow!("Unknown attribution option: {}", other)),
+        }
+    }
+
+    let report = storage::build_profit_attribution(Path::new(&data_dir), since)?;
+    if report.executions == 0 {
+        warn!("No executions found in {}/executions", data_dir);
+        return Ok(());
+    }
+
+    info!("💰 Profit attribution: {} executions, realized ${:.2}, {:?} → {:?}",
+        report.executions, report.realized_pnl_usd, report.start, report.end);
+    let dimensions = [
+        ("Pool", &report.by_pool),
+        ("Direction", &report.by_direction),
+        ("Hour (UTC)", &report.by_hour),
+        ("Volatility", &report.by_volatility),
+    ];
+    for (dimension, buckets) in dimensions {
+        info!("   {}:", dimension);
+        for bucket in buckets {
+            info!("      {}: {} executions, realized ${:.2} (mean ${:.4}, {:.1}% winning)",
+                bucket.key, bucket.executions, bucket.realized_pnl_usd, bucket.mean_pnl_usd, bucket.win_rate_pct);
+        }
+    }
+    if report.unattributed_executions > 0 {
+        warn!("   {} executions had no recorded opportunity and count under pool unknown", report.unattributed_executions);
+    }
+
+    let filename = storage::save_profit_attribution(&report)?;
+    info!("   Report: {}", filename);
+    Ok(())
+}
+
+/// Convert a day's opportunities, signals and executions for pandas/DuckDB:
+/// `export [--format csv|parquet] [--date YYYY-MM-DD] [--dataset NAME]
+/// [--data-dir DIR] [--out-dir DIR]`. Exports every dataset unless one is given.
+pub fn run_export_command(args: &[String]) -> Result<()> {
+    let mut format = storage::ExportFormat::Csv;
+    let mut date = chrono::Utc::now().date_naive();
+    let mut datasets = storage::ExportDataset::ALL.to_vec();
+    let mut data_dir = "output".to_string();
+    let mut out_dir = storage::EXPORTS_DIR.to_string();
+
+    for pair in args.chunks(2) {
+        let [flag, value] = pair else {
+            return Err(anyhow::anyhow!("Missing value for {}", pair[0]));
+        };
+        match flag.as_str() {
+            "--format" => format = value.parse().map_err(|e| anyhow::anyhow!("Invalid --format: {}", e))?,
+            "--date" => date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .map_err(|e| anyhow::anyhow!("Invalid --date {}: {}", value, e))?,
+            "--dataset" => datasets = vec![value.parse().map_err(|e| anyhow::anyhow!("Invalid --dataset: {}", e))?],
+            "--data-dir" => data_dir = value.clone(),
+            "--out-dir" => out_dir = value.clone(),
+            other => return Err(anyhow::anyhow!("Unknown export option: {}", other)),
+        }
+    }
+
+    info!("📤 Exporting {} outputs as {}", date, format.extension());
+    for dataset in datasets {
+        match storage::export_dataset(dataset, format, Path::new(&data_dir), date, Path::new(&out_dir))? {
+            Some(export) => {
+                info!("   {}: {} rows → {}", dataset, export.rows, export.path.display());
+                if export.unreadable_lines > 0 {
+                    warn!("   {}: skipped {} unreadable lines", dataset, export.unreadable_lines);
+                }
+            }
+            None => info!("   {}: no records for {}", dataset, date),
+        }
+    }
+    Ok(())
+}
+
+/// Print the unit economics of a hypothetical trade: `economics --pool NAME
+/// [--size ETH] [--cex-price USD] [--eth-price USD] [--gas-gwei N]`.
+/// Market inputs default to live Binance prices and the current Base gas fee.
+pub async fn run_economics_command(config: &Config, args: &[String]) -> Result<()> {
+    let mut pool_name = None;
+    let mut trade_size = config.trade_size_eth;
+    let mut cex_price = None;
+    let mut eth_price = None;
+    let mut gas_gwei: Option<Decimal> = None;
+
+    for pair in args.chunks(2) {
+        let [flag, value] = pair else {
+            return Err(anyhow::anyhow!("Missing value for {}", pair[0]));
+        };
+        match flag.as_str() {
+            "--pool" => pool_name = Some(value.clone()),
+            "--size" => trade_size = value.parse()?,
+            "--cex-price" => cex_price = Some(value.parse()?),
+            "--eth-price" => eth_price = Some(value.parse()?),
+            "--gas-gwei" => gas_gwei = Some(value.parse()?),
+            other => return Err(anyhow::anyhow!("Unknown economics option: {}", other)),
+        }
+    }
+    let pool_name = pool_name.ok_or_else(|| anyhow::anyhow!("economics requires --pool NAME"))?;
+
+    let provider = network::setup_mainnet_provider(config).await?;
+    let pool = load_pools(&provider, config).await.into_iter()
+        .find(|pool| pool.name == pool_name)
+        .ok_or_else(|| anyhow::anyhow!("Unknown pool {}", pool_name))?;
+
+    let price_source = network::cex_price_source(config)?;
+    let eth_usd = match eth_price {
+        Some(price) => price,
+        None => price_source.price(network::ETH_USD_CEX_SYMBOL).await?,
+    };
+    let cex_price = match cex_price {
+        Some(price) => price,
+        None if pool.pair.cex_symbol == network::ETH_USD_CEX_SYMBOL => eth_usd,
+        None => {
+            let quote_usd = pool.pair.cex_quote_usd(eth_usd)
+                .ok_or_else(|| anyhow::anyhow!("No USD reference for {}", pool.pair.cex_symbol))?;
+            price_source.price(&pool.pair.cex_symbol).await? * quote_usd
+        }
+    };
+    let gas = match gas_gwei {
+        Some(gwei) => network::GasEstimate::from_gwei(gwei),
+        None => network::GasOracle::new().refresh(provider.as_ref()).await?,
+    };
+
+    let stable_oracle = network::StablePriceOracle::from_config(config);
+    stable_oracle.refresh(provider.as_ref()).await;
+    let stable_prices = stable_oracle.prices().await;
+
+    let inputs = execution::EconomicsInputs { trade_size, cex_price, eth_usd, stable_prices, gas };
+    let economics = execution::evaluate_unit_economics(&provider, &pool, &inputs, config).await?;
+    let usd = |value: Option<Decimal>| value.map_or("n/a".to_string(), |v| format!("${:.4}", v));
+
+    info!("🧮 Unit economics: {} {} on {} (${:.2} notional)",
+        economics.trade_size, pool.pair.base_symbol, economics.pool, economics.notional_usd);
+    info!("   Direction: {}", economics.direction);
+    info!("   DEX mid: ${:.4} | CEX: ${:.4} | Spread: {:.1} bps",
+        economics.dex_mid_price, economics.cex_price, economics.spread_bps);
+    info!("   Execution price: {}", usd(economics.execution_price));
+    info!("   Pool fee: ${:.4} ({}%)", economics.pool_fee_usd, economics.pool_fee_rate * dec!(100));
+    info!("   Price impact: {}", usd(economics.price_impact_usd));
+    info!("   L2 gas: ${:.4} ({:.4} gwei)", economics.l2_gas_usd, economics.gas_price_gwei);
+    info!("   L1 data fee: {}", usd(economics.l1_fee_usd));
+    info!("   CEX {} fee: ${:.4}", if config.binance_maker_orders { "maker" } else { "taker" }, economics.cex_fee_usd);
+    info!("   Transfer cost: ${:.4}", economics.transfer_cost_usd);
+    info!("   Slippage allowance: ${:.4} ({} bps)", economics.slippage_allowance_usd, config.slippage_tolerance_bps);
+    info!("   Gross profit: ${:.4}", economics.gross_profit_usd);
+    info!("   Net profit (as detected): ${:.4}", economics.net_profit_usd);
+    info!("   Net profit (all-in): ${:.4}", economics.net_profit_all_in_usd);
+    info!("   Breakeven spread: {:.1} bps (with ${} min profit: {:.1} bps)",
+        economics.breakeven_spread_bps, config.min_profit_usd, economics.required_spread_bps);
+    if economics.blockers.is_empty() {
+        info!("   ✅ The bot would take this trade");
+    } else {
+        for blocker in &economics.blockers {
+            info!("   ❌ {}", blocker);
+        }
+    }
+    Ok(())
+}
+
+/// Static pools, validated (from cache when available), merged with
+/// discovered pools when discovery is enabled
+async fn load_pools(provider: &Arc<ConcreteProvider>, config: &Config) -> Vec<PoolInfo> {
+    let mut pools = match pools::initialize_pools_with_cache(provider, config).await {
+        Ok(pools) => pools,
+        Err(e) => {
+            warn!("Static pool validation failed: {}", e);
+            Vec::new()
+        }
+    };
+    if let Some(mut discovery) = pools::PoolDiscovery::from_config(config) {
+        if let Err(e) = discovery.refresh(provider, config).await {
+            warn!("Pool discovery failed: {}", e);
+        }
+        pools = pools::merge_pools(&pools, discovery.pools());
+    }
+    pools
+}
+
+/// Checks that need more than one setting, beyond what parsing validates
+pub fn check_config(config: &Config) -> Result<()> {
+    if config.trade_size_eth < config::MIN_TRADE_SIZE_ETH ||
+       config.trade_size_eth > config::MAX_TRADE_SIZE_ETH {
+        return Err(anyhow::anyhow!("Trade size out of bounds: {} ETH", config.trade_size_eth));
+    }
+    Ok(())
+}
+
+/// Load the config file and environment and report whether they are valid:
+/// `validate-config`
+pub fn run_validate_config_command() -> Result<Config> {
+    let config = Config::try_load().map_err(|e| anyhow::anyhow!("Invalid configuration: {}", e))?;
+    check_config(&config)?;
+
+    info!("✅ Configuration is valid");
+    if let Some(path) = &config.config_file {
+        info!("   Config File: {}", path);
+    }
+    info!("   Network: {}{}", config.network, if config.watch_only { " (watch-only)" } else { "" });
+    info!("   Pools: {}", config.enabled_pools.as_ref().map_or("all".to_string(), |pools| pools.join(", ")));
+    info!("   Trade Size: {} ETH, Min Profit: ${}", config.trade_size_eth, config.min_profit_usd);
+    info!("   Market Making: {}, Trade Execution: {} ({})",
+        config.enable_market_making, config.enable_trade_execution, config.execution_network);
+    Ok(config)
+}
+
+/// Print the pools the bot would monitor: `pools list`
+pub async fn run_pools_list_command(config: &Config) -> Result<()> {
+    let provider = network::setup_mainnet_provider(config).await?;
+    let pools = load_pools(&provider, config).await;
+    info!("🏊 {} pools on {}:", pools.len(), config.network);
+    for pool in &pools {
+        let kind = match pool.kind {
+            PoolKind::Basic if pool.is_stable => "sAMM".to_string(),
+            PoolKind::Basic => "vAMM".to_string(),
+            PoolKind::Concentrated { tick_spacing } => format!("CL{}", tick_spacing),
+        };
+        info!("   {} ({}) {} {} → {}", pool.name, kind, pool.address, pool.pair, pool.pair.cex_symbol);
+    }
+    Ok(())
+}
+
+/// Run a parsed subcommand other than `run`. Commands that need settings
+/// load them here, so `help` and `validate-config` work with a broken config.
+pub async fn run_command(command: Command<'_>) -> Result<()> {
+    let config = || CONFIG.init().map_err(|e| anyhow::anyhow!("Invalid configuration: {}", e));
+    match command {
+        Command::Run { .. } => Err(anyhow::anyhow!("run starts live monitoring")),
+        Command::Help => {
+            println!("{}", super::USAGE);
+            Ok(())
+        }
+        Command::ValidateConfig => run_validate_config_command().map(|_| ()),
+        Command::PoolsList => run_pools_list_command(&config()?).await,
+        Command::Backtest(args) => run_backtest_command(&config()?, args).await,
+        Command::Replay { file, args } => run_replay_command(&config()?, file, args).await,
+        Command::Race(args) => run_race_command(&config()?, args),
+        Command::Economics(args) => run_economics_command(&config()?, args).await,
+        Command::Heatmap(args) => run_heatmap_command(args),
+        Command::Lifecycles(args) => run_lifecycles_command(args),
+        Command::Attribution(args) => run_attribution_command(args),
+        Command::Export(args) => run_export_command(args),
+        Command::Report(args) => run_report_command(args),
+        Command::Candles(args) => run_candles_command(args),
+    }
+}
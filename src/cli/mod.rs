@@ -0,0 +1,7 @@
+//! Command-line subcommands
+
+pub mod args;
+pub mod commands;
+
+pub use args::*;
+pub use commands::*;
@@ -0,0 +1,337 @@
+//! Config file (TOML/YAML) loading with environment variable overrides
+//!
+//! Each file field maps to the environment variable that configures it, so
+//! `[strategies.arbitrage] trade_size_eth = 0.2` in `bot.toml` is equivalent
+//! to `TRADE_SIZE_ETH=0.2`. Environment variables always win over the file.
+
+use std::collections::HashMap;
+use std::env;
+use std::path::Path;
+use std::str::FromStr;
+use serde_json::Value;
+use thiserror::Error;
+
+/// Files searched in the working directory when `CONFIG_FILE` is unset
+pub const DEFAULT_CONFIG_FILES: &[&str] = &["bot.toml", "bot.yaml", "bot.yml"];
+
+/// Config file fields and the environment variables they set.
+/// Secrets such as `PRIVATE_KEY` are deliberately env-only.
+pub const CONFIG_FILE_FIELDS: &[(&str, &str)] = &[
+    // Monitoring
+    ("monitoring.poll_interval_secs", "POLL_INTERVAL_SECS"),
+    ("monitoring.adaptive_polling", "ADAPTIVE_POLLING"),
+    ("monitoring.min_poll_interval_ms", "MIN_POLL_INTERVAL_MS"),
+    ("monitoring.max_poll_interval_ms", "MAX_POLL_INTERVAL_MS"),
+    ("monitoring.max_concurrent_pools", "MAX_CONCURRENT_POOLS"),
+    ("monitoring.trigger", "MONITOR_TRIGGER"),
+    ("monitoring.ws_rpc_url", "WS_RPC_URL"),
+    ("monitoring.api_bind_addr", "API_BIND_ADDR"),
+    ("monitoring.watch_only", "WATCH_ONLY"),
+    ("monitoring.dry_run", "DRY_RUN"),
+    ("monitoring.simulation_seed", "SIMULATION_SEED"),
+    ("monitoring.simulation_mode", "SIMULATION_MODE"),
+    ("monitoring.enable_safety_checks", "ENABLE_SAFETY_CHECKS"),
+    ("monitoring.record_decisions", "RECORD_DECISIONS"),
+    ("monitoring.record_spreads", "RECORD_SPREADS"),
+    ("monitoring.record_prices", "RECORD_PRICES"),
+    ("monitoring.sqlite_path", "SQLITE_PATH"),
+    ("monitoring.checkpoint_interval_secs", "CHECKPOINT_INTERVAL_SECS"),
+    ("monitoring.state_save_interval_secs", "STATE_SAVE_INTERVAL_SECS"),
+    ("monitoring.health_check_interval_secs", "HEALTH_CHECK_INTERVAL_SECS"),
+    ("monitoring.stats_interval_secs", "STATS_INTERVAL_SECS"),
+    ("monitoring.stats_every_opportunities", "STATS_EVERY_OPPORTUNITIES"),
+    ("monitoring.stats_every_signals", "STATS_EVERY_SIGNALS"),
+    ("monitoring.stats_every_executions", "STATS_EVERY_EXECUTIONS"),
+    ("monitoring.history_memory_samples", "HISTORY_MEMORY_SAMPLES"),
+    ("monitoring.history_retention_days", "HISTORY_RETENTION_DAYS"),
+    ("monitoring.output_compress_after_days", "OUTPUT_COMPRESS_AFTER_DAYS"),
+    ("monitoring.output_retention_days", "OUTPUT_RETENTION_DAYS"),
+    ("monitoring.output_max_size_mb", "OUTPUT_MAX_SIZE_MB"),
+    // Circuit breakers
+    ("circuit_breakers.cex.max_errors", "CEX_BREAKER_MAX_ERRORS"),
+    ("circuit_breakers.cex.cooldown_secs", "CEX_BREAKER_COOLDOWN_SECS"),
+    ("circuit_breakers.rpc.max_errors", "RPC_BREAKER_MAX_ERRORS"),
+    ("circuit_breakers.rpc.cooldown_secs", "RPC_BREAKER_COOLDOWN_SECS"),
+    ("circuit_breakers.pool.max_errors", "POOL_BREAKER_MAX_ERRORS"),
+    ("circuit_breakers.pool.cooldown_secs", "POOL_BREAKER_COOLDOWN_SECS"),
+    // Error recovery
+    ("recovery.strategies", "RECOVERY_STRATEGIES"),
+    ("recovery.error_decay_secs", "RECOVERY_ERROR_DECAY_SECS"),
+    ("recovery.max_panics", "MAX_PANICS"),
+    // Paper trading
+    ("paper_trading.enabled", "PAPER_TRADING"),
+    ("paper_trading.starting_weth", "PAPER_STARTING_WETH"),
+    ("paper_trading.starting_usdc", "PAPER_STARTING_USDC"),
+    // Pools
+    ("pools.enabled", "POOLS"),
+    ("pools.custom", "CUSTOM_POOLS"),
+    ("pools.cex_symbols", "CEX_SYMBOLS"),
+    ("pools.discovery.enabled", "POOL_DISCOVERY"),
+    ("pools.discovery.min_tvl_usd", "POOL_DISCOVERY_MIN_TVL_USD"),
+    ("pools.discovery.interval_secs", "POOL_DISCOVERY_INTERVAL_SECS"),
+    ("pools.discovery.stables", "DISCOVERY_STABLES"),
+    // Strategies
+    ("strategies.arbitrage.trade_size_eth", "TRADE_SIZE_ETH"),
+    ("strategies.arbitrage.min_profit_usd", "MIN_PROFIT_USD"),
+    ("strategies.arbitrage.min_price_diff_pct", "MIN_PRICE_DIFF_PCT"),
+    ("strategies.arbitrage.auto_min_price_diff", "AUTO_MIN_PRICE_DIFF"),
+    ("strategies.price_basis", "PRICE_BASIS"),
+    ("strategies.twap_window_secs", "TWAP_WINDOW_SECS"),
+    ("strategies.max_twap_deviation_pct", "MAX_TWAP_DEVIATION_PCT"),
+    ("strategies.max_price_skew_ms", "MAX_PRICE_SKEW_MS"),
+    ("strategies.arbitrage.basis_z_threshold", "BASIS_Z_THRESHOLD"),
+    ("strategies.arbitrage.basis_window_secs", "BASIS_WINDOW_SECS"),
+    ("strategies.market_making.enabled", "ENABLE_MARKET_MAKING"),
+    ("strategies.market_making.base_spread_bps", "BASE_SPREAD_BPS"),
+    ("strategies.market_making.max_position_size_eth", "MAX_POSITION_SIZE_ETH"),
+    ("strategies.market_making.inventory_target_ratio", "INVENTORY_TARGET_RATIO"),
+    ("strategies.market_making.rebalance_threshold", "REBALANCE_THRESHOLD"),
+    ("strategies.market_making.gauge_yield", "ENABLE_GAUGE_YIELD"),
+    ("strategies.market_making.epoch_flip_window_secs", "EPOCH_FLIP_WINDOW_SECS"),
+    // Gas
+    ("gas.deferral_enabled", "ENABLE_GAS_DEFERRAL"),
+    ("gas.spike_multiplier", "GAS_SPIKE_MULTIPLIER"),
+    ("gas.deferral_ttl_secs", "GAS_DEFERRAL_TTL_SECS"),
+    // Oracle cross-check
+    ("oracle.enabled", "ORACLE_CHECK"),
+    ("oracle.feed_address", "ORACLE_FEED_ADDRESS"),
+    ("oracle.max_deviation_pct", "MAX_ORACLE_DEVIATION_PCT"),
+    ("oracle.max_age_secs", "ORACLE_MAX_AGE_SECS"),
+    ("oracle.stable_prices", "STABLE_PRICES"),
+    // Fees
+    ("fees.binance_maker_bps", "BINANCE_MAKER_FEE_BPS"),
+    ("fees.binance_taker_bps", "BINANCE_TAKER_FEE_BPS"),
+    ("fees.binance_maker_orders", "BINANCE_MAKER_ORDERS"),
+    ("fees.dex_bps", "DEX_FEE_BPS"),
+    ("fees.transfer_cost_usd", "TRANSFER_COST_USD"),
+    // Volatility
+    ("volatility.threshold", "VOLATILITY_THRESHOLD"),
+    ("volatility.spread_multiplier", "VOLATILITY_SPREAD_MULTIPLIER"),
+    ("volatility.estimator", "VOLATILITY_ESTIMATOR"),
+    ("volatility.ewma_lambda", "EWMA_LAMBDA"),
+    // Networks
+    ("networks.network", "NETWORK"),
+    ("networks.alchemy_api_key", "ALCHEMY_API_KEY"),
+    ("networks.rpc_requests_per_sec", "RPC_REQUESTS_PER_SEC"),
+    ("networks.binance_requests_per_sec", "BINANCE_REQUESTS_PER_SEC"),
+    ("networks.cex_source", "CEX_SOURCE"),
+    ("networks.cex_fixture_path", "CEX_FIXTURE_PATH"),
+    ("networks.cex_depth_levels", "CEX_DEPTH_LEVELS"),
+    ("networks.reserve_cache_ttl_ms", "RESERVE_CACHE_TTL_MS"),
+    ("networks.execution.enabled", "ENABLE_TRADE_EXECUTION"),
+    ("networks.execution.network", "EXECUTION_NETWORK"),
+    ("networks.execution.max_gas_price_gwei", "MAX_GAS_PRICE_GWEI"),
+    ("networks.execution.slippage_tolerance_bps", "SLIPPAGE_TOLERANCE_BPS"),
+    ("networks.execution.confirmation_tiers", "CONFIRMATION_TIERS"),
+    ("networks.execution.submission_mode", "SUBMISSION_MODE"),
+    ("networks.execution.private_rpc_url", "PRIVATE_RPC_URL"),
+    ("networks.execution.flash_loan_source", "FLASH_LOAN_SOURCE"),
+    ("networks.execution.flash_loan_receiver", "FLASH_LOAN_RECEIVER"),
+    ("networks.execution.infinite_approvals", "INFINITE_APPROVALS"),
+    ("networks.execution.wallet_address", "WALLET_ADDRESS"),
+    ("networks.execution.i_understand_real_funds", "I_UNDERSTAND_REAL_FUNDS"),
+    ("networks.execution.max_trade_notional_usd", "MAX_TRADE_NOTIONAL_USD"),
+    ("networks.execution.max_daily_loss_usd", "MAX_DAILY_LOSS_USD"),
+    ("networks.execution.max_open_exposure_eth", "MAX_OPEN_EXPOSURE_ETH"),
+    ("networks.execution.max_executions_per_hour", "MAX_EXECUTIONS_PER_HOUR"),
+    ("networks.execution.token_safety_checks", "TOKEN_SAFETY_CHECKS"),
+    ("networks.execution.anvil_path", "ANVIL_PATH"),
+    ("networks.execution.anvil_fork_url", "ANVIL_FORK_URL"),
+    ("networks.execution.signer_max_gas_limit", "SIGNER_MAX_GAS_LIMIT"),
+    ("networks.execution.signer_max_value_eth", "SIGNER_MAX_VALUE_ETH"),
+    ("networks.execution.signer_allowed_calls", "SIGNER_ALLOWED_CALLS"),
+    ("networks.execution.max_concurrent_executions", "MAX_CONCURRENT_EXECUTIONS"),
+    ("networks.execution.queue_max_age_secs", "EXECUTION_QUEUE_MAX_AGE_SECS"),
+    // Integrations
+    ("binance_testnet.api_key", "BINANCE_TESTNET_API_KEY"),
+    ("binance_testnet.api_secret", "BINANCE_TESTNET_API_SECRET"),
+    ("binance_testnet.symbol", "BINANCE_TESTNET_SYMBOL"),
+    ("reconciler.url", "RECONCILER_URL"),
+    ("reconciler.api_key", "RECONCILER_API_KEY"),
+    ("leader.instance_id", "INSTANCE_ID"),
+    ("leader.lease_path", "LEADER_LEASE_PATH"),
+    ("leader.lease_secs", "LEADER_LEASE_SECS"),
+];
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to read config file {path}: {source}")]
+    Read {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Failed to parse config file {path}: {message}")]
+    Parse {
+        path: String,
+        message: String,
+    },
+
+    #[error("Unknown config field `{field}` in {path}")]
+    UnknownField {
+        path: String,
+        field: String,
+    },
+
+    #[error("Invalid value for {field} ({origin}): {value:?} is not {expected}")]
+    InvalidValue {
+        field: String,
+        origin: String,
+        value: String,
+        expected: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ConfigFormat {
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// Layered config values: environment variables over an optional config file
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSource {
+    /// Env var name -> (value, file field it came from)
+    file_values: HashMap<&'static str, (String, String)>,
+    file_path: Option<String>,
+    use_env: bool,
+}
+
+impl ConfigSource {
+    /// Load `CONFIG_FILE`, or the first of `DEFAULT_CONFIG_FILES` that exists
+    pub fn load() -> Result<Self, ConfigError> {
+        let path = env::var("CONFIG_FILE").ok().or_else(|| {
+            DEFAULT_CONFIG_FILES.iter()
+                .find(|p| Path::new(p).exists())
+                .map(|p| p.to_string())
+        });
+
+        let mut source = match path {
+            Some(path) => Self::from_file(&path)?,
+            None => Self::default(),
+        };
+        source.use_env = true;
+        Ok(source)
+    }
+
+    /// Read a config file without environment overrides
+    pub fn from_file(path: &str) -> Result<Self, ConfigError> {
+        let format = ConfigFormat::from_path(Path::new(path)).ok_or_else(|| ConfigError::Parse {
+            path: path.to_string(),
+            message: "expected a .toml, .yaml or .yml file".to_string(),
+        })?;
+        let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_string(),
+            source,
+        })?;
+        Self::from_contents(path, &contents, format)
+    }
+
+    /// Parse config file contents without environment overrides
+    pub fn from_contents(path: &str, contents: &str, format: ConfigFormat) -> Result<Self, ConfigError> {
+        let parse_error = |message: String| ConfigError::Parse { path: path.to_string(), message };
+        let root: Value = match format {
+            ConfigFormat::Toml => toml::from_str(contents).map_err(|e| parse_error(e.to_string()))?,
+            ConfigFormat::Yaml => serde_yaml::from_str(contents).map_err(|e| parse_error(e.to_string()))?,
+        };
+
+        let mut flat = Vec::new();
+        flatten(&root, String::new(), &mut flat);
+
+        let mut file_values = HashMap::new();
+        for (field, value) in flat {
+            let var = CONFIG_FILE_FIELDS.iter()
+                .find(|(name, _)| *name == field)
+                .map(|(_, var)| *var)
+                .ok_or_else(|| ConfigError::UnknownField { path: path.to_string(), field: field.clone() })?;
+            file_values.insert(var, (value, field));
+        }
+
+        Ok(Self {
+            file_values,
+            file_path: Some(path.to_string()),
+            use_env: false,
+        })
+    }
+
+    pub fn file_path(&self) -> Option<&str> {
+        self.file_path.as_deref()
+    }
+
+    /// Raw value for `var` and where it came from
+    fn lookup(&self, var: &str) -> Option<(String, String)> {
+        if self.use_env {
+            if let Ok(value) = env::var(var) {
+                return Some((value, format!("env {}", var)));
+            }
+        }
+        self.file_values.get(var).map(|(value, field)| {
+            (value.clone(), format!("{} in {}", field, self.file_path.as_deref().unwrap_or("config file")))
+        })
+    }
+
+    pub fn string(&self, var: &str) -> Option<String> {
+        self.lookup(var).map(|(value, _)| value)
+    }
+
+    /// Parse `var`, failing with the field name instead of falling back to a default
+    pub fn parse<T: FromStr>(&self, var: &str, expected: &str) -> Result<Option<T>, ConfigError> {
+        self.lookup(var)
+            .map(|(value, origin)| {
+                value.trim().parse().map_err(|_| ConfigError::InvalidValue {
+                    field: var.to_string(),
+                    origin,
+                    value,
+                    expected: expected.to_string(),
+                })
+            })
+            .transpose()
+    }
+
+    /// Reject a value that parsed but is out of range
+    pub fn invalid(&self, var: &str, expected: &str) -> ConfigError {
+        let (value, origin) = self.lookup(var).unwrap_or_default();
+        ConfigError::InvalidValue {
+            field: var.to_string(),
+            origin,
+            value,
+            expected: expected.to_string(),
+        }
+    }
+}
+
+/// Flatten nested tables into dotted field names; arrays join with commas
+fn flatten(value: &Value, prefix: String, out: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let field = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten(value, field, out);
+            }
+        }
+        Value::Array(items) => {
+            let joined = items.iter()
+                .map(|item| match item {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            out.push((prefix, joined));
+        }
+        Value::String(s) => out.push((prefix, s.clone())),
+        Value::Null => {}
+        other => out.push((prefix, other.to_string())),
+    }
+}
@@ -1,11 +1,64 @@
 //! Configuration management for the Aerodrome bot
 
+pub mod file;
 pub mod settings;
+pub mod watcher;
 
+pub use file::*;
 pub use settings::*;
+pub use watcher::*;
 
 use lazy_static::lazy_static;
+use std::sync::{Arc, RwLock};
+use tracing::error;
+
+/// Process-wide configuration that can be swapped on reload. The bot
+/// installs its own config when it starts; until something is installed,
+/// the first read loads the config file and environment.
+pub struct ConfigHandle {
+    current: RwLock<Option<Arc<Config>>>,
+}
+
+impl ConfigHandle {
+    pub fn new(config: Config) -> Self {
+        Self {
+            current: RwLock::new(Some(Arc::new(config))),
+        }
+    }
+
+    /// Nothing installed yet
+    pub fn empty() -> Self {
+        Self {
+            current: RwLock::new(None),
+        }
+    }
+
+    /// Load from the config file and environment and install the result
+    pub fn init(&self) -> Result<Arc<Config>, ConfigError> {
+        let config = Arc::new(Config::try_load()?);
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = Some(config.clone());
+        Ok(config)
+    }
+
+    /// Snapshot of the active configuration. Read before any config was
+    /// installed, invalid settings are logged and the defaults used.
+    pub fn get(&self) -> Arc<Config> {
+        if let Some(config) = self.current.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            return config.clone();
+        }
+        self.current.write().unwrap_or_else(|e| e.into_inner())
+            .get_or_insert_with(|| Arc::new(Config::try_load().unwrap_or_else(|e| {
+                error!("❌ Invalid configuration, using defaults until one is installed: {}", e);
+                Config::defaults()
+            })))
+            .clone()
+    }
+
+    pub fn replace(&self, config: Config) {
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(config));
+    }
+}
 
 lazy_static! {
-    pub static ref CONFIG: Config = Config::load();
+    pub static ref CONFIG: ConfigHandle = ConfigHandle::empty();
 }
@@ -1,9 +1,22 @@
-//! Bot configuration settings and environment variable handling
+//! Bot configuration settings from the config file and environment variables
 
+use alloy::primitives::Address;
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
-use std::env;
+use std::collections::HashMap;
 use std::str::FromStr;
+use std::time::Duration;
+use crate::{
+    arbitrage::{BasisStats, DEFAULT_BASIS_WINDOW_SECS, DEFAULT_MIN_PRICE_DIFF_PCT},
+    errors::{RecoveryStrategy, ERROR_CLASSES},
+    execution::{SimulationMode, SubmissionMode},
+    market_making::{DEFAULT_EPOCH_FLIP_WINDOW_SECS, EPOCH_DURATION_SECS},
+    network::{CexSource, MonitorTrigger, StablePriceSource},
+    pools::{default_cex_symbol, DEFAULT_MAX_TWAP_DEVIATION_PCT, DEFAULT_TWAP_WINDOW_SECS, DISCOVERY_STABLES},
+    storage::{DEFAULT_HISTORY_MEMORY_SAMPLES, DEFAULT_HISTORY_RETENTION_DAYS},
+    types::{PriceBasis, VolatilityEstimator, CHAINLINK_ETH_USD_BASE, CL_POOLS_MAINNET, POOLS_MAINNET, POOLS_SEPOLIA},
+};
+use super::{ConfigError, ConfigSource};
 
 // Configuration constants
 pub const MIN_TRADE_SIZE_ETH: Decimal = dec!(0.01);
@@ -24,19 +37,159 @@ pub const DEFAULT_GAS_PRICE_GWEI: u32 = 50;
 pub const MAX_GAS_PRICE_GWEI: u32 = 200;
 pub const EXECUTION_TIMEOUT_SECS: u64 = 30;
 
+// Gas Spike Deferral Constants
+pub const DEFAULT_GAS_SPIKE_MULTIPLIER: Decimal = dec!(2.0); // gas at 2x baseline is a spike
+pub const DEFAULT_GAS_DEFERRAL_TTL_SECS: u64 = 30;
+
+// Arbitrage Fee Model Constants (Binance VIP 0 spot rates)
+pub const DEFAULT_BINANCE_MAKER_FEE_BPS: Decimal = dec!(10);
+pub const DEFAULT_BINANCE_TAKER_FEE_BPS: Decimal = dec!(10);
+
+// Confirmation depth by trade notional: (min notional USD, blocks)
+pub const DEFAULT_CONFIRMATION_TIERS: &str = "0:1,5000:3";
+pub const BASE_BLOCK_TIME_SECS: u64 = 2;
+
+// Mainnet Execution Guard Defaults
+pub const DEFAULT_MAX_TRADE_NOTIONAL_USD: Decimal = dec!(500);
+pub const DEFAULT_MAX_DAILY_LOSS_USD: Decimal = dec!(50);
+
+// Scoped Circuit Breaker Defaults
+pub const DEFAULT_CEX_BREAKER_MAX_ERRORS: u32 = 5;
+pub const DEFAULT_CEX_BREAKER_COOLDOWN_SECS: u64 = 120;
+pub const DEFAULT_RPC_BREAKER_MAX_ERRORS: u32 = 5;
+pub const DEFAULT_RPC_BREAKER_COOLDOWN_SECS: u64 = 300;
+pub const DEFAULT_POOL_BREAKER_MAX_ERRORS: u32 = 3;
+pub const DEFAULT_POOL_BREAKER_COOLDOWN_SECS: u64 = 600;
+
+// Oracle Cross-Check Defaults
+pub const DEFAULT_MAX_ORACLE_DEVIATION_PCT: Decimal = dec!(3);
+pub const DEFAULT_ORACLE_MAX_AGE_SECS: u64 = 3600;
+
+// Error Recovery Defaults
+pub const DEFAULT_RECOVERY_ERROR_DECAY_SECS: u64 = 300;
+pub const DEFAULT_MAX_PANICS: u64 = 10;
+
+// Risk Manager Defaults
+pub const DEFAULT_MAX_OPEN_EXPOSURE_ETH: Decimal = dec!(1);
+pub const DEFAULT_MAX_EXECUTIONS_PER_HOUR: u32 = 60;
+
+// Signer Policy Defaults
+pub const DEFAULT_SIGNER_MAX_GAS_LIMIT: u64 = 500_000;
+
+// Execution Queue Defaults
+pub const DEFAULT_MAX_CONCURRENT_EXECUTIONS: usize = 1;
+pub const DEFAULT_EXECUTION_QUEUE_MAX_AGE_SECS: u64 = 15;
+
+// Paper Trading Constants
+pub const DEFAULT_PAPER_STARTING_WETH: Decimal = dec!(1);
+pub const DEFAULT_PAPER_STARTING_USDC: Decimal = dec!(3000);
+
+// Monitoring Constants
+pub const DEFAULT_POLL_INTERVAL_SECS: u64 = 2;
+pub const DEFAULT_MIN_POLL_INTERVAL_MS: u64 = 500; // extreme volatility
+pub const DEFAULT_MAX_POLL_INTERVAL_MS: u64 = 10_000; // dead markets
+pub const DEFAULT_MAX_CONCURRENT_POOLS: usize = 4;
+pub const DEFAULT_MAX_PRICE_SKEW_MS: u64 = 2000; // 0 disables
+
+// CEX Depth Constants (0 = ticker price only)
+pub const DEFAULT_CEX_DEPTH_LEVELS: usize = 20;
+pub const MAX_CEX_DEPTH_LEVELS: usize = 5000; // Binance's deepest depth snapshot
+
+// Rate Limit Constants (requests per second, 0 = unlimited)
+pub const DEFAULT_RPC_REQUESTS_PER_SEC: u32 = 15;
+pub const DEFAULT_BINANCE_REQUESTS_PER_SEC: u32 = 10;
+
+// Reserve Cache Constants (0 = disabled)
+pub const DEFAULT_RESERVE_CACHE_TTL_MS: u64 = 1000;
+
+// Pool Discovery Constants
+pub const DEFAULT_POOL_DISCOVERY_MIN_TVL_USD: Decimal = dec!(100000);
+pub const DEFAULT_POOL_DISCOVERY_INTERVAL_SECS: u64 = 3600;
+pub const DEFAULT_DISCOVERY_STABLES: &str = "USDC,USDbC";
+
+// Storage Checkpoint Constants
+pub const DEFAULT_CHECKPOINT_INTERVAL_SECS: u64 = 3600;
+pub const DEFAULT_STATE_SAVE_INTERVAL_SECS: u64 = 60;
+pub const DEFAULT_STATS_INTERVAL_SECS: u64 = 900;
+pub const DEFAULT_HEALTH_CHECK_INTERVAL_SECS: u64 = 30;
+pub const DEFAULT_STATS_EVERY_OPPORTUNITIES: u64 = 50;
+pub const DEFAULT_STATS_EVERY_SIGNALS: u64 = 25;
+pub const DEFAULT_STATS_EVERY_EXECUTIONS: u64 = 10;
+pub const DEFAULT_OUTPUT_COMPRESS_AFTER_DAYS: u64 = 7;
+
+// Price History Constants
+/// Enough in-memory samples for the 5-minute volatility window at 2s polls
+pub const MIN_HISTORY_MEMORY_SAMPLES: usize = 150;
+
+// Volatility Constants
+pub const DEFAULT_EWMA_LAMBDA: Decimal = dec!(0.94); // RiskMetrics daily decay
+
+// Leader Election Constants
+pub const DEFAULT_LEADER_LEASE_SECS: u64 = 15;
+
+// Watch-only Mode Constants
+pub const PUBLIC_BASE_RPC_URL: &str = "https://mainnet.base.org";
+pub const WATCH_ONLY_MIN_POLL_INTERVAL_SECS: u64 = 10; // public RPCs are heavily rate limited
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub trade_size_eth: Decimal,
     pub min_profit_usd: Decimal,
+    /// DEX/CEX price difference below which no opportunity is reported, in percent
+    pub min_price_diff_pct: Decimal,
+    /// Raise a pool's price-difference threshold to the noise of its basis
+    pub auto_min_price_diff: bool,
     pub max_consecutive_errors: u32,
     pub circuit_breaker_cooldown_secs: u64,
+    // Scoped circuit breakers: consecutive errors to open, seconds to stay open
+    pub cex_breaker_max_errors: u32,
+    pub cex_breaker_cooldown_secs: u64,
+    pub rpc_breaker_max_errors: u32,
+    pub rpc_breaker_cooldown_secs: u64,
+    pub pool_breaker_max_errors: u32,
+    pub pool_breaker_cooldown_secs: u64,
+    /// Per-error-class overrides of the built-in recovery table
+    pub recovery_strategies: Vec<(String, RecoveryStrategy)>,
+    /// Quiet period after which an error class's count resets (0 = never)
+    pub recovery_error_decay_secs: u64,
+    /// Caught cycle and pool panics that shut the bot down (0 = never)
+    pub max_panics: u64,
     pub enable_safety_checks: bool,
+    /// Pool names to monitor (all known pools when unset)
+    pub enabled_pools: Option<Vec<String>>,
+    /// Extra pools by name and address, e.g. `vAMM-AERO/USDC`; the base
+    /// token is matched by symbol against the left side of the pair
+    pub custom_pools: Vec<(String, Address)>,
+    /// Binance symbol overrides by pair, e.g. `cbETH/WETH` -> `CBETHETH`
+    pub cex_symbols: HashMap<String, String>,
+    /// USD pricing of other quote assets by symbol, e.g. `DOLA` -> a $1 peg or a Chainlink feed
+    pub stable_prices: HashMap<String, StablePriceSource>,
+    // Pool discovery via the Aerodrome PoolFactory
+    pub pool_discovery_enabled: bool,
+    pub pool_discovery_min_tvl_usd: Decimal,
+    pub pool_discovery_interval_secs: u64,
+    pub discovery_stables: Vec<String>,
+    // Fair-value price basis for arbitrage and market making
+    pub price_basis: PriceBasis,
+    pub twap_window_secs: u64,
+    /// Flag opportunities whose DEX spot is further than this from the pool's on-chain TWAP (zero disables)
+    pub max_twap_deviation_pct: Decimal,
+    /// Skip arbitrage on a pool when its DEX and CEX prices were sampled further apart (zero disables)
+    pub max_price_skew_ms: u64,
+    /// Only flag arbitrage when a pool's DEX/CEX basis is this many standard
+    /// deviations from its recent mean (zero disables)
+    pub basis_z_threshold: Decimal,
+    pub basis_window_secs: u64,
     // Market Making Configuration
     pub enable_market_making: bool,
     pub base_spread_bps: u32,
     pub max_position_size_eth: Decimal,
     pub inventory_target_ratio: Decimal,
     pub rebalance_threshold: Decimal,
+    /// Read pool gauges and count AERO emissions in market-making returns
+    pub enable_gauge_yield: bool,
+    /// Widen spreads and cut size this long either side of the weekly epoch flip (zero disables)
+    pub epoch_flip_window_secs: u64,
     // Trade Execution Configuration
     pub enable_trade_execution: bool,
     pub network: String,
@@ -44,86 +197,803 @@ pub struct Config {
     pub max_gas_price_gwei: u32,
     pub slippage_tolerance_bps: u32,
     pub private_key: Option<String>,
+    pub wallet_address: Option<String>,
+    pub confirmation_tiers: Vec<(Decimal, u64)>,
+    /// Public mempool, or a private RPC or bundle endpoint for swaps
+    pub submission_mode: SubmissionMode,
+    pub private_rpc_url: Option<String>,
+    /// Approve routers for the maximum amount instead of each swap's input
+    pub infinite_approvals: bool,
+    // Gas spike deferral
+    pub gas_deferral_enabled: bool,
+    pub gas_spike_multiplier: Decimal,
+    pub gas_deferral_ttl_secs: u64,
+    // Oracle cross-check
+    /// Bound WETH/USD prices by an on-chain oracle (default on mainnet)
+    pub oracle_check: bool,
+    /// Chainlink-compatible ETH/USD aggregator
+    pub oracle_feed_address: Address,
+    /// Reject opportunities whose DEX or CEX price is further than this from the oracle
+    pub max_oracle_deviation_pct: Decimal,
+    /// Oracle answers older than this are ignored
+    pub oracle_max_age_secs: u64,
+    // Arbitrage fee model
+    pub binance_maker_fee_bps: Decimal,
+    pub binance_taker_fee_bps: Decimal,
+    /// Price the CEX leg at the maker rate (limit orders) instead of taker
+    pub binance_maker_orders: bool,
+    /// DEX fee override; the pool type's default fee when unset
+    pub dex_fee_bps: Option<Decimal>,
+    /// Bridge/withdrawal cost charged once per round trip
+    pub transfer_cost_usd: Decimal,
+    // Mainnet execution guards
+    pub i_understand_real_funds: bool,
+    pub max_trade_notional_usd: Decimal,
+    pub max_daily_loss_usd: Decimal,
+    // Risk manager (with max_daily_loss_usd, applied on every network)
+    /// Net WETH left open by unhedged legs before execution halts
+    pub max_open_exposure_eth: Decimal,
+    /// Zero disables the rate limit
+    pub max_executions_per_hour: u32,
+    // Token safety simulation
+    pub token_safety_checks: bool,
+    pub anvil_path: String,
+    /// Running Anvil node for fork simulation instead of spawning `anvil_path`
+    pub anvil_fork_url: Option<String>,
+    // Signer transaction policy
+    pub signer_max_gas_limit: u64,
+    pub signer_max_value_eth: Decimal,
+    /// Calls permitted beyond the built-in router swaps, as (contract, selector)
+    pub signer_allowed_calls: Vec<(Address, [u8; 4])>,
+    // Execution queue
+    pub max_concurrent_executions: usize,
+    /// Queued opportunities older than this are dropped unexecuted
+    pub execution_queue_max_age_secs: u64,
     // Volatility Configuration
     pub volatility_threshold: Decimal,
     pub volatility_spread_multiplier: Decimal,
+    pub volatility_estimator: VolatilityEstimator,
+    /// EWMA decay per sample, between 0 and 1 exclusive
+    pub ewma_lambda: Decimal,
     // Alchemy API Key
     pub alchemy_api_key: Option<String>,
+    // Watch-only Configuration
+    pub watch_only: bool,
+    /// Log records and executions instead of writing or sending them
+    pub dry_run: bool,
+    /// Seed for simulated execution outcomes; None draws one per run
+    pub simulation_seed: Option<u64>,
+    /// Random outcomes, or swaps on an Anvil fork
+    pub simulation_mode: SimulationMode,
+    pub poll_interval_secs: u64,
+    /// Poll faster in volatile markets and slower in quiet ones, within the bounds below
+    pub adaptive_polling: bool,
+    pub min_poll_interval_ms: u64,
+    pub max_poll_interval_ms: u64,
+    /// Pools processed at once within a cycle
+    pub max_concurrent_pools: usize,
+    pub monitor_trigger: MonitorTrigger,
+    pub ws_rpc_url: Option<String>,
+    // CEX Price Source
+    pub cex_source: CexSource,
+    /// Prices for `CEX_SOURCE=fixture`
+    pub cex_fixture_path: Option<String>,
+    /// Order book levels fetched to price the CEX leg (0 disables)
+    pub cex_depth_levels: usize,
+    // Rate Limits
+    pub rpc_requests_per_sec: u32,
+    pub binance_requests_per_sec: u32,
+    /// Reuse a pool's reserves for this long within a block (0 = disabled)
+    pub reserve_cache_ttl_ms: u64,
+    // Status API
+    pub api_bind_addr: Option<String>,
+    // Fills Reconciler Configuration
+    pub reconciler_url: Option<String>,
+    pub reconciler_api_key: Option<String>,
+    // Storage Configuration
+    pub sqlite_path: Option<String>,
+    pub record_decisions: bool,
+    pub record_spreads: bool,
+    pub record_prices: bool,
+    pub checkpoint_interval_secs: u64,
+    /// Save the bot state this often while running (0 = shutdown only)
+    pub state_save_interval_secs: u64,
+    /// Check and log health this often on its own task (0 = off)
+    pub health_check_interval_secs: u64,
+    /// Print the session statistics at least this often (0 = only on counts)
+    pub stats_interval_secs: u64,
+    /// Also print them each time these counts pass a multiple (0 = never)
+    pub stats_every_opportunities: u64,
+    pub stats_every_signals: u64,
+    pub stats_every_executions: u64,
+    /// Price history samples kept in memory before older ones spill to disk
+    pub history_memory_samples: usize,
+    pub history_retention_days: u64,
+    /// Gzip rotated logs and daily output files after this many days (0 = never)
+    pub output_compress_after_days: u64,
+    /// Delete rotated logs and daily output files after this many days (0 = keep)
+    pub output_retention_days: u64,
+    /// Delete the oldest of them while they take more than this (0 = unlimited)
+    pub output_max_size_mb: u64,
+    // Paper trading ledger
+    pub paper_trading: bool,
+    pub paper_starting_weth: Decimal,
+    pub paper_starting_usdc: Decimal,
+    // Binance Spot Testnet CEX leg
+    pub binance_testnet_api_key: Option<String>,
+    pub binance_testnet_api_secret: Option<String>,
+    pub binance_testnet_symbol: String,
+    // Leader election
+    pub instance_id: String,
+    pub leader_lease_path: Option<String>,
+    pub leader_lease_secs: u64,
+    /// Config file the settings were layered on, if any
+    pub config_file: Option<String>,
+}
+
+/// Parse `key=value` pairs, e.g. "AERO/USDC=AEROUSDC,cbETH/WETH=CBETHETH"
+fn parse_assignments(spec: &str) -> Option<Vec<(String, String)>> {
+    spec.split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let (key, value) = entry.trim().split_once('=')?;
+            let (key, value) = (key.trim(), value.trim());
+            (!key.is_empty() && !value.is_empty()).then(|| (key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// A fixed positive USD price, or the address of a USD feed
+fn parse_stable_price_source(value: &str) -> Option<StablePriceSource> {
+    if value.starts_with("0x") {
+        return Address::from_str(value).ok().map(StablePriceSource::Feed);
+    }
+    Decimal::from_str(value).ok()
+        .filter(|price| *price > dec!(0))
+        .map(StablePriceSource::Fixed)
+}
+
+/// Parse `notional:blocks` pairs, e.g. "0:1,5000:3", sorted by notional
+fn parse_confirmation_tiers(spec: &str) -> Option<Vec<(Decimal, u64)>> {
+    let mut tiers = spec.split(',')
+        .map(|tier| {
+            let (notional, blocks) = tier.trim().split_once(':')?;
+            Some((Decimal::from_str(notional.trim()).ok()?, blocks.trim().parse().ok()?))
+        })
+        .collect::<Option<Vec<(Decimal, u64)>>>()?;
+    tiers.sort_by(|a, b| a.0.cmp(&b.0));
+    Some(tiers)
+}
+
+/// Parse `class=strategy` pairs, e.g. "network_timeout=retry:3:500,parse_error=shutdown:bad data"
+fn parse_recovery_strategies(spec: &str) -> Option<Vec<(String, RecoveryStrategy)>> {
+    spec.split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let (class, strategy) = entry.trim().split_once('=')?;
+            let class = class.trim();
+            if !ERROR_CLASSES.contains(&class) {
+                return None;
+            }
+            Some((class.to_string(), RecoveryStrategy::from_spec(strategy)?))
+        })
+        .collect()
+}
+
+/// Parse `address:selector` pairs, e.g. "0x4200000000000000000000000000000000000006:0x095ea7b3"
+fn parse_allowed_calls(spec: &str) -> Option<Vec<(Address, [u8; 4])>> {
+    spec.split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let (address, selector) = entry.trim().split_once(':')?;
+            let selector = alloy::primitives::hex::decode(selector.trim()).ok()?;
+            Some((Address::from_str(address.trim()).ok()?, selector.try_into().ok()?))
+        })
+        .collect()
 }
 
 impl Config {
-    pub fn load() -> Self {
-        Self {
-            alchemy_api_key: env::var("ALCHEMY_API_KEY").ok(),
-            trade_size_eth: env::var("TRADE_SIZE_ETH")
-                .ok()
-                .and_then(|s| Decimal::from_str(&s).ok())
-                .unwrap_or(dec!(0.1))
-                .max(MIN_TRADE_SIZE_ETH)
-                .min(MAX_TRADE_SIZE_ETH),
-            min_profit_usd: env::var("MIN_PROFIT_USD")
-                .ok()
-                .and_then(|s| Decimal::from_str(&s).ok())
-                .unwrap_or(dec!(0.50))
-                .max(MIN_PROFIT_USD),
+    /// Blocks to wait for before treating a trade of `notional_usd` as final
+    pub fn required_confirmations(&self, notional_usd: Decimal) -> u64 {
+        self.confirmation_tiers.iter()
+            .rev()
+            .find(|(min_notional, _)| notional_usd >= *min_notional)
+            .map(|(_, blocks)| (*blocks).max(1))
+            .unwrap_or(1)
+    }
+
+    /// Longest a trade can take to execute: an approval and the swap, each
+    /// waited on to the deepest confirmation tier
+    pub fn longest_execution(&self) -> Duration {
+        let confirmations = self.confirmation_tiers.iter().map(|(_, blocks)| *blocks).max().unwrap_or(1).max(1);
+        Duration::from_secs(2 * (EXECUTION_TIMEOUT_SECS + (confirmations - 1) * BASE_BLOCK_TIME_SECS))
+    }
+
+    /// Longest gap between timed cycles
+    pub fn slowest_poll_interval(&self) -> Duration {
+        if self.adaptive_polling {
+            Duration::from_millis(self.max_poll_interval_ms).max(Duration::from_secs(self.poll_interval_secs))
+        } else {
+            Duration::from_secs(self.poll_interval_secs)
+        }
+    }
+
+    /// Whether `pool_name` is selected by the `POOLS` setting
+    pub fn pool_enabled(&self, pool_name: &str) -> bool {
+        self.enabled_pools.as_ref()
+            .is_none_or(|enabled| enabled.iter().any(|name| name == pool_name))
+    }
+
+    /// Binance symbol for a pair: the `CEX_SYMBOLS` override, else derived from the symbols
+    pub fn cex_symbol(&self, base_symbol: &str, quote_symbol: &str) -> String {
+        self.cex_symbols.get(&format!("{}/{}", base_symbol, quote_symbol))
+            .cloned()
+            .unwrap_or_else(|| default_cex_symbol(base_symbol, quote_symbol))
+    }
+
+    /// Price-difference threshold for a pool whose basis is `basis` at
+    /// `price`: the configured minimum, raised to the basis noise with
+    /// AUTO_MIN_PRICE_DIFF
+    pub fn min_price_diff_pct_for(&self, basis: Option<&BasisStats>, price: Decimal) -> Decimal {
+        match basis.filter(|_| self.auto_min_price_diff) {
+            Some(basis) => basis.calibrated_min_diff_pct(self.min_price_diff_pct, price),
+            None => self.min_price_diff_pct,
+        }
+    }
+
+    /// Copy the settings that are safe to change at runtime from `new`,
+    /// returning the names of the fields that changed. Network, wallet,
+    /// execution guard and leader settings still require a restart.
+    pub fn apply_reloadable(&mut self, new: &Config) -> Vec<&'static str> {
+        let mut changed = Vec::new();
+        macro_rules! reload {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    if self.$field != new.$field {
+                        self.$field = new.$field.clone();
+                        changed.push(stringify!($field));
+                    }
+                )*
+            };
+        }
+        reload!(
+            trade_size_eth,
+            min_profit_usd,
+            min_price_diff_pct,
+            auto_min_price_diff,
+            enabled_pools,
+            custom_pools,
+            cex_symbols,
+            pool_discovery_enabled,
+            pool_discovery_min_tvl_usd,
+            pool_discovery_interval_secs,
+            discovery_stables,
+            price_basis,
+            twap_window_secs,
+            max_twap_deviation_pct,
+            max_price_skew_ms,
+            basis_z_threshold,
+            basis_window_secs,
+            enable_market_making,
+            base_spread_bps,
+            max_position_size_eth,
+            inventory_target_ratio,
+            rebalance_threshold,
+            enable_gauge_yield,
+            epoch_flip_window_secs,
+            max_gas_price_gwei,
+            slippage_tolerance_bps,
+            infinite_approvals,
+            gas_deferral_enabled,
+            gas_spike_multiplier,
+            max_oracle_deviation_pct,
+            cex_depth_levels,
+            binance_maker_fee_bps,
+            binance_taker_fee_bps,
+            binance_maker_orders,
+            dex_fee_bps,
+            transfer_cost_usd,
+            volatility_threshold,
+            volatility_spread_multiplier,
+            record_decisions,
+            record_spreads,
+            record_prices,
+            output_compress_after_days,
+            output_retention_days,
+            output_max_size_mb,
+            stats_interval_secs,
+            stats_every_opportunities,
+            stats_every_signals,
+            stats_every_executions,
+            max_concurrent_pools,
+            adaptive_polling,
+            min_poll_interval_ms,
+            max_poll_interval_ms,
+        );
+        changed
+    }
+
+    /// Built-in defaults, ignoring the config file and environment
+    pub fn defaults() -> Self {
+        Self::from_source(&ConfigSource::default()).expect("default settings are valid")
+    }
+
+    /// Load from `CONFIG_FILE` (or `bot.toml`/`bot.yaml`) with env var overrides
+    pub fn try_load() -> Result<Self, ConfigError> {
+        Self::from_source(&ConfigSource::load()?)
+    }
+
+    pub fn from_source(src: &ConfigSource) -> Result<Self, ConfigError> {
+        let alchemy_api_key = src.string("ALCHEMY_API_KEY");
+        // Fall back to watch-only mode when no Alchemy key is configured
+        let watch_only = src.parse("WATCH_ONLY", "a boolean")?
+            .unwrap_or(alchemy_api_key.is_none());
+        let poll_interval_secs = src.parse("POLL_INTERVAL_SECS", "a whole number of seconds")?
+            .unwrap_or(DEFAULT_POLL_INTERVAL_SECS)
+            .max(1);
+        let mut min_poll_interval_ms = src.parse("MIN_POLL_INTERVAL_MS", "a whole number of milliseconds")?
+            .unwrap_or(DEFAULT_MIN_POLL_INTERVAL_MS);
+        let mut max_poll_interval_ms = src.parse("MAX_POLL_INTERVAL_MS", "a whole number of milliseconds")?
+            .unwrap_or(DEFAULT_MAX_POLL_INTERVAL_MS);
+        if min_poll_interval_ms == 0 {
+            return Err(src.invalid("MIN_POLL_INTERVAL_MS", "at least 1 millisecond"));
+        }
+        if max_poll_interval_ms < min_poll_interval_ms {
+            return Err(src.invalid("MAX_POLL_INTERVAL_MS", "at least MIN_POLL_INTERVAL_MS"));
+        }
+        if watch_only {
+            min_poll_interval_ms = min_poll_interval_ms.max(WATCH_ONLY_MIN_POLL_INTERVAL_SECS * 1000);
+            max_poll_interval_ms = max_poll_interval_ms.max(min_poll_interval_ms);
+        }
+        let max_concurrent_pools = src.parse("MAX_CONCURRENT_POOLS", "a whole number of pools")?
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_POOLS);
+        if max_concurrent_pools == 0 {
+            return Err(src.invalid("MAX_CONCURRENT_POOLS", "at least 1"));
+        }
+
+        let inventory_target_ratio = src.parse("INVENTORY_TARGET_RATIO", "a decimal")?
+            .unwrap_or(dec!(0.5));
+        if inventory_target_ratio < dec!(0) || inventory_target_ratio > dec!(1) {
+            return Err(src.invalid("INVENTORY_TARGET_RATIO", "a ratio between 0 and 1"));
+        }
+        let rebalance_threshold = src.parse("REBALANCE_THRESHOLD", "a decimal")?
+            .unwrap_or(dec!(0.1));
+        if rebalance_threshold < dec!(0) || rebalance_threshold > dec!(1) {
+            return Err(src.invalid("REBALANCE_THRESHOLD", "a ratio between 0 and 1"));
+        }
+
+        let network = src.string("NETWORK").unwrap_or_else(|| "mainnet".to_string());
+        if network != "mainnet" && network != "sepolia" {
+            return Err(src.invalid("NETWORK", "`mainnet` or `sepolia`"));
+        }
+        let execution_network = src.string("EXECUTION_NETWORK").unwrap_or_else(|| "sepolia".to_string());
+        if execution_network != "mainnet" && execution_network != "sepolia" {
+            return Err(src.invalid("EXECUTION_NETWORK", "`mainnet` or `sepolia`"));
+        }
+        let submission_mode: SubmissionMode = src.parse("SUBMISSION_MODE", "one of public, private, bundle")?
+            .unwrap_or_default();
+        let private_rpc_url = src.string("PRIVATE_RPC_URL");
+        if submission_mode != SubmissionMode::Public && private_rpc_url.is_none() {
+            return Err(src.invalid("PRIVATE_RPC_URL", "an endpoint URL when SUBMISSION_MODE is private or bundle"));
+        }
+        // Every arbitrage closes on the CEX, so no on-chain leg could repay a flash loan
+        for field in ["FLASH_LOAN_SOURCE", "FLASH_LOAN_RECEIVER"] {
+            if src.string(field).is_some() {
+                return Err(src.invalid(field, "unset: flash loans are unsupported while the closing leg is on the CEX"));
+            }
+        }
+
+        // The default feed is a Base mainnet contract
+        let oracle_check = src.parse("ORACLE_CHECK", "a boolean")?
+            .unwrap_or(network == "mainnet");
+        let oracle_feed_address = match src.string("ORACLE_FEED_ADDRESS") {
+            Some(address) => Address::from_str(address.trim())
+                .map_err(|_| src.invalid("ORACLE_FEED_ADDRESS", "a contract address"))?,
+            None => CHAINLINK_ETH_USD_BASE,
+        };
+        let max_oracle_deviation_pct = src.parse("MAX_ORACLE_DEVIATION_PCT", "a decimal percentage")?
+            .unwrap_or(DEFAULT_MAX_ORACLE_DEVIATION_PCT);
+        if max_oracle_deviation_pct <= dec!(0) {
+            return Err(src.invalid("MAX_ORACLE_DEVIATION_PCT", "a positive percentage"));
+        }
+
+        let volatility_estimator: VolatilityEstimator = src
+            .parse("VOLATILITY_ESTIMATOR", "one of log_returns, realized, ewma, garch, price_stdev")?
+            .unwrap_or_default();
+        let fee_bps = |var: &str, default: Decimal| -> Result<Decimal, ConfigError> {
+            let bps = src.parse(var, "a decimal number of bps")?.unwrap_or(default);
+            if bps < dec!(0) {
+                return Err(src.invalid(var, "a non-negative number of bps"));
+            }
+            Ok(bps)
+        };
+        let binance_maker_fee_bps = fee_bps("BINANCE_MAKER_FEE_BPS", DEFAULT_BINANCE_MAKER_FEE_BPS)?;
+        let binance_taker_fee_bps = fee_bps("BINANCE_TAKER_FEE_BPS", DEFAULT_BINANCE_TAKER_FEE_BPS)?;
+        let dex_fee_bps: Option<Decimal> = src.parse("DEX_FEE_BPS", "a decimal number of bps")?;
+        if dex_fee_bps.is_some_and(|bps| bps < dec!(0)) {
+            return Err(src.invalid("DEX_FEE_BPS", "a non-negative number of bps"));
+        }
+        let transfer_cost_usd = src.parse("TRANSFER_COST_USD", "a decimal USD amount")?
+            .unwrap_or(dec!(0));
+        if transfer_cost_usd < dec!(0) {
+            return Err(src.invalid("TRANSFER_COST_USD", "a non-negative USD amount"));
+        }
+
+        let ewma_lambda = src.parse("EWMA_LAMBDA", "a decimal")?
+            .unwrap_or(DEFAULT_EWMA_LAMBDA);
+        if ewma_lambda <= dec!(0) || ewma_lambda >= dec!(1) {
+            return Err(src.invalid("EWMA_LAMBDA", "a decay between 0 and 1 exclusive"));
+        }
+
+        let gas_spike_multiplier = src.parse("GAS_SPIKE_MULTIPLIER", "a decimal multiplier")?
+            .unwrap_or(DEFAULT_GAS_SPIKE_MULTIPLIER);
+        if gas_spike_multiplier < dec!(1) {
+            return Err(src.invalid("GAS_SPIKE_MULTIPLIER", "a multiplier of at least 1"));
+        }
+
+        let max_concurrent_executions = src.parse("MAX_CONCURRENT_EXECUTIONS", "a whole number of executions")?
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_EXECUTIONS);
+        if max_concurrent_executions == 0 {
+            return Err(src.invalid("MAX_CONCURRENT_EXECUTIONS", "at least 1"));
+        }
+        let execution_queue_max_age_secs = src.parse("EXECUTION_QUEUE_MAX_AGE_SECS", "a whole number of seconds")?
+            .unwrap_or(DEFAULT_EXECUTION_QUEUE_MAX_AGE_SECS);
+        if execution_queue_max_age_secs == 0 {
+            return Err(src.invalid("EXECUTION_QUEUE_MAX_AGE_SECS", "at least 1 second"));
+        }
+
+        let confirmation_tiers = match src.string("CONFIRMATION_TIERS") {
+            Some(spec) => parse_confirmation_tiers(&spec)
+                .filter(|tiers| !tiers.is_empty())
+                .ok_or_else(|| src.invalid("CONFIRMATION_TIERS", "a list of `notional:blocks` pairs"))?,
+            None => parse_confirmation_tiers(DEFAULT_CONFIRMATION_TIERS).unwrap_or_default(),
+        };
+
+        let signer_allowed_calls = match src.string("SIGNER_ALLOWED_CALLS") {
+            Some(spec) => parse_allowed_calls(&spec)
+                .ok_or_else(|| src.invalid("SIGNER_ALLOWED_CALLS", "a list of `address:selector` pairs"))?,
+            None => Vec::new(),
+        };
+
+        let recovery_strategies = match src.string("RECOVERY_STRATEGIES") {
+            Some(spec) => parse_recovery_strategies(&spec)
+                .ok_or_else(|| src.invalid("RECOVERY_STRATEGIES", "a list of `class=strategy` pairs"))?,
+            None => Vec::new(),
+        };
+
+        let pool_discovery_enabled = src.parse("POOL_DISCOVERY", "a boolean")?
+            .unwrap_or(false);
+        let discovery_stables: Vec<String> = src.string("DISCOVERY_STABLES")
+            .unwrap_or_else(|| DEFAULT_DISCOVERY_STABLES.to_string())
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        if discovery_stables.is_empty()
+            || discovery_stables.iter().any(|s| !DISCOVERY_STABLES.iter().any(|(symbol, _)| symbol == s))
+        {
+            let known: Vec<&str> = DISCOVERY_STABLES.iter().map(|(symbol, _)| *symbol).collect();
+            return Err(src.invalid("DISCOVERY_STABLES", &format!("a list of known stables ({})", known.join(", "))));
+        }
+
+        let custom_pools = match src.string("CUSTOM_POOLS") {
+            Some(spec) => parse_assignments(&spec)
+                .and_then(|entries| {
+                    entries.into_iter()
+                        .map(|(name, address)| Some((name, Address::from_str(&address).ok()?)))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or_else(|| src.invalid("CUSTOM_POOLS", "a list of `name=0xaddress` pairs"))?,
+            None => Vec::new(),
+        };
+        let cex_symbols = match src.string("CEX_SYMBOLS") {
+            Some(spec) => parse_assignments(&spec)
+                .map(|entries| entries.into_iter().collect::<HashMap<_, _>>())
+                .ok_or_else(|| src.invalid("CEX_SYMBOLS", "a list of `BASE/QUOTE=SYMBOL` pairs"))?,
+            None => HashMap::new(),
+        };
+        let stable_prices = match src.string("STABLE_PRICES") {
+            Some(spec) => parse_assignments(&spec)
+                .and_then(|entries| {
+                    entries.into_iter()
+                        .map(|(symbol, source)| Some((symbol, parse_stable_price_source(&source)?)))
+                        .collect::<Option<HashMap<_, _>>>()
+                })
+                .ok_or_else(|| src.invalid("STABLE_PRICES", "a list of `SYMBOL=price` or `SYMBOL=0xfeed` pairs"))?,
+            None => HashMap::new(),
+        };
+
+        let enabled_pools = src.string("POOLS")
+            .map(|names| names.split(',').map(|n| n.trim().to_string()).filter(|n| !n.is_empty()).collect::<Vec<_>>());
+        // Discovered pools have names that are not in the static lists
+        if let Some(names) = enabled_pools.as_ref().filter(|_| !pool_discovery_enabled) {
+            let known = POOLS_MAINNET.iter().chain(CL_POOLS_MAINNET).chain(POOLS_SEPOLIA);
+            let known: Vec<&str> = known.map(|(name, _)| *name)
+                .chain(custom_pools.iter().map(|(name, _)| name.as_str()))
+                .collect();
+            if names.is_empty() || names.iter().any(|n| !known.contains(&n.as_str())) {
+                return Err(src.invalid("POOLS", &format!("a list of known pools ({})", known.join(", "))));
+            }
+        }
+
+        let twap_window_secs = src.parse("TWAP_WINDOW_SECS", "a whole number of seconds")?
+            .unwrap_or(DEFAULT_TWAP_WINDOW_SECS);
+        if twap_window_secs == 0 {
+            return Err(src.invalid("TWAP_WINDOW_SECS", "a positive number of seconds"));
+        }
+        let max_twap_deviation_pct = src.parse("MAX_TWAP_DEVIATION_PCT", "a decimal percentage")?
+            .unwrap_or(DEFAULT_MAX_TWAP_DEVIATION_PCT);
+        if max_twap_deviation_pct < dec!(0) {
+            return Err(src.invalid("MAX_TWAP_DEVIATION_PCT", "a non-negative percentage"));
+        }
+        let min_price_diff_pct = src.parse("MIN_PRICE_DIFF_PCT", "a decimal percentage")?
+            .unwrap_or(DEFAULT_MIN_PRICE_DIFF_PCT);
+        if min_price_diff_pct < dec!(0) {
+            return Err(src.invalid("MIN_PRICE_DIFF_PCT", "a non-negative percentage"));
+        }
+        let basis_z_threshold = src.parse("BASIS_Z_THRESHOLD", "a decimal number of standard deviations")?
+            .unwrap_or(Decimal::ZERO);
+        if basis_z_threshold < dec!(0) {
+            return Err(src.invalid("BASIS_Z_THRESHOLD", "a non-negative number of standard deviations"));
+        }
+        let basis_window_secs = src.parse("BASIS_WINDOW_SECS", "a whole number of seconds")?
+            .unwrap_or(DEFAULT_BASIS_WINDOW_SECS);
+        if basis_window_secs == 0 {
+            return Err(src.invalid("BASIS_WINDOW_SECS", "a positive number of seconds"));
+        }
+        let epoch_flip_window_secs = src.parse("EPOCH_FLIP_WINDOW_SECS", "a whole number of seconds")?
+            .unwrap_or(DEFAULT_EPOCH_FLIP_WINDOW_SECS);
+        if epoch_flip_window_secs > EPOCH_DURATION_SECS / 2 {
+            return Err(src.invalid("EPOCH_FLIP_WINDOW_SECS", "at most half a week of seconds"));
+        }
+        let cex_source: CexSource = src.parse("CEX_SOURCE", "one of binance, coinbase, fixture")?
+            .unwrap_or_default();
+        let cex_fixture_path = src.string("CEX_FIXTURE_PATH");
+        if cex_source == CexSource::Fixture && cex_fixture_path.is_none() {
+            return Err(src.invalid("CEX_FIXTURE_PATH", "a fixture file path when CEX_SOURCE is fixture"));
+        }
+        let cex_depth_levels = src.parse("CEX_DEPTH_LEVELS", "a whole number of levels")?
+            .unwrap_or(DEFAULT_CEX_DEPTH_LEVELS);
+        if cex_depth_levels > MAX_CEX_DEPTH_LEVELS {
+            return Err(src.invalid("CEX_DEPTH_LEVELS", "at most 5000 levels"));
+        }
+
+        let trade_size_eth = src.parse("TRADE_SIZE_ETH", "a decimal amount of ETH")?
+            .unwrap_or(dec!(0.1));
+        if !(MIN_TRADE_SIZE_ETH..=MAX_TRADE_SIZE_ETH).contains(&trade_size_eth) {
+            return Err(src.invalid("TRADE_SIZE_ETH", &format!("between {} and {} ETH", MIN_TRADE_SIZE_ETH, MAX_TRADE_SIZE_ETH)));
+        }
+        let min_profit_usd = src.parse("MIN_PROFIT_USD", "a decimal USD amount")?
+            .unwrap_or(dec!(0.50));
+        if min_profit_usd < MIN_PROFIT_USD {
+            return Err(src.invalid("MIN_PROFIT_USD", &format!("at least {} USD", MIN_PROFIT_USD)));
+        }
+        let base_spread_bps = src.parse("BASE_SPREAD_BPS", "a whole number of bps")?
+            .unwrap_or(DEFAULT_SPREAD_BPS);
+        if !(MIN_SPREAD_BPS..=MAX_SPREAD_BPS).contains(&base_spread_bps) {
+            return Err(src.invalid("BASE_SPREAD_BPS", &format!("between {} and {} bps", MIN_SPREAD_BPS, MAX_SPREAD_BPS)));
+        }
+        let max_gas_price_gwei = src.parse("MAX_GAS_PRICE_GWEI", "a whole number of gwei")?
+            .unwrap_or(DEFAULT_GAS_PRICE_GWEI);
+        if !(1..=MAX_GAS_PRICE_GWEI).contains(&max_gas_price_gwei) {
+            return Err(src.invalid("MAX_GAS_PRICE_GWEI", &format!("between 1 and {} gwei", MAX_GAS_PRICE_GWEI)));
+        }
+        let slippage_tolerance_bps = src.parse("SLIPPAGE_TOLERANCE_BPS", "a whole number of bps")?
+            .unwrap_or(50); // 0.5% default
+        if slippage_tolerance_bps > MAX_SLIPPAGE_BPS {
+            return Err(src.invalid("SLIPPAGE_TOLERANCE_BPS", &format!("between 0 and {} bps", MAX_SLIPPAGE_BPS)));
+        }
+
+        Ok(Self {
+            alchemy_api_key,
+            trade_size_eth,
+            min_profit_usd,
+            min_price_diff_pct,
+            auto_min_price_diff: src.parse("AUTO_MIN_PRICE_DIFF", "a boolean")?
+                .unwrap_or(false),
             max_consecutive_errors: 5,
             circuit_breaker_cooldown_secs: 300, // 5 minutes
-            enable_safety_checks: env::var("ENABLE_SAFETY_CHECKS")
-                .unwrap_or_else(|_| "true".to_string())
-                .parse()
+            cex_breaker_max_errors: src.parse("CEX_BREAKER_MAX_ERRORS", "a whole number of errors")?
+                .unwrap_or(DEFAULT_CEX_BREAKER_MAX_ERRORS),
+            cex_breaker_cooldown_secs: src.parse("CEX_BREAKER_COOLDOWN_SECS", "a whole number of seconds")?
+                .unwrap_or(DEFAULT_CEX_BREAKER_COOLDOWN_SECS),
+            rpc_breaker_max_errors: src.parse("RPC_BREAKER_MAX_ERRORS", "a whole number of errors")?
+                .unwrap_or(DEFAULT_RPC_BREAKER_MAX_ERRORS),
+            rpc_breaker_cooldown_secs: src.parse("RPC_BREAKER_COOLDOWN_SECS", "a whole number of seconds")?
+                .unwrap_or(DEFAULT_RPC_BREAKER_COOLDOWN_SECS),
+            pool_breaker_max_errors: src.parse("POOL_BREAKER_MAX_ERRORS", "a whole number of errors")?
+                .unwrap_or(DEFAULT_POOL_BREAKER_MAX_ERRORS),
+            pool_breaker_cooldown_secs: src.parse("POOL_BREAKER_COOLDOWN_SECS", "a whole number of seconds")?
+                .unwrap_or(DEFAULT_POOL_BREAKER_COOLDOWN_SECS),
+            recovery_strategies,
+            recovery_error_decay_secs: src.parse("RECOVERY_ERROR_DECAY_SECS", "a whole number of seconds")?
+                .unwrap_or(DEFAULT_RECOVERY_ERROR_DECAY_SECS),
+            max_panics: src.parse("MAX_PANICS", "a whole number of panics")?
+                .unwrap_or(DEFAULT_MAX_PANICS),
+            enable_safety_checks: src.parse("ENABLE_SAFETY_CHECKS", "a boolean")?
                 .unwrap_or(true),
+            enabled_pools,
+            custom_pools,
+            cex_symbols,
+            stable_prices,
+            pool_discovery_enabled,
+            pool_discovery_min_tvl_usd: src.parse("POOL_DISCOVERY_MIN_TVL_USD", "a decimal USD amount")?
+                .unwrap_or(DEFAULT_POOL_DISCOVERY_MIN_TVL_USD),
+            pool_discovery_interval_secs: src.parse("POOL_DISCOVERY_INTERVAL_SECS", "a whole number of seconds")?
+                .unwrap_or(DEFAULT_POOL_DISCOVERY_INTERVAL_SECS),
+            discovery_stables,
+            // Fair-value price basis
+            price_basis: src.parse("PRICE_BASIS", "one of cex_last, cex_mid, cex_microprice, composite, dex_twap")?
+                .unwrap_or_default(),
+            twap_window_secs,
+            max_twap_deviation_pct,
+            max_price_skew_ms: src.parse("MAX_PRICE_SKEW_MS", "a whole number of milliseconds")?
+                .unwrap_or(DEFAULT_MAX_PRICE_SKEW_MS),
+            basis_z_threshold,
+            basis_window_secs,
             // Market Making defaults
-            enable_market_making: env::var("ENABLE_MARKET_MAKING")
-                .unwrap_or_else(|_| "true".to_string())
-                .parse()
+            enable_market_making: src.parse("ENABLE_MARKET_MAKING", "a boolean")?
                 .unwrap_or(true),
-            base_spread_bps: env::var("BASE_SPREAD_BPS")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(DEFAULT_SPREAD_BPS)
-                .max(MIN_SPREAD_BPS)
-                .min(MAX_SPREAD_BPS),
-            max_position_size_eth: env::var("MAX_POSITION_SIZE_ETH")
-                .ok()
-                .and_then(|s| Decimal::from_str(&s).ok())
+            base_spread_bps,
+            max_position_size_eth: src.parse("MAX_POSITION_SIZE_ETH", "a decimal amount of ETH")?
                 .unwrap_or(dec!(5.0)),
-            inventory_target_ratio: env::var("INVENTORY_TARGET_RATIO")
-                .ok()
-                .and_then(|s| Decimal::from_str(&s).ok())
-                .unwrap_or(dec!(0.5)),
-            rebalance_threshold: env::var("REBALANCE_THRESHOLD")
-                .ok()
-                .and_then(|s| Decimal::from_str(&s).ok())
-                .unwrap_or(dec!(0.1)),
+            inventory_target_ratio,
+            rebalance_threshold,
+            enable_gauge_yield: src.parse("ENABLE_GAUGE_YIELD", "a boolean")?
+                .unwrap_or(false),
+            epoch_flip_window_secs,
             // Trade Execution Configuration
-            enable_trade_execution: env::var("ENABLE_TRADE_EXECUTION")
-                .unwrap_or_else(|_| "false".to_string())
-                .parse()
+            enable_trade_execution: !watch_only && src.parse("ENABLE_TRADE_EXECUTION", "a boolean")?
+                .unwrap_or(false),
+            network,
+            execution_network,
+            max_gas_price_gwei,
+            slippage_tolerance_bps,
+            private_key: src.string("PRIVATE_KEY"),
+            wallet_address: src.string("WALLET_ADDRESS"),
+            confirmation_tiers,
+            submission_mode,
+            private_rpc_url,
+            infinite_approvals: src.parse("INFINITE_APPROVALS", "a boolean")?
+                .unwrap_or(false),
+            // Gas Spike Deferral
+            gas_deferral_enabled: src.parse("ENABLE_GAS_DEFERRAL", "a boolean")?
+                .unwrap_or(true),
+            gas_spike_multiplier,
+            gas_deferral_ttl_secs: src.parse("GAS_DEFERRAL_TTL_SECS", "a whole number of seconds")?
+                .unwrap_or(DEFAULT_GAS_DEFERRAL_TTL_SECS),
+            // Oracle Cross-Check
+            oracle_check,
+            oracle_feed_address,
+            max_oracle_deviation_pct,
+            oracle_max_age_secs: src.parse("ORACLE_MAX_AGE_SECS", "a whole number of seconds")?
+                .unwrap_or(DEFAULT_ORACLE_MAX_AGE_SECS),
+            // Arbitrage Fee Model
+            binance_maker_fee_bps,
+            binance_taker_fee_bps,
+            binance_maker_orders: src.parse("BINANCE_MAKER_ORDERS", "a boolean")?
                 .unwrap_or(false),
-            network: env::var("NETWORK")
-                .unwrap_or_else(|_| "mainnet".to_string()),
-            execution_network: env::var("EXECUTION_NETWORK")
-                .unwrap_or_else(|_| "sepolia".to_string()),                
-            max_gas_price_gwei: env::var("MAX_GAS_PRICE_GWEI")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(DEFAULT_GAS_PRICE_GWEI)
-                .min(MAX_GAS_PRICE_GWEI),
-            slippage_tolerance_bps: env::var("SLIPPAGE_TOLERANCE_BPS")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(50) // 0.5% default
-                .min(MAX_SLIPPAGE_BPS),
-            private_key: env::var("PRIVATE_KEY").ok(),
+            dex_fee_bps,
+            transfer_cost_usd,
+            // Mainnet Execution Guards
+            i_understand_real_funds: src.parse("I_UNDERSTAND_REAL_FUNDS", "a boolean")?
+                .unwrap_or(false),
+            max_trade_notional_usd: src.parse("MAX_TRADE_NOTIONAL_USD", "a decimal USD amount")?
+                .unwrap_or(DEFAULT_MAX_TRADE_NOTIONAL_USD),
+            max_daily_loss_usd: src.parse("MAX_DAILY_LOSS_USD", "a decimal USD amount")?
+                .unwrap_or(DEFAULT_MAX_DAILY_LOSS_USD),
+            // Risk Manager
+            max_open_exposure_eth: src.parse("MAX_OPEN_EXPOSURE_ETH", "a decimal amount of ETH")?
+                .unwrap_or(DEFAULT_MAX_OPEN_EXPOSURE_ETH),
+            max_executions_per_hour: src.parse("MAX_EXECUTIONS_PER_HOUR", "a whole number of executions")?
+                .unwrap_or(DEFAULT_MAX_EXECUTIONS_PER_HOUR),
+            // Token Safety Simulation
+            token_safety_checks: src.parse("TOKEN_SAFETY_CHECKS", "a boolean")?
+                .unwrap_or(true),
+            anvil_path: src.string("ANVIL_PATH").unwrap_or_else(|| "anvil".to_string()),
+            anvil_fork_url: src.string("ANVIL_FORK_URL"),
+            // Signer Transaction Policy
+            signer_max_gas_limit: src.parse("SIGNER_MAX_GAS_LIMIT", "a whole number of gas units")?
+                .unwrap_or(DEFAULT_SIGNER_MAX_GAS_LIMIT),
+            signer_max_value_eth: src.parse("SIGNER_MAX_VALUE_ETH", "a decimal ETH amount")?
+                .unwrap_or(dec!(0)),
+            signer_allowed_calls,
+            // Execution Queue
+            max_concurrent_executions,
+            execution_queue_max_age_secs,
             // Volatility Configuration
-            volatility_threshold: env::var("VOLATILITY_THRESHOLD")
-                .ok()
-                .and_then(|s| Decimal::from_str(&s).ok())
-                .unwrap_or(dec!(5.0)), // 5% threshold
-            volatility_spread_multiplier: env::var("VOLATILITY_SPREAD_MULTIPLIER")
-                .ok()
-                .and_then(|s| Decimal::from_str(&s).ok())
+            volatility_threshold: src.parse("VOLATILITY_THRESHOLD", "a decimal percentage")?
+                .unwrap_or(volatility_estimator.default_threshold()),
+            volatility_spread_multiplier: src.parse("VOLATILITY_SPREAD_MULTIPLIER", "a decimal multiplier")?
                 .unwrap_or(dec!(2.0)), // 2x multiplier for high volatility
-        }
+            volatility_estimator,
+            ewma_lambda,
+            // Watch-only Configuration
+            watch_only,
+            dry_run: src.parse("DRY_RUN", "a boolean")?.unwrap_or(false),
+            simulation_seed: src.parse("SIMULATION_SEED", "an unsigned integer")?,
+            simulation_mode: src.parse("SIMULATION_MODE", "`random` or `fork`")?
+                .unwrap_or_default(),
+            poll_interval_secs: if watch_only {
+                poll_interval_secs.max(WATCH_ONLY_MIN_POLL_INTERVAL_SECS)
+            } else {
+                poll_interval_secs
+            },
+            adaptive_polling: src.parse("ADAPTIVE_POLLING", "a boolean")?.unwrap_or(true),
+            min_poll_interval_ms,
+            max_poll_interval_ms,
+            max_concurrent_pools,
+            monitor_trigger: src.parse("MONITOR_TRIGGER", "`interval` or `blocks`")?
+                .unwrap_or_default(),
+            ws_rpc_url: src.string("WS_RPC_URL"),
+            // CEX Price Source
+            cex_source,
+            cex_fixture_path,
+            cex_depth_levels,
+            // Rate Limits
+            rpc_requests_per_sec: src.parse("RPC_REQUESTS_PER_SEC", "a whole number of requests per second")?
+                .unwrap_or(DEFAULT_RPC_REQUESTS_PER_SEC),
+            binance_requests_per_sec: src.parse("BINANCE_REQUESTS_PER_SEC", "a whole number of requests per second")?
+                .unwrap_or(DEFAULT_BINANCE_REQUESTS_PER_SEC),
+            reserve_cache_ttl_ms: src.parse("RESERVE_CACHE_TTL_MS", "a whole number of milliseconds")?
+                .unwrap_or(DEFAULT_RESERVE_CACHE_TTL_MS),
+            // Status API
+            api_bind_addr: src.string("API_BIND_ADDR"),
+            // Fills Reconciler Configuration
+            reconciler_url: src.string("RECONCILER_URL"),
+            reconciler_api_key: src.string("RECONCILER_API_KEY"),
+            // Storage Configuration
+            sqlite_path: src.string("SQLITE_PATH"),
+            record_decisions: src.parse("RECORD_DECISIONS", "a boolean")?
+                .unwrap_or(false),
+            record_spreads: src.parse("RECORD_SPREADS", "a boolean")?
+                .unwrap_or(true),
+            record_prices: src.parse("RECORD_PRICES", "a boolean")?
+                .unwrap_or(true),
+            checkpoint_interval_secs: src.parse("CHECKPOINT_INTERVAL_SECS", "a whole number of seconds")?
+                .unwrap_or(DEFAULT_CHECKPOINT_INTERVAL_SECS),
+            state_save_interval_secs: src.parse("STATE_SAVE_INTERVAL_SECS", "a whole number of seconds")?
+                .unwrap_or(DEFAULT_STATE_SAVE_INTERVAL_SECS),
+            health_check_interval_secs: src.parse("HEALTH_CHECK_INTERVAL_SECS", "a whole number of seconds")?
+                .unwrap_or(DEFAULT_HEALTH_CHECK_INTERVAL_SECS),
+            stats_interval_secs: src.parse("STATS_INTERVAL_SECS", "a whole number of seconds")?
+                .unwrap_or(DEFAULT_STATS_INTERVAL_SECS),
+            stats_every_opportunities: src.parse("STATS_EVERY_OPPORTUNITIES", "a whole number of opportunities")?
+                .unwrap_or(DEFAULT_STATS_EVERY_OPPORTUNITIES),
+            stats_every_signals: src.parse("STATS_EVERY_SIGNALS", "a whole number of signals")?
+                .unwrap_or(DEFAULT_STATS_EVERY_SIGNALS),
+            stats_every_executions: src.parse("STATS_EVERY_EXECUTIONS", "a whole number of executions")?
+                .unwrap_or(DEFAULT_STATS_EVERY_EXECUTIONS),
+            history_memory_samples: src.parse("HISTORY_MEMORY_SAMPLES", "a whole number of samples")?
+                .unwrap_or(DEFAULT_HISTORY_MEMORY_SAMPLES)
+                .max(MIN_HISTORY_MEMORY_SAMPLES),
+            history_retention_days: src.parse("HISTORY_RETENTION_DAYS", "a whole number of days")?
+                .unwrap_or(DEFAULT_HISTORY_RETENTION_DAYS),
+            output_compress_after_days: src.parse("OUTPUT_COMPRESS_AFTER_DAYS", "a whole number of days")?
+                .unwrap_or(DEFAULT_OUTPUT_COMPRESS_AFTER_DAYS),
+            output_retention_days: src.parse("OUTPUT_RETENTION_DAYS", "a whole number of days")?
+                .unwrap_or(0),
+            output_max_size_mb: src.parse("OUTPUT_MAX_SIZE_MB", "a whole number of megabytes")?
+                .unwrap_or(0),
+            // Paper Trading
+            paper_trading: src.parse("PAPER_TRADING", "a boolean")?
+                .unwrap_or(true),
+            paper_starting_weth: src.parse("PAPER_STARTING_WETH", "a decimal amount of WETH")?
+                .unwrap_or(DEFAULT_PAPER_STARTING_WETH),
+            paper_starting_usdc: src.parse("PAPER_STARTING_USDC", "a decimal USDC amount")?
+                .unwrap_or(DEFAULT_PAPER_STARTING_USDC),
+            // Binance Spot Testnet Configuration
+            binance_testnet_api_key: src.string("BINANCE_TESTNET_API_KEY"),
+            binance_testnet_api_secret: src.string("BINANCE_TESTNET_API_SECRET"),
+            binance_testnet_symbol: src.string("BINANCE_TESTNET_SYMBOL")
+                .unwrap_or_else(|| "ETHUSDT".to_string()),
+            // Leader Election Configuration
+            instance_id: src.string("INSTANCE_ID")
+                .unwrap_or_else(|| format!("instance-{}", uuid::Uuid::new_v4())),
+            leader_lease_path: src.string("LEADER_LEASE_PATH"),
+            leader_lease_secs: src.parse("LEADER_LEASE_SECS", "a whole number of seconds")?
+                .unwrap_or(DEFAULT_LEADER_LEASE_SECS),
+            config_file: src.file_path().map(str::to_string),
+        })
     }
 }
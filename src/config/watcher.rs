@@ -0,0 +1,54 @@
+//! Config reload triggers: SIGHUP and config file changes
+
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use tracing::{info, warn};
+use crate::{
+    control::ControlSender,
+    types::ControlEvent,
+};
+
+/// How often the config file's modification time is checked
+pub const CONFIG_WATCH_INTERVAL_SECS: u64 = 5;
+
+fn modified_at(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(Path::new(path)).ok()?.modified().ok()
+}
+
+/// Send `ControlEvent::ReloadConfig` on SIGHUP and whenever `path` changes
+pub fn spawn_config_watcher(path: Option<String>, control: ControlSender) {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let control = control.clone();
+        tokio::spawn(async move {
+            let mut hangup = match signal(SignalKind::hangup()) {
+                Ok(hangup) => hangup,
+                Err(e) => {
+                    warn!("Failed to listen for SIGHUP, config reload on signal disabled: {}", e);
+                    return;
+                }
+            };
+            while hangup.recv().await.is_some() {
+                control.send(ControlEvent::ReloadConfig { reason: "SIGHUP".to_string() });
+            }
+        });
+    }
+
+    if let Some(path) = path {
+        info!("👁️  Watching {} for config changes", path);
+        tokio::spawn(async move {
+            let mut last_modified = modified_at(&path);
+            let mut interval = tokio::time::interval(Duration::from_secs(CONFIG_WATCH_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                let modified = modified_at(&path);
+                if modified.is_some() && modified != last_modified {
+                    last_modified = modified;
+                    control.send(ControlEvent::ReloadConfig { reason: format!("{} changed", path) });
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,49 @@
+//! Dedicated high-priority channel for control-plane events
+
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tracing::warn;
+use crate::types::ControlEvent;
+
+/// Cloneable handle used to send control events from anywhere in the bot
+#[derive(Clone)]
+pub struct ControlSender {
+    tx: UnboundedSender<ControlEvent>,
+}
+
+impl ControlSender {
+    pub fn send(&self, event: ControlEvent) {
+        if self.tx.send(event).is_err() {
+            warn!("Control channel closed, event dropped");
+        }
+    }
+
+    pub fn pause(&self, reason: &str) {
+        self.send(ControlEvent::Pause { reason: reason.to_string() });
+    }
+
+    pub fn resume(&self) {
+        self.send(ControlEvent::Resume);
+    }
+
+    pub fn kill(&self, reason: &str) {
+        self.send(ControlEvent::KillSwitch { reason: reason.to_string() });
+    }
+}
+
+/// Receiving side, polled ahead of price updates in the main loop.
+///
+/// The channel is unbounded so safety actions never block behind a full queue.
+pub struct ControlReceiver {
+    rx: UnboundedReceiver<ControlEvent>,
+}
+
+impl ControlReceiver {
+    pub async fn recv(&mut self) -> Option<ControlEvent> {
+        self.rx.recv().await
+    }
+}
+
+pub fn control_channel() -> (ControlSender, ControlReceiver) {
+    let (tx, rx) = unbounded_channel();
+    (ControlSender { tx }, ControlReceiver { rx })
+}
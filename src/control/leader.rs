@@ -0,0 +1,269 @@
+//! File lease leader election for multi-replica deployments
+//!
+//! All replicas monitor; only the holder of an unexpired lease executes.
+//! A background task renews the lease every third of its duration, and
+//! another replica takes over once the leader stops renewing and its lease
+//! expires. Reading, checking and writing the lease happen under an
+//! exclusive lock file, so two replicas never both take an expired lease.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use crate::config::Config;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lease {
+    pub holder: String,
+    pub expires_at_ms: i64,
+}
+
+/// Whether this instance currently holds the lease, shared with the
+/// execution queue so it can re-check before each job
+#[derive(Debug, Clone)]
+pub struct LeaderFlag(Arc<AtomicBool>);
+
+impl LeaderFlag {
+    pub fn is_leader(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    fn set(&self, leading: bool) {
+        self.0.store(leading, Ordering::Release);
+    }
+}
+
+/// The lock file, removed when dropped
+struct LeaseLock(PathBuf);
+
+impl Drop for LeaseLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+pub struct LeaderElection {
+    path: PathBuf,
+    lock_path: PathBuf,
+    instance_id: String,
+    lease_duration_ms: i64,
+    /// Expiry of the lease this instance last wrote
+    held_until_ms: Option<i64>,
+    flag: LeaderFlag,
+}
+
+impl LeaderElection {
+    /// Returns None when LEADER_LEASE_PATH is unset (single instance, always leader)
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let path = config.leader_lease_path.as_ref()?;
+        // Leases must outlive several poll intervals to avoid flapping, and
+        // the longest execution so a lease never lapses mid-trade
+        let lease_secs = config.leader_lease_secs
+            .max(config.slowest_poll_interval().as_secs_f64().ceil() as u64 * 3)
+            .max(config.longest_execution().as_secs() + 1);
+        Some(Self::new(PathBuf::from(path), config.instance_id.clone(), Duration::from_secs(lease_secs)))
+    }
+
+    pub fn new(path: PathBuf, instance_id: String, lease_duration: Duration) -> Self {
+        let lock_path = path.with_extension("lock");
+        Self {
+            path,
+            lock_path,
+            instance_id,
+            lease_duration_ms: lease_duration.as_millis() as i64,
+            held_until_ms: None,
+            flag: LeaderFlag(Arc::new(AtomicBool::new(false))),
+        }
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    pub fn lease_duration(&self) -> Duration {
+        Duration::from_millis(self.lease_duration_ms as u64)
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.flag.is_leader()
+    }
+
+    pub fn flag(&self) -> LeaderFlag {
+        self.flag.clone()
+    }
+
+    fn read_lease(&self) -> Option<Lease> {
+        let data = fs::read_to_string(&self.path).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
+    fn write_lease(&self, lease: &Lease) -> Result<()> {
+        // Write then rename so readers never see a partial lease
+        let tmp = self.path.with_extension(format!("{}.tmp", self.instance_id));
+        fs::write(&tmp, serde_json::to_string(lease)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Failed to replace {}", self.path.display()))?;
+        Ok(())
+    }
+
+    /// Take the lock file; None while another replica holds it. A lock
+    /// older than a lease was left by a crashed replica and is broken.
+    fn lock(&self) -> Result<Option<LeaseLock>> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&self.lock_path) {
+                Ok(_) => return Ok(Some(LeaseLock(self.lock_path.clone()))),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    let stale = fs::metadata(&self.lock_path)
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                        .is_some_and(|age| age > self.lease_duration());
+                    if !stale {
+                        return Ok(None);
+                    }
+                    warn!("Breaking stale leader lock {}", self.lock_path.display());
+                    let _ = fs::remove_file(&self.lock_path);
+                }
+                Err(e) => return Err(e).with_context(|| format!("Failed to create {}", self.lock_path.display())),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Acquire or renew the lease, returning whether this instance leads
+    pub fn try_acquire(&mut self) -> bool {
+        let now = Utc::now().timestamp_millis();
+        let leading = match self.lock() {
+            Ok(Some(_lock)) => {
+                let available = match self.read_lease() {
+                    Some(lease) => lease.holder == self.instance_id || lease.expires_at_ms <= now,
+                    None => true,
+                };
+                available && {
+                    let lease = Lease {
+                        holder: self.instance_id.clone(),
+                        expires_at_ms: now + self.lease_duration_ms,
+                    };
+                    match self.write_lease(&lease) {
+                        Ok(()) => {
+                            self.held_until_ms = Some(lease.expires_at_ms);
+                            true
+                        }
+                        Err(e) => {
+                            warn!("Failed to write leader lease: {}", e);
+                            false
+                        }
+                    }
+                }
+            }
+            // Another replica is mid-update; a lease we already hold stays ours until it expires
+            Ok(None) => self.held_until_ms.is_some_and(|until| until > now),
+            Err(e) => {
+                warn!("Failed to lock leader lease: {}", e);
+                self.held_until_ms.is_some_and(|until| until > now)
+            }
+        };
+        if !leading {
+            self.held_until_ms = None;
+        }
+
+        if leading != self.is_leader() {
+            if leading {
+                info!("👑 {} acquired execution leadership", self.instance_id);
+            } else {
+                warn!("🔁 {} lost execution leadership, monitoring only", self.instance_id);
+            }
+        }
+        self.flag.set(leading);
+        leading
+    }
+
+    /// Give up the lease on shutdown so a standby can take over immediately
+    pub fn release(&mut self) {
+        if self.is_leader() {
+            match self.lock() {
+                Ok(Some(_lock)) => {
+                    if self.read_lease().is_some_and(|l| l.holder == self.instance_id) {
+                        if let Err(e) = fs::remove_file(&self.path) {
+                            warn!("Failed to release leader lease: {}", e);
+                        }
+                    }
+                }
+                Ok(None) => warn!("Leader lease is locked, leaving it to expire"),
+                Err(e) => warn!("Failed to lock leader lease for release: {}", e),
+            }
+        }
+        self.held_until_ms = None;
+        self.flag.set(false);
+    }
+
+    /// Renew the lease every third of its duration on a background task
+    pub fn spawn_renewal(mut self) -> LeaderRenewal {
+        let flag = self.flag();
+        let (stop_tx, mut stop_rx) = oneshot::channel();
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval((self.lease_duration() / 3).max(Duration::from_millis(100)));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        self.try_acquire();
+                    }
+                    _ = &mut stop_rx => break,
+                }
+            }
+            self.release();
+        });
+        LeaderRenewal { flag, stop: Some(stop_tx), task: Some(task) }
+    }
+}
+
+/// Handle to the background lease renewal
+pub struct LeaderRenewal {
+    flag: LeaderFlag,
+    stop: Option<oneshot::Sender<()>>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl LeaderRenewal {
+    pub fn is_leader(&self) -> bool {
+        self.flag.is_leader()
+    }
+
+    pub fn flag(&self) -> LeaderFlag {
+        self.flag.clone()
+    }
+
+    /// Stop renewing and give up the lease
+    pub async fn release(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        if let Some(task) = self.task.take() {
+            if let Err(e) = task.await {
+                warn!("Leader renewal task failed: {}", e);
+            }
+        }
+    }
+}
+
+impl Drop for LeaderRenewal {
+    fn drop(&mut self) {
+        // Dropped without release (e.g. startup failed): the task still gives up the lease
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+    }
+}
@@ -0,0 +1,7 @@
+//! Control-plane events (pause, kill switch, breaker trips) and leader election
+
+pub mod channel;
+pub mod leader;
+
+pub use channel::*;
+pub use leader::*;
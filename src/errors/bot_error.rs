@@ -2,7 +2,6 @@
 
 use alloy::primitives::Address;
 use rust_decimal::Decimal;
-use std::error::Error;
 use std::time::Duration;
 use thiserror::Error;
 
@@ -24,9 +23,10 @@ pub enum BotError {
         source: anyhow::Error,
     },
     
-    #[error("Price validation failed: {source} price ${price} is invalid - {reason}")]
+    #[error("Price validation failed: {feed} price ${price} is invalid - {reason}")]
     PriceValidation {
-        source: Box<dyn Error + Send + Sync>,
+        /// Where the price came from, e.g. "Binance ETHUSDT"
+        feed: String,
         price: Decimal,
         reason: String,
     },
@@ -44,6 +44,15 @@ pub enum BotError {
         source: anyhow::Error,
     },
     
+    #[error("Insufficient balance: {wallet} holds {available} {asset}, needs {required}")]
+    InsufficientBalance {
+        wallet: Address,
+        /// `ETH` for gas, else the token symbol
+        asset: String,
+        required: Decimal,
+        available: Decimal,
+    },
+    
     #[error("Circuit breaker active: {reason}")]
     CircuitBreakerOpen {
         reason: String,
@@ -52,7 +61,3 @@ pub enum BotError {
 }
 
 pub type BotResult<T> = Result<T, BotError>;
-
-// implement a safe send and sync trait for BotError to allow for cross-thread communication
-unsafe impl Send for BotError {}
-unsafe impl Sync for BotError {}
\ No newline at end of file
@@ -4,13 +4,22 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{error, info};
-use crate::config::CONFIG;
+use crate::{
+    config::CONFIG,
+    control::ControlSender,
+    types::{CircuitBreakerState, ControlEvent},
+};
 
 pub struct CircuitBreaker {
     pub consecutive_errors: Arc<RwLock<u32>>,
     pub is_open: Arc<RwLock<bool>>,
     pub last_error_time: Arc<RwLock<Option<Instant>>>,
     pub cooldown_duration: Duration,
+    /// Consecutive errors that open the breaker; `MAX_CONSECUTIVE_ERRORS` config when unset
+    pub max_errors: Option<u32>,
+    /// Scope named in logs and trip events
+    pub name: Option<String>,
+    control: Option<ControlSender>,
 }
 
 impl CircuitBreaker {
@@ -20,9 +29,28 @@ impl CircuitBreaker {
             is_open: Arc::new(RwLock::new(false)),
             last_error_time: Arc::new(RwLock::new(None)),
             cooldown_duration: Duration::from_secs(cooldown_secs),
+            max_errors: None,
+            name: None,
+            control: None,
         }
     }
 
+    pub fn with_max_errors(mut self, max_errors: u32) -> Self {
+        self.max_errors = Some(max_errors);
+        self
+    }
+
+    pub fn named(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Report breaker trips on the control channel
+    pub fn with_control(mut self, control: ControlSender) -> Self {
+        self.control = Some(control);
+        self
+    }
+
     pub async fn record_success(&self) {
         *self.consecutive_errors.write().await = 0;
         *self.is_open.write().await = false;
@@ -32,15 +60,36 @@ impl CircuitBreaker {
         let mut errors = self.consecutive_errors.write().await;
         *errors += 1;
         
-        if *errors >= CONFIG.max_consecutive_errors {
-            *self.is_open.write().await = true;
+        let max_errors = self.max_errors.unwrap_or_else(|| CONFIG.get().max_consecutive_errors);
+        if *errors >= max_errors {
+            let was_open = std::mem::replace(&mut *self.is_open.write().await, true);
             *self.last_error_time.write().await = Some(Instant::now());
-            error!("Circuit breaker OPEN after {} consecutive errors", *errors);
+            let reason = match &self.name {
+                Some(name) => format!("{}: {} consecutive errors", name, *errors),
+                None => format!("{} consecutive errors", *errors),
+            };
+            error!("Circuit breaker OPEN after {}", reason);
+            if let (false, Some(control)) = (was_open, &self.control) {
+                control.send(ControlEvent::BreakerTripped { reason });
+            }
             return true;
         }
         false
     }
 
+    pub async fn state(&self) -> CircuitBreakerState {
+        let open = *self.is_open.read().await;
+        let cooldown_remaining_secs = match *self.last_error_time.read().await {
+            Some(last_error) if open => Some(self.cooldown_duration.saturating_sub(last_error.elapsed()).as_secs()),
+            _ => None,
+        };
+        CircuitBreakerState {
+            open,
+            consecutive_errors: *self.consecutive_errors.read().await,
+            cooldown_remaining_secs,
+        }
+    }
+
     pub async fn can_proceed(&self) -> bool {
         let is_open = *self.is_open.read().await;
         if !is_open {
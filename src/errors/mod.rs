@@ -3,7 +3,9 @@
 pub mod bot_error;
 pub mod recovery;
 pub mod circuit_breaker;
+pub mod registry;
 
 pub use bot_error::*;
 pub use recovery::*;
 pub use circuit_breaker::*;
+pub use registry::*;
@@ -1,26 +1,75 @@
 //! Error recovery strategies
+//!
+//! Each error class maps to a strategy. The built-in table can be overridden
+//! per class with `RECOVERY_STRATEGIES`, and a class's error count resets once
+//! it has been quiet for `RECOVERY_ERROR_DECAY_SECS`.
 
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::Level;
+use crate::config::{Config, DEFAULT_RECOVERY_ERROR_DECAY_SECS};
 use super::BotError;
 
+/// Error classes strategies can be configured for
+pub const ERROR_CLASSES: &[&str] = &[
+    "network_timeout",
+    "invalid_price",
+    "contract_error",
+    "low_liquidity",
+    "parse_error",
+    "circuit_breaker",
+    "insufficient_balance",
+];
+
+/// Errors of one class since it was last quiet
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorCount {
+    pub count: u32,
+    pub last_seen: Instant,
+}
+
 pub struct ErrorRecovery {
-    pub error_counts: Arc<RwLock<HashMap<String, u32>>>,
+    pub error_counts: Arc<RwLock<HashMap<String, ErrorCount>>>,
     pub recovery_strategies: HashMap<String, RecoveryStrategy>,
+    /// Quiet period after which a class's count resets; zero never resets
+    pub error_decay: Duration,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum RecoveryStrategy {
     Retry { max_attempts: u32, delay_ms: u64 },
     Fallback { alternative_source: String },
     Skip { log_level: Level },
-    #[allow(dead_code)]
     Shutdown { reason: String },
 }
 
+impl RecoveryStrategy {
+    /// Parse `retry:<attempts>:<delay_ms>`, `fallback:<source>`,
+    /// `skip:<log level>` or `shutdown:<reason>`
+    pub fn from_spec(spec: &str) -> Option<Self> {
+        let (kind, args) = spec.trim().split_once(':').unwrap_or((spec.trim(), ""));
+        let args = args.trim();
+        match kind.trim() {
+            "retry" => {
+                let (attempts, delay_ms) = args.split_once(':')?;
+                Some(Self::Retry {
+                    max_attempts: attempts.trim().parse().ok()?,
+                    delay_ms: delay_ms.trim().parse().ok()?,
+                })
+            }
+            "fallback" if !args.is_empty() => Some(Self::Fallback { alternative_source: args.to_string() }),
+            "skip" => Some(Self::Skip {
+                log_level: if args.is_empty() { Level::WARN } else { Level::from_str(args).ok()? },
+            }),
+            "shutdown" if !args.is_empty() => Some(Self::Shutdown { reason: args.to_string() }),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum RecoveryAction {
     Retry { delay: Duration },
@@ -59,18 +108,55 @@ impl ErrorRecovery {
         Self {
             error_counts: Arc::new(RwLock::new(HashMap::new())),
             recovery_strategies: strategies,
+            error_decay: Duration::from_secs(DEFAULT_RECOVERY_ERROR_DECAY_SECS),
         }
     }
     
+    /// Built-in table with the configured per-class overrides and decay
+    pub fn from_config(config: &Config) -> Self {
+        Self::new()
+            .with_strategies(config.recovery_strategies.iter().cloned())
+            .with_error_decay(Duration::from_secs(config.recovery_error_decay_secs))
+    }
+    
+    /// Replace the strategy of each given error class
+    pub fn with_strategies(mut self, strategies: impl IntoIterator<Item = (String, RecoveryStrategy)>) -> Self {
+        self.recovery_strategies.extend(strategies);
+        self
+    }
+    
+    pub fn with_error_decay(mut self, error_decay: Duration) -> Self {
+        self.error_decay = error_decay;
+        self
+    }
+    
+    /// Errors of `error_type` since it was last quiet
+    pub async fn error_count(&self, error_type: &str) -> u32 {
+        self.error_counts.read().await
+            .get(error_type)
+            .filter(|c| !self.decayed(c, Instant::now()))
+            .map_or(0, |c| c.count)
+    }
+    
+    fn decayed(&self, count: &ErrorCount, now: Instant) -> bool {
+        !self.error_decay.is_zero() && now.duration_since(count.last_seen) >= self.error_decay
+    }
+    
     pub async fn handle_error(&self, error: &BotError, _context: &str) -> RecoveryAction {
         let error_type = self.classify_error(error);
+        let now = Instant::now();
         let mut counts = self.error_counts.write().await;
-        let count = counts.entry(error_type.clone()).or_insert(0);
-        *count += 1;
+        let entry = counts.entry(error_type.clone()).or_insert(ErrorCount { count: 0, last_seen: now });
+        if self.decayed(entry, now) {
+            entry.count = 0;
+        }
+        entry.count += 1;
+        entry.last_seen = now;
+        let count = entry.count;
         
         match self.recovery_strategies.get(&error_type) {
             Some(RecoveryStrategy::Retry { max_attempts, delay_ms }) => {
-                if *count <= *max_attempts {
+                if count <= *max_attempts {
                     RecoveryAction::Retry {
                         delay: Duration::from_millis(*delay_ms),
                     }
@@ -105,6 +191,7 @@ impl ErrorRecovery {
             BotError::InsufficientLiquidity { .. } => "low_liquidity".to_string(),
             BotError::DataParsing { .. } => "parse_error".to_string(),
             BotError::CircuitBreakerOpen { .. } => "circuit_breaker".to_string(),
+            BotError::InsufficientBalance { .. } => "insufficient_balance".to_string(),
         }
     }
 }
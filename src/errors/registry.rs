@@ -0,0 +1,100 @@
+//! Circuit breakers scoped to the CEX feed, the RPC and each pool
+//!
+//! Each scope counts errors and cools down on its own, so a flaky Binance
+//! endpoint or one broken pool no longer stops on-chain monitoring of the rest.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::{
+    config::Config,
+    control::ControlSender,
+    errors::CircuitBreaker,
+    types::CircuitBreakerStatus,
+};
+
+/// Consecutive errors that open a breaker and how long it stays open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BreakerLimits {
+    pub max_errors: u32,
+    pub cooldown_secs: u64,
+}
+
+impl BreakerLimits {
+    fn breaker(&self, name: &str) -> CircuitBreaker {
+        CircuitBreaker::new(self.cooldown_secs)
+            .with_max_errors(self.max_errors)
+            .named(name)
+    }
+}
+
+pub struct CircuitBreakerRegistry {
+    /// Binance price feeds; while open, cycles run on the last known price without executing
+    pub cex: CircuitBreaker,
+    /// Chain RPC; while open, monitoring cycles are skipped
+    pub rpc: CircuitBreaker,
+    pool_limits: BreakerLimits,
+    pools: RwLock<HashMap<String, Arc<CircuitBreaker>>>,
+    control: Option<ControlSender>,
+}
+
+impl CircuitBreakerRegistry {
+    pub fn new(cex: BreakerLimits, rpc: BreakerLimits, pool: BreakerLimits) -> Self {
+        Self {
+            cex: cex.breaker("cex"),
+            rpc: rpc.breaker("rpc"),
+            pool_limits: pool,
+            pools: RwLock::new(HashMap::new()),
+            control: None,
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(
+            BreakerLimits { max_errors: config.cex_breaker_max_errors, cooldown_secs: config.cex_breaker_cooldown_secs },
+            BreakerLimits { max_errors: config.rpc_breaker_max_errors, cooldown_secs: config.rpc_breaker_cooldown_secs },
+            BreakerLimits { max_errors: config.pool_breaker_max_errors, cooldown_secs: config.pool_breaker_cooldown_secs },
+        )
+    }
+
+    /// Report trips of every breaker, including pools added later, on the control channel
+    pub fn with_control(mut self, control: ControlSender) -> Self {
+        self.cex = self.cex.with_control(control.clone());
+        self.rpc = self.rpc.with_control(control.clone());
+        self.control = Some(control);
+        self
+    }
+
+    /// Breaker for `pool`, created on first use
+    pub async fn pool(&self, pool: &str) -> Arc<CircuitBreaker> {
+        if let Some(breaker) = self.pools.read().await.get(pool) {
+            return breaker.clone();
+        }
+        let mut pools = self.pools.write().await;
+        pools.entry(pool.to_string())
+            .or_insert_with(|| {
+                let breaker = self.pool_limits.breaker(&format!("pool:{}", pool));
+                Arc::new(match &self.control {
+                    Some(control) => breaker.with_control(control.clone()),
+                    None => breaker,
+                })
+            })
+            .clone()
+    }
+
+    pub async fn state(&self) -> CircuitBreakerStatus {
+        let pool_breakers: Vec<_> = self.pools.read().await
+            .iter()
+            .map(|(pool, breaker)| (pool.clone(), breaker.clone()))
+            .collect();
+        let mut pools = BTreeMap::new();
+        for (pool, breaker) in pool_breakers {
+            pools.insert(pool, breaker.state().await);
+        }
+        CircuitBreakerStatus {
+            cex: self.cex.state().await,
+            rpc: self.rpc.state().await,
+            pools,
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! ERC20 allowances from the execution wallet to the routers
+//!
+//! A router swap pulls its input token with `transferFrom`, so the wallet
+//! must have approved the router for at least the swap amount. Known
+//! allowances are cached per (token, router) and drawn down as swaps spend
+//! them; the chain is only queried, and `approve()` only sent, when the
+//! cached amount cannot cover the next swap.
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+    sol_types::SolCall,
+};
+use anyhow::Result;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use crate::network::{call_contract, IAerodromeRouter, IERC20, IUniswapV2Router};
+
+/// `approve()` on a standard ERC20, with headroom for proxied tokens
+pub const APPROVAL_GAS_LIMIT: u64 = 100_000;
+
+/// Amount to approve for a swap needing `required`: exactly that, or the
+/// maximum so later swaps of the token need no further approvals
+pub fn approval_amount(required: U256, infinite: bool) -> U256 {
+    if infinite { U256::MAX } else { required }
+}
+
+pub fn approve_calldata(spender: Address, amount: U256) -> Vec<u8> {
+    IERC20::approveCall { spender, amount }.abi_encode()
+}
+
+/// Input token and amount of an Aerodrome or Uniswap V2 router swap
+pub fn router_swap_input(calldata: &[u8]) -> Option<(Address, U256)> {
+    if let Ok(call) = IAerodromeRouter::swapExactTokensForTokensCall::abi_decode(calldata, true) {
+        return Some((call.routes.first()?.from, call.amountIn));
+    }
+    let call = IUniswapV2Router::swapExactTokensForTokensCall::abi_decode(calldata, true).ok()?;
+    Some((*call.path.first()?, call.amountIn))
+}
+
+/// Output token and recipient of an Aerodrome or Uniswap V2 router swap
+pub fn router_swap_output(calldata: &[u8]) -> Option<(Address, Address)> {
+    if let Ok(call) = IAerodromeRouter::swapExactTokensForTokensCall::abi_decode(calldata, true) {
+        return Some((call.routes.last()?.to, call.to));
+    }
+    let call = IUniswapV2Router::swapExactTokensForTokensCall::abi_decode(calldata, true).ok()?;
+    Some((*call.path.last()?, call.to))
+}
+
+/// ERC20 `allowance(owner, spender)` via eth_call
+pub async fn get_allowance(provider: &dyn Provider, token: Address, owner: Address, spender: Address) -> Result<U256> {
+    Ok(call_contract(provider, token, IERC20::allowanceCall { owner, spender }).await?._0)
+}
+
+/// Last known allowance of each (token, spender) pair
+#[derive(Default)]
+pub struct AllowanceCache {
+    allowances: RwLock<HashMap<(Address, Address), U256>>,
+}
+
+impl AllowanceCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// True if the cached allowance already covers `amount`
+    pub async fn covers(&self, token: Address, spender: Address, amount: U256) -> bool {
+        self.allowances.read().await
+            .get(&(token, spender))
+            .is_some_and(|allowance| *allowance >= amount)
+    }
+
+    pub async fn record(&self, token: Address, spender: Address, allowance: U256) {
+        self.allowances.write().await.insert((token, spender), allowance);
+    }
+
+    /// Draw down after a swap spent `amount`. Infinite approvals are not
+    /// decremented by the token, so they stay as they are.
+    pub async fn spend(&self, token: Address, spender: Address, amount: U256) {
+        if let Some(allowance) = self.allowances.write().await.get_mut(&(token, spender)) {
+            if *allowance != U256::MAX {
+                *allowance = allowance.saturating_sub(amount);
+            }
+        }
+    }
+
+    /// Forget a pair, e.g. after a swap reverted, so the next swap re-reads it
+    pub async fn invalidate(&self, token: Address, spender: Address) {
+        self.allowances.write().await.remove(&(token, spender));
+    }
+}
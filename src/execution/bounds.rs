@@ -0,0 +1,49 @@
+//! Swap minimum output and deadline scaled by volatility
+//!
+//! Calm markets get a tight `amountOutMin` and a short deadline, so a swap
+//! that lands late or after the price moved reverts instead of filling
+//! badly. Turbulent markets widen both so a real arb still fills.
+//! `SLIPPAGE_TOLERANCE_BPS` is the bound in the moderate band; no band goes
+//! past the `MAX_SLIPPAGE_BPS` cap.
+
+use alloy::{
+    primitives::U256,
+    sol_types::SolCall,
+};
+use anyhow::Result;
+use std::time::SystemTime;
+use crate::{
+    config::MAX_SLIPPAGE_BPS,
+    network::{IAerodromeRouter, IUniswapV2Router},
+    types::{SwapBounds, VolatilityImpact},
+};
+
+/// Slippage bound and deadline for a swap sent in `impact` volatility
+pub fn swap_bounds(slippage_tolerance_bps: u32, impact: VolatilityImpact) -> SwapBounds {
+    // (numerator, denominator) applied to the configured slippage
+    let ((num, den), deadline_secs) = match impact {
+        VolatilityImpact::Low => ((1, 2), 60),
+        VolatilityImpact::Moderate => ((1, 1), 120),
+        VolatilityImpact::High => ((3, 2), 300),
+        VolatilityImpact::Extreme => ((2, 1), 600),
+    };
+    SwapBounds {
+        slippage_bps: (slippage_tolerance_bps * num / den).clamp(1, MAX_SLIPPAGE_BPS),
+        deadline_secs,
+    }
+}
+
+/// Router `deadline` argument: `bounds.deadline_secs` from now
+pub fn swap_deadline(bounds: &SwapBounds) -> Result<U256> {
+    let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?.as_secs();
+    Ok(U256::from(now + bounds.deadline_secs))
+}
+
+/// `amountOutMin` of an Aerodrome or Uniswap V2 router swap
+pub fn router_swap_min_out(calldata: &[u8]) -> Option<U256> {
+    if let Ok(call) = IAerodromeRouter::swapExactTokensForTokensCall::abi_decode(calldata, true) {
+        return Some(call.amountOutMin);
+    }
+    IUniswapV2Router::swapExactTokensForTokensCall::abi_decode(calldata, true).ok()
+        .map(|call| call.amountOutMin)
+}
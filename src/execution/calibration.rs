@@ -0,0 +1,146 @@
+//! Simulation slippage and success rates calibrated from past executions
+//!
+//! Each volatility band starts from the simulator's fixed table, weighted as
+//! `PRIOR_WEIGHT` executions. Submitted executions recorded in
+//! `output/executions` pull the band towards their observed success rate
+//! and profit shortfall (actual vs expected profit, in bps).
+
+use chrono::{Duration, Utc};
+use rust_decimal::prelude::*;
+use serde::Serialize;
+use std::path::Path;
+use tracing::{info, warn};
+use crate::{
+    storage::load_trade_executions_since,
+    types::{ExecutionStatus, TradeExecution, VolatilityImpact},
+};
+
+/// Days of recorded executions read at startup
+pub const CALIBRATION_LOOKBACK_DAYS: i64 = 30;
+/// Executions the fixed table counts for against observed ones
+pub const PRIOR_WEIGHT: f64 = 20.0;
+const BASE_SLIPPAGE_BPS: f64 = 25.0;
+
+const BANDS: [VolatilityImpact; 4] = [
+    VolatilityImpact::Low,
+    VolatilityImpact::Moderate,
+    VolatilityImpact::High,
+    VolatilityImpact::Extreme,
+];
+
+fn band(impact: VolatilityImpact) -> usize {
+    BANDS.iter().position(|b| *b == impact).unwrap_or(0)
+}
+
+/// Success rate the simulator assumes without observations
+pub fn prior_success_rate(impact: VolatilityImpact) -> f64 {
+    match impact {
+        VolatilityImpact::Low => 0.95,
+        VolatilityImpact::Moderate => 0.85,
+        VolatilityImpact::High => 0.70,
+        VolatilityImpact::Extreme => 0.50,
+    }
+}
+
+/// Slippage the simulator assumes without observations
+pub fn prior_slippage_bps(impact: VolatilityImpact) -> f64 {
+    BASE_SLIPPAGE_BPS + match impact {
+        VolatilityImpact::Low => 0.0,
+        VolatilityImpact::Moderate => 25.0,
+        VolatilityImpact::High => 75.0,
+        VolatilityImpact::Extreme => 150.0,
+    }
+}
+
+/// Observed outcomes in one volatility band
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct BandOutcomes {
+    pub attempts: u64,
+    pub successes: u64,
+    /// Successful executions with both an expected and an actual profit
+    pub shortfall_samples: u64,
+    pub shortfall_bps_sum: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExecutionCalibration {
+    bands: [BandOutcomes; 4],
+}
+
+impl ExecutionCalibration {
+    pub fn from_executions(executions: &[TradeExecution]) -> Self {
+        let mut calibration = Self::default();
+        for execution in executions {
+            calibration.record(execution);
+        }
+        calibration
+    }
+
+    /// Calibrate from the last `CALIBRATION_LOOKBACK_DAYS` of executions in
+    /// `dir`, falling back to the fixed table when they cannot be read
+    pub fn load(dir: &Path) -> Self {
+        let since = (Utc::now() - Duration::days(CALIBRATION_LOOKBACK_DAYS)).date_naive();
+        match load_trade_executions_since(dir, since) {
+            Ok(executions) => {
+                let calibration = Self::from_executions(&executions);
+                if calibration.observed() > 0 {
+                    info!("📐 Calibrated execution simulation from {} submitted executions", calibration.observed());
+                }
+                calibration
+            }
+            Err(e) => {
+                warn!("Execution calibration unavailable, using fixed rates: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Add a submitted execution. Simulated ones and those without a
+    /// volatility band are ignored, so the simulator never learns from itself.
+    pub fn record(&mut self, execution: &TradeExecution) {
+        let Some(impact) = execution.volatility_impact else {
+            return;
+        };
+        if execution.simulated {
+            return;
+        }
+        let outcomes = &mut self.bands[band(impact)];
+        outcomes.attempts += 1;
+        if matches!(execution.status, ExecutionStatus::Failed) {
+            return;
+        }
+        outcomes.successes += 1;
+
+        let (Some(actual), Some(expected)) = (execution.actual_profit_usd, execution.expected_profit_usd.to_f64()) else {
+            return;
+        };
+        if expected <= 0.0 {
+            return;
+        }
+        let shortfall = (1.0 - actual.to_f64().unwrap_or(expected) / expected).clamp(0.0, 1.0);
+        outcomes.shortfall_samples += 1;
+        outcomes.shortfall_bps_sum += shortfall * 10000.0;
+    }
+
+    /// Submitted executions recorded across all bands
+    pub fn observed(&self) -> u64 {
+        self.bands.iter().map(|b| b.attempts).sum()
+    }
+
+    pub fn outcomes(&self, impact: VolatilityImpact) -> BandOutcomes {
+        self.bands[band(impact)]
+    }
+
+    pub fn success_rate(&self, impact: VolatilityImpact) -> f64 {
+        let outcomes = self.outcomes(impact);
+        (prior_success_rate(impact) * PRIOR_WEIGHT + outcomes.successes as f64)
+            / (PRIOR_WEIGHT + outcomes.attempts as f64)
+    }
+
+    pub fn slippage_bps(&self, impact: VolatilityImpact) -> u32 {
+        let outcomes = self.outcomes(impact);
+        let bps = (prior_slippage_bps(impact) * PRIOR_WEIGHT + outcomes.shortfall_bps_sum)
+            / (PRIOR_WEIGHT + outcomes.shortfall_samples as f64);
+        bps.round() as u32
+    }
+}
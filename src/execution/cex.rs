@@ -0,0 +1,218 @@
+//! CEX leg execution on Binance Spot Testnet
+
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use serde::Serialize;
+use sha2::Sha256;
+use std::time::Duration;
+use tracing::{info, warn};
+use crate::{
+    config::Config,
+    portfolio::VenueBalances,
+};
+
+pub const BINANCE_TESTNET_URL: &str = "https://testnet.binance.vision";
+/// Binance spot taker fee, used when modeling the CEX leg
+pub const BINANCE_TAKER_FEE: Decimal = dec!(0.001);
+const RECV_WINDOW_MS: u64 = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CexOrderSide {
+    Buy,
+    Sell,
+}
+
+impl CexOrderSide {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CexOrderSide::Buy => "BUY",
+            CexOrderSide::Sell => "SELL",
+        }
+    }
+}
+
+/// Result of a CEX market order (or a modeled one)
+#[derive(Debug, Clone, Serialize)]
+pub struct CexFill {
+    pub order_id: Option<String>,
+    pub side: CexOrderSide,
+    pub executed_qty: Decimal,
+    /// Average fill price including the taker fee
+    pub avg_price: Decimal,
+    pub simulated: bool,
+}
+
+impl CexFill {
+    /// Model a market order at `reference_price` paying the taker fee
+    pub fn modeled(side: CexOrderSide, quantity: Decimal, reference_price: Decimal) -> Self {
+        let avg_price = match side {
+            CexOrderSide::Buy => reference_price * (dec!(1) + BINANCE_TAKER_FEE),
+            CexOrderSide::Sell => reference_price * (dec!(1) - BINANCE_TAKER_FEE),
+        };
+        Self {
+            order_id: None,
+            side,
+            executed_qty: quantity,
+            avg_price,
+            simulated: true,
+        }
+    }
+}
+
+pub struct BinanceTestnetClient {
+    client: reqwest::Client,
+    api_key: String,
+    api_secret: String,
+    symbol: String,
+}
+
+impl BinanceTestnetClient {
+    /// Build a client if Binance testnet credentials are configured
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        let (Some(api_key), Some(api_secret)) = (
+            config.binance_testnet_api_key.clone(),
+            config.binance_testnet_api_secret.clone(),
+        ) else {
+            return Ok(None);
+        };
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .context("Failed to build Binance HTTP client")?;
+
+        info!("🏦 Binance Spot Testnet CEX leg enabled ({})", config.binance_testnet_symbol);
+        Ok(Some(Self {
+            client,
+            api_key,
+            api_secret,
+            symbol: config.binance_testnet_symbol.clone(),
+        }))
+    }
+
+    /// Hex HMAC-SHA256 signature of a query string
+    fn sign(&self, query: &str) -> Result<String> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.api_secret.as_bytes())
+            .context("Invalid Binance API secret")?;
+        mac.update(query.as_bytes());
+        Ok(hex::encode(mac.finalize().into_bytes()))
+    }
+
+    /// Place a signed market order and return the volume-weighted fill
+    pub async fn place_market_order(&self, side: CexOrderSide, quantity: Decimal) -> Result<CexFill> {
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        let query = format!(
+            "symbol={}&side={}&type=MARKET&quantity={}&newOrderRespType=FULL&recvWindow={}&timestamp={}",
+            self.symbol, side.as_str(), quantity.round_dp(4).normalize(), RECV_WINDOW_MS, timestamp
+        );
+        let signature = self.sign(&query)?;
+
+        let response = self.client
+            .post(format!("{}/api/v3/order?{}&signature={}", BINANCE_TESTNET_URL, query, signature))
+            .header("X-MBX-APIKEY", &self.api_key)
+            .send()
+            .await
+            .context("Binance order request failed")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            warn!("⚠️ Binance testnet rejected order: {} - {}", status, body);
+            return Err(anyhow::anyhow!("Binance order error: {} - {}", status, body));
+        }
+
+        let json: serde_json::Value = response.json().await
+            .context("Failed to parse Binance order response")?;
+        let (base_asset, quote_asset) = self.assets();
+        let fill = parse_order_fill(&json, side, base_asset, quote_asset)?;
+
+        info!("🏦 Binance testnet {} {} {} @ ${:.2} (order {:?})",
+            side.as_str(), fill.executed_qty, self.symbol, fill.avg_price, fill.order_id);
+        Ok(fill)
+    }
+
+    /// Base and quote assets of the symbol, e.g. ("ETH", "USDT")
+    fn assets(&self) -> (&str, &str) {
+        ("ETH", self.symbol.strip_prefix("ETH").unwrap_or("USDT"))
+    }
+
+    /// Free + locked balances of the symbol's base (ETH) and quote assets
+    pub async fn get_balances(&self) -> Result<VenueBalances> {
+        let query = format!("recvWindow={}&timestamp={}", RECV_WINDOW_MS, chrono::Utc::now().timestamp_millis());
+        let signature = self.sign(&query)?;
+
+        let response = self.client
+            .get(format!("{}/api/v3/account?{}&signature={}", BINANCE_TESTNET_URL, query, signature))
+            .header("X-MBX-APIKEY", &self.api_key)
+            .send()
+            .await
+            .context("Binance account request failed")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Binance account error: {} - {}", status, body));
+        }
+
+        let json: serde_json::Value = response.json().await
+            .context("Failed to parse Binance account response")?;
+        let (base_asset, quote_asset) = self.assets();
+
+        let mut balances = VenueBalances::default();
+        for balance in json["balances"].as_array().into_iter().flatten() {
+            let total = decimal_field(balance, "free").unwrap_or_default()
+                + decimal_field(balance, "locked").unwrap_or_default();
+            match balance["asset"].as_str() {
+                Some(asset) if asset == base_asset => balances.weth += total,
+                Some(asset) if asset == quote_asset => balances.usd += total,
+                _ => {}
+            }
+        }
+        Ok(balances)
+    }
+}
+
+fn decimal_field(value: &serde_json::Value, field: &str) -> Option<Decimal> {
+    value[field].as_str().and_then(|s| Decimal::from_str(s).ok())
+}
+
+/// Volume-weighted price from a FULL order response. Commissions paid in the
+/// quote asset are folded into the price; on a buy, commissions paid in the
+/// base asset come out of the executed quantity.
+pub fn parse_order_fill(json: &serde_json::Value, side: CexOrderSide, base_asset: &str, quote_asset: &str) -> Result<CexFill> {
+    let executed_qty = decimal_field(json, "executedQty")
+        .ok_or_else(|| anyhow::anyhow!("Missing executedQty in Binance response"))?;
+    let quote_qty = decimal_field(json, "cummulativeQuoteQty")
+        .ok_or_else(|| anyhow::anyhow!("Missing cummulativeQuoteQty in Binance response"))?;
+    if executed_qty <= dec!(0) {
+        return Err(anyhow::anyhow!("Binance order was not filled"));
+    }
+
+    let commission_in = |asset: &str| -> Decimal {
+        json["fills"].as_array()
+            .map(|fills| fills.iter()
+                .filter(|f| f["commissionAsset"].as_str() == Some(asset))
+                .filter_map(|f| decimal_field(f, "commission"))
+                .sum())
+            .unwrap_or_default()
+    };
+    let quote_commission = commission_in(quote_asset);
+
+    let (net_qty, net_quote) = match side {
+        CexOrderSide::Buy => (executed_qty - commission_in(base_asset), quote_qty + quote_commission),
+        CexOrderSide::Sell => (executed_qty, quote_qty - quote_commission),
+    };
+    if net_qty <= dec!(0) {
+        return Err(anyhow::anyhow!("Binance commission exceeds the executed quantity"));
+    }
+
+    Ok(CexFill {
+        order_id: json["orderId"].as_u64().map(|id| id.to_string()),
+        side,
+        executed_qty: net_qty,
+        avg_price: net_quote / net_qty,
+        simulated: false,
+    })
+}
@@ -0,0 +1,254 @@
+//! Unit economics of a hypothetical arbitrage trade
+//!
+//! Prices a trade through the same quoter, gas oracle, opportunity calculator
+//! and router calldata as live execution, then itemizes every cost, the
+//! breakeven spread and whatever would stop the bot from taking it.
+
+use alloy::primitives::Address;
+use anyhow::Result;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::debug;
+use crate::{
+    arbitrage::{self, FeeModel},
+    config::Config,
+    execution::{router_swap_calldata, swap_bounds},
+    network::{self, GasEstimate, ESTIMATED_SWAP_GAS_UNITS},
+    pools::{self, PoolReserves},
+    types::{PoolInfo, PoolKind, StablePrices, VolatilityImpact},
+    utils::scale_from_raw,
+    ConcreteProvider,
+};
+
+/// Market inputs for one hypothetical trade
+#[derive(Debug, Clone)]
+pub struct EconomicsInputs {
+    /// Trade size in base tokens (WETH on WETH/USD pools)
+    pub trade_size: Decimal,
+    /// USD fair value of the base token
+    pub cex_price: Decimal,
+    pub eth_usd: Decimal,
+    /// USD prices of the `STABLE_PRICES` quote assets
+    pub stable_prices: StablePrices,
+    pub gas: GasEstimate,
+}
+
+/// Itemized costs of one hypothetical trade, all amounts in USD
+#[derive(Debug, Clone, Serialize)]
+pub struct UnitEconomics {
+    pub pool: String,
+    pub direction: String,
+    pub trade_size: Decimal,
+    pub notional_usd: Decimal,
+    pub dex_mid_price: Decimal,
+    pub cex_price: Decimal,
+    pub spread_bps: Decimal,
+    /// Price for the trade size from getAmountOut, or from the reserves when
+    /// the quoter is unavailable; None when neither is
+    pub execution_price: Option<Decimal>,
+    /// Fee rate the pool charges, as read at validation
+    pub pool_fee_rate: Decimal,
+    pub pool_fee_usd: Decimal,
+    /// Execution price shortfall against the mid price beyond the pool fee
+    pub price_impact_usd: Option<Decimal>,
+    pub gas_price_gwei: Decimal,
+    pub l2_gas_usd: Decimal,
+    /// None when the router cannot encode the swap or the oracle call fails
+    pub l1_fee_usd: Option<Decimal>,
+    /// Binance maker or taker fee on the CEX leg
+    pub cex_fee_usd: Decimal,
+    /// Bridge/withdrawal cost per round trip
+    pub transfer_cost_usd: Decimal,
+    /// Worst-case shortfall the router's minimum output still accepts
+    pub slippage_allowance_usd: Decimal,
+    pub gross_profit_usd: Decimal,
+    /// Net profit as the live detector computes it (L2 gas and fees)
+    pub net_profit_usd: Decimal,
+    /// Net profit after the L1 data fee as well
+    pub net_profit_all_in_usd: Decimal,
+    /// Spread needed for the all-in net profit to reach zero
+    pub breakeven_spread_bps: Decimal,
+    /// Spread needed for the all-in net profit to reach the minimum profit
+    pub required_spread_bps: Decimal,
+    /// Reasons the live bot would not take this trade
+    pub blockers: Vec<String>,
+}
+
+/// Evaluate a hypothetical trade of `inputs.trade_size` on `pool`
+pub async fn evaluate_unit_economics(
+    provider: &Arc<ConcreteProvider>,
+    pool: &PoolInfo,
+    inputs: &EconomicsInputs,
+    config: &Config,
+) -> Result<UnitEconomics> {
+    let dex_quote_price = pools::calculate_pool_price_safe_with_retry(provider, pool).await
+        .map_err(|e| anyhow::anyhow!("Failed to calculate DEX price: {}", e))?;
+    let quote_usd = pool.pair.quote_usd(inputs.eth_usd, &inputs.stable_prices)
+        .ok_or_else(|| anyhow::anyhow!("No USD reference for {}", pool.pair.quote_symbol))?;
+    let dex_mid_price = dex_quote_price * quote_usd;
+    let cex_price = inputs.cex_price;
+    let trade_size = inputs.trade_size;
+    let buy_on_dex = dex_mid_price < cex_price;
+
+    let quote = match pools::quote_trade_prices(provider.as_ref(), pool, trade_size, dex_quote_price).await {
+        Ok(quote) => Some(quote.scaled(quote_usd)),
+        Err(e) => {
+            debug!("Quoter unavailable for {}, using mid price: {}", pool.name, e);
+            None
+        }
+    };
+    let reserves = match pools::get_pool_reserves_enhanced(provider.as_ref(), pool.address, &pool.name, pool.kind).await {
+        Ok((r0, r1)) => pool.base_quote_reserves(r0, r1)
+            .map(|(base, quote)| PoolReserves::new(pool, base, quote, quote_usd)),
+        Err(e) => {
+            debug!("Reserves unavailable for {}: {}", pool.name, e);
+            None
+        }
+    };
+    let execution_quote = quote.clone().or_else(|| reserves.and_then(|r| r.quote(trade_size)));
+    let execution_price = execution_quote.map(|q| if buy_on_dex { q.buy_price } else { q.sell_price });
+
+    let l2_gas_usd = inputs.gas.cost_usd(ESTIMATED_SWAP_GAS_UNITS, inputs.eth_usd);
+    let pool_fee_rate = pool.fee;
+    let fees = FeeModel::from_config(config, pool_fee_rate);
+    let detected = arbitrage::calculate_arbitrage(
+        &pool.name,
+        dex_mid_price,
+        cex_price,
+        trade_size,
+        quote.as_ref(),
+        reserves.as_ref(),
+        l2_gas_usd,
+        &fees,
+        config.min_price_diff_pct,
+    );
+
+    let direction = if buy_on_dex {
+        "Buy on Aerodrome → Sell on Binance"
+    } else {
+        "Buy on Binance → Sell on Aerodrome"
+    };
+    let gross_profit_usd = match &detected {
+        Some(opportunity) => opportunity.gross_profit_usd,
+        None => {
+            let price = execution_price.unwrap_or(dex_mid_price);
+            if buy_on_dex { trade_size * (cex_price - price) } else { trade_size * (price - cex_price) }
+        }
+    };
+    let cex_fee_usd = fees.cex_fee_usd(trade_size, cex_price);
+    let net_profit_usd = match &detected {
+        Some(opportunity) => opportunity.net_profit_usd,
+        None => {
+            let dex_fee_usd = match execution_price {
+                Some(_) => Decimal::ZERO,
+                None => fees.dex_fee_usd(trade_size, dex_mid_price),
+            };
+            gross_profit_usd - l2_gas_usd - cex_fee_usd - dex_fee_usd - fees.transfer_cost_usd
+        }
+    };
+
+    let notional_usd = trade_size * cex_price;
+    let pool_fee_usd = trade_size * dex_mid_price * pool_fee_rate;
+    let price_impact_usd = execution_price.map(|price| (price - dex_mid_price).abs() * trade_size - pool_fee_usd);
+
+    // Router calldata is priced in quote tokens, like the engine's
+    let router_price = execution_price.unwrap_or(dex_mid_price) / quote_usd;
+    let l1_fee_usd = match l1_fee_usd(provider, pool, buy_on_dex, trade_size, router_price, inputs.eth_usd, config).await {
+        Ok(fee) => Some(fee),
+        Err(e) => {
+            debug!("L1 fee unavailable for {}: {}", pool.name, e);
+            None
+        }
+    };
+    let slippage_allowance_usd = trade_size
+        * execution_price.unwrap_or(dex_mid_price)
+        * Decimal::from(config.slippage_tolerance_bps) / dec!(10000);
+
+    let net_profit_all_in_usd = net_profit_usd - l1_fee_usd.unwrap_or_default();
+    let spread_usd = (dex_mid_price - cex_price).abs() * trade_size;
+    let costs_usd = spread_usd - net_profit_all_in_usd;
+    let to_bps = |usd: Decimal| if notional_usd > dec!(0) { usd / notional_usd * dec!(10000) } else { Decimal::ZERO };
+
+    let mut economics = UnitEconomics {
+        pool: pool.name.clone(),
+        direction: direction.to_string(),
+        trade_size,
+        notional_usd,
+        dex_mid_price,
+        cex_price,
+        spread_bps: to_bps(spread_usd),
+        execution_price,
+        pool_fee_rate,
+        pool_fee_usd,
+        price_impact_usd,
+        gas_price_gwei: inputs.gas.gas_price_gwei(),
+        l2_gas_usd,
+        l1_fee_usd,
+        cex_fee_usd,
+        transfer_cost_usd: fees.transfer_cost_usd,
+        slippage_allowance_usd,
+        gross_profit_usd,
+        net_profit_usd,
+        net_profit_all_in_usd,
+        breakeven_spread_bps: to_bps(costs_usd),
+        required_spread_bps: to_bps(costs_usd + config.min_profit_usd),
+        blockers: Vec::new(),
+    };
+    economics.blockers = blockers(&economics, detected.is_some(), pool, config);
+    Ok(economics)
+}
+
+/// L1 data fee for the router swap the engine would send for this trade
+async fn l1_fee_usd(
+    provider: &Arc<ConcreteProvider>,
+    pool: &PoolInfo,
+    buy_base: bool,
+    size: Decimal,
+    price: Decimal,
+    eth_usd: Decimal,
+    config: &Config,
+) -> Result<Decimal> {
+    // The recipient and bounds only change calldata bytes, not their length
+    let bounds = swap_bounds(config.slippage_tolerance_bps, VolatilityImpact::Moderate);
+    let calldata = router_swap_calldata(pool, buy_base, size, price, Address::ZERO, bounds)?;
+    let fee_wei = network::estimate_l1_fee_wei(provider.as_ref(), &calldata).await?;
+    let fee_eth = scale_from_raw(fee_wei, 18)
+        .ok_or_else(|| anyhow::anyhow!("L1 fee out of range: {}", fee_wei))?;
+    Ok(fee_eth * eth_usd)
+}
+
+fn blockers(economics: &UnitEconomics, detected: bool, pool: &PoolInfo, config: &Config) -> Vec<String> {
+    let mut blockers = Vec::new();
+    if !detected {
+        blockers.push(format!(
+            "spread {:.1} bps is below the {}% detection threshold",
+            economics.spread_bps, config.min_price_diff_pct
+        ));
+    }
+    if economics.net_profit_usd < config.min_profit_usd {
+        blockers.push(format!(
+            "net profit ${:.4} is below the ${} minimum",
+            economics.net_profit_usd, config.min_profit_usd
+        ));
+    }
+    if economics.gas_price_gwei > Decimal::from(config.max_gas_price_gwei) {
+        blockers.push(format!(
+            "gas {:.4} gwei exceeds the {} gwei cap",
+            economics.gas_price_gwei, config.max_gas_price_gwei
+        ));
+    }
+    if config.execution_network == "mainnet" && economics.notional_usd > config.max_trade_notional_usd {
+        blockers.push(format!(
+            "notional ${:.2} exceeds the ${} mainnet cap",
+            economics.notional_usd, config.max_trade_notional_usd
+        ));
+    }
+    if matches!(pool.kind, PoolKind::Concentrated { .. }) {
+        blockers.push("CL pools cannot be routed through the Aerodrome v2 Router".to_string());
+    } else if !pool.pair.quote_is_usd() {
+        blockers.push(format!("only USD-quoted pairs are executable, not {}", pool.pair));
+    }
+    blockers
+}
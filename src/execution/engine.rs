@@ -2,65 +2,360 @@
 
 use alloy::{
     network::EthereumWallet,
-    primitives::{Address, address, keccak256, U256},
+    primitives::{Address, TxHash, U256},
     providers::{Provider, ProviderBuilder},
-    rpc::types::eth::TransactionRequest,
+    rpc::types::eth::{TransactionReceipt, TransactionRequest},
     signers::local::PrivateKeySigner,
+    sol_types::SolCall,
 };
 use anyhow::{Context, Result};
+use std::path::Path;
 use std::str::FromStr;
-use std::sync::Arc;
-use std::time::{Duration, SystemTime};
-use tracing::{info, warn};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::RwLock;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tracing::{error, info, warn};
 use rust_decimal_macros::dec;
-use rust_decimal::prelude::ToPrimitive;
 use crate::{
     config::{Config, CONFIG},
-    types::{ArbitrageOpportunity, TradeExecution, ExecutionStatus, TradeType, VolatilityMetrics},
-    ConcreteProvider,
+    execution::{
+        approval_amount, approve_calldata, check_wallet_balances, fork_swap, get_allowance, preflight_swap, received_amount,
+        router_swap_input, router_swap_min_out, router_swap_output, simulation_rng, swap_bounds, swap_deadline,
+        wait_for_receipt,
+        AllowanceCache, AnvilFork, BinanceTestnetClient, CexFill, CexOrderSide, ExecutionCalibration, MainnetGuards,
+        NonceManager, PrivateSubmitter, SimulationMode, TokenRegistry, TransactionPolicy,
+        APPROVAL_GAS_LIMIT,
+    },
+    network::{self, IAerodromeRouter, IUniswapV2Router},
+    pools::{self, POOL_STATE_CACHE},
+    risk::RiskManager,
+    storage::EXECUTIONS_DIR,
+    types::{
+        ArbitrageOpportunity, TradeExecution, ExecutionStatus, TradeType, VolatilityMetrics, PoolInfo, PoolKind,
+        SwapBounds, SwapPreflight,
+        AERODROME_ROUTER_MAINNET, AERODROME_POOL_FACTORY_MAINNET, UNISWAP_V2_ROUTER_SEPOLIA, USDC_SEPOLIA, WETH_SEPOLIA,
+    },
+    utils::{scale_from_raw, scale_to_raw},
 };
 
+/// Which leg an opportunity buys on the DEX
+fn trade_type_of(opportunity: &ArbitrageOpportunity) -> TradeType {
+    if opportunity.direction.contains("Buy on Aerodrome") {
+        TradeType::BuyDexSellCex
+    } else {
+        TradeType::BuyCexSellDex
+    }
+}
+
+/// Provider that fills gas and chain id and signs with `wallet`.
+/// Nonces are assigned by the engine's [`NonceManager`].
+fn signing_provider(rpc_url: &str, wallet: EthereumWallet) -> Result<Arc<dyn Provider>> {
+    let root = network::http_provider(rpc_url)?;
+    Ok(Arc::new(
+        ProviderBuilder::new()
+            .with_gas_estimation()
+            .fetch_chain_id()
+            .wallet(wallet)
+            .on_provider(root)
+    ))
+}
+
+/// A swap's preflight and, if it passed, the send result
+struct SentSwap {
+    preflight: SwapPreflight,
+    /// Router `amountOutMin` in the output token
+    min_amount_out: Option<rust_decimal::Decimal>,
+    /// Swap input and the output the receipt shows the recipient got
+    filled: Option<(rust_decimal::Decimal, rust_decimal::Decimal)>,
+    /// Gas used and effective gas price in gwei from the receipt
+    gas: Option<(u64, rust_decimal::Decimal)>,
+    /// Transaction hash; an error when the preflight reverted or sending failed
+    result: Result<String>,
+}
+
+impl SentSwap {
+    /// USD price per base token the DEX leg actually filled at (USD-quoted pairs)
+    fn fill_price(&self, trade_type: TradeType) -> Option<rust_decimal::Decimal> {
+        let (amount_in, amount_out) = self.filled?;
+        let (quote, base) = match trade_type {
+            TradeType::BuyDexSellCex => (amount_in, amount_out),
+            TradeType::BuyCexSellDex => (amount_out, amount_in),
+        };
+        (!base.is_zero()).then(|| quote / base)
+    }
+
+    /// What the receipt shows the DEX leg cost and filled at
+    fn measured(&self, opportunity: &ArbitrageOpportunity) -> SwapMeasurements {
+        let trade_type = trade_type_of(opportunity);
+        let dex_fill_price = self.fill_price(trade_type);
+        let quoted_price = opportunity.quoted_dex_price.unwrap_or(opportunity.dex_price);
+        SwapMeasurements {
+            dex_fill_price,
+            slippage_bps: dex_fill_price.and_then(|fill| fill_slippage_bps(fill, quoted_price, matches!(trade_type, TradeType::BuyDexSellCex))),
+            gas_used: self.gas.map(|(gas_used, _)| gas_used),
+            gas_price_gwei: self.gas.map(|(_, price)| price),
+        }
+    }
+}
+
+/// Fill price, slippage and gas of a sent swap; all None when nothing was measured
+#[derive(Debug, Default)]
+struct SwapMeasurements {
+    dex_fill_price: Option<rust_decimal::Decimal>,
+    slippage_bps: Option<u32>,
+    gas_used: Option<u64>,
+    gas_price_gwei: Option<rust_decimal::Decimal>,
+}
+
+/// Shortfall of `fill` against the quoted price; fills better than quoted count as zero
+fn fill_slippage_bps(fill: rust_decimal::Decimal, quoted_price: rust_decimal::Decimal, buy_on_dex: bool) -> Option<u32> {
+    use rust_decimal::prelude::ToPrimitive;
+
+    let ratio = fill.checked_div(quoted_price)?;
+    let shortfall = if buy_on_dex { ratio - dec!(1) } else { dec!(1) - ratio };
+    (shortfall.max(dec!(0)) * dec!(10000)).round().to_u32()
+}
+
 pub struct TradeExecutionEngine {
-    pub sepolia_provider: Option<Arc<ConcreteProvider>>,
+    /// Sepolia provider; signs transactions when a wallet is configured
+    pub sepolia_provider: Option<Arc<dyn Provider>>,
     pub wallet: Option<EthereumWallet>,
+    /// Signing provider for real-funds execution (EXECUTION_NETWORK=mainnet)
+    pub mainnet_provider: Option<Arc<dyn Provider>>,
+    pub wallet_address: Option<Address>,
+    pub guards: Option<MainnetGuards>,
+    nonce_manager: NonceManager,
+    cex_client: Option<BinanceTestnetClient>,
+    /// Token safety verdicts, checked before the first trade on a pool
+    pub token_registry: TokenRegistry,
+    /// Whitelist every transaction must pass before it is signed
+    policy: TransactionPolicy,
+    /// Simulator rates learned from submitted executions
+    calibration: RwLock<ExecutionCalibration>,
+    /// Seeds each simulated execution, in execution order
+    simulation_rng: Mutex<StdRng>,
+    /// Fork that simulated swaps run on (SIMULATION_MODE=fork), one at a time
+    fork: Option<tokio::sync::Mutex<AnvilFork>>,
+    /// Global limits consulted before every execution
+    risk_manager: Option<Arc<RiskManager>>,
+    /// Private endpoint for swaps; None submits through the public mempool
+    submitter: Option<PrivateSubmitter>,
+    /// Known router allowances of the wallet, per (token, router)
+    allowances: AllowanceCache,
 }
 
 impl TradeExecutionEngine {
     pub async fn new(config: &Config) -> Result<Self> {
-        let (sepolia_provider, wallet) = if config.enable_trade_execution {
+        if config.enable_trade_execution && config.execution_network == "mainnet" {
+            return Self::new_mainnet(config);
+        }
+
+        let (sepolia_provider, wallet, wallet_address) = if config.enable_trade_execution {
             // Setup Sepolia provider
             let alchemy_key = config.alchemy_api_key.as_ref()
                 .expect("ALCHEMY_API_KEY is required");
             let sepolia_url = format!("https://base-sepolia.g.alchemy.com/v2/{}", alchemy_key);
-            let sepolia_provider = Arc::new(
-                ProviderBuilder::new()
-                    .on_http(sepolia_url.parse()?)
-                    .boxed()
-            );
-
-            // Setup wallet if private key provided
-            let wallet = if let Some(pk) = &config.private_key {
-                let signer = PrivateKeySigner::from_str(pk)
-                    .context("Failed to parse private key")?;
-                Some(EthereumWallet::from(signer))
-            } else {
-                None
-            };
 
-            (Some(sepolia_provider), wallet)
+            // Sign with the wallet if a private key is provided, otherwise read-only
+            match &config.private_key {
+                Some(pk) => {
+                    let signer = PrivateKeySigner::from_str(pk)
+                        .context("Failed to parse private key")?;
+                    let wallet_address = signer.address();
+                    let wallet = EthereumWallet::from(signer);
+                    info!("🔑 Testnet wallet: {:?}", wallet_address);
+                    (Some(signing_provider(&sepolia_url, wallet.clone())?), Some(wallet), Some(wallet_address))
+                }
+                None => {
+                    let sepolia_provider: Arc<dyn Provider> = Arc::new(network::http_provider(&sepolia_url)?);
+                    (Some(sepolia_provider), None, None)
+                }
+            }
+        } else {
+            (None, None, None)
+        };
+        let submitter = if config.enable_trade_execution {
+            PrivateSubmitter::from_config(config, wallet.as_ref())?
         } else {
-            (None, None)
+            None
+        };
+        let simulating = sepolia_provider.is_none() || wallet.is_none();
+        let (simulation_rng, seed) = simulation_rng(config.simulation_seed);
+        let fork = match config.simulation_mode {
+            SimulationMode::Fork if simulating => {
+                let fork = AnvilFork::from_config(config).await
+                    .context("SIMULATION_MODE=fork needs an Anvil fork")?;
+                info!("🍴 Simulating executions on an Anvil fork of Base mainnet");
+                Some(tokio::sync::Mutex::new(fork))
+            }
+            _ => {
+                if simulating {
+                    info!("🎲 Simulation seed: {} (set SIMULATION_SEED to replay)", seed);
+                }
+                None
+            }
         };
 
         Ok(Self {
             sepolia_provider,
             wallet,
+            mainnet_provider: None,
+            wallet_address,
+            guards: None,
+            nonce_manager: NonceManager::new(),
+            cex_client: BinanceTestnetClient::from_config(config)?,
+            token_registry: TokenRegistry::new(config),
+            policy: TransactionPolicy::new(config, wallet_address),
+            calibration: RwLock::new(ExecutionCalibration::load(Path::new(EXECUTIONS_DIR))),
+            simulation_rng: Mutex::new(simulation_rng),
+            fork,
+            risk_manager: None,
+            submitter,
+            allowances: AllowanceCache::new(),
+        })
+    }
+
+    fn new_mainnet(config: &Config) -> Result<Self> {
+        if !config.i_understand_real_funds {
+            return Err(anyhow::anyhow!(
+                "EXECUTION_NETWORK=mainnet trades real funds; set I_UNDERSTAND_REAL_FUNDS=true to enable it"
+            ));
+        }
+        let alchemy_key = config.alchemy_api_key.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("ALCHEMY_API_KEY is required for mainnet execution"))?;
+        let pk = config.private_key.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("PRIVATE_KEY is required for mainnet execution"))?;
+
+        let signer = PrivateKeySigner::from_str(pk)
+            .context("Failed to parse private key")?;
+        let wallet_address = signer.address();
+        let wallet = EthereumWallet::from(signer);
+
+        let mainnet_url = format!("https://base-mainnet.g.alchemy.com/v2/{}", alchemy_key);
+        let mainnet_provider = signing_provider(&mainnet_url, wallet.clone())?;
+
+        let policy = TransactionPolicy::new(config, Some(wallet_address));
+        let submitter = PrivateSubmitter::from_config(config, Some(&wallet))?;
+        warn!("⚠️  MAINNET EXECUTION ENABLED - real funds from {:?}", wallet_address);
+        if config.binance_testnet_api_key.is_some() {
+            warn!("   Binance testnet credentials ignored: mainnet DEX legs are not hedged on testnet");
+        }
+        warn!("   Max notional: ${} per trade, max daily loss: ${}",
+            config.max_trade_notional_usd, config.max_daily_loss_usd);
+        info!("   Signer policy: {}", policy.summary());
+        info!("   Submission: {}", config.submission_mode);
+
+        Ok(Self {
+            sepolia_provider: None,
+            wallet: Some(wallet),
+            mainnet_provider: Some(mainnet_provider),
+            wallet_address: Some(wallet_address),
+            guards: Some(MainnetGuards::new(config)),
+            nonce_manager: NonceManager::new(),
+            // Testnet orders would only pretend to hedge real-funds fills
+            cex_client: None,
+            token_registry: TokenRegistry::new(config),
+            policy,
+            calibration: RwLock::new(ExecutionCalibration::load(Path::new(EXECUTIONS_DIR))),
+            simulation_rng: Mutex::new(simulation_rng(config.simulation_seed).0),
+            fork: None,
+            risk_manager: None,
+            submitter,
+            allowances: AllowanceCache::new(),
         })
     }
 
+    /// Enforce the risk manager's limits before executing and record each execution
+    pub fn with_risk_manager(mut self, risk_manager: Arc<RiskManager>) -> Self {
+        self.risk_manager = Some(risk_manager);
+        self
+    }
+
+    /// Whether the risk manager allows executing `opportunity` now
+    pub async fn risk_check(&self, opportunity: &ArbitrageOpportunity) -> Result<(), String> {
+        match &self.risk_manager {
+            Some(risk_manager) => risk_manager.can_execute(opportunity).await,
+            None => Ok(()),
+        }
+    }
+
+    fn network_name(&self) -> &'static str {
+        if self.mainnet_provider.is_some() { "Base Mainnet" } else { "Base Sepolia" }
+    }
+
+    /// Execute (or simulate) the DEX leg, then the opposite CEX leg, and
+    /// report combined fill prices and P&L
     pub async fn simulate_trade_execution(
         &self,
         opportunity: &ArbitrageOpportunity,
+        pool_info: &PoolInfo,
+        volatility_metrics: &VolatilityMetrics,
+    ) -> Result<TradeExecution> {
+        // Held until the execution is recorded, so concurrent jobs see each other's budget
+        let _reservation = match &self.risk_manager {
+            Some(risk_manager) => Some(risk_manager.check_execution(opportunity).await.map_err(|reason| anyhow::anyhow!(reason))?),
+            None => None,
+        };
+        let mut execution = self.execute_dex_leg(opportunity, pool_info, volatility_metrics).await?;
+        if !matches!(execution.status, ExecutionStatus::Failed) {
+            self.complete_cex_leg(&mut execution, opportunity, pool_info).await;
+        }
+        execution.volatility_impact = Some(volatility_metrics.impact_assessment);
+        self.calibration.write().await.record(&execution);
+        if let Some(risk_manager) = &self.risk_manager {
+            risk_manager.record_execution(&execution, opportunity).await;
+        }
+        Ok(execution)
+    }
+
+    /// Hedge the DEX fill on Binance testnet, or model the fill for
+    /// simulated executions. A real DEX fill without a testnet order (no
+    /// credentials, a non-ETH pair, or mainnet) is left unhedged: no CEX
+    /// fill price and no P&L are reported for it.
+    async fn complete_cex_leg(&self, execution: &mut TradeExecution, opportunity: &ArbitrageOpportunity, pool_info: &PoolInfo) {
+        let side = match execution.trade_type {
+            TradeType::BuyDexSellCex => CexOrderSide::Sell,
+            TradeType::BuyCexSellDex => CexOrderSide::Buy,
+        };
+        let dex_fill_price = execution.dex_fill_price
+            .unwrap_or(opportunity.quoted_dex_price.unwrap_or(opportunity.dex_price));
+        execution.dex_fill_price = Some(dex_fill_price);
+
+        let fill = match &self.cex_client {
+            Some(client) if pool_info.pair.is_weth_usd() => match client.place_market_order(side, opportunity.size_eth).await {
+                Ok(fill) => fill,
+                Err(e) => {
+                    error!("CEX leg failed after DEX leg: {}", e);
+                    execution.error_message = Some(format!("CEX leg failed after DEX leg: {}", e));
+                    execution.actual_profit_usd = None;
+                    return;
+                }
+            },
+            _ if execution.simulated => CexFill::modeled(side, opportunity.size_eth, opportunity.cex_price),
+            _ => {
+                warn!("⚠️  DEX leg of {} left unhedged: no CEX order was placed", opportunity.id);
+                execution.error_message = Some("DEX leg unhedged: no CEX order was placed".to_string());
+                execution.cex_fill_price = None;
+                execution.actual_profit_usd = None;
+                return;
+            }
+        };
+
+        let (buy_price, sell_price) = match side {
+            CexOrderSide::Sell => (dex_fill_price, fill.avg_price),
+            CexOrderSide::Buy => (fill.avg_price, dex_fill_price),
+        };
+        let filled_eth = fill.executed_qty.min(opportunity.size_eth);
+        execution.actual_profit_usd = Some(filled_eth * (sell_price - buy_price) - opportunity.gas_cost_usd);
+        execution.cex_fill_price = Some(fill.avg_price);
+        execution.cex_order_id = fill.order_id;
+    }
+
+    async fn execute_dex_leg(
+        &self,
+        opportunity: &ArbitrageOpportunity,
+        pool_info: &PoolInfo,
         volatility_metrics: &VolatilityMetrics,
     ) -> Result<TradeExecution> {
         use crate::execution::simulation::create_simulated_execution;
@@ -69,21 +364,48 @@ impl TradeExecutionEngine {
         let execution_start = Instant::now();
         let execution_id = uuid::Uuid::new_v4().to_string();
 
+        // Tighter minimum output and deadline in calm markets, wider in turbulent ones
+        let bounds = swap_bounds(CONFIG.get().slippage_tolerance_bps, volatility_metrics.impact_assessment);
+
+        if self.mainnet_provider.is_some() {
+            let execution = self.execute_with_guards(execution_id, opportunity, pool_info, bounds, execution_start).await;
+            // The swap may have moved the pool; the next read must see the new reserves
+            POOL_STATE_CACHE.invalidate(pool_info.address);
+            return execution;
+        }
+
         info!("🚀 Simulating trade execution for opportunity {}", opportunity.id);
 
         // Check if we're in simulation mode or have real execution capability
         if self.sepolia_provider.is_none() || self.wallet.is_none() {
+            if let Some(fork) = &self.fork {
+                let fork = fork.lock().await;
+                return self.execute_on_fork(&fork, execution_id, opportunity, pool_info, bounds, execution_start).await;
+            }
             // Pure simulation mode
+            // One draw per execution keeps the lock off the simulated latency
+            let seed = self.simulation_rng.lock().unwrap_or_else(|e| e.into_inner()).random::<u64>();
+            let mut rng = StdRng::seed_from_u64(seed);
             return create_simulated_execution(
                 execution_id,
                 opportunity,
                 volatility_metrics,
+                &*self.calibration.read().await,
                 execution_start,
+                &mut rng,
             ).await;
         }
 
-        // Testnet execution simulation
-        match self.execute_on_testnet(opportunity, volatility_metrics).await {
+        // Testnet execution: gas and fill come from the receipt
+        let trade_type = trade_type_of(opportunity);
+        let (preflight, min_amount_out, measured, result) = match self.execute_on_testnet(opportunity, bounds).await {
+            Ok(sent) => {
+                let measured = sent.measured(opportunity);
+                (Some(sent.preflight), sent.min_amount_out, measured, sent.result)
+            }
+            Err(e) => (None, None, SwapMeasurements::default(), Err(e)),
+        };
+        match result {
             Ok(tx_hash) => {
                 let execution_time = execution_start.elapsed().as_millis() as u64;
                 
@@ -91,145 +413,442 @@ impl TradeExecutionEngine {
                     id: execution_id,
                     opportunity_id: opportunity.id.clone(),
                     timestamp: chrono::Utc::now(),
-                    network: "Base Sepolia".to_string(),
-                    trade_type: if opportunity.direction.contains("Buy on Aerodrome") {
-                        TradeType::BuyDexSellCex
-                    } else {
-                        TradeType::BuyCexSellDex
-                    },
+                    network: self.network_name().to_string(),
+                    trade_type,
                     status: ExecutionStatus::Success,
                     tx_hash: Some(tx_hash),
-                    gas_used: Some(150000), // Estimated
-                    gas_price_gwei: Some(rust_decimal::Decimal::from(CONFIG.max_gas_price_gwei)),
+                    gas_used: measured.gas_used,
+                    gas_price_gwei: measured.gas_price_gwei,
                     execution_time_ms: execution_time,
                     expected_profit_usd: opportunity.net_profit_usd,
-                    actual_profit_usd: Some(opportunity.net_profit_usd * rust_decimal_macros::dec!(0.95)), // 5% slippage
-                    slippage_bps: Some(50),
+                    // Realized only once a CEX fill closes the trade
+                    actual_profit_usd: None,
+                    slippage_bps: measured.slippage_bps,
                     error_message: None,
+                    dex_fill_price: measured.dex_fill_price,
+                    cex_fill_price: None,
+                    cex_order_id: None,
+                    volatility_impact: None,
+                    simulated: false,
+                    preflight,
+                    swap_bounds: Some(bounds),
+                    min_amount_out,
                 })
             }
             Err(e) => {
                 warn!("Testnet execution failed: {}", e);
-                self.create_failed_execution(
+                let mut execution = self.create_failed_execution(
                     execution_id,
                     opportunity,
                     execution_start,
                     e.to_string(),
-                ).await
+                ).await?;
+                execution.preflight = preflight;
+                execution.swap_bounds = Some(bounds);
+                execution.min_amount_out = min_amount_out;
+                Ok(execution)
             }
         }
     }
 
+    /// Real-funds path: enforce the guards and execute, recording the fill
+    /// price from the receipt. Realized P&L and the daily loss limit are the
+    /// risk manager's.
+    async fn execute_with_guards(
+        &self,
+        execution_id: String,
+        opportunity: &ArbitrageOpportunity,
+        pool_info: &PoolInfo,
+        bounds: SwapBounds,
+        execution_start: std::time::Instant,
+    ) -> Result<TradeExecution> {
+        let guards = self.guards.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Mainnet guards not initialized"))?;
+        if self.risk_manager.is_none() {
+            return Err(anyhow::anyhow!("Mainnet execution needs a risk manager to enforce the daily loss limit"));
+        }
+
+        if let Err(e) = guards.check(opportunity).await {
+            warn!("🛑 Mainnet execution blocked: {}", e);
+            return self.create_failed_execution(execution_id, opportunity, execution_start, e.to_string()).await;
+        }
+
+        info!("🚀 Executing opportunity {} on Base mainnet", opportunity.id);
+        let trade_type = trade_type_of(opportunity);
+        let (preflight, min_amount_out, measured, result) = match self.execute_on_mainnet(opportunity, pool_info, bounds).await {
+            Ok(sent) => {
+                let measured = sent.measured(opportunity);
+                (Some(sent.preflight), sent.min_amount_out, measured, sent.result)
+            }
+            Err(e) => (None, None, SwapMeasurements::default(), Err(e)),
+        };
+        match result {
+            Ok(tx_hash) => {
+                Ok(TradeExecution {
+                    id: execution_id,
+                    opportunity_id: opportunity.id.clone(),
+                    timestamp: chrono::Utc::now(),
+                    network: self.network_name().to_string(),
+                    trade_type,
+                    status: ExecutionStatus::Success,
+                    tx_hash: Some(tx_hash),
+                    gas_used: measured.gas_used,
+                    gas_price_gwei: measured.gas_price_gwei,
+                    execution_time_ms: execution_start.elapsed().as_millis() as u64,
+                    expected_profit_usd: opportunity.net_profit_usd,
+                    actual_profit_usd: None,
+                    slippage_bps: measured.slippage_bps,
+                    error_message: None,
+                    dex_fill_price: measured.dex_fill_price,
+                    cex_fill_price: None,
+                    cex_order_id: None,
+                    volatility_impact: None,
+                    simulated: false,
+                    preflight,
+                    swap_bounds: Some(bounds),
+                    min_amount_out,
+                })
+            }
+            Err(e) => {
+                error!("Mainnet execution failed: {}", e);
+                let mut execution = self.create_failed_execution(execution_id, opportunity, execution_start, e.to_string()).await?;
+                execution.preflight = preflight;
+                execution.swap_bounds = Some(bounds);
+                execution.min_amount_out = min_amount_out;
+                Ok(execution)
+            }
+        }
+    }
+
+    async fn execute_on_mainnet(
+        &self,
+        opportunity: &ArbitrageOpportunity,
+        pool_info: &PoolInfo,
+        bounds: SwapBounds,
+    ) -> Result<SentSwap> {
+        let provider = self.mainnet_provider.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Mainnet provider not initialized"))?;
+
+        let to = self.wallet_address
+            .ok_or_else(|| anyhow::anyhow!("Wallet not initialized"))?;
+        // The router pulls this pool's tokens, so only they may be approved
+        self.policy.allow_pair_approvals(&pool_info.pair);
+        let config = CONFIG.get();
+        let max_fee = config.max_gas_price_gwei as u128 * 1_000_000_000;
+
+        let swap_data = encode_router_swap(opportunity, pool_info, to, bounds)?;
+        info!("📤 Sending transaction to Base mainnet:");
+        info!("   Router: {:?}", AERODROME_ROUTER_MAINNET);
+        let output_decimals = if opportunity.direction.contains("Buy on Aerodrome") {
+            pool_info.pair.base_decimals
+        } else {
+            pool_info.pair.quote_decimals
+        };
+        let tx = TransactionRequest::default()
+            .to(AERODROME_ROUTER_MAINNET)
+            .input(swap_data.into())
+            .gas_limit(300000)
+            .max_fee_per_gas(max_fee);
+        info!("   Pool: {} ({} ETH)", pool_info.name, opportunity.size_eth);
+        info!("   Bounds: {} bps slippage, {}s deadline", bounds.slippage_bps, bounds.deadline_secs);
+
+        let confirmations = config.required_confirmations(opportunity.size_eth * opportunity.cex_price);
+        self.preflight_and_send(provider.as_ref(), tx, output_decimals, confirmations).await
+    }
+
+    /// Send the opportunity's swap through the Uniswap V2 router on Sepolia
     async fn execute_on_testnet(
         &self,
         opportunity: &ArbitrageOpportunity,
-        _volatility_metrics: &VolatilityMetrics,
-    ) -> Result<String> {
-        use crate::config::EXECUTION_TIMEOUT_SECS;
-        
+        bounds: SwapBounds,
+    ) -> Result<SentSwap> {
         let provider = self.sepolia_provider.as_ref()
             .ok_or_else(|| anyhow::anyhow!("Sepolia provider not initialized"))?;
         
-        let _wallet = self.wallet.as_ref()
+        let wallet_address = self.wallet_address
             .ok_or_else(|| anyhow::anyhow!("Wallet not initialized"))?;
 
+        let weth_decimals = pools::token_decimals(provider.as_ref(), WETH_SEPOLIA).await?;
+        let usdc_decimals = pools::token_decimals(provider.as_ref(), USDC_SEPOLIA).await?;
+        let output_decimals = if opportunity.direction.contains("Buy on Aerodrome") { weth_decimals } else { usdc_decimals };
+
         // For testing, we'll use Uniswap V2 Router on Sepolia
         // We can replace this with any DEX router on Sepolia
-        const UNISWAP_V2_ROUTER_SEPOLIA: Address = address!("0xC532a74256D3Db42D0Bf7a0400fEFDbad7694008");
-
-        // Build swap transaction data
-        let swap_data = self.encode_swap_data(opportunity)?;
-        
-        // Determine value - if buying WETH with USDC, no ETH value needed
-        // If selling WETH for USDC, we need to send ETH
-        let value = if opportunity.direction.contains("Sell on Aerodrome") {
-            U256::from((opportunity.size_eth * dec!(1e18)).to_u128().unwrap_or(0))
-        } else {
-            U256::from(0)
-        };
+        let swap_data = sepolia_swap_calldata(opportunity, wallet_address, bounds, weth_decimals, usdc_decimals)?;
         
-        // Build the transaction
+        // Token-to-token swap: WETH is spent as an ERC20, so no ETH value is sent
         let tx = TransactionRequest::default()
             .to(UNISWAP_V2_ROUTER_SEPOLIA)
-            .value(value)
             .input(swap_data.into())
             .gas_limit(300000)
-            .max_fee_per_gas(CONFIG.max_gas_price_gwei as u128 * 1_000_000_000)
+            .max_fee_per_gas(CONFIG.get().max_gas_price_gwei as u128 * 1_000_000_000)
             .max_priority_fee_per_gas(1_000_000_000); // 1 gwei
 
         info!("📤 Sending transaction to Sepolia:");
         info!("   Router: {:?}", UNISWAP_V2_ROUTER_SEPOLIA);
-        info!("   Value: {} ETH", opportunity.size_eth);
+        info!("   From: {:?}", wallet_address);
+        info!("   Size: {} ETH", opportunity.size_eth);
+        info!("   Bounds: {} bps slippage, {}s deadline", bounds.slippage_bps, bounds.deadline_secs);
 
-        // Sign and send transaction
-        let pending_tx = provider
-            .send_transaction(tx)
-            .await
-            .context("Failed to send transaction")?;
+        let confirmations = CONFIG.get().required_confirmations(opportunity.size_eth * opportunity.cex_price);
+        self.preflight_and_send(provider.as_ref(), tx, output_decimals, confirmations).await
+    }
 
-        let tx_hash = format!("{:?}", pending_tx.tx_hash());
-        
-        info!("📡 Transaction sent on Base Sepolia: {}", tx_hash);
+    /// Simulate `tx` with eth_call and send it only if the simulation passes
+    async fn preflight_and_send(
+        &self,
+        provider: &dyn Provider,
+        tx: TransactionRequest,
+        output_decimals: i32,
+        confirmations: u64,
+    ) -> Result<SentSwap> {
+        let wallet_address = self.wallet_address
+            .ok_or_else(|| anyhow::anyhow!("Wallet not initialized"))?;
 
-        // Wait for confirmation with timeout
+        // Router swaps pull their input from the wallet
+        let router = tx.to.and_then(|to| to.to().copied());
+        let spend = tx.input.input().and_then(|data| router_swap_input(data));
+        // An unfunded wallet fails here with its balances, before an approval spends gas
+        check_wallet_balances(provider, wallet_address, &tx, spend).await?;
+        if let (Some(router), Some((token, amount))) = (router, spend) {
+            self.ensure_allowance(provider, token, router, amount, confirmations).await?;
+        }
+
+        let min_amount_out = tx.input.input()
+            .and_then(|data| router_swap_min_out(data))
+            .and_then(|raw| scale_from_raw(raw, output_decimals));
+        let output = tx.input.input().and_then(|data| router_swap_output(data));
+        let preflight = preflight_swap(provider, &tx, wallet_address, Some(output_decimals)).await;
+        let sent = match &preflight.revert_reason {
+            Some(reason) => Err(anyhow::anyhow!("Preflight simulation reverted: {}", reason)),
+            None => self.send_with_nonce(provider, tx, confirmations).await,
+        };
+        if let (Some(router), Some((token, amount))) = (router, spend) {
+            match sent {
+                Ok(_) => self.allowances.spend(token, router, amount).await,
+                Err(_) => self.allowances.invalidate(token, router).await,
+            }
+        }
+
+        // Realized amounts from the receipt's token transfers
+        let filled = match (&sent, spend, output) {
+            (Ok(receipt), Some((token_in, raw_in)), Some((token_out, recipient))) => {
+                let decimals_in = pools::token_decimals(provider, token_in).await.ok();
+                decimals_in
+                    .and_then(|decimals_in| scale_from_raw(raw_in, decimals_in))
+                    .zip(scale_from_raw(received_amount(receipt, token_out, recipient), output_decimals))
+            }
+            _ => None,
+        };
+        let gas = sent.as_ref().ok().map(|receipt| {
+            (receipt.gas_used as u64, rust_decimal::Decimal::from(receipt.effective_gas_price) / dec!(1_000_000_000))
+        });
+        let result = sent.map(|receipt| format!("{:?}", receipt.transaction_hash));
+        Ok(SentSwap { preflight, min_amount_out, filled, gas, result })
+    }
+
+    /// Make sure the wallet has approved `spender` for at least `amount` of
+    /// `token`, reading the chain only when the cache can't tell
+    async fn ensure_allowance(
+        &self,
+        provider: &dyn Provider,
+        token: Address,
+        spender: Address,
+        amount: U256,
+        confirmations: u64,
+    ) -> Result<()> {
+        if self.allowances.covers(token, spender, amount).await {
+            return Ok(());
+        }
+        let wallet_address = self.wallet_address
+            .ok_or_else(|| anyhow::anyhow!("Wallet not initialized"))?;
+        let allowance = get_allowance(provider, token, wallet_address, spender).await?;
+        self.allowances.record(token, spender, allowance).await;
+        if allowance >= amount {
+            return Ok(());
+        }
+
+        let approved = approval_amount(amount, CONFIG.get().infinite_approvals);
+        info!("🔓 Approving {:?} to spend {} of token {:?} (allowance {})", spender, approved, token, allowance);
+        let tx = TransactionRequest::default()
+            .to(token)
+            .input(approve_calldata(spender, approved).into())
+            .gas_limit(APPROVAL_GAS_LIMIT);
+        self.send_with_nonce(provider, tx, confirmations).await
+            .context("Token approval failed")?;
+        self.allowances.record(token, spender, approved).await;
+        Ok(())
+    }
+
+    /// Send `tx` with a nonce from the nonce manager, publicly or through the
+    /// private submitter, and wait until it has `confirmations` blocks.
+    /// Stuck or gapped nonces from earlier sends are cleared first.
+    async fn send_with_nonce(&self, provider: &dyn Provider, tx: TransactionRequest, confirmations: u64) -> Result<TransactionReceipt> {
+        use crate::config::{BASE_BLOCK_TIME_SECS, EXECUTION_TIMEOUT_SECS};
+
+        let wallet_address = self.wallet_address
+            .ok_or_else(|| anyhow::anyhow!("Wallet not initialized"))?;
+        self.policy.check(&tx).context("Transaction rejected by signer policy")?;
+
+        if let Err(e) = self.unstick_nonces(provider, wallet_address).await {
+            warn!("Nonce maintenance failed: {}", e);
+        }
+
+        let max_fee = tx.max_fee_per_gas.unwrap_or(CONFIG.get().max_gas_price_gwei as u128 * 1_000_000_000);
+        let priority_fee = tx.max_priority_fee_per_gas.unwrap_or(1_000_000_000);
+        let nonce = self.nonce_manager.reserve(provider, wallet_address, max_fee, priority_fee).await?;
+        let tx = tx
+            .from(wallet_address)
+            .nonce(nonce)
+            .max_fee_per_gas(max_fee)
+            .max_priority_fee_per_gas(priority_fee);
+
+        let sent_hash = match self.submit(provider, tx).await {
+            Ok(sent_hash) => sent_hash,
+            Err(e) => {
+                self.nonce_manager.release(wallet_address, nonce).await;
+                return Err(anyhow::anyhow!("Failed to send transaction: {}", e));
+            }
+        };
+
+        let tx_hash = format!("{:?}", sent_hash);
+        self.nonce_manager.mark_sent(wallet_address, nonce, tx_hash.clone(), max_fee, priority_fee).await;
+        let submission = self.submitter.as_ref().map_or("public", |s| s.mode().as_str());
+        info!("📡 Transaction sent on {} ({}, nonce {}), waiting for {} confirmation(s): {}",
+            self.network_name(), submission, nonce, confirmations, tx_hash);
+
+        // Wait for the required depth with timeout; a timed-out nonce stays tracked for replacement
+        let timeout_secs = EXECUTION_TIMEOUT_SECS + (confirmations - 1) * BASE_BLOCK_TIME_SECS;
         tokio::select! {
-            result = pending_tx.get_receipt() => {
-                match result {
-                    Ok(receipt) => {
-                        info!("✅ Transaction confirmed: {:?}", receipt.transaction_hash);
-                        Ok(tx_hash)
-                    }
-                    Err(e) => Err(anyhow::anyhow!("Transaction failed: {}", e))
+            result = wait_for_receipt(provider, sent_hash, confirmations) => {
+                let receipt = result.map_err(|e| anyhow::anyhow!("Transaction failed: {}", e))?;
+                self.nonce_manager.confirm(wallet_address, nonce).await;
+                if !receipt.status() {
+                    return Err(anyhow::anyhow!("Transaction reverted: {}", tx_hash));
                 }
+                info!("✅ Transaction confirmed with {} block(s): {:?}", confirmations, receipt.transaction_hash);
+                Ok(receipt)
             }
-            _ = tokio::time::sleep(Duration::from_secs(EXECUTION_TIMEOUT_SECS)) => {
-                Err(anyhow::anyhow!("Transaction timeout after {} seconds", EXECUTION_TIMEOUT_SECS))
+            _ = tokio::time::sleep(Duration::from_secs(timeout_secs)) => {
+                Err(anyhow::anyhow!("Transaction not confirmed to depth {} after {} seconds", confirmations, timeout_secs))
             }
         }
     }
 
-    fn encode_swap_data(&self, opportunity: &ArbitrageOpportunity) -> Result<Vec<u8>> {
-        use rust_decimal::prelude::*;
-        use rust_decimal_macros::dec;
-        use crate::types::{USDC_SEPOLIA, WETH_SEPOLIA};
-        
-        // Calculate amounts
-        let amount_in = U256::from((opportunity.size_eth * dec!(1e18)).to_u128().unwrap_or(0));
-        
-        // Calculate minimum amount out with slippage
-        let expected_out = opportunity.size_eth * opportunity.cex_price;
-        let slippage_factor = dec!(1) - (rust_decimal::Decimal::from(CONFIG.slippage_tolerance_bps) / dec!(10000));
-        let amount_out_min = U256::from((expected_out * slippage_factor * dec!(1e6)).to_u128().unwrap_or(0));
-        
-        // Build the path based on trade direction
-        let path: Vec<Address> = if opportunity.direction.contains("Buy on Aerodrome") {
-            vec![USDC_SEPOLIA, WETH_SEPOLIA]
-        } else {
-            vec![WETH_SEPOLIA, USDC_SEPOLIA]
-        };
-        
-        let to = address!("0000000000000000000000000000000000000001");
-        let deadline = U256::from(SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?.as_secs() + 1200);
-        
-        // Encode the function call
-        let mut encoded = keccak256("swapExactTokensForTokens(uint256,uint256,address[],address,uint256)")[..4].to_vec();
-        
-        // Encode parameters
-        encoded.extend_from_slice(&amount_in.to_be_bytes::<32>());
-        encoded.extend_from_slice(&amount_out_min.to_be_bytes::<32>());
-        encoded.extend_from_slice(&U256::from(160).to_be_bytes::<32>());
-        encoded.extend_from_slice(&[0u8; 12]);
-        encoded.extend_from_slice(to.as_slice());
-        encoded.extend_from_slice(&deadline.to_be_bytes::<32>());
-        encoded.extend_from_slice(&U256::from(path.len()).to_be_bytes::<32>());
-        
-        for addr in path {
-            encoded.extend_from_slice(&[0u8; 12]); // Padding for address
-            encoded.extend_from_slice(addr.as_slice());
+    /// Send `tx` (nonce and fees set) through the private submitter when configured,
+    /// otherwise publicly through `provider`
+    async fn submit(&self, provider: &dyn Provider, tx: TransactionRequest) -> Result<TxHash> {
+        match &self.submitter {
+            Some(submitter) => submitter.submit(provider, tx).await,
+            None => provider.send_transaction(tx).await
+                .map(|pending_tx| *pending_tx.tx_hash())
+                .map_err(anyhow::Error::from),
         }
-        
-        info!("📝 Encoded swap data for testnet execution");
-        Ok(encoded)
+    }
+
+    /// Fill nonce gaps and replace transactions pending past the execution
+    /// timeout with zero-value self-transfers at bumped fees, so later
+    /// nonces aren't blocked behind them.
+    async fn unstick_nonces(&self, provider: &dyn Provider, wallet: Address) -> Result<()> {
+        use crate::config::EXECUTION_TIMEOUT_SECS;
+
+        let base_max_fee = CONFIG.get().max_gas_price_gwei as u128 * 1_000_000_000;
+        for nonce in self.nonce_manager.detect_gaps(provider, wallet).await? {
+            self.nonce_manager.reserve_gap(wallet, nonce, base_max_fee, 1_000_000_000).await;
+            self.send_cancellation(provider, wallet, nonce, base_max_fee, 1_000_000_000).await?;
+        }
+
+        for (nonce, pending) in self.nonce_manager.pending(wallet).await {
+            if pending.tx_hash.is_none() || pending.updated_at.elapsed() < Duration::from_secs(EXECUTION_TIMEOUT_SECS) {
+                continue;
+            }
+            if let Some((max_fee, priority_fee)) = self.nonce_manager.replacement_fees(wallet, nonce).await {
+                warn!("♻️  Replacing stuck nonce {} ({:?})", nonce, pending.tx_hash);
+                self.send_cancellation(provider, wallet, nonce, max_fee, priority_fee).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn send_cancellation(
+        &self,
+        provider: &dyn Provider,
+        wallet: Address,
+        nonce: u64,
+        max_fee: u128,
+        priority_fee: u128,
+    ) -> Result<()> {
+        let tx = TransactionRequest::default()
+            .from(wallet)
+            .to(wallet)
+            .value(U256::ZERO)
+            .nonce(nonce)
+            .gas_limit(21000)
+            .max_fee_per_gas(max_fee)
+            .max_priority_fee_per_gas(priority_fee);
+        self.policy.check(&tx).context("Nonce replacement rejected by signer policy")?;
+
+        // Replacements go the same way as trades, so private mode never leaks a pending nonce
+        let sent_hash = self.submit(provider, tx).await
+            .context("Failed to send nonce replacement")?;
+        let tx_hash = format!("{:?}", sent_hash);
+        self.nonce_manager.mark_sent(wallet, nonce, tx_hash.clone(), max_fee, priority_fee).await;
+        info!("📡 Sent nonce {} replacement: {}", nonce, tx_hash);
+        Ok(())
+    }
+
+    /// Run the opportunity's router swap on the fork and report what it
+    /// actually paid, received and spent in gas
+    async fn execute_on_fork(
+        &self,
+        fork: &AnvilFork,
+        execution_id: String,
+        opportunity: &ArbitrageOpportunity,
+        pool_info: &PoolInfo,
+        bounds: SwapBounds,
+        execution_start: std::time::Instant,
+    ) -> Result<TradeExecution> {
+        let buy_on_dex = opportunity.direction.contains("Buy on Aerodrome");
+        let swap = match fork_swap(fork, opportunity, pool_info, bounds).await {
+            Ok(swap) => swap,
+            Err(e) => {
+                error!("Fork execution failed: {:#}", e);
+                let mut execution = self.create_failed_execution(execution_id, opportunity, execution_start, format!("{:#}", e)).await?;
+                execution.network = "Base Mainnet fork".to_string();
+                execution.simulated = true;
+                execution.swap_bounds = Some(bounds);
+                return Ok(execution);
+            }
+        };
+
+        let quoted_price = opportunity.quoted_dex_price.unwrap_or(opportunity.dex_price);
+        let fill_price = swap.fill_price(buy_on_dex).filter(|_| !swap.reverted);
+        let slippage_bps = fill_price.and_then(|fill| fill_slippage_bps(fill, quoted_price, buy_on_dex));
+
+        Ok(TradeExecution {
+            id: execution_id,
+            opportunity_id: opportunity.id.clone(),
+            timestamp: chrono::Utc::now(),
+            network: "Base Mainnet fork".to_string(),
+            trade_type: if buy_on_dex { TradeType::BuyDexSellCex } else { TradeType::BuyCexSellDex },
+            status: if swap.reverted { ExecutionStatus::Failed } else { ExecutionStatus::Simulated },
+            tx_hash: Some(swap.tx_hash),
+            gas_used: Some(swap.gas_used),
+            gas_price_gwei: Some(swap.gas_price_gwei),
+            execution_time_ms: execution_start.elapsed().as_millis() as u64,
+            expected_profit_usd: opportunity.net_profit_usd,
+            actual_profit_usd: None,
+            slippage_bps,
+            error_message: swap.reverted.then(|| "Swap reverted on the fork".to_string()),
+            dex_fill_price: fill_price,
+            cex_fill_price: None,
+            cex_order_id: None,
+            volatility_impact: None,
+            simulated: true,
+            preflight: None,
+            swap_bounds: Some(bounds),
+            min_amount_out: swap.min_amount_out,
+        })
     }
 
     async fn create_failed_execution(
@@ -243,12 +862,8 @@ impl TradeExecutionEngine {
             id: execution_id,
             opportunity_id: opportunity.id.clone(),
             timestamp: chrono::Utc::now(),
-            network: "Base Sepolia".to_string(),
-            trade_type: if opportunity.direction.contains("Buy on Aerodrome") {
-                TradeType::BuyDexSellCex
-            } else {
-                TradeType::BuyCexSellDex
-            },
+            network: self.network_name().to_string(),
+            trade_type: trade_type_of(opportunity),
             status: ExecutionStatus::Failed,
             tx_hash: None,
             gas_used: None,
@@ -258,6 +873,120 @@ impl TradeExecutionEngine {
             actual_profit_usd: None,
             slippage_bps: None,
             error_message: Some(error),
+            dex_fill_price: None,
+            cex_fill_price: None,
+            cex_order_id: None,
+            volatility_impact: None,
+            simulated: false,
+            preflight: None,
+            swap_bounds: None,
+            min_amount_out: None,
         })
     }
 }
+
+/// Aerodrome v2 Router `swapExactTokensForTokens` calldata for an opportunity,
+/// paying out to `to`
+pub fn encode_router_swap(
+    opportunity: &ArbitrageOpportunity,
+    pool_info: &PoolInfo,
+    to: Address,
+    bounds: SwapBounds,
+) -> Result<Vec<u8>> {
+    router_swap_calldata(
+        pool_info,
+        opportunity.direction.contains("Buy on Aerodrome"),
+        opportunity.size_eth,
+        opportunity.quoted_dex_price.unwrap_or(opportunity.dex_price),
+        to,
+        bounds,
+    )
+}
+
+/// Raw token amount for calldata; an amount too large to encode fails the trade
+fn raw_amount(amount: rust_decimal::Decimal, decimals: i32) -> Result<U256> {
+    scale_to_raw(amount, decimals)
+        .ok_or_else(|| anyhow::anyhow!("Amount {} does not fit in a raw token amount", amount))
+}
+
+/// Exact input and minimum output of swapping `size` base at `price`: buying
+/// spends `size * price` quote for at least `size` base, selling spends `size`
+/// base for at least `size * price` quote, both less the slippage bound
+fn swap_amounts(
+    buy_base: bool,
+    size: rust_decimal::Decimal,
+    price: rust_decimal::Decimal,
+    bounds: SwapBounds,
+    base_decimals: i32,
+    quote_decimals: i32,
+) -> Result<(U256, U256)> {
+    let slippage_factor = dec!(1) - rust_decimal::Decimal::from(bounds.slippage_bps) / dec!(10000);
+    if buy_base {
+        Ok((raw_amount(size * price, quote_decimals)?, raw_amount(size * slippage_factor, base_decimals)?))
+    } else {
+        Ok((raw_amount(size, base_decimals)?, raw_amount(size * price * slippage_factor, quote_decimals)?))
+    }
+}
+
+/// Encode Uniswap V2 Router `swapExactTokensForTokens` for the Sepolia
+/// WETH/USDC path, with the same direction-aware amounts as the mainnet route
+pub fn sepolia_swap_calldata(
+    opportunity: &ArbitrageOpportunity,
+    to: Address,
+    bounds: SwapBounds,
+    weth_decimals: i32,
+    usdc_decimals: i32,
+) -> Result<Vec<u8>> {
+    let buy_base = opportunity.direction.contains("Buy on Aerodrome");
+    let price = opportunity.quoted_dex_price.unwrap_or(opportunity.dex_price);
+    let (amount_in, amount_out_min) = swap_amounts(buy_base, opportunity.size_eth, price, bounds, weth_decimals, usdc_decimals)?;
+    let path = if buy_base { vec![USDC_SEPOLIA, WETH_SEPOLIA] } else { vec![WETH_SEPOLIA, USDC_SEPOLIA] };
+
+    Ok(IUniswapV2Router::swapExactTokensForTokensCall {
+        amountIn: amount_in,
+        amountOutMin: amount_out_min,
+        path,
+        to,
+        deadline: swap_deadline(&bounds)?,
+    }.abi_encode())
+}
+
+/// Encode Aerodrome Router `swapExactTokensForTokens` with a single-hop route
+/// trading `size` base tokens at `price` (quote per base), accepting
+/// `bounds.slippage_bps` below it as the minimum output until
+/// `bounds.deadline_secs` from now. Opportunity prices are in USD, so only
+/// USD-quoted pairs can be routed.
+pub fn router_swap_calldata(
+    pool_info: &PoolInfo,
+    buy_base: bool,
+    size: rust_decimal::Decimal,
+    price: rust_decimal::Decimal,
+    to: Address,
+    bounds: SwapBounds,
+) -> Result<Vec<u8>> {
+    if matches!(pool_info.kind, PoolKind::Concentrated { .. }) {
+        return Err(anyhow::anyhow!("CL pools cannot be routed through the Aerodrome v2 Router"));
+    }
+    let pair = &pool_info.pair;
+    if !pair.quote_is_usd() {
+        return Err(anyhow::anyhow!("Cannot route {}: only USD-quoted pairs are executable", pair));
+    }
+
+    let (token_in, token_out) = if buy_base { (pair.quote, pair.base) } else { (pair.base, pair.quote) };
+    let (amount_in, amount_out_min) = swap_amounts(buy_base, size, price, bounds, pair.base_decimals, pair.quote_decimals)?;
+    let route = IAerodromeRouter::Route {
+        from: token_in,
+        to: token_out,
+        stable: pool_info.is_stable,
+        factory: AERODROME_POOL_FACTORY_MAINNET,
+    };
+    let deadline = swap_deadline(&bounds)?;
+
+    Ok(IAerodromeRouter::swapExactTokensForTokensCall {
+        amountIn: amount_in,
+        amountOutMin: amount_out_min,
+        routes: vec![route],
+        to,
+        deadline,
+    }.abi_encode())
+}
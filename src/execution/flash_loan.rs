@@ -0,0 +1,187 @@
+//! Flash-loan planning for a wrapped DEX leg
+//!
+//! A flash loan borrows the DEX leg's input token from Aave v3 or the
+//! Balancer vault. The lender calls a receiver contract, which runs the
+//! router swap passed in the loan params and must hold the loan plus fee of
+//! the borrowed token when the callback returns, or the whole transaction
+//! reverts.
+//!
+//! Only on-chain legs can repay the loan. Every arbitrage the bot trades
+//! closes on Binance, so each plan fails [`check_flash_loan_repayment`] and
+//! the engine never sends one; `FLASH_LOAN_SOURCE` is rejected at startup
+//! until a strategy closes back into the borrowed token on-chain.
+
+use alloy::{
+    primitives::{keccak256, Address, Bytes, U256},
+    sol_types::SolValue,
+};
+use anyhow::Result;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use crate::{
+    types::{ArbitrageOpportunity, PoolInfo, AAVE_V3_POOL_BASE, AERODROME_ROUTER_MAINNET, BALANCER_VAULT},
+    utils::scale_to_raw,
+};
+
+/// Aave v3 Pool single-asset flash loan
+pub const AAVE_FLASH_LOAN_SIGNATURE: &str = "flashLoanSimple(address,address,uint256,bytes,uint16)";
+/// Balancer v2 Vault flash loan
+pub const BALANCER_FLASH_LOAN_SIGNATURE: &str = "flashLoan(address,address[],uint256[],bytes)";
+/// Loan, callback swap and repayment, within the default signer gas limit
+pub const FLASH_LOAN_GAS_LIMIT: u64 = 450_000;
+
+/// Lender the DEX leg borrows from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlashLoanSource {
+    /// Aave v3 Pool on Base, 5 bps premium
+    Aave,
+    /// Balancer v2 Vault, no fee
+    Balancer,
+}
+
+impl FlashLoanSource {
+    pub const ALL: [FlashLoanSource; 2] = [FlashLoanSource::Aave, FlashLoanSource::Balancer];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlashLoanSource::Aave => "aave",
+            FlashLoanSource::Balancer => "balancer",
+        }
+    }
+
+    /// Contract the loan is requested from
+    pub fn lender(&self) -> Address {
+        match self {
+            FlashLoanSource::Aave => AAVE_V3_POOL_BASE,
+            FlashLoanSource::Balancer => BALANCER_VAULT,
+        }
+    }
+
+    pub fn signature(&self) -> &'static str {
+        match self {
+            FlashLoanSource::Aave => AAVE_FLASH_LOAN_SIGNATURE,
+            FlashLoanSource::Balancer => BALANCER_FLASH_LOAN_SIGNATURE,
+        }
+    }
+
+    pub fn fee_bps(&self) -> u32 {
+        match self {
+            FlashLoanSource::Aave => 5,
+            FlashLoanSource::Balancer => 0,
+        }
+    }
+}
+
+impl fmt::Display for FlashLoanSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FlashLoanSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter()
+            .find(|source| source.as_str() == s.trim().to_lowercase())
+            .ok_or_else(|| format!("unknown flash loan source {:?}", s))
+    }
+}
+
+/// What a flash-loan execution borrows and what it nets after the fee
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlashLoanPlan {
+    pub source: FlashLoanSource,
+    /// The DEX leg's input token
+    pub asset: Address,
+    pub amount: U256,
+    /// Token the on-chain legs leave the receiver holding when the callback returns
+    pub proceeds_asset: Address,
+    pub notional_usd: Decimal,
+    pub fee_usd: Decimal,
+    /// Opportunity net profit less the flash fee
+    pub net_profit_usd: Decimal,
+}
+
+/// Borrow the input of the opportunity's DEX leg: the quote token when
+/// buying on Aerodrome, the base token when selling there. Fails if the
+/// amount cannot be encoded.
+pub fn plan_flash_loan(
+    source: FlashLoanSource,
+    opportunity: &ArbitrageOpportunity,
+    pool_info: &PoolInfo,
+) -> Result<FlashLoanPlan> {
+    let pair = &pool_info.pair;
+    let dex_price = opportunity.quoted_dex_price.unwrap_or(opportunity.dex_price);
+    let notional_usd = opportunity.size_eth * dex_price;
+    let (asset, amount, proceeds_asset) = if opportunity.direction.contains("Buy on Aerodrome") {
+        (pair.quote, scale_to_raw(notional_usd, pair.quote_decimals), pair.base)
+    } else {
+        (pair.base, scale_to_raw(opportunity.size_eth, pair.base_decimals), pair.quote)
+    };
+    let amount = amount.ok_or_else(|| anyhow::anyhow!("Flash loan of ${:.2} does not fit in a raw token amount", notional_usd))?;
+    let fee_usd = notional_usd * Decimal::from(source.fee_bps()) / dec!(10000);
+    Ok(FlashLoanPlan {
+        source,
+        asset,
+        amount,
+        proceeds_asset,
+        notional_usd,
+        fee_usd,
+        net_profit_usd: opportunity.net_profit_usd - fee_usd,
+    })
+}
+
+/// Params handed to the receiver: `abi.encode(router, swapCalldata, repayAmount)`
+pub fn flash_loan_params(swap_calldata: Vec<u8>, repay_amount: U256) -> Vec<u8> {
+    (AERODROME_ROUTER_MAINNET, Bytes::from(swap_calldata), repay_amount).abi_encode_params()
+}
+
+/// Lender calldata requesting `plan` for `receiver` with the receiver `params`
+pub fn encode_flash_loan(plan: &FlashLoanPlan, receiver: Address, params: Vec<u8>) -> Vec<u8> {
+    let mut encoded = keccak256(plan.source.signature())[..4].to_vec();
+    let params = Bytes::from(params);
+    match plan.source {
+        FlashLoanSource::Aave => {
+            encoded.extend_from_slice(&(receiver, plan.asset, plan.amount, params, 0u16).abi_encode_params());
+        }
+        FlashLoanSource::Balancer => {
+            encoded.extend_from_slice(&(receiver, vec![plan.asset], vec![plan.amount], params).abi_encode_params());
+        }
+    }
+    encoded
+}
+
+/// Loan plus fee in raw token units, rounded up
+pub fn repay_amount(plan: &FlashLoanPlan) -> U256 {
+    let fee = (plan.amount * U256::from(plan.source.fee_bps())).div_ceil(U256::from(10000u64));
+    plan.amount + fee
+}
+
+/// Reject the plan unless its on-chain legs end in the borrowed token, so
+/// the receiver can repay from them. The off-chain CEX leg never counts.
+pub fn check_flash_loan_repayment(plan: &FlashLoanPlan) -> Result<()> {
+    if plan.proceeds_asset != plan.asset {
+        return Err(anyhow::anyhow!(
+            "Flash loan of {:?} cannot be repaid: the DEX leg pays out {:?} and the closing leg is on the CEX",
+            plan.asset, plan.proceeds_asset
+        ));
+    }
+    Ok(())
+}
+
+/// Reject the plan unless it still clears `min_profit_usd` after the flash
+/// fee. Only meaningful for a plan that passed [`check_flash_loan_repayment`].
+pub fn check_flash_loan_profit(plan: &FlashLoanPlan, min_profit_usd: Decimal) -> Result<()> {
+    if plan.net_profit_usd < min_profit_usd {
+        return Err(anyhow::anyhow!(
+            "Flash loan fee ${:.4} ({}) leaves ${:.4} profit, below ${:.2}",
+            plan.fee_usd, plan.source, plan.net_profit_usd, min_profit_usd
+        ));
+    }
+    Ok(())
+}
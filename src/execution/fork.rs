@@ -0,0 +1,198 @@
+//! Anvil forks of Base mainnet
+//!
+//! Token safety checks and `SIMULATION_MODE=fork` run real transactions
+//! against a local fork instead of the live chain. A fork is either spawned
+//! from `ANVIL_PATH` against the mainnet RPC, or an Anvil node already
+//! running at `ANVIL_FORK_URL`.
+
+use alloy::{
+    primitives::{keccak256, Address, U256},
+    providers::{Provider, ProviderBuilder},
+    rpc::types::eth::{TransactionReceipt, TransactionRequest},
+    sol_types::SolCall,
+};
+use anyhow::{Context, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use serde_json::json;
+use std::net::TcpListener;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::{Child, Command};
+use tracing::info;
+use crate::{
+    config::Config,
+    execution::{encode_router_swap, router_swap_input, router_swap_min_out},
+    network::{call_contract, mainnet_rpc_url, IERC20},
+    types::{ArbitrageOpportunity, PoolInfo, SwapBounds, AERODROME_ROUTER_MAINNET},
+    utils::scale_from_raw,
+    ConcreteProvider,
+};
+
+const ANVIL_STARTUP_TIMEOUT_SECS: u64 = 30;
+const FORK_GAS_LIMIT: u64 = 1_000_000;
+
+/// A local Anvil fork; a spawned node is killed when dropped
+pub struct AnvilFork {
+    _child: Option<Child>,
+    pub provider: ConcreteProvider,
+    /// Upstream RPC of a spawned fork; None re-forks a connected node's own upstream
+    fork_url: Option<String>,
+}
+
+impl AnvilFork {
+    /// Fork for fork-mode simulation: `ANVIL_FORK_URL` if set, otherwise a
+    /// node spawned against the mainnet RPC
+    pub async fn from_config(config: &Config) -> Result<Self> {
+        match &config.anvil_fork_url {
+            Some(url) => Self::connect(url).await,
+            None => Self::spawn(&config.anvil_path, &mainnet_rpc_url(config)?).await,
+        }
+    }
+
+    pub async fn spawn(anvil_path: &str, fork_url: &str) -> Result<Self> {
+        let port = TcpListener::bind("127.0.0.1:0")?.local_addr()?.port();
+
+        let child = Command::new(anvil_path)
+            .args(["--fork-url", fork_url, "--port", &port.to_string(), "--silent"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to start {}", anvil_path))?;
+        let provider = Self::wait_ready(&format!("http://127.0.0.1:{}", port)).await?;
+        Ok(Self { _child: Some(child), provider, fork_url: Some(fork_url.to_string()) })
+    }
+
+    /// Use an Anvil node that is already running
+    pub async fn connect(url: &str) -> Result<Self> {
+        let provider = Self::wait_ready(url).await?;
+        Ok(Self { _child: None, provider, fork_url: None })
+    }
+
+    async fn wait_ready(url: &str) -> Result<ConcreteProvider> {
+        let provider: ConcreteProvider = ProviderBuilder::new()
+            .on_http(url.parse()?)
+            .boxed();
+
+        let deadline = Instant::now() + Duration::from_secs(ANVIL_STARTUP_TIMEOUT_SECS);
+        while provider.get_block_number().await.is_err() {
+            if Instant::now() > deadline {
+                return Err(anyhow::anyhow!("Anvil at {} not reachable within {}s", url, ANVIL_STARTUP_TIMEOUT_SECS));
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+        Ok(provider)
+    }
+
+    /// Re-fork at the latest block, discarding transactions sent to the fork
+    pub async fn reset(&self) -> Result<()> {
+        let forking = match &self.fork_url {
+            Some(url) => json!({ "jsonRpcUrl": url }),
+            None => json!({}),
+        };
+        self.provider.raw_request::<_, serde_json::Value>("anvil_reset".into(), (json!({ "forking": forking }),)).await
+            .context("Failed to reset the fork")?;
+        Ok(())
+    }
+
+    /// Set `owner`'s balance of `token` to `amount`
+    pub async fn deal(&self, token: Address, owner: Address, amount: U256) -> Result<()> {
+        self.provider.raw_request::<_, serde_json::Value>("anvil_dealERC20".into(), (owner, token, amount)).await
+            .with_context(|| format!("Failed to fund {} with {}", owner, token))?;
+        Ok(())
+    }
+}
+
+pub(crate) fn probe_address(label: &str) -> Address {
+    Address::from_word(keccak256(format!("aero-arb-mm-bot token probe {}", label)))
+}
+
+pub(crate) async fn balance_of(provider: &dyn Provider, token: Address, owner: Address) -> Result<U256> {
+    Ok(call_contract(provider, token, IERC20::balanceOfCall { account: owner }).await?._0)
+}
+
+/// Send a transaction as an impersonated account on the fork
+pub(crate) async fn send_as(fork: &ConcreteProvider, from: Address, to: Address, data: Vec<u8>) -> Result<TransactionReceipt> {
+    fork.raw_request::<_, serde_json::Value>("anvil_impersonateAccount".into(), (from,)).await?;
+    fork.raw_request::<_, serde_json::Value>("anvil_setBalance".into(), (from, U256::from(10).pow(U256::from(18)))).await?;
+
+    // A fixed gas limit skips estimation, so reverts are mined instead of rejected
+    let tx = TransactionRequest::default()
+        .from(from)
+        .to(to)
+        .input(data.into())
+        .gas_limit(FORK_GAS_LIMIT);
+    Ok(fork.send_transaction(tx).await?.get_receipt().await?)
+}
+
+/// An opportunity's router swap as mined on the fork
+#[derive(Debug, Clone)]
+pub struct ForkSwap {
+    pub tx_hash: String,
+    pub reverted: bool,
+    /// Input token spent and output token received, in token units
+    pub amount_in: Decimal,
+    pub amount_out: Decimal,
+    pub min_amount_out: Option<Decimal>,
+    pub gas_used: u64,
+    pub gas_price_gwei: Decimal,
+}
+
+impl ForkSwap {
+    /// Quote per base token actually paid or received
+    pub fn fill_price(&self, buy_base: bool) -> Option<Decimal> {
+        if buy_base {
+            self.amount_in.checked_div(self.amount_out)
+        } else {
+            self.amount_out.checked_div(self.amount_in)
+        }
+    }
+}
+
+/// Fund a fresh trader with the swap input, approve the router and send the
+/// same router calldata a live execution would, on a fork of the latest block
+pub async fn fork_swap(
+    fork: &AnvilFork,
+    opportunity: &ArbitrageOpportunity,
+    pool_info: &PoolInfo,
+    bounds: SwapBounds,
+) -> Result<ForkSwap> {
+    fork.reset().await?;
+
+    let trader = probe_address("fork trader");
+    let buy_base = opportunity.direction.contains("Buy on Aerodrome");
+    let (token_out, in_decimals, out_decimals) = if buy_base {
+        (pool_info.pair.base, pool_info.pair.quote_decimals, pool_info.pair.base_decimals)
+    } else {
+        (pool_info.pair.quote, pool_info.pair.base_decimals, pool_info.pair.quote_decimals)
+    };
+
+    let swap_data = encode_router_swap(opportunity, pool_info, trader, bounds)?;
+    let (token_in, amount_in) = router_swap_input(&swap_data)
+        .ok_or_else(|| anyhow::anyhow!("Router calldata has no swap input"))?;
+    let min_amount_out = router_swap_min_out(&swap_data).and_then(|raw| scale_from_raw(raw, out_decimals));
+
+    fork.deal(token_in, trader, amount_in).await?;
+    let approve = IERC20::approveCall { spender: AERODROME_ROUTER_MAINNET, amount: amount_in }.abi_encode();
+    if !send_as(&fork.provider, trader, token_in, approve).await?.status() {
+        return Err(anyhow::anyhow!("Router approval reverted on the fork"));
+    }
+
+    let before = balance_of(&fork.provider, token_out, trader).await?;
+    let receipt = send_as(&fork.provider, trader, AERODROME_ROUTER_MAINNET, swap_data).await?;
+    let received = balance_of(&fork.provider, token_out, trader).await?.saturating_sub(before);
+
+    let swap = ForkSwap {
+        tx_hash: receipt.transaction_hash.to_string(),
+        reverted: !receipt.status(),
+        amount_in: scale_from_raw(amount_in, in_decimals).unwrap_or_default(),
+        amount_out: scale_from_raw(received, out_decimals).unwrap_or_default(),
+        min_amount_out,
+        gas_used: receipt.gas_used as u64,
+        gas_price_gwei: Decimal::from(receipt.effective_gas_price) / dec!(1_000_000_000),
+    };
+    info!("🍴 Fork swap {}: {} in, {} out, {} gas",
+        if swap.reverted { "reverted" } else { "mined" }, swap.amount_in, swap.amount_out, swap.gas_used);
+    Ok(swap)
+}
@@ -0,0 +1,39 @@
+//! Safety guards for real-funds (mainnet) execution
+//!
+//! The daily loss limit (`MAX_DAILY_LOSS_USD`) is enforced by the risk
+//! manager for every network, on realized P&L; the guards add the per-trade
+//! notional cap.
+
+use anyhow::Result;
+use rust_decimal::Decimal;
+use tracing::warn;
+use crate::{
+    config::Config,
+    types::ArbitrageOpportunity,
+};
+
+/// Per-trade notional cap
+pub struct MainnetGuards {
+    pub max_trade_notional_usd: Decimal,
+}
+
+impl MainnetGuards {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            max_trade_notional_usd: config.max_trade_notional_usd,
+        }
+    }
+
+    /// Reject trades above the notional cap
+    pub async fn check(&self, opportunity: &ArbitrageOpportunity) -> Result<()> {
+        let notional_usd = opportunity.size_eth * opportunity.cex_price;
+        if notional_usd > self.max_trade_notional_usd {
+            warn!("🛑 Trade notional ${:.2} exceeds limit ${:.2}", notional_usd, self.max_trade_notional_usd);
+            return Err(anyhow::anyhow!(
+                "Trade notional ${:.2} exceeds limit ${:.2}",
+                notional_usd, self.max_trade_notional_usd
+            ));
+        }
+        Ok(())
+    }
+}
@@ -2,6 +2,34 @@
 
 pub mod engine;
 pub mod simulation;
+pub mod guards;
+pub mod nonce;
+pub mod cex;
+pub mod token_safety;
+pub mod economics;
+pub mod policy;
+pub mod calibration;
+pub mod submission;
+pub mod flash_loan;
+pub mod preflight;
+pub mod approvals;
+pub mod bounds;
+pub mod queue;
+pub mod fork;
 
 pub use engine::*;
 pub use simulation::*;
+pub use guards::*;
+pub use nonce::*;
+pub use cex::*;
+pub use token_safety::*;
+pub use economics::*;
+pub use policy::*;
+pub use calibration::*;
+pub use submission::*;
+pub use flash_loan::*;
+pub use preflight::*;
+pub use approvals::*;
+pub use bounds::*;
+pub use queue::*;
+pub use fork::*;
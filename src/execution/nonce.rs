@@ -0,0 +1,171 @@
+//! Nonce management for concurrent executions
+//!
+//! Tracks the next nonce and in-flight transactions per wallet so several
+//! executions in one cycle get distinct nonces, detects gaps left by dropped
+//! transactions, and prices fee bumps for same-nonce replacements.
+
+use alloy::{
+    primitives::Address,
+    providers::Provider,
+};
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+/// Minimum fee increase nodes accept for a replacement (geth requires 10%)
+pub const REPLACEMENT_FEE_BUMP_PCT: u128 = 12;
+
+#[derive(Debug, Clone)]
+pub struct PendingNonce {
+    pub tx_hash: Option<String>,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    pub updated_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct WalletNonces {
+    next: u64,
+    pending: BTreeMap<u64, PendingNonce>,
+}
+
+#[derive(Default)]
+pub struct NonceManager {
+    wallets: Mutex<HashMap<Address, WalletNonces>>,
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserve the next nonce, syncing from the node's pending count on first use
+    pub async fn reserve(
+        &self,
+        provider: &dyn Provider,
+        wallet: Address,
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+    ) -> Result<u64> {
+        let mut wallets = self.wallets.lock().await;
+        if !wallets.contains_key(&wallet) {
+            let next = provider.get_transaction_count(wallet).pending().await
+                .context("Failed to fetch pending nonce")?;
+            info!("🔢 Nonce manager synced {:?} at nonce {}", wallet, next);
+            wallets.insert(wallet, WalletNonces { next, ..Default::default() });
+        }
+
+        let state = wallets.get_mut(&wallet).expect("wallet state inserted above");
+        let nonce = state.next;
+        state.next += 1;
+        state.pending.insert(nonce, PendingNonce {
+            tx_hash: None,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            updated_at: Instant::now(),
+        });
+        debug!("Reserved nonce {} for {:?}", nonce, wallet);
+        Ok(nonce)
+    }
+
+    /// Record the hash and fees of a broadcast transaction
+    pub async fn mark_sent(&self, wallet: Address, nonce: u64, tx_hash: String, max_fee_per_gas: u128, max_priority_fee_per_gas: u128) {
+        if let Some(pending) = self.wallets.lock().await
+            .get_mut(&wallet)
+            .and_then(|s| s.pending.get_mut(&nonce))
+        {
+            pending.tx_hash = Some(tx_hash);
+            pending.max_fee_per_gas = max_fee_per_gas;
+            pending.max_priority_fee_per_gas = max_priority_fee_per_gas;
+            pending.updated_at = Instant::now();
+        }
+    }
+
+    /// Forget a nonce whose transaction was mined
+    pub async fn confirm(&self, wallet: Address, nonce: u64) {
+        if let Some(state) = self.wallets.lock().await.get_mut(&wallet) {
+            state.pending.remove(&nonce);
+        }
+    }
+
+    /// Return a nonce that was never broadcast. Only the most recent
+    /// reservation can be rolled back; earlier ones leave a gap.
+    pub async fn release(&self, wallet: Address, nonce: u64) {
+        if let Some(state) = self.wallets.lock().await.get_mut(&wallet) {
+            state.pending.remove(&nonce);
+            if nonce + 1 == state.next {
+                state.next = nonce;
+            } else {
+                warn!("Released nonce {} below next {}; gap until reused", nonce, state.next);
+            }
+        }
+    }
+
+    /// Compare local state with the chain: drop mined nonces and return gaps,
+    /// i.e. nonces below our next that the chain hasn't seen and nothing is tracking.
+    pub async fn detect_gaps(&self, provider: &dyn Provider, wallet: Address) -> Result<Vec<u64>> {
+        let mined = provider.get_transaction_count(wallet).latest().await
+            .context("Failed to fetch confirmed nonce")?;
+
+        let mut wallets = self.wallets.lock().await;
+        let Some(state) = wallets.get_mut(&wallet) else {
+            return Ok(Vec::new());
+        };
+
+        state.pending.retain(|nonce, _| *nonce >= mined);
+        if state.next < mined {
+            // Transactions were sent outside this manager
+            warn!("Nonce manager behind chain for {:?}: {} < {}", wallet, state.next, mined);
+            state.next = mined;
+        }
+
+        let gaps: Vec<u64> = (mined..state.next)
+            .filter(|nonce| !state.pending.contains_key(nonce))
+            .collect();
+        if !gaps.is_empty() {
+            warn!("⚠️  Nonce gaps for {:?}: {:?}", wallet, gaps);
+        }
+        Ok(gaps)
+    }
+
+    /// Take ownership of a gap nonce so it can be filled
+    pub async fn reserve_gap(&self, wallet: Address, nonce: u64, max_fee_per_gas: u128, max_priority_fee_per_gas: u128) {
+        if let Some(state) = self.wallets.lock().await.get_mut(&wallet) {
+            state.pending.insert(nonce, PendingNonce {
+                tx_hash: None,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                updated_at: Instant::now(),
+            });
+        }
+    }
+
+    /// Fees for replacing the transaction at `nonce`, bumped enough for nodes to accept it
+    pub async fn replacement_fees(&self, wallet: Address, nonce: u64) -> Option<(u128, u128)> {
+        let wallets = self.wallets.lock().await;
+        let pending = wallets.get(&wallet)?.pending.get(&nonce)?;
+        Some((
+            bump_fee(pending.max_fee_per_gas),
+            bump_fee(pending.max_priority_fee_per_gas),
+        ))
+    }
+
+    /// In-flight transactions per nonce
+    pub async fn pending(&self, wallet: Address) -> Vec<(u64, PendingNonce)> {
+        self.wallets.lock().await
+            .get(&wallet)
+            .map(|s| s.pending.iter().map(|(n, p)| (*n, p.clone())).collect())
+            .unwrap_or_default()
+    }
+
+    /// Discard local state so the next reservation resyncs from the node
+    pub async fn reset(&self, wallet: Address) {
+        self.wallets.lock().await.remove(&wallet);
+    }
+}
+
+fn bump_fee(fee: u128) -> u128 {
+    (fee * (100 + REPLACEMENT_FEE_BUMP_PCT)).div_ceil(100).max(fee + 1)
+}
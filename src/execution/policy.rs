@@ -0,0 +1,165 @@
+//! Transaction policy checked before the engine signs anything
+//!
+//! Only calls to whitelisted contracts with whitelisted selectors, within
+//! value and gas limit bounds, are signed. A strategy bug that builds an
+//! unintended transaction is rejected here rather than sent. ERC20
+//! `approve()` is allowed only on the tokens of pools the engine trades, and
+//! only toward whitelisted spenders.
+
+use alloy::{
+    primitives::{keccak256, Address, TxKind, U256},
+    rpc::types::eth::TransactionRequest,
+    sol_types::SolCall,
+};
+use anyhow::Result;
+use rust_decimal_macros::dec;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use tracing::warn;
+use crate::{
+    config::Config,
+    network::IERC20,
+    types::{TokenPair, AERODROME_ROUTER_MAINNET, UNISWAP_V2_ROUTER_SEPOLIA, USDC_SEPOLIA, WETH_SEPOLIA},
+    utils::{scale_from_raw, scale_to_raw},
+};
+
+/// Aerodrome v2 Router swap sent by the mainnet execution path
+pub const AERODROME_SWAP_SIGNATURE: &str =
+    "swapExactTokensForTokens(uint256,uint256,(address,address,bool,address)[],address,uint256)";
+/// Uniswap V2 Router swap sent by the Sepolia execution path
+pub const UNISWAP_V2_SWAP_SIGNATURE: &str = "swapExactTokensForTokens(uint256,uint256,address[],address,uint256)";
+
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature);
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+pub struct TransactionPolicy {
+    allowed_calls: HashMap<Address, HashSet<[u8; 4]>>,
+    /// Spenders the approval tokens may be approved for
+    approved_spenders: HashSet<Address>,
+    /// Tokens `approve()` may be called on: the traded pools' pair tokens
+    approval_tokens: Mutex<HashSet<Address>>,
+    max_gas_limit: u64,
+    max_value_wei: U256,
+    /// Zero-value self-transfers from this wallet (nonce cancellations) are allowed
+    wallet: Option<Address>,
+}
+
+impl TransactionPolicy {
+    /// Policy with no whitelisted calls
+    pub fn with_limits(max_gas_limit: u64, max_value_wei: U256, wallet: Option<Address>) -> Self {
+        Self {
+            allowed_calls: HashMap::new(),
+            approved_spenders: HashSet::new(),
+            approval_tokens: Mutex::new(HashSet::new()),
+            max_gas_limit,
+            max_value_wei,
+            wallet,
+        }
+    }
+
+    /// The engine's router swaps and approvals to those routers, plus any
+    /// `SIGNER_ALLOWED_CALLS`
+    pub fn new(config: &Config, wallet: Option<Address>) -> Self {
+        // A cap too large to express in wei fails closed: no value may be sent
+        let max_value_wei = scale_to_raw(config.signer_max_value_eth.max(dec!(0)), 18).unwrap_or_else(|| {
+            warn!("SIGNER_MAX_VALUE_ETH {} is out of range, rejecting all value transfers", config.signer_max_value_eth);
+            U256::ZERO
+        });
+        let mut policy = Self::with_limits(config.signer_max_gas_limit, max_value_wei, wallet);
+        policy.allow(AERODROME_ROUTER_MAINNET, selector(AERODROME_SWAP_SIGNATURE));
+        policy.allow(UNISWAP_V2_ROUTER_SEPOLIA, selector(UNISWAP_V2_SWAP_SIGNATURE));
+        policy.allow_approvals_to(AERODROME_ROUTER_MAINNET);
+        policy.allow_approvals_to(UNISWAP_V2_ROUTER_SEPOLIA);
+        // The Sepolia path always trades WETH/USDC; mainnet pairs are added per pool
+        policy.allow_approvals_on(WETH_SEPOLIA);
+        policy.allow_approvals_on(USDC_SEPOLIA);
+        for (contract, call) in &config.signer_allowed_calls {
+            policy.allow(*contract, *call);
+        }
+        policy
+    }
+
+    pub fn allow(&mut self, contract: Address, selector: [u8; 4]) {
+        self.allowed_calls.entry(contract).or_default().insert(selector);
+    }
+
+    /// Allow `approve(spender, amount)` toward `spender` on the approval tokens
+    pub fn allow_approvals_to(&mut self, spender: Address) {
+        self.approved_spenders.insert(spender);
+    }
+
+    /// Allow approvals on `token`
+    pub fn allow_approvals_on(&self, token: Address) {
+        self.approval_tokens.lock().unwrap_or_else(|e| e.into_inner()).insert(token);
+    }
+
+    /// Allow approvals on both tokens of a pool about to be traded
+    pub fn allow_pair_approvals(&self, pair: &TokenPair) {
+        self.allow_approvals_on(pair.base);
+        self.allow_approvals_on(pair.quote);
+    }
+
+    fn is_approval_token(&self, token: Address) -> bool {
+        self.approval_tokens.lock().unwrap_or_else(|e| e.into_inner()).contains(&token)
+    }
+
+    /// Reject `tx` unless it is a whitelisted call (or a zero-value
+    /// self-transfer) with an explicit gas limit and value inside the bounds
+    pub fn check(&self, tx: &TransactionRequest) -> Result<()> {
+        let to = match tx.to {
+            Some(TxKind::Call(to)) => to,
+            _ => return Err(anyhow::anyhow!("Contract creation is not permitted")),
+        };
+
+        let value = tx.value.unwrap_or_default();
+        if value > self.max_value_wei {
+            return Err(anyhow::anyhow!("Value {} wei exceeds the {} wei limit", value, self.max_value_wei));
+        }
+        let gas_limit = tx.gas
+            .ok_or_else(|| anyhow::anyhow!("Transaction to {} has no explicit gas limit", to))?;
+        if gas_limit > self.max_gas_limit {
+            return Err(anyhow::anyhow!("Gas limit {} exceeds the {} limit", gas_limit, self.max_gas_limit));
+        }
+
+        let input = tx.input.input().map(|data| data.as_ref()).unwrap_or_default();
+        if input.is_empty() {
+            if Some(to) == self.wallet && value.is_zero() {
+                return Ok(());
+            }
+            return Err(anyhow::anyhow!("Plain transfer to {} is not permitted", to));
+        }
+
+        let call: [u8; 4] = input.get(..4)
+            .and_then(|call| call.try_into().ok())
+            .ok_or_else(|| anyhow::anyhow!("Calldata to {} is shorter than a selector", to))?;
+        // Approvals of a pair token to a whitelisted spender pass; others need an explicit entry
+        let approves_spender = call == IERC20::approveCall::SELECTOR
+            && self.is_approval_token(to)
+            && IERC20::approveCall::abi_decode(input, true)
+                .is_ok_and(|approve| self.approved_spenders.contains(&approve.spender));
+        if approves_spender {
+            return Ok(());
+        }
+        match self.allowed_calls.get(&to) {
+            Some(calls) if calls.contains(&call) => Ok(()),
+            Some(_) => Err(anyhow::anyhow!(
+                "Selector 0x{} is not whitelisted on {}",
+                alloy::primitives::hex::encode(call), to
+            )),
+            None => Err(anyhow::anyhow!("{} is not a whitelisted contract", to)),
+        }
+    }
+
+    /// One-line description for startup logs
+    pub fn summary(&self) -> String {
+        let calls: usize = self.allowed_calls.values().map(HashSet::len).sum();
+        let max_value_eth = scale_from_raw(self.max_value_wei, 18).unwrap_or_default();
+        let tokens = self.approval_tokens.lock().unwrap_or_else(|e| e.into_inner()).len();
+        format!(
+            "{} contract(s), {} call(s), {} approval spender(s) on {} token(s), gas limit ≤ {}, value ≤ {} ETH",
+            self.allowed_calls.len(), calls, self.approved_spenders.len(), tokens, self.max_gas_limit, max_value_eth
+        )
+    }
+}
@@ -0,0 +1,139 @@
+//! eth_call pre-flight of swap transactions
+//!
+//! The exact calldata is simulated against the latest block before signing,
+//! so a revert (missing approval, expired deadline, minimum output not met)
+//! is caught without paying gas.
+//! Router swaps return their amounts, which gives the expected output.
+//!
+//! Before that, the wallet's ETH must cover the transaction's worst-case gas
+//! and its token balance the swap input. An unfunded wallet fails with
+//! `BotError::InsufficientBalance` instead of an opaque revert, and the
+//! balances read are kept for the health status.
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+    rpc::types::eth::TransactionRequest,
+    sol_types::SolCall,
+};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use lazy_static::lazy_static;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tracing::{info, warn};
+use crate::{
+    errors::BotError,
+    network::{call_contract, IAerodromeRouter, IERC20},
+    pools::token_metadata,
+    types::{SwapPreflight, WalletBalanceReport},
+    utils::scale_from_raw,
+};
+
+lazy_static! {
+    /// Balances from the last execution attempt, for the health status
+    pub static ref WALLET_BALANCES: WalletBalanceRecorder = WalletBalanceRecorder::default();
+}
+
+/// Latest wallet balances, keeping token balances across checks
+#[derive(Default)]
+pub struct WalletBalanceRecorder {
+    latest: Mutex<Option<WalletBalanceReport>>,
+}
+
+impl WalletBalanceRecorder {
+    pub fn record(&self, wallet: Address, eth: Decimal, token: Option<(String, Decimal)>, shortfall: Option<String>) {
+        let mut latest = self.latest.lock().unwrap_or_else(|e| e.into_inner());
+        let mut tokens = latest.take()
+            .filter(|report| report.wallet == wallet)
+            .map(|report| report.tokens)
+            .unwrap_or_default();
+        tokens.extend(token);
+        *latest = Some(WalletBalanceReport { wallet, checked_at: Utc::now(), eth, tokens, shortfall });
+    }
+
+    pub fn snapshot(&self) -> Option<WalletBalanceReport> {
+        self.latest.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+/// Worst-case wei `tx` can cost: gas limit at the max fee, plus any value sent
+pub fn max_transaction_cost(tx: &TransactionRequest) -> U256 {
+    let gas = U256::from(tx.gas.unwrap_or_default()) * U256::from(tx.max_fee_per_gas.unwrap_or_default());
+    gas + tx.value.unwrap_or_default()
+}
+
+/// Fail with `InsufficientBalance` when `available` is below `required`
+pub fn require_balance(wallet: Address, asset: &str, required: Decimal, available: Decimal) -> Result<(), BotError> {
+    if available < required {
+        return Err(BotError::InsufficientBalance { wallet, asset: asset.to_string(), required, available });
+    }
+    Ok(())
+}
+
+/// Check that `wallet` holds enough ETH for `tx`'s gas and enough of the
+/// token it spends (`spend`, in raw units), recording the balances read
+pub async fn check_wallet_balances(
+    provider: &dyn Provider,
+    wallet: Address,
+    tx: &TransactionRequest,
+    spend: Option<(Address, U256)>,
+) -> Result<()> {
+    let eth_raw = provider.get_balance(wallet).await
+        .context("Failed to read the wallet's ETH balance")?;
+    let eth = scale_from_raw(eth_raw, 18)
+        .ok_or_else(|| anyhow::anyhow!("ETH balance out of range"))?;
+    let gas = scale_from_raw(max_transaction_cost(tx), 18)
+        .ok_or_else(|| anyhow::anyhow!("Transaction cost out of range"))?;
+    let mut result = require_balance(wallet, "ETH", gas, eth);
+
+    let mut token_balance = None;
+    if let Some((token, amount)) = spend {
+        let metadata = token_metadata(provider, token).await?;
+        let balance_raw = call_contract(provider, token, IERC20::balanceOfCall { account: wallet }).await
+            .with_context(|| format!("Failed to read the wallet's {} balance", metadata.symbol))?._0;
+        let (balance, required) = scale_from_raw(balance_raw, metadata.decimals)
+            .zip(scale_from_raw(amount, metadata.decimals))
+            .ok_or_else(|| anyhow::anyhow!("{} balance out of range", metadata.symbol))?;
+        result = result.and(require_balance(wallet, &metadata.symbol, required, balance));
+        token_balance = Some((metadata.symbol, balance));
+    }
+
+    WALLET_BALANCES.record(wallet, eth, token_balance, result.as_ref().err().map(|e| e.to_string()));
+    if let Err(e) = &result {
+        warn!("💸 {}", e);
+    }
+    result.map_err(Into::into)
+}
+
+/// Final amount of a router swap's returned `uint256[] amounts` (the
+/// Aerodrome and Uniswap V2 routers return the same shape)
+pub fn decode_amount_out(output: &[u8]) -> Option<U256> {
+    IAerodromeRouter::swapExactTokensForTokensCall::abi_decode_returns(output, true).ok()?
+        .amounts.last().copied()
+}
+
+/// Simulate `tx` from `from`; `output_decimals` scales the router's amount
+/// out, None for calls that don't return swap amounts
+pub async fn preflight_swap(
+    provider: &dyn Provider,
+    tx: &TransactionRequest,
+    from: Address,
+    output_decimals: Option<i32>,
+) -> SwapPreflight {
+    match provider.call(&tx.clone().from(from)).await {
+        Ok(output) => {
+            let amount_out = output_decimals
+                .and_then(|decimals| scale_from_raw(decode_amount_out(&output)?, decimals));
+            if let Some(amount_out) = amount_out {
+                info!("🧪 Preflight passed, expected output {}", amount_out);
+            }
+            SwapPreflight { amount_out, revert_reason: None }
+        }
+        Err(e) => {
+            warn!("🧪 Preflight reverted: {}", e);
+            SwapPreflight { amount_out: None, revert_reason: Some(e.to_string()) }
+        }
+    }
+}
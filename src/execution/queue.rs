@@ -0,0 +1,297 @@
+//! Prioritized execution queue
+//!
+//! The monitoring cycle hands validated opportunities to the queue instead
+//! of executing them inline. A background task keeps pending jobs ordered by
+//! [`ExecutionPriority`], then net profit, then arrival; runs up to
+//! `MAX_CONCURRENT_EXECUTIONS` at a time; drops jobs that waited longer than
+//! `EXECUTION_QUEUE_MAX_AGE_SECS`; and reports every job on a results channel
+//! the main loop drains into its statistics. A pool has at most one job
+//! queued or running, so repeated sightings of the same spread don't stack.
+//! With leader election, each job re-checks the lease before it starts, so a
+//! replica that lost leadership while jobs were queued never sends them.
+//! On shutdown the queue is closed: pending jobs are dropped, running ones
+//! finish and report, and the results channel closes once they have.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::Notify;
+use tokio::task::JoinSet;
+use tracing::{debug, error, info};
+use crate::{
+    control::LeaderFlag,
+    execution::TradeExecutionEngine,
+    types::{ArbitrageOpportunity, ExecutionPriority, ExecutionUrgency, PoolInfo, TradeExecution, VolatilityMetrics},
+};
+
+/// Priority of an arbitrage opportunity: immediate when volatility calls
+/// for fast execution, otherwise by how far profit clears `min_profit_usd`
+pub fn opportunity_priority(
+    opportunity: &ArbitrageOpportunity,
+    volatility: &VolatilityMetrics,
+    min_profit_usd: Decimal,
+) -> ExecutionPriority {
+    match volatility.recommended_adjustments.execution_urgency {
+        ExecutionUrgency::Fast => return ExecutionPriority::Immediate,
+        ExecutionUrgency::Cautious => return ExecutionPriority::Low,
+        ExecutionUrgency::Normal => {}
+    }
+    if min_profit_usd <= dec!(0) {
+        return ExecutionPriority::Medium;
+    }
+    let multiple = opportunity.net_profit_usd / min_profit_usd;
+    if multiple >= dec!(5) {
+        ExecutionPriority::High
+    } else if multiple >= dec!(2) {
+        ExecutionPriority::Medium
+    } else {
+        ExecutionPriority::Low
+    }
+}
+
+/// An opportunity waiting to be executed
+#[derive(Clone)]
+pub struct ExecutionJob {
+    pub opportunity: ArbitrageOpportunity,
+    pub pool: PoolInfo,
+    pub volatility: VolatilityMetrics,
+    pub priority: ExecutionPriority,
+    pub queued_at: Instant,
+}
+
+impl ExecutionJob {
+    pub fn new(
+        opportunity: ArbitrageOpportunity,
+        pool: PoolInfo,
+        volatility: VolatilityMetrics,
+        priority: ExecutionPriority,
+    ) -> Self {
+        Self { opportunity, pool, volatility, priority, queued_at: Instant::now() }
+    }
+}
+
+pub enum ExecutionResult {
+    Completed(Box<TradeExecution>),
+    /// The engine returned an error instead of an execution record
+    Failed(String),
+    /// Waited past the queue's max age and was dropped unexecuted
+    Expired,
+    /// This replica lost the leader lease before the job started
+    NotLeader,
+}
+
+/// A finished job, reported back to the monitoring loop
+pub struct ExecutionOutcome {
+    pub job: ExecutionJob,
+    /// Time spent queued before the job started (or expired)
+    pub waited: Duration,
+    pub result: ExecutionResult,
+}
+
+struct Pending {
+    job: ExecutionJob,
+    seq: u64,
+}
+
+impl Pending {
+    fn key(&self) -> (u8, Decimal, std::cmp::Reverse<u64>) {
+        (self.job.priority.rank(), self.job.opportunity.net_profit_usd, std::cmp::Reverse(self.seq))
+    }
+}
+
+impl PartialEq for Pending {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Pending {}
+
+impl PartialOrd for Pending {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Pending {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// Jobs not yet started, highest priority first
+#[derive(Default)]
+pub struct PendingExecutions {
+    heap: BinaryHeap<Pending>,
+    next_seq: u64,
+}
+
+impl PendingExecutions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, job: ExecutionJob) {
+        self.heap.push(Pending { job, seq: self.next_seq });
+        self.next_seq += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<ExecutionJob> {
+        self.heap.pop().map(|pending| pending.job)
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+/// Handle the monitoring cycle submits jobs through
+#[derive(Clone)]
+pub struct ExecutionQueue {
+    tx: UnboundedSender<ExecutionJob>,
+    /// Pools with a job queued or running
+    in_flight: Arc<Mutex<HashSet<String>>>,
+    closed: Arc<AtomicBool>,
+    shutdown: Arc<Notify>,
+}
+
+impl ExecutionQueue {
+    /// Queue `job`; false if it is on hold, its pool already has a job
+    /// queued or running, or the queue is closed
+    pub fn submit(&self, job: ExecutionJob) -> bool {
+        if job.priority == ExecutionPriority::Hold || self.closed.load(AtomicOrdering::Acquire) {
+            return false;
+        }
+        let pool = job.pool.name.clone();
+        if !self.in_flight.lock().unwrap_or_else(|e| e.into_inner()).insert(pool.clone()) {
+            return false;
+        }
+        if self.tx.send(job).is_err() {
+            release(&self.in_flight, &pool);
+            return false;
+        }
+        true
+    }
+
+    /// Stop accepting and starting jobs; running ones still finish and report
+    pub fn close(&self) {
+        if !self.closed.swap(true, AtomicOrdering::AcqRel) {
+            self.shutdown.notify_one();
+        }
+    }
+}
+
+fn release(in_flight: &Mutex<HashSet<String>>, pool: &str) {
+    in_flight.lock().unwrap_or_else(|e| e.into_inner()).remove(pool);
+}
+
+/// Receiving side of the execution results
+pub struct ExecutionResults {
+    rx: UnboundedReceiver<ExecutionOutcome>,
+}
+
+impl ExecutionResults {
+    pub async fn recv(&mut self) -> Option<ExecutionOutcome> {
+        self.rx.recv().await
+    }
+}
+
+/// Start the queue task running jobs on `engine`; with `leader`, only
+/// while this replica holds the lease
+pub fn spawn_execution_queue(
+    engine: Arc<TradeExecutionEngine>,
+    max_concurrent: usize,
+    max_age: Duration,
+    leader: Option<LeaderFlag>,
+) -> (ExecutionQueue, ExecutionResults) {
+    let (job_tx, mut job_rx) = unbounded_channel::<ExecutionJob>();
+    let (result_tx, result_rx) = unbounded_channel();
+    let queue = ExecutionQueue {
+        tx: job_tx,
+        in_flight: Arc::new(Mutex::new(HashSet::new())),
+        closed: Arc::new(AtomicBool::new(false)),
+        shutdown: Arc::new(Notify::new()),
+    };
+    let max_concurrent = max_concurrent.max(1);
+    info!("📬 Execution queue: up to {} concurrent, max wait {}s", max_concurrent, max_age.as_secs());
+
+    let in_flight = queue.in_flight.clone();
+    let shutdown = queue.shutdown.clone();
+    tokio::spawn(async move {
+        let mut pending = PendingExecutions::new();
+        let mut running = JoinSet::new();
+        // The job each running task was spawned for, to report it if the task panics
+        let mut running_jobs = HashMap::new();
+        let mut open = true;
+        loop {
+            while running.len() < max_concurrent {
+                let Some(job) = pending.pop() else { break };
+                let waited = job.queued_at.elapsed();
+                if waited > max_age {
+                    debug!("Execution for {} expired after {:.1}s in queue", job.pool.name, waited.as_secs_f64());
+                    release(&in_flight, &job.pool.name);
+                    let _ = result_tx.send(ExecutionOutcome { job, waited, result: ExecutionResult::Expired });
+                    continue;
+                }
+                if leader.as_ref().is_some_and(|leader| !leader.is_leader()) {
+                    debug!("Dropping execution for {}: not the leader replica", job.pool.name);
+                    release(&in_flight, &job.pool.name);
+                    let _ = result_tx.send(ExecutionOutcome { job, waited, result: ExecutionResult::NotLeader });
+                    continue;
+                }
+                let engine = engine.clone();
+                let spawned = job.clone();
+                let task = running.spawn(async move {
+                    let result = match engine.simulate_trade_execution(&job.opportunity, &job.pool, &job.volatility).await {
+                        Ok(execution) => ExecutionResult::Completed(Box::new(execution)),
+                        Err(e) => ExecutionResult::Failed(e.to_string()),
+                    };
+                    ExecutionOutcome { job, waited, result }
+                });
+                running_jobs.insert(task.id(), (spawned, waited));
+            }
+
+            tokio::select! {
+                job = job_rx.recv(), if open => match job {
+                    Some(job) => pending.push(job),
+                    None => open = false,
+                },
+                _ = shutdown.notified(), if open => {
+                    open = false;
+                    info!("📬 Execution queue closed: dropping {} pending, waiting for {} running", pending.len(), running.len());
+                    while let Some(job) = pending.pop() {
+                        release(&in_flight, &job.pool.name);
+                    }
+                },
+                Some(done) = running.join_next_with_id() => match done {
+                    Ok((id, outcome)) => {
+                        running_jobs.remove(&id);
+                        release(&in_flight, &outcome.job.pool.name);
+                        let _ = result_tx.send(outcome);
+                    }
+                    // Free the pool and report the job, or the pool would never trade again
+                    Err(e) => {
+                        error!("Execution task failed: {}", e);
+                        if let Some((job, waited)) = running_jobs.remove(&e.id()) {
+                            release(&in_flight, &job.pool.name);
+                            let reason = if e.is_panic() { "panicked" } else { "cancelled" };
+                            let _ = result_tx.send(ExecutionOutcome { job, waited, result: ExecutionResult::Failed(reason.to_string()) });
+                        }
+                    }
+                },
+                else => break,
+            }
+        }
+    });
+
+    (queue, ExecutionResults { rx: result_rx })
+}
@@ -1,18 +1,64 @@
 //! Trade execution simulation
 
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
+use std::fmt;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
 use tracing::info;
-use crate::types::{
-    ArbitrageOpportunity, TradeExecution, ExecutionStatus, TradeType, VolatilityMetrics, VolatilityImpact
+use crate::{
+    execution::ExecutionCalibration,
+    types::{ArbitrageOpportunity, TradeExecution, ExecutionStatus, TradeType, VolatilityMetrics, VolatilityImpact},
 };
 
+/// How executions are simulated when no testnet wallet is configured
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SimulationMode {
+    /// Calibrated success rate and slippage per volatility band
+    #[default]
+    Random,
+    /// The real router swap on an Anvil fork of Base mainnet
+    Fork,
+}
+
+impl fmt::Display for SimulationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SimulationMode::Random => "random",
+            SimulationMode::Fork => "fork",
+        })
+    }
+}
+
+impl FromStr for SimulationMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "random" => Ok(SimulationMode::Random),
+            "fork" => Ok(SimulationMode::Fork),
+            other => Err(format!("unknown simulation mode {:?}", other)),
+        }
+    }
+}
+
+/// RNG for simulated executions and its seed. Without a configured seed one
+/// is drawn at random, so every run can be replayed with `SIMULATION_SEED`.
+pub fn simulation_rng(seed: Option<u64>) -> (StdRng, u64) {
+    let seed = seed.unwrap_or_else(rand::random);
+    (StdRng::seed_from_u64(seed), seed)
+}
+
+/// Simulate an execution, drawing its outcome and transaction hash from
+/// `rng` so a seeded RNG reproduces the same executions
 pub async fn create_simulated_execution(
     execution_id: String,
     opportunity: &ArbitrageOpportunity,
     volatility_metrics: &VolatilityMetrics,
+    calibration: &ExecutionCalibration,
     start_time: Instant,
+    rng: &mut (impl Rng + Send),
 ) -> anyhow::Result<TradeExecution> {
     // Simulate network latency based on volatility
     let base_latency = 100;
@@ -25,33 +71,32 @@ pub async fn create_simulated_execution(
     
     tokio::time::sleep(Duration::from_millis(base_latency + volatility_latency)).await;
 
-    // Simulate success rate based on volatility
-    let success_rate = match volatility_metrics.impact_assessment {
-        VolatilityImpact::Low => 0.95,
-        VolatilityImpact::Moderate => 0.85,
-        VolatilityImpact::High => 0.70,
-        VolatilityImpact::Extreme => 0.50,
-    };
+    // Success rate and slippage for the volatility band, calibrated from past executions
+    let impact = volatility_metrics.impact_assessment;
+    let success_rate = calibration.success_rate(impact);
 
-    let is_successful = rand::random::<f64>() < success_rate;
+    let is_successful = rng.random::<f64>() < success_rate;
 
-    // Calculate simulated slippage based on volatility
-    let base_slippage_bps = 25;
-    let volatility_slippage = match volatility_metrics.impact_assessment {
-        VolatilityImpact::Low => 0,
-        VolatilityImpact::Moderate => 25,
-        VolatilityImpact::High => 75,
-        VolatilityImpact::Extreme => 150,
-    };
-    let total_slippage_bps = base_slippage_bps + volatility_slippage;
+    let total_slippage_bps = calibration.slippage_bps(impact);
 
     // Calculate actual profit after slippage
     let slippage_factor = dec!(1) - (Decimal::from(total_slippage_bps) / dec!(10000));
     let actual_profit = opportunity.net_profit_usd * slippage_factor;
 
+    // DEX fill moves against us by the simulated slippage
+    let buy_on_dex = opportunity.direction.contains("Buy on Aerodrome");
+    let dex_price = opportunity.quoted_dex_price.unwrap_or(opportunity.dex_price);
+    let slippage = Decimal::from(total_slippage_bps) / dec!(10000);
+    let dex_fill_price = if buy_on_dex {
+        dex_price * (dec!(1) + slippage)
+    } else {
+        dex_price * (dec!(1) - slippage)
+    };
+
     let execution_time = start_time.elapsed().as_millis() as u64;
 
-    info!("🎭 Simulated execution: success={}, slippage={}bps", is_successful, total_slippage_bps);
+    info!("🎭 Simulated execution: success={} ({:.0}% rate), slippage={}bps",
+        is_successful, success_rate * 100.0, total_slippage_bps);
 
     Ok(TradeExecution {
         id: execution_id,
@@ -69,7 +114,7 @@ pub async fn create_simulated_execution(
             ExecutionStatus::Failed
         },
         tx_hash: if is_successful {
-            Some(format!("0x{}", uuid::Uuid::new_v4().to_string().replace("-", "")))
+            Some(format!("0x{}", hex::encode(rng.random::<[u8; 32]>())))
         } else {
             None
         },
@@ -84,5 +129,13 @@ pub async fn create_simulated_execution(
         } else {
             None
         },
+        dex_fill_price: if is_successful { Some(dex_fill_price) } else { None },
+        cex_fill_price: None,
+        cex_order_id: None,
+        volatility_impact: Some(impact),
+        simulated: true,
+        preflight: None,
+        swap_bounds: None,
+        min_amount_out: None,
     })
 }
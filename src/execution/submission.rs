@@ -0,0 +1,227 @@
+//! Public or private transaction submission
+//!
+//! Public submission broadcasts through the execution provider, where a
+//! pending swap is visible to anyone watching the mempool. Private
+//! submission signs locally and hands the raw transaction only to a private
+//! endpoint: a sequencer RPC or Flashbots Protect-style relay
+//! (`eth_sendRawTransaction`), or a bundle RPC (`eth_sendBundle` targeting the
+//! next block). A bundle that misses its block is retargeted at the next one
+//! up to `BUNDLE_RETARGET_BLOCKS` times, then sent as a private raw
+//! transaction instead.
+
+use alloy::{
+    eips::eip2718::Encodable2718,
+    network::{EthereumWallet, TransactionBuilder},
+    primitives::{hex, Address, TxHash, U256},
+    providers::Provider,
+    rpc::types::eth::{TransactionReceipt, TransactionRequest},
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+use crate::{
+    config::{Config, BASE_BLOCK_TIME_SECS},
+    network::IERC20,
+};
+
+/// Times a bundle that missed its target block is re-sent for the next one
+pub const BUNDLE_RETARGET_BLOCKS: u32 = 3;
+
+/// How signed transactions reach the chain
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionMode {
+    /// Through the execution RPC into the public mempool
+    #[default]
+    Public,
+    /// `eth_sendRawTransaction` to `PRIVATE_RPC_URL` only
+    Private,
+    /// `eth_sendBundle` to `PRIVATE_RPC_URL`, targeting the next block
+    Bundle,
+}
+
+impl SubmissionMode {
+    pub const ALL: [SubmissionMode; 3] = [
+        SubmissionMode::Public,
+        SubmissionMode::Private,
+        SubmissionMode::Bundle,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubmissionMode::Public => "public",
+            SubmissionMode::Private => "private",
+            SubmissionMode::Bundle => "bundle",
+        }
+    }
+}
+
+impl fmt::Display for SubmissionMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SubmissionMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter()
+            .find(|mode| mode.as_str() == s.trim().to_lowercase())
+            .ok_or_else(|| format!("unknown submission mode {:?}", s))
+    }
+}
+
+/// JSON-RPC body sending one raw transaction
+pub fn raw_transaction_request(raw_tx: &[u8]) -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_sendRawTransaction",
+        "params": [hex::encode_prefixed(raw_tx)],
+    })
+}
+
+/// JSON-RPC body for a single-transaction bundle valid only in `block`
+pub fn bundle_request(raw_tx: &[u8], block: u64) -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_sendBundle",
+        "params": [{
+            "txs": [hex::encode_prefixed(raw_tx)],
+            "blockNumber": format!("0x{:x}", block),
+        }],
+    })
+}
+
+/// Block to retarget a bundle at after `misses` missed blocks with the chain
+/// at `head`; None once the retargets are used up
+pub fn bundle_retarget(head: u64, misses: u32) -> Option<u64> {
+    (misses <= BUNDLE_RETARGET_BLOCKS).then_some(head + 1)
+}
+
+/// Signs transactions locally and sends them to the private endpoint
+pub struct PrivateSubmitter {
+    client: reqwest::Client,
+    mode: SubmissionMode,
+    endpoint: String,
+    wallet: EthereumWallet,
+}
+
+impl PrivateSubmitter {
+    /// Build a submitter unless submission is public
+    pub fn from_config(config: &Config, wallet: Option<&EthereumWallet>) -> Result<Option<Self>> {
+        if config.submission_mode == SubmissionMode::Public {
+            return Ok(None);
+        }
+        let (Some(endpoint), Some(wallet)) = (config.private_rpc_url.clone(), wallet) else {
+            return Err(anyhow::anyhow!(
+                "SUBMISSION_MODE={} needs PRIVATE_RPC_URL and a PRIVATE_KEY to sign with",
+                config.submission_mode
+            ));
+        };
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .context("Failed to build private submission HTTP client")?;
+
+        info!("🕶️  Private transaction submission ({}) via {}", config.submission_mode, endpoint);
+        Ok(Some(Self {
+            client,
+            mode: config.submission_mode,
+            endpoint,
+            wallet: wallet.clone(),
+        }))
+    }
+
+    pub fn mode(&self) -> SubmissionMode {
+        self.mode
+    }
+
+    /// Sign `tx` (nonce and fees already set) and send it privately
+    pub async fn submit(&self, provider: &dyn Provider, mut tx: TransactionRequest) -> Result<TxHash> {
+        if tx.chain_id.is_none() {
+            tx.set_chain_id(provider.get_chain_id().await.context("Failed to fetch chain id")?);
+        }
+        if tx.gas.is_none() {
+            tx.set_gas_limit(provider.estimate_gas(&tx).await.context("Failed to estimate gas")?);
+        }
+        let envelope = tx.build(&self.wallet).await
+            .map_err(|e| anyhow::anyhow!("Failed to sign transaction: {}", e))?;
+        let tx_hash = *envelope.tx_hash();
+        let raw_tx = envelope.encoded_2718();
+
+        match self.mode {
+            SubmissionMode::Bundle => self.land_bundle(provider, tx_hash, &raw_tx).await?,
+            _ => self.send(&raw_transaction_request(&raw_tx)).await?,
+        }
+        Ok(tx_hash)
+    }
+
+    /// Send `raw_tx` as a bundle for the next block, retargeting it while it
+    /// misses, then fall back to a private raw transaction
+    async fn land_bundle(&self, provider: &dyn Provider, tx_hash: TxHash, raw_tx: &[u8]) -> Result<()> {
+        let mut head = provider.get_block_number().await.context("Failed to fetch block number")?;
+        let mut misses = 0;
+        while let Some(target) = bundle_retarget(head, misses) {
+            self.send(&bundle_request(raw_tx, target)).await?;
+            while head < target {
+                tokio::time::sleep(Duration::from_millis(BASE_BLOCK_TIME_SECS * 250)).await;
+                head = provider.get_block_number().await.context("Failed to fetch block number")?;
+            }
+            if provider.get_transaction_receipt(tx_hash).await.context("Failed to fetch transaction receipt")?.is_some() {
+                return Ok(());
+            }
+            misses += 1;
+            debug!("Bundle for {:?} missed block {}", tx_hash, target);
+        }
+        warn!("📦 Bundle for {:?} missed {} blocks, sending it as a private transaction", tx_hash, misses);
+        self.send(&raw_transaction_request(raw_tx)).await
+    }
+
+    async fn send(&self, body: &serde_json::Value) -> Result<()> {
+        let response: serde_json::Value = self.client.post(&self.endpoint)
+            .json(body)
+            .send().await
+            .context("Private submission request failed")?
+            .json().await
+            .context("Failed to parse private submission response")?;
+        if let Some(error) = response.get("error") {
+            return Err(anyhow::anyhow!("Private endpoint rejected the transaction: {}", error));
+        }
+        debug!("Private submission response: {}", response);
+        Ok(())
+    }
+}
+
+/// Poll for the receipt of `tx_hash` until it is `confirmations` blocks deep
+pub async fn wait_for_receipt(provider: &dyn Provider, tx_hash: TxHash, confirmations: u64) -> Result<TransactionReceipt> {
+    loop {
+        if let Some(receipt) = provider.get_transaction_receipt(tx_hash).await
+            .context("Failed to fetch transaction receipt")?
+        {
+            let mined_in = receipt.block_number.unwrap_or_default();
+            let head = provider.get_block_number().await.context("Failed to fetch block number")?;
+            if head + 1 >= mined_in + confirmations {
+                return Ok(receipt);
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(BASE_BLOCK_TIME_SECS)).await;
+    }
+}
+
+/// Amount of `token` transferred to `recipient` by a mined transaction, from
+/// its ERC20 `Transfer` logs
+pub fn received_amount(receipt: &TransactionReceipt, token: Address, recipient: Address) -> U256 {
+    receipt.inner.logs().iter()
+        .filter(|log| log.address() == token)
+        .filter_map(|log| log.log_decode::<IERC20::Transfer>().ok())
+        .filter(|transfer| transfer.inner.data.to == recipient)
+        .fold(U256::ZERO, |total, transfer| total + transfer.inner.data.value)
+}
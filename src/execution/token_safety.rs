@@ -0,0 +1,259 @@
+//! Token tax, blacklist and honeypot simulation before the first trade on a pool
+//!
+//! Each untrusted token is checked once and the verdict cached in the token
+//! registry. On a local Anvil fork the check pulls tokens out of the pool (a
+//! buy), passes them between two fresh addresses, and sells them back into
+//! the pool. Short receipts reveal transfer taxes and reverts reveal
+//! blacklists or honeypots. Without Anvil, a read-only `eth_call` transfer
+//! out of the pool catches blacklisted or paused tokens but not taxes.
+
+use alloy::{
+    primitives::{Address, Bytes, U256},
+    providers::Provider,
+    rpc::types::eth::TransactionRequest,
+    sol_types::SolCall,
+    transports::RpcError,
+};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use crate::{
+    config::Config,
+    execution::fork::{balance_of, probe_address, send_as, AnvilFork},
+    network::{mainnet_rpc_url, IAerodromePool, IERC20},
+    pools::get_amount_out,
+    types::{PoolInfo, PoolKind, USDBC_MAINNET, USDC_MAINNET, USDC_SEPOLIA, WETH_MAINNET},
+    ConcreteProvider,
+};
+
+const TOKEN_REGISTRY_PATH: &str = "output/cache/tokens.json";
+/// The round trip moves 1/1000 of the pool's reserve of the token
+const PROBE_RESERVE_DIVISOR: u64 = 1000;
+
+/// Tokens with known, well-behaved contracts that are never simulated
+const TRUSTED_TOKENS: &[Address] = &[WETH_MAINNET, USDC_MAINNET, USDBC_MAINNET, USDC_SEPOLIA];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SafetyMethod {
+    AnvilFork,
+    EthCall,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenVerdict {
+    Safe,
+    /// A leg of the round trip received less than was sent
+    Taxed { tax_bps: u64 },
+    /// A plain transfer reverted (blacklist, pause or transfer limit)
+    TransferBlocked { reason: String },
+    /// Buying from the pool worked but selling back did not
+    Honeypot { reason: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenSafety {
+    pub token: Address,
+    pub symbol: String,
+    /// Pool the round trip was simulated against
+    pub pool: String,
+    pub method: SafetyMethod,
+    pub verdict: TokenVerdict,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl TokenSafety {
+    pub fn is_tradable(&self) -> bool {
+        self.verdict == TokenVerdict::Safe
+    }
+}
+
+/// Cached token verdicts, persisted under `output/cache`
+pub struct TokenRegistry {
+    entries: RwLock<HashMap<Address, TokenSafety>>,
+    enabled: bool,
+    anvil_path: String,
+    fork_url: Option<String>,
+}
+
+impl TokenRegistry {
+    pub fn new(config: &Config) -> Self {
+        let entries: Vec<TokenSafety> = std::fs::read_to_string(TOKEN_REGISTRY_PATH).ok()
+            .and_then(|contents| match serde_json::from_str(&contents) {
+                Ok(entries) => Some(entries),
+                Err(e) => {
+                    warn!("Ignoring unreadable token registry: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self {
+            entries: RwLock::new(entries.into_iter().map(|e| (e.token, e)).collect()),
+            enabled: config.token_safety_checks,
+            anvil_path: config.anvil_path.clone(),
+            fork_url: mainnet_rpc_url(config).ok(),
+        }
+    }
+
+    pub async fn get(&self, token: Address) -> Option<TokenSafety> {
+        self.entries.read().await.get(&token).cloned()
+    }
+
+    /// Check the pool's untrusted tokens, simulating any not yet in the
+    /// registry. Returns why the pool must not be traded, including checks
+    /// that could not run (those are retried on the next call).
+    pub async fn ensure_tradable(&self, provider: &dyn Provider, pool: &PoolInfo) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        for (token, symbol) in [(pool.pair.base, &pool.pair.base_symbol), (pool.pair.quote, &pool.pair.quote_symbol)] {
+            if TRUSTED_TOKENS.contains(&token) {
+                continue;
+            }
+            let safety = match self.get(token).await {
+                Some(safety) => safety,
+                None => {
+                    let safety = self.check_token(provider, pool, token, symbol).await
+                        .map_err(|e| format!("{} safety check failed: {}", symbol, e))?;
+                    info!("🧪 {} token check on {} ({:?}): {:?}", symbol, pool.name, safety.method, safety.verdict);
+                    self.record(safety.clone()).await;
+                    safety
+                }
+            };
+            if !safety.is_tradable() {
+                return Err(format!("{} is not safe to trade: {:?}", symbol, safety.verdict));
+            }
+        }
+        Ok(())
+    }
+
+    async fn record(&self, safety: TokenSafety) {
+        let mut entries = self.entries.write().await;
+        entries.insert(safety.token, safety);
+        let snapshot: Vec<&TokenSafety> = entries.values().collect();
+        let saved = serde_json::to_string_pretty(&snapshot)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| std::fs::write(TOKEN_REGISTRY_PATH, json).context("Failed to write token registry"));
+        if let Err(e) = saved {
+            warn!("Failed to save token registry: {}", e);
+        }
+    }
+
+    async fn check_token(&self, provider: &dyn Provider, pool: &PoolInfo, token: Address, symbol: &str) -> Result<TokenSafety> {
+        let (method, verdict) = match self.spawn_fork().await {
+            Ok(fork) => (SafetyMethod::AnvilFork, fork_round_trip(&fork.provider, pool, token).await?),
+            Err(e) => {
+                warn!("Anvil fork unavailable ({}), checking {} with eth_call only", e, symbol);
+                (SafetyMethod::EthCall, eth_call_transfer_check(provider, pool, token).await?)
+            }
+        };
+
+        Ok(TokenSafety {
+            token,
+            symbol: symbol.to_string(),
+            pool: pool.name.clone(),
+            method,
+            verdict,
+            checked_at: Utc::now(),
+        })
+    }
+
+    async fn spawn_fork(&self) -> Result<AnvilFork> {
+        let fork_url = self.fork_url.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("no mainnet RPC to fork"))?;
+        AnvilFork::spawn(&self.anvil_path, fork_url).await
+    }
+}
+
+fn transfer_data(to: Address, amount: U256) -> Vec<u8> {
+    IERC20::transferCall { to, amount }.abi_encode()
+}
+
+/// Send as an impersonated account on the fork; false if it reverted
+async fn send_ok(fork: &ConcreteProvider, from: Address, to: Address, data: Vec<u8>) -> Result<bool> {
+    Ok(send_as(fork, from, to, data).await?.status())
+}
+
+/// Shortfall between sent and received amounts, in whole bps
+fn shortfall_bps(sent: U256, received: U256) -> u64 {
+    if sent.is_zero() || received >= sent {
+        return 0;
+    }
+    ((sent - received) * U256::from(10000) / sent).to::<u64>()
+}
+
+async fn fork_round_trip(fork: &ConcreteProvider, pool: &PoolInfo, token: Address) -> Result<TokenVerdict> {
+    let (probe_a, probe_b) = (probe_address("a"), probe_address("b"));
+    let amount = balance_of(fork, token, pool.address).await? / U256::from(PROBE_RESERVE_DIVISOR);
+    if amount.is_zero() {
+        return Err(anyhow::anyhow!("Pool holds too little {} to simulate", token));
+    }
+
+    // Buy side: tokens leaving the pool
+    if !send_ok(fork, pool.address, token, transfer_data(probe_a, amount)).await? {
+        return Ok(TokenVerdict::TransferBlocked { reason: "transfer out of the pool reverted".to_string() });
+    }
+    let received_a = balance_of(fork, token, probe_a).await?;
+
+    // Wallet to wallet
+    if !send_ok(fork, probe_a, token, transfer_data(probe_b, received_a)).await? {
+        return Ok(TokenVerdict::TransferBlocked { reason: "wallet-to-wallet transfer reverted".to_string() });
+    }
+    let received_b = balance_of(fork, token, probe_b).await?;
+
+    // Sell side: back into the pool, then swap out (CL pools only check the transfer)
+    let pool_before = balance_of(fork, token, pool.address).await?;
+    if !send_ok(fork, probe_b, token, transfer_data(pool.address, received_b)).await? {
+        return Ok(TokenVerdict::Honeypot { reason: "transfer into the pool reverted".to_string() });
+    }
+    let pool_received = balance_of(fork, token, pool.address).await?.saturating_sub(pool_before);
+
+    if matches!(pool.kind, PoolKind::Basic) {
+        let amount_out = get_amount_out(fork, pool.address, pool_received, token).await?;
+        let (amount0_out, amount1_out) = if token == pool.token0 {
+            (U256::ZERO, amount_out)
+        } else {
+            (amount_out, U256::ZERO)
+        };
+        let swap = IAerodromePool::swapCall {
+            amount0Out: amount0_out,
+            amount1Out: amount1_out,
+            to: probe_b,
+            data: Bytes::new(),
+        }.abi_encode();
+        if !send_ok(fork, probe_b, pool.address, swap).await? {
+            return Ok(TokenVerdict::Honeypot { reason: "swap back out of the pool reverted".to_string() });
+        }
+    }
+
+    let tax_bps = [(amount, received_a), (received_a, received_b), (received_b, pool_received)]
+        .into_iter()
+        .map(|(sent, received)| shortfall_bps(sent, received))
+        .max()
+        .unwrap_or(0);
+    Ok(if tax_bps > 0 { TokenVerdict::Taxed { tax_bps } } else { TokenVerdict::Safe })
+}
+
+/// Read-only fallback: can the pool transfer the token out at all
+async fn eth_call_transfer_check(provider: &dyn Provider, pool: &PoolInfo, token: Address) -> Result<TokenVerdict> {
+    let amount = balance_of(provider, token, pool.address).await? / U256::from(PROBE_RESERVE_DIVISOR);
+    let tx = TransactionRequest::default()
+        .from(pool.address)
+        .to(token)
+        .input(transfer_data(probe_address("a"), amount).into());
+
+    Ok(match provider.call(&tx).await {
+        // Tokens such as USDT return nothing instead of `true`
+        Ok(result) if result.is_empty()
+            || IERC20::transferCall::abi_decode_returns(&result, true).is_ok_and(|ret| ret._0) => TokenVerdict::Safe,
+        Ok(_) => TokenVerdict::TransferBlocked { reason: "transfer out of the pool returned false".to_string() },
+        Err(RpcError::ErrorResp(payload)) => TokenVerdict::TransferBlocked {
+            reason: format!("transfer out of the pool reverted: {}", payload.message),
+        },
+        Err(e) => return Err(e.into()),
+    })
+}
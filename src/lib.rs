@@ -16,8 +16,19 @@ pub mod volatility;
 pub mod validation;
 pub mod utils;
 pub mod storage;
+pub mod control;
+pub mod backtest;
+pub mod portfolio;
+pub mod risk;
+pub mod strategy;
+pub mod api;
+pub mod cli;
+pub mod bot;
+#[cfg(feature = "test-support")]
+pub mod test_support;
 
 // Re-export commonly used items
+pub use bot::{Bot, BotHandle};
 pub use config::{Config, CONFIG};
 pub use errors::{BotError, BotResult};
 pub use types::*;
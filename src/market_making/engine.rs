@@ -12,55 +12,150 @@ use tracing::debug;
 use crate::{
     config::{CONFIG, MIN_SPREAD_BPS, MAX_SPREAD_BPS},
     types::{
-        PoolInfo, LiquidityDepth, MarketMakingSignal, InventoryAnalysis, MarketConditions,
-        LiquidityStrategy, RiskMetrics, ExecutionPriority, VolatilityMetrics,
+        PoolInfo, LiquidityDepth, MarketMakingSignal, FairValueQuotes, PriceBasis, InventoryAnalysis, MarketConditions,
+        LiquidityStrategy, RiskMetrics, ExecutionPriority, VolatilityMetrics, PoolVolatility,
         InventoryImbalance, MarketTrend, SpreadEnvironment, VolumeProfile, DepthQuality,
-        StrategyType, RangeBounds, RiskLevel, VolatilityImpact, VolatilityTrend, ExecutionUrgency,
+        StrategyType, RangeBounds, RiskLevel, InventorySource, VolatilityImpact, VolatilityTrend, ExecutionUrgency,
+        QuoteBookReport, QuoteFill, EmissionYield, ExpectedReturn, EpochPhase, StressTestResult,
     },
+    market_making::{
+        epoch_position, estimate_impermanent_loss, expected_return, run_stress_tests, SimulatedOrderBook,
+        EPOCH_FLIP_SIZE_FACTOR, EPOCH_FLIP_SPREAD_MULTIPLIER,
+    },
+    pools::{GaugeTracker, EMISSION_STRATEGY_MIN_APR_PCT},
+    risk::RiskManager,
+    storage::HistorySnapshot,
     volatility::MultiTimeframeVolatilityCalculator,
 };
 
 pub struct MarketMakingEngine {
     volatility_calculator: Arc<RwLock<MultiTimeframeVolatilityCalculator>>,
     last_signals: Arc<RwLock<HashMap<String, MarketMakingSignal>>>,
+    /// Real (WETH, USD) inventory from the portfolio tracker, if any
+    inventory: Arc<RwLock<Option<(Decimal, Decimal)>>>,
+    /// Global limits capping signal position sizes
+    risk_manager: Option<Arc<RiskManager>>,
+    /// Simulated quotes placed from signals
+    order_book: Arc<RwLock<SimulatedOrderBook>>,
+    /// Pool gauge emissions, read when gauge yield is enabled
+    gauges: GaugeTracker,
 }
 
 impl MarketMakingEngine {
     pub fn new() -> Self {
+        let config = CONFIG.get();
+        let volatility_calculator = MultiTimeframeVolatilityCalculator::spilling(
+            "eth_usd",
+            config.history_memory_samples,
+            config.history_retention_days,
+        ).with_estimator(config.volatility_estimator, config.ewma_lambda.to_f64().unwrap_or_default());
         Self {
-            volatility_calculator: Arc::new(RwLock::new(MultiTimeframeVolatilityCalculator::new())),
+            volatility_calculator: Arc::new(RwLock::new(volatility_calculator)),
             last_signals: Arc::new(RwLock::new(HashMap::new())),
+            inventory: Arc::new(RwLock::new(None)),
+            risk_manager: None,
+            order_book: Arc::new(RwLock::new(SimulatedOrderBook::new())),
+            gauges: GaugeTracker::new(),
         }
     }
 
+    /// Cap position sizes to the risk manager's exposure headroom, zero while halted
+    pub fn with_risk_manager(mut self, risk_manager: Arc<RiskManager>) -> Self {
+        self.risk_manager = Some(risk_manager);
+        self
+    }
+
+    /// Quote the signal's bid and ask, replacing the pool's open quotes
+    pub async fn place_quotes(&self, signal: &MarketMakingSignal) {
+        self.order_book.write().await.place(signal);
+    }
+
+    /// Fill the pool's open quotes this cycle's prices crossed
+    pub async fn match_quotes(&self, pool: &str, dex_price: Decimal, fair_value_price: Decimal) -> Vec<QuoteFill> {
+        self.order_book.write().await.match_prices(pool, dex_price, fair_value_price, rand::random::<f64>)
+    }
+
+    pub async fn quote_book_report(&self, mark_price: Decimal) -> QuoteBookReport {
+        self.order_book.read().await.report(mark_price)
+    }
+
+    /// Inventory analysis from the most recent signal across pools
+    pub async fn latest_inventory(&self) -> Option<(InventoryAnalysis, Decimal)> {
+        self.last_signals.read().await
+            .values()
+            .max_by_key(|s| s.timestamp)
+            .map(|s| (s.inventory_analysis.clone(), s.fair_value_price))
+    }
+
+    /// Stress tests from the most recent signal across pools
+    pub async fn latest_stress_tests(&self) -> Vec<StressTestResult> {
+        self.last_signals.read().await
+            .values()
+            .max_by_key(|s| s.timestamp)
+            .map(|s| s.risk_metrics.stress_tests.clone())
+            .unwrap_or_default()
+    }
+
+    /// Use real balances for inventory analysis instead of simulated ones
+    pub async fn set_inventory(&self, weth_balance: Decimal, usd_balance: Decimal) {
+        *self.inventory.write().await = Some((weth_balance, usd_balance));
+    }
+
     pub async fn update_price_history(&self, price: Decimal) {
         self.volatility_calculator.write().await.add_price(price).await;
     }
 
+    /// Track a pool's DEX price and its basis to the CEX price
+    pub async fn update_dex_price_history(&self, pool: &str, dex_price: Decimal, cex_price: Decimal) {
+        self.volatility_calculator.read().await.add_dex_price(pool, dex_price, cex_price).await;
+    }
+
+    pub async fn volatility_history(&self) -> HistorySnapshot {
+        self.volatility_calculator.read().await.history_snapshot().await
+    }
+
+    pub async fn restore_volatility_history(&self, snapshot: &HistorySnapshot) {
+        self.volatility_calculator.read().await.restore_history(snapshot).await;
+    }
+
     pub async fn get_volatility_metrics(&self) -> VolatilityMetrics {
         self.volatility_calculator.read().await.get_volatility_metrics().await
     }
 
+    pub async fn pool_volatility(&self, pool: &str) -> Option<PoolVolatility> {
+        self.volatility_calculator.read().await.pool_volatility(pool).await
+    }
+
+    /// Volatility metrics raised to `pool`'s DEX or basis volatility band
+    pub async fn get_pool_volatility_metrics(&self, pool: &str) -> VolatilityMetrics {
+        self.volatility_calculator.read().await.get_pool_volatility_metrics(pool).await
+    }
+
     pub async fn generate_market_making_signal(
         &self,
         pool_info: &PoolInfo,
-        fair_value_price: Decimal,
+        fair_values: &FairValueQuotes,
+        price_basis: PriceBasis,
         current_pool_price: Decimal,
         liquidity_depth: LiquidityDepth,
-        _provider: &dyn Provider,
+        gas_cost_usd: Decimal,
+        provider: &dyn Provider,
     ) -> Result<MarketMakingSignal> {
         let signal_id = uuid::Uuid::new_v4().to_string();
         let timestamp = chrono::Utc::now();
+        let (price_basis, fair_value_price) = fair_values.resolve(price_basis);
 
-        // Get volatility metrics
-        let volatility_metrics = self.get_volatility_metrics().await;
+        // Get volatility metrics, including the pool's own DEX and basis volatility
+        let volatility_metrics = self.get_pool_volatility_metrics(&pool_info.name).await;
 
         debug!(
-            "Volatility analysis: Short={:.2}%, Medium={:.2}%, Long={:.2}%, Trend={:?}",
+            "Volatility analysis: Short={:.2}%, Medium={:.2}%, Long={:.2}%, Trend={:?}, Confidence={:?} ({} samples)",
             volatility_metrics.short_term_volatility,
             volatility_metrics.medium_term_volatility,
             volatility_metrics.long_term_volatility,
-            volatility_metrics.volatility_trend
+            volatility_metrics.volatility_trend,
+            volatility_metrics.confidence,
+            volatility_metrics.sample_count
         );
 
         let market_conditions = self.analyze_market_conditions(
@@ -90,26 +185,54 @@ impl MarketMakingEngine {
         let target_ask_price = fair_value_price + half_spread;
 
         // Adjust position size based on volatility
-        let position_size_eth = self.calculate_position_size_with_volatility(
+        let mut position_size_eth = self.calculate_position_size_with_volatility(
             &market_conditions,
             &inventory_analysis,
             &liquidity_depth,
             &volatility_metrics,
         ).await;
+        if let Some(risk_manager) = &self.risk_manager {
+            position_size_eth = risk_manager.market_making_allowance(position_size_eth).await;
+        }
+
+        let emission_yield = if CONFIG.get().enable_gauge_yield {
+            self.gauges.emission_yield(provider, pool_info, liquidity_depth.total_liquidity_usd).await
+        } else {
+            None
+        };
+        let emission_apr_pct = emission_yield.as_ref().map_or(dec!(0), |emissions| emissions.apr_pct);
 
         let strategy = self.select_liquidity_strategy(
             &market_conditions,
             &inventory_analysis,
             current_pool_price,
             fair_value_price,
+            emission_apr_pct,
         ).await;
 
-        let risk_metrics = self.calculate_risk_metrics_with_volatility(
+        let mut risk_metrics = self.calculate_risk_metrics_with_volatility(
             position_size_eth,
             fair_value_price,
+            &strategy,
             &volatility_metrics,
             &liquidity_depth,
         ).await;
+        risk_metrics.stress_tests = run_stress_tests(
+            &inventory_analysis,
+            &strategy,
+            fair_value_price,
+            gas_cost_usd,
+        );
+
+        let expected_return = expected_return(
+            &strategy,
+            fair_value_price,
+            effective_spread_bps,
+            pool_info.fee,
+            position_size_eth * fair_value_price,
+            emission_apr_pct,
+            risk_metrics.impermanent_loss.expected_loss_usd,
+        );
 
         let execution_priority = self.determine_execution_priority_with_volatility(
             &market_conditions,
@@ -124,7 +247,10 @@ impl MarketMakingEngine {
             &market_conditions,
             &inventory_analysis,
             &strategy,
+            &risk_metrics,
             &volatility_metrics,
+            emission_yield.as_ref(),
+            &expected_return,
             effective_spread_bps,
             fair_value_price,
             current_pool_price,
@@ -135,6 +261,8 @@ impl MarketMakingEngine {
             timestamp,
             pool: pool_info.name.clone(),
             fair_value_price,
+            price_basis,
+            fair_values: Some(fair_values.clone()),
             current_pool_price,
             target_bid_price,
             target_ask_price,
@@ -146,6 +274,8 @@ impl MarketMakingEngine {
             risk_metrics,
             volatility_metrics,
             execution_priority,
+            emission_yield,
+            expected_return,
             rationale,
         };
 
@@ -184,12 +314,16 @@ impl MarketMakingEngine {
             _ => VolumeProfile::Low,
         };
 
+        let now = chrono::Utc::now().timestamp().max(0) as u64;
+        let epoch = epoch_position(now, CONFIG.get().epoch_flip_window_secs);
+
         MarketConditions {
             price_volatility_1h: volatility,
             liquidity_depth: liquidity_depth.clone(),
             spread_environment,
             market_trend,
             volume_profile,
+            epoch,
         }
     }
 
@@ -198,15 +332,25 @@ impl MarketMakingEngine {
         fair_value_price: Decimal,
         liquidity_depth: &LiquidityDepth,
     ) -> InventoryAnalysis {
-        let current_weth_balance = CONFIG.max_position_size_eth * dec!(0.4);
-        let current_usd_balance = CONFIG.max_position_size_eth * fair_value_price * dec!(0.6);
-
-        let max_feasible_position = liquidity_depth.weth_reserves * dec!(0.1);
-        let adjusted_weth_balance = current_weth_balance.min(max_feasible_position);
+        let config = CONFIG.get();
+        let (adjusted_weth_balance, current_usd_balance, source) = match *self.inventory.read().await {
+            Some((weth, usd)) => (weth, usd, InventorySource::Portfolio),
+            None => {
+                let current_weth_balance = config.max_position_size_eth * dec!(0.4);
+                let current_usd_balance = config.max_position_size_eth * fair_value_price * dec!(0.6);
+
+                let max_feasible_position = liquidity_depth.weth_reserves * dec!(0.1);
+                (current_weth_balance.min(max_feasible_position), current_usd_balance, InventorySource::Simulated)
+            }
+        };
 
         let total_value_usd = (adjusted_weth_balance * fair_value_price) + current_usd_balance;
-        let weth_ratio = (adjusted_weth_balance * fair_value_price) / total_value_usd;
-        let target_weth_ratio = CONFIG.inventory_target_ratio;
+        let weth_ratio = if total_value_usd > dec!(0) {
+            (adjusted_weth_balance * fair_value_price) / total_value_usd
+        } else {
+            config.inventory_target_ratio
+        };
+        let target_weth_ratio = config.inventory_target_ratio;
 
         let ratio_diff = (weth_ratio - target_weth_ratio).abs();
         let imbalance_severity = match ratio_diff {
@@ -228,7 +372,7 @@ impl MarketMakingEngine {
             _ => InventoryImbalance::CriticallyImbalanced,
         };
 
-        let rebalance_needed = ratio_diff > CONFIG.rebalance_threshold;
+        let rebalance_needed = ratio_diff > config.rebalance_threshold;
         let rebalance_amount_eth = if rebalance_needed {
             (target_weth_ratio - weth_ratio) * total_value_usd / fair_value_price
         } else {
@@ -244,6 +388,7 @@ impl MarketMakingEngine {
             imbalance_severity,
             rebalance_needed,
             rebalance_amount_eth,
+            source,
         }
     }
 
@@ -254,7 +399,7 @@ impl MarketMakingEngine {
         volatility_metrics: &VolatilityMetrics,
         fair_value_price: Decimal,
     ) -> u32 {
-        let mut spread_bps = CONFIG.base_spread_bps;
+        let mut spread_bps = CONFIG.get().base_spread_bps;
 
         // Apply volatility adjustments
         spread_bps = (Decimal::from(spread_bps) * volatility_metrics.recommended_adjustments.spread_multiplier)
@@ -303,19 +448,24 @@ impl MarketMakingEngine {
             SpreadEnvironment::VeryWide => spread_bps = (spread_bps as f64 * 1.5) as u32,
         }
 
+        // Liquidity migrates around the weekly emissions flip
+        if market_conditions.epoch.is_near_flip() {
+            spread_bps = (spread_bps as f64 * EPOCH_FLIP_SPREAD_MULTIPLIER) as u32;
+        }
+
         spread_bps.max(MIN_SPREAD_BPS).min(MAX_SPREAD_BPS)
     }
 
     async fn calculate_position_size_with_volatility(
         &self,
-        _market_conditions: &MarketConditions,
+        market_conditions: &MarketConditions,
         inventory_analysis: &InventoryAnalysis,
         liquidity_depth: &LiquidityDepth,
         volatility_metrics: &VolatilityMetrics,
     ) -> Decimal {
         use crate::config::{MIN_TRADE_SIZE_ETH};
         
-        let mut base_size = CONFIG.max_position_size_eth * dec!(0.1);
+        let mut base_size = CONFIG.get().max_position_size_eth * dec!(0.1);
 
         // Apply volatility-based position sizing
         base_size *= volatility_metrics.recommended_adjustments.position_size_factor;
@@ -329,6 +479,9 @@ impl MarketMakingEngine {
             _ => {}
         }
 
+        // Scale by pool health (liquidity, volume, stability, spread, age)
+        base_size *= (liquidity_depth.health_score / dec!(100)).max(dec!(0.25));
+
         // Pool impact check
         let pool_impact = base_size / liquidity_depth.weth_reserves;
         if pool_impact > dec!(0.01) {
@@ -342,7 +495,11 @@ impl MarketMakingEngine {
             _ => {},
         }
 
-        base_size.max(MIN_TRADE_SIZE_ETH).min(CONFIG.max_position_size_eth)
+        if market_conditions.epoch.is_near_flip() {
+            base_size *= EPOCH_FLIP_SIZE_FACTOR;
+        }
+
+        base_size.max(MIN_TRADE_SIZE_ETH).min(CONFIG.get().max_position_size_eth)
     }
 
     async fn select_liquidity_strategy(
@@ -351,6 +508,7 @@ impl MarketMakingEngine {
         inventory_analysis: &InventoryAnalysis,
         current_price: Decimal,
         fair_value: Decimal,
+        emission_apr_pct: Decimal,
     ) -> LiquidityStrategy {
         let price_deviation = ((current_price - fair_value).abs() / fair_value) * dec!(100);
 
@@ -362,6 +520,8 @@ impl MarketMakingEngine {
             (vol, _, _) if *vol > dec!(15) => StrategyType::VolatilityAdaptive,
             (_, InventoryImbalance::SignificantlyLong | InventoryImbalance::SignificantlyShort, _) => 
                 StrategyType::InventoryManagement,
+            // Rich emissions pay for a long-lived range that needs little rebalancing
+            _ if emission_apr_pct >= EMISSION_STRATEGY_MIN_APR_PCT => StrategyType::WideSpread,
             (_, _, SpreadEnvironment::Tight) if price_deviation < dec!(0.1) => 
                 StrategyType::TightSpread,
             (_, _, SpreadEnvironment::Wide | SpreadEnvironment::VeryWide) => 
@@ -369,7 +529,7 @@ impl MarketMakingEngine {
             _ => StrategyType::TrendFollowing,
         };
 
-        let base_size = CONFIG.max_position_size_eth * dec!(0.1);
+        let base_size = CONFIG.get().max_position_size_eth * dec!(0.1);
         
         let (bid_size_eth, ask_size_eth) = match (&strategy_type, &inventory_analysis.imbalance_severity) {
             (StrategyType::InventoryManagement, InventoryImbalance::SignificantlyLong) => 
@@ -415,18 +575,25 @@ impl MarketMakingEngine {
         &self,
         position_size: Decimal,
         fair_value: Decimal,
+        strategy: &LiquidityStrategy,
         volatility_metrics: &VolatilityMetrics,
         liquidity_depth: &LiquidityDepth,
     ) -> RiskMetrics {
         let position_value = position_size * fair_value;
         
-        // Use short-term volatility for VaR calculation
-        let daily_volatility = volatility_metrics.short_term_volatility * dec!(4.899);
+        // Use short-term volatility for VaR calculation, scaled to one day
+        // (Moderate until there are enough samples to measure it)
+        let short_term_volatility = volatility_metrics.conservative_short_term_volatility();
+        let daily_volatility = if volatility_metrics.estimator.is_return_based() {
+            short_term_volatility / dec!(19.105) // sqrt(365)
+        } else {
+            short_term_volatility * dec!(4.899)
+        };
         let value_at_risk_1d = position_value * daily_volatility / dec!(100) * dec!(1.65);
 
         let max_drawdown_usd = position_value * dec!(0.1);
 
-        let inventory_risk_score = (position_size / CONFIG.max_position_size_eth * dec!(100))
+        let inventory_risk_score = (position_size / CONFIG.get().max_position_size_eth * dec!(100))
             .min(dec!(100));
 
         let liquidity_risk_score = match liquidity_depth.depth_quality {
@@ -444,13 +611,24 @@ impl MarketMakingEngine {
             VolatilityImpact::Extreme => dec!(90),
         };
 
+        // Expected IL over the strategy's range and duration; 0.1% scores 100
+        let impermanent_loss = estimate_impermanent_loss(
+            &strategy.range_bounds,
+            fair_value,
+            daily_volatility,
+            strategy.duration_estimate,
+            position_value,
+        );
+        let impermanent_loss_risk_score = (impermanent_loss.expected_loss_pct * dec!(1000)).min(dec!(100));
+
         // Weighted overall risk score
-        let overall_risk_score = inventory_risk_score * dec!(0.3) + 
-                                 liquidity_risk_score * dec!(0.25) + 
-                                 volatility_risk_score * dec!(0.35) +
-                                 volatility_metrics.short_term_volatility.min(dec!(50)) * dec!(0.1);
+        let overall_risk_score = inventory_risk_score * dec!(0.25) + 
+                                 liquidity_risk_score * dec!(0.2) + 
+                                 volatility_risk_score * dec!(0.3) +
+                                 impermanent_loss_risk_score * dec!(0.15) +
+                                 short_term_volatility.min(dec!(50)) * dec!(0.1);
 
-        let recommended_max_exposure = CONFIG.max_position_size_eth * 
+        let recommended_max_exposure = CONFIG.get().max_position_size_eth * 
             (dec!(100) - overall_risk_score) / dec!(100);
 
         RiskMetrics {
@@ -461,6 +639,9 @@ impl MarketMakingEngine {
             volatility_risk_score,
             overall_risk_score,
             recommended_max_exposure,
+            stress_tests: Vec::new(),
+            impermanent_loss,
+            impermanent_loss_risk_score,
         }
     }
 
@@ -514,7 +695,10 @@ impl MarketMakingEngine {
         market_conditions: &MarketConditions,
         inventory_analysis: &InventoryAnalysis,
         strategy: &LiquidityStrategy,
+        risk_metrics: &RiskMetrics,
         volatility_metrics: &VolatilityMetrics,
+        emission_yield: Option<&EmissionYield>,
+        expected_return: &ExpectedReturn,
         spread_bps: u32,
         fair_value: Decimal,
         current_price: Decimal,
@@ -543,6 +727,9 @@ impl MarketMakingEngine {
             StrategyType::TightSpread => {
                 rationale.push_str("TIGHT SPREAD strategy selected due to stable conditions and tight current spreads. ");
             },
+            StrategyType::WideSpread if emission_yield.is_some_and(|e| e.apr_pct >= EMISSION_STRATEGY_MIN_APR_PCT) => {
+                rationale.push_str("WIDE SPREAD strategy selected to keep a long-lived range staked for gauge emissions. ");
+            },
             StrategyType::WideSpread => {
                 rationale.push_str("WIDE SPREAD strategy selected to capture larger price movements in volatile environment. ");
             },
@@ -563,6 +750,19 @@ impl MarketMakingEngine {
             },
         }
 
+        let epoch = &market_conditions.epoch;
+        match epoch.phase {
+            EpochPhase::PostFlip => rationale.push_str(&format!(
+                "Epoch flipped {}m ago - spreads widened and size reduced while liquidity migrates. ",
+                epoch.secs_since_flip / 60
+            )),
+            EpochPhase::PreFlip => rationale.push_str(&format!(
+                "Epoch flips in {}m - spreads widened and size reduced ahead of the vote flip. ",
+                epoch.secs_until_flip / 60
+            )),
+            EpochPhase::Mid => {}
+        }
+
         // Volatility-based adjustments
         if matches!(volatility_metrics.impact_assessment, VolatilityImpact::High | VolatilityImpact::Extreme) {
             rationale.push_str(&format!(
@@ -572,6 +772,34 @@ impl MarketMakingEngine {
             ));
         }
 
+        let impermanent_loss = &risk_metrics.impermanent_loss;
+        rationale.push_str(&format!(
+            "Expected impermanent loss over {}m in ${:.2}-${:.2}: {:.4}% (${:.2}), {:.2}% if price exits the range. ",
+            impermanent_loss.horizon_secs / 60,
+            strategy.range_bounds.lower_bound,
+            strategy.range_bounds.upper_bound,
+            impermanent_loss.expected_loss_pct,
+            impermanent_loss.expected_loss_usd,
+            impermanent_loss.range_exit_loss_pct,
+        ));
+
+        if let Some(emissions) = emission_yield {
+            rationale.push_str(&format!(
+                "Gauge emissions: {:.1}% APR on ${:.0} staked at ${:.3}/AERO. ",
+                emissions.apr_pct,
+                emissions.staked_tvl_usd,
+                emissions.aero_price_usd,
+            ));
+        }
+        rationale.push_str(&format!(
+            "Expected return: ${:.2} spread (after ${:.2} pool fee) + ${:.2} emissions - ${:.2} IL = ${:.2}. ",
+            expected_return.spread_capture_usd,
+            expected_return.pool_fee_usd,
+            expected_return.emission_usd,
+            expected_return.impermanent_loss_usd,
+            expected_return.net_usd,
+        ));
+
         match strategy.risk_level {
             RiskLevel::Conservative => rationale.push_str("Conservative sizing due to uncertain conditions. "),
             RiskLevel::Moderate => rationale.push_str("Moderate risk profile with balanced exposure. "),
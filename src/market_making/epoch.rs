@@ -0,0 +1,47 @@
+//! Aerodrome weekly epoch calendar
+//!
+//! Emissions and veAERO votes flip every Thursday 00:00 UTC, when a new
+//! epoch starts. Liquidity routinely migrates around the flip as LPs follow
+//! the new gauge weights, so quotes near it are widened and sized down.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use crate::types::{EpochPhase, EpochPosition};
+
+/// Length of an Aerodrome epoch; the Unix epoch fell on a Thursday, so
+/// epochs start at multiples of it
+pub const EPOCH_DURATION_SECS: u64 = 604_800;
+
+/// Default time on each side of a flip treated as near it
+pub const DEFAULT_EPOCH_FLIP_WINDOW_SECS: u64 = 7200;
+
+/// Spread multiplier applied near a flip
+pub const EPOCH_FLIP_SPREAD_MULTIPLIER: f64 = 1.25;
+/// Position size factor applied near a flip
+pub const EPOCH_FLIP_SIZE_FACTOR: Decimal = dec!(0.7);
+
+/// Start of the epoch containing Unix time `now`
+pub fn epoch_start(now: u64) -> u64 {
+    now - now % EPOCH_DURATION_SECS
+}
+
+/// Where `now` falls in its epoch, with `flip_window_secs` on each side of
+/// a flip counted as near it (zero never is)
+pub fn epoch_position(now: u64, flip_window_secs: u64) -> EpochPosition {
+    let epoch_start = epoch_start(now);
+    let secs_since_flip = now - epoch_start;
+    let secs_until_flip = EPOCH_DURATION_SECS - secs_since_flip;
+    let phase = if secs_since_flip < flip_window_secs {
+        EpochPhase::PostFlip
+    } else if secs_until_flip <= flip_window_secs {
+        EpochPhase::PreFlip
+    } else {
+        EpochPhase::Mid
+    };
+    EpochPosition {
+        phase,
+        epoch_start,
+        secs_since_flip,
+        secs_until_flip,
+    }
+}
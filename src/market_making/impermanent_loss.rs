@@ -0,0 +1,115 @@
+//! Impermanent loss of a concentrated liquidity position
+//!
+//! A position over `RangeBounds` is valued like a Slipstream/Uniswap v3
+//! range: inside the range it holds both tokens, past a bound only one. The
+//! expected loss against simply holding is averaged over a lognormal price
+//! move with the strategy's duration as horizon, so a wide, long-lived range
+//! and a tight, short-lived one can be compared in dollars.
+
+use rust_decimal::prelude::*;
+use std::time::Duration;
+use crate::types::{ImpermanentLossEstimate, RangeBounds};
+
+/// Standard deviations covered by the expectation grid, each side
+const GRID_SIGMAS: f64 = 4.0;
+const GRID_STEPS: i32 = 80;
+
+/// Impermanent loss of a full-range position after the price changes by
+/// `ratio`, as a positive fraction of the held value
+pub fn full_range_loss(ratio: f64) -> f64 {
+    if ratio <= 0.0 {
+        return 0.0;
+    }
+    1.0 - 2.0 * ratio.sqrt() / (1.0 + ratio)
+}
+
+/// Range bounds as f64, or None when they cannot hold a position
+fn bounds_f64(bounds: &RangeBounds) -> Option<(f64, f64)> {
+    let lower = bounds.lower_bound.to_f64()?;
+    let upper = bounds.upper_bound.to_f64()?;
+    (lower > 0.0 && upper > lower).then_some((lower, upper))
+}
+
+/// Liquidity per unit of capital of a position in `bounds` at `price`,
+/// relative to a full-range position (1 when the range is unusable)
+pub fn concentration(bounds: &RangeBounds, price: f64) -> f64 {
+    let Some((lower, upper)) = bounds_f64(bounds) else {
+        return 1.0;
+    };
+    let price = price.clamp(lower, upper);
+    let denominator = 2.0 * price.sqrt() - lower.sqrt() - price / upper.sqrt();
+    if denominator <= 0.0 {
+        return 1.0;
+    }
+    2.0 * price.sqrt() / denominator
+}
+
+/// Impermanent loss of a position in `bounds` opened at `price` when the
+/// price moves to `new_price`, as a positive fraction of the held value
+pub fn concentrated_loss(bounds: &RangeBounds, price: f64, new_price: f64) -> f64 {
+    let Some((lower, upper)) = bounds_f64(bounds) else {
+        return full_range_loss(new_price / price);
+    };
+    let price = price.clamp(lower, upper);
+    let (sqrt_lower, sqrt_upper) = (lower.sqrt(), upper.sqrt());
+    // Token amounts per unit of liquidity at the opening price
+    let base = 1.0 / price.sqrt() - 1.0 / sqrt_upper;
+    let quote = price.sqrt() - sqrt_lower;
+    let held = base * new_price + quote;
+
+    let provided = if new_price <= lower {
+        (1.0 / sqrt_lower - 1.0 / sqrt_upper) * new_price
+    } else if new_price >= upper {
+        sqrt_upper - sqrt_lower
+    } else {
+        2.0 * new_price.sqrt() - sqrt_lower - new_price / sqrt_upper
+    };
+    if held <= 0.0 {
+        return 0.0;
+    }
+    (1.0 - provided / held).max(0.0)
+}
+
+/// Expected impermanent loss of `position_value_usd` in `bounds` over
+/// `horizon`, given `daily_volatility_pct` of the price
+pub fn estimate_impermanent_loss(
+    bounds: &RangeBounds,
+    price: Decimal,
+    daily_volatility_pct: Decimal,
+    horizon: Duration,
+    position_value_usd: Decimal,
+) -> ImpermanentLossEstimate {
+    let price_f64 = price.to_f64().unwrap_or_default();
+    let sigma = daily_volatility_pct.to_f64().unwrap_or_default().max(0.0) / 100.0
+        * (horizon.as_secs_f64() / 86_400.0).sqrt();
+
+    let expected = if price_f64 > 0.0 && sigma > 0.0 {
+        // Lognormal move with zero drift, on a grid of standard normal outcomes
+        let (mut loss, mut weight) = (0.0, 0.0);
+        for step in -GRID_STEPS..=GRID_STEPS {
+            let z = GRID_SIGMAS * step as f64 / GRID_STEPS as f64;
+            let density = (-z * z / 2.0).exp();
+            let new_price = price_f64 * (sigma * z - sigma * sigma / 2.0).exp();
+            loss += density * concentrated_loss(bounds, price_f64, new_price);
+            weight += density;
+        }
+        loss / weight
+    } else {
+        0.0
+    };
+    let range_exit = bounds_f64(bounds)
+        .map(|(lower, upper)| {
+            concentrated_loss(bounds, price_f64, lower).max(concentrated_loss(bounds, price_f64, upper))
+        })
+        .unwrap_or_default();
+
+    let pct = |fraction: f64| Decimal::from_f64(fraction * 100.0).unwrap_or_default().round_dp(6);
+    let expected_loss_pct = pct(expected);
+    ImpermanentLossEstimate {
+        horizon_secs: horizon.as_secs(),
+        concentration: Decimal::from_f64(concentration(bounds, price_f64)).unwrap_or_default().round_dp(2),
+        expected_loss_pct,
+        expected_loss_usd: position_value_usd * expected_loss_pct / Decimal::ONE_HUNDRED,
+        range_exit_loss_pct: pct(range_exit),
+    }
+}
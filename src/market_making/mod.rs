@@ -1,5 +1,15 @@
 //! Market making strategies and signal generation
 
 pub mod engine;
+pub mod stress;
+pub mod orders;
+pub mod impermanent_loss;
+pub mod returns;
+pub mod epoch;
 
 pub use engine::*;
+pub use stress::*;
+pub use orders::*;
+pub use impermanent_loss::*;
+pub use returns::*;
+pub use epoch::*;
@@ -0,0 +1,162 @@
+//! Simulated quote lifecycle for market-making signals
+//!
+//! Each signal places a bid and an ask at its target prices, replacing the
+//! pool's previous quotes. Every cycle the pool's DEX and CEX prices are
+//! checked against the open quotes: a quote the market touched fills with
+//! `TOUCH_FILL_PROBABILITY`, rising to certain once the market trades
+//! `CERTAIN_FILL_THROUGH_BPS` through it. Fills are booked at the quote
+//! price and scored against fair value, so the strategy's spread capture
+//! and resulting position can be measured.
+
+use chrono::Utc;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+use crate::types::{
+    ExecutionPriority, MarketMakingSignal, QuoteBookReport, QuoteFill, QuoteSide, SimulatedQuote,
+};
+
+/// Chance a quote fills when the market just reaches its price
+pub const TOUCH_FILL_PROBABILITY: f64 = 0.5;
+/// Distance through the quote at which it always fills
+pub const CERTAIN_FILL_THROUGH_BPS: f64 = 10.0;
+
+/// Fill chance for a quote the market traded `through_bps` past
+pub fn fill_probability(through_bps: Decimal) -> f64 {
+    let through = through_bps.to_f64().unwrap_or_default();
+    if through < 0.0 {
+        return 0.0;
+    }
+    (TOUCH_FILL_PROBABILITY + (1.0 - TOUCH_FILL_PROBABILITY) * through / CERTAIN_FILL_THROUGH_BPS).min(1.0)
+}
+
+#[derive(Default)]
+struct PoolQuotes {
+    bid: Option<SimulatedQuote>,
+    ask: Option<SimulatedQuote>,
+}
+
+/// Open simulated quotes per pool and the totals of their fills
+#[derive(Default)]
+pub struct SimulatedOrderBook {
+    pools: HashMap<String, PoolQuotes>,
+    report: QuoteBookReport,
+}
+
+impl SimulatedOrderBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the pool's quotes with the signal's bid and ask. A signal on
+    /// hold only cancels the pool's open quotes.
+    pub fn place(&mut self, signal: &MarketMakingSignal) {
+        let size_eth = if signal.execution_priority == ExecutionPriority::Hold {
+            dec!(0)
+        } else {
+            signal.position_size_eth
+        };
+        self.quote(&signal.pool, &signal.id, signal.target_bid_price, signal.target_ask_price, size_eth);
+    }
+
+    /// Cancel the pool's open quotes and, with a positive size, quote
+    /// `bid_price` and `ask_price` in their place
+    pub fn quote(&mut self, pool: &str, signal_id: &str, bid_price: Decimal, ask_price: Decimal, size_eth: Decimal) {
+        let quotes = self.pools.entry(pool.to_string()).or_default();
+        let cancelled = quotes.bid.take().is_some() as u64 + quotes.ask.take().is_some() as u64;
+        self.report.cancelled += cancelled;
+
+        if size_eth <= dec!(0) {
+            return;
+        }
+        let placed_at = Utc::now();
+        let quote = |side, price| SimulatedQuote {
+            id: uuid::Uuid::new_v4().to_string(),
+            signal_id: signal_id.to_string(),
+            pool: pool.to_string(),
+            side,
+            price,
+            size_eth,
+            placed_at,
+        };
+        quotes.bid = Some(quote(QuoteSide::Bid, bid_price));
+        quotes.ask = Some(quote(QuoteSide::Ask, ask_price));
+        self.report.placed += 2;
+    }
+
+    /// Fill the pool's quotes the DEX or CEX price crossed. `roll` returns
+    /// uniform values in [0, 1) deciding each crossed quote's fill.
+    pub fn match_prices(
+        &mut self,
+        pool: &str,
+        dex_price: Decimal,
+        fair_value_price: Decimal,
+        mut roll: impl FnMut() -> f64,
+    ) -> Vec<QuoteFill> {
+        let Some(quotes) = self.pools.get_mut(pool) else {
+            return Vec::new();
+        };
+        let mut fills = Vec::new();
+
+        // Bids fill on the lower of the two prices, asks on the higher
+        for slot in [&mut quotes.bid, &mut quotes.ask] {
+            let Some(quote) = slot.as_ref() else { continue };
+            let (market_price, through) = match quote.side {
+                QuoteSide::Bid => {
+                    let low = dex_price.min(fair_value_price);
+                    (low, quote.price - low)
+                }
+                QuoteSide::Ask => {
+                    let high = dex_price.max(fair_value_price);
+                    (high, high - quote.price)
+                }
+            };
+            if quote.price <= dec!(0) || roll() >= fill_probability(through / quote.price * dec!(10000)) {
+                continue;
+            }
+            let Some(quote) = slot.take() else { continue };
+            fills.push(self.report.book(quote, market_price, fair_value_price));
+        }
+        fills
+    }
+
+    /// Totals with the position marked at `mark_price`
+    pub fn report(&self, mark_price: Decimal) -> QuoteBookReport {
+        QuoteBookReport {
+            open_quotes: self.pools.values()
+                .map(|quotes| quotes.bid.is_some() as usize + quotes.ask.is_some() as usize)
+                .sum(),
+            pnl_usd: self.report.cash_usd + self.report.position_eth * mark_price,
+            ..self.report.clone()
+        }
+    }
+}
+
+impl QuoteBookReport {
+    fn book(&mut self, quote: SimulatedQuote, market_price: Decimal, fair_value_price: Decimal) -> QuoteFill {
+        let notional = quote.price * quote.size_eth;
+        let spread_capture_usd = match quote.side {
+            QuoteSide::Bid => {
+                self.bid_fills += 1;
+                self.position_eth += quote.size_eth;
+                self.cash_usd -= notional;
+                (fair_value_price - quote.price) * quote.size_eth
+            }
+            QuoteSide::Ask => {
+                self.ask_fills += 1;
+                self.position_eth -= quote.size_eth;
+                self.cash_usd += notional;
+                (quote.price - fair_value_price) * quote.size_eth
+            }
+        };
+        self.filled_eth += quote.size_eth;
+        self.spread_capture_usd += spread_capture_usd;
+        QuoteFill {
+            quote,
+            filled_at: Utc::now(),
+            market_price,
+            fair_value_price,
+            spread_capture_usd,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Expected return of a market-making position
+//!
+//! Over the strategy's expected duration a position earns its share of the
+//! spread on the expected volume, less the pool fee paid unwinding the fills,
+//! and, when staked, the pool gauge's AERO emissions, and gives up the
+//! expected impermanent loss.
+
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use crate::{
+    pools::emission_return_usd,
+    types::{ExpectedReturn, LiquidityStrategy},
+};
+
+/// Spread capture net of `pool_fee_rate` plus emissions at
+/// `emission_apr_pct`, less `impermanent_loss_usd`, for `position_value_usd`
+/// quoted at `spread_bps`
+pub fn expected_return(
+    strategy: &LiquidityStrategy,
+    fair_value: Decimal,
+    spread_bps: u32,
+    pool_fee_rate: Decimal,
+    position_value_usd: Decimal,
+    emission_apr_pct: Decimal,
+    impermanent_loss_usd: Decimal,
+) -> ExpectedReturn {
+    let days = Decimal::from(strategy.duration_estimate.as_secs()) / dec!(86400);
+    let volume_usd = strategy.expected_daily_volume * fair_value * days;
+    // Each side of a fill earns half the spread, and pays the pool fee when unwound
+    let pool_fee_usd = volume_usd * pool_fee_rate;
+    let spread_capture_usd = volume_usd * Decimal::from(spread_bps) / dec!(20000) - pool_fee_usd;
+    let emission_usd = emission_return_usd(position_value_usd, emission_apr_pct, strategy.duration_estimate);
+    ExpectedReturn {
+        spread_capture_usd,
+        pool_fee_usd,
+        emission_usd,
+        impermanent_loss_usd,
+        net_usd: spread_capture_usd + emission_usd - impermanent_loss_usd,
+    }
+}
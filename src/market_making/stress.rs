@@ -0,0 +1,79 @@
+//! Scenario stress tests for market making inventory
+
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use crate::types::{InventoryAnalysis, LiquidityStrategy, StressScenario, StressTestResult};
+
+pub const STRESS_SCENARIOS: [StressScenario; 4] = [
+    StressScenario::EthDown10,
+    StressScenario::EthDown20,
+    StressScenario::StableDepeg,
+    StressScenario::GasSpike10x,
+];
+
+/// Swaps needed to work both sides of an open plan
+const OPEN_PLAN_SWAPS: u32 = 2;
+
+impl StressScenario {
+    /// Relative change in the ETH price
+    pub fn eth_shock(&self) -> Decimal {
+        match self {
+            StressScenario::EthDown10 => dec!(-0.10),
+            StressScenario::EthDown20 => dec!(-0.20),
+            _ => dec!(0),
+        }
+    }
+
+    /// Relative change in the value of the USD stablecoin leg
+    pub fn stable_shock(&self) -> Decimal {
+        match self {
+            StressScenario::StableDepeg => dec!(-0.05),
+            _ => dec!(0),
+        }
+    }
+
+    pub fn gas_multiplier(&self) -> Decimal {
+        match self {
+            StressScenario::GasSpike10x => dec!(10),
+            _ => dec!(1),
+        }
+    }
+}
+
+/// Revalue inventory plus the strategy's open plans under each predefined shock.
+///
+/// Resting bids are assumed to fill into a falling market, so they add to ETH
+/// exposure. Gas is charged for executing the open plan at the shocked gas price.
+pub fn run_stress_tests(
+    inventory: &InventoryAnalysis,
+    strategy: &LiquidityStrategy,
+    fair_value_price: Decimal,
+    gas_cost_usd: Decimal,
+) -> Vec<StressTestResult> {
+    let exposure_eth = inventory.current_weth_balance + strategy.bid_size_eth;
+    let baseline_value_usd = inventory.current_weth_balance * fair_value_price
+        + inventory.current_usd_balance;
+    let baseline_gas_usd = gas_cost_usd * Decimal::from(OPEN_PLAN_SWAPS);
+
+    STRESS_SCENARIOS.iter().map(|&scenario| {
+        let eth_pnl = exposure_eth * fair_value_price * scenario.eth_shock();
+        let stable_pnl = inventory.current_usd_balance * scenario.stable_shock();
+        let gas_pnl = -(baseline_gas_usd * (scenario.gas_multiplier() - dec!(1)));
+        let pnl_usd = eth_pnl + stable_pnl + gas_pnl;
+
+        let pnl_pct = if baseline_value_usd > dec!(0) {
+            pnl_usd / baseline_value_usd * dec!(100)
+        } else {
+            dec!(0)
+        };
+
+        StressTestResult {
+            scenario,
+            exposure_eth,
+            baseline_value_usd,
+            stressed_value_usd: baseline_value_usd + pnl_usd,
+            pnl_usd,
+            pnl_pct,
+        }
+    }).collect()
+}
@@ -0,0 +1,196 @@
+//! What starts each monitoring cycle: a fixed interval or new Base blocks
+//!
+//! In block mode a background task keeps a WebSocket `newHeads` subscription
+//! open and publishes the latest block number. Cycles coalesce to the newest
+//! block, so a cycle slower than the block time skips blocks instead of
+//! falling behind, and a stalled subscription falls back to a timed cycle.
+//! Timed cycles speed up in volatile markets and slow down in quiet ones
+//! unless `ADAPTIVE_POLLING` is off.
+
+use alloy::providers::{Provider, ProviderBuilder, WsConnect};
+use anyhow::{Context, Result};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::{self, Interval};
+use tracing::{debug, info, warn};
+use crate::{
+    config::{Config, BASE_BLOCK_TIME_SECS},
+    types::{VolatilityImpact, VolatilityMetrics, VolatilityTrend},
+};
+
+/// Run a cycle anyway when no block has arrived for this many block times
+const BLOCK_STALL_BLOCKS: u64 = 5;
+const RECONNECT_DELAY_SECS: u64 = 5;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MonitorTrigger {
+    /// Every `POLL_INTERVAL_SECS`
+    #[default]
+    Interval,
+    /// Every new block from a WebSocket subscription
+    Blocks,
+}
+
+impl fmt::Display for MonitorTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            MonitorTrigger::Interval => "interval",
+            MonitorTrigger::Blocks => "blocks",
+        })
+    }
+}
+
+impl FromStr for MonitorTrigger {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "interval" => Ok(MonitorTrigger::Interval),
+            "blocks" => Ok(MonitorTrigger::Blocks),
+            other => Err(format!("unknown monitor trigger {:?}", other)),
+        }
+    }
+}
+
+/// Base mainnet WebSocket RPC: `WS_RPC_URL`, else Alchemy's. The public
+/// endpoint has no WebSocket, so watch-only mode needs `WS_RPC_URL`.
+pub fn mainnet_ws_url(config: &Config) -> Result<String> {
+    match (&config.ws_rpc_url, &config.alchemy_api_key, config.watch_only) {
+        (Some(url), _, _) => Ok(url.clone()),
+        (None, Some(alchemy_key), false) => Ok(format!("wss://base-mainnet.g.alchemy.com/v2/{}", alchemy_key)),
+        _ => Err(anyhow::anyhow!("MONITOR_TRIGGER=blocks needs WS_RPC_URL or an Alchemy key")),
+    }
+}
+
+/// Interval between timed cycles for the current volatility: the fastest
+/// bound when extreme, `POLL_INTERVAL_SECS` when moderate and the slowest
+/// bound in a dead market. Until prices fill the short-term window, and
+/// with adaptive polling off, it is `POLL_INTERVAL_SECS`.
+pub fn adaptive_poll_interval(volatility: &VolatilityMetrics, config: &Config) -> Duration {
+    let base = Duration::from_secs(config.poll_interval_secs);
+    if !config.adaptive_polling || volatility.is_low_confidence() {
+        return base;
+    }
+    let min = Duration::from_millis(config.min_poll_interval_ms);
+    let max = Duration::from_millis(config.max_poll_interval_ms);
+    let base = base.clamp(min, max);
+    match volatility.impact_assessment {
+        VolatilityImpact::Extreme => min,
+        VolatilityImpact::High => (min * 2).min(base),
+        VolatilityImpact::Moderate => base,
+        // A calm market that is starting to move backs off only halfway
+        VolatilityImpact::Low => match volatility.volatility_trend {
+            VolatilityTrend::Increasing => base + (max - base) / 2,
+            _ => max,
+        },
+    }
+}
+
+pub enum CycleTrigger {
+    Interval(Interval),
+    Blocks {
+        latest: watch::Receiver<Option<u64>>,
+        stall_timeout: Duration,
+    },
+}
+
+impl CycleTrigger {
+    /// Trigger for `config.monitor_trigger`, falling back to the interval
+    /// when no WebSocket URL is available
+    pub fn from_config(config: &Config) -> Self {
+        let interval = Self::interval(config.poll_interval_secs);
+        if config.monitor_trigger != MonitorTrigger::Blocks {
+            return interval;
+        }
+        match mainnet_ws_url(config) {
+            Ok(url) => {
+                info!("⛓️  Running one monitoring cycle per Base block");
+                Self::Blocks {
+                    latest: spawn_block_subscription(url),
+                    stall_timeout: Duration::from_secs(BLOCK_STALL_BLOCKS * BASE_BLOCK_TIME_SECS),
+                }
+            }
+            Err(e) => {
+                warn!("{}; polling every {}s instead", e, config.poll_interval_secs);
+                interval
+            }
+        }
+    }
+
+    pub fn interval(poll_interval_secs: u64) -> Self {
+        Self::Interval(time::interval(Duration::from_secs(poll_interval_secs)))
+    }
+
+    /// Change the interval between timed cycles, starting from now. Returns
+    /// whether it changed; block triggers keep following blocks.
+    pub fn set_poll_interval(&mut self, period: Duration) -> bool {
+        match self {
+            CycleTrigger::Interval(interval) if interval.period() != period => {
+                *interval = time::interval_at(time::Instant::now() + period, period);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Wait until the next cycle is due. Returns the block that triggered
+    /// it, or None for timed cycles.
+    pub async fn next(&mut self) -> Option<u64> {
+        match self {
+            CycleTrigger::Interval(interval) => {
+                interval.tick().await;
+                None
+            }
+            CycleTrigger::Blocks { latest, stall_timeout } => {
+                match time::timeout(*stall_timeout, latest.changed()).await {
+                    Ok(Ok(())) => *latest.borrow_and_update(),
+                    Ok(Err(_)) => {
+                        // The subscription task is gone; keep cycling on the stall timeout
+                        time::sleep(*stall_timeout).await;
+                        None
+                    }
+                    Err(_) => {
+                        warn!("⛓️  No new block for {:?}, running a timed cycle", stall_timeout);
+                        None
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Keep a `newHeads` subscription open, reconnecting on failure, and
+/// publish each new block number
+fn spawn_block_subscription(ws_url: String) -> watch::Receiver<Option<u64>> {
+    let (tx, rx) = watch::channel(None);
+    tokio::spawn(async move {
+        while !tx.is_closed() {
+            if let Err(e) = forward_blocks(&ws_url, &tx).await {
+                warn!("Block subscription failed: {}; reconnecting in {}s", e, RECONNECT_DELAY_SECS);
+            }
+            time::sleep(Duration::from_secs(RECONNECT_DELAY_SECS)).await;
+        }
+    });
+    rx
+}
+
+async fn forward_blocks(ws_url: &str, tx: &watch::Sender<Option<u64>>) -> Result<()> {
+    let provider = ProviderBuilder::new()
+        .on_ws(WsConnect::new(ws_url))
+        .await
+        .context("Failed to connect to the WebSocket RPC")?;
+    let mut subscription = provider.subscribe_blocks().await
+        .context("Failed to subscribe to new blocks")?;
+    info!("⛓️  Subscribed to new Base blocks");
+
+    loop {
+        let header = subscription.recv().await
+            .context("Block subscription closed")?;
+        debug!("⛓️  New block {}", header.number);
+        if tx.send(Some(header.number)).is_err() {
+            return Ok(());
+        }
+    }
+}
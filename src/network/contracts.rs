@@ -0,0 +1,133 @@
+//! Typed contract bindings generated with alloy's `sol!`
+//!
+//! Calls are encoded and their return values decoded from these interface
+//! definitions instead of hand-built selectors and padded words.
+
+use alloy::{
+    primitives::Address,
+    providers::Provider,
+    rpc::types::eth::TransactionRequest,
+    sol,
+    sol_types::SolCall,
+};
+use anyhow::{Context, Result};
+
+sol! {
+    /// Aerodrome v2 Router
+    interface IAerodromeRouter {
+        struct Route {
+            address from;
+            address to;
+            bool stable;
+            address factory;
+        }
+
+        function swapExactTokensForTokens(
+            uint256 amountIn,
+            uint256 amountOutMin,
+            Route[] calldata routes,
+            address to,
+            uint256 deadline
+        ) external returns (uint256[] memory amounts);
+    }
+
+    /// Uniswap V2 Router, used by the Sepolia execution path
+    interface IUniswapV2Router {
+        function swapExactTokensForTokens(
+            uint256 amountIn,
+            uint256 amountOutMin,
+            address[] calldata path,
+            address to,
+            uint256 deadline
+        ) external returns (uint256[] memory amounts);
+    }
+
+    /// Aerodrome v2 PoolFactory
+    interface IPoolFactory {
+        function getPool(address tokenA, address tokenB, bool stable) external view returns (address pool);
+        /// Fee in basis points, the pool's custom fee or the stable/volatile default
+        function getFee(address pool, bool stable) external view returns (uint256);
+    }
+
+    /// Aerodrome v2 (vAMM/sAMM) pool
+    interface IAerodromePool {
+        struct Observation {
+            uint256 timestamp;
+            uint256 reserve0Cumulative;
+            uint256 reserve1Cumulative;
+        }
+
+        function token0() external view returns (address);
+        function token1() external view returns (address);
+        function stable() external view returns (bool);
+        function factory() external view returns (address);
+        function getReserves() external view returns (uint256 reserve0, uint256 reserve1, uint256 blockTimestampLast);
+        function getAmountOut(uint256 amountIn, address tokenIn) external view returns (uint256 amountOut);
+        function swap(uint256 amount0Out, uint256 amount1Out, address to, bytes calldata data) external;
+        function currentCumulativePrices() external view returns (
+            uint256 reserve0Cumulative,
+            uint256 reserve1Cumulative,
+            uint256 blockTimestamp
+        );
+        function lastObservation() external view returns (Observation memory);
+        function observationLength() external view returns (uint256);
+        function observations(uint256 index) external view returns (
+            uint256 timestamp,
+            uint256 reserve0Cumulative,
+            uint256 reserve1Cumulative
+        );
+    }
+
+    /// Aerodrome Slipstream (concentrated liquidity) pool
+    interface ISlipstreamPool {
+        function slot0() external view returns (
+            uint160 sqrtPriceX96,
+            int24 tick,
+            uint16 observationIndex,
+            uint16 observationCardinality,
+            uint16 observationCardinalityNext,
+            bool unlocked
+        );
+        function liquidity() external view returns (uint128);
+        function tickSpacing() external view returns (int24);
+        /// Fee in hundredths of a basis point
+        function fee() external view returns (uint24);
+        function stakedLiquidity() external view returns (uint128);
+    }
+
+    /// Aerodrome Voter, mapping pools to their gauges
+    interface IVoter {
+        function gauges(address pool) external view returns (address);
+    }
+
+    /// Aerodrome gauge streaming AERO emissions to staked liquidity
+    interface IGauge {
+        function rewardRate() external view returns (uint256);
+        function totalSupply() external view returns (uint256);
+        function periodFinish() external view returns (uint256);
+    }
+
+    /// ERC20 token
+    interface IERC20 {
+        function decimals() external view returns (uint8);
+        function symbol() external view returns (string);
+        function balanceOf(address account) external view returns (uint256);
+        function totalSupply() external view returns (uint256);
+        function transfer(address to, uint256 amount) external returns (bool);
+        function allowance(address owner, address spender) external view returns (uint256);
+        function approve(address spender, uint256 amount) external returns (bool);
+
+        event Transfer(address indexed from, address indexed to, uint256 value);
+    }
+}
+
+/// `eth_call` `call` on `contract` and decode its typed return values
+pub async fn call_contract<C: SolCall>(provider: &dyn Provider, contract: Address, call: C) -> Result<C::Return> {
+    let tx = TransactionRequest::default()
+        .to(contract)
+        .input(call.abi_encode().into());
+    let output = provider.call(&tx).await
+        .with_context(|| format!("Failed to call {}", C::SIGNATURE))?;
+    C::abi_decode_returns(&output, true)
+        .with_context(|| format!("Failed to decode {} result", C::SIGNATURE))
+}
@@ -0,0 +1,150 @@
+//! Gas price oracle with EIP-1559 fee estimation
+
+use alloy::{
+    eips::BlockNumberOrTag,
+    primitives::{keccak256, Bytes, U256},
+    providers::Provider,
+    rpc::types::eth::TransactionRequest,
+    sol_types::SolValue,
+};
+use anyhow::{Context, Result};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use std::time::Instant;
+use tokio::sync::RwLock;
+use tracing::debug;
+use crate::types::GAS_PRICE_ORACLE_BASE;
+
+/// Estimated gas units for a single Aerodrome swap
+pub const ESTIMATED_SWAP_GAS_UNITS: u64 = 150_000;
+/// Gas cost used before the oracle has observed any fee history
+pub const FALLBACK_GAS_COST_USD: Decimal = dec!(0.02);
+
+const FEE_HISTORY_BLOCKS: u64 = 10;
+const PRIORITY_FEE_PERCENTILE: f64 = 50.0;
+/// Weight of each new sample in the baseline fee (EWMA)
+const BASELINE_EWMA_DIVISOR: u128 = 10;
+
+#[derive(Debug, Clone)]
+pub struct GasEstimate {
+    pub base_fee_wei: u128,
+    pub priority_fee_wei: u128,
+    pub updated_at: Instant,
+}
+
+impl GasEstimate {
+    /// Fixed per-gas price, for pricing trades at a hypothetical fee
+    pub fn from_gwei(gwei: Decimal) -> Self {
+        Self {
+            base_fee_wei: (gwei * dec!(1_000_000_000)).to_u128().unwrap_or_default(),
+            priority_fee_wei: 0,
+            updated_at: Instant::now(),
+        }
+    }
+
+    /// Expected per-gas price for the next block (base fee + median tip)
+    pub fn max_fee_per_gas_wei(&self) -> u128 {
+        self.base_fee_wei.saturating_add(self.priority_fee_wei)
+    }
+
+    pub fn gas_price_gwei(&self) -> Decimal {
+        Decimal::from(self.max_fee_per_gas_wei()) / dec!(1_000_000_000)
+    }
+
+    /// Cost in USD of `gas_units` at the current fee, given the ETH price
+    pub fn cost_usd(&self, gas_units: u64, eth_price_usd: Decimal) -> Decimal {
+        let cost_wei = Decimal::from(self.max_fee_per_gas_wei()) * Decimal::from(gas_units);
+        cost_wei / dec!(1_000_000_000_000_000_000) * eth_price_usd
+    }
+}
+
+#[derive(Default)]
+pub struct GasOracle {
+    latest: RwLock<Option<GasEstimate>>,
+    /// Slow-moving average of the per-gas fee, for spike detection
+    baseline_fee_wei: RwLock<Option<u128>>,
+}
+
+impl GasOracle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Refresh base and priority fee estimates from eth_feeHistory
+    pub async fn refresh(&self, provider: &dyn Provider) -> Result<GasEstimate> {
+        let history = provider
+            .get_fee_history(FEE_HISTORY_BLOCKS, BlockNumberOrTag::Latest, &[PRIORITY_FEE_PERCENTILE])
+            .await
+            .context("Failed to fetch fee history")?;
+
+        // The last entry is the base fee of the next (pending) block
+        let base_fee_wei = *history.base_fee_per_gas.last()
+            .ok_or_else(|| anyhow::anyhow!("Empty base fee history"))?;
+
+        let tips: Vec<u128> = history.reward.unwrap_or_default()
+            .iter()
+            .filter_map(|rewards| rewards.first().copied())
+            .collect();
+        let priority_fee_wei = if tips.is_empty() {
+            0
+        } else {
+            let mut sorted = tips;
+            sorted.sort_unstable();
+            sorted[sorted.len() / 2]
+        };
+
+        let estimate = GasEstimate {
+            base_fee_wei,
+            priority_fee_wei,
+            updated_at: Instant::now(),
+        };
+
+        debug!(
+            "⛽ Gas oracle: base fee {} wei, priority fee {} wei ({:.4} gwei)",
+            base_fee_wei, priority_fee_wei, estimate.gas_price_gwei()
+        );
+
+        let fee_wei = estimate.max_fee_per_gas_wei();
+        let mut baseline = self.baseline_fee_wei.write().await;
+        *baseline = Some(match *baseline {
+            Some(prev) => prev - prev / BASELINE_EWMA_DIVISOR + fee_wei / BASELINE_EWMA_DIVISOR,
+            None => fee_wei,
+        });
+
+        *self.latest.write().await = Some(estimate.clone());
+        Ok(estimate)
+    }
+
+    pub async fn latest(&self) -> Option<GasEstimate> {
+        self.latest.read().await.clone()
+    }
+
+    /// USD cost of one swap at the baseline fee, or None before the first refresh
+    pub async fn baseline_swap_cost_usd(&self, eth_price_usd: Decimal) -> Option<Decimal> {
+        let baseline_fee_wei = (*self.baseline_fee_wei.read().await)?;
+        let cost_wei = Decimal::from(baseline_fee_wei) * Decimal::from(ESTIMATED_SWAP_GAS_UNITS);
+        Some(cost_wei / dec!(1_000_000_000_000_000_000) * eth_price_usd)
+    }
+
+    /// USD cost of one swap, falling back to a fixed estimate before the first refresh
+    pub async fn estimate_swap_cost_usd(&self, eth_price_usd: Decimal) -> Decimal {
+        match self.latest().await {
+            Some(estimate) => estimate.cost_usd(ESTIMATED_SWAP_GAS_UNITS, eth_price_usd),
+            None => FALLBACK_GAS_COST_USD,
+        }
+    }
+}
+
+/// L1 data fee in wei that Base charges on top of L2 gas for a transaction
+/// carrying `calldata`, from the GasPriceOracle predeploy. The oracle expects
+/// the full signed transaction, so passing only calldata slightly underestimates.
+pub async fn estimate_l1_fee_wei(provider: &dyn Provider, calldata: &[u8]) -> Result<U256> {
+    let mut data = keccak256("getL1Fee(bytes)")[..4].to_vec();
+    data.extend_from_slice(&(Bytes::copy_from_slice(calldata),).abi_encode_params());
+
+    let tx = TransactionRequest::default()
+        .to(GAS_PRICE_ORACLE_BASE)
+        .input(data.into());
+    let result = provider.call(&tx).await.context("Failed to call getL1Fee")?;
+    U256::abi_decode(&result, true).context("Failed to decode getL1Fee result")
+}
@@ -0,0 +1,177 @@
+//! Request latency per network source
+//!
+//! RPC requests are timed by a transport layer inside the rate limit, and CEX
+//! requests time each HTTP round trip, so rate-limit waits and retry backoff
+//! are not counted. Health checks report a summary per source.
+
+use alloy::{
+    rpc::json_rpc::{RequestPacket, ResponsePacket},
+    transports::{TransportError, TransportFut},
+};
+use lazy_static::lazy_static;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::{Layer, Service};
+use crate::types::{LatencyBucket, LatencySummary};
+
+pub const RPC_LATENCY_SOURCE: &str = "rpc";
+pub const BINANCE_LATENCY_SOURCE: &str = "binance";
+pub const COINBASE_LATENCY_SOURCE: &str = "coinbase";
+
+/// Upper bounds of the histogram buckets; slower requests land in an overflow bucket
+pub const LATENCY_BUCKETS_MS: [u64; 10] = [10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+lazy_static! {
+    /// Shared by every timed RPC and CEX request
+    pub static ref LATENCY: LatencyRecorder = LatencyRecorder::default();
+}
+
+/// Request count per latency bucket for one source
+#[derive(Debug, Clone, Default)]
+pub struct LatencyHistogram {
+    /// One count per `LATENCY_BUCKETS_MS` bound, then the overflow bucket
+    counts: [u64; LATENCY_BUCKETS_MS.len() + 1],
+    errors: u64,
+    total: Duration,
+    max: Duration,
+}
+
+impl LatencyHistogram {
+    pub fn record(&mut self, elapsed: Duration, ok: bool) {
+        let ms = elapsed.as_millis();
+        let bucket = LATENCY_BUCKETS_MS.iter()
+            .position(|bound| ms <= u128::from(*bound))
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.counts[bucket] += 1;
+        if !ok {
+            self.errors += 1;
+        }
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Upper bound of the bucket holding the `quantile` request, capped at the slowest request
+    pub fn quantile_ms(&self, quantile: f64) -> Option<u64> {
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+        let rank = ((quantile.clamp(0.0, 1.0) * count as f64).ceil() as u64).max(1);
+        let max_ms = self.max.as_millis() as u64;
+        let mut seen = 0;
+        for (bucket, bucket_count) in self.counts.iter().enumerate() {
+            seen += bucket_count;
+            if seen >= rank {
+                return Some(LATENCY_BUCKETS_MS.get(bucket).map_or(max_ms, |bound| (*bound).min(max_ms)));
+            }
+        }
+        Some(max_ms)
+    }
+
+    pub fn summary(&self) -> LatencySummary {
+        let count = self.count();
+        LatencySummary {
+            count,
+            errors: self.errors,
+            mean_ms: (count > 0).then(|| self.total.as_millis() as u64 / count),
+            p50_ms: self.quantile_ms(0.5),
+            p95_ms: self.quantile_ms(0.95),
+            p99_ms: self.quantile_ms(0.99),
+            max_ms: (count > 0).then(|| self.max.as_millis() as u64),
+            buckets: self.counts.iter().enumerate()
+                .map(|(bucket, count)| LatencyBucket { le_ms: LATENCY_BUCKETS_MS.get(bucket).copied(), count: *count })
+                .collect(),
+        }
+    }
+}
+
+/// Latency histograms by source name
+#[derive(Default)]
+pub struct LatencyRecorder {
+    sources: Mutex<BTreeMap<String, LatencyHistogram>>,
+}
+
+impl LatencyRecorder {
+    pub fn record(&self, source: &str, elapsed: Duration, ok: bool) {
+        self.sources.lock().unwrap_or_else(|e| e.into_inner())
+            .entry(source.to_string())
+            .or_default()
+            .record(elapsed, ok);
+    }
+
+    /// Await `request`, recording how long it took and whether it failed
+    pub async fn timed<T, E>(&self, source: &str, request: impl Future<Output = Result<T, E>>) -> Result<T, E> {
+        let started = Instant::now();
+        let result = request.await;
+        self.record(source, started.elapsed(), result.is_ok());
+        result
+    }
+
+    /// Summary of every source timed so far
+    pub fn snapshot(&self) -> BTreeMap<String, LatencySummary> {
+        self.sources.lock().unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(source, histogram)| (source.clone(), histogram.summary()))
+            .collect()
+    }
+}
+
+/// Transport layer that records each RPC request's latency in [`LATENCY`]
+/// (a batch counts as one)
+#[derive(Clone)]
+pub struct LatencyLayer {
+    source: &'static str,
+}
+
+impl LatencyLayer {
+    pub fn new(source: &'static str) -> Self {
+        Self { source }
+    }
+}
+
+impl<S> Layer<S> for LatencyLayer {
+    type Service = LatencyService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        LatencyService {
+            inner,
+            source: self.source,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LatencyService<S> {
+    inner: S,
+    source: &'static str,
+}
+
+impl<S> Service<RequestPacket> for LatencyService<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError, Future = TransportFut<'static>>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let source = self.source;
+        let mut inner = self.inner.clone();
+        Box::pin(async move { LATENCY.timed(source, inner.call(request)).await })
+    }
+}
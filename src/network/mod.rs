@@ -2,6 +2,22 @@
 
 pub mod providers;
 pub mod retry;
+pub mod reconciler;
+pub mod gas_oracle;
+pub mod blocks;
+pub mod rate_limit;
+pub mod latency;
+pub mod oracle;
+pub mod contracts;
+pub mod price_source;
 
 pub use providers::*;
 pub use retry::*;
+pub use reconciler::*;
+pub use gas_oracle::*;
+pub use blocks::*;
+pub use rate_limit::*;
+pub use latency::*;
+pub use oracle::*;
+pub use contracts::*;
+pub use price_source::*;
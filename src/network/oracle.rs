@@ -0,0 +1,181 @@
+//! On-chain ETH/USD oracle used as a sanity bound on DEX and CEX prices
+//!
+//! Reads a Chainlink aggregator (`latestRoundData`), by default the ETH/USD
+//! feed on Base. Pyth's Chainlink-compatible adapters work the same way.
+//! Answers older than the configured age are not used.
+//!
+//! `StablePriceOracle` values quote assets other than the USD stables and
+//! WETH (e.g. DOLA or eUSD) from a fixed peg or their own feed.
+
+use alloy::{
+    primitives::{keccak256, Address, I256, U256},
+    providers::Provider,
+    rpc::types::eth::TransactionRequest,
+    sol_types::SolValue,
+};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+use crate::{config::Config, types::StablePrices};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OraclePrice {
+    pub price: Decimal,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OraclePrice {
+    /// Price from a raw aggregator answer, None for non-positive answers
+    pub fn from_answer(answer: i128, decimals: u32, updated_at: u64) -> Option<Self> {
+        if answer <= 0 {
+            return None;
+        }
+        Some(Self {
+            price: Decimal::try_from_i128_with_scale(answer, decimals).ok()?,
+            updated_at: DateTime::from_timestamp(i64::try_from(updated_at).ok()?, 0)?,
+        })
+    }
+
+    pub fn is_fresh(&self, max_age: Duration, now: DateTime<Utc>) -> bool {
+        now - self.updated_at <= max_age
+    }
+}
+
+/// Percentage by which `price` differs from the oracle price
+pub fn oracle_deviation_pct(price: Decimal, oracle_price: Decimal) -> Decimal {
+    if oracle_price <= dec!(0) {
+        return Decimal::MAX;
+    }
+    ((price - oracle_price) / oracle_price * dec!(100)).abs()
+}
+
+fn selector(signature: &str) -> Vec<u8> {
+    keccak256(signature)[..4].to_vec()
+}
+
+/// Latest answer of the Chainlink-compatible aggregator at `feed`
+pub async fn read_chainlink_price(provider: &dyn Provider, feed: Address) -> Result<OraclePrice> {
+    let call = |data: Vec<u8>| TransactionRequest::default().to(feed).input(data.into());
+
+    let decimals = provider.call(&call(selector("decimals()"))).await
+        .context("Failed to call decimals on the oracle feed")?;
+    let decimals = U256::abi_decode(&decimals, true).context("Failed to decode oracle decimals")?;
+
+    let round = provider.call(&call(selector("latestRoundData()"))).await
+        .context("Failed to call latestRoundData on the oracle feed")?;
+    let (_, answer, _, updated_at, _) = <(U256, I256, U256, U256, U256)>::abi_decode(&round, true)
+        .context("Failed to decode oracle round data")?;
+
+    let answer = i128::try_from(answer).map_err(|_| anyhow::anyhow!("Oracle answer out of range"))?;
+    OraclePrice::from_answer(answer, decimals.saturating_to::<u32>(), updated_at.saturating_to::<u64>())
+        .ok_or_else(|| anyhow::anyhow!("Oracle returned an invalid answer: {}", answer))
+}
+
+pub struct PriceOracle {
+    pub feed: Address,
+    pub max_age: Duration,
+    latest: RwLock<Option<OraclePrice>>,
+}
+
+impl PriceOracle {
+    pub fn new(feed: Address, max_age_secs: u64) -> Self {
+        Self {
+            feed,
+            max_age: Duration::seconds(max_age_secs as i64),
+            latest: RwLock::new(None),
+        }
+    }
+
+    /// The configured feed, or None with `ORACLE_CHECK` off
+    pub fn from_config(config: &Config) -> Option<Self> {
+        config.oracle_check.then(|| Self::new(config.oracle_feed_address, config.oracle_max_age_secs))
+    }
+
+    pub async fn refresh(&self, provider: &dyn Provider) -> Result<OraclePrice> {
+        let price = read_chainlink_price(provider, self.feed).await?;
+        debug!("🔮 Oracle ETH/USD ${:.2} (updated {})", price.price, price.updated_at);
+        *self.latest.write().await = Some(price);
+        Ok(price)
+    }
+
+    /// Last answer if it is within the max age
+    pub async fn fresh_price(&self) -> Option<Decimal> {
+        self.latest.read().await
+            .filter(|price| price.is_fresh(self.max_age, Utc::now()))
+            .map(|price| price.price)
+    }
+}
+
+/// How a configured quote asset is valued in USD
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StablePriceSource {
+    /// Fixed USD peg, e.g. `1` for DOLA
+    Fixed(Decimal),
+    /// Chainlink-compatible USD feed for the asset
+    Feed(Address),
+}
+
+/// USD prices of the `STABLE_PRICES` quote assets, refreshed once per cycle
+pub struct StablePriceOracle {
+    sources: HashMap<String, StablePriceSource>,
+    pub max_age: Duration,
+    latest: RwLock<HashMap<String, OraclePrice>>,
+}
+
+impl StablePriceOracle {
+    pub fn new(sources: HashMap<String, StablePriceSource>, max_age_secs: u64) -> Self {
+        Self {
+            sources,
+            max_age: Duration::seconds(max_age_secs as i64),
+            latest: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(config.stable_prices.clone(), config.oracle_max_age_secs)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Reads every feed-backed asset; returns how many reads failed
+    pub async fn refresh(&self, provider: &dyn Provider) -> usize {
+        let mut failures = 0;
+        for (symbol, source) in &self.sources {
+            let StablePriceSource::Feed(feed) = *source else { continue };
+            match read_chainlink_price(provider, feed).await {
+                Ok(price) => {
+                    debug!("🔮 Oracle {}/USD ${:.4} (updated {})", symbol, price.price, price.updated_at);
+                    self.latest.write().await.insert(symbol.clone(), price);
+                }
+                Err(e) => {
+                    warn!("Stable price feed for {} failed: {}", symbol, e);
+                    failures += 1;
+                }
+            }
+        }
+        failures
+    }
+
+    /// Fixed pegs plus every feed answer within the max age
+    pub async fn prices(&self) -> StablePrices {
+        let now = Utc::now();
+        let latest = self.latest.read().await;
+        self.sources.iter()
+            .filter_map(|(symbol, source)| {
+                let price = match source {
+                    StablePriceSource::Fixed(price) => *price,
+                    StablePriceSource::Feed(_) => latest.get(symbol)
+                        .filter(|price| price.is_fresh(self.max_age, now))?
+                        .price,
+                };
+                Some((symbol.clone(), price))
+            })
+            .collect()
+    }
+}
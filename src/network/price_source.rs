@@ -0,0 +1,331 @@
+//! Pluggable CEX reference price sources
+//!
+//! The cycle reads the ETH/USD reference, pair prices and books through the
+//! [`PriceSource`] named by `CEX_SOURCE`. Symbols are Binance-style
+//! (`ETHUSDC`, `CBETHETH`); other venues map them to their own product ids.
+//! Every source's prices and books go through the same range checks.
+
+use anyhow::{Context, Result};
+use rust_decimal::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use crate::{
+    config::Config,
+    errors::{BotError, BotResult},
+    network::{
+        latency::{COINBASE_LATENCY_SOURCE, LATENCY},
+        providers::{
+            check_book_ticker, check_cex_price, check_order_book, cex_http_client, get_binance_book_ticker,
+            get_binance_order_book, get_binance_price_enhanced, parse_book_levels,
+        },
+        rate_limit::BINANCE_RATE_LIMITER,
+        retry::{retry_with_backoff, RetryConfig},
+    },
+    types::{BookTicker, OrderBook},
+};
+
+/// Coinbase Exchange public market data API
+pub const COINBASE_API_URL: &str = "https://api.exchange.coinbase.com";
+
+/// Quote assets recognised at the end of a Binance-style symbol, longest first
+const COINBASE_QUOTE_ASSETS: [&str; 6] = ["USDC", "USDT", "USD", "EUR", "BTC", "ETH"];
+
+/// Boxed future returned by [`PriceSource`] methods, so sources stay object safe
+pub type PriceFuture<'a, T> = Pin<Box<dyn Future<Output = BotResult<T>> + Send + 'a>>;
+
+pub trait PriceSource: Send + Sync {
+    /// Venue name used in logs and price validation errors
+    fn name(&self) -> &str;
+
+    /// Last trade price for `symbol`
+    fn price<'a>(&'a self, symbol: &'a str) -> PriceFuture<'a, Decimal>;
+
+    /// Best bid/ask for `symbol`, for the mid and microprice price bases
+    fn book_ticker<'a>(&'a self, symbol: &'a str) -> PriceFuture<'a, BookTicker>;
+
+    /// Top `levels` of each side of the book, to price the CEX leg for the trade size
+    fn order_book<'a>(&'a self, symbol: &'a str, levels: usize) -> PriceFuture<'a, OrderBook>;
+}
+
+/// Price source selected by `CEX_SOURCE`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CexSource {
+    #[default]
+    Binance,
+    Coinbase,
+    /// Prices from `CEX_FIXTURE_PATH`, for tests and offline runs
+    Fixture,
+}
+
+impl fmt::Display for CexSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CexSource::Binance => "binance",
+            CexSource::Coinbase => "coinbase",
+            CexSource::Fixture => "fixture",
+        })
+    }
+}
+
+impl FromStr for CexSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "binance" => Ok(CexSource::Binance),
+            "coinbase" => Ok(CexSource::Coinbase),
+            "fixture" => Ok(CexSource::Fixture),
+            other => Err(format!("unknown CEX source {:?}", other)),
+        }
+    }
+}
+
+/// The configured price source
+pub fn cex_price_source(config: &Config) -> Result<Arc<dyn PriceSource>> {
+    Ok(match config.cex_source {
+        CexSource::Binance => Arc::new(BinancePriceSource),
+        CexSource::Coinbase => Arc::new(CoinbasePriceSource),
+        CexSource::Fixture => {
+            let path = config.cex_fixture_path.as_deref()
+                .ok_or_else(|| anyhow::anyhow!("CEX_SOURCE=fixture needs CEX_FIXTURE_PATH"))?;
+            Arc::new(FixturePriceSource::from_file(path)?)
+        }
+    })
+}
+
+/// Binance spot tickers
+pub struct BinancePriceSource;
+
+impl PriceSource for BinancePriceSource {
+    fn name(&self) -> &str {
+        "Binance"
+    }
+
+    fn price<'a>(&'a self, symbol: &'a str) -> PriceFuture<'a, Decimal> {
+        Box::pin(get_binance_price_enhanced(symbol))
+    }
+
+    fn book_ticker<'a>(&'a self, symbol: &'a str) -> PriceFuture<'a, BookTicker> {
+        Box::pin(get_binance_book_ticker(symbol))
+    }
+
+    fn order_book<'a>(&'a self, symbol: &'a str, levels: usize) -> PriceFuture<'a, OrderBook> {
+        Box::pin(get_binance_order_book(symbol, levels))
+    }
+}
+
+/// Coinbase product id for a Binance-style symbol, e.g. `ETHUSDC` -> `ETH-USD`.
+/// Coinbase merged its USDC books into USD, so USDC quotes map to USD.
+pub fn coinbase_product_id(symbol: &str) -> Option<String> {
+    let symbol = symbol.to_uppercase();
+    COINBASE_QUOTE_ASSETS.iter().find_map(|quote| {
+        let base = symbol.strip_suffix(quote).filter(|base| !base.is_empty())?;
+        let quote = if *quote == "USDC" { "USD" } else { quote };
+        Some(format!("{}-{}", base, quote))
+    })
+}
+
+/// Coinbase Exchange tickers, sharing the CEX request budget
+pub struct CoinbasePriceSource;
+
+impl CoinbasePriceSource {
+    async fn get_json(product_id: &str, path: &str) -> BotResult<serde_json::Value> {
+        let client = cex_http_client()?;
+        let operation = || async {
+            BINANCE_RATE_LIMITER.acquire().await;
+            let response = LATENCY.timed(COINBASE_LATENCY_SOURCE, client
+                .get(format!("{}/products/{}/{}", COINBASE_API_URL, product_id, path))
+                // Coinbase rejects requests without a User-Agent
+                .header("User-Agent", concat!("aero-arb-mm-bot/", env!("CARGO_PKG_VERSION")))
+                .send())
+                .await
+                .context("HTTP request failed")?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                return Err(anyhow::anyhow!("Coinbase API error: {} - {}", status, body));
+            }
+            response.json::<serde_json::Value>().await.context("Failed to parse JSON response")
+        };
+
+        retry_with_backoff(
+            operation,
+            &RetryConfig {
+                max_attempts: 3,
+                initial_delay_ms: 200,
+                ..Default::default()
+            },
+            &format!("Coinbase {} {} fetch", product_id, path),
+        ).await
+    }
+
+    fn product_id(symbol: &str) -> BotResult<String> {
+        coinbase_product_id(symbol).ok_or_else(|| BotError::DataParsing {
+            context: format!("No Coinbase product for symbol {}", symbol),
+            source: anyhow::anyhow!("unrecognised quote asset"),
+        })
+    }
+}
+
+fn decimal_field(value: &serde_json::Value, name: &str) -> BotResult<Decimal> {
+    value.as_str()
+        .and_then(|s| Decimal::from_str(s).ok())
+        .ok_or_else(|| BotError::DataParsing {
+            context: format!("Missing or invalid '{}' in Coinbase response", name),
+            source: anyhow::anyhow!("{}", value),
+        })
+}
+
+impl PriceSource for CoinbasePriceSource {
+    fn name(&self) -> &str {
+        "Coinbase"
+    }
+
+    fn price<'a>(&'a self, symbol: &'a str) -> PriceFuture<'a, Decimal> {
+        Box::pin(async move {
+            let json = Self::get_json(&Self::product_id(symbol)?, "ticker").await?;
+            check_cex_price(self.name(), symbol, decimal_field(&json["price"], "price")?)
+        })
+    }
+
+    fn book_ticker<'a>(&'a self, symbol: &'a str) -> PriceFuture<'a, BookTicker> {
+        Box::pin(async move {
+            let json = Self::get_json(&Self::product_id(symbol)?, "book?level=1").await?;
+            // Levels are [price, size, num_orders]
+            let book = BookTicker {
+                bid_price: decimal_field(&json["bids"][0][0], "bids")?,
+                bid_qty: decimal_field(&json["bids"][0][1], "bids")?,
+                ask_price: decimal_field(&json["asks"][0][0], "asks")?,
+                ask_qty: decimal_field(&json["asks"][0][1], "asks")?,
+            };
+            check_book_ticker(self.name(), symbol, book)
+        })
+    }
+
+    fn order_book<'a>(&'a self, symbol: &'a str, levels: usize) -> PriceFuture<'a, OrderBook> {
+        Box::pin(async move {
+            // Level 2 aggregates the top 50 levels per side
+            let json = Self::get_json(&Self::product_id(symbol)?, "book?level=2").await?;
+            let levels = |side: &str| parse_book_levels(&json[side], levels).map_err(|e| BotError::DataParsing {
+                context: format!("Invalid '{}' in Coinbase book", side),
+                source: e,
+            });
+            let book = OrderBook { bids: levels("bids")?, asks: levels("asks")? };
+            check_order_book(self.name(), symbol, book)
+        })
+    }
+}
+
+/// Contents of a `CEX_FIXTURE_PATH` file
+#[derive(Debug, Default, Deserialize)]
+struct FixtureFile {
+    #[serde(default)]
+    prices: HashMap<String, Decimal>,
+    #[serde(default)]
+    books: HashMap<String, BookTicker>,
+    #[serde(default)]
+    depths: HashMap<String, OrderBook>,
+}
+
+/// Fixed prices and books, settable while the bot runs. Symbols without a
+/// fixture fail like an unreachable venue.
+#[derive(Default)]
+pub struct FixturePriceSource {
+    prices: RwLock<HashMap<String, Decimal>>,
+    books: RwLock<HashMap<String, BookTicker>>,
+    depths: RwLock<HashMap<String, OrderBook>>,
+}
+
+impl FixturePriceSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load `{"prices": {"ETHUSDC": "3000"}, "books": {"ETHUSDC": {"bid_price": ...}},
+    /// "depths": {"ETHUSDC": {"bids": [{"price": ..., "qty": ...}], "asks": [...]}}}`
+    pub fn from_file(path: &str) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read CEX fixture {}", path))?;
+        let fixture: FixtureFile = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid CEX fixture {}", path))?;
+        Ok(Self {
+            prices: RwLock::new(fixture.prices),
+            books: RwLock::new(fixture.books),
+            depths: RwLock::new(fixture.depths),
+        })
+    }
+
+    pub fn with_price(self, symbol: &str, price: Decimal) -> Self {
+        self.set_price(symbol, price);
+        self
+    }
+
+    pub fn with_book(self, symbol: &str, book: BookTicker) -> Self {
+        self.set_book(symbol, book);
+        self
+    }
+
+    pub fn with_order_book(self, symbol: &str, book: OrderBook) -> Self {
+        self.set_order_book(symbol, book);
+        self
+    }
+
+    pub fn set_price(&self, symbol: &str, price: Decimal) {
+        self.prices.write().unwrap_or_else(|e| e.into_inner()).insert(symbol.to_string(), price);
+    }
+
+    pub fn set_book(&self, symbol: &str, book: BookTicker) {
+        self.books.write().unwrap_or_else(|e| e.into_inner()).insert(symbol.to_string(), book);
+    }
+
+    pub fn set_order_book(&self, symbol: &str, book: OrderBook) {
+        self.depths.write().unwrap_or_else(|e| e.into_inner()).insert(symbol.to_string(), book);
+    }
+
+    fn missing(symbol: &str, what: &str) -> BotError {
+        BotError::Network {
+            message: format!("No fixture {} for {}", what, symbol),
+            source: None,
+            retry_count: 0,
+        }
+    }
+}
+
+impl PriceSource for FixturePriceSource {
+    fn name(&self) -> &str {
+        "Fixture"
+    }
+
+    fn price<'a>(&'a self, symbol: &'a str) -> PriceFuture<'a, Decimal> {
+        let price = self.prices.read().unwrap_or_else(|e| e.into_inner()).get(symbol).copied();
+        Box::pin(async move {
+            let price = price.ok_or_else(|| Self::missing(symbol, "price"))?;
+            check_cex_price(self.name(), symbol, price)
+        })
+    }
+
+    fn book_ticker<'a>(&'a self, symbol: &'a str) -> PriceFuture<'a, BookTicker> {
+        let book = self.books.read().unwrap_or_else(|e| e.into_inner()).get(symbol).copied();
+        Box::pin(async move {
+            let book = book.ok_or_else(|| Self::missing(symbol, "book"))?;
+            check_book_ticker(self.name(), symbol, book)
+        })
+    }
+
+    fn order_book<'a>(&'a self, symbol: &'a str, levels: usize) -> PriceFuture<'a, OrderBook> {
+        let book = self.depths.read().unwrap_or_else(|e| e.into_inner()).get(symbol).map(|book| OrderBook {
+            bids: book.bids.iter().take(levels).copied().collect(),
+            asks: book.asks.iter().take(levels).copied().collect(),
+        });
+        Box::pin(async move {
+            let book = book.ok_or_else(|| Self::missing(symbol, "order book"))?;
+            check_order_book(self.name(), symbol, book)
+        })
+    }
+}
@@ -2,6 +2,7 @@
 
 use alloy::{
     providers::{Provider, ProviderBuilder},
+    rpc::client::ClientBuilder,
 };
 use anyhow::{Context, Result};
 use rust_decimal::prelude::*;
@@ -11,22 +12,45 @@ use std::sync::Arc;
 use std::time::Duration;
 use tracing::{info, warn};
 use crate::{
-    config::Config,
+    config::{Config, PUBLIC_BASE_RPC_URL},
     errors::{BotError, BotResult},
-    network::retry::{retry_with_backoff, RetryConfig},
+    network::{
+        latency::{LatencyLayer, BINANCE_LATENCY_SOURCE, LATENCY, RPC_LATENCY_SOURCE},
+        rate_limit::{RateLimitLayer, BINANCE_RATE_LIMITER, RPC_RATE_LIMITER},
+        retry::{retry_with_backoff, RetryConfig},
+    },
+    types::{BookLevel, BookTicker, OrderBook},
     ConcreteProvider,
 };
 
+/// Binance symbol for the ETH/USD reference price used for gas, inventory and volatility
+pub const ETH_USD_CEX_SYMBOL: &str = "ETHUSDC";
+
+/// Base mainnet RPC: Alchemy, or the public endpoint in watch-only mode
+pub fn mainnet_rpc_url(config: &Config) -> Result<String> {
+    match (&config.alchemy_api_key, config.watch_only) {
+        (Some(alchemy_key), false) => Ok(format!("https://base-mainnet.g.alchemy.com/v2/{}", alchemy_key)),
+        (None, false) => Err(anyhow::anyhow!("ALCHEMY_API_KEY is required unless WATCH_ONLY=true")),
+        (_, true) => Ok(PUBLIC_BASE_RPC_URL.to_string()),
+    }
+}
+
+/// HTTP provider for `rpc_url` that shares the process-wide RPC rate limit
+pub fn http_provider(rpc_url: &str) -> Result<ConcreteProvider> {
+    let client = ClientBuilder::default()
+        .layer(RateLimitLayer::new(RPC_RATE_LIMITER.clone()))
+        .layer(LatencyLayer::new(RPC_LATENCY_SOURCE))
+        .http(rpc_url.parse()?);
+    Ok(ProviderBuilder::new().on_client(client).boxed())
+}
+
 pub async fn setup_mainnet_provider(config: &Config) -> Result<Arc<ConcreteProvider>> {
-    let alchemy_key = config.alchemy_api_key.as_ref()
-        .expect("ALCHEMY_API_KEY is required");
-    let rpc_url = format!("https://base-mainnet.g.alchemy.com/v2/{}", alchemy_key);
+    let rpc_url = mainnet_rpc_url(config)?;
+    if config.watch_only {
+        info!("👀 Watch-only mode: using public Base RPC {}", PUBLIC_BASE_RPC_URL);
+    }
     
-    let provider: Arc<ConcreteProvider> = Arc::new(
-        ProviderBuilder::new()
-            .on_http(rpc_url.parse()?)
-            .boxed()
-    );
+    let provider: Arc<ConcreteProvider> = Arc::new(http_provider(&rpc_url)?);
     
     info!("🔗 Testing connection to Base network...");
     let block = retry_with_backoff(
@@ -51,8 +75,9 @@ pub async fn setup_mainnet_provider(config: &Config) -> Result<Arc<ConcreteProvi
     Ok(provider)
 }
 
-pub async fn get_binance_price_enhanced() -> BotResult<Decimal> {
-    let client = reqwest::Client::builder()
+/// HTTP client for CEX price requests
+pub(crate) fn cex_http_client() -> BotResult<reqwest::Client> {
+    reqwest::Client::builder()
         .timeout(Duration::from_secs(3))
         .build()
         .map_err(|e| {
@@ -62,12 +87,119 @@ pub async fn get_binance_price_enhanced() -> BotResult<Decimal> {
                 source: Some(e.into()),
                 retry_count: 0,
             }
-        })?;
+        })
+}
+
+/// Reject non-positive prices, and ETH/USD reference prices outside a sane range
+pub fn check_cex_price(feed: &str, symbol: &str, price: Decimal) -> BotResult<Decimal> {
+    let out_of_range = symbol == ETH_USD_CEX_SYMBOL && (price < dec!(100) || price > dec!(100000));
+    if price <= dec!(0) || out_of_range {
+        warn!("⚠️ Invalid price received from {}: {}", feed, price);
+        return Err(BotError::PriceValidation {
+            feed: format!("{} {}", feed, symbol),
+            price,
+            reason: "Price outside valid range".to_string(),
+        });
+    }
+    Ok(price)
+}
+
+/// Reject empty and crossed books
+pub fn check_book_ticker(feed: &str, symbol: &str, book: BookTicker) -> BotResult<BookTicker> {
+    if book.bid_price <= dec!(0) || book.ask_price < book.bid_price {
+        return Err(BotError::PriceValidation {
+            feed: format!("{} {} book", feed, symbol),
+            price: book.bid_price,
+            reason: format!("Crossed or empty book (bid {}, ask {})", book.bid_price, book.ask_price),
+        });
+    }
+    Ok(book)
+}
+
+/// Reject order books with an empty side or crossed best prices
+pub fn check_order_book(feed: &str, symbol: &str, book: OrderBook) -> BotResult<OrderBook> {
+    let (Some(best_bid), Some(best_ask)) = (book.bids.first(), book.asks.first()) else {
+        return Err(BotError::PriceValidation {
+            feed: format!("{} {} depth", feed, symbol),
+            price: dec!(0),
+            reason: "Empty order book side".to_string(),
+        });
+    };
+    if best_bid.price <= dec!(0) || best_ask.price < best_bid.price {
+        return Err(BotError::PriceValidation {
+            feed: format!("{} {} depth", feed, symbol),
+            price: best_bid.price,
+            reason: format!("Crossed book (bid {}, ask {})", best_bid.price, best_ask.price),
+        });
+    }
+    Ok(book)
+}
+
+/// `[["price", "qty", ...], ...]` levels from a depth response
+pub(crate) fn parse_book_levels(levels: &serde_json::Value, max_levels: usize) -> Result<Vec<BookLevel>> {
+    levels.as_array()
+        .ok_or_else(|| anyhow::anyhow!("Missing order book levels"))?
+        .iter()
+        .take(max_levels)
+        .map(|level| {
+            let field = |i: usize| -> Result<Decimal> {
+                let value = level[i].as_str()
+                    .ok_or_else(|| anyhow::anyhow!("Invalid order book level {}", level))?;
+                Decimal::from_str(value).with_context(|| format!("Failed to parse order book level {}", level))
+            };
+            Ok(BookLevel { price: field(0)?, qty: field(1)? })
+        })
+        .collect()
+}
+
+/// Top `levels` bids and asks for `symbol`, to size the CEX leg against real depth
+pub async fn get_binance_order_book(symbol: &str, levels: usize) -> BotResult<OrderBook> {
+    let client = cex_http_client()?;
+
+    let operation = || async {
+        BINANCE_RATE_LIMITER.acquire().await;
+        let response = LATENCY.timed(BINANCE_LATENCY_SOURCE, client
+            .get(format!("https://api.binance.com/api/v3/depth?symbol={}&limit={}", symbol, levels))
+            .send())
+            .await
+            .context("HTTP request failed")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Binance API error: {} - {}", status, body));
+        }
+
+        let json: serde_json::Value = response.json().await
+            .context("Failed to parse JSON response")?;
+        Ok(OrderBook {
+            bids: parse_book_levels(&json["bids"], levels)?,
+            asks: parse_book_levels(&json["asks"], levels)?,
+        })
+    };
+
+    let book = retry_with_backoff(
+        operation,
+        &RetryConfig {
+            max_attempts: 3,
+            initial_delay_ms: 200,
+            ..Default::default()
+        },
+        &format!("Binance {} order book fetch", symbol),
+    ).await?;
+
+    check_order_book("Binance", symbol, book)
+}
+
+/// Latest Binance price for `symbol`, range-checked for the ETH/USD reference
+pub async fn get_binance_price_enhanced(symbol: &str) -> BotResult<Decimal> {
+    let client = cex_http_client()?;
     
     let operation = || async {
-        let response = client
-            .get("https://api.binance.com/api/v3/ticker/price?symbol=ETHUSDC")
-            .send()
+        BINANCE_RATE_LIMITER.acquire().await;
+        let response = LATENCY.timed(BINANCE_LATENCY_SOURCE, client
+            .get(format!("https://api.binance.com/api/v3/ticker/price?symbol={}", symbol))
+            .send())
             .await
             .context("HTTP request failed")?;
             
@@ -102,17 +234,55 @@ pub async fn get_binance_price_enhanced() -> BotResult<Decimal> {
             initial_delay_ms: 200,
             ..Default::default()
         },
-        "Binance price fetch",
+        &format!("Binance {} price fetch", symbol),
     ).await?;
     
-    if price <= dec!(0) || price < dec!(100) || price > dec!(100000) {
-        warn!("⚠️ Invalid price received from Binance: {}", price);
-        return Err(BotError::PriceValidation {
-            source: Box::new(std::io::Error::new(std::io::ErrorKind::Other, "Binance price validation failed")),
-            price,
-            reason: "Price outside valid range".to_string(),
-        });
-    }
-    
-    Ok(price)
+    check_cex_price("Binance", symbol, price)
+}
+
+/// Best bid/ask for `symbol`, for the mid and microprice price bases
+pub async fn get_binance_book_ticker(symbol: &str) -> BotResult<BookTicker> {
+    let client = cex_http_client()?;
+
+    let operation = || async {
+        BINANCE_RATE_LIMITER.acquire().await;
+        let response = LATENCY.timed(BINANCE_LATENCY_SOURCE, client
+            .get(format!("https://api.binance.com/api/v3/ticker/bookTicker?symbol={}", symbol))
+            .send())
+            .await
+            .context("HTTP request failed")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("Binance API error: {} - {}", status, body));
+        }
+
+        let json: serde_json::Value = response.json().await
+            .context("Failed to parse JSON response")?;
+        let field = |name: &str| -> Result<Decimal> {
+            let value = json[name].as_str()
+                .ok_or_else(|| anyhow::anyhow!("Missing '{}' field in response", name))?;
+            Decimal::from_str(value).with_context(|| format!("Failed to parse {}", name))
+        };
+
+        Ok(BookTicker {
+            bid_price: field("bidPrice")?,
+            bid_qty: field("bidQty")?,
+            ask_price: field("askPrice")?,
+            ask_qty: field("askQty")?,
+        })
+    };
+
+    let book = retry_with_backoff(
+        operation,
+        &RetryConfig {
+            max_attempts: 3,
+            initial_delay_ms: 200,
+            ..Default::default()
+        },
+        &format!("Binance {} book ticker fetch", symbol),
+    ).await?;
+
+    check_book_ticker("Binance", symbol, book)
 }
@@ -0,0 +1,169 @@
+//! Token-bucket rate limits for the Alchemy RPC and the Binance API
+//!
+//! Each endpoint has one process-wide bucket, so pools fetched in the same
+//! cycle share a budget and wait for tokens instead of bursting into 429s.
+//! RPC providers take the limit as a transport layer; Binance fetches call
+//! `acquire` before each request. Starting a bot resets both buckets to its
+//! config's rates.
+
+use alloy::{
+    rpc::json_rpc::{RequestPacket, ResponsePacket},
+    transports::{TransportError, TransportFut},
+};
+use lazy_static::lazy_static;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::{Layer, Service};
+use tracing::debug;
+use crate::config::{Config, CONFIG};
+
+lazy_static! {
+    /// Shared by every Alchemy (or public RPC) provider
+    pub static ref RPC_RATE_LIMITER: RateLimiter = RateLimiter::per_second(CONFIG.get().rpc_requests_per_sec);
+    /// Shared by the Binance price and book ticker fetches
+    pub static ref BINANCE_RATE_LIMITER: RateLimiter = RateLimiter::per_second(CONFIG.get().binance_requests_per_sec);
+}
+
+struct TokenBucket {
+    tokens_per_sec: f64,
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// A full bucket of `requests_per_sec`; None when 0 disables the limit
+    fn per_second(requests_per_sec: u32) -> Option<Self> {
+        if requests_per_sec == 0 {
+            return None;
+        }
+        let rate = f64::from(requests_per_sec);
+        Some(Self {
+            tokens_per_sec: rate,
+            capacity: rate,
+            tokens: rate,
+            last_refill: Instant::now(),
+        })
+    }
+
+    /// Take a token, or return how long until one is available
+    fn take(&mut self) -> Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.tokens_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.tokens_per_sec))
+        }
+    }
+}
+
+/// Requests-per-second budget with one second of burst. Clones share the bucket.
+#[derive(Clone)]
+pub struct RateLimiter {
+    /// None when unlimited
+    bucket: Arc<Mutex<Option<TokenBucket>>>,
+}
+
+impl RateLimiter {
+    /// Limit to `requests_per_sec`; 0 disables the limit
+    pub fn per_second(requests_per_sec: u32) -> Self {
+        Self { bucket: Arc::new(Mutex::new(TokenBucket::per_second(requests_per_sec))) }
+    }
+
+    pub fn unlimited() -> Self {
+        Self::per_second(0)
+    }
+
+    /// Change the budget of this limiter and its clones, starting from a full bucket
+    pub fn set_rate(&self, requests_per_sec: u32) {
+        *self.bucket.lock().unwrap_or_else(|e| e.into_inner()) = TokenBucket::per_second(requests_per_sec);
+    }
+
+    /// Take a token if one is available now
+    pub fn try_acquire(&self) -> bool {
+        self.take().is_ok()
+    }
+
+    /// Wait for a token
+    pub async fn acquire(&self) {
+        while let Err(wait) = self.take() {
+            debug!("Rate limit reached, waiting {:?}", wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn take(&self) -> Result<(), Duration> {
+        match self.bucket.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            Some(bucket) => bucket.take(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Apply `config`'s request budgets to the process-wide limiters
+pub fn configure_rate_limits(config: &Config) {
+    RPC_RATE_LIMITER.set_rate(config.rpc_requests_per_sec);
+    BINANCE_RATE_LIMITER.set_rate(config.binance_requests_per_sec);
+}
+
+/// Transport layer that waits on a [`RateLimiter`] before each RPC request
+/// (a batch counts as one)
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    limiter: RateLimiter,
+}
+
+impl RateLimitLayer {
+    pub fn new(limiter: RateLimiter) -> Self {
+        Self { limiter }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: RateLimiter,
+}
+
+impl<S> Service<RequestPacket> for RateLimitService<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError, Future = TransportFut<'static>>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let limiter = self.limiter.clone();
+        let mut inner = self.inner.clone();
+        Box::pin(async move {
+            limiter.acquire().await;
+            inner.call(request).await
+        })
+    }
+}
@@ -0,0 +1,105 @@
+//! Fills reconciler client for posting executions to an external accounting endpoint
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::{info, warn};
+use crate::{
+    config::Config,
+    errors::BotResult,
+    network::retry::{retry_with_backoff, RetryConfig},
+    types::TradeExecution,
+};
+
+/// Acknowledgment returned by the reconciler for a posted execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationAck {
+    pub execution_id: String,
+    pub accepted: bool,
+    #[serde(default)]
+    pub reference: Option<String>,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default = "Utc::now")]
+    pub received_at: DateTime<Utc>,
+}
+
+/// The reconciler answered but did not accept the execution
+#[derive(Debug, thiserror::Error)]
+#[error("reconciler rejected execution {}: {}", .0.execution_id, .0.message.as_deref().unwrap_or("no reason given"))]
+pub struct ReconciliationRejected(pub ReconciliationAck);
+
+pub struct ReconciliationClient {
+    client: reqwest::Client,
+    endpoint: String,
+    api_key: Option<String>,
+}
+
+impl ReconciliationClient {
+    /// Build a client if a reconciler endpoint is configured
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        let Some(endpoint) = config.reconciler_url.clone() else {
+            return Ok(None);
+        };
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .context("Failed to build reconciler HTTP client")?;
+
+        info!("🧾 Fills reconciler enabled: {}", endpoint);
+        Ok(Some(Self {
+            client,
+            endpoint,
+            api_key: config.reconciler_api_key.clone(),
+        }))
+    }
+
+    /// Post a completed execution and parse the reconciler acknowledgment
+    pub async fn submit_execution(&self, execution: &TradeExecution) -> Result<ReconciliationAck> {
+        let mut request = self.client.post(&self.endpoint).json(execution);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await
+            .context("Reconciler request failed")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            warn!("⚠️ Reconciler returned error status {}: {}", status, body);
+            return Err(anyhow::anyhow!("Reconciler error: {} - {}", status, body));
+        }
+
+        let ack: ReconciliationAck = response.json().await
+            .context("Failed to parse reconciler acknowledgment")?;
+
+        if ack.execution_id != execution.id {
+            return Err(anyhow::anyhow!(
+                "Reconciler acknowledged {} but {} was submitted",
+                ack.execution_id, execution.id
+            ));
+        }
+
+        Ok(ack)
+    }
+
+    /// Submit an execution until the reconciler accepts it. Failed requests
+    /// and rejections are retried with backoff; when the last attempt was
+    /// rejected, the error's source is [`ReconciliationRejected`].
+    pub async fn reconcile(&self, execution: &TradeExecution) -> BotResult<ReconciliationAck> {
+        retry_with_backoff(
+            || async move {
+                let ack = self.submit_execution(execution).await?;
+                if !ack.accepted {
+                    return Err(ReconciliationRejected(ack).into());
+                }
+                Ok(ack)
+            },
+            &RetryConfig::default(),
+            "reconciliation",
+        ).await
+    }
+}
@@ -0,0 +1,150 @@
+//! On-disk cache of validated pool metadata for fast cold starts
+
+use alloy::primitives::Address;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{info, warn};
+use crate::{
+    config::Config,
+    pools::{default_pool_fee, initialize_and_validate_pools},
+    types::{PoolInfo, PoolKind, TokenPair},
+    ConcreteProvider,
+};
+
+const POOL_CACHE_PATH: &str = "output/cache/pools.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedPool {
+    name: String,
+    address: Address,
+    token0: Address,
+    token1: Address,
+    pair: TokenPair,
+    is_stable: bool,
+    tick_spacing: Option<i32>,
+    /// Absent from caches written before fees were read on-chain
+    #[serde(default)]
+    fee: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PoolCache {
+    network: String,
+    saved_at: DateTime<Utc>,
+    pools: Vec<CachedPool>,
+}
+
+impl From<&PoolInfo> for CachedPool {
+    fn from(pool: &PoolInfo) -> Self {
+        Self {
+            name: pool.name.clone(),
+            address: pool.address,
+            token0: pool.token0,
+            token1: pool.token1,
+            pair: pool.pair.clone(),
+            is_stable: pool.is_stable,
+            tick_spacing: match pool.kind {
+                PoolKind::Concentrated { tick_spacing } => Some(tick_spacing),
+                PoolKind::Basic => None,
+            },
+            fee: Some(pool.fee),
+        }
+    }
+}
+
+impl From<CachedPool> for PoolInfo {
+    fn from(pool: CachedPool) -> Self {
+        let kind = match pool.tick_spacing {
+            Some(tick_spacing) => PoolKind::Concentrated { tick_spacing },
+            None => PoolKind::Basic,
+        };
+        Self {
+            address: pool.address,
+            name: pool.name,
+            token0: pool.token0,
+            token1: pool.token1,
+            pair: pool.pair,
+            is_stable: pool.is_stable,
+            kind,
+            fee: pool.fee.unwrap_or_else(|| default_pool_fee(kind, pool.is_stable)),
+            min_liquidity: rust_decimal_macros::dec!(1000),
+            last_update: Instant::now(),
+        }
+    }
+}
+
+pub fn load_cached_pools(network: &str) -> Option<Vec<PoolInfo>> {
+    let contents = std::fs::read_to_string(POOL_CACHE_PATH).ok()?;
+    let cache: PoolCache = match serde_json::from_str(&contents) {
+        Ok(cache) => cache,
+        Err(e) => {
+            warn!("Ignoring unreadable pool cache: {}", e);
+            return None;
+        }
+    };
+
+    if cache.network != network || cache.pools.is_empty() {
+        return None;
+    }
+
+    info!("📦 Loaded {} pools from cache (saved {})", cache.pools.len(), cache.saved_at);
+    Some(cache.pools.into_iter().map(PoolInfo::from).collect())
+}
+
+pub fn save_pool_cache(network: &str, pools: &[PoolInfo]) -> Result<()> {
+    let cache = PoolCache {
+        network: network.to_string(),
+        saved_at: Utc::now(),
+        pools: pools.iter().map(CachedPool::from).collect(),
+    };
+
+    std::fs::write(POOL_CACHE_PATH, serde_json::to_string_pretty(&cache)?)
+        .context("Failed to write pool cache")?;
+    Ok(())
+}
+
+/// Return cached pools immediately when available and re-validate them in the
+/// background; otherwise validate on-chain and populate the cache.
+pub async fn initialize_pools_with_cache(
+    provider: &Arc<ConcreteProvider>,
+    config: &Config,
+) -> Result<Vec<PoolInfo>> {
+    let cached = load_cached_pools(&config.network)
+        .map(|pools| pools.into_iter().filter(|p| config.pool_enabled(&p.name)).collect::<Vec<_>>())
+        .filter(|pools| !pools.is_empty());
+    if let Some(cached) = cached {
+        let provider = provider.clone();
+        let config = config.clone();
+        let cached_pairs: Vec<(Address, TokenPair)> = cached.iter().map(|p| (p.address, p.pair.clone())).collect();
+
+        tokio::spawn(async move {
+            match initialize_and_validate_pools(&provider, &config).await {
+                Ok(validated) => {
+                    let changed = validated.len() != cached_pairs.len()
+                        || validated.iter().any(|p| !cached_pairs.contains(&(p.address, p.pair.clone())));
+                    if changed {
+                        warn!("⚠️ Pool re-validation differs from cache; refreshed cache takes effect on next restart");
+                    } else {
+                        info!("✅ Background pool re-validation matches cache");
+                    }
+                    if let Err(e) = save_pool_cache(&config.network, &validated) {
+                        warn!("Failed to refresh pool cache: {}", e);
+                    }
+                }
+                Err(e) => warn!("⚠️ Background pool re-validation failed: {}", e),
+            }
+        });
+
+        return Ok(cached);
+    }
+
+    let validated = initialize_and_validate_pools(provider, config).await?;
+    if let Err(e) = save_pool_cache(&config.network, &validated) {
+        warn!("Failed to save pool cache: {}", e);
+    }
+    Ok(validated)
+}
@@ -0,0 +1,64 @@
+//! Aerodrome Slipstream (concentrated liquidity) pool adapter
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+};
+use anyhow::{Context, Result};
+use crate::network::{call_contract, ISlipstreamPool};
+
+/// Current price state read from a CL pool's slot0()
+#[derive(Debug, Clone, Copy)]
+pub struct ClPoolState {
+    pub sqrt_price_x96: U256,
+    pub tick: i32,
+    pub liquidity: u128,
+}
+
+pub async fn get_cl_tick_spacing(provider: &dyn Provider, pool: Address) -> Result<i32> {
+    Ok(call_contract(provider, pool, ISlipstreamPool::tickSpacingCall {}).await?._0)
+}
+
+pub async fn get_cl_pool_state(provider: &dyn Provider, pool: Address) -> Result<ClPoolState> {
+    let slot0 = call_contract(provider, pool, ISlipstreamPool::slot0Call {}).await
+        .context("Failed to read slot0")?;
+    let liquidity = call_contract(provider, pool, ISlipstreamPool::liquidityCall {}).await
+        .context("Failed to read liquidity")?;
+
+    Ok(ClPoolState {
+        sqrt_price_x96: U256::from(slot0.sqrtPriceX96),
+        tick: slot0.tick,
+        liquidity: liquidity._0,
+    })
+}
+
+/// Virtual reserves of the active liquidity at the current price.
+///
+/// For in-range liquidity L at sqrt price P, the pool behaves locally like a
+/// constant product pool with x = L / sqrt(P) and y = L * sqrt(P), so these
+/// amounts give both the spot price (y / x) and the effective depth.
+pub fn cl_virtual_reserves(state: &ClPoolState) -> Result<(U256, U256)> {
+    if state.sqrt_price_x96.is_zero() {
+        return Err(anyhow::anyhow!("Pool is not initialized (sqrtPriceX96 is zero)"));
+    }
+
+    let q96 = U256::from(1u8) << 96;
+    let liquidity = U256::from(state.liquidity);
+
+    // u128 * 2^96 fits in 256 bits, but u128 * uint160 may not: multiply the
+    // whole and fractional Q96 parts of the sqrt price separately
+    let reserve0 = liquidity * q96 / state.sqrt_price_x96;
+    let whole = state.sqrt_price_x96 >> 96;
+    let fraction = state.sqrt_price_x96 & (q96 - U256::from(1u8));
+    let reserve1 = liquidity.checked_mul(whole)
+        .and_then(|high| high.checked_add((liquidity * fraction) >> 96))
+        .ok_or_else(|| anyhow::anyhow!("Virtual reserves of liquidity {} overflow", state.liquidity))?;
+
+    Ok((reserve0, reserve1))
+}
+
+/// Fetch virtual reserves for a CL pool, shaped like getReserves() output
+pub async fn get_cl_pool_reserves(provider: &dyn Provider, pool: Address) -> Result<(U256, U256)> {
+    let state = get_cl_pool_state(provider, pool).await?;
+    cl_virtual_reserves(&state)
+}
@@ -0,0 +1,151 @@
+//! Pool discovery through the Aerodrome PoolFactory
+//!
+//! Looks up the vAMM and sAMM pool for WETH against each configured stable
+//! with `getPool(tokenA, tokenB, stable)`, validates it, and keeps pools whose
+//! TVL clears the configured threshold. Slipstream CL pools are not covered.
+
+use alloy::{
+    primitives::Address,
+    providers::Provider,
+};
+use anyhow::Result;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+use crate::{
+    config::Config,
+    network::{call_contract, IPoolFactory},
+    pools::{analyze_liquidity_depth, calculate_pool_price_safe_with_retry, validate_pool_with_retry},
+    types::{PoolInfo, AERODROME_POOL_FACTORY_MAINNET, USDBC_MAINNET, USDC_MAINNET, WETH_MAINNET},
+    ConcreteProvider,
+};
+
+/// Stables discovery can pair with WETH, by symbol
+pub const DISCOVERY_STABLES: &[(&str, Address)] = &[
+    ("USDC", USDC_MAINNET),
+    ("USDbC", USDBC_MAINNET),
+];
+
+/// Call `getPool(address,address,bool)` on the PoolFactory; zero if no pool exists
+pub async fn get_factory_pool(
+    provider: &dyn Provider,
+    factory: Address,
+    token_a: Address,
+    token_b: Address,
+    stable: bool,
+) -> Result<Address> {
+    let call = IPoolFactory::getPoolCall { tokenA: token_a, tokenB: token_b, stable };
+    Ok(call_contract(provider, factory, call).await?.pool)
+}
+
+/// Static pools followed by discovered pools not already in the static set
+pub fn merge_pools(base: &[PoolInfo], discovered: &[PoolInfo]) -> Vec<PoolInfo> {
+    base.iter()
+        .chain(discovered.iter().filter(|d| !base.iter().any(|b| b.address == d.address)))
+        .cloned()
+        .collect()
+}
+
+pub struct PoolDiscovery {
+    stables: Vec<(&'static str, Address)>,
+    min_tvl_usd: Decimal,
+    interval: Duration,
+    last_refresh: Option<Instant>,
+    discovered: Vec<PoolInfo>,
+}
+
+impl PoolDiscovery {
+    /// None unless discovery is enabled on mainnet (the factory is mainnet-only)
+    pub fn from_config(config: &Config) -> Option<Self> {
+        if !config.pool_discovery_enabled || config.network != "mainnet" {
+            return None;
+        }
+
+        let stables = DISCOVERY_STABLES.iter()
+            .filter(|(symbol, _)| config.discovery_stables.iter().any(|s| s == symbol))
+            .copied()
+            .collect();
+
+        Some(Self {
+            stables,
+            min_tvl_usd: config.pool_discovery_min_tvl_usd,
+            interval: Duration::from_secs(config.pool_discovery_interval_secs),
+            last_refresh: None,
+            discovered: Vec::new(),
+        })
+    }
+
+    pub fn due(&self) -> bool {
+        self.last_refresh.is_none_or(|last| last.elapsed() >= self.interval)
+    }
+
+    pub fn pools(&self) -> &[PoolInfo] {
+        &self.discovered
+    }
+
+    /// Query the factory and replace the discovered set, returning true if it changed
+    pub async fn refresh(&mut self, provider: &Arc<ConcreteProvider>, config: &Config) -> Result<bool> {
+        self.last_refresh = Some(Instant::now());
+        let mut discovered = Vec::new();
+
+        for (symbol, stable_token) in &self.stables {
+            for stable in [false, true] {
+                let name = format!("{}AMM-WETH/{}", if stable { "s" } else { "v" }, symbol);
+                if !config.pool_enabled(&name) {
+                    continue;
+                }
+
+                let address = get_factory_pool(
+                    provider.as_ref(),
+                    AERODROME_POOL_FACTORY_MAINNET,
+                    WETH_MAINNET,
+                    *stable_token,
+                    stable,
+                ).await?;
+                if address == Address::ZERO {
+                    continue;
+                }
+
+                let pool = match validate_pool_with_retry(
+                    provider, &name, address, false, Some(WETH_MAINNET), &[*stable_token], config,
+                ).await {
+                    Ok(pool) => pool,
+                    Err(e) => {
+                        warn!("🔭 Discovered {} ({}) failed validation: {}", name, address, e);
+                        continue;
+                    }
+                };
+
+                let tvl_usd = match self.pool_tvl_usd(provider, &pool).await {
+                    Ok(tvl_usd) => tvl_usd,
+                    Err(e) => {
+                        warn!("🔭 Failed to measure TVL of {}: {}", name, e);
+                        continue;
+                    }
+                };
+                if tvl_usd < self.min_tvl_usd {
+                    info!("🔭 Skipping {}: TVL ${:.0} below ${:.0}", name, tvl_usd, self.min_tvl_usd);
+                    continue;
+                }
+
+                info!("🔭 Discovered {} at {} (TVL ${:.0})", name, address, tvl_usd);
+                discovered.push(pool);
+            }
+        }
+
+        let changed = discovered.len() != self.discovered.len()
+            || discovered.iter().any(|d| !self.discovered.iter().any(|p| p.address == d.address));
+        self.discovered = discovered;
+        Ok(changed)
+    }
+
+    /// Pool TVL in USD, valuing WETH at the pool's own price
+    async fn pool_tvl_usd(&self, provider: &Arc<ConcreteProvider>, pool: &PoolInfo) -> Result<Decimal> {
+        let price = calculate_pool_price_safe_with_retry(provider, pool).await
+            .map_err(|e| anyhow::anyhow!("Failed to price pool: {}", e))?;
+        let depth = analyze_liquidity_depth(provider.as_ref(), pool, price, dec!(1)).await?;
+        Ok(depth.total_liquidity_usd)
+    }
+}
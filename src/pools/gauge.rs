@@ -0,0 +1,165 @@
+//! AERO gauge emissions per pool
+//!
+//! Aerodrome pays most LP yield as AERO streamed by each pool's gauge to the
+//! liquidity staked in it, often far more than swap fees. [`GaugeTracker`]
+//! reads a pool's gauge through the Voter, values its reward rate at the
+//! vAMM-USDC/AERO price and turns it into an APR on the staked share of the
+//! pool's TVL. Reward rates change once per weekly epoch, so reads are
+//! refreshed hourly rather than every cycle.
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+};
+use anyhow::Result;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::debug;
+use crate::{
+    network::{call_contract, IAerodromePool, IERC20, IGauge, ISlipstreamPool, IVoter},
+    types::{EmissionYield, PoolInfo, PoolKind, AERODROME_VOTER_MAINNET, AERO_MAINNET, AERO_USDC_POOL_MAINNET},
+    utils::scale_from_raw,
+};
+
+pub const SECONDS_PER_YEAR: u64 = 31_536_000;
+
+/// How long a pool's gauge read is reused before reading it again
+pub const GAUGE_REFRESH_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Emission APR at which market making favours a long-lived wide range
+pub const EMISSION_STRATEGY_MIN_APR_PCT: Decimal = dec!(20);
+
+/// A pool gauge's emissions as read on-chain
+#[derive(Debug, Clone)]
+pub struct GaugeRewards {
+    pub gauge: Address,
+    pub reward_rate_aero_per_sec: Decimal,
+    /// Share of the pool's liquidity staked in the gauge, 0-1
+    pub staked_fraction: Decimal,
+    pub aero_price_usd: Decimal,
+    /// Unix time the current reward period ends
+    pub period_finish: u64,
+}
+
+impl GaugeRewards {
+    /// Emission yield for a pool with `tvl_usd` of liquidity at unix time `now`.
+    /// Emissions stop at the end of the period until the next epoch's
+    /// distribution.
+    pub fn emission_yield(&self, tvl_usd: Decimal, now: u64) -> EmissionYield {
+        let reward_rate_aero_per_sec = if now > self.period_finish {
+            dec!(0)
+        } else {
+            self.reward_rate_aero_per_sec
+        };
+        let staked_tvl_usd = tvl_usd * self.staked_fraction;
+        EmissionYield {
+            gauge: self.gauge,
+            reward_rate_aero_per_sec,
+            aero_price_usd: self.aero_price_usd,
+            staked_tvl_usd,
+            apr_pct: emission_apr_pct(reward_rate_aero_per_sec, self.aero_price_usd, staked_tvl_usd),
+        }
+    }
+}
+
+/// Annual emissions as a percentage of the staked TVL (zero without stake)
+pub fn emission_apr_pct(reward_rate_aero_per_sec: Decimal, aero_price_usd: Decimal, staked_tvl_usd: Decimal) -> Decimal {
+    if staked_tvl_usd <= dec!(0) {
+        return dec!(0);
+    }
+    reward_rate_aero_per_sec * Decimal::from(SECONDS_PER_YEAR) * aero_price_usd / staked_tvl_usd * dec!(100)
+}
+
+/// Emissions earned by `position_value_usd` staked at `apr_pct` for `duration`
+pub fn emission_return_usd(position_value_usd: Decimal, apr_pct: Decimal, duration: Duration) -> Decimal {
+    position_value_usd * apr_pct * Decimal::from(duration.as_secs())
+        / (dec!(100) * Decimal::from(SECONDS_PER_YEAR))
+}
+
+/// USD price of AERO from the vAMM-USDC/AERO pool
+pub async fn get_aero_price_usd(provider: &dyn Provider) -> Result<Decimal> {
+    let amount_in = U256::from(10u64).pow(U256::from(18));
+    let amount_out = call_contract(
+        provider,
+        AERO_USDC_POOL_MAINNET,
+        IAerodromePool::getAmountOutCall { amountIn: amount_in, tokenIn: AERO_MAINNET },
+    ).await?.amountOut;
+    scale_from_raw(amount_out, 6).ok_or_else(|| anyhow::anyhow!("AERO price out of range"))
+}
+
+/// Read the pool's gauge, or None when the Voter has no gauge for it
+pub async fn get_gauge_rewards(provider: &dyn Provider, pool_info: &PoolInfo) -> Result<Option<GaugeRewards>> {
+    let gauge = call_contract(provider, AERODROME_VOTER_MAINNET, IVoter::gaugesCall { pool: pool_info.address })
+        .await?._0;
+    if gauge == Address::ZERO {
+        return Ok(None);
+    }
+
+    let reward_rate = call_contract(provider, gauge, IGauge::rewardRateCall {}).await?._0;
+    let period_finish = call_contract(provider, gauge, IGauge::periodFinishCall {}).await?._0;
+
+    // Basic gauges hold staked LP tokens; Slipstream pools track staked liquidity themselves
+    let (staked, total) = match pool_info.kind {
+        PoolKind::Basic => (
+            call_contract(provider, gauge, IGauge::totalSupplyCall {}).await?._0,
+            call_contract(provider, pool_info.address, IERC20::totalSupplyCall {}).await?._0,
+        ),
+        PoolKind::Concentrated { .. } => (
+            U256::from(call_contract(provider, pool_info.address, ISlipstreamPool::stakedLiquidityCall {}).await?._0),
+            U256::from(call_contract(provider, pool_info.address, ISlipstreamPool::liquidityCall {}).await?._0),
+        ),
+    };
+    let staked_fraction = match (scale_from_raw(staked, 0), scale_from_raw(total, 0)) {
+        (Some(staked), Some(total)) if total > dec!(0) => (staked / total).min(dec!(1)),
+        _ => dec!(0),
+    };
+
+    Ok(Some(GaugeRewards {
+        gauge,
+        reward_rate_aero_per_sec: scale_from_raw(reward_rate, 18)
+            .ok_or_else(|| anyhow::anyhow!("Reward rate out of range"))?,
+        staked_fraction,
+        aero_price_usd: get_aero_price_usd(provider).await?,
+        period_finish: period_finish.saturating_to::<u64>(),
+    }))
+}
+
+/// Gauge reads per pool, refreshed every `GAUGE_REFRESH_INTERVAL`
+#[derive(Default)]
+pub struct GaugeTracker {
+    pools: RwLock<HashMap<String, (Instant, Option<GaugeRewards>)>>,
+}
+
+impl GaugeTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emission yield of the pool at `tvl_usd`, reading its gauge when the
+    /// cached read is missing or stale. None for pools without a gauge or
+    /// when the read fails.
+    pub async fn emission_yield(&self, provider: &dyn Provider, pool_info: &PoolInfo, tvl_usd: Decimal) -> Option<EmissionYield> {
+        let cached = self.pools.read().await.get(&pool_info.name)
+            .filter(|(read_at, _)| read_at.elapsed() < GAUGE_REFRESH_INTERVAL)
+            .map(|(_, rewards)| rewards.clone());
+        let rewards = match cached {
+            Some(rewards) => rewards,
+            None => {
+                let rewards = match get_gauge_rewards(provider, pool_info).await {
+                    Ok(rewards) => rewards,
+                    Err(e) => {
+                        debug!("Gauge read failed for {}: {}", pool_info.name, e);
+                        return None;
+                    }
+                };
+                self.pools.write().await.insert(pool_info.name.clone(), (Instant::now(), rewards.clone()));
+                rewards
+            }
+        };
+        let now = chrono::Utc::now().timestamp().max(0) as u64;
+        rewards.map(|rewards| rewards.emission_yield(tvl_usd, now))
+    }
+}
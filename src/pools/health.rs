@@ -0,0 +1,168 @@
+//! Composite pool health scoring
+
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use std::collections::{HashMap, VecDeque};
+use std::time::Instant;
+use tokio::sync::RwLock;
+use crate::types::{DepthQuality, LiquidityDepth, PoolHealth};
+
+const MAX_SAMPLES: usize = 120;
+/// Price deviation from fair value that counts as a persistent spread
+const PERSISTENT_SPREAD_PCT: Decimal = dec!(0.5);
+/// Observation time after which a pool gets the full age score
+const MATURE_AGE_SECS: u64 = 3600;
+/// Pools scoring below this are skipped by validation
+pub const MIN_POOL_HEALTH_SCORE: Decimal = dec!(30);
+
+const LIQUIDITY_WEIGHT: Decimal = dec!(0.35);
+const VOLUME_WEIGHT: Decimal = dec!(0.15);
+const STABILITY_WEIGHT: Decimal = dec!(0.20);
+const SPREAD_WEIGHT: Decimal = dec!(0.20);
+const AGE_WEIGHT: Decimal = dec!(0.10);
+
+/// Log-scaled liquidity score: $10k → 0, $100M → 100
+pub fn liquidity_score(total_liquidity_usd: Decimal) -> Decimal {
+    let liquidity = total_liquidity_usd.to_f64().unwrap_or(0.0);
+    if liquidity <= 1.0 {
+        return dec!(0);
+    }
+    let score = (liquidity.log10() - 4.0) * 25.0;
+    Decimal::from_f64(score.clamp(0.0, 100.0)).unwrap_or(dec!(0))
+}
+
+pub fn depth_quality_for_score(score: Decimal) -> DepthQuality {
+    match score {
+        s if s >= dec!(80) => DepthQuality::Excellent,
+        s if s >= dec!(60) => DepthQuality::Good,
+        s if s >= dec!(40) => DepthQuality::Fair,
+        _ => DepthQuality::Poor,
+    }
+}
+
+struct PoolSample {
+    total_liquidity_usd: Decimal,
+    weth_reserves: Decimal,
+    price_diff_pct: Decimal,
+}
+
+struct PoolObservations {
+    first_seen: Instant,
+    samples: VecDeque<PoolSample>,
+}
+
+impl PoolObservations {
+    fn score(&self, pool: &str) -> PoolHealth {
+        let latest = self.samples.back();
+        let liquidity = latest
+            .map(|s| liquidity_score(s.total_liquidity_usd))
+            .unwrap_or(dec!(0));
+
+        // Volume proxy: WETH reserve turnover across the window
+        let turnover: Decimal = self.samples.iter()
+            .zip(self.samples.iter().skip(1))
+            .map(|(a, b)| (b.weth_reserves - a.weth_reserves).abs())
+            .sum();
+        let mean_weth = mean(self.samples.iter().map(|s| s.weth_reserves));
+        let volume = match mean_weth {
+            Some(mean) if mean > dec!(0) => (turnover / mean * dec!(100) * dec!(10)).min(dec!(100)),
+            _ => dec!(0),
+        };
+
+        // Reserve stability: coefficient of variation of total liquidity
+        let stability = match coefficient_of_variation_pct(self.samples.iter().map(|s| s.total_liquidity_usd)) {
+            Some(cv) => (dec!(100) - cv * dec!(10)).max(dec!(0)),
+            None => dec!(50),
+        };
+
+        // Spread persistence: share of samples close to fair value
+        let spread = if self.samples.is_empty() {
+            dec!(50)
+        } else {
+            let tight = self.samples.iter()
+                .filter(|s| s.price_diff_pct < PERSISTENT_SPREAD_PCT)
+                .count();
+            Decimal::from(tight) / Decimal::from(self.samples.len()) * dec!(100)
+        };
+
+        let age = (Decimal::from(self.first_seen.elapsed().as_secs()) / Decimal::from(MATURE_AGE_SECS)
+            * dec!(100)).min(dec!(100));
+
+        let overall_score = liquidity * LIQUIDITY_WEIGHT
+            + volume * VOLUME_WEIGHT
+            + stability * STABILITY_WEIGHT
+            + spread * SPREAD_WEIGHT
+            + age * AGE_WEIGHT;
+
+        PoolHealth {
+            pool: pool.to_string(),
+            liquidity_score: liquidity,
+            volume_score: volume,
+            stability_score: stability,
+            spread_score: spread,
+            age_score: age,
+            overall_score,
+        }
+    }
+}
+
+fn mean(values: impl Iterator<Item = Decimal>) -> Option<Decimal> {
+    let values: Vec<Decimal> = values.collect();
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<Decimal>() / Decimal::from(values.len()))
+}
+
+fn coefficient_of_variation_pct(values: impl Iterator<Item = Decimal>) -> Option<Decimal> {
+    let values: Vec<f64> = values.filter_map(|v| v.to_f64()).collect();
+    if values.len() < 2 {
+        return None;
+    }
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    if mean <= 0.0 {
+        return None;
+    }
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+    Decimal::from_f64(variance.sqrt() / mean * 100.0)
+}
+
+#[derive(Default)]
+pub struct PoolHealthTracker {
+    pools: RwLock<HashMap<String, PoolObservations>>,
+}
+
+impl PoolHealthTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a liquidity observation and return the updated health score
+    pub async fn record(
+        &self,
+        pool: &str,
+        liquidity_depth: &LiquidityDepth,
+        price_diff_pct: Decimal,
+    ) -> PoolHealth {
+        let mut pools = self.pools.write().await;
+        let observations = pools.entry(pool.to_string()).or_insert_with(|| PoolObservations {
+            first_seen: Instant::now(),
+            samples: VecDeque::with_capacity(MAX_SAMPLES),
+        });
+
+        observations.samples.push_back(PoolSample {
+            total_liquidity_usd: liquidity_depth.total_liquidity_usd,
+            weth_reserves: liquidity_depth.weth_reserves,
+            price_diff_pct,
+        });
+        while observations.samples.len() > MAX_SAMPLES {
+            observations.samples.pop_front();
+        }
+
+        observations.score(pool)
+    }
+
+    pub async fn score(&self, pool: &str) -> Option<PoolHealth> {
+        self.pools.read().await.get(pool).map(|o| o.score(pool))
+    }
+}
@@ -1,44 +1,97 @@
 //! Pool information retrieval and management
 
 use alloy::{
-    primitives::{Address, keccak256},
+    primitives::Address,
     providers::Provider,
-    rpc::types::eth::TransactionRequest,
-    sol_types::SolValue,
 };
 use anyhow::{Context, Result};
 use std::time::Instant;
 use tracing::debug;
-use crate::types::PoolInfo;
+use crate::{
+    config::Config,
+    network::{call_contract, IAerodromePool},
+    pools::{default_pool_fee, get_cl_tick_spacing, get_pool_fee, pair_symbols, token_metadata},
+    types::{PoolInfo, PoolKind, TokenPair},
+};
 
+/// Read a pool's tokens and curve. The base token is `base` when given,
+/// otherwise the token whose symbol matches the left side of the pool name.
 pub async fn get_pool_info_internal(
     provider: &dyn Provider, 
     name: &str, 
-    address: Address
+    address: Address,
+    concentrated: bool,
+    base: Option<Address>,
+    config: &Config,
 ) -> Result<PoolInfo> {
     debug!("Getting info for pool: {} at {}", name, address);
     
-    let token0_data = keccak256("token0()")[..4].to_vec();
-    let token1_data = keccak256("token1()")[..4].to_vec();
-    let stable_data = keccak256("stable()")[..4].to_vec();
+    let token0 = call_contract(provider, address, IAerodromePool::token0Call {}).await
+        .context("Failed to get token0")?._0;
+    let token1 = call_contract(provider, address, IAerodromePool::token1Call {}).await
+        .context("Failed to get token1")?._0;
     
-    let tx0 = TransactionRequest::default().to(address).input(token0_data.into());
-    let tx1 = TransactionRequest::default().to(address).input(token1_data.into());
-    let tx_stable = TransactionRequest::default().to(address).input(stable_data.into());
+    let metadata0 = token_metadata(provider, token0).await?;
+    let metadata1 = token_metadata(provider, token1).await?;
+    let base_is_token0 = match base {
+        Some(base) if base == token0 => true,
+        Some(base) if base == token1 => false,
+        Some(base) => return Err(anyhow::anyhow!("Pool does not hold base token {}", base)),
+        None => {
+            let (base_symbol, _) = pair_symbols(name)
+                .ok_or_else(|| anyhow::anyhow!("Pool name {} is not of the form BASE/QUOTE", name))?;
+            if metadata0.symbol.eq_ignore_ascii_case(base_symbol) {
+                true
+            } else if metadata1.symbol.eq_ignore_ascii_case(base_symbol) {
+                false
+            } else {
+                return Err(anyhow::anyhow!("Pool tokens {}/{} do not include {}", metadata0.symbol, metadata1.symbol, base_symbol));
+            }
+        }
+    };
+    let ((base, base_metadata), (quote, quote_metadata)) = if base_is_token0 {
+        ((token0, metadata0), (token1, metadata1))
+    } else {
+        ((token1, metadata1), (token0, metadata0))
+    };
+    let (base_symbol, quote_symbol) = (base_metadata.symbol, quote_metadata.symbol);
+    let pair = TokenPair {
+        base,
+        quote,
+        base_decimals: base_metadata.decimals,
+        quote_decimals: quote_metadata.decimals,
+        cex_symbol: config.cex_symbol(&base_symbol, &quote_symbol),
+        base_symbol,
+        quote_symbol,
+    };
     
-    let token0 = Address::abi_decode(&provider.call(&tx0).await
-        .context("Failed to get token0")?, true)?;
-    let token1 = Address::abi_decode(&provider.call(&tx1).await
-        .context("Failed to get token1")?, true)?;
-    let is_stable = bool::abi_decode(&provider.call(&tx_stable).await
-        .context("Failed to get stable flag")?, true)?;
+    // CL pools have no stable() flag; they expose their tick spacing instead
+    let (is_stable, kind) = if concentrated {
+        let tick_spacing = get_cl_tick_spacing(provider, address).await
+            .context("Failed to get tick spacing")?;
+        (false, PoolKind::Concentrated { tick_spacing })
+    } else {
+        let is_stable = call_contract(provider, address, IAerodromePool::stableCall {}).await
+            .context("Failed to get stable flag")?._0;
+        (is_stable, PoolKind::Basic)
+    };
+    let fee = match get_pool_fee(provider, address, is_stable, kind).await {
+        Ok(fee) => fee,
+        Err(e) => {
+            debug!("Fee unavailable for {}, using the default: {}", name, e);
+            default_pool_fee(kind, is_stable)
+        }
+    };
     
     Ok(PoolInfo {
         address,
         name: name.to_string(),
         token0,
         token1,
+        pair,
         is_stable,
+        kind,
+        fee,
         min_liquidity: rust_decimal_macros::dec!(1000),
         last_update: Instant::now(),
     })
@@ -1,52 +1,37 @@
 //! Liquidity depth analysis
 
 use alloy::providers::Provider;
-use anyhow::{Context, Result};
+use anyhow::Result;
 use rust_decimal::prelude::*;
-use rust_decimal_macros::dec;
-use std::str::FromStr;
 use crate::{
-    pools::get_pool_reserves_enhanced,
-    types::{LiquidityDepth, DepthQuality, PoolInfo, WETH_MAINNET, USDC_MAINNET, USDBC_MAINNET},
-    utils::pow10,
+    pools::{depth_quality_for_score, get_pool_reserves_enhanced, liquidity_score},
+    types::{LiquidityDepth, PoolInfo},
 };
 
+/// Reserves and TVL of a pool. `fair_value_price` is in quote tokens per
+/// base token and `quote_usd` values one quote token in USD.
 pub async fn analyze_liquidity_depth(
     provider: &dyn Provider,
     pool_info: &PoolInfo,
     fair_value_price: Decimal,
+    quote_usd: Decimal,
 ) -> Result<LiquidityDepth> {
-    let (r0, r1) = get_pool_reserves_enhanced(provider, pool_info.address, &pool_info.name).await
+    let (r0, r1) = get_pool_reserves_enhanced(provider, pool_info.address, &pool_info.name, pool_info.kind).await
         .map_err(|e| anyhow::anyhow!("Failed to get reserves for liquidity analysis: {}", e))?;
     
-    let (weth_reserves, usd_reserves, usd_decimals) = if pool_info.token0 == WETH_MAINNET {
-        let decimals = if pool_info.token1 == USDC_MAINNET || pool_info.token1 == USDBC_MAINNET { 6 } else { 18 };
-        (r0, r1, decimals)
-    } else if pool_info.token1 == WETH_MAINNET {
-        let decimals = if pool_info.token0 == USDC_MAINNET || pool_info.token0 == USDBC_MAINNET { 6 } else { 18 };
-        (r1, r0, decimals)
-    } else {
-        return Err(anyhow::anyhow!("Not a WETH/USD pool"));
-    };
+    let (base_amount, quote_amount) = pool_info.base_quote_reserves(r0, r1)
+        .ok_or_else(|| anyhow::anyhow!("Reserves do not match the {} pair", pool_info.pair))?;
     
-    let weth_amount = Decimal::from_str(&weth_reserves.to_string())
-        .context("Failed to parse WETH reserve")? / pow10(18);
-    let usd_amount = Decimal::from_str(&usd_reserves.to_string())
-        .context("Failed to parse USD reserve")? / pow10(usd_decimals);
+    let total_liquidity_usd = ((base_amount * fair_value_price) + quote_amount) * quote_usd;
     
-    let total_liquidity_usd = (weth_amount * fair_value_price) + usd_amount;
-    
-    let depth_quality = match total_liquidity_usd {
-        liq if liq > dec!(10000000) => DepthQuality::Excellent,
-        liq if liq > dec!(1000000) => DepthQuality::Good,
-        liq if liq > dec!(100000) => DepthQuality::Fair,
-        _ => DepthQuality::Poor,
-    };
+    // Liquidity-only score until the health tracker has history for this pool
+    let health_score = liquidity_score(total_liquidity_usd);
     
     Ok(LiquidityDepth {
         total_liquidity_usd,
-        weth_reserves: weth_amount,
-        usd_reserves: usd_amount,
-        depth_quality,
+        weth_reserves: base_amount,
+        usd_reserves: quote_amount,
+        health_score,
+        depth_quality: depth_quality_for_score(health_score),
     })
 }
@@ -4,8 +4,28 @@ pub mod info;
 pub mod reserves;
 pub mod validation;
 pub mod liquidity;
+pub mod concentrated;
+pub mod quoter;
+pub mod stable;
+pub mod health;
+pub mod cache;
+pub mod discovery;
+pub mod tokens;
+pub mod twap;
+pub mod state_cache;
+pub mod gauge;
 
 pub use info::*;
 pub use reserves::*;
 pub use validation::*;
 pub use liquidity::*;
+pub use concentrated::*;
+pub use quoter::*;
+pub use stable::*;
+pub use health::*;
+pub use cache::*;
+pub use discovery::*;
+pub use tokens::*;
+pub use twap::*;
+pub use state_cache::*;
+pub use gauge::*;
@@ -0,0 +1,193 @@
+//! Execution-accurate pricing via the pool's getAmountOut quoter
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+};
+use anyhow::Result;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use crate::{
+    network::{call_contract, IAerodromePool, IPoolFactory, ISlipstreamPool},
+    pools::{stable_amount_out, stable_spot_price, volatile_amount_out, STABLE_POOL_FEE, VOLATILE_POOL_FEE},
+    types::{PoolInfo, PoolKind},
+    utils::{scale_from_raw, scale_to_raw},
+};
+
+/// Effective prices for trading a fixed base token size against a pool
+#[derive(Debug, Clone)]
+pub struct PoolQuote {
+    /// Base token amount (WETH on WETH/USD pools)
+    pub amount_in_eth: Decimal,
+    /// Quote received per base token when selling `amount_in_eth` into the pool
+    pub sell_price: Decimal,
+    /// Quote paid per base token when buying `amount_in_eth` from the pool
+    pub buy_price: Decimal,
+}
+
+impl PoolQuote {
+    /// Re-express both prices in another unit, e.g. quote tokens to USD
+    pub fn scaled(&self, factor: Decimal) -> Self {
+        Self {
+            amount_in_eth: self.amount_in_eth,
+            sell_price: self.sell_price * factor,
+            buy_price: self.buy_price * factor,
+        }
+    }
+}
+
+/// Call `getAmountOut(uint256,address)` on an Aerodrome vAMM/sAMM pool.
+/// The pool applies its own curve (x*y=k or stable-swap) and fee.
+pub async fn get_amount_out(
+    provider: &dyn Provider,
+    pool: Address,
+    amount_in: U256,
+    token_in: Address,
+) -> Result<U256> {
+    let call = IAerodromePool::getAmountOutCall { amountIn: amount_in, tokenIn: token_in };
+    Ok(call_contract(provider, pool, call).await?.amountOut)
+}
+
+fn from_raw(amount: U256, decimals: i32) -> Result<Decimal> {
+    scale_from_raw(amount, decimals)
+        .ok_or_else(|| anyhow::anyhow!("Failed to parse quoted amount"))
+}
+
+fn to_raw(amount: Decimal, decimals: i32) -> Result<U256> {
+    scale_to_raw(amount, decimals)
+        .ok_or_else(|| anyhow::anyhow!("Quote amount {} out of range", amount))
+}
+
+/// Quote buy and sell prices for `trade_size_eth` base tokens using the pool's own quoter.
+///
+/// `mid_price` (quote per base) sizes the quote leg of the buy quote. CL pools have no
+/// getAmountOut on the pool contract, so this returns an error for them and
+/// callers should fall back to [`PoolReserves::quote`].
+pub async fn quote_trade_prices(
+    provider: &dyn Provider,
+    pool_info: &PoolInfo,
+    trade_size_eth: Decimal,
+    mid_price: Decimal,
+) -> Result<PoolQuote> {
+    if matches!(pool_info.kind, PoolKind::Concentrated { .. }) {
+        return Err(anyhow::anyhow!("getAmountOut quoting is not supported for CL pools"));
+    }
+
+    let pair = &pool_info.pair;
+
+    // Sell base for quote
+    let quote_out = get_amount_out(provider, pool_info.address, to_raw(trade_size_eth, pair.base_decimals)?, pair.base).await?;
+    let quote_out = from_raw(quote_out, pair.quote_decimals)?;
+
+    // Buy base with the quote notional of the trade at mid price
+    let quote_in = trade_size_eth * mid_price;
+    let base_out = get_amount_out(provider, pool_info.address, to_raw(quote_in, pair.quote_decimals)?, pair.quote).await?;
+    let base_out = from_raw(base_out, pair.base_decimals)?;
+
+    if quote_out == dec!(0) || base_out == dec!(0) {
+        return Err(anyhow::anyhow!("Quoter returned zero output"));
+    }
+
+    Ok(PoolQuote {
+        amount_in_eth: trade_size_eth,
+        sell_price: quote_out / trade_size_eth,
+        buy_price: quote_in / base_out,
+    })
+}
+
+/// Aerodrome's default fee for a pool. Slipstream pools derive it from the
+/// tick spacing; pools with a custom fee set by the factory will differ.
+pub fn default_pool_fee(kind: PoolKind, is_stable: bool) -> Decimal {
+    match kind {
+        PoolKind::Basic if is_stable => STABLE_POOL_FEE,
+        PoolKind::Basic => VOLATILE_POOL_FEE,
+        PoolKind::Concentrated { tick_spacing } => match tick_spacing {
+            1 => dec!(0.0001),
+            50 | 100 => dec!(0.0005),
+            200 => dec!(0.003),
+            _ => dec!(0.01),
+        },
+    }
+}
+
+/// Read a pool's fee rate: from its factory's `getFee` for vAMM/sAMM pools
+/// (which covers custom fees), from the pool itself for Slipstream pools
+pub async fn get_pool_fee(provider: &dyn Provider, pool: Address, is_stable: bool, kind: PoolKind) -> Result<Decimal> {
+    match kind {
+        PoolKind::Basic => {
+            let factory = call_contract(provider, pool, IAerodromePool::factoryCall {}).await?._0;
+            let call = IPoolFactory::getFeeCall { pool, stable: is_stable };
+            let fee_bps = call_contract(provider, factory, call).await?._0;
+            let fee_bps = u32::try_from(fee_bps)
+                .map_err(|_| anyhow::anyhow!("Pool {} reports a fee of {} bps", pool, fee_bps))?;
+            Ok(Decimal::from(fee_bps) / dec!(10000))
+        }
+        PoolKind::Concentrated { .. } => {
+            let fee_pips = call_contract(provider, pool, ISlipstreamPool::feeCall {}).await?._0;
+            Ok(Decimal::from(fee_pips) / dec!(1000000))
+        }
+    }
+}
+
+/// Pool reserves for pricing a trade off-chain when the quoter is
+/// unavailable. CL pools use their virtual reserves, which follow x*y=k
+/// within the current tick range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolReserves {
+    /// Base token reserves (WETH on WETH/USD pools)
+    pub base: Decimal,
+    /// Quote token reserves
+    pub quote: Decimal,
+    /// USD value of one quote token, so quotes come out in USD
+    pub quote_usd: Decimal,
+    /// Price on the stable-swap curve instead of x*y=k
+    pub is_stable: bool,
+    pub fee: Decimal,
+}
+
+impl PoolReserves {
+    pub fn new(pool_info: &PoolInfo, base: Decimal, quote: Decimal, quote_usd: Decimal) -> Self {
+        Self {
+            base,
+            quote,
+            quote_usd,
+            is_stable: pool_info.is_stable && pool_info.kind == PoolKind::Basic,
+            fee: pool_info.fee,
+        }
+    }
+
+    fn amount_out(&self, amount_in: Decimal, reserve_in: Decimal, reserve_out: Decimal) -> Option<Decimal> {
+        if self.is_stable {
+            stable_amount_out(amount_in, reserve_in, reserve_out, self.fee)
+        } else {
+            volatile_amount_out(amount_in, reserve_in, reserve_out, self.fee)
+        }
+    }
+
+    /// Buy and sell prices in USD for `trade_size` base tokens, from the
+    /// pool's curve and fee, mirroring [`quote_trade_prices`]
+    pub fn quote(&self, trade_size: Decimal) -> Option<PoolQuote> {
+        if trade_size <= dec!(0) {
+            return None;
+        }
+        let mid_price = if self.is_stable {
+            stable_spot_price(self.base, self.quote)?
+        } else {
+            self.quote.checked_div(self.base)?
+        };
+
+        let quote_out = self.amount_out(trade_size, self.base, self.quote)?;
+        let quote_in = trade_size * mid_price;
+        let base_out = self.amount_out(quote_in, self.quote, self.base)?;
+        if quote_out <= dec!(0) || base_out <= dec!(0) {
+            return None;
+        }
+
+        let quote = PoolQuote {
+            amount_in_eth: trade_size,
+            sell_price: quote_out / trade_size,
+            buy_price: quote_in / base_out,
+        };
+        Some(quote.scaled(self.quote_usd))
+    }
+}
@@ -1,40 +1,51 @@
 //! Pool reserves fetching and management
 
 use alloy::{
-    primitives::{Address, keccak256, U256},
+    primitives::{Address, U256},
     providers::Provider,
-    rpc::types::eth::TransactionRequest,
-    sol_types::SolValue,
 };
-use anyhow::{Context, Result};
+use anyhow::Result;
 use std::sync::Arc;
 use crate::{
     errors::{BotError, BotResult},
-    network::retry::{retry_with_backoff, RetryConfig},
-    types::PoolInfo,
+    network::{
+        call_contract,
+        retry::{retry_with_backoff, RetryConfig},
+        IAerodromePool,
+    },
+    pools::{get_cl_pool_reserves, POOL_STATE_CACHE},
+    types::{PoolInfo, PoolKind},
     ConcreteProvider,
 };
 
 pub async fn get_pool_reserves(provider: &dyn Provider, pool: Address) -> Result<(U256, U256)> {
-    let data = keccak256("getReserves()")[..4].to_vec();
-    let tx = TransactionRequest::default()
-        .to(pool)
-        .input(data.into());
-    
-    let result = provider.call(&tx).await
-        .context("Failed to call getReserves")?;
-    let decoded = <(U256, U256, U256)>::abi_decode(&result, true)
-        .context("Failed to decode reserves")?;
-    Ok((decoded.0, decoded.1))
+    let reserves = call_contract(provider, pool, IAerodromePool::getReservesCall {}).await?;
+    Ok((reserves.reserve0, reserves.reserve1))
+}
+
+/// Fetch reserves for any supported pool kind (virtual reserves for CL pools)
+pub async fn get_pool_reserves_for_kind(
+    provider: &dyn Provider,
+    pool: Address,
+    kind: PoolKind,
+) -> Result<(U256, U256)> {
+    match kind {
+        PoolKind::Basic => get_pool_reserves(provider, pool).await,
+        PoolKind::Concentrated { .. } => get_cl_pool_reserves(provider, pool).await,
+    }
 }
 
 pub async fn get_pool_reserves_enhanced(
     provider: &dyn Provider,
     pool: Address,
     pool_name: &str,
+    kind: PoolKind,
 ) -> BotResult<(U256, U256)> {
+    if let Some(reserves) = POOL_STATE_CACHE.get(pool) {
+        return Ok(reserves);
+    }
     let operation = || async {
-        get_pool_reserves(provider, pool).await
+        get_pool_reserves_for_kind(provider, pool, kind).await
     };
     
     retry_with_backoff(
@@ -42,6 +53,7 @@ pub async fn get_pool_reserves_enhanced(
         &RetryConfig::default(),
         &format!("get reserves for {}", pool_name),
     ).await
+    .inspect(|reserves| POOL_STATE_CACHE.insert(pool, *reserves))
     .map_err(|e| match e {
         BotError::Network { .. } => e,
         _ => BotError::Contract {
@@ -80,43 +92,33 @@ pub async fn calculate_pool_price_safe(
     provider: &dyn Provider,
     pool_info: &PoolInfo,
 ) -> Result<rust_decimal::Decimal> {
-    use rust_decimal::prelude::*;
     use rust_decimal_macros::dec;
-    use std::str::FromStr;
     use crate::{
-        types::{WETH_MAINNET, USDC_MAINNET, USDBC_MAINNET},
-        validation::validate_price,
-        utils::pow10,
+        validation::validate_pair_price,
+        pools::stable_spot_price,
     };
     
-    let (r0, r1) = get_pool_reserves_enhanced(provider, pool_info.address, &pool_info.name).await
+    let (r0, r1) = get_pool_reserves_enhanced(provider, pool_info.address, &pool_info.name, pool_info.kind).await
         .map_err(|e| anyhow::anyhow!("Failed to get reserves for price calculation: {}", e))?;
     
     if r0 == U256::from(0) || r1 == U256::from(0) {
         return Err(anyhow::anyhow!("Pool has zero reserves"));
     }
     
-    let (weth_reserve, usd_reserve, usd_decimals) = if pool_info.token0 == WETH_MAINNET {
-        let decimals = if pool_info.token1 == USDC_MAINNET || pool_info.token1 == USDBC_MAINNET { 6 } else { 18 };
-        (r0, r1, decimals)
-    } else if pool_info.token1 == WETH_MAINNET {
-        let decimals = if pool_info.token0 == USDC_MAINNET || pool_info.token0 == USDBC_MAINNET { 6 } else { 18 };
-        (r1, r0, decimals)
-    } else {
-        return Err(anyhow::anyhow!("Not a WETH/USD pool"));
-    };
-    
-    let weth_amount = Decimal::from_str(&weth_reserve.to_string())
-        .context("Failed to parse WETH reserve")? / pow10(18);
-    let usd_amount = Decimal::from_str(&usd_reserve.to_string())
-        .context("Failed to parse USD reserve")? / pow10(usd_decimals);
+    let (base_amount, quote_amount) = pool_info.base_quote_reserves(r0, r1)
+        .ok_or_else(|| anyhow::anyhow!("Reserves do not match the {} pair", pool_info.pair))?;
     
-    if weth_amount == dec!(0) {
-        return Err(anyhow::anyhow!("WETH amount is zero"));
+    if base_amount == dec!(0) {
+        return Err(anyhow::anyhow!("{} amount is zero", pool_info.pair.base_symbol));
     }
     
-    let price = usd_amount / weth_amount;
-    validate_price(price, "DEX")?;
+    let price = if pool_info.is_stable {
+        stable_spot_price(base_amount, quote_amount)
+            .ok_or_else(|| anyhow::anyhow!("Failed to compute stable pool price"))?
+    } else {
+        quote_amount / base_amount
+    };
+    validate_pair_price(price, "DEX", &pool_info.pair)?;
     
     Ok(price)
 }
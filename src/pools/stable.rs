@@ -0,0 +1,111 @@
+//! Solidly stable-swap (sAMM) curve math
+//!
+//! Aerodrome stable pools use the invariant x³y + xy³ = k over reserves
+//! normalized to 18 decimals. Pricing these pools from the reserve ratio
+//! (as for x*y=k) gives a wrong price whenever reserves are unbalanced.
+
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+
+/// Aerodrome default fee for stable pools (0.05%)
+pub const STABLE_POOL_FEE: Decimal = dec!(0.0005);
+/// Aerodrome default fee for volatile pools (0.3%)
+pub const VOLATILE_POOL_FEE: Decimal = dec!(0.003);
+
+const NEWTON_MAX_ITERATIONS: usize = 255;
+const NEWTON_TOLERANCE: f64 = 1e-12;
+
+fn invariant(x: f64, y: f64) -> f64 {
+    x * x * x * y + x * y * y * y
+}
+
+/// Marginal price of `x` in units of `y` on the stable curve: -(∂k/∂x)/(∂k/∂y)
+pub fn stable_spot_price(reserve_x: Decimal, reserve_y: Decimal) -> Option<Decimal> {
+    let x = reserve_x.to_f64()?;
+    let y = reserve_y.to_f64()?;
+    if x <= 0.0 || y <= 0.0 {
+        return None;
+    }
+
+    let price = (3.0 * x * x * y + y * y * y) / (x * x * x + 3.0 * x * y * y);
+    Decimal::from_f64(price)
+}
+
+/// Solve for the new `y` reserve that keeps the invariant after `x` changes
+fn solve_y(x: f64, k: f64, y_start: f64) -> Option<f64> {
+    let mut y = y_start;
+    for _ in 0..NEWTON_MAX_ITERATIONS {
+        let f = invariant(x, y) - k;
+        let df = x * x * x + 3.0 * x * y * y;
+        if df == 0.0 {
+            return None;
+        }
+        let next = y - f / df;
+        if (next - y).abs() <= NEWTON_TOLERANCE * y.max(1.0) {
+            return Some(next);
+        }
+        y = next;
+    }
+    Some(y)
+}
+
+/// Amount of `y` received for `amount_in` of `x` on the stable curve, after fee
+pub fn stable_amount_out(
+    amount_in: Decimal,
+    reserve_in: Decimal,
+    reserve_out: Decimal,
+    fee: Decimal,
+) -> Option<Decimal> {
+    let amount_in = (amount_in * (dec!(1) - fee)).to_f64()?;
+    let x = reserve_in.to_f64()?;
+    let y = reserve_out.to_f64()?;
+    if amount_in <= 0.0 || x <= 0.0 || y <= 0.0 {
+        return None;
+    }
+
+    let k = invariant(x, y);
+    let y_after = solve_y(x + amount_in, k, y)?;
+    let out = (y - y_after).max(0.0);
+    Decimal::from_f64(out)
+}
+
+/// Amount of `y` received for `amount_in` of `x` on the constant-product curve, after fee
+pub fn volatile_amount_out(
+    amount_in: Decimal,
+    reserve_in: Decimal,
+    reserve_out: Decimal,
+    fee: Decimal,
+) -> Option<Decimal> {
+    if amount_in <= dec!(0) || reserve_in <= dec!(0) || reserve_out <= dec!(0) {
+        return None;
+    }
+    let amount_in = amount_in * (dec!(1) - fee);
+    Some(amount_in * reserve_out / (reserve_in + amount_in))
+}
+
+/// Estimated price impact in bps of selling `amount_in` base into a pool,
+/// measured against the curve's marginal price (fee excluded).
+pub fn estimate_price_impact_bps(
+    is_stable: bool,
+    amount_in: Decimal,
+    reserve_in: Decimal,
+    reserve_out: Decimal,
+) -> Option<Decimal> {
+    let (spot, out) = if is_stable {
+        (
+            stable_spot_price(reserve_in, reserve_out)?,
+            stable_amount_out(amount_in, reserve_in, reserve_out, dec!(0))?,
+        )
+    } else {
+        (
+            reserve_out.checked_div(reserve_in)?,
+            volatile_amount_out(amount_in, reserve_in, reserve_out, dec!(0))?,
+        )
+    };
+
+    let expected = amount_in * spot;
+    if expected <= dec!(0) {
+        return None;
+    }
+    Some(((expected - out) / expected * dec!(10000)).max(dec!(0)))
+}
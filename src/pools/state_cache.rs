@@ -0,0 +1,106 @@
+//! Shared cache of pool reserves within a block or cycle
+//!
+//! Price calculation, liquidity depth and opportunity validation each read
+//! the same pool's reserves in a cycle. The first read goes to the chain and
+//! the rest are served from here until the entry is older than
+//! `RESERVE_CACHE_TTL_MS` or a newer block is seen, so a cycle costs one
+//! `getReserves()` per pool instead of three.
+
+use alloy::primitives::{Address, U256};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use crate::config::CONFIG;
+
+lazy_static! {
+    /// Shared by every reserves read made through `get_pool_reserves_enhanced`
+    pub static ref POOL_STATE_CACHE: PoolStateCache =
+        PoolStateCache::new(Duration::from_millis(CONFIG.get().reserve_cache_ttl_ms));
+}
+
+struct CachedReserves {
+    reserves: (U256, U256),
+    fetched_at: Instant,
+}
+
+struct CacheInner {
+    /// Newest block reported by `advance_block`
+    block: Option<u64>,
+    entries: HashMap<Address, CachedReserves>,
+}
+
+/// Hits and misses since start
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+pub struct PoolStateCache {
+    /// Zero disables the cache
+    ttl: Duration,
+    inner: Mutex<CacheInner>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl PoolStateCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            inner: Mutex::new(CacheInner { block: None, entries: HashMap::new() }),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.ttl.is_zero()
+    }
+
+    /// Reserves of `pool` if read within the TTL and the current block
+    pub fn get(&self, pool: Address) -> Option<(U256, U256)> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let cached = self.lock().entries.get(&pool)
+            .filter(|entry| entry.fetched_at.elapsed() < self.ttl)
+            .map(|entry| entry.reserves);
+        let counter = if cached.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        cached
+    }
+
+    pub fn insert(&self, pool: Address, reserves: (U256, U256)) {
+        if self.is_enabled() {
+            self.lock().entries.insert(pool, CachedReserves { reserves, fetched_at: Instant::now() });
+        }
+    }
+
+    /// Drop every entry read before `block`; older or repeated blocks are ignored
+    pub fn advance_block(&self, block: u64) {
+        let mut inner = self.lock();
+        if inner.block.is_none_or(|current| block > current) {
+            inner.block = Some(block);
+            inner.entries.clear();
+        }
+    }
+
+    /// Forget `pool`, e.g. after a swap through it
+    pub fn invalidate(&self, pool: Address) {
+        self.lock().entries.remove(&pool);
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,126 @@
+//! ERC20 token metadata and pair naming
+//!
+//! Decimals and symbols are read from the token contract once and kept in
+//! [`TOKEN_METADATA`], so every amount the bot scales (reserves, quotes,
+//! balances, router amounts) uses the token's own decimals rather than a
+//! guess from its address.
+
+use alloy::{
+    primitives::{Address, FixedBytes},
+    providers::Provider,
+    rpc::types::eth::TransactionRequest,
+    sol_types::{SolCall, SolValue},
+};
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use crate::network::{call_contract, IERC20};
+
+lazy_static! {
+    /// Shared by every decimals and symbol lookup made through `token_metadata`
+    pub static ref TOKEN_METADATA: TokenMetadataCache = TokenMetadataCache::new();
+}
+
+/// A token's on-chain `symbol()` and `decimals()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenMetadata {
+    pub symbol: String,
+    pub decimals: i32,
+}
+
+/// Token metadata by address, filled on first use. Entries never expire:
+/// decimals and symbols are fixed once a token is deployed.
+#[derive(Default)]
+pub struct TokenMetadataCache {
+    entries: Mutex<HashMap<Address, TokenMetadata>>,
+}
+
+impl TokenMetadataCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<Address, TokenMetadata>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Metadata of `token` if it has been read before
+    pub fn get(&self, token: Address) -> Option<TokenMetadata> {
+        self.lock().get(&token).cloned()
+    }
+
+    pub fn insert(&self, token: Address, metadata: TokenMetadata) {
+        self.lock().insert(token, metadata);
+    }
+
+    /// Metadata of `token`, calling `symbol()` and `decimals()` on the first request
+    pub async fn metadata(&self, provider: &dyn Provider, token: Address) -> Result<TokenMetadata> {
+        if let Some(metadata) = self.get(token) {
+            return Ok(metadata);
+        }
+        let metadata = TokenMetadata {
+            symbol: get_token_symbol(provider, token).await?,
+            decimals: get_token_decimals(provider, token).await?,
+        };
+        self.insert(token, metadata.clone());
+        Ok(metadata)
+    }
+}
+
+/// Metadata of `token` from the shared cache, read on-chain on first use
+pub async fn token_metadata(provider: &dyn Provider, token: Address) -> Result<TokenMetadata> {
+    TOKEN_METADATA.metadata(provider, token).await
+}
+
+/// Decimals of `token` from the shared cache, read on-chain on first use
+pub async fn token_decimals(provider: &dyn Provider, token: Address) -> Result<i32> {
+    Ok(token_metadata(provider, token).await?.decimals)
+}
+
+/// Call `decimals()` on an ERC20 token
+pub async fn get_token_decimals(provider: &dyn Provider, token: Address) -> Result<i32> {
+    let decimals = call_contract(provider, token, IERC20::decimalsCall {}).await
+        .context("Failed to get token decimals")?._0;
+    if decimals > 36 {
+        return Err(anyhow::anyhow!("Token {} reports {} decimals", token, decimals));
+    }
+    Ok(decimals as i32)
+}
+
+/// Call `symbol()` on an ERC20 token, accepting `bytes32` symbols from older tokens
+pub async fn get_token_symbol(provider: &dyn Provider, token: Address) -> Result<String> {
+    let tx = TransactionRequest::default()
+        .to(token)
+        .input(IERC20::symbolCall {}.abi_encode().into());
+    let result = provider.call(&tx).await
+        .context("Failed to get token symbol")?;
+    if let Ok(symbol) = IERC20::symbolCall::abi_decode_returns(&result, true) {
+        return Ok(symbol._0);
+    }
+    let raw = FixedBytes::<32>::abi_decode(&result, true)
+        .context("Failed to decode token symbol")?;
+    Ok(String::from_utf8_lossy(raw.as_slice()).trim_end_matches('\0').to_string())
+}
+
+/// Base and quote symbols from a pool name such as `vAMM-AERO/USDC`,
+/// `CL100-WETH/USDC` or `WETH/USDC-Sepolia`
+pub fn pair_symbols(pool_name: &str) -> Option<(&str, &str)> {
+    let pair = pool_name.split_once('-')
+        .filter(|(prefix, _)| !prefix.contains('/'))
+        .map_or(pool_name, |(_, rest)| rest);
+    let (base, quote) = pair.split_once('/')?;
+    let quote = quote.split('-').next().unwrap_or(quote);
+    (!base.is_empty() && !quote.is_empty()).then_some((base, quote))
+}
+
+/// Binance symbol for a pair when none is configured: wrapped/bridged
+/// variants map to the asset Binance lists (WETH → ETH, USDbC → USDC)
+pub fn default_cex_symbol(base_symbol: &str, quote_symbol: &str) -> String {
+    let cex_asset = |symbol: &str| match symbol {
+        "WETH" => "ETH".to_string(),
+        "USDbC" => "USDC".to_string(),
+        other => other.to_uppercase(),
+    };
+    format!("{}{}", cex_asset(base_symbol), cex_asset(quote_symbol))
+}
@@ -0,0 +1,166 @@
+//! Per-pool time-weighted average DEX price
+//!
+//! [`TwapTracker`] averages the prices the bot has sampled itself. Basic
+//! Aerodrome pools also record cumulative reserves on-chain (an observation
+//! every 30 minutes), which gives a TWAP that a single-block sandwich cannot
+//! move: a spot price far from it is more likely manipulation than a real arb.
+
+use alloy::{
+    primitives::U256,
+    providers::Provider,
+};
+use anyhow::Result;
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use crate::{
+    network::{call_contract, IAerodromePool},
+    pools::stable_spot_price,
+    types::{PoolInfo, PoolKind},
+};
+
+/// Default averaging window for the DEX TWAP price basis
+pub const DEFAULT_TWAP_WINDOW_SECS: u64 = 300;
+
+/// Default bound on DEX spot vs the pool's on-chain TWAP
+pub const DEFAULT_MAX_TWAP_DEVIATION_PCT: Decimal = dec!(2);
+
+/// Shortest span averaged from on-chain observations; a fresher last
+/// observation is paired with the one before it
+pub const MIN_OBSERVATION_SPAN_SECS: u64 = 60;
+
+/// Each observed price holds until the next one, so a price seen for two
+/// minutes outweighs one seen for ten seconds
+#[derive(Default)]
+pub struct TwapTracker {
+    pools: RwLock<HashMap<String, VecDeque<(Instant, Decimal)>>>,
+}
+
+impl TwapTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// TWAP of the prices seen for `pool` over the last `window`, then record
+    /// `price`. None until the pool has a previous observation.
+    pub async fn record(&self, pool: &str, price: Decimal, window: Duration) -> Option<Decimal> {
+        self.record_at(pool, price, window, Instant::now()).await
+    }
+
+    pub async fn record_at(&self, pool: &str, price: Decimal, window: Duration, now: Instant) -> Option<Decimal> {
+        let mut pools = self.pools.write().await;
+        let samples = pools.entry(pool.to_string()).or_default();
+        let start = now.checked_sub(window).unwrap_or(now);
+
+        // Keep the last sample before the window: it held into the window
+        while samples.len() > 1 && samples[1].0 <= start {
+            samples.pop_front();
+        }
+
+        let mut weighted = Decimal::ZERO;
+        let mut total_secs = Decimal::ZERO;
+        for (i, (seen_at, sample_price)) in samples.iter().enumerate() {
+            let from = (*seen_at).max(start);
+            let to = samples.get(i + 1).map_or(now, |(next, _)| *next);
+            let secs = Decimal::from_f64(to.saturating_duration_since(from).as_secs_f64())
+                .unwrap_or_default();
+            weighted += *sample_price * secs;
+            total_secs += secs;
+        }
+        samples.push_back((now, price));
+
+        (total_secs > Decimal::ZERO).then(|| weighted / total_secs)
+    }
+}
+
+/// Cumulative reserves recorded by a basic Aerodrome pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CumulativeObservation {
+    pub timestamp: u64,
+    pub reserve0_cumulative: U256,
+    pub reserve1_cumulative: U256,
+}
+
+impl From<IAerodromePool::Observation> for CumulativeObservation {
+    fn from(observation: IAerodromePool::Observation) -> Self {
+        Self {
+            timestamp: observation.timestamp.saturating_to::<u64>(),
+            reserve0_cumulative: observation.reserve0Cumulative,
+            reserve1_cumulative: observation.reserve1Cumulative,
+        }
+    }
+}
+
+/// Time-weighted average (reserve0, reserve1) between two observations
+pub fn average_reserves(start: &CumulativeObservation, end: &CumulativeObservation) -> Option<(U256, U256)> {
+    let elapsed = end.timestamp.checked_sub(start.timestamp).filter(|secs| *secs > 0)?;
+    let elapsed = U256::from(elapsed);
+    Some((
+        end.reserve0_cumulative.checked_sub(start.reserve0_cumulative)? / elapsed,
+        end.reserve1_cumulative.checked_sub(start.reserve1_cumulative)? / elapsed,
+    ))
+}
+
+/// TWAP in quote tokens per base token between two observations
+pub fn observation_twap_price(
+    pool_info: &PoolInfo,
+    start: &CumulativeObservation,
+    end: &CumulativeObservation,
+) -> Option<Decimal> {
+    let (reserve0, reserve1) = average_reserves(start, end)?;
+    let (base, quote) = pool_info.base_quote_reserves(reserve0, reserve1)?;
+    if base <= dec!(0) || quote <= dec!(0) {
+        return None;
+    }
+    if pool_info.is_stable {
+        stable_spot_price(base, quote)
+    } else {
+        Some(quote / base)
+    }
+}
+
+/// Percentage by which the spot price differs from the TWAP
+pub fn spot_twap_divergence_pct(spot: Decimal, twap: Decimal) -> Decimal {
+    if twap <= dec!(0) {
+        return Decimal::MAX;
+    }
+    ((spot - twap) / twap * dec!(100)).abs()
+}
+
+/// On-chain TWAP of a basic pool in quote tokens, averaged from its last
+/// observation (or the one before, if that is too recent) up to now
+pub async fn get_observation_twap(provider: &dyn Provider, pool_info: &PoolInfo) -> Result<Decimal> {
+    if !matches!(pool_info.kind, PoolKind::Basic) {
+        return Err(anyhow::anyhow!("{} does not record cumulative reserves", pool_info.name));
+    }
+    let pool = pool_info.address;
+
+    let current = call_contract(provider, pool, IAerodromePool::currentCumulativePricesCall {}).await?;
+    let now = CumulativeObservation {
+        timestamp: current.blockTimestamp.saturating_to::<u64>(),
+        reserve0_cumulative: current.reserve0Cumulative,
+        reserve1_cumulative: current.reserve1Cumulative,
+    };
+
+    let mut start = CumulativeObservation::from(
+        call_contract(provider, pool, IAerodromePool::lastObservationCall {}).await?._0,
+    );
+    if now.timestamp.saturating_sub(start.timestamp) < MIN_OBSERVATION_SPAN_SECS {
+        let length = call_contract(provider, pool, IAerodromePool::observationLengthCall {}).await?._0;
+        if length < U256::from(2) {
+            return Err(anyhow::anyhow!("{} has no earlier observation", pool_info.name));
+        }
+        let index = length - U256::from(2);
+        let observation = call_contract(provider, pool, IAerodromePool::observationsCall { index }).await?;
+        start = CumulativeObservation {
+            timestamp: observation.timestamp.saturating_to::<u64>(),
+            reserve0_cumulative: observation.reserve0Cumulative,
+            reserve1_cumulative: observation.reserve1Cumulative,
+        };
+    }
+
+    observation_twap_price(pool_info, &start, &now)
+        .ok_or_else(|| anyhow::anyhow!("No TWAP available for {}", pool_info.name))
+}
@@ -2,35 +2,46 @@
 
 use alloy::{primitives::Address, primitives::U256};
 use anyhow::Result;
+use rust_decimal_macros::dec;
 use std::sync::Arc;
 use tracing::info;
 use crate::{
+    config::Config,
     network::retry::{retry_with_backoff, RetryConfig},
-    pools::{get_pool_info_internal, get_pool_reserves},
-    types::PoolInfo,
+    pools::{get_pool_info_internal, get_pool_reserves_for_kind},
+    types::{PoolInfo, StablePrices},
     ConcreteProvider,
 };
 
+/// Validate a pool's tokens and liquidity. `base` pins the base token (else
+/// it is matched by symbol from the name); a non-empty `quotes` restricts the
+/// quote token to those addresses.
 pub async fn validate_pool_with_retry(
     provider: &Arc<ConcreteProvider>,
     name: &str,
     address: Address,
-    weth_addr: Address,
-    usdc_addr: Address,
-    usdbc_addr: Address,
+    concentrated: bool,
+    base: Option<Address>,
+    quotes: &[Address],
+    config: &Config,
 ) -> Result<PoolInfo> {
     retry_with_backoff(
         || async {
-            let pool_info = get_pool_info_internal(provider.as_ref(), name, address).await?;
+            let pool_info = get_pool_info_internal(provider.as_ref(), name, address, concentrated, base, config).await?;
+            let pair = &pool_info.pair;
             
-            // Validate it's a WETH/USD pool
-            if !((pool_info.token0 == weth_addr || pool_info.token1 == weth_addr) &&
-                 (pool_info.token0 == usdc_addr || pool_info.token1 == usdc_addr || 
-                  pool_info.token0 == usdbc_addr || pool_info.token1 == usdbc_addr)) {
-                return Err(anyhow::anyhow!("Not a WETH/USD pool"));
+            if !quotes.is_empty() && !quotes.contains(&pair.quote) {
+                return Err(anyhow::anyhow!("Unexpected quote token for {} pool", pair));
+            }
+            // Prices are compared in USD, so both quote assets need a USD reference
+            if pair.quote_usd(dec!(1), &StablePrices::new()).is_none() && !config.stable_prices.contains_key(&pair.quote_symbol) {
+                return Err(anyhow::anyhow!("Quote token {} has no USD reference (use a USD stable or WETH, or add it to STABLE_PRICES)", pair.quote_symbol));
+            }
+            if pair.cex_quote_usd(dec!(1)).is_none() {
+                return Err(anyhow::anyhow!("CEX symbol {} is not quoted in USD or ETH", pair.cex_symbol));
             }
             
-            let (r0, r1) = get_pool_reserves(provider.as_ref(), pool_info.address).await?;
+            let (r0, r1) = get_pool_reserves_for_kind(provider.as_ref(), pool_info.address, pool_info.kind).await?;
             if r0 == U256::from(0) || r1 == U256::from(0) {
                 return Err(anyhow::anyhow!("Pool has zero liquidity"));
             }
@@ -45,35 +56,51 @@ pub async fn validate_pool_with_retry(
 
 pub async fn initialize_and_validate_pools(
     provider: &Arc<ConcreteProvider>,
-    config: &crate::config::Config,
+    config: &Config,
 ) -> Result<Vec<PoolInfo>> {
     use crate::types::{
-        POOLS_MAINNET, POOLS_SEPOLIA,
+        POOLS_MAINNET, POOLS_SEPOLIA, CL_POOLS_MAINNET,
         WETH_MAINNET, USDC_MAINNET, USDBC_MAINNET,
         WETH_SEPOLIA, USDC_SEPOLIA,
     };
     
     // Determine which pools to use based on network
-    let pools_to_validate = if config.network == "mainnet" {
-        POOLS_MAINNET
+    let pools_to_validate: Vec<(&str, Address, bool)> = if config.network == "mainnet" {
+        POOLS_MAINNET.iter()
+            .map(|(name, address)| (*name, *address, false))
+            .chain(CL_POOLS_MAINNET.iter().map(|(name, address)| (*name, *address, true)))
+            .collect()
     } else {
-        POOLS_SEPOLIA
+        POOLS_SEPOLIA.iter()
+            .map(|(name, address)| (*name, *address, false))
+            .collect()
     };
+    let pools_to_validate: Vec<_> = pools_to_validate.into_iter()
+        .filter(|(name, _, _)| config.pool_enabled(name))
+        .collect();
     
-    let (weth_addr, usdc_addr, usdbc_addr) = if config.network == "mainnet" {
-        (WETH_MAINNET, USDC_MAINNET, USDBC_MAINNET)
+    let (weth_addr, usd_addrs) = if config.network == "mainnet" {
+        (WETH_MAINNET, vec![USDC_MAINNET, USDBC_MAINNET])
     } else {
-        (WETH_SEPOLIA, USDC_SEPOLIA, USDC_SEPOLIA) // Use USDC for both on Sepolia
+        (WETH_SEPOLIA, vec![USDC_SEPOLIA])
     };
     
+    // Built-in pools are pinned to WETH/USD; custom pools resolve their pair by symbol
+    let pools_to_validate: Vec<(&str, Address, bool, Option<Address>, &[Address])> = pools_to_validate.into_iter()
+        .map(|(name, address, concentrated)| (name, address, concentrated, Some(weth_addr), usd_addrs.as_slice()))
+        .chain(config.custom_pools.iter()
+            .filter(|(name, _)| config.pool_enabled(name))
+            .map(|(name, address)| (name.as_str(), *address, false, None, &[][..])))
+        .collect();
+    
     info!("\n🔍 Validating Aerodrome pools on {}...", config.network);
     let mut valid_pools = Vec::new();
     let mut pool_errors = 0;
     
-    for (name, address) in pools_to_validate {
-        match validate_pool_with_retry(provider, name, *address, weth_addr, usdc_addr, usdbc_addr).await {
+    for (name, address, concentrated, base, quotes) in &pools_to_validate {
+        match validate_pool_with_retry(provider, name, *address, *concentrated, *base, quotes, config).await {
             Ok(pool_info) => {
-                info!("✅ {} - Valid WETH/USD pool", name);
+                info!("✅ {} - Valid {} pool (CEX {})", name, pool_info.pair, pool_info.pair.cex_symbol);
                 valid_pools.push(pool_info);
             }
             Err(e) => {
@@ -0,0 +1,46 @@
+//! Wallet and CEX balance queries
+
+use alloy::{
+    primitives::Address,
+    providers::Provider,
+};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use crate::{
+    network::{call_contract, IERC20},
+    pools::token_decimals,
+    utils::scale_from_raw,
+};
+
+/// ERC20 `balanceOf(owner)` via eth_call, scaled by the token's decimals
+pub async fn get_token_balance(provider: &dyn Provider, token: Address, owner: Address) -> Result<Decimal> {
+    let raw = call_contract(provider, token, IERC20::balanceOfCall { account: owner }).await
+        .with_context(|| format!("Failed to read balance of {:?}", token))?._0;
+    scale_from_raw(raw, token_decimals(provider, token).await?)
+        .ok_or_else(|| anyhow::anyhow!("Balance out of range for {:?}", token))
+}
+
+/// Base and quote balances held in one venue
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VenueBalances {
+    pub weth: Decimal,
+    pub usd: Decimal,
+}
+
+/// Balances per venue. Only the wallet counts as inventory: Binance Spot
+/// Testnet funds are not real, so they are reported but never summed in.
+#[derive(Debug, Clone, Serialize)]
+pub struct PortfolioSnapshot {
+    pub timestamp: DateTime<Utc>,
+    pub wallet: Option<VenueBalances>,
+    pub cex_testnet: Option<VenueBalances>,
+}
+
+impl PortfolioSnapshot {
+    /// Balances market making and the session P&L run on; None without a wallet
+    pub fn inventory(&self) -> Option<&VenueBalances> {
+        self.wallet.as_ref()
+    }
+}
@@ -0,0 +1,12 @@
+//! Portfolio and inventory tracking from on-chain and CEX balances, and the
+//! paper-trading ledger
+
+pub mod balances;
+pub mod tracker;
+pub mod valuation;
+pub mod paper;
+
+pub use balances::*;
+pub use tracker::*;
+pub use valuation::*;
+pub use paper::*;
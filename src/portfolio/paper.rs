@@ -0,0 +1,176 @@
+//! Paper-trading ledger of simulated WETH and USDC balances
+//!
+//! Starts from `PAPER_STARTING_WETH` and `PAPER_STARTING_USDC` at the first
+//! CEX price, applies each WETH/USD execution's DEX and CEX fills plus gas
+//! and transfer costs, and is marked to the CEX price every cycle for
+//! drawdown and the Sharpe ratio. Both venues share one balance.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::{
+    portfolio::InventoryMark,
+    types::{ArbitrageOpportunity, ExecutionStatus, TradeExecution, TradeType},
+};
+
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 3600.0;
+
+/// Running sums of the per-mark returns
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ReturnStats {
+    count: u64,
+    sum: f64,
+    sum_sq: f64,
+    elapsed_secs: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperPortfolio {
+    pub weth: Decimal,
+    pub usdc: Decimal,
+    /// Starting balances and the price they were first marked at
+    pub start: InventoryMark,
+    /// Cash P&L of executions, after gas and transfer costs
+    pub realized_pnl_usd: Decimal,
+    pub fees_usd: Decimal,
+    pub trades: u64,
+    pub peak_value_usd: Decimal,
+    pub max_drawdown_usd: Decimal,
+    returns: ReturnStats,
+    last_mark: Option<(DateTime<Utc>, Decimal)>,
+}
+
+/// Ledger valuation at one price
+#[derive(Debug, Clone, Serialize)]
+pub struct PaperPortfolioReport {
+    pub timestamp: DateTime<Utc>,
+    pub weth: Decimal,
+    pub usdc: Decimal,
+    pub price: Decimal,
+    pub value_usd: Decimal,
+    pub start_value_usd: Decimal,
+    pub total_pnl_usd: Decimal,
+    pub realized_pnl_usd: Decimal,
+    /// Total minus realized: open legs and the price move on held WETH
+    pub unrealized_pnl_usd: Decimal,
+    pub fees_usd: Decimal,
+    pub trades: u64,
+    pub drawdown_usd: Decimal,
+    pub max_drawdown_usd: Decimal,
+    /// Annualized from the per-cycle returns; None before two marks or without variance
+    pub sharpe_ratio: Option<f64>,
+}
+
+impl PaperPortfolio {
+    pub fn new(weth: Decimal, usdc: Decimal, price: Decimal) -> Self {
+        let start = InventoryMark { weth, usd: usdc, price };
+        Self {
+            weth,
+            usdc,
+            peak_value_usd: start.value_usd(),
+            start,
+            realized_pnl_usd: Decimal::ZERO,
+            fees_usd: Decimal::ZERO,
+            trades: 0,
+            max_drawdown_usd: Decimal::ZERO,
+            returns: ReturnStats::default(),
+            last_mark: None,
+        }
+    }
+
+    pub fn value_usd(&self, price: Decimal) -> Decimal {
+        self.weth * price + self.usdc
+    }
+
+    /// Book an execution's fills. Failed executions and those without a DEX
+    /// fill change nothing; a missing CEX fill leaves the DEX leg open.
+    /// Returns whether the ledger changed.
+    pub fn apply_execution(&mut self, execution: &TradeExecution, opportunity: &ArbitrageOpportunity) -> bool {
+        if matches!(execution.status, ExecutionStatus::Failed) {
+            return false;
+        }
+        let Some(dex_fill) = execution.dex_fill_price else {
+            return false;
+        };
+        let size = opportunity.size_eth;
+        // WETH bought on the DEX leg (negative when sold there)
+        let dex_weth = match execution.trade_type {
+            TradeType::BuyDexSellCex => size,
+            TradeType::BuyCexSellDex => -size,
+        };
+        self.weth += dex_weth;
+        self.usdc -= dex_weth * dex_fill;
+
+        let mut fees = opportunity.gas_cost_usd;
+        let mut cash_pnl = Decimal::ZERO;
+        if let Some(cex_fill) = execution.cex_fill_price {
+            self.weth -= dex_weth;
+            self.usdc += dex_weth * cex_fill;
+            cash_pnl = dex_weth * (cex_fill - dex_fill);
+            fees += opportunity.transfer_cost_usd;
+        }
+        self.usdc -= fees;
+        self.fees_usd += fees;
+        self.realized_pnl_usd += cash_pnl - fees;
+        self.trades += 1;
+        true
+    }
+
+    /// Value the ledger at `price`, updating the drawdown and return series
+    pub fn mark(&mut self, price: Decimal, at: DateTime<Utc>) {
+        let value = self.value_usd(price);
+        self.peak_value_usd = self.peak_value_usd.max(value);
+        self.max_drawdown_usd = self.max_drawdown_usd.max(self.peak_value_usd - value);
+
+        if let Some((last_at, last_value)) = self.last_mark {
+            let elapsed_secs = (at - last_at).num_milliseconds() as f64 / 1000.0;
+            if let (Some(value), Some(last_value)) = (value.to_f64(), last_value.to_f64()) {
+                if last_value > 0.0 && elapsed_secs > 0.0 {
+                    let ret = value / last_value - 1.0;
+                    self.returns.count += 1;
+                    self.returns.sum += ret;
+                    self.returns.sum_sq += ret * ret;
+                    self.returns.elapsed_secs += elapsed_secs;
+                }
+            }
+        }
+        self.last_mark = Some((at, value));
+    }
+
+    pub fn sharpe_ratio(&self) -> Option<f64> {
+        let stats = &self.returns;
+        if stats.count < 2 {
+            return None;
+        }
+        let n = stats.count as f64;
+        let mean = stats.sum / n;
+        let variance = (stats.sum_sq - n * mean * mean) / (n - 1.0);
+        if variance <= 0.0 {
+            return None;
+        }
+        let periods_per_year = SECONDS_PER_YEAR / (stats.elapsed_secs / n);
+        Some(mean / variance.sqrt() * periods_per_year.sqrt())
+    }
+
+    pub fn report(&self, price: Decimal) -> PaperPortfolioReport {
+        let value_usd = self.value_usd(price);
+        let start_value_usd = self.start.value_usd();
+        let total_pnl_usd = value_usd - start_value_usd;
+        PaperPortfolioReport {
+            timestamp: Utc::now(),
+            weth: self.weth,
+            usdc: self.usdc,
+            price,
+            value_usd,
+            start_value_usd,
+            total_pnl_usd,
+            realized_pnl_usd: self.realized_pnl_usd,
+            unrealized_pnl_usd: total_pnl_usd - self.realized_pnl_usd,
+            fees_usd: self.fees_usd,
+            trades: self.trades,
+            drawdown_usd: (self.peak_value_usd - value_usd).max(Decimal::ZERO),
+            max_drawdown_usd: self.max_drawdown_usd,
+            sharpe_ratio: self.sharpe_ratio(),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Portfolio tracker feeding real inventory into the market making engine
+
+use alloy::{
+    primitives::Address,
+    providers::Provider,
+    signers::local::PrivateKeySigner,
+};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use crate::{
+    config::Config,
+    execution::BinanceTestnetClient,
+    portfolio::{get_token_balance, PortfolioSnapshot, VenueBalances},
+    types::{USDBC_MAINNET, USDC_MAINNET, WETH_MAINNET},
+};
+
+/// Minimum time between balance refreshes
+pub const PORTFOLIO_REFRESH_SECS: u64 = 30;
+
+pub struct PortfolioTracker {
+    wallet: Option<Address>,
+    cex_testnet: Option<BinanceTestnetClient>,
+    latest: RwLock<Option<(Instant, PortfolioSnapshot)>>,
+}
+
+impl PortfolioTracker {
+    /// Track the configured wallet (WALLET_ADDRESS, else the PRIVATE_KEY address)
+    /// and Binance testnet account. Returns None when neither is configured.
+    /// Testnet balances are reported alongside the wallet, not as inventory.
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        let wallet = match (&config.wallet_address, &config.private_key) {
+            (Some(address), _) => Some(Address::from_str(address).context("Invalid WALLET_ADDRESS")?),
+            (None, Some(pk)) => Some(PrivateKeySigner::from_str(pk).context("Failed to parse private key")?.address()),
+            (None, None) => None,
+        };
+        let cex_testnet = BinanceTestnetClient::from_config(config)?;
+
+        if wallet.is_none() && cex_testnet.is_none() {
+            return Ok(None);
+        }
+        info!("💼 Portfolio tracking: wallet={:?}, cex_testnet={}", wallet, cex_testnet.is_some());
+        Ok(Some(Self {
+            wallet,
+            cex_testnet,
+            latest: RwLock::new(None),
+        }))
+    }
+
+    pub async fn latest(&self) -> Option<PortfolioSnapshot> {
+        self.latest.read().await.as_ref().map(|(_, snapshot)| snapshot.clone())
+    }
+
+    /// Refresh balances if the last snapshot is older than PORTFOLIO_REFRESH_SECS
+    pub async fn refresh_if_stale(&self, provider: &dyn Provider) -> Result<PortfolioSnapshot> {
+        if let Some((fetched_at, snapshot)) = self.latest.read().await.as_ref() {
+            if fetched_at.elapsed() < Duration::from_secs(PORTFOLIO_REFRESH_SECS) {
+                return Ok(snapshot.clone());
+            }
+        }
+        self.refresh(provider).await
+    }
+
+    pub async fn refresh(&self, provider: &dyn Provider) -> Result<PortfolioSnapshot> {
+        let wallet = match self.wallet {
+            Some(owner) => Some(VenueBalances {
+                weth: get_token_balance(provider, WETH_MAINNET, owner).await?,
+                usd: get_token_balance(provider, USDC_MAINNET, owner).await?
+                    + get_token_balance(provider, USDBC_MAINNET, owner).await?,
+            }),
+            None => None,
+        };
+
+        // A CEX outage shouldn't hide on-chain inventory
+        let cex_testnet = match &self.cex_testnet {
+            Some(client) => match client.get_balances().await {
+                Ok(balances) => Some(balances),
+                Err(e) => {
+                    warn!("Failed to fetch CEX balances: {}", e);
+                    self.latest().await.and_then(|s| s.cex_testnet)
+                }
+            },
+            None => None,
+        };
+
+        let snapshot = PortfolioSnapshot {
+            timestamp: Utc::now(),
+            wallet,
+            cex_testnet,
+        };
+        if let Some(wallet) = &snapshot.wallet {
+            info!("💼 Inventory: {:.4} WETH, ${:.2} USD", wallet.weth, wallet.usd);
+        }
+        if let Some(cex) = &snapshot.cex_testnet {
+            info!("💼 Binance testnet (not inventory): {:.4} ETH, ${:.2} USD", cex.weth, cex.usd);
+        }
+        *self.latest.write().await = Some((Instant::now(), snapshot.clone()));
+        Ok(snapshot)
+    }
+}
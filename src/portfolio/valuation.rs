@@ -0,0 +1,94 @@
+//! End-of-session inventory valuation and hedging status
+
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use crate::types::InventorySource;
+
+/// Inventory at a point in time, valued at `price`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryMark {
+    pub weth: Decimal,
+    pub usd: Decimal,
+    pub price: Decimal,
+}
+
+impl InventoryMark {
+    pub fn value_usd(&self) -> Decimal {
+        self.weth * self.price + self.usd
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum HedgeStatus {
+    /// WETH share within the rebalance threshold of target
+    Hedged,
+    /// Too much WETH: sell `hedge_amount_eth` to return to target
+    OverExposed,
+    /// Too little WETH: buy `hedge_amount_eth` to return to target
+    UnderExposed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InventoryReport {
+    pub timestamp: DateTime<Utc>,
+    pub source: InventorySource,
+    pub start: Option<InventoryMark>,
+    pub end: InventoryMark,
+    pub end_value_usd: Decimal,
+    /// End value minus start value (None without a starting mark)
+    pub unrealized_pnl_usd: Option<Decimal>,
+    /// Portion of P&L from the ETH price move on the starting WETH balance
+    pub price_move_pnl_usd: Option<Decimal>,
+    pub weth_ratio: Decimal,
+    pub target_weth_ratio: Decimal,
+    pub hedge_status: HedgeStatus,
+    pub hedge_amount_eth: Decimal,
+}
+
+pub fn value_inventory(
+    source: InventorySource,
+    start: Option<InventoryMark>,
+    end: InventoryMark,
+    target_weth_ratio: Decimal,
+    rebalance_threshold: Decimal,
+) -> InventoryReport {
+    let end_value_usd = end.value_usd();
+    let weth_ratio = if end_value_usd > dec!(0) {
+        end.weth * end.price / end_value_usd
+    } else {
+        target_weth_ratio
+    };
+
+    let ratio_diff = weth_ratio - target_weth_ratio;
+    let hedge_amount_eth = if end.price > dec!(0) {
+        (ratio_diff.abs() * end_value_usd / end.price).round_dp(6)
+    } else {
+        dec!(0)
+    };
+    let hedge_status = if ratio_diff.abs() <= rebalance_threshold {
+        HedgeStatus::Hedged
+    } else if ratio_diff > dec!(0) {
+        HedgeStatus::OverExposed
+    } else {
+        HedgeStatus::UnderExposed
+    };
+
+    let unrealized_pnl_usd = start.as_ref().map(|s| end_value_usd - s.value_usd());
+    let price_move_pnl_usd = start.as_ref().map(|s| s.weth * (end.price - s.price));
+
+    InventoryReport {
+        timestamp: Utc::now(),
+        source,
+        start,
+        end,
+        end_value_usd,
+        unrealized_pnl_usd,
+        price_move_pnl_usd,
+        weth_ratio,
+        target_weth_ratio,
+        hedge_status,
+        hedge_amount_eth,
+    }
+}
@@ -0,0 +1,98 @@
+//! Custom opportunity validators and position sizers
+//!
+//! Implement [`OpportunityValidator`] or [`PositionSizer`] and register them
+//! on [`RiskHooks`] to add risk logic without changing `validation` or
+//! `market_making`. Custom validators run after the built-in checks and can
+//! only reject; sizers run in registration order, each seeing the previous
+//! sizer's output.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+use crate::types::{ArbitrageOpportunity, PoolHealth, PoolInfo, VolatilityMetrics};
+
+/// Market state available to custom risk logic for one pool
+#[derive(Clone, Copy)]
+pub struct RiskContext<'a> {
+    pub pool: &'a PoolInfo,
+    pub dex_price: Decimal,
+    pub cex_price: Decimal,
+    pub gas_cost_usd: Decimal,
+    pub volatility: &'a VolatilityMetrics,
+    pub pool_health: Option<&'a PoolHealth>,
+}
+
+/// What a position size is being proposed for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizingTarget {
+    /// Arbitrage trade size, before quoting and profit calculation
+    Arbitrage,
+    /// Market-making position size on a generated signal
+    MarketMaking,
+}
+
+pub trait OpportunityValidator: Send + Sync {
+    /// Rule name recorded in decision traces
+    fn name(&self) -> &str;
+
+    /// Return `Err(reason)` to reject the opportunity
+    fn validate(&self, opportunity: &ArbitrageOpportunity, ctx: &RiskContext) -> Result<(), String>;
+}
+
+pub trait PositionSizer: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Size in ETH for `target`, given the size proposed so far. Zero skips the trade.
+    fn size(&self, target: SizingTarget, proposed_eth: Decimal, ctx: &RiskContext) -> Decimal;
+}
+
+/// Outcome of one custom validator
+#[derive(Debug, Clone)]
+pub struct ValidatorVerdict {
+    pub validator: String,
+    pub rejection: Option<String>,
+}
+
+/// Registered custom validators and sizers
+#[derive(Clone, Default)]
+pub struct RiskHooks {
+    validators: Vec<Arc<dyn OpportunityValidator>>,
+    sizers: Vec<Arc<dyn PositionSizer>>,
+}
+
+impl RiskHooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_validator(mut self, validator: impl OpportunityValidator + 'static) -> Self {
+        self.validators.push(Arc::new(validator));
+        self
+    }
+
+    pub fn with_sizer(mut self, sizer: impl PositionSizer + 'static) -> Self {
+        self.sizers.push(Arc::new(sizer));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty() && self.sizers.is_empty()
+    }
+
+    /// Run every validator, so traces show all rejections rather than the first
+    pub fn validate(&self, opportunity: &ArbitrageOpportunity, ctx: &RiskContext) -> Vec<ValidatorVerdict> {
+        self.validators.iter()
+            .map(|validator| ValidatorVerdict {
+                validator: validator.name().to_string(),
+                rejection: validator.validate(opportunity, ctx).err(),
+            })
+            .collect()
+    }
+
+    /// Apply the sizers in order; negative sizes are treated as zero
+    pub fn size(&self, target: SizingTarget, proposed_eth: Decimal, ctx: &RiskContext) -> Decimal {
+        self.sizers.iter().fold(proposed_eth, |size, sizer| {
+            sizer.size(target, size, ctx).max(dec!(0))
+        })
+    }
+}
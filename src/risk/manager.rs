@@ -0,0 +1,297 @@
+//! Global loss, exposure and execution-rate limits
+//!
+//! Consulted by the execution engine before every trade and by the market
+//! making engine when sizing signals. A breached daily loss or exposure limit,
+//! or a manual halt, stops execution while monitoring carries on. The loss
+//! halt clears at the next UTC day, the exposure halt once open exposure is
+//! back within the limit. An admitted trade holds a [`RiskReservation`] of
+//! its size and one rate slot until it is recorded, so concurrent executions
+//! cannot all pass the same check.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::sync::RwLock;
+use tracing::error;
+use crate::{
+    config::Config,
+    control::ControlSender,
+    types::{ArbitrageOpportunity, ControlEvent, ExecutionStatus, TradeExecution, TradeType},
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RiskLimits {
+    pub max_daily_loss_usd: Decimal,
+    /// Largest net WETH position left open by unhedged legs
+    pub max_open_exposure_eth: Decimal,
+    /// Zero disables the rate limit
+    pub max_executions_per_hour: u32,
+}
+
+/// Current usage against the limits
+#[derive(Debug, Clone, Serialize)]
+pub struct RiskStatus {
+    pub daily_pnl_usd: Decimal,
+    pub open_exposure_eth: Decimal,
+    pub executions_last_hour: u32,
+    /// Why execution is halted, if it is
+    pub halted: Option<String>,
+}
+
+struct RiskState {
+    day: NaiveDate,
+    daily_pnl_usd: Decimal,
+    /// Net WETH bought (positive) or sold on legs without a CEX hedge
+    open_exposure_eth: Decimal,
+    recent_executions: VecDeque<DateTime<Utc>>,
+    manual_halt: Option<String>,
+    tripped: bool,
+}
+
+impl RiskState {
+    fn roll_day(&mut self, now: DateTime<Utc>) {
+        let today = now.date_naive();
+        if self.day != today {
+            self.day = today;
+            self.daily_pnl_usd = Decimal::ZERO;
+        }
+        while self.recent_executions.front().is_some_and(|t| now - *t >= Duration::hours(1)) {
+            self.recent_executions.pop_front();
+        }
+    }
+}
+
+/// Exposure and rate budget held by admitted trades not yet recorded
+#[derive(Debug, Default)]
+struct Reserved {
+    exposure_eth: Decimal,
+    executions: u32,
+}
+
+/// Budget held for one admitted trade; given back when dropped
+#[derive(Debug)]
+pub struct RiskReservation {
+    size_eth: Decimal,
+    reserved: Arc<Mutex<Reserved>>,
+}
+
+impl Drop for RiskReservation {
+    fn drop(&mut self) {
+        let mut reserved = self.reserved.lock().unwrap_or_else(|e| e.into_inner());
+        reserved.exposure_eth -= self.size_eth;
+        reserved.executions = reserved.executions.saturating_sub(1);
+    }
+}
+
+pub struct RiskManager {
+    limits: RiskLimits,
+    state: RwLock<RiskState>,
+    reserved: Arc<Mutex<Reserved>>,
+    control: Option<ControlSender>,
+}
+
+impl RiskManager {
+    pub fn new(limits: RiskLimits) -> Self {
+        Self {
+            limits,
+            state: RwLock::new(RiskState {
+                day: Utc::now().date_naive(),
+                daily_pnl_usd: Decimal::ZERO,
+                open_exposure_eth: Decimal::ZERO,
+                recent_executions: VecDeque::new(),
+                manual_halt: None,
+                tripped: false,
+            }),
+            reserved: Arc::new(Mutex::new(Reserved::default())),
+            control: None,
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(RiskLimits {
+            max_daily_loss_usd: config.max_daily_loss_usd,
+            max_open_exposure_eth: config.max_open_exposure_eth,
+            max_executions_per_hour: config.max_executions_per_hour,
+        })
+    }
+
+    /// Report limit breaches on the control channel
+    pub fn with_control(mut self, control: ControlSender) -> Self {
+        self.control = Some(control);
+        self
+    }
+
+    pub fn limits(&self) -> &RiskLimits {
+        &self.limits
+    }
+
+    fn halt_reason(&self, state: &RiskState) -> Option<String> {
+        if let Some(reason) = &state.manual_halt {
+            return Some(reason.clone());
+        }
+        if -state.daily_pnl_usd >= self.limits.max_daily_loss_usd {
+            return Some(format!(
+                "Daily loss limit reached: ${:.2} (limit ${:.2})",
+                state.daily_pnl_usd, self.limits.max_daily_loss_usd
+            ));
+        }
+        if state.open_exposure_eth.abs() > self.limits.max_open_exposure_eth {
+            return Some(format!(
+                "Open exposure {:.4} ETH exceeds limit {:.4} ETH",
+                state.open_exposure_eth, self.limits.max_open_exposure_eth
+            ));
+        }
+        None
+    }
+
+    pub async fn status(&self) -> RiskStatus {
+        let mut state = self.state.write().await;
+        state.roll_day(Utc::now());
+        RiskStatus {
+            daily_pnl_usd: state.daily_pnl_usd,
+            open_exposure_eth: state.open_exposure_eth,
+            executions_last_hour: state.recent_executions.len() as u32,
+            halted: self.halt_reason(&state),
+        }
+    }
+
+    /// Kill switch: halt execution until [`resume`](Self::resume)
+    pub async fn halt(&self, reason: &str) {
+        self.state.write().await.manual_halt = Some(reason.to_string());
+        self.notify(reason.to_string());
+    }
+
+    /// Clear a manual halt; breached limits still apply
+    pub async fn resume(&self) {
+        let mut state = self.state.write().await;
+        state.manual_halt = None;
+        state.tripped = self.halt_reason(&state).is_some();
+    }
+
+    pub async fn is_halted(&self) -> bool {
+        self.status().await.halted.is_some()
+    }
+
+    /// Reject the trade when halted, at the hourly execution cap, or when a
+    /// failed hedge would leave exposure above the limit. Trades admitted but
+    /// not yet recorded count against both.
+    fn admit(&self, state: &RiskState, reserved: &Reserved, opportunity: &ArbitrageOpportunity) -> Result<(), String> {
+        if let Some(reason) = self.halt_reason(state) {
+            return Err(reason);
+        }
+        let max_per_hour = self.limits.max_executions_per_hour;
+        if max_per_hour > 0 && state.recent_executions.len() as u32 + reserved.executions >= max_per_hour {
+            return Err(format!("Execution rate limit reached: {} in the last hour", max_per_hour));
+        }
+        let open_eth = state.open_exposure_eth.abs() + reserved.exposure_eth;
+        if open_eth + opportunity.size_eth > self.limits.max_open_exposure_eth {
+            return Err(format!(
+                "Trade of {:.4} ETH could take exposure past {:.4} ETH (open {:.4} ETH)",
+                opportunity.size_eth, self.limits.max_open_exposure_eth, open_eth
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether the limits allow executing `opportunity` now, without reserving anything
+    pub async fn can_execute(&self, opportunity: &ArbitrageOpportunity) -> Result<(), String> {
+        let mut state = self.state.write().await;
+        state.roll_day(Utc::now());
+        let reserved = self.reserved.lock().unwrap_or_else(|e| e.into_inner());
+        self.admit(&state, &reserved, opportunity)
+    }
+
+    /// Admit `opportunity` and reserve its size and a rate slot until the
+    /// returned reservation is dropped, after [`record_execution`](Self::record_execution)
+    pub async fn check_execution(&self, opportunity: &ArbitrageOpportunity) -> Result<RiskReservation, String> {
+        let mut state = self.state.write().await;
+        state.roll_day(Utc::now());
+        let mut reserved = self.reserved.lock().unwrap_or_else(|e| e.into_inner());
+        self.admit(&state, &reserved, opportunity)?;
+        reserved.exposure_eth += opportunity.size_eth;
+        reserved.executions += 1;
+        Ok(RiskReservation { size_eth: opportunity.size_eth, reserved: self.reserved.clone() })
+    }
+
+    /// Market-making size allowed for a proposed size: zero when halted,
+    /// otherwise capped to the remaining exposure headroom
+    pub async fn market_making_allowance(&self, proposed_eth: Decimal) -> Decimal {
+        let mut state = self.state.write().await;
+        state.roll_day(Utc::now());
+        if self.halt_reason(&state).is_some() {
+            return Decimal::ZERO;
+        }
+        let reserved_eth = self.reserved.lock().unwrap_or_else(|e| e.into_inner()).exposure_eth;
+        let headroom = (self.limits.max_open_exposure_eth - state.open_exposure_eth.abs() - reserved_eth).max(Decimal::ZERO);
+        proposed_eth.min(headroom)
+    }
+
+    /// Count an execution against the hourly cap and book its realized P&L
+    /// and any unhedged DEX leg. Sends one control event when a limit newly trips.
+    pub async fn record_execution(&self, execution: &TradeExecution, opportunity: &ArbitrageOpportunity) {
+        let mut state = self.state.write().await;
+        state.roll_day(Utc::now());
+        state.recent_executions.push_back(execution.timestamp);
+        state.daily_pnl_usd += realized_pnl_usd(execution, opportunity);
+
+        let failed = matches!(execution.status, ExecutionStatus::Failed);
+        if !failed && execution.dex_fill_price.is_some() && !is_hedged(execution) {
+            state.open_exposure_eth += match execution.trade_type {
+                TradeType::BuyDexSellCex => opportunity.size_eth,
+                TradeType::BuyCexSellDex => -opportunity.size_eth,
+            };
+        }
+
+        let reason = self.halt_reason(&state);
+        let newly_tripped = reason.is_some() && !state.tripped;
+        state.tripped = reason.is_some();
+        drop(state);
+        if let (true, Some(reason)) = (newly_tripped, reason) {
+            self.notify(reason);
+        }
+    }
+
+    fn notify(&self, reason: String) {
+        error!("🛑 Execution halted by risk manager: {}", reason);
+        if let Some(control) = &self.control {
+            control.send(ControlEvent::ExecutionHalted { reason });
+        }
+    }
+}
+
+/// A real DEX leg is hedged only by a placed CEX order; a simulated one also
+/// by its modeled fill
+fn is_hedged(execution: &TradeExecution) -> bool {
+    execution.cex_order_id.is_some() || (execution.simulated && execution.cex_fill_price.is_some())
+}
+
+/// P&L an execution actually realized. Simulated executions book their
+/// simulated result. A real one books its two-leg result only when a CEX
+/// order filled; an unhedged DEX leg books its gas and its slippage against
+/// the quoted price, and a failed transaction that was sent books its gas.
+pub fn realized_pnl_usd(execution: &TradeExecution, opportunity: &ArbitrageOpportunity) -> Decimal {
+    if execution.simulated {
+        return execution.actual_profit_usd.unwrap_or_default();
+    }
+    // Nothing reached the chain when the preflight reverted or never ran
+    let sent = execution.tx_hash.is_some() || execution.preflight.as_ref().is_some_and(|p| !p.reverted());
+    if !sent {
+        return Decimal::ZERO;
+    }
+    if matches!(execution.status, ExecutionStatus::Failed) {
+        return -opportunity.gas_cost_usd;
+    }
+    if execution.cex_order_id.is_some() {
+        if let Some(profit) = execution.actual_profit_usd {
+            return profit;
+        }
+    }
+    let quoted = opportunity.quoted_dex_price.unwrap_or(opportunity.dex_price);
+    let slippage_usd = execution.dex_fill_price.map_or(Decimal::ZERO, |fill| match execution.trade_type {
+        TradeType::BuyDexSellCex => quoted - fill,
+        TradeType::BuyCexSellDex => fill - quoted,
+    }) * opportunity.size_eth;
+    slippage_usd - opportunity.gas_cost_usd
+}
@@ -0,0 +1,7 @@
+//! Global risk limits and pluggable risk logic for library users
+
+pub mod hooks;
+pub mod manager;
+
+pub use hooks::*;
+pub use manager::*;
@@ -0,0 +1,190 @@
+//! Periodic, end-of-day and end-of-epoch storage checkpoints
+//!
+//! A checkpoint fsyncs the daily JSONL outputs and the SQLite database,
+//! verifies each file (line count, every line parses as JSON, no lines lost
+//! since the last checkpoint), and appends a record with a snapshot of the
+//! session statistics to `output/checkpoints/checkpoints.jsonl`.
+
+use anyhow::Result;
+use chrono::{Datelike, NaiveDate, Utc, Weekday};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+use crate::types::{Checkpoint, CheckpointKind, FileIntegrity, SessionSummary};
+
+const CHECKPOINT_LOG_PATH: &str = "output/checkpoints/checkpoints.jsonl";
+/// Invalid line numbers listed per file before only counting
+const MAX_REPORTED_INVALID_LINES: usize = 10;
+
+/// Daily JSONL outputs as (directory, file prefix)
+const DAILY_OUTPUTS: &[(&str, &str)] = &[
+    ("output/opportunities", "arbitrage_"),
+    ("output/market_making", "signals_"),
+    ("output/executions", "trades_"),
+    ("output/executions", "reconciled_"),
+    ("output/decisions", "decisions_"),
+    ("output/lifecycles", "lifecycles_"),
+];
+
+/// Aerodrome epochs flip weekly at Thursday 00:00 UTC
+const EPOCH_START_WEEKDAY: Weekday = Weekday::Thu;
+
+fn daily_output_paths(date: NaiveDate) -> Vec<String> {
+    DAILY_OUTPUTS.iter()
+        .map(|(dir, prefix)| format!("{}/{}{}.jsonl", dir, prefix, date.format("%Y-%m-%d")))
+        .collect()
+}
+
+/// Fsync `path` and check every line parses as JSON
+fn verify_jsonl(path: &str, previous_lines: Option<u64>) -> Result<FileIntegrity> {
+    let file = File::open(path)?;
+    file.sync_all()?;
+    let bytes = file.metadata()?.len();
+
+    let mut lines = 0;
+    let mut invalid_lines = Vec::new();
+    let mut invalid_line_count = 0;
+    for line in BufReader::new(file).lines() {
+        lines += 1;
+        let valid = line.ok()
+            .is_some_and(|l| serde_json::from_str::<serde_json::Value>(&l).is_ok());
+        if !valid {
+            invalid_line_count += 1;
+            if invalid_lines.len() < MAX_REPORTED_INVALID_LINES {
+                invalid_lines.push(lines);
+            }
+        }
+    }
+
+    Ok(FileIntegrity {
+        path: path.to_string(),
+        lines,
+        bytes,
+        invalid_lines,
+        invalid_line_count,
+        previous_lines,
+        truncated: previous_lines.is_some_and(|prev| lines < prev),
+    })
+}
+
+pub struct Checkpointer {
+    interval: Option<Duration>,
+    last_checkpoint: Instant,
+    current_day: NaiveDate,
+    /// Line counts at the last checkpoint, by path
+    line_counts: HashMap<String, u64>,
+}
+
+impl Checkpointer {
+    /// `interval_secs` of 0 disables periodic checkpoints (day/epoch ones still run)
+    pub fn new(interval_secs: u64) -> Self {
+        Self {
+            interval: (interval_secs > 0).then(|| Duration::from_secs(interval_secs)),
+            last_checkpoint: Instant::now(),
+            current_day: Utc::now().date_naive(),
+            line_counts: HashMap::new(),
+        }
+    }
+
+    /// The UTC day whose files are open; a rollover checkpoint closes it
+    pub fn current_day(&self) -> NaiveDate {
+        self.current_day
+    }
+
+    /// The checkpoint due now, if any
+    pub fn due(&self) -> Option<CheckpointKind> {
+        let today = Utc::now().date_naive();
+        if today != self.current_day {
+            return Some(if today.weekday() == EPOCH_START_WEEKDAY {
+                CheckpointKind::EndOfEpoch
+            } else {
+                CheckpointKind::EndOfDay
+            });
+        }
+        self.interval
+            .filter(|interval| self.last_checkpoint.elapsed() >= *interval)
+            .map(|_| CheckpointKind::Interval)
+    }
+
+    /// Sync and verify storage, then append the checkpoint record
+    pub fn run(&mut self, kind: CheckpointKind, session: SessionSummary) -> Result<Checkpoint> {
+        let today = Utc::now().date_naive();
+        // A rollover closes yesterday's files, so verify them one last time
+        let mut dates = vec![self.current_day];
+        if today != self.current_day {
+            dates.push(today);
+        }
+
+        let mut files = Vec::new();
+        for path in dates.iter().flat_map(|date| daily_output_paths(*date)) {
+            if !std::path::Path::new(&path).exists() {
+                continue;
+            }
+            match verify_jsonl(&path, self.line_counts.get(&path).copied()) {
+                Ok(integrity) => {
+                    if !integrity.is_healthy() {
+                        warn!(
+                            "🧾 Checkpoint integrity problem in {}: {} invalid lines {:?}, lines {} (previously {:?})",
+                            path, integrity.invalid_line_count, integrity.invalid_lines,
+                            integrity.lines, integrity.previous_lines
+                        );
+                    }
+                    self.line_counts.insert(path, integrity.lines);
+                    files.push(integrity);
+                }
+                Err(e) => warn!("Failed to verify {}: {}", path, e),
+            }
+        }
+
+        let database_synced = match super::database() {
+            Some(db) => match db.checkpoint() {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("Failed to checkpoint SQLite database: {}", e);
+                    false
+                }
+            },
+            None => false,
+        };
+
+        // Files from closed days are no longer tracked
+        if today != self.current_day {
+            let closed = daily_output_paths(self.current_day);
+            self.line_counts.retain(|path, _| !closed.contains(path));
+        }
+
+        let checkpoint = Checkpoint {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            kind,
+            healthy: files.iter().all(FileIntegrity::is_healthy),
+            dates,
+            files,
+            database_synced,
+            session,
+        };
+
+        if !super::skip_write(CHECKPOINT_LOG_PATH, &checkpoint) {
+            let mut log = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(CHECKPOINT_LOG_PATH)?;
+            writeln!(log, "{}", serde_json::to_string(&checkpoint)?)?;
+            log.sync_all()?;
+        }
+
+        self.last_checkpoint = Instant::now();
+        self.current_day = today;
+
+        info!(
+            kind = ?checkpoint.kind,
+            files = checkpoint.files.len(),
+            healthy = checkpoint.healthy,
+            "🧾 Storage checkpoint written"
+        );
+
+        Ok(checkpoint)
+    }
+}
@@ -0,0 +1,384 @@
+//! Daily summary reports aggregated from the JSONL outputs
+//!
+//! Reads one UTC day's opportunities, market-making signals, executions,
+//! decision traces and paper portfolio snapshots and summarizes them: totals,
+//! potential and realized P&L, best and worst pools, execution success rate,
+//! volatility statistics, the worst stress test outcomes, the paper ledger
+//! at the end of the day and the day's session counters from the lifetime
+//! statistics.
+//! Written at the day rollover and by the `report` command.
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use tracing::debug;
+use crate::types::DayStats;
+
+/// Per-pool totals for one day
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PoolDayStats {
+    pub pool: String,
+    pub opportunities: u64,
+    pub potential_profit_usd: Decimal,
+    pub market_making_signals: u64,
+    pub executions: u64,
+    pub successful_executions: u64,
+    pub realized_pnl_usd: Decimal,
+}
+
+/// Short- and long-term volatility across the day's signals and opportunities, in percent
+#[derive(Debug, Clone, Serialize)]
+pub struct VolatilityDayStats {
+    pub samples: u64,
+    pub mean_short_term_pct: Decimal,
+    pub max_short_term_pct: Decimal,
+    pub mean_long_term_pct: Decimal,
+}
+
+/// Worst and last outcome of one stress scenario across the day's signals
+#[derive(Debug, Clone, Serialize)]
+pub struct StressDayStats {
+    pub scenario: String,
+    pub samples: u64,
+    pub worst_pnl_usd: Decimal,
+    pub worst_pnl_pct: Decimal,
+    pub last_pnl_usd: Decimal,
+}
+
+/// Last paper portfolio snapshot of the day
+#[derive(Debug, Clone, Serialize)]
+pub struct PaperDayStats {
+    pub value_usd: Decimal,
+    pub total_pnl_usd: Decimal,
+    pub realized_pnl_usd: Decimal,
+    pub unrealized_pnl_usd: Decimal,
+    pub max_drawdown_usd: Decimal,
+    pub sharpe_ratio: Option<f64>,
+    pub trades: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyReport {
+    pub date: NaiveDate,
+    pub generated_at: DateTime<Utc>,
+    pub opportunities: u64,
+    pub potential_profit_usd: Decimal,
+    pub best_opportunity_usd: Option<Decimal>,
+    pub market_making_signals: u64,
+    pub mean_spread_bps: Option<Decimal>,
+    pub executions: u64,
+    pub successful_executions: u64,
+    pub failed_executions: u64,
+    pub execution_success_rate_pct: Option<Decimal>,
+    pub expected_profit_usd: Decimal,
+    pub realized_pnl_usd: Decimal,
+    /// Decision trace outcomes (with RECORD_DECISIONS)
+    pub decisions: BTreeMap<String, u64>,
+    /// Ranked by realized P&L on days with executions, otherwise by potential profit
+    pub best_pool: Option<String>,
+    pub worst_pool: Option<String>,
+    /// Best first
+    pub pools: Vec<PoolDayStats>,
+    pub volatility: Option<VolatilityDayStats>,
+    /// Per scenario, from the signals' risk metrics
+    pub stress_tests: Vec<StressDayStats>,
+    pub paper_portfolio: Option<PaperDayStats>,
+    /// Runs, runtime and counters for the day from `stats/lifetime.json`
+    pub sessions: Option<DayStats>,
+    /// Lines that were not valid JSON
+    pub unreadable_lines: u64,
+}
+
+pub(super) fn decimal_field(record: &Value, field: &str) -> Option<Decimal> {
+    match &record[field] {
+        Value::String(s) => Decimal::from_str(s).ok(),
+        Value::Number(n) => Decimal::from_str(&n.to_string()).ok(),
+        _ => None,
+    }
+}
+
+/// Records in a daily JSONL file; a missing file has none
+pub(super) fn read_records(path: &Path, unreadable: &mut u64) -> Vec<Value> {
+    let Ok(file) = fs::File::open(path) else {
+        return Vec::new();
+    };
+    let mut records = Vec::new();
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        match serde_json::from_str(&line) {
+            Ok(record) => records.push(record),
+            Err(e) => {
+                debug!("Skipping unreadable line in {}: {}", path.display(), e);
+                *unreadable += 1;
+            }
+        }
+    }
+    records
+}
+
+fn pool_stats<'a>(pools: &'a mut HashMap<String, PoolDayStats>, pool: &str) -> &'a mut PoolDayStats {
+    pools.entry(pool.to_string())
+        .or_insert_with(|| PoolDayStats { pool: pool.to_string(), ..Default::default() })
+}
+
+/// Summarize `date` from the JSONL files under `data_dir` (normally `output`)
+pub fn build_daily_report(data_dir: &Path, date: NaiveDate) -> Result<DailyReport> {
+    let day = date.format("%Y-%m-%d");
+    let mut unreadable_lines = 0;
+    let opportunities = read_records(&data_dir.join(format!("opportunities/arbitrage_{}.jsonl", day)), &mut unreadable_lines);
+    let signals = read_records(&data_dir.join(format!("market_making/signals_{}.jsonl", day)), &mut unreadable_lines);
+    let executions = read_records(&data_dir.join(format!("executions/trades_{}.jsonl", day)), &mut unreadable_lines);
+    let decisions = read_records(&data_dir.join(format!("decisions/decisions_{}.jsonl", day)), &mut unreadable_lines);
+    let paper = read_records(&data_dir.join(format!("portfolio/paper_{}.jsonl", day)), &mut unreadable_lines);
+
+    let mut pools: HashMap<String, PoolDayStats> = HashMap::new();
+    let mut volatility = Vec::new();
+
+    // Executions record the opportunity id, not the pool
+    let mut opportunity_pools: HashMap<&str, &str> = HashMap::new();
+    let mut potential_profit_usd = Decimal::ZERO;
+    let mut best_opportunity_usd: Option<Decimal> = None;
+    for record in &opportunities {
+        let pool = record["pool"].as_str().unwrap_or("unknown");
+        let net_profit = decimal_field(record, "net_profit_usd").unwrap_or_default();
+        if let Some(id) = record["id"].as_str() {
+            opportunity_pools.insert(id, pool);
+        }
+        let stats = pool_stats(&mut pools, pool);
+        stats.opportunities += 1;
+        stats.potential_profit_usd += net_profit;
+        potential_profit_usd += net_profit;
+        best_opportunity_usd = Some(best_opportunity_usd.map_or(net_profit, |best| best.max(net_profit)));
+        volatility.extend(volatility_sample(&record["volatility_assessment"]));
+    }
+
+    let mut spread_total = Decimal::ZERO;
+    let mut stress_tests: BTreeMap<String, StressDayStats> = BTreeMap::new();
+    for record in &signals {
+        pool_stats(&mut pools, record["pool"].as_str().unwrap_or("unknown")).market_making_signals += 1;
+        spread_total += decimal_field(record, "effective_spread_bps").unwrap_or_default();
+        volatility.extend(volatility_sample(&record["volatility_metrics"]));
+        for test in record["risk_metrics"]["stress_tests"].as_array().into_iter().flatten() {
+            let (Some(scenario), Some(pnl_usd)) = (test["scenario"].as_str(), decimal_field(test, "pnl_usd")) else {
+                continue;
+            };
+            let pnl_pct = decimal_field(test, "pnl_pct").unwrap_or_default();
+            let stats = stress_tests.entry(scenario.to_string()).or_insert_with(|| StressDayStats {
+                scenario: scenario.to_string(),
+                samples: 0,
+                worst_pnl_usd: pnl_usd,
+                worst_pnl_pct: pnl_pct,
+                last_pnl_usd: pnl_usd,
+            });
+            stats.samples += 1;
+            if pnl_usd < stats.worst_pnl_usd {
+                stats.worst_pnl_usd = pnl_usd;
+                stats.worst_pnl_pct = pnl_pct;
+            }
+            stats.last_pnl_usd = pnl_usd;
+        }
+    }
+
+    let (mut successful_executions, mut failed_executions) = (0, 0);
+    let (mut expected_profit_usd, mut realized_pnl_usd) = (Decimal::ZERO, Decimal::ZERO);
+    for record in &executions {
+        let pool = record["opportunity_id"].as_str()
+            .and_then(|id| opportunity_pools.get(id).copied())
+            .unwrap_or("unknown");
+        let succeeded = matches!(record["status"].as_str(), Some("Success" | "Simulated"));
+        let realized = decimal_field(record, "actual_profit_usd").unwrap_or_default();
+        let stats = pool_stats(&mut pools, pool);
+        stats.executions += 1;
+        stats.realized_pnl_usd += realized;
+        if succeeded {
+            stats.successful_executions += 1;
+            successful_executions += 1;
+        } else {
+            failed_executions += 1;
+        }
+        expected_profit_usd += decimal_field(record, "expected_profit_usd").unwrap_or_default();
+        realized_pnl_usd += realized;
+    }
+
+    let mut decision_counts = BTreeMap::new();
+    for record in &decisions {
+        let outcome = record["outcome"].as_str().unwrap_or("unknown").to_string();
+        *decision_counts.entry(outcome).or_insert(0) += 1;
+    }
+
+    let rank_by_realized = !executions.is_empty();
+    let mut pools: Vec<PoolDayStats> = pools.into_values().collect();
+    pools.sort_by(|a, b| {
+        let score = |p: &PoolDayStats| if rank_by_realized { p.realized_pnl_usd } else { p.potential_profit_usd };
+        score(b).cmp(&score(a)).then_with(|| a.pool.cmp(&b.pool))
+    });
+    let ranked: Vec<&PoolDayStats> = pools.iter()
+        .filter(|p| if rank_by_realized { p.executions > 0 } else { p.opportunities > 0 })
+        .collect();
+
+    let count = |n: usize| Decimal::from(n as u64);
+    Ok(DailyReport {
+        date,
+        generated_at: Utc::now(),
+        opportunities: opportunities.len() as u64,
+        potential_profit_usd,
+        best_opportunity_usd,
+        market_making_signals: signals.len() as u64,
+        mean_spread_bps: (!signals.is_empty()).then(|| spread_total / count(signals.len())),
+        executions: executions.len() as u64,
+        successful_executions,
+        failed_executions,
+        execution_success_rate_pct: (!executions.is_empty())
+            .then(|| Decimal::from(successful_executions) * dec!(100) / count(executions.len())),
+        expected_profit_usd,
+        realized_pnl_usd,
+        decisions: decision_counts,
+        best_pool: ranked.first().map(|p| p.pool.clone()),
+        worst_pool: (ranked.len() > 1).then(|| ranked[ranked.len() - 1].pool.clone()),
+        volatility: (!volatility.is_empty()).then(|| VolatilityDayStats {
+            samples: volatility.len() as u64,
+            mean_short_term_pct: volatility.iter().map(|(short, _)| *short).sum::<Decimal>() / count(volatility.len()),
+            max_short_term_pct: volatility.iter().map(|(short, _)| *short).max().unwrap_or_default(),
+            mean_long_term_pct: volatility.iter().map(|(_, long)| *long).sum::<Decimal>() / count(volatility.len()),
+        }),
+        pools,
+        stress_tests: stress_tests.into_values().collect(),
+        paper_portfolio: paper.last().and_then(paper_day_stats),
+        sessions: day_sessions(data_dir, date),
+        unreadable_lines,
+    })
+}
+
+fn day_sessions(data_dir: &Path, date: NaiveDate) -> Option<DayStats> {
+    match super::load_lifetime_stats_from(&data_dir.join("stats/lifetime.json")) {
+        Ok(lifetime) => lifetime?.days.remove(&date),
+        Err(e) => {
+            debug!("No session statistics for {}: {}", date, e);
+            None
+        }
+    }
+}
+
+fn paper_day_stats(snapshot: &Value) -> Option<PaperDayStats> {
+    Some(PaperDayStats {
+        value_usd: decimal_field(snapshot, "value_usd")?,
+        total_pnl_usd: decimal_field(snapshot, "total_pnl_usd")?,
+        realized_pnl_usd: decimal_field(snapshot, "realized_pnl_usd")?,
+        unrealized_pnl_usd: decimal_field(snapshot, "unrealized_pnl_usd")?,
+        max_drawdown_usd: decimal_field(snapshot, "max_drawdown_usd")?,
+        sharpe_ratio: snapshot["sharpe_ratio"].as_f64(),
+        trades: snapshot["trades"].as_u64().unwrap_or_default(),
+    })
+}
+
+/// (short-term, long-term) volatility from a serialized `VolatilityMetrics`
+fn volatility_sample(metrics: &Value) -> Option<(Decimal, Decimal)> {
+    Some((
+        decimal_field(metrics, "short_term_volatility")?,
+        decimal_field(metrics, "long_term_volatility")?,
+    ))
+}
+
+/// Markdown rendering of a daily report
+pub fn render_daily_report_markdown(report: &DailyReport) -> String {
+    let optional = |value: Option<Decimal>, digits: u32| {
+        value.map(|v| v.round_dp(digits).to_string()).unwrap_or_else(|| "–".to_string())
+    };
+    let mut md = String::new();
+    let _ = writeln!(md, "# Daily Report {}\n", report.date);
+    let _ = writeln!(md, "Generated {}\n", report.generated_at.format("%Y-%m-%d %H:%M:%S UTC"));
+
+    let _ = writeln!(md, "## Totals\n");
+    let _ = writeln!(md, "| Metric | Value |\n|---|---|");
+    let _ = writeln!(md, "| Opportunities | {} |", report.opportunities);
+    let _ = writeln!(md, "| Potential profit | ${} |", report.potential_profit_usd.round_dp(2));
+    let _ = writeln!(md, "| Best opportunity | ${} |", optional(report.best_opportunity_usd, 4));
+    let _ = writeln!(md, "| Market-making signals | {} |", report.market_making_signals);
+    let _ = writeln!(md, "| Mean spread | {} bps |", optional(report.mean_spread_bps, 1));
+    let _ = writeln!(md, "| Executions | {} ({} failed) |", report.executions, report.failed_executions);
+    let _ = writeln!(md, "| Execution success rate | {}% |", optional(report.execution_success_rate_pct, 1));
+    let _ = writeln!(md, "| Expected profit | ${} |", report.expected_profit_usd.round_dp(2));
+    let _ = writeln!(md, "| Realized P&L | ${} |", report.realized_pnl_usd.round_dp(2));
+    let _ = writeln!(md, "| Best pool | {} |", report.best_pool.as_deref().unwrap_or("–"));
+    let _ = writeln!(md, "| Worst pool | {} |", report.worst_pool.as_deref().unwrap_or("–"));
+
+    if !report.pools.is_empty() {
+        let _ = writeln!(md, "\n## Pools\n");
+        let _ = writeln!(md, "| Pool | Opportunities | Potential profit | MM signals | Executions | Successful | Realized P&L |");
+        let _ = writeln!(md, "|---|---|---|---|---|---|---|");
+        for pool in &report.pools {
+            let _ = writeln!(
+                md, "| {} | {} | ${} | {} | {} | {} | ${} |",
+                pool.pool, pool.opportunities, pool.potential_profit_usd.round_dp(2), pool.market_making_signals,
+                pool.executions, pool.successful_executions, pool.realized_pnl_usd.round_dp(2)
+            );
+        }
+    }
+
+    if let Some(volatility) = &report.volatility {
+        let _ = writeln!(md, "\n## Volatility\n");
+        let _ = writeln!(md, "| Metric | Value |\n|---|---|");
+        let _ = writeln!(md, "| Samples | {} |", volatility.samples);
+        let _ = writeln!(md, "| Mean 5-minute | {}% |", volatility.mean_short_term_pct.round_dp(2));
+        let _ = writeln!(md, "| Max 5-minute | {}% |", volatility.max_short_term_pct.round_dp(2));
+        let _ = writeln!(md, "| Mean 1-hour | {}% |", volatility.mean_long_term_pct.round_dp(2));
+    }
+
+    if !report.stress_tests.is_empty() {
+        let _ = writeln!(md, "\n## Stress Tests\n");
+        let _ = writeln!(md, "| Scenario | Signals | Worst P&L | Last P&L |\n|---|---|---|---|");
+        for test in &report.stress_tests {
+            let _ = writeln!(
+                md, "| {} | {} | ${} ({}%) | ${} |",
+                test.scenario, test.samples, test.worst_pnl_usd.round_dp(2), test.worst_pnl_pct.round_dp(2),
+                test.last_pnl_usd.round_dp(2)
+            );
+        }
+    }
+
+    if let Some(paper) = &report.paper_portfolio {
+        let _ = writeln!(md, "\n## Paper Portfolio\n");
+        let _ = writeln!(md, "| Metric | Value |\n|---|---|");
+        let _ = writeln!(md, "| Value | ${} |", paper.value_usd.round_dp(2));
+        let _ = writeln!(md, "| Total P&L | ${} |", paper.total_pnl_usd.round_dp(2));
+        let _ = writeln!(md, "| Realized P&L | ${} |", paper.realized_pnl_usd.round_dp(2));
+        let _ = writeln!(md, "| Unrealized P&L | ${} |", paper.unrealized_pnl_usd.round_dp(2));
+        let _ = writeln!(md, "| Max drawdown | ${} |", paper.max_drawdown_usd.round_dp(2));
+        let _ = writeln!(md, "| Sharpe ratio | {} |", paper.sharpe_ratio.map_or("–".to_string(), |s| format!("{:.2}", s)));
+        let _ = writeln!(md, "| Trades | {} |", paper.trades);
+    }
+
+    if let Some(sessions) = &report.sessions {
+        let counters = &sessions.counters;
+        let _ = writeln!(md, "\n## Sessions\n");
+        let _ = writeln!(md, "| Metric | Value |\n|---|---|");
+        let _ = writeln!(md, "| Runs | {} |", sessions.sessions);
+        let _ = writeln!(md, "| Runtime | {:.1} h |", sessions.runtime_secs as f64 / 3600.0);
+        let _ = writeln!(md, "| Opportunities counted | {} ({} profitable) |",
+            counters.total_opportunities, counters.profitable_opportunities);
+        let _ = writeln!(md, "| Executions counted | {} ({} successful) |",
+            counters.total_executions, counters.successful_executions);
+        let _ = writeln!(md, "| Gas-spike deferrals | {} |", counters.deferred_opportunities);
+        let _ = writeln!(md, "| Errors | {} |", sessions.error_counts.values().sum::<u64>());
+    }
+
+    if !report.decisions.is_empty() {
+        let _ = writeln!(md, "\n## Decisions\n");
+        let _ = writeln!(md, "| Outcome | Count |\n|---|---|");
+        for (outcome, count) in &report.decisions {
+            let _ = writeln!(md, "| {} | {} |", outcome, count);
+        }
+    }
+
+    if report.unreadable_lines > 0 {
+        let _ = writeln!(md, "\n{} unreadable lines were skipped.", report.unreadable_lines);
+    }
+    md
+}
@@ -0,0 +1,298 @@
+//! SQLite storage backend supplementing the JSONL files
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection};
+use rust_decimal::prelude::*;
+use std::sync::{Arc, Mutex, RwLock};
+use tracing::info;
+use crate::types::{ArbitrageOpportunity, MarketMakingSignal, OpportunityLifecycle, TradeExecution};
+
+/// Path and handle of the open database
+static DATABASE: RwLock<Option<(String, Arc<StorageDb>)>> = RwLock::new(None);
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS opportunities (
+    id TEXT PRIMARY KEY,
+    timestamp TEXT NOT NULL,
+    pool TEXT NOT NULL,
+    direction TEXT NOT NULL,
+    dex_price REAL NOT NULL,
+    cex_price REAL NOT NULL,
+    price_diff_pct REAL NOT NULL,
+    size_eth REAL NOT NULL,
+    net_profit_usd REAL NOT NULL,
+    roi_pct REAL NOT NULL,
+    validations_passed INTEGER NOT NULL,
+    payload TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_opportunities_timestamp ON opportunities(timestamp);
+CREATE INDEX IF NOT EXISTS idx_opportunities_pool ON opportunities(pool);
+CREATE INDEX IF NOT EXISTS idx_opportunities_profit ON opportunities(net_profit_usd);
+
+CREATE TABLE IF NOT EXISTS signals (
+    id TEXT PRIMARY KEY,
+    timestamp TEXT NOT NULL,
+    pool TEXT NOT NULL,
+    strategy TEXT NOT NULL,
+    fair_value_price REAL NOT NULL,
+    effective_spread_bps INTEGER NOT NULL,
+    position_size_eth REAL NOT NULL,
+    priority TEXT NOT NULL,
+    payload TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_signals_timestamp ON signals(timestamp);
+CREATE INDEX IF NOT EXISTS idx_signals_pool ON signals(pool);
+
+CREATE TABLE IF NOT EXISTS executions (
+    id TEXT PRIMARY KEY,
+    opportunity_id TEXT NOT NULL,
+    timestamp TEXT NOT NULL,
+    network TEXT NOT NULL,
+    status TEXT NOT NULL,
+    expected_profit_usd REAL NOT NULL,
+    actual_profit_usd REAL,
+    payload TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_executions_timestamp ON executions(timestamp);
+CREATE INDEX IF NOT EXISTS idx_executions_opportunity ON executions(opportunity_id);
+
+CREATE TABLE IF NOT EXISTS lifecycles (
+    id TEXT PRIMARY KEY,
+    pool TEXT NOT NULL,
+    direction TEXT NOT NULL,
+    stage TEXT NOT NULL,
+    end_reason TEXT NOT NULL,
+    detected_at TEXT NOT NULL,
+    ended_at TEXT NOT NULL,
+    persisted_ms INTEGER NOT NULL,
+    detected_net_profit_usd REAL NOT NULL,
+    execution_profit_usd REAL,
+    payload TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_lifecycles_detected ON lifecycles(detected_at);
+CREATE INDEX IF NOT EXISTS idx_lifecycles_pool ON lifecycles(pool);
+";
+
+/// Rewrite timestamps stored by earlier versions (`to_rfc3339`, variable
+/// fractional digits and a `+00:00` offset) in the fixed-width format
+const NORMALIZE_TIMESTAMPS: &str = "
+UPDATE opportunities SET timestamp = strftime('%Y-%m-%dT%H:%M:%fZ', timestamp)
+    WHERE timestamp NOT LIKE '%Z' AND strftime('%Y-%m-%dT%H:%M:%fZ', timestamp) IS NOT NULL;
+UPDATE signals SET timestamp = strftime('%Y-%m-%dT%H:%M:%fZ', timestamp)
+    WHERE timestamp NOT LIKE '%Z' AND strftime('%Y-%m-%dT%H:%M:%fZ', timestamp) IS NOT NULL;
+UPDATE executions SET timestamp = strftime('%Y-%m-%dT%H:%M:%fZ', timestamp)
+    WHERE timestamp NOT LIKE '%Z' AND strftime('%Y-%m-%dT%H:%M:%fZ', timestamp) IS NOT NULL;
+UPDATE lifecycles SET detected_at = strftime('%Y-%m-%dT%H:%M:%fZ', detected_at)
+    WHERE detected_at NOT LIKE '%Z' AND strftime('%Y-%m-%dT%H:%M:%fZ', detected_at) IS NOT NULL;
+UPDATE lifecycles SET ended_at = strftime('%Y-%m-%dT%H:%M:%fZ', ended_at)
+    WHERE ended_at NOT LIKE '%Z' AND strftime('%Y-%m-%dT%H:%M:%fZ', ended_at) IS NOT NULL;
+";
+
+/// Summary row returned by opportunity queries
+#[derive(Debug, Clone)]
+pub struct OpportunityRow {
+    pub id: String,
+    pub timestamp: DateTime<Utc>,
+    pub pool: String,
+    pub direction: String,
+    pub net_profit_usd: f64,
+    pub roi_pct: f64,
+    pub payload: String,
+}
+
+pub struct StorageDb {
+    conn: Mutex<Connection>,
+}
+
+fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(0.0)
+}
+
+/// UTC with milliseconds and a `Z` suffix: fixed width, so comparing the
+/// text compares the times
+fn db_timestamp(at: &DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+impl StorageDb {
+    pub fn open(path: &str) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open SQLite database {}", path))?;
+        conn.execute_batch(SCHEMA)
+            .context("Failed to initialize SQLite schema")?;
+        conn.execute_batch(NORMALIZE_TIMESTAMPS)
+            .context("Failed to normalize SQLite timestamps")?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn insert_opportunity(&self, opp: &ArbitrageOpportunity) -> Result<()> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO opportunities
+             (id, timestamp, pool, direction, dex_price, cex_price, price_diff_pct, size_eth,
+              net_profit_usd, roi_pct, validations_passed, payload)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                opp.id,
+                db_timestamp(&opp.timestamp),
+                opp.pool,
+                opp.direction,
+                to_f64(opp.dex_price),
+                to_f64(opp.cex_price),
+                to_f64(opp.price_diff_pct),
+                to_f64(opp.size_eth),
+                to_f64(opp.net_profit_usd),
+                to_f64(opp.roi_pct),
+                opp.validation_checks.all_passed,
+                serde_json::to_string(opp)?,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_signal(&self, signal: &MarketMakingSignal) -> Result<()> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO signals
+             (id, timestamp, pool, strategy, fair_value_price, effective_spread_bps,
+              position_size_eth, priority, payload)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                signal.id,
+                db_timestamp(&signal.timestamp),
+                signal.pool,
+                format!("{:?}", signal.strategy.strategy_type),
+                to_f64(signal.fair_value_price),
+                signal.effective_spread_bps,
+                to_f64(signal.position_size_eth),
+                format!("{:?}", signal.execution_priority),
+                serde_json::to_string(signal)?,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_execution(&self, execution: &TradeExecution) -> Result<()> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO executions
+             (id, opportunity_id, timestamp, network, status, expected_profit_usd,
+              actual_profit_usd, payload)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                execution.id,
+                execution.opportunity_id,
+                db_timestamp(&execution.timestamp),
+                execution.network,
+                format!("{:?}", execution.status),
+                to_f64(execution.expected_profit_usd),
+                execution.actual_profit_usd.map(to_f64),
+                serde_json::to_string(execution)?,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_lifecycle(&self, lifecycle: &OpportunityLifecycle) -> Result<()> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO lifecycles
+             (id, pool, direction, stage, end_reason, detected_at, ended_at, persisted_ms,
+              detected_net_profit_usd, execution_profit_usd, payload)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                lifecycle.id,
+                lifecycle.pool,
+                lifecycle.direction,
+                format!("{:?}", lifecycle.stage),
+                format!("{:?}", lifecycle.end),
+                db_timestamp(&lifecycle.detected_at),
+                db_timestamp(&lifecycle.ended_at),
+                lifecycle.persisted_ms,
+                to_f64(lifecycle.detected_net_profit_usd),
+                lifecycle.execution_profit_usd.map(to_f64),
+                serde_json::to_string(lifecycle)?,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Opportunities detected since `since` with net profit above `min_profit_usd`
+    pub fn opportunities_since(&self, since: DateTime<Utc>, min_profit_usd: Decimal) -> Result<Vec<OpportunityRow>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, timestamp, pool, direction, net_profit_usd, roi_pct, payload
+             FROM opportunities
+             WHERE timestamp >= ?1 AND net_profit_usd > ?2
+             ORDER BY timestamp DESC",
+        )?;
+
+        let rows = stmt.query_map(params![db_timestamp(&since), to_f64(min_profit_usd)], |row| {
+            let timestamp: String = row.get(1)?;
+            Ok(OpportunityRow {
+                id: row.get(0)?,
+                timestamp: DateTime::parse_from_rfc3339(&timestamp)
+                    .map(|t| t.with_timezone(&Utc))
+                    .unwrap_or_default(),
+                pool: row.get(2)?,
+                direction: row.get(3)?,
+                net_profit_usd: row.get(4)?,
+                roi_pct: row.get(5)?,
+                payload: row.get(6)?,
+            })
+        })?;
+
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    /// Opportunities in the last 24 hours with net profit above `min_profit_usd`
+    pub fn recent_profitable_opportunities(&self, min_profit_usd: Decimal) -> Result<Vec<OpportunityRow>> {
+        self.opportunities_since(Utc::now() - chrono::Duration::hours(24), min_profit_usd)
+    }
+
+    /// Total actual profit of executions since `since`
+    pub fn realized_profit_since(&self, since: DateTime<Utc>) -> Result<f64> {
+        let total: Option<f64> = self.conn().query_row(
+            "SELECT SUM(actual_profit_usd) FROM executions WHERE timestamp >= ?1",
+            params![db_timestamp(&since)],
+            |row| row.get(0),
+        )?;
+        Ok(total.unwrap_or(0.0))
+    }
+
+    /// Flush the WAL (if any) into the database file and run a quick integrity check
+    pub fn checkpoint(&self) -> Result<()> {
+        let conn = self.conn();
+        conn.execute_batch("PRAGMA wal_checkpoint(FULL);")
+            .context("Failed to checkpoint SQLite WAL")?;
+        let status: String = conn.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+        if status != "ok" {
+            return Err(anyhow::anyhow!("SQLite integrity check failed: {}", status));
+        }
+        Ok(())
+    }
+}
+
+/// Open the SQLite database used alongside the JSONL files. Opening the
+/// path already open keeps it; another path replaces it.
+pub fn init_database(path: &str) -> Result<()> {
+    let mut current = DATABASE.write().unwrap_or_else(|e| e.into_inner());
+    if current.as_ref().is_some_and(|(open, _)| open == path) {
+        return Ok(());
+    }
+    let db = StorageDb::open(path)?;
+    *current = Some((path.to_string(), Arc::new(db)));
+    info!("🗄️  SQLite storage enabled at {}", path);
+    Ok(())
+}
+
+/// Stop writing to the SQLite database, if one is open
+pub fn close_database() {
+    DATABASE.write().unwrap_or_else(|e| e.into_inner()).take();
+}
+
+/// The SQLite database, if one was configured
+pub fn database() -> Option<Arc<StorageDb>> {
+    DATABASE.read().unwrap_or_else(|e| e.into_inner()).as_ref().map(|(_, db)| db.clone())
+}
@@ -0,0 +1,32 @@
+//! Decision trace storage
+
+use anyhow::Result;
+use chrono::Utc;
+use std::fs::OpenOptions;
+use std::io::Write;
+use tracing::debug;
+use crate::types::DecisionTrace;
+
+pub fn save_decision_trace(trace: &DecisionTrace) -> Result<()> {
+    let filename = format!("output/decisions/decisions_{}.jsonl", 
+        Utc::now().format("%Y-%m-%d"));
+    if super::skip_write(&filename, trace) {
+        return Ok(());
+    }
+    
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&filename)?;
+    
+    writeln!(file, "{}", serde_json::to_string(trace)?)?;
+    
+    debug!(
+        pool = %trace.pool,
+        outcome = ?trace.outcome,
+        rejected_by = ?trace.rejected_by,
+        "Saved decision trace"
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,32 @@
+//! Dry-run mode
+//!
+//! With `DRY_RUN=true` (or `run --dry-run`) the bot monitors as usual, but
+//! nothing is written under `output/` and nothing is executed: every record
+//! that would have been saved is logged with its destination instead, so a
+//! config change can be tried in production without polluting the history.
+//! The SQLite database is not opened either.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::info;
+
+static DRY_RUN: AtomicBool = AtomicBool::new(false);
+
+pub fn set_dry_run(enabled: bool) {
+    DRY_RUN.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_dry_run() -> bool {
+    DRY_RUN.load(Ordering::Relaxed)
+}
+
+/// In dry-run mode, log `record` as it would have been written to `target`
+/// and return true so the caller skips the write
+pub fn skip_write<T: Serialize + ?Sized>(target: &str, record: &T) -> bool {
+    if !is_dry_run() {
+        return false;
+    }
+    let json = serde_json::to_string(record).unwrap_or_else(|e| format!("<unserializable: {}>", e));
+    info!("🧪 Dry run: would write to {}: {}", target, json);
+    true
+}
@@ -1,15 +1,21 @@
 //! Trade execution storage
 
-use anyhow::Result;
-use chrono::Utc;
-use std::fs::OpenOptions;
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
+use std::fs::{self, OpenOptions};
 use std::io::Write;
-use tracing::info;
+use std::path::Path;
+use tracing::{debug, info};
 use crate::types::TradeExecution;
 
+pub const EXECUTIONS_DIR: &str = "output/executions";
+
 pub fn save_trade_execution(execution: &TradeExecution) -> Result<()> {
-    let filename = format!("output/executions/trades_{}.jsonl", 
+    let filename = format!("{}/trades_{}.jsonl", EXECUTIONS_DIR,
         Utc::now().format("%Y-%m-%d"));
+    if super::skip_write(&filename, execution) {
+        return Ok(());
+    }
     
     let mut file = OpenOptions::new()
         .create(true)
@@ -18,6 +24,10 @@ pub fn save_trade_execution(execution: &TradeExecution) -> Result<()> {
     
     writeln!(file, "{}", serde_json::to_string(execution)?)?;
     
+    if let Some(db) = super::database() {
+        db.insert_execution(execution)?;
+    }
+    
     info!(
         execution_id = %execution.id,
         status = ?execution.status,
@@ -27,3 +37,33 @@ pub fn save_trade_execution(execution: &TradeExecution) -> Result<()> {
     
     Ok(())
 }
+
+/// Executions recorded in `dir` from `since` onwards, oldest day first.
+/// No recordings means none.
+pub fn load_trade_executions_since(dir: &Path, since: NaiveDate) -> Result<Vec<TradeExecution>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let first_file = format!("trades_{}.jsonl", since.format("%Y-%m-%d"));
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.file_name().is_some_and(|name| {
+            let name = name.to_string_lossy();
+            name.starts_with("trades_") && name.ends_with(".jsonl") && name.as_ref() >= first_file.as_str()
+        }))
+        .collect();
+    paths.sort();
+
+    let mut executions = Vec::new();
+    for path in paths {
+        let contents = fs::read_to_string(&path)?;
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(execution) => executions.push(execution),
+                Err(e) => debug!("Skipping unreadable execution in {}: {}", path.display(), e),
+            }
+        }
+    }
+    Ok(executions)
+}
@@ -0,0 +1,297 @@
+//! CSV and Parquet export of the daily JSONL outputs
+//!
+//! Flattens one day's opportunities, market-making signals or executions
+//! into a fixed set of typed columns for pandas or DuckDB. Decimal fields
+//! keep their exact text in CSV and become doubles in Parquet; nested
+//! values without a column of their own are left out.
+
+use anyhow::{Context, Result};
+use arrow::array::{ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray, TimestampMillisecondArray};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use chrono::{DateTime, NaiveDate};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use serde_json::Value;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::debug;
+use super::daily_report::read_records;
+
+pub const EXPORTS_DIR: &str = "output/exports";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Parquet,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Parquet => "parquet",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "parquet" => Ok(ExportFormat::Parquet),
+            other => Err(format!("unknown export format {:?} (expected csv or parquet)", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportDataset {
+    Opportunities,
+    Signals,
+    Executions,
+}
+
+impl ExportDataset {
+    pub const ALL: [ExportDataset; 3] = [ExportDataset::Opportunities, ExportDataset::Signals, ExportDataset::Executions];
+
+    /// The day's JSONL file under `data_dir` (normally `output`)
+    pub fn source(&self, data_dir: &Path, date: NaiveDate) -> PathBuf {
+        let (dir, prefix) = match self {
+            ExportDataset::Opportunities => ("opportunities", "arbitrage_"),
+            ExportDataset::Signals => ("market_making", "signals_"),
+            ExportDataset::Executions => ("executions", "trades_"),
+        };
+        data_dir.join(dir).join(format!("{}{}.jsonl", prefix, date.format("%Y-%m-%d")))
+    }
+
+    fn columns(&self) -> &'static [Column] {
+        match self {
+            ExportDataset::Opportunities => OPPORTUNITY_COLUMNS,
+            ExportDataset::Signals => SIGNAL_COLUMNS,
+            ExportDataset::Executions => EXECUTION_COLUMNS,
+        }
+    }
+}
+
+impl fmt::Display for ExportDataset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ExportDataset::Opportunities => "opportunities",
+            ExportDataset::Signals => "signals",
+            ExportDataset::Executions => "executions",
+        })
+    }
+}
+
+impl FromStr for ExportDataset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "opportunities" => Ok(ExportDataset::Opportunities),
+            "signals" => Ok(ExportDataset::Signals),
+            "executions" => Ok(ExportDataset::Executions),
+            other => Err(format!("unknown dataset {:?} (expected opportunities, signals or executions)", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ColumnKind {
+    Text,
+    /// Decimal strings or JSON numbers
+    Number,
+    Integer,
+    Bool,
+    /// RFC 3339 strings, as UTC milliseconds
+    Timestamp,
+}
+
+/// An output column and the JSON pointer it is read from
+struct Column(&'static str, &'static str, ColumnKind);
+
+const OPPORTUNITY_COLUMNS: &[Column] = &[
+    Column("id", "/id", ColumnKind::Text),
+    Column("timestamp", "/timestamp", ColumnKind::Timestamp),
+    Column("pool", "/pool", ColumnKind::Text),
+    Column("direction", "/direction", ColumnKind::Text),
+    Column("dex_price", "/dex_price", ColumnKind::Number),
+    Column("cex_price", "/cex_price", ColumnKind::Number),
+    Column("price_basis", "/price_basis", ColumnKind::Text),
+    Column("price_diff_pct", "/price_diff_pct", ColumnKind::Number),
+    Column("cex_fill_price", "/cex_fill_price", ColumnKind::Number),
+    Column("size_eth", "/size_eth", ColumnKind::Number),
+    Column("gross_profit_usd", "/gross_profit_usd", ColumnKind::Number),
+    Column("gas_cost_usd", "/gas_cost_usd", ColumnKind::Number),
+    Column("cex_fee_usd", "/cex_fee_usd", ColumnKind::Number),
+    Column("dex_fee_usd", "/dex_fee_usd", ColumnKind::Number),
+    Column("transfer_cost_usd", "/transfer_cost_usd", ColumnKind::Number),
+    Column("net_profit_usd", "/net_profit_usd", ColumnKind::Number),
+    Column("roi_pct", "/roi_pct", ColumnKind::Number),
+    Column("validations_passed", "/validation_checks/all_passed", ColumnKind::Bool),
+    Column("short_term_volatility", "/volatility_assessment/short_term_volatility", ColumnKind::Number),
+    Column("volatility_impact", "/volatility_assessment/impact_assessment", ColumnKind::Text),
+    Column("stage", "/stage", ColumnKind::Text),
+];
+
+const SIGNAL_COLUMNS: &[Column] = &[
+    Column("id", "/id", ColumnKind::Text),
+    Column("timestamp", "/timestamp", ColumnKind::Timestamp),
+    Column("pool", "/pool", ColumnKind::Text),
+    Column("fair_value_price", "/fair_value_price", ColumnKind::Number),
+    Column("price_basis", "/price_basis", ColumnKind::Text),
+    Column("current_pool_price", "/current_pool_price", ColumnKind::Number),
+    Column("target_bid_price", "/target_bid_price", ColumnKind::Number),
+    Column("target_ask_price", "/target_ask_price", ColumnKind::Number),
+    Column("effective_spread_bps", "/effective_spread_bps", ColumnKind::Integer),
+    Column("position_size_eth", "/position_size_eth", ColumnKind::Number),
+    Column("weth_ratio", "/inventory_analysis/weth_ratio", ColumnKind::Number),
+    Column("rebalance_needed", "/inventory_analysis/rebalance_needed", ColumnKind::Bool),
+    Column("strategy", "/strategy", ColumnKind::Text),
+    Column("execution_priority", "/execution_priority", ColumnKind::Text),
+    Column("short_term_volatility", "/volatility_metrics/short_term_volatility", ColumnKind::Number),
+    Column("volatility_impact", "/volatility_metrics/impact_assessment", ColumnKind::Text),
+    Column("expected_net_usd", "/expected_return/net_usd", ColumnKind::Number),
+    Column("rationale", "/rationale", ColumnKind::Text),
+];
+
+const EXECUTION_COLUMNS: &[Column] = &[
+    Column("id", "/id", ColumnKind::Text),
+    Column("opportunity_id", "/opportunity_id", ColumnKind::Text),
+    Column("timestamp", "/timestamp", ColumnKind::Timestamp),
+    Column("network", "/network", ColumnKind::Text),
+    Column("trade_type", "/trade_type", ColumnKind::Text),
+    Column("status", "/status", ColumnKind::Text),
+    Column("tx_hash", "/tx_hash", ColumnKind::Text),
+    Column("gas_used", "/gas_used", ColumnKind::Integer),
+    Column("gas_price_gwei", "/gas_price_gwei", ColumnKind::Number),
+    Column("execution_time_ms", "/execution_time_ms", ColumnKind::Integer),
+    Column("expected_profit_usd", "/expected_profit_usd", ColumnKind::Number),
+    Column("actual_profit_usd", "/actual_profit_usd", ColumnKind::Number),
+    Column("slippage_bps", "/slippage_bps", ColumnKind::Integer),
+    Column("dex_fill_price", "/dex_fill_price", ColumnKind::Number),
+    Column("cex_fill_price", "/cex_fill_price", ColumnKind::Number),
+    Column("volatility_impact", "/volatility_impact", ColumnKind::Text),
+    Column("simulated", "/simulated", ColumnKind::Bool),
+    Column("error_message", "/error_message", ColumnKind::Text),
+];
+
+/// One exported file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportSummary {
+    pub dataset: ExportDataset,
+    pub path: PathBuf,
+    pub rows: u64,
+    /// Source lines that were not valid JSON
+    pub unreadable_lines: u64,
+}
+
+/// Export `dataset` for `date` from `data_dir` into `out_dir` as
+/// `<dataset>_YYYY-MM-DD.<csv|parquet>`. None when the day has no source file.
+pub fn export_dataset(
+    dataset: ExportDataset,
+    format: ExportFormat,
+    data_dir: &Path,
+    date: NaiveDate,
+    out_dir: &Path,
+) -> Result<Option<ExportSummary>> {
+    let source = dataset.source(data_dir, date);
+    if !source.exists() {
+        debug!("Nothing to export: {} does not exist", source.display());
+        return Ok(None);
+    }
+    let mut unreadable_lines = 0;
+    let records = read_records(&source, &mut unreadable_lines);
+
+    fs::create_dir_all(out_dir)?;
+    let path = out_dir.join(format!("{}_{}.{}", dataset, date.format("%Y-%m-%d"), format.extension()));
+    match format {
+        ExportFormat::Csv => write_csv(&path, dataset.columns(), &records),
+        ExportFormat::Parquet => write_parquet(&path, dataset.columns(), &records),
+    }
+    .with_context(|| format!("Failed to export {} to {}", source.display(), path.display()))?;
+
+    Ok(Some(ExportSummary { dataset, path, rows: records.len() as u64, unreadable_lines }))
+}
+
+fn csv_cell(value: Option<&Value>) -> String {
+    let text = match value {
+        None | Some(Value::Null) => return String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    };
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+fn write_csv(path: &Path, columns: &[Column], records: &[Value]) -> Result<()> {
+    let mut csv = columns.iter().map(|Column(name, ..)| *name).collect::<Vec<_>>().join(",");
+    csv.push('\n');
+    for record in records {
+        let row: Vec<String> = columns.iter().map(|Column(_, pointer, _)| csv_cell(record.pointer(pointer))).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    fs::write(path, csv)?;
+    Ok(())
+}
+
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn column_array(kind: ColumnKind, values: Vec<Option<&Value>>) -> (DataType, ArrayRef) {
+    let values = values.into_iter().map(|value| value.filter(|v| !v.is_null()));
+    match kind {
+        ColumnKind::Text => (DataType::Utf8, Arc::new(values.map(|v| v.and_then(text)).collect::<StringArray>())),
+        ColumnKind::Number => (DataType::Float64, Arc::new(values
+            .map(|v| v.and_then(|v| v.as_f64().or_else(|| v.as_str()?.parse().ok())))
+            .collect::<Float64Array>())),
+        ColumnKind::Integer => (DataType::Int64, Arc::new(values
+            .map(|v| v.and_then(|v| v.as_i64().or_else(|| v.as_str()?.parse().ok())))
+            .collect::<Int64Array>())),
+        ColumnKind::Bool => (DataType::Boolean, Arc::new(values
+            .map(|v| v.and_then(Value::as_bool))
+            .collect::<BooleanArray>())),
+        ColumnKind::Timestamp => (
+            DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+            Arc::new(values
+                .map(|v| v.and_then(Value::as_str)
+                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                    .map(|t| t.timestamp_millis()))
+                .collect::<TimestampMillisecondArray>()
+                .with_timezone("UTC")),
+        ),
+    }
+}
+
+fn write_parquet(path: &Path, columns: &[Column], records: &[Value]) -> Result<()> {
+    let (fields, arrays): (Vec<Field>, Vec<ArrayRef>) = columns.iter()
+        .map(|Column(name, pointer, kind)| {
+            let (data_type, array) = column_array(*kind, records.iter().map(|r| r.pointer(pointer)).collect());
+            (Field::new(*name, data_type, true), array)
+        })
+        .unzip();
+    let schema = Arc::new(Schema::new(fields));
+    let batch = RecordBatch::try_new(schema.clone(), arrays)?;
+
+    let properties = WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+    let mut writer = ArrowWriter::try_new(fs::File::create(path)?, schema, Some(properties))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
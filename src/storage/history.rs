@@ -0,0 +1,299 @@
+//! Bounded in-memory price history with spill-to-disk
+//!
+//! Recent samples stay in memory up to a sample cap. Past the cap the oldest
+//! half is appended to daily JSONL files under `output/history/<series>/`,
+//! and reads that reach further back than memory transparently include them.
+//! Spill files older than the retention period are deleted.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+pub const HISTORY_DIR: &str = "output/history";
+pub const DEFAULT_HISTORY_MEMORY_SAMPLES: usize = 1024;
+pub const DEFAULT_HISTORY_RETENTION_DAYS: u64 = 30;
+
+/// One spilled sample: unix milliseconds and price
+#[derive(Serialize, Deserialize)]
+struct SpilledSample {
+    t: i64,
+    p: f64,
+}
+
+fn unix_millis(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_millis() as i64).unwrap_or(0)
+}
+
+fn day_of(time: SystemTime) -> NaiveDate {
+    DateTime::<Utc>::from(time).date_naive()
+}
+
+/// The in-memory samples and spill position of a [`PriceHistory`], saved
+/// with the bot state on shutdown
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistorySnapshot {
+    /// (unix milliseconds, price), oldest first
+    pub samples: Vec<(i64, f64)>,
+    /// Newest sample already in the spill files
+    pub newest_spilled_ms: Option<i64>,
+}
+
+impl HistorySnapshot {
+    /// Append samples newer than the newest one already held (e.g. prices
+    /// recorded after the snapshot was saved), returning how many were added
+    pub fn append_newer(&mut self, samples: impl IntoIterator<Item = (i64, f64)>) -> usize {
+        let newest = self.samples.iter().map(|(t, _)| *t).max()
+            .into_iter()
+            .chain(self.newest_spilled_ms)
+            .max();
+        let before = self.samples.len();
+        self.samples.extend(samples.into_iter().filter(|(t, _)| newest.is_none_or(|newest| *t > newest)));
+        self.samples.sort_by_key(|(t, _)| *t);
+        self.samples.len() - before
+    }
+}
+
+fn from_unix_millis(ms: i64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(ms.max(0) as u64)
+}
+
+/// Where and for how long overflow samples are kept
+struct SpillTarget {
+    dir: PathBuf,
+    retention_days: u64,
+    last_pruned: Option<NaiveDate>,
+}
+
+pub struct PriceHistory {
+    memory: VecDeque<(SystemTime, f64)>,
+    max_memory: usize,
+    /// Samples older than this behind the latest one are dropped from memory
+    window: Duration,
+    spill: Option<SpillTarget>,
+    /// Newest spilled sample, while it is still inside the window
+    newest_spilled: Option<SystemTime>,
+}
+
+impl PriceHistory {
+    /// Memory-only history holding everything within `window` (used for replays)
+    pub fn in_memory(window: Duration) -> Self {
+        Self {
+            memory: VecDeque::new(),
+            max_memory: usize::MAX,
+            window,
+            spill: None,
+            newest_spilled: None,
+        }
+    }
+
+    /// History keeping at most `max_memory` samples in memory and spilling
+    /// the rest to `output/history/<series>/`
+    pub fn spilling(series: &str, window: Duration, max_memory: usize, retention_days: u64) -> Self {
+        Self::spilling_to(PathBuf::from(HISTORY_DIR).join(series), window, max_memory, retention_days)
+    }
+
+    pub fn spilling_to(dir: PathBuf, window: Duration, max_memory: usize, retention_days: u64) -> Self {
+        Self {
+            memory: VecDeque::new(),
+            max_memory: max_memory.max(2),
+            window,
+            spill: Some(SpillTarget { dir, retention_days, last_pruned: None }),
+            newest_spilled: None,
+        }
+    }
+
+    /// Add a sample. One stamped before the latest sample (the clock that
+    /// labeled it stepped backwards) is kept at the latest sample's time so
+    /// the history stays in order.
+    pub fn push(&mut self, at: SystemTime, price: f64) {
+        let at = match self.latest() {
+            Some((latest, _)) if at < latest => {
+                let behind = latest.duration_since(at).unwrap_or_default();
+                debug!("Price sample {:.3}s behind the latest one, keeping it at the latest time", behind.as_secs_f64());
+                latest
+            }
+            _ => at,
+        };
+        self.memory.push_back((at, price));
+
+        let cutoff = at.checked_sub(self.window).unwrap_or(UNIX_EPOCH);
+        while let Some((timestamp, _)) = self.memory.front() {
+            if *timestamp < cutoff {
+                self.memory.pop_front();
+            } else {
+                break;
+            }
+        }
+        if self.newest_spilled.is_some_and(|newest| newest < cutoff) {
+            self.newest_spilled = None;
+        }
+
+        if self.memory.len() > self.max_memory {
+            let evicted: Vec<(SystemTime, f64)> = self.memory.drain(..self.memory.len() - self.max_memory / 2).collect();
+            self.spill(&evicted);
+        }
+    }
+
+    /// Samples at or after `from` within the window, oldest first, reading
+    /// spilled samples back if needed
+    pub fn since(&self, from: SystemTime) -> Vec<(SystemTime, f64)> {
+        let from = match self.latest() {
+            Some((latest, _)) => from.max(latest.checked_sub(self.window).unwrap_or(UNIX_EPOCH)),
+            None => return Vec::new(),
+        };
+        let mut samples = Vec::new();
+        let memory_start = self.memory_start();
+        if self.newest_spilled.is_some_and(|newest| newest >= from) {
+            samples = self.read_spilled(from, memory_start);
+        }
+        samples.extend(self.memory.iter().filter(|(t, _)| *t >= from).copied());
+        samples
+    }
+
+    /// Every sample inside the window
+    pub fn samples(&self) -> Vec<(SystemTime, f64)> {
+        self.since(UNIX_EPOCH)
+    }
+
+    pub fn latest(&self) -> Option<(SystemTime, f64)> {
+        self.memory.back().copied()
+    }
+
+    pub fn memory_len(&self) -> usize {
+        self.memory.len()
+    }
+
+    pub fn snapshot(&self) -> HistorySnapshot {
+        HistorySnapshot {
+            samples: self.memory.iter().map(|(t, p)| (unix_millis(*t), *p)).collect(),
+            newest_spilled_ms: self.newest_spilled.map(unix_millis),
+        }
+    }
+
+    /// Replace the in-memory samples with a snapshot's. Samples past the
+    /// memory cap are spilled; older ones are read back from the spill
+    /// files as before the restart.
+    pub fn restore(&mut self, snapshot: &HistorySnapshot) {
+        self.restore_until(snapshot, None);
+    }
+
+    /// [`restore`](Self::restore), dropping samples stamped after `now`,
+    /// which a clock running ahead in the previous run saved
+    pub fn restore_until(&mut self, snapshot: &HistorySnapshot, now: Option<SystemTime>) {
+        let now_ms = now.map(unix_millis).unwrap_or(i64::MAX);
+        let mut samples: Vec<(SystemTime, f64)> = snapshot.samples.iter()
+            .filter(|(t, _)| *t <= now_ms)
+            .map(|(t, p)| (from_unix_millis(*t), *p))
+            .collect();
+        if samples.len() < snapshot.samples.len() {
+            warn!(
+                "Dropped {} saved price samples stamped in the future; the clock has moved back since they were saved",
+                snapshot.samples.len() - samples.len(),
+            );
+        }
+        samples.sort_by_key(|(t, _)| *t);
+        self.memory = samples.into();
+        self.newest_spilled = snapshot.newest_spilled_ms.filter(|t| *t <= now_ms).map(from_unix_millis);
+
+        if self.memory.len() > self.max_memory {
+            let evicted: Vec<(SystemTime, f64)> = self.memory.drain(..self.memory.len() - self.max_memory).collect();
+            self.spill(&evicted);
+        }
+    }
+
+    fn memory_start(&self) -> Option<SystemTime> {
+        self.memory.front().map(|(t, _)| *t)
+    }
+
+    fn spill(&mut self, evicted: &[(SystemTime, f64)]) {
+        self.newest_spilled = evicted.last().map(|(t, _)| *t);
+        let Some(target) = self.spill.as_mut() else {
+            return;
+        };
+        if super::is_dry_run() {
+            debug!("🧪 Dry run: would spill {} price samples to {}", evicted.len(), target.dir.display());
+            return;
+        }
+        if let Err(e) = fs::create_dir_all(&target.dir) {
+            warn!("Failed to create {}: {}", target.dir.display(), e);
+            return;
+        }
+
+        for day_samples in evicted.chunk_by(|a, b| day_of(a.0) == day_of(b.0)) {
+            let path = target.dir.join(format!("{}.jsonl", day_of(day_samples[0].0).format("%Y-%m-%d")));
+            let written = OpenOptions::new().create(true).append(true).open(&path).and_then(|mut file| {
+                let mut lines = String::new();
+                for (t, p) in day_samples {
+                    let sample = SpilledSample { t: unix_millis(*t), p: *p };
+                    lines.push_str(&serde_json::to_string(&sample).unwrap_or_default());
+                    lines.push('\n');
+                }
+                file.write_all(lines.as_bytes())
+            });
+            if let Err(e) = written {
+                warn!("Failed to spill price history to {}: {}", path.display(), e);
+            }
+        }
+        debug!("Spilled {} price samples to {}", evicted.len(), target.dir.display());
+
+        let today = Utc::now().date_naive();
+        if target.last_pruned != Some(today) {
+            target.last_pruned = Some(today);
+            prune_spill_files(target, today);
+        }
+    }
+
+    fn read_spilled(&self, from: SystemTime, until: Option<SystemTime>) -> Vec<(SystemTime, f64)> {
+        let Some(target) = self.spill.as_ref() else {
+            return Vec::new();
+        };
+        let (from_ms, until_ms) = (unix_millis(from), until.map(unix_millis).unwrap_or(i64::MAX));
+        let last_day = until.map(day_of).unwrap_or_else(|| Utc::now().date_naive());
+
+        let mut samples = Vec::new();
+        let mut day = day_of(from);
+        while day <= last_day {
+            let path = target.dir.join(format!("{}.jsonl", day.format("%Y-%m-%d")));
+            if let Ok(file) = fs::File::open(&path) {
+                for line in BufReader::new(file).lines().map_while(Result::ok) {
+                    match serde_json::from_str::<SpilledSample>(&line) {
+                        Ok(sample) if sample.t >= from_ms && sample.t < until_ms => {
+                            samples.push((from_unix_millis(sample.t), sample.p));
+                        }
+                        Ok(_) => {}
+                        Err(e) => debug!("Skipping unreadable history line in {}: {}", path.display(), e),
+                    }
+                }
+            }
+            day = match day.succ_opt() {
+                Some(next) => next,
+                None => break,
+            };
+        }
+        samples
+    }
+}
+
+/// Delete spill files older than the retention period
+fn prune_spill_files(target: &SpillTarget, today: NaiveDate) {
+    let Ok(entries) = fs::read_dir(&target.dir) else {
+        return;
+    };
+    let oldest_kept = today - chrono::Duration::days(target.retention_days as i64);
+    for path in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+        let expired = path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| NaiveDate::parse_from_str(stem, "%Y-%m-%d").ok())
+            .is_some_and(|date| date < oldest_kept);
+        if expired {
+            if let Err(e) = fs::remove_file(&path) {
+                warn!("Failed to remove expired history file {}: {}", path.display(), e);
+            }
+        }
+    }
+}
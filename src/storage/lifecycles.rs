@@ -0,0 +1,147 @@
+//! Finished opportunity lifecycles for fill-rate and decay analysis
+//!
+//! One JSON line per lifecycle in `output/lifecycles/lifecycles_YYYY-MM-DD.jsonl`,
+//! dated by the day the lifecycle ended.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use tracing::debug;
+use crate::types::{OpportunityLifecycle, OpportunityStage};
+
+pub const LIFECYCLES_DIR: &str = "output/lifecycles";
+const LIFECYCLE_PREFIX: &str = "lifecycles_";
+
+pub fn save_opportunity_lifecycles(lifecycles: &[OpportunityLifecycle]) -> Result<()> {
+    if super::skip_write(LIFECYCLES_DIR, lifecycles) {
+        return Ok(());
+    }
+    save_opportunity_lifecycles_in(Path::new(LIFECYCLES_DIR), lifecycles)?;
+
+    if let Some(db) = super::database() {
+        for lifecycle in lifecycles {
+            db.insert_lifecycle(lifecycle)?;
+        }
+    }
+    Ok(())
+}
+
+/// Append lifecycles to the file for the day they ended in `dir`
+pub fn save_opportunity_lifecycles_in(dir: &Path, lifecycles: &[OpportunityLifecycle]) -> Result<()> {
+    let Some(first) = lifecycles.first() else {
+        return Ok(());
+    };
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}{}.jsonl", LIFECYCLE_PREFIX, first.ended_at.format("%Y-%m-%d")));
+
+    let mut lines = String::new();
+    for lifecycle in lifecycles {
+        lines.push_str(&serde_json::to_string(lifecycle)?);
+        lines.push('\n');
+    }
+    OpenOptions::new().create(true).append(true).open(&path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    debug!("Saved {} opportunity lifecycles to {}", lifecycles.len(), path.display());
+    Ok(())
+}
+
+/// Every lifecycle recorded in `dir`, oldest file first
+pub fn load_opportunity_lifecycles(dir: &Path) -> Result<Vec<OpportunityLifecycle>> {
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect();
+    paths.sort();
+
+    let mut lifecycles = Vec::new();
+    for path in paths {
+        let file = fs::File::open(&path)?;
+        for line in BufReader::new(file).lines().map_while(Result::ok) {
+            match serde_json::from_str(&line) {
+                Ok(lifecycle) => lifecycles.push(lifecycle),
+                Err(e) => debug!("Skipping unreadable lifecycle in {}: {}", path.display(), e),
+            }
+        }
+    }
+    Ok(lifecycles)
+}
+
+/// How far a set of lifecycles got, and how their profit held up
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LifecycleSummary {
+    pub lifecycles: u64,
+    pub validated: u64,
+    pub executed: u64,
+    pub settled: u64,
+    /// Executed lifecycles whose execution was still profitable
+    pub profitable_at_execution: u64,
+    /// Settled share of validated lifecycles, in percent
+    pub fill_rate_pct: Option<Decimal>,
+    pub mean_persisted_ms: Option<Decimal>,
+    /// Mean detected net profit less execution profit, over executed lifecycles
+    pub mean_profit_decay_usd: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LifecycleReport {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub overall: LifecycleSummary,
+    pub pools: BTreeMap<String, LifecycleSummary>,
+}
+
+pub fn summarize_lifecycles<'a>(lifecycles: impl IntoIterator<Item = &'a OpportunityLifecycle>) -> LifecycleSummary {
+    let mut summary = LifecycleSummary::default();
+    let mut persisted_ms = Decimal::ZERO;
+    let mut decay_usd = Decimal::ZERO;
+    for lifecycle in lifecycles {
+        summary.lifecycles += 1;
+        persisted_ms += Decimal::from(lifecycle.persisted_ms);
+        if lifecycle.stage >= OpportunityStage::Validated {
+            summary.validated += 1;
+        }
+        if lifecycle.stage >= OpportunityStage::Executed {
+            summary.executed += 1;
+            if let Some(profit) = lifecycle.execution_profit_usd {
+                decay_usd += lifecycle.detected_net_profit_usd - profit;
+            }
+        }
+        if lifecycle.stage == OpportunityStage::Settled {
+            summary.settled += 1;
+        }
+        if lifecycle.profitable_at_execution == Some(true) {
+            summary.profitable_at_execution += 1;
+        }
+    }
+
+    let mean = |total: Decimal, count: u64| (count > 0).then(|| total / Decimal::from(count));
+    summary.fill_rate_pct = mean(Decimal::from(summary.settled) * dec!(100), summary.validated);
+    summary.mean_persisted_ms = mean(persisted_ms, summary.lifecycles);
+    summary.mean_profit_decay_usd = mean(decay_usd, summary.executed);
+    summary
+}
+
+/// Summaries over all lifecycles and per pool
+pub fn build_lifecycle_report(lifecycles: &[OpportunityLifecycle]) -> LifecycleReport {
+    let mut by_pool: BTreeMap<&str, Vec<&OpportunityLifecycle>> = BTreeMap::new();
+    for lifecycle in lifecycles {
+        by_pool.entry(&lifecycle.pool).or_default().push(lifecycle);
+    }
+    LifecycleReport {
+        start: lifecycles.iter().map(|l| l.detected_at).min(),
+        end: lifecycles.iter().map(|l| l.ended_at).max(),
+        overall: summarize_lifecycles(lifecycles),
+        pools: by_pool.into_iter()
+            .map(|(pool, pool_lifecycles)| (pool.to_string(), summarize_lifecycles(pool_lifecycles)))
+            .collect(),
+    }
+}
@@ -5,11 +5,14 @@ use chrono::Utc;
 use std::fs::OpenOptions;
 use std::io::Write;
 use tracing::info;
-use crate::types::MarketMakingSignal;
+use crate::types::{MarketMakingSignal, QuoteFill};
 
 pub fn save_market_making_signal(signal: &MarketMakingSignal) -> Result<()> {
     let filename = format!("output/market_making/signals_{}.jsonl", 
         Utc::now().format("%Y-%m-%d"));
+    if super::skip_write(&filename, signal) {
+        return Ok(());
+    }
     
     let mut file = OpenOptions::new()
         .create(true)
@@ -18,6 +21,10 @@ pub fn save_market_making_signal(signal: &MarketMakingSignal) -> Result<()> {
     
     writeln!(file, "{}", serde_json::to_string(signal)?)?;
     
+    if let Some(db) = super::database() {
+        db.insert_signal(signal)?;
+    }
+    
     info!(
         signal_id = %signal.id,
         strategy = ?signal.strategy.strategy_type,
@@ -28,3 +35,20 @@ pub fn save_market_making_signal(signal: &MarketMakingSignal) -> Result<()> {
     
     Ok(())
 }
+
+/// Append a simulated quote fill to the day's fills file
+pub fn save_quote_fill(fill: &QuoteFill) -> Result<()> {
+    let filename = format!("output/market_making/fills_{}.jsonl",
+        Utc::now().format("%Y-%m-%d"));
+    if super::skip_write(&filename, fill) {
+        return Ok(());
+    }
+    
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&filename)?;
+    
+    writeln!(file, "{}", serde_json::to_string(fill)?)?;
+    Ok(())
+}
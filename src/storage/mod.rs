@@ -3,7 +3,39 @@
 pub mod opportunities;
 pub mod market_making;
 pub mod executions;
+pub mod reconciliation;
+pub mod db;
+pub mod reports;
+pub mod decisions;
+pub mod checkpoint;
+pub mod history;
+pub mod spreads;
+pub mod state;
+pub mod daily_report;
+pub mod prices;
+pub mod paper;
+pub mod dry_run;
+pub mod lifecycles;
+pub mod export;
+pub mod retention;
+pub mod stats;
 
 pub use opportunities::*;
 pub use market_making::*;
 pub use executions::*;
+pub use reconciliation::*;
+pub use db::*;
+pub use reports::*;
+pub use decisions::*;
+pub use checkpoint::*;
+pub use history::*;
+pub use spreads::*;
+pub use state::*;
+pub use daily_report::*;
+pub use prices::*;
+pub use paper::*;
+pub use dry_run::*;
+pub use lifecycles::*;
+pub use export::*;
+pub use retention::*;
+pub use stats::*;
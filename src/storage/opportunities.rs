@@ -10,6 +10,9 @@ use crate::types::ArbitrageOpportunity;
 pub fn save_opportunity(opp: &ArbitrageOpportunity) -> Result<()> {
     let filename = format!("output/opportunities/arbitrage_{}.jsonl", 
         Utc::now().format("%Y-%m-%d"));
+    if super::skip_write(&filename, opp) {
+        return Ok(());
+    }
     
     let mut file = OpenOptions::new()
         .create(true)
@@ -18,6 +21,10 @@ pub fn save_opportunity(opp: &ArbitrageOpportunity) -> Result<()> {
     
     writeln!(file, "{}", serde_json::to_string(opp)?)?;
     
+    if let Some(db) = super::database() {
+        db.insert_opportunity(opp)?;
+    }
+    
     info!(
         opportunity_id = %opp.id,
         profit = %opp.net_profit_usd,
@@ -0,0 +1,25 @@
+//! Paper portfolio snapshots
+
+use anyhow::Result;
+use std::fs::OpenOptions;
+use std::io::Write;
+use tracing::debug;
+use crate::portfolio::PaperPortfolioReport;
+
+pub const PAPER_DIR: &str = "output/portfolio";
+
+/// Append a snapshot to the day's `paper_YYYY-MM-DD.jsonl`
+pub fn save_paper_portfolio_report(report: &PaperPortfolioReport) -> Result<()> {
+    let filename = format!("{}/paper_{}.jsonl", PAPER_DIR, report.timestamp.format("%Y-%m-%d"));
+    if super::skip_write(&filename, report) {
+        return Ok(());
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&filename)?;
+    writeln!(file, "{}", serde_json::to_string(report)?)?;
+
+    debug!(file = %filename, value_usd = %report.value_usd, "Saved paper portfolio snapshot");
+    Ok(())
+}
@@ -0,0 +1,283 @@
+//! Recorded CEX and DEX prices and the OHLC candles built from them
+//!
+//! Every fetched price is appended as a CSV row to
+//! `output/prices/prices_YYYY-MM-DD.csv`. The `candles` command buckets
+//! the rows into 1m/5m/1h candles per market for offline volatility and
+//! backtest replay.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use tracing::{debug, info};
+
+pub const PRICES_DIR: &str = "output/prices";
+pub const CANDLES_DIR: &str = "output/candles";
+const PRICES_HEADER: &str = "timestamp_ms,source,market,price";
+const CANDLES_HEADER: &str = "open_time_ms,source,market,open,high,low,close,samples";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum PriceSource {
+    /// Binance last price; the market is the symbol
+    Cex,
+    /// Pool price in USD; the market is the pool name
+    Dex,
+}
+
+impl fmt::Display for PriceSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PriceSource::Cex => "cex",
+            PriceSource::Dex => "dex",
+        })
+    }
+}
+
+impl FromStr for PriceSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cex" => Ok(PriceSource::Cex),
+            "dex" => Ok(PriceSource::Dex),
+            other => Err(format!("unknown price source {:?}", other)),
+        }
+    }
+}
+
+/// One fetched price
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceSample {
+    pub timestamp: DateTime<Utc>,
+    pub source: PriceSource,
+    pub market: String,
+    pub price: Decimal,
+}
+
+impl PriceSample {
+    pub fn new(source: PriceSource, market: &str, price: Decimal) -> Self {
+        Self { timestamp: Utc::now(), source, market: market.to_string(), price }
+    }
+
+    fn to_row(&self) -> String {
+        format!("{},{},{},{}", self.timestamp.timestamp_millis(), self.source, self.market.replace(',', ";"), self.price)
+    }
+
+    fn from_row(row: &str) -> Option<Self> {
+        let fields: Vec<&str> = row.split(',').collect();
+        let [timestamp_ms, source, market, price] = fields[..] else {
+            return None;
+        };
+        Some(Self {
+            timestamp: DateTime::from_timestamp_millis(timestamp_ms.parse().ok()?)?,
+            source: source.parse().ok()?,
+            market: market.to_string(),
+            price: Decimal::from_str(price).ok()?,
+        })
+    }
+}
+
+pub fn save_price_samples(samples: &[PriceSample]) -> Result<()> {
+    if super::skip_write(PRICES_DIR, &samples.iter().map(PriceSample::to_row).collect::<Vec<_>>()) {
+        return Ok(());
+    }
+    save_price_samples_in(Path::new(PRICES_DIR), samples)
+}
+
+/// Append samples to their day's file in `dir`, writing the header for a new
+/// file. A batch that crosses UTC midnight is split across the days' files.
+pub fn save_price_samples_in(dir: &Path, samples: &[PriceSample]) -> Result<()> {
+    if samples.is_empty() {
+        return Ok(());
+    }
+    fs::create_dir_all(dir)?;
+
+    let mut days: BTreeMap<NaiveDate, Vec<&PriceSample>> = BTreeMap::new();
+    for sample in samples {
+        days.entry(sample.timestamp.date_naive()).or_default().push(sample);
+    }
+    for (day, samples) in days {
+        let path = dir.join(format!("prices_{}.csv", day.format("%Y-%m-%d")));
+        let mut rows = String::new();
+        if !path.exists() {
+            rows.push_str(PRICES_HEADER);
+            rows.push('\n');
+        }
+        for sample in &samples {
+            rows.push_str(&sample.to_row());
+            rows.push('\n');
+        }
+        OpenOptions::new().create(true).append(true).open(&path)
+            .and_then(|mut file| file.write_all(rows.as_bytes()))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        debug!("Saved {} price samples to {}", samples.len(), path.display());
+    }
+    Ok(())
+}
+
+/// Every sample recorded in `dir`, in time order
+pub fn load_price_samples(dir: &Path) -> Result<Vec<PriceSample>> {
+    load_price_samples_since(dir, DateTime::<Utc>::MIN_UTC)
+}
+
+/// Samples recorded in `dir` at or after `since`, in time order. Only the
+/// daily files from `since` onwards are read.
+pub fn load_price_samples_since(dir: &Path, since: DateTime<Utc>) -> Result<Vec<PriceSample>> {
+    let first_file = format!("prices_{}.csv", since.format("%Y-%m-%d"));
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "csv"))
+        .filter(|path| path.file_name().is_some_and(|name| name.to_string_lossy().as_ref() >= first_file.as_str()))
+        .collect();
+    paths.sort();
+
+    let mut samples = Vec::new();
+    for path in paths {
+        let file = fs::File::open(&path)?;
+        for line in BufReader::new(file).lines().map_while(Result::ok) {
+            if line == PRICES_HEADER {
+                continue;
+            }
+            match PriceSample::from_row(&line) {
+                Some(sample) if sample.timestamp >= since => samples.push(sample),
+                Some(_) => {}
+                None => debug!("Skipping unreadable price row in {}: {}", path.display(), line),
+            }
+        }
+    }
+    samples.sort_by_key(|s| s.timestamp);
+    Ok(samples)
+}
+
+/// (unix milliseconds, price) of one CEX symbol recorded since `since`, for
+/// rebuilding volatility windows after a restart. No recordings means none.
+pub fn recorded_cex_history(dir: &Path, symbol: &str, since: DateTime<Utc>) -> Result<Vec<(i64, f64)>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(load_price_samples_since(dir, since)?
+        .into_iter()
+        .filter(|s| s.source == PriceSource::Cex && s.market == symbol)
+        .filter_map(|s| Some((s.timestamp.timestamp_millis(), s.price.to_f64()?)))
+        .collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CandleInterval {
+    OneMinute,
+    FiveMinutes,
+    OneHour,
+}
+
+impl CandleInterval {
+    pub const ALL: [CandleInterval; 3] = [CandleInterval::OneMinute, CandleInterval::FiveMinutes, CandleInterval::OneHour];
+
+    pub fn secs(&self) -> i64 {
+        match self {
+            CandleInterval::OneMinute => 60,
+            CandleInterval::FiveMinutes => 300,
+            CandleInterval::OneHour => 3600,
+        }
+    }
+
+    /// Start of the candle containing `timestamp`
+    pub fn open_time(&self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        let secs = timestamp.timestamp();
+        DateTime::from_timestamp(secs - secs.rem_euclid(self.secs()), 0).unwrap_or(timestamp)
+    }
+}
+
+impl fmt::Display for CandleInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CandleInterval::OneMinute => "1m",
+            CandleInterval::FiveMinutes => "5m",
+            CandleInterval::OneHour => "1h",
+        })
+    }
+}
+
+impl FromStr for CandleInterval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1m" => Ok(CandleInterval::OneMinute),
+            "5m" => Ok(CandleInterval::FiveMinutes),
+            "1h" => Ok(CandleInterval::OneHour),
+            other => Err(format!("unknown candle interval {:?} (expected 1m, 5m or 1h)", other)),
+        }
+    }
+}
+
+/// Open, high, low and close of one market over one interval. The price
+/// feeds carry no traded volume, so `samples` counts the prices instead.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Candle {
+    pub source: PriceSource,
+    pub market: String,
+    pub open_time: DateTime<Utc>,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub samples: u64,
+}
+
+/// Bucket samples into candles, sorted by source, market and open time.
+/// Intervals without samples produce no candle.
+pub fn build_candles(samples: &[PriceSample], interval: CandleInterval) -> Vec<Candle> {
+    let mut ordered: Vec<&PriceSample> = samples.iter().collect();
+    ordered.sort_by_key(|s| s.timestamp);
+
+    let mut candles: BTreeMap<(PriceSource, &str, DateTime<Utc>), Candle> = BTreeMap::new();
+    for sample in ordered {
+        let open_time = interval.open_time(sample.timestamp);
+        candles.entry((sample.source, sample.market.as_str(), open_time))
+            .and_modify(|candle| {
+                candle.high = candle.high.max(sample.price);
+                candle.low = candle.low.min(sample.price);
+                candle.close = sample.price;
+                candle.samples += 1;
+            })
+            .or_insert_with(|| Candle {
+                source: sample.source,
+                market: sample.market.clone(),
+                open_time,
+                open: sample.price,
+                high: sample.price,
+                low: sample.price,
+                close: sample.price,
+                samples: 1,
+            });
+    }
+    candles.into_values().collect()
+}
+
+/// Write `candles_<interval>.csv` in `dir`, replacing an earlier build
+pub fn save_candles_in(dir: &Path, interval: CandleInterval, candles: &[Candle]) -> Result<String> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("candles_{}.csv", interval));
+
+    let mut rows = String::from(CANDLES_HEADER);
+    rows.push('\n');
+    for candle in candles {
+        rows.push_str(&format!(
+            "{},{},{},{},{},{},{},{}\n",
+            candle.open_time.timestamp_millis(), candle.source, candle.market.replace(',', ";"),
+            candle.open, candle.high, candle.low, candle.close, candle.samples
+        ));
+    }
+    fs::write(&path, rows).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let filename = path.display().to_string();
+    info!(file = %filename, candles = candles.len(), "Saved {} candles", interval);
+    Ok(filename)
+}
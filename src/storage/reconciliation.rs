@@ -0,0 +1,37 @@
+//! Reconciliation acknowledgment storage
+
+use anyhow::Result;
+use chrono::Utc;
+use std::fs::OpenOptions;
+use std::io::Write;
+use tracing::info;
+use crate::network::ReconciliationAck;
+
+/// Record that an execution was acknowledged by the external reconciler.
+/// Rejected acks are refused: the file only lists reconciled executions.
+pub fn save_reconciliation_ack(ack: &ReconciliationAck) -> Result<()> {
+    if !ack.accepted {
+        return Err(anyhow::anyhow!("Execution {} was not accepted by the reconciler", ack.execution_id));
+    }
+    let filename = format!("output/executions/reconciled_{}.jsonl", 
+        Utc::now().format("%Y-%m-%d"));
+    if super::skip_write(&filename, ack) {
+        return Ok(());
+    }
+    
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&filename)?;
+    
+    writeln!(file, "{}", serde_json::to_string(ack)?)?;
+    
+    info!(
+        execution_id = %ack.execution_id,
+        accepted = ack.accepted,
+        reference = ?ack.reference,
+        "Marked trade execution as reconciled"
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,276 @@
+//! Report storage and the profit attribution analytics
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Timelike, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::info;
+use crate::{
+    backtest::{BacktestReport, LatencyRaceReport},
+    storage::{render_daily_report_markdown, DailyReport, LifecycleReport, SpreadHeatmap},
+    types::SessionSummary,
+};
+use super::daily_report::{decimal_field, read_records};
+
+pub fn save_backtest_report(report: &BacktestReport) -> Result<String> {
+    let basis_suffix = report.config.price_basis
+        .map(|basis| format!("_{}", basis))
+        .unwrap_or_default();
+    let filename = format!("output/reports/backtest_{}{}.json",
+        Utc::now().format("%Y-%m-%d_%H%M%S"), basis_suffix);
+    
+    std::fs::write(&filename, serde_json::to_string_pretty(report)?)?;
+    
+    info!(
+        file = %filename,
+        total_pnl = %report.total_pnl_usd,
+        "Saved backtest report"
+    );
+    
+    Ok(filename)
+}
+
+pub fn save_session_summary(summary: &SessionSummary) -> Result<String> {
+    let filename = format!("output/reports/session_{}.json", 
+        Utc::now().format("%Y-%m-%d_%H%M%S"));
+    if super::skip_write(&filename, summary) {
+        return Ok(filename);
+    }
+    
+    std::fs::write(&filename, serde_json::to_string_pretty(summary)?)?;
+    
+    info!(
+        file = %filename,
+        runtime_secs = summary.runtime_secs,
+        "Saved session summary"
+    );
+    
+    Ok(filename)
+}
+
+pub fn save_latency_race_report(report: &LatencyRaceReport) -> Result<String> {
+    let filename = format!("output/reports/latency_race_{}.json", 
+        Utc::now().format("%Y-%m-%d_%H%M%S"));
+    
+    std::fs::write(&filename, serde_json::to_string_pretty(report)?)?;
+    
+    info!(
+        file = %filename,
+        contested = report.contested_opportunities,
+        "Saved latency race report"
+    );
+    
+    Ok(filename)
+}
+
+/// Write `daily_YYYY-MM-DD.json` and `.md`, replacing an earlier report for the same day
+pub fn save_daily_report(report: &DailyReport) -> Result<(String, String)> {
+    let base = format!("output/reports/daily_{}", report.date.format("%Y-%m-%d"));
+    let json_file = format!("{}.json", base);
+    let markdown_file = format!("{}.md", base);
+    if super::skip_write(&json_file, report) {
+        return Ok((json_file, markdown_file));
+    }
+
+    std::fs::write(&json_file, serde_json::to_string_pretty(report)?)?;
+    std::fs::write(&markdown_file, render_daily_report_markdown(report))?;
+
+    info!(
+        file = %json_file,
+        opportunities = report.opportunities,
+        executions = report.executions,
+        "Saved daily report"
+    );
+
+    Ok((json_file, markdown_file))
+}
+
+pub fn save_spread_heatmap(heatmap: &SpreadHeatmap) -> Result<String> {
+    let filename = format!("output/reports/spread_heatmap_{}.json", 
+        Utc::now().format("%Y-%m-%d_%H%M%S"));
+    
+    std::fs::write(&filename, serde_json::to_string_pretty(heatmap)?)?;
+    
+    info!(
+        file = %filename,
+        cells = heatmap.cells.len(),
+        "Saved spread heatmap"
+    );
+    
+    Ok(filename)
+}
+
+pub fn save_lifecycle_report(report: &LifecycleReport) -> Result<String> {
+    let filename = format!("output/reports/lifecycles_{}.json", 
+        Utc::now().format("%Y-%m-%d_%H%M%S"));
+    
+    std::fs::write(&filename, serde_json::to_string_pretty(report)?)?;
+    
+    info!(
+        file = %filename,
+        lifecycles = report.overall.lifecycles,
+        "Saved opportunity lifecycle report"
+    );
+    
+    Ok(filename)
+}
+
+/// Executions sharing one value of an attribution dimension
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AttributionBucket {
+    pub key: String,
+    pub executions: u64,
+    pub successful_executions: u64,
+    /// Executions with positive realized P&L
+    pub winning_executions: u64,
+    pub expected_profit_usd: Decimal,
+    pub realized_pnl_usd: Decimal,
+    pub mean_pnl_usd: Decimal,
+    /// Winning share of the executions, in percent
+    pub win_rate_pct: Decimal,
+}
+
+/// Realized P&L of the stored executions broken down by pool, direction,
+/// UTC hour of day and volatility regime
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfitAttributionReport {
+    pub generated_at: DateTime<Utc>,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub executions: u64,
+    pub realized_pnl_usd: Decimal,
+    /// Best first
+    pub by_pool: Vec<AttributionBucket>,
+    pub by_direction: Vec<AttributionBucket>,
+    /// Ordered by hour, `00` to `23`
+    pub by_hour: Vec<AttributionBucket>,
+    pub by_volatility: Vec<AttributionBucket>,
+    /// Executions whose opportunity was not found, counted under pool `unknown`
+    pub unattributed_executions: u64,
+    /// Lines that were not valid JSON
+    pub unreadable_lines: u64,
+}
+
+/// Daily files `<prefix>YYYY-MM-DD.jsonl` in `dir` dated on or after `since`, oldest first
+fn daily_files(dir: &Path, prefix: &str, since: Option<NaiveDate>) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            let date = path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix(prefix))
+                .and_then(|name| name.strip_suffix(".jsonl"))
+                .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+            date.is_some_and(|date| since.is_none_or(|since| date >= since))
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Attribute the executions in `data_dir/executions` (normally under `output`)
+/// from `since` on to their opportunities' pools
+pub fn build_profit_attribution(data_dir: &Path, since: Option<NaiveDate>) -> Result<ProfitAttributionReport> {
+    let mut unreadable_lines = 0;
+    let mut executions = Vec::new();
+    for path in daily_files(&data_dir.join("executions"), "trades_", since)? {
+        executions.extend(read_records(&path, &mut unreadable_lines));
+    }
+    // An execution can finish the day after its opportunity was detected
+    let mut opportunities = Vec::new();
+    for path in daily_files(&data_dir.join("opportunities"), "arbitrage_", since.and_then(|d| d.pred_opt()))? {
+        opportunities.extend(read_records(&path, &mut unreadable_lines));
+    }
+    let opportunities: HashMap<&str, &serde_json::Value> = opportunities.iter()
+        .filter_map(|record| Some((record["id"].as_str()?, record)))
+        .collect();
+
+    let mut buckets: [HashMap<String, AttributionBucket>; 4] = Default::default();
+    let (mut start, mut end) = (None::<DateTime<Utc>>, None::<DateTime<Utc>>);
+    let mut realized_pnl_usd = Decimal::ZERO;
+    let mut unattributed_executions = 0;
+    for record in &executions {
+        let opportunity = record["opportunity_id"].as_str().and_then(|id| opportunities.get(id));
+        if opportunity.is_none() {
+            unattributed_executions += 1;
+        }
+        let timestamp = record["timestamp"].as_str()
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc));
+        if let Some(timestamp) = timestamp {
+            start = Some(start.map_or(timestamp, |s| s.min(timestamp)));
+            end = Some(end.map_or(timestamp, |e| e.max(timestamp)));
+        }
+        let regime = record["volatility_impact"].as_str()
+            .or_else(|| opportunity.and_then(|o| o["volatility_assessment"]["impact_assessment"].as_str()));
+        let keys = [
+            opportunity.and_then(|o| o["pool"].as_str()).unwrap_or("unknown").to_string(),
+            record["trade_type"].as_str().unwrap_or("unknown").to_string(),
+            timestamp.map_or("unknown".to_string(), |t| format!("{:02}", t.hour())),
+            regime.unwrap_or("unknown").to_string(),
+        ];
+
+        let realized = decimal_field(record, "actual_profit_usd").unwrap_or_default();
+        let succeeded = matches!(record["status"].as_str(), Some("Success" | "Simulated"));
+        realized_pnl_usd += realized;
+        for (dimension, key) in buckets.iter_mut().zip(keys) {
+            let bucket = dimension.entry(key.clone())
+                .or_insert_with(|| AttributionBucket { key, ..Default::default() });
+            bucket.executions += 1;
+            bucket.successful_executions += u64::from(succeeded);
+            bucket.expected_profit_usd += decimal_field(record, "expected_profit_usd").unwrap_or_default();
+            bucket.realized_pnl_usd += realized;
+            bucket.winning_executions += u64::from(realized > Decimal::ZERO);
+        }
+    }
+
+    let [by_pool, by_direction, mut by_hour, by_volatility] = buckets.map(|dimension| {
+        let mut buckets: Vec<AttributionBucket> = dimension.into_values()
+            .map(|mut bucket| {
+                let executions = Decimal::from(bucket.executions);
+                bucket.mean_pnl_usd = bucket.realized_pnl_usd / executions;
+                bucket.win_rate_pct = Decimal::from(bucket.winning_executions) * dec!(100) / executions;
+                bucket
+            })
+            .collect();
+        buckets.sort_by(|a, b| b.realized_pnl_usd.cmp(&a.realized_pnl_usd).then_with(|| a.key.cmp(&b.key)));
+        buckets
+    });
+    by_hour.sort_by(|a, b| a.key.cmp(&b.key));
+
+    Ok(ProfitAttributionReport {
+        generated_at: Utc::now(),
+        start,
+        end,
+        executions: executions.len() as u64,
+        realized_pnl_usd,
+        by_pool,
+        by_direction,
+        by_hour,
+        by_volatility,
+        unattributed_executions,
+        unreadable_lines,
+    })
+}
+
+pub fn save_profit_attribution(report: &ProfitAttributionReport) -> Result<String> {
+    let filename = format!("output/reports/attribution_{}.json", 
+        Utc::now().format("%Y-%m-%d_%H%M%S"));
+    
+    std::fs::write(&filename, serde_json::to_string_pretty(report)?)?;
+    
+    info!(
+        file = %filename,
+        executions = report.executions,
+        "Saved profit attribution report"
+    );
+    
+    Ok(filename)
+}
@@ -0,0 +1,174 @@
+//! Retention for the rotated logs and daily output files
+//!
+//! Files untouched for `compress_after_days` are gzipped in place (`<name>.gz`,
+//! keeping the modification time). Files older than `retention_days` are
+//! deleted, and while the managed directories exceed `max_total_mb` the
+//! oldest files go first. Files written in the last day are never touched.
+
+use anyhow::{Context, Result};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::{debug, info};
+use crate::config::Config;
+
+/// Directories holding hourly logs and daily JSONL/CSV outputs
+pub const RETENTION_DIRS: &[&str] = &[
+    "output/logs",
+    "output/opportunities",
+    "output/market_making",
+    "output/executions",
+    "output/decisions",
+    "output/spreads",
+    "output/prices",
+    "output/portfolio",
+    "output/lifecycles",
+];
+
+fn days(n: u64) -> Duration {
+    Duration::from_secs(n.saturating_mul(24 * 60 * 60))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Gzip files older than this many days (0 = never)
+    pub compress_after_days: u64,
+    /// Delete files older than this many days (0 = keep)
+    pub retention_days: u64,
+    /// Delete the oldest files while the directories exceed this size (0 = unlimited)
+    pub max_total_mb: u64,
+}
+
+impl RetentionPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            compress_after_days: config.output_compress_after_days,
+            retention_days: config.output_retention_days,
+            max_total_mb: config.output_max_size_mb,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.compress_after_days > 0 || self.retention_days > 0 || self.max_total_mb > 0
+    }
+}
+
+/// What one sweep did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionSweep {
+    pub compressed: u64,
+    pub deleted: u64,
+    pub freed_bytes: u64,
+    /// Size of the managed files after the sweep
+    pub total_bytes: u64,
+}
+
+struct OutputFile {
+    path: PathBuf,
+    modified: SystemTime,
+    len: u64,
+}
+
+/// Apply `policy` to the output directories
+pub fn sweep_output(policy: &RetentionPolicy) -> Result<RetentionSweep> {
+    let dirs: Vec<PathBuf> = RETENTION_DIRS.iter().map(PathBuf::from).collect();
+    let sweep = sweep_dirs(&dirs, policy, SystemTime::now())?;
+    if sweep.compressed > 0 || sweep.deleted > 0 {
+        info!("🧹 Output retention: {} files compressed, {} deleted ({:.1} MB freed), {:.1} MB kept",
+            sweep.compressed, sweep.deleted, mb(sweep.freed_bytes), mb(sweep.total_bytes));
+    }
+    Ok(sweep)
+}
+
+fn mb(bytes: u64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+fn age(file: &OutputFile, now: SystemTime) -> Duration {
+    now.duration_since(file.modified).unwrap_or_default()
+}
+
+/// Apply `policy` to the files directly in `dirs`, ages measured from `now`
+pub fn sweep_dirs(dirs: &[PathBuf], policy: &RetentionPolicy, now: SystemTime) -> Result<RetentionSweep> {
+    let mut sweep = RetentionSweep::default();
+    if !policy.is_enabled() {
+        return Ok(sweep);
+    }
+    let mut files = Vec::new();
+    for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+        for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_file() {
+                files.push(OutputFile { path: entry.path(), modified: metadata.modified()?, len: metadata.len() });
+            }
+        }
+    }
+
+    let mut kept = Vec::new();
+    for mut file in files {
+        if policy.retention_days > 0 && age(&file, now) > days(policy.retention_days) {
+            delete(&file, &mut sweep)?;
+            continue;
+        }
+        let compressed = file.path.extension().is_some_and(|ext| ext == "gz");
+        if policy.compress_after_days > 0 && !compressed && age(&file, now) > days(policy.compress_after_days) {
+            let gz_len = compress(&file.path, file.modified)?;
+            sweep.compressed += 1;
+            sweep.freed_bytes += file.len.saturating_sub(gz_len);
+            let mut name = file.path.into_os_string();
+            name.push(".gz");
+            file.path = name.into();
+            file.len = gz_len;
+        }
+        kept.push(file);
+    }
+
+    // Oldest first, sparing the files still being written
+    kept.sort_by_key(|file| file.modified);
+    let mut total: u64 = kept.iter().map(|file| file.len).sum();
+    let max_total = policy.max_total_mb.saturating_mul(1024 * 1024);
+    if policy.max_total_mb > 0 {
+        for file in kept.iter().filter(|file| age(file, now) > days(1)) {
+            if total <= max_total {
+                break;
+            }
+            delete(file, &mut sweep)?;
+            total -= file.len;
+        }
+    }
+    sweep.total_bytes = total;
+    Ok(sweep)
+}
+
+fn delete(file: &OutputFile, sweep: &mut RetentionSweep) -> Result<()> {
+    fs::remove_file(&file.path).with_context(|| format!("Failed to delete {}", file.path.display()))?;
+    debug!("Deleted {}", file.path.display());
+    sweep.deleted += 1;
+    sweep.freed_bytes += file.len;
+    Ok(())
+}
+
+/// Gzip `path` to `<path>.gz` with its modification time, remove the original
+/// and return the compressed size
+fn compress(path: &Path, modified: SystemTime) -> Result<u64> {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".gz");
+    let gz_path = PathBuf::from(name);
+
+    let mut input = BufReader::new(File::open(path)?);
+    let mut encoder = GzEncoder::new(File::create(&gz_path)?, Compression::default());
+    io::copy(&mut input, &mut encoder)
+        .with_context(|| format!("Failed to compress {}", path.display()))?;
+    let output = encoder.finish()?;
+    output.sync_all()?;
+    output.set_modified(modified)?;
+    let len = output.metadata()?.len();
+
+    fs::remove_file(path)?;
+    debug!("Compressed {} to {}", path.display(), gz_path.display());
+    Ok(len)
+}
@@ -0,0 +1,202 @@
+//! Per-cycle spread and depth observations for spread heatmaps
+//!
+//! One CSV row per pool per cycle in `output/spreads/spreads_YYYY-MM-DD.csv`,
+//! aggregated offline into hour-of-day × pool cells.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Timelike, Utc};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use tracing::debug;
+
+pub const SPREADS_DIR: &str = "output/spreads";
+const SPREADS_HEADER: &str = "timestamp_ms,hour,pool,spread_bps,edge_bps,liquidity_usd,health_score";
+
+/// One pool's spread against fair value in one monitoring cycle
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpreadObservation {
+    pub timestamp: DateTime<Utc>,
+    pub pool: String,
+    /// (DEX - fair value) / fair value, signed
+    pub spread_bps: Decimal,
+    /// Best of buying or selling the trade size against fair value, after
+    /// curve and fee; None without an execution quote
+    pub edge_bps: Option<Decimal>,
+    pub liquidity_usd: Option<Decimal>,
+    pub health_score: Option<Decimal>,
+}
+
+impl SpreadObservation {
+    fn to_row(&self) -> String {
+        let optional = |value: Option<Decimal>| value.map(|v| v.round_dp(2).to_string()).unwrap_or_default();
+        format!(
+            "{},{},{},{},{},{},{}",
+            self.timestamp.timestamp_millis(),
+            self.timestamp.hour(),
+            self.pool.replace(',', ";"),
+            self.spread_bps.round_dp(2),
+            optional(self.edge_bps),
+            optional(self.liquidity_usd.map(|usd| usd.round())),
+            optional(self.health_score),
+        )
+    }
+
+    fn from_row(row: &str) -> Option<Self> {
+        let fields: Vec<&str> = row.split(',').collect();
+        let [timestamp_ms, _hour, pool, spread_bps, edge_bps, liquidity_usd, health_score] = fields[..] else {
+            return None;
+        };
+        let optional = |field: &str| (!field.is_empty()).then(|| Decimal::from_str(field).ok()).flatten();
+        Some(Self {
+            timestamp: DateTime::from_timestamp_millis(timestamp_ms.parse().ok()?)?,
+            pool: pool.to_string(),
+            spread_bps: Decimal::from_str(spread_bps).ok()?,
+            edge_bps: optional(edge_bps),
+            liquidity_usd: optional(liquidity_usd),
+            health_score: optional(health_score),
+        })
+    }
+}
+
+pub fn save_spread_observations(observations: &[SpreadObservation]) -> Result<()> {
+    if super::skip_write(SPREADS_DIR, &observations.iter().map(SpreadObservation::to_row).collect::<Vec<_>>()) {
+        return Ok(());
+    }
+    save_spread_observations_in(Path::new(SPREADS_DIR), observations)
+}
+
+/// Append observations to the day's file in `dir`, writing the header for a new file
+pub fn save_spread_observations_in(dir: &Path, observations: &[SpreadObservation]) -> Result<()> {
+    let Some(first) = observations.first() else {
+        return Ok(());
+    };
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("spreads_{}.csv", first.timestamp.format("%Y-%m-%d")));
+    let is_new = !path.exists();
+
+    let mut rows = String::new();
+    if is_new {
+        rows.push_str(SPREADS_HEADER);
+        rows.push('\n');
+    }
+    for observation in observations {
+        rows.push_str(&observation.to_row());
+        rows.push('\n');
+    }
+    OpenOptions::new().create(true).append(true).open(&path)
+        .and_then(|mut file| file.write_all(rows.as_bytes()))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    debug!("Saved {} spread observations to {}", observations.len(), path.display());
+    Ok(())
+}
+
+/// Every observation recorded in `dir`, oldest file first
+pub fn load_spread_observations(dir: &Path) -> Result<Vec<SpreadObservation>> {
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "csv"))
+        .collect();
+    paths.sort();
+
+    let mut observations = Vec::new();
+    for path in paths {
+        let file = fs::File::open(&path)?;
+        for line in BufReader::new(file).lines().map_while(Result::ok) {
+            if line == SPREADS_HEADER {
+                continue;
+            }
+            match SpreadObservation::from_row(&line) {
+                Some(observation) => observations.push(observation),
+                None => debug!("Skipping unreadable spread row in {}: {}", path.display(), line),
+            }
+        }
+    }
+    Ok(observations)
+}
+
+/// Spread statistics for one pool in one UTC hour of the day
+#[derive(Debug, Clone, Serialize)]
+pub struct SpreadHeatmapCell {
+    pub pool: String,
+    pub hour: u32,
+    pub samples: u64,
+    pub mean_abs_spread_bps: Decimal,
+    pub max_abs_spread_bps: Decimal,
+    /// Mean edge over the samples that had an execution quote
+    pub mean_edge_bps: Option<Decimal>,
+    /// Share of quoted samples with positive edge, in percent
+    pub positive_edge_pct: Option<Decimal>,
+    pub mean_liquidity_usd: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpreadHeatmap {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub observations: u64,
+    /// Sorted by pool, then hour
+    pub cells: Vec<SpreadHeatmapCell>,
+}
+
+#[derive(Default)]
+struct CellTotals {
+    samples: u64,
+    abs_spread_bps: Decimal,
+    max_abs_spread_bps: Decimal,
+    edge_samples: u64,
+    edge_bps: Decimal,
+    positive_edge: u64,
+    liquidity_samples: u64,
+    liquidity_usd: Decimal,
+}
+
+/// Aggregate observations into hour-of-day × pool cells
+pub fn build_spread_heatmap(observations: &[SpreadObservation]) -> SpreadHeatmap {
+    let mut totals: BTreeMap<(String, u32), CellTotals> = BTreeMap::new();
+    for observation in observations {
+        let cell = totals.entry((observation.pool.clone(), observation.timestamp.hour())).or_default();
+        let abs_spread = observation.spread_bps.abs();
+        cell.samples += 1;
+        cell.abs_spread_bps += abs_spread;
+        cell.max_abs_spread_bps = cell.max_abs_spread_bps.max(abs_spread);
+        if let Some(edge) = observation.edge_bps {
+            cell.edge_samples += 1;
+            cell.edge_bps += edge;
+            if edge > dec!(0) {
+                cell.positive_edge += 1;
+            }
+        }
+        if let Some(liquidity) = observation.liquidity_usd {
+            cell.liquidity_samples += 1;
+            cell.liquidity_usd += liquidity;
+        }
+    }
+
+    let mean = |total: Decimal, count: u64| (count > 0).then(|| total / Decimal::from(count));
+    let cells = totals.into_iter()
+        .map(|((pool, hour), cell)| SpreadHeatmapCell {
+            pool,
+            hour,
+            samples: cell.samples,
+            mean_abs_spread_bps: mean(cell.abs_spread_bps, cell.samples).unwrap_or_default(),
+            max_abs_spread_bps: cell.max_abs_spread_bps,
+            mean_edge_bps: mean(cell.edge_bps, cell.edge_samples),
+            positive_edge_pct: mean(Decimal::from(cell.positive_edge) * dec!(100), cell.edge_samples),
+            mean_liquidity_usd: mean(cell.liquidity_usd, cell.liquidity_samples),
+        })
+        .collect();
+
+    SpreadHeatmap {
+        start: observations.iter().map(|o| o.timestamp).min(),
+        end: observations.iter().map(|o| o.timestamp).max(),
+        observations: observations.len() as u64,
+        cells,
+    }
+}
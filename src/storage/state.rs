@@ -0,0 +1,57 @@
+//! Bot state saved while running and on shutdown, restored on startup
+//!
+//! `output/state.json` holds the session counters, the ETH/USD samples behind
+//! the volatility windows and the last prices, so a restart resumes with warm
+//! volatility windows instead of empty ones. Delete the file to start fresh.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use tracing::debug;
+use crate::types::BotState;
+
+pub const STATE_PATH: &str = "output/state.json";
+
+pub fn save_bot_state(state: &BotState) -> Result<()> {
+    if super::is_dry_run() {
+        // The full state repeats every volatility sample, so only note it
+        tracing::info!("🧪 Dry run: would write bot state to {}", STATE_PATH);
+        return Ok(());
+    }
+    save_bot_state_to(Path::new(STATE_PATH), state)
+}
+
+/// Write `state` through a temporary file so a crash mid-write keeps the previous state
+pub fn save_bot_state_to(path: &Path, state: &BotState) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_string_pretty(state)?)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    fs::rename(&tmp, path)
+        .with_context(|| format!("Failed to replace {}", path.display()))?;
+
+    debug!(
+        file = %path.display(),
+        volatility_samples = state.volatility_history.samples.len(),
+        "Saved bot state"
+    );
+    Ok(())
+}
+
+pub fn load_bot_state() -> Result<Option<BotState>> {
+    load_bot_state_from(Path::new(STATE_PATH))
+}
+
+/// The saved state, or None when no state file exists
+pub fn load_bot_state_from(path: &Path) -> Result<Option<BotState>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let state = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(Some(state))
+}
@@ -0,0 +1,125 @@
+//! Session statistics aggregated across restarts
+//!
+//! `output/stats/lifetime.json` sums what every run counted, in total and per
+//! UTC day. A run folds what its counters added since the previous fold in
+//! whenever it saves its state, at each day rollover and at shutdown.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use tracing::debug;
+use crate::types::{DayStats, LifetimeStats, SessionCounters};
+
+pub const LIFETIME_STATS_PATH: &str = "output/stats/lifetime.json";
+
+/// Folds a running session's counters into the lifetime statistics
+#[derive(Debug, Clone, Default)]
+pub struct SessionStatsRecorder {
+    lifetime: LifetimeStats,
+    /// Counters, errors and runtime already folded in
+    folded: SessionCounters,
+    folded_errors: HashMap<String, u32>,
+    folded_runtime_secs: u64,
+    /// Days this run has counted on
+    days: BTreeSet<NaiveDate>,
+}
+
+impl SessionStatsRecorder {
+    /// Start a run on top of `lifetime`. `counters` are what the session
+    /// starts from (restored from the saved state) and aren't counted again.
+    pub fn new(mut lifetime: LifetimeStats, counters: &SessionCounters, now: DateTime<Utc>) -> Self {
+        lifetime.sessions += 1;
+        lifetime.first_started_at.get_or_insert(now);
+        lifetime.updated_at = Some(now);
+        let today = now.date_naive();
+        lifetime.days.entry(today).or_default().sessions += 1;
+        Self {
+            lifetime,
+            folded: counters.clone(),
+            folded_errors: HashMap::new(),
+            folded_runtime_secs: 0,
+            days: BTreeSet::from([today]),
+        }
+    }
+
+    /// Add what the session counted since the last fold to `day` and the totals
+    pub fn record(
+        &mut self,
+        day: NaiveDate,
+        counters: &SessionCounters,
+        error_counts: &HashMap<String, u32>,
+        runtime_secs: u64,
+    ) {
+        let added = counters.since(&self.folded);
+        let runtime = runtime_secs.saturating_sub(self.folded_runtime_secs);
+        let day_stats = self.lifetime.days.entry(day).or_default();
+        if self.days.insert(day) {
+            day_stats.sessions += 1;
+        }
+        day_stats.counters.add(&added);
+        day_stats.runtime_secs += runtime;
+        self.lifetime.counters.add(&added);
+        self.lifetime.runtime_secs += runtime;
+
+        for (kind, &count) in error_counts {
+            let new = u64::from(count.saturating_sub(self.folded_errors.get(kind).copied().unwrap_or(0)));
+            if new > 0 {
+                *day_stats.error_counts.entry(kind.clone()).or_insert(0) += new;
+                *self.lifetime.error_counts.entry(kind.clone()).or_insert(0) += new;
+            }
+        }
+
+        self.folded = counters.clone();
+        self.folded_errors = error_counts.clone();
+        self.folded_runtime_secs = runtime_secs;
+        self.lifetime.updated_at = Some(Utc::now());
+    }
+
+    pub fn lifetime(&self) -> &LifetimeStats {
+        &self.lifetime
+    }
+
+    pub fn day(&self, day: NaiveDate) -> Option<&DayStats> {
+        self.lifetime.days.get(&day)
+    }
+}
+
+pub fn save_lifetime_stats(stats: &LifetimeStats) -> Result<()> {
+    if super::is_dry_run() {
+        tracing::info!("🧪 Dry run: would write lifetime statistics to {}", LIFETIME_STATS_PATH);
+        return Ok(());
+    }
+    save_lifetime_stats_to(Path::new(LIFETIME_STATS_PATH), stats)
+}
+
+/// Write `stats` through a temporary file so a crash mid-write keeps the previous totals
+pub fn save_lifetime_stats_to(path: &Path, stats: &LifetimeStats) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_string_pretty(stats)?)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    fs::rename(&tmp, path)
+        .with_context(|| format!("Failed to replace {}", path.display()))?;
+    debug!(file = %path.display(), sessions = stats.sessions, "Saved lifetime statistics");
+    Ok(())
+}
+
+pub fn load_lifetime_stats() -> Result<Option<LifetimeStats>> {
+    load_lifetime_stats_from(Path::new(LIFETIME_STATS_PATH))
+}
+
+/// The saved statistics, or None when no file exists
+pub fn load_lifetime_stats_from(path: &Path) -> Result<Option<LifetimeStats>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let stats = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(Some(stats))
+}
@@ -0,0 +1,104 @@
+//! The bot's own arbitrage and market-making strategies
+
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+use tracing::{debug, warn};
+use crate::{
+    arbitrage,
+    config::Config,
+    market_making::MarketMakingEngine,
+    ConcreteProvider,
+};
+use super::registry::{Action, MarketState, PoolState, Strategy, StrategyFuture};
+
+/// DEX/CEX arbitrage at the configured trade size
+pub struct ArbitrageStrategy;
+
+impl Strategy for ArbitrageStrategy {
+    fn name(&self) -> &str {
+        "arbitrage"
+    }
+
+    fn evaluate<'a>(&'a self, pool: &'a PoolState<'a>, market: &'a MarketState<'a>) -> StrategyFuture<'a> {
+        Box::pin(async move {
+            if pool.trade_size <= dec!(0) {
+                return Ok(Vec::new());
+            }
+            // With a z-score threshold, only a basis outside its usual range is an opportunity
+            let threshold = market.config.basis_z_threshold;
+            if pool.basis.is_some_and(|basis| !basis.signals(threshold)) {
+                debug!("{}: basis within {} standard deviations of its mean", pool.pool.name, threshold);
+                return Ok(Vec::new());
+            }
+            let opportunity = arbitrage::calculate_arbitrage(
+                &pool.pool.name,
+                pool.dex_price,
+                pool.fair_value,
+                pool.trade_size,
+                pool.quote,
+                pool.reserves,
+                market.gas_cost_usd,
+                &arbitrage::FeeModel::from_config(market.config, pool.pool.fee),
+                market.config.min_price_diff_pct_for(pool.basis, pool.fair_value),
+            ).map(|mut opportunity| {
+                opportunity.price_basis = pool.price_basis;
+                opportunity.fair_values = Some(pool.fair_values.clone());
+                Action::Arbitrage(opportunity)
+            });
+            Ok(opportunity.into_iter().collect())
+        })
+    }
+}
+
+/// Volatility-adaptive market making on WETH/USD pools (with ENABLE_MARKET_MAKING)
+pub struct MarketMakingStrategy {
+    engine: Arc<MarketMakingEngine>,
+    provider: Arc<ConcreteProvider>,
+}
+
+impl MarketMakingStrategy {
+    pub fn new(engine: Arc<MarketMakingEngine>, provider: Arc<ConcreteProvider>) -> Self {
+        Self { engine, provider }
+    }
+}
+
+impl Strategy for MarketMakingStrategy {
+    fn name(&self) -> &str {
+        "market_making"
+    }
+
+    fn enabled(&self, config: &Config) -> bool {
+        config.enable_market_making
+    }
+
+    fn evaluate<'a>(&'a self, pool: &'a PoolState<'a>, market: &'a MarketState<'a>) -> StrategyFuture<'a> {
+        Box::pin(async move {
+            // Inventory is WETH/USD
+            if !pool.pool.pair.is_weth_usd() {
+                return Ok(Vec::new());
+            }
+            // Fill the quotes placed by the previous signal before quoting again
+            let mut actions: Vec<Action> = self.engine
+                .match_quotes(&pool.pool.name, pool.dex_price, pool.fair_value).await
+                .into_iter()
+                .map(Action::QuoteFill)
+                .collect();
+            let Some(liquidity_depth) = pool.liquidity_depth else {
+                return Ok(actions);
+            };
+            match self.engine.generate_market_making_signal(
+                pool.pool,
+                pool.fair_values,
+                market.config.price_basis,
+                pool.dex_price,
+                liquidity_depth.clone(),
+                market.gas_cost_usd,
+                self.provider.as_ref(),
+            ).await {
+                Ok(signal) => actions.push(Action::MarketMaking(signal)),
+                Err(e) => warn!("Failed to generate market making signal for {}: {}", pool.pool.name, e),
+            }
+            Ok(actions)
+        })
+    }
+}
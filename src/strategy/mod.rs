@@ -0,0 +1,7 @@
+//! Pluggable trading strategies evaluated for every pool each cycle
+
+pub mod registry;
+pub mod builtin;
+
+pub use registry::*;
+pub use builtin::*;
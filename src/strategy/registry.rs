@@ -0,0 +1,129 @@
+//! Strategy trait and the registered strategy set
+//!
+//! A [`Strategy`] turns one pool's state into [`Action`]s. The cycle gathers
+//! prices, depth and quotes once per pool and evaluates every registered
+//! strategy against them; the actions then go through the same validation,
+//! risk checks, execution and recording as the built-in arbitrage and
+//! market-making logic.
+
+use anyhow::Result;
+use rust_decimal::Decimal;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tracing::warn;
+use crate::arbitrage::BasisStats;
+use crate::config::Config;
+use crate::pools::{PoolQuote, PoolReserves};
+use crate::types::{
+    ArbitrageOpportunity, FairValueQuotes, LiquidityDepth, MarketMakingSignal, PoolHealth, PoolInfo, PriceBasis,
+    QuoteFill, VolatilityMetrics,
+};
+
+/// Boxed future returned by [`Strategy::evaluate`], so strategies stay object safe
+pub type StrategyFuture<'a> = Pin<Box<dyn Future<Output = Result<Vec<Action>>> + Send + 'a>>;
+
+/// What one pool looks like this cycle, in USD
+#[derive(Clone, Copy)]
+pub struct PoolState<'a> {
+    pub pool: &'a PoolInfo,
+    pub dex_price: Decimal,
+    /// USD value of one quote token
+    pub quote_usd: Decimal,
+    pub cex_price: Decimal,
+    pub fair_values: &'a FairValueQuotes,
+    pub price_basis: PriceBasis,
+    pub fair_value: Decimal,
+    pub price_diff_pct: Decimal,
+    /// Arbitrage trade size in base tokens after the risk hooks' sizers (zero skips)
+    pub trade_size: Decimal,
+    /// Quoted execution prices for `trade_size`, when the quoter answered
+    pub quote: Option<&'a PoolQuote>,
+    pub reserves: Option<&'a PoolReserves>,
+    pub liquidity_depth: Option<&'a LiquidityDepth>,
+    pub health: Option<&'a PoolHealth>,
+    /// The DEX/CEX basis against the pool's recent history
+    pub basis: Option<&'a BasisStats>,
+}
+
+/// Cycle-wide market state shared by every pool
+#[derive(Clone, Copy)]
+pub struct MarketState<'a> {
+    /// ETH/USD reference price of the cycle
+    pub eth_usd: Decimal,
+    pub gas_cost_usd: Decimal,
+    pub gas_baseline_usd: Decimal,
+    pub oracle_price: Option<Decimal>,
+    pub volatility: &'a VolatilityMetrics,
+    pub config: &'a Config,
+}
+
+/// What a strategy wants done for a pool
+#[derive(Debug, Clone)]
+pub enum Action {
+    /// Trade the spread: validated, recorded and, with trade execution
+    /// enabled, queued like built-in opportunities
+    Arbitrage(ArbitrageOpportunity),
+    /// Quote the pool: sized by the risk hooks, placed on the simulated
+    /// quote book and recorded
+    MarketMaking(MarketMakingSignal),
+    /// A simulated quote from an earlier signal that the market traded through
+    QuoteFill(QuoteFill),
+}
+
+pub trait Strategy: Send + Sync {
+    /// Name used in logs
+    fn name(&self) -> &str;
+
+    /// Whether the strategy runs under `config`, checked every cycle so
+    /// reloaded settings apply without a restart
+    fn enabled(&self, _config: &Config) -> bool {
+        true
+    }
+
+    fn evaluate<'a>(&'a self, pool: &'a PoolState<'a>, market: &'a MarketState<'a>) -> StrategyFuture<'a>;
+}
+
+/// Registered strategies, evaluated in registration order
+#[derive(Clone, Default)]
+pub struct Strategies {
+    strategies: Vec<Arc<dyn Strategy>>,
+}
+
+impl Strategies {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_strategy(mut self, strategy: impl Strategy + 'static) -> Self {
+        self.strategies.push(Arc::new(strategy));
+        self
+    }
+
+    /// Append another set's strategies after these
+    pub fn with_strategies(mut self, other: Strategies) -> Self {
+        self.strategies.extend(other.strategies);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strategies.is_empty()
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.strategies.iter().map(|strategy| strategy.name()).collect()
+    }
+
+    /// Actions of every enabled strategy. A failing strategy is logged and
+    /// skipped so it cannot hold back the others.
+    pub async fn evaluate(&self, pool: &PoolState<'_>, market: &MarketState<'_>) -> Vec<Action> {
+        let mut actions = Vec::new();
+        for strategy in self.strategies.iter().filter(|strategy| strategy.enabled(market.config)) {
+            match strategy.evaluate(pool, market).await {
+                Ok(strategy_actions) => actions.extend(strategy_actions),
+                Err(e) => warn!("Strategy {} failed for {}: {}", strategy.name(), pool.pool.name, e),
+            }
+        }
+        actions
+    }
+}
@@ -0,0 +1,208 @@
+//! Scripted chain behind a mock JSON-RPC transport
+//!
+//! `eth_call` results are scripted per contract and selector (or exact
+//! calldata), `eth_blockNumber` follows [`MockChain::set_block_number`], and
+//! any other method answers from [`MockChain::set_response`]. Unscripted
+//! requests fail with a JSON-RPC error naming what was missing, so a test
+//! never silently reads a default.
+
+use alloy::{
+    primitives::{Address, Bytes, U256},
+    providers::ProviderBuilder,
+    rpc::{
+        client::ClientBuilder,
+        json_rpc::{RequestPacket, Response, ResponsePacket, SerializedRequest},
+    },
+    sol_types::SolCall,
+    transports::{TransportError, TransportFut},
+};
+use rust_decimal::prelude::*;
+use rust_decimal_macros::dec;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+use tower::Service;
+use crate::{
+    network::{IAerodromePool, IPoolFactory, IERC20},
+    types::{PoolInfo, AERODROME_POOL_FACTORY_MAINNET},
+    ConcreteProvider,
+};
+
+/// Scripted `eth_call` output, or a revert reason
+type CallResult = Result<Bytes, String>;
+
+#[derive(Default)]
+struct ChainState {
+    block_number: u64,
+    calls: HashMap<(Address, [u8; 4]), CallResult>,
+    /// Checked before `calls`
+    exact_calls: HashMap<(Address, Bytes), CallResult>,
+    responses: HashMap<String, Value>,
+    /// Methods requested, in order
+    requests: Vec<String>,
+}
+
+impl ChainState {
+    fn result(&mut self, method: &str, params: &Value) -> Result<Value, String> {
+        self.requests.push(method.to_string());
+        match method {
+            "eth_blockNumber" => Ok(json!(format!("0x{:x}", self.block_number))),
+            "eth_call" => self.call_result(&params[0]),
+            _ => self.responses.get(method).cloned()
+                .ok_or_else(|| format!("mock chain has no response for {}", method)),
+        }
+    }
+
+    fn call_result(&self, tx: &Value) -> Result<Value, String> {
+        let contract: Address = tx["to"].as_str()
+            .and_then(|to| to.parse().ok())
+            .ok_or("eth_call without a contract")?;
+        let input: Bytes = tx["input"].as_str()
+            .or(tx["data"].as_str())
+            .and_then(|input| input.parse().ok())
+            .unwrap_or_default();
+        let selector: [u8; 4] = input.get(..4)
+            .and_then(|selector| selector.try_into().ok())
+            .ok_or("eth_call without a selector")?;
+        let scripted = self.exact_calls.get(&(contract, input.clone()))
+            .or_else(|| self.calls.get(&(contract, selector)));
+        match scripted {
+            Some(Ok(output)) => Ok(json!(output)),
+            Some(Err(reason)) => Err(format!("execution reverted: {}", reason)),
+            None => Err(format!("no scripted result for 0x{} on {}", hex::encode(selector), contract)),
+        }
+    }
+}
+
+/// Shared handle to the scripted chain; clones see the same state
+#[derive(Clone, Default)]
+pub struct MockChain {
+    state: Arc<Mutex<ChainState>>,
+}
+
+impl MockChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> MutexGuard<'_, ChainState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Provider backed by this chain, usable wherever the bot takes one
+    pub fn provider(&self) -> ConcreteProvider {
+        let client = ClientBuilder::default().transport(MockTransport { state: self.state.clone() }, true);
+        ProviderBuilder::new().on_client(client).boxed()
+    }
+
+    pub fn set_block_number(&self, block_number: u64) {
+        self.state().block_number = block_number;
+    }
+
+    /// Mine one block, returning its number
+    pub fn advance_block(&self) -> u64 {
+        let mut state = self.state();
+        state.block_number += 1;
+        state.block_number
+    }
+
+    /// Answer every `C` call on `contract` with `output`, e.g.
+    /// `IERC20::decimalsCall::abi_encode_returns(&(18u8,))`
+    pub fn set_call<C: SolCall>(&self, contract: Address, output: Vec<u8>) {
+        self.state().calls.insert((contract, C::SELECTOR), Ok(output.into()));
+    }
+
+    /// Answer `call` with exactly these arguments, ahead of [`MockChain::set_call`]
+    pub fn set_call_exact<C: SolCall>(&self, contract: Address, call: C, output: Vec<u8>) {
+        self.state().exact_calls.insert((contract, call.abi_encode().into()), Ok(output.into()));
+    }
+
+    /// Revert every `C` call on `contract`
+    pub fn revert_call<C: SolCall>(&self, contract: Address, reason: &str) {
+        self.state().calls.insert((contract, C::SELECTOR), Err(reason.to_string()));
+    }
+
+    /// Result for a method other than `eth_call` and `eth_blockNumber`
+    pub fn set_response(&self, method: &str, result: Value) {
+        self.state().responses.insert(method.to_string(), result);
+    }
+
+    pub fn set_reserves(&self, pool: Address, reserve0: U256, reserve1: U256) {
+        self.set_call::<IAerodromePool::getReservesCall>(
+            pool,
+            IAerodromePool::getReservesCall::abi_encode_returns(&(reserve0, reserve1, U256::ZERO)),
+        );
+    }
+
+    /// Script a basic pool's tokens, stability, factory fee, token metadata and reserves
+    pub fn set_basic_pool(&self, pool: &PoolInfo, reserve0: U256, reserve1: U256) {
+        self.set_call::<IAerodromePool::token0Call>(pool.address, IAerodromePool::token0Call::abi_encode_returns(&(pool.token0,)));
+        self.set_call::<IAerodromePool::token1Call>(pool.address, IAerodromePool::token1Call::abi_encode_returns(&(pool.token1,)));
+        self.set_call::<IAerodromePool::stableCall>(pool.address, IAerodromePool::stableCall::abi_encode_returns(&(pool.is_stable,)));
+        self.set_call::<IAerodromePool::factoryCall>(
+            pool.address,
+            IAerodromePool::factoryCall::abi_encode_returns(&(AERODROME_POOL_FACTORY_MAINNET,)),
+        );
+        let fee_bps = (pool.fee * dec!(10000)).to_u64().unwrap_or_default();
+        self.set_call_exact(
+            AERODROME_POOL_FACTORY_MAINNET,
+            IPoolFactory::getFeeCall { pool: pool.address, stable: pool.is_stable },
+            IPoolFactory::getFeeCall::abi_encode_returns(&(U256::from(fee_bps),)),
+        );
+        let pair = &pool.pair;
+        for (token, symbol, decimals) in [
+            (pair.base, &pair.base_symbol, pair.base_decimals),
+            (pair.quote, &pair.quote_symbol, pair.quote_decimals),
+        ] {
+            self.set_call::<IERC20::symbolCall>(token, IERC20::symbolCall::abi_encode_returns(&(symbol.clone(),)));
+            self.set_call::<IERC20::decimalsCall>(token, IERC20::decimalsCall::abi_encode_returns(&(decimals as u8,)));
+        }
+        self.set_reserves(pool.address, reserve0, reserve1);
+    }
+
+    /// Methods requested so far, in order
+    pub fn requests(&self) -> Vec<String> {
+        self.state().requests.clone()
+    }
+}
+
+/// JSON-RPC transport answering from a [`MockChain`]
+#[derive(Clone)]
+struct MockTransport {
+    state: Arc<Mutex<ChainState>>,
+}
+
+impl MockTransport {
+    fn respond(&self, request: &SerializedRequest) -> Response {
+        let params: Value = request.params()
+            .and_then(|params| serde_json::from_str(params.get()).ok())
+            .unwrap_or(Value::Null);
+        let result = self.state.lock().unwrap_or_else(|e| e.into_inner()).result(request.method(), &params);
+        let body = match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": request.id(), "result": result }),
+            Err(message) => json!({ "jsonrpc": "2.0", "id": request.id(), "error": { "code": -32000, "message": message } }),
+        };
+        serde_json::from_str(&body.to_string()).expect("mock response is valid JSON-RPC")
+    }
+}
+
+impl Service<RequestPacket> for MockTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let response = match request {
+            RequestPacket::Single(request) => ResponsePacket::Single(self.respond(&request)),
+            RequestPacket::Batch(requests) => {
+                ResponsePacket::Batch(requests.iter().map(|request| self.respond(request)).collect())
+            }
+        };
+        Box::pin(async move { Ok(response) })
+    }
+}
@@ -0,0 +1,12 @@
+//! Deterministic test doubles for integration tests (`test-support` feature)
+//!
+//! [`MockChain`] answers JSON-RPC from scripted state behind a real
+//! [`ConcreteProvider`](crate::ConcreteProvider), so pool, arbitrage,
+//! validation and execution code runs unchanged against it. [`seeded_rng`]
+//! makes the execution simulator's draws reproducible.
+
+pub mod chain;
+pub mod rng;
+
+pub use chain::*;
+pub use rng::*;
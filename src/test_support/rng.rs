@@ -0,0 +1,11 @@
+//! Seeded randomness for reproducible simulations
+
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Seed used by tests that only need some fixed sequence
+pub const DEFAULT_TEST_SEED: u64 = 42;
+
+/// RNG that yields the same sequence for the same seed on every run
+pub fn seeded_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
@@ -10,7 +10,21 @@ pub const USDBC_MAINNET: Address = address!("d9aAEc86B65D86f6A7B5B1b0c42FFA53171
 // Base Sepolia testnet addresses
 pub const WETH_SEPOLIA: Address = address!("4200000000000000000000000000000000000006");
 pub const USDC_SEPOLIA: Address = address!("AF33ADd7918F685B2A82C1077bd8c07d220FFA04"); // Base Sepolia USDC
-#[allow(dead_code)]
+// Aerodrome core contracts (Base mainnet)
+pub const AERODROME_ROUTER_MAINNET: Address = address!("cF77a3Ba9A5CA399B7c97c74d54e5b1Beb874E43");
+pub const AERODROME_POOL_FACTORY_MAINNET: Address = address!("420DD381b31aEf6683db6B902084cB0FFECe40Da");
+pub const AERODROME_VOTER_MAINNET: Address = address!("16613524e02ad97eDfeF371bC883F2F5d6C480A5");
+// AERO emissions token and the vAMM-USDC/AERO pool pricing it
+pub const AERO_MAINNET: Address = address!("940181a94A35A4569E4529A3CDfB74e38FD98631");
+pub const AERO_USDC_POOL_MAINNET: Address = address!("6cDcb1C4A4D1C3C6d054b27AC5B77e89eAFb971d");
+// OP Stack predeploy that prices the L1 data fee of Base transactions
+pub const GAS_PRICE_ORACLE_BASE: Address = address!("420000000000000000000000000000000000000F");
+// Chainlink ETH/USD aggregator proxy on Base mainnet
+pub const CHAINLINK_ETH_USD_BASE: Address = address!("71041dddad3595F9CEd3DcCFBe3D1F4b0a16Bb70");
+// Flash loan lenders on Base mainnet
+pub const AAVE_V3_POOL_BASE: Address = address!("A238Dd80C259a72e81d7e4664a9801593F98d1c5");
+pub const BALANCER_VAULT: Address = address!("BA12222222228d8Ba445958a75a0704d566BF2C8");
+
 pub const UNISWAP_V2_ROUTER_SEPOLIA: Address = address!("0xC532a74256D3Db42D0Bf7a0400fEFDbad7694008");
 
 // Mainnet pools
@@ -19,6 +33,11 @@ pub const POOLS_MAINNET: &[(&str, Address)] = &[
     ("WETH/USDC", address!("cDAC0d6c6C59727a65F871236188350531885C43")),
 ];
 
+// Mainnet Slipstream (concentrated liquidity) pools
+pub const CL_POOLS_MAINNET: &[(&str, Address)] = &[
+    ("CL100-WETH/USDC", address!("b2cc224c1c9feE385f8ad6a55b4d94E92359DC59")),
+];
+
 // Sepolia testnet pools (for execution)
 pub const POOLS_SEPOLIA: &[(&str, Address)] = &[
     ("WETH/USDC-Sepolia", address!("92b8274aba7ab667bee7eb776ec1de32438d90bf")), 
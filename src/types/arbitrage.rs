@@ -2,8 +2,8 @@
 
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use serde::Serialize;
-use super::{ValidationResult, VolatilityMetrics, TradeExecution};
+use serde::{Deserialize, Serialize};
+use super::{FairValueQuotes, PriceBasis, ValidationResult, VolatilityMetrics, TradeExecution};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct ArbitrageOpportunity {
@@ -12,14 +12,117 @@ pub struct ArbitrageOpportunity {
     pub pool: String,
     pub direction: String,
     pub dex_price: Decimal,
+    pub quoted_dex_price: Option<Decimal>,
+    /// Fair value the DEX price was compared against, on `price_basis`
     pub cex_price: Decimal,
+    pub price_basis: PriceBasis,
+    /// Every basis's price at detection
+    pub fair_values: Option<FairValueQuotes>,
     pub price_diff_pct: Decimal,
+    /// Average CEX fill for the trade size from the order book; None without depth
+    pub cex_fill_price: Option<Decimal>,
+    /// Trade size in base tokens (WETH on WETH/USD pools)
     pub size_eth: Decimal,
     pub gross_profit_usd: Decimal,
     pub gas_cost_usd: Decimal,
+    pub cex_fee_usd: Decimal,
+    /// Pool fee charged separately; zero when the execution price includes it
+    pub dex_fee_usd: Decimal,
+    pub transfer_cost_usd: Decimal,
     pub net_profit_usd: Decimal,
     pub roi_pct: Decimal,
     pub validation_checks: ValidationResult,
     pub volatility_assessment: Option<VolatilityMetrics>,
     pub execution_simulation: Option<TradeExecution>,
+    /// How far the opportunity got this cycle
+    pub stage: OpportunityStage,
+}
+
+impl ArbitrageOpportunity {
+    /// Move to the next stage; false if `stage` is not the one after the current one
+    pub fn advance(&mut self, stage: OpportunityStage) -> bool {
+        let allowed = self.stage.can_advance_to(stage);
+        if allowed {
+            self.stage = stage;
+        }
+        allowed
+    }
+}
+
+/// Opportunity state machine: detected → validated → executed → settled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpportunityStage {
+    /// Spread seen by a strategy
+    #[default]
+    Detected,
+    /// Passed validation and cleared min profit
+    Validated,
+    /// Sent for execution
+    Executed,
+    /// Execution finished with a final result
+    Settled,
+}
+
+impl OpportunityStage {
+    pub fn next(self) -> Option<Self> {
+        match self {
+            Self::Detected => Some(Self::Validated),
+            Self::Validated => Some(Self::Executed),
+            Self::Executed => Some(Self::Settled),
+            Self::Settled => None,
+        }
+    }
+
+    pub fn can_advance_to(self, stage: Self) -> bool {
+        self.next() == Some(stage)
+    }
+}
+
+/// Why a tracked opportunity stopped being tracked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LifecycleEnd {
+    /// The spread was gone before anything executed
+    Closed,
+    /// Dropped from the execution queue unexecuted: too old, or leadership was lost
+    Expired,
+    /// The execution errored or its transaction failed
+    Failed,
+    /// The execution succeeded or was simulated
+    Settled,
+    /// Still open when the bot stopped
+    Interrupted,
+}
+
+/// One opportunity followed across the cycles it was seen in, from first
+/// detection to its execution result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpportunityLifecycle {
+    /// Id of the first detection
+    pub id: String,
+    pub pool: String,
+    pub direction: String,
+    /// Furthest stage reached
+    pub stage: OpportunityStage,
+    pub end: LifecycleEnd,
+    pub detected_at: DateTime<Utc>,
+    pub validated_at: Option<DateTime<Utc>>,
+    pub executed_at: Option<DateTime<Utc>>,
+    pub settled_at: Option<DateTime<Utc>>,
+    pub last_seen_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    /// Cycles the spread was detected in
+    pub sightings: u32,
+    /// From first to last detection
+    pub persisted_ms: i64,
+    pub detected_net_profit_usd: Decimal,
+    pub peak_net_profit_usd: Decimal,
+    pub last_net_profit_usd: Decimal,
+    /// Detection that was queued for execution
+    pub queued_opportunity_id: Option<String>,
+    pub execution_id: Option<String>,
+    /// Realized profit, or the expected profit when none was realized
+    pub execution_profit_usd: Option<Decimal>,
+    pub profitable_at_execution: Option<bool>,
 }
@@ -0,0 +1,50 @@
+//! Storage checkpoint types
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use super::SessionSummary;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub enum CheckpointKind {
+    /// Periodic checkpoint during the day
+    Interval,
+    /// The UTC day rolled over
+    EndOfDay,
+    /// The UTC day rolled over into a new Aerodrome epoch (Thursday 00:00 UTC)
+    EndOfEpoch,
+    Shutdown,
+}
+
+/// Integrity of one daily JSONL output file at checkpoint time
+#[derive(Debug, Clone, Serialize)]
+pub struct FileIntegrity {
+    pub path: String,
+    pub lines: u64,
+    pub bytes: u64,
+    /// 1-based line numbers that are not valid JSON (first few only)
+    pub invalid_lines: Vec<u64>,
+    pub invalid_line_count: u64,
+    /// Line count at the previous checkpoint, if the file was checked before
+    pub previous_lines: Option<u64>,
+    /// File has fewer lines than at the previous checkpoint
+    pub truncated: bool,
+}
+
+impl FileIntegrity {
+    pub fn is_healthy(&self) -> bool {
+        self.invalid_line_count == 0 && !self.truncated
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Checkpoint {
+    pub id: String,
+    pub timestamp: DateTime<Utc>,
+    pub kind: CheckpointKind,
+    /// UTC days whose files were synced and verified
+    pub dates: Vec<NaiveDate>,
+    pub files: Vec<FileIntegrity>,
+    pub database_synced: bool,
+    pub healthy: bool,
+    pub session: SessionSummary,
+}
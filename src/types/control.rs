@@ -0,0 +1,25 @@
+//! Control-plane event types
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub enum ControlEvent {
+    /// Stop starting new monitoring cycles until resumed
+    Pause { reason: String },
+    Resume,
+    /// Stop the bot immediately
+    KillSwitch { reason: String },
+    /// The circuit breaker opened
+    BreakerTripped { reason: String },
+    /// The risk manager halted execution; monitoring continues
+    ExecutionHalted { reason: String },
+    /// Re-read the config file and environment, applying runtime-safe changes
+    ReloadConfig { reason: String },
+}
+
+impl ControlEvent {
+    /// Whether the event cancels an in-flight monitoring cycle rather than waiting for it
+    pub fn preempts_cycle(&self) -> bool {
+        matches!(self, ControlEvent::KillSwitch { .. } | ControlEvent::Pause { .. })
+    }
+}
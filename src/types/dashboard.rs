@@ -0,0 +1,83 @@
+//! Live dashboard types
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+use crate::types::{ArbitrageOpportunity, PriceBasis, StatusReport, TradeExecution, VolatilityMetrics};
+
+/// Opportunities and executions kept for the dashboard
+pub const DASHBOARD_RECENT_ITEMS: usize = 20;
+/// Volatility chart points kept for the dashboard (one per cycle)
+pub const DASHBOARD_VOLATILITY_POINTS: usize = 600;
+
+/// A pool's prices from its latest cycle
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolPriceSnapshot {
+    pub timestamp: DateTime<Utc>,
+    pub dex_price: Decimal,
+    pub fair_value: Decimal,
+    pub price_basis: PriceBasis,
+    pub diff_pct: Decimal,
+    pub health_score: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VolatilityPoint {
+    pub timestamp: DateTime<Utc>,
+    pub eth_usd: Option<Decimal>,
+    pub short_term: Decimal,
+    pub medium_term: Decimal,
+    pub long_term: Decimal,
+}
+
+/// Bounded recent activity the monitoring loop keeps for the dashboard
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RecentActivity {
+    pub pools: BTreeMap<String, PoolPriceSnapshot>,
+    /// Oldest first
+    pub opportunities: VecDeque<ArbitrageOpportunity>,
+    /// Oldest first
+    pub executions: VecDeque<TradeExecution>,
+    /// Oldest first
+    pub volatility: VecDeque<VolatilityPoint>,
+}
+
+fn push_bounded<T>(items: &mut VecDeque<T>, item: T, cap: usize) {
+    items.push_back(item);
+    while items.len() > cap {
+        items.pop_front();
+    }
+}
+
+impl RecentActivity {
+    pub fn record_pool_price(&mut self, pool: &str, snapshot: PoolPriceSnapshot) {
+        self.pools.insert(pool.to_string(), snapshot);
+    }
+
+    pub fn record_opportunity(&mut self, opportunity: &ArbitrageOpportunity) {
+        push_bounded(&mut self.opportunities, opportunity.clone(), DASHBOARD_RECENT_ITEMS);
+    }
+
+    pub fn record_execution(&mut self, execution: &TradeExecution) {
+        push_bounded(&mut self.executions, execution.clone(), DASHBOARD_RECENT_ITEMS);
+    }
+
+    pub fn record_volatility(&mut self, eth_usd: Option<Decimal>, metrics: &VolatilityMetrics) {
+        let point = VolatilityPoint {
+            timestamp: Utc::now(),
+            eth_usd,
+            short_term: metrics.short_term_volatility,
+            medium_term: metrics.medium_term_volatility,
+            long_term: metrics.long_term_volatility,
+        };
+        push_bounded(&mut self.volatility, point, DASHBOARD_VOLATILITY_POINTS);
+    }
+}
+
+/// Everything the dashboard renders
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardReport {
+    pub status: StatusReport,
+    pub activity: RecentActivity,
+}
@@ -0,0 +1,93 @@
+//! Per-pool decision trace types
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use super::{ExecutionPriority, PriceBasis, StrategyType};
+
+/// Why the bot did or didn't act on a pool in one cycle
+#[derive(Debug, Clone, Serialize)]
+pub struct DecisionTrace {
+    pub id: String,
+    pub timestamp: DateTime<Utc>,
+    pub pool: String,
+    pub inputs: DecisionInputs,
+    pub checks: Vec<RuleCheck>,
+    pub outcome: DecisionOutcome,
+    pub rejected_by: Vec<String>,
+    pub market_making: Option<MarketMakingDecision>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DecisionInputs {
+    pub dex_price: Decimal,
+    pub cex_price: Decimal,
+    /// Fair value on `price_basis`; `price_diff_pct` is measured against it
+    pub fair_value_price: Decimal,
+    pub price_basis: PriceBasis,
+    pub price_diff_pct: Decimal,
+    pub quoted_buy_price: Option<Decimal>,
+    pub quoted_sell_price: Option<Decimal>,
+    pub gas_cost_usd: Decimal,
+    pub net_profit_usd: Option<Decimal>,
+    pub short_term_volatility: Decimal,
+    pub health_score: Option<Decimal>,
+    pub liquidity_usd: Option<Decimal>,
+}
+
+/// One rule evaluated against its threshold
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleCheck {
+    pub rule: String,
+    pub value: Option<Decimal>,
+    pub threshold: Option<Decimal>,
+    pub passed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum DecisionOutcome {
+    NoOpportunity,
+    Rejected,
+    BelowMinProfit,
+    /// Parked in the gas-spike deferral queue
+    Deferred,
+    Recorded,
+    /// Handed to the execution queue; the result is saved with the execution
+    Queued,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketMakingDecision {
+    pub strategy_type: StrategyType,
+    pub effective_spread_bps: u32,
+    pub position_size_eth: Decimal,
+    pub execution_priority: ExecutionPriority,
+}
+
+impl DecisionTrace {
+    pub fn new(pool: &str, inputs: DecisionInputs) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            pool: pool.to_string(),
+            inputs,
+            checks: Vec::new(),
+            outcome: DecisionOutcome::NoOpportunity,
+            rejected_by: Vec::new(),
+            market_making: None,
+        }
+    }
+
+    /// Record a rule evaluation; failed rules are listed in `rejected_by`
+    pub fn check(&mut self, rule: &str, value: Option<Decimal>, threshold: Option<Decimal>, passed: bool) {
+        if !passed {
+            self.rejected_by.push(rule.to_string());
+        }
+        self.checks.push(RuleCheck {
+            rule: rule.to_string(),
+            value,
+            threshold,
+            passed,
+        });
+    }
+}
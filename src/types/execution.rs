@@ -2,9 +2,10 @@
 
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use super::VolatilityImpact;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeExecution {
     pub id: String,
     pub opportunity_id: String,
@@ -20,15 +21,56 @@ pub struct TradeExecution {
     pub actual_profit_usd: Option<Decimal>,
     pub slippage_bps: Option<u32>,
     pub error_message: Option<String>,
+    pub dex_fill_price: Option<Decimal>,
+    pub cex_fill_price: Option<Decimal>,
+    pub cex_order_id: Option<String>,
+    /// Volatility band the trade was taken in
+    pub volatility_impact: Option<VolatilityImpact>,
+    /// Produced by the simulator rather than a submitted transaction
+    #[serde(default)]
+    pub simulated: bool,
+    /// eth_call of the exact swap calldata before it was sent
+    #[serde(default)]
+    pub preflight: Option<SwapPreflight>,
+    /// Slippage bound and deadline the swap was sent with
+    #[serde(default)]
+    pub swap_bounds: Option<SwapBounds>,
+    /// Router `amountOutMin`, in the output token
+    #[serde(default)]
+    pub min_amount_out: Option<Decimal>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Slippage bound and deadline for a router swap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapBounds {
+    /// Minimum output is the expected output less this many bps
+    pub slippage_bps: u32,
+    /// Seconds from sending until the router rejects the swap
+    pub deadline_secs: u64,
+}
+
+/// Result of simulating a swap transaction against the latest block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwapPreflight {
+    /// Router output in the output token (None when the call returns no amounts)
+    pub amount_out: Option<Decimal>,
+    /// Why the simulated call reverted; a reverted swap is not sent
+    pub revert_reason: Option<String>,
+}
+
+impl SwapPreflight {
+    pub fn reverted(&self) -> bool {
+        self.revert_reason.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum TradeType {
     BuyDexSellCex,
     BuyCexSellDex,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ExecutionStatus {
     Simulated,
     Success,
@@ -1,17 +1,277 @@
 //! Health monitoring types
 
+use alloy::primitives::Address;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::time::Instant;
+use crate::{
+    arbitrage::LifecycleTracker,
+    portfolio::{InventoryMark, PaperPortfolio},
+    storage::{HistorySnapshot, SessionStatsRecorder},
+    types::{BotState, QuoteBookReport, RecentActivity, SessionCounters, SessionSummary, StressTestResult, VolatilityMetrics},
+};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct HealthStatus {
     pub dex_connection: bool,
     pub cex_connection: bool,
     #[allow(dead_code)]
+    #[serde(skip)]
     pub last_dex_update: Option<Instant>,
     #[allow(dead_code)]
+    #[serde(skip)]
     pub last_cex_update: Option<Instant>,
+    /// Highest consecutive error count across the CEX and RPC breakers
     pub consecutive_errors: u32,
-    #[allow(dead_code)]
+    /// The CEX or RPC breaker is open
     pub circuit_breaker_active: bool,
+    /// Open breaker scopes: `cex`, `rpc` or `pool:<name>`
+    pub open_breakers: Vec<String>,
     pub uptime_seconds: u64,
+    /// Request latency by source (`rpc`, `binance`, `coinbase`)
+    pub latency: BTreeMap<String, LatencySummary>,
+    /// Executing wallet's balances as of the last execution attempt
+    pub wallet_balances: Option<WalletBalanceReport>,
+}
+
+impl HealthStatus {
+    /// Both price feeds fresh and the CEX and RPC breakers closed
+    pub fn is_healthy(&self) -> bool {
+        self.dex_connection && self.cex_connection && !self.circuit_breaker_active
+    }
+}
+
+/// Balances read by the pre-execution balance check
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WalletBalanceReport {
+    pub wallet: Address,
+    pub checked_at: DateTime<Utc>,
+    /// Native ETH available for gas
+    pub eth: Decimal,
+    /// Balance of each token a swap has spent, by symbol
+    pub tokens: BTreeMap<String, Decimal>,
+    /// Shortfall that blocked the last attempt, None when it was funded
+    pub shortfall: Option<String>,
+}
+
+/// Request latency of one network source since startup
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencySummary {
+    pub count: u64,
+    pub errors: u64,
+    pub mean_ms: Option<u64>,
+    /// Percentiles are bucket upper bounds
+    pub p50_ms: Option<u64>,
+    pub p95_ms: Option<u64>,
+    pub p99_ms: Option<u64>,
+    pub max_ms: Option<u64>,
+    pub buckets: Vec<LatencyBucket>,
+}
+
+/// Requests at or under `le_ms` and over the previous bucket's bound
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyBucket {
+    /// None for the overflow bucket
+    pub le_ms: Option<u64>,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitBreakerState {
+    pub open: bool,
+    pub consecutive_errors: u32,
+    /// Seconds until an open breaker lets cycles through again
+    pub cooldown_remaining_secs: Option<u64>,
+}
+
+/// State of every scoped circuit breaker
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitBreakerStatus {
+    pub cex: CircuitBreakerState,
+    pub rpc: CircuitBreakerState,
+    /// Breaker of every monitored pool
+    pub pools: BTreeMap<String, CircuitBreakerState>,
+}
+
+impl CircuitBreakerStatus {
+    /// Scopes whose breaker is open
+    pub fn open_scopes(&self) -> Vec<String> {
+        let mut scopes = Vec::new();
+        if self.cex.open {
+            scopes.push("cex".to_string());
+        }
+        if self.rpc.open {
+            scopes.push("rpc".to_string());
+        }
+        scopes.extend(self.pools.iter().filter(|(_, s)| s.open).map(|(pool, _)| format!("pool:{}", pool)));
+        scopes
+    }
+}
+
+/// Everything the status API serves, refreshed after each monitoring cycle
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusReport {
+    pub timestamp: DateTime<Utc>,
+    pub paused: bool,
+    pub is_leader: bool,
+    pub health: HealthStatus,
+    pub volatility: VolatilityMetrics,
+    pub circuit_breakers: CircuitBreakerStatus,
+    pub session: SessionSummary,
+    pub monitoring: MonitoringSnapshot,
+}
+
+/// Statistics and last prices the monitoring loop carries across cycles.
+/// Owned by the loop; other tasks read it through [`MonitoringState::snapshot`].
+#[derive(Debug, Default, Serialize)]
+pub struct MonitoringState {
+    pub total_opportunities: u64,
+    pub profitable_opportunities: u64,
+    pub total_potential_profit: Decimal,
+    pub total_market_making_signals: u64,
+    pub total_executions: u64,
+    pub successful_executions: u64,
+    pub error_counts: HashMap<String, u32>,
+    #[serde(skip)]
+    pub dex_last_update: Option<Instant>,
+    #[serde(skip)]
+    pub cex_last_update: Option<Instant>,
+    pub last_known_cex_price: Option<Decimal>,
+    pub consecutive_cex_failures: u32,
+    /// CEX breaker open this cycle: prices are the last known ones, so nothing executes
+    pub cex_degraded: bool,
+    pub is_leader: bool,
+    pub deferred_opportunities: u64,
+    pub reevaluated_deferrals: u64,
+    pub expired_deferrals: u64,
+    /// Portfolio inventory at the first successful refresh, for session P&L
+    pub initial_inventory: Option<InventoryMark>,
+    /// Last DEX price per pool, in USD
+    pub last_dex_prices: HashMap<String, Decimal>,
+    /// Pool prices, opportunities, executions and volatility for the dashboard
+    #[serde(skip)]
+    pub activity: RecentActivity,
+    /// Simulated balances the executions are booked against (with PAPER_TRADING)
+    pub paper_portfolio: Option<PaperPortfolio>,
+    /// Simulated market-making quotes at the end of the last cycle
+    pub quote_book: Option<QuoteBookReport>,
+    /// Stress tests of the last market-making signal
+    pub stress_tests: Vec<StressTestResult>,
+    /// Opportunities followed from detection to settlement
+    #[serde(skip)]
+    pub lifecycles: LifecycleTracker,
+    /// Totals across restarts and the per-day history
+    #[serde(skip)]
+    pub stats: SessionStatsRecorder,
+    /// Cycle and pool panics caught this session
+    pub panics: u64,
+    /// When the periodic statistics were last printed (None = not yet)
+    #[serde(skip)]
+    pub stats_printed_at: Option<Instant>,
+    /// Counters as of that print
+    #[serde(skip)]
+    pub stats_printed_counters: SessionCounters,
+}
+
+impl MonitoringState {
+    pub fn new() -> Self {
+        Self {
+            is_leader: true,
+            ..Default::default()
+        }
+    }
+
+    /// Counters and last prices from a previous run's shutdown state
+    pub fn restore(&mut self, saved: &BotState) {
+        let counters = &saved.counters;
+        self.total_opportunities = counters.total_opportunities;
+        self.profitable_opportunities = counters.profitable_opportunities;
+        self.total_potential_profit = counters.total_potential_profit_usd;
+        self.total_market_making_signals = counters.total_market_making_signals;
+        self.total_executions = counters.total_executions;
+        self.successful_executions = counters.successful_executions;
+        self.deferred_opportunities = counters.deferred_opportunities;
+        self.reevaluated_deferrals = counters.reevaluated_deferrals;
+        self.expired_deferrals = counters.expired_deferrals;
+        self.last_known_cex_price = saved.last_cex_price;
+        self.last_dex_prices = saved.last_dex_prices.clone();
+        self.paper_portfolio = saved.paper_portfolio.clone();
+        // Counts restored from the previous run were already reported
+        self.stats_printed_counters = self.counters();
+    }
+
+    pub fn counters(&self) -> SessionCounters {
+        SessionCounters {
+            total_opportunities: self.total_opportunities,
+            profitable_opportunities: self.profitable_opportunities,
+            total_potential_profit_usd: self.total_potential_profit,
+            total_market_making_signals: self.total_market_making_signals,
+            total_executions: self.total_executions,
+            successful_executions: self.successful_executions,
+            deferred_opportunities: self.deferred_opportunities,
+            reevaluated_deferrals: self.reevaluated_deferrals,
+            expired_deferrals: self.expired_deferrals,
+        }
+    }
+
+    /// State to save on shutdown
+    pub fn bot_state(&self, volatility_history: HistorySnapshot) -> BotState {
+        BotState {
+            saved_at: Utc::now(),
+            counters: self.counters(),
+            volatility_history,
+            last_cex_price: self.last_known_cex_price,
+            last_dex_prices: self.last_dex_prices.clone(),
+            paper_portfolio: self.paper_portfolio.clone(),
+        }
+    }
+
+    /// Owned copy of the statistics, consistent as of the end of the last cycle
+    pub fn snapshot(&self) -> MonitoringSnapshot {
+        let age_ms = |at: Option<Instant>| at.map(|at| at.elapsed().as_millis() as u64);
+        MonitoringSnapshot {
+            taken_at: Utc::now(),
+            counters: self.counters(),
+            error_counts: self.error_counts.iter().map(|(kind, &count)| (kind.clone(), count)).collect(),
+            last_cex_price: self.last_known_cex_price,
+            last_dex_prices: self.last_dex_prices.iter().map(|(pool, &price)| (pool.clone(), price)).collect(),
+            cex_update_age_ms: age_ms(self.cex_last_update),
+            dex_update_age_ms: age_ms(self.dex_last_update),
+            consecutive_cex_failures: self.consecutive_cex_failures,
+            cex_degraded: self.cex_degraded,
+            is_leader: self.is_leader,
+            panics: self.panics,
+            open_lifecycles: self.lifecycles.len(),
+            lifetime_sessions: self.stats.lifetime().sessions,
+            lifetime_counters: self.stats.lifetime().counters.clone(),
+        }
+    }
+}
+
+/// Point-in-time copy of the monitoring statistics for the status API,
+/// metrics and alerting
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonitoringSnapshot {
+    pub taken_at: DateTime<Utc>,
+    /// This session's counters (continued from the saved state)
+    pub counters: SessionCounters,
+    pub error_counts: BTreeMap<String, u32>,
+    pub last_cex_price: Option<Decimal>,
+    /// Last DEX price per pool, in USD
+    pub last_dex_prices: BTreeMap<String, Decimal>,
+    /// Milliseconds since the last successful price fetch
+    pub cex_update_age_ms: Option<u64>,
+    pub dex_update_age_ms: Option<u64>,
+    pub consecutive_cex_failures: u32,
+    pub cex_degraded: bool,
+    pub is_leader: bool,
+    /// Cycle and pool panics caught this session
+    pub panics: u64,
+    /// Opportunities still being tracked from detection to settlement
+    pub open_lifecycles: usize,
+    /// Runs counted in `output/stats/lifetime.json`, this one included
+    pub lifetime_sessions: u64,
+    pub lifetime_counters: SessionCounters,
 }
@@ -1,10 +1,11 @@
 //! Market making types and structures
 
+use alloy::primitives::Address;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::Serialize;
 use std::time::Duration;
-use super::VolatilityMetrics;
+use super::{FairValueQuotes, PriceBasis, VolatilityMetrics};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct MarketMakingSignal {
@@ -12,6 +13,9 @@ pub struct MarketMakingSignal {
     pub timestamp: DateTime<Utc>,
     pub pool: String,
     pub fair_value_price: Decimal,
+    pub price_basis: PriceBasis,
+    /// Every basis's price when the signal was generated
+    pub fair_values: Option<FairValueQuotes>,
     pub current_pool_price: Decimal,
     pub target_bid_price: Decimal,
     pub target_ask_price: Decimal,
@@ -23,6 +27,9 @@ pub struct MarketMakingSignal {
     pub risk_metrics: RiskMetrics,
     pub volatility_metrics: VolatilityMetrics,
     pub execution_priority: ExecutionPriority,
+    /// Gauge emissions, when gauge yield is enabled and the pool has a gauge
+    pub emission_yield: Option<EmissionYield>,
+    pub expected_return: ExpectedReturn,
     pub rationale: String,
 }
 
@@ -36,6 +43,15 @@ pub struct InventoryAnalysis {
     pub imbalance_severity: InventoryImbalance,
     pub rebalance_needed: bool,
     pub rebalance_amount_eth: Decimal,
+    pub source: InventorySource,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub enum InventorySource {
+    /// Balances derived from config (no wallet or CEX account tracked)
+    Simulated,
+    /// Wallet and/or CEX balances from the portfolio tracker
+    Portfolio,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -55,6 +71,32 @@ pub struct MarketConditions {
     pub spread_environment: SpreadEnvironment,
     pub market_trend: MarketTrend,
     pub volume_profile: VolumeProfile,
+    pub epoch: EpochPosition,
+}
+
+/// Part of the weekly Aerodrome epoch, relative to the emissions/vote flip
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum EpochPhase {
+    /// Just after a flip, while liquidity moves to the new gauges
+    PostFlip,
+    Mid,
+    /// Just before the next flip, while votes settle
+    PreFlip,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct EpochPosition {
+    pub phase: EpochPhase,
+    /// Unix time the current epoch started
+    pub epoch_start: u64,
+    pub secs_since_flip: u64,
+    pub secs_until_flip: u64,
+}
+
+impl EpochPosition {
+    pub fn is_near_flip(&self) -> bool {
+        self.phase != EpochPhase::Mid
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -82,8 +124,11 @@ pub enum VolumeProfile {
 #[derive(Debug, Clone, Serialize)]
 pub struct LiquidityDepth {
     pub total_liquidity_usd: Decimal,
+    /// Base token reserves (WETH on WETH/USD pools)
     pub weth_reserves: Decimal,
+    /// Quote token reserves (USD on WETH/USD pools)
     pub usd_reserves: Decimal,
+    pub health_score: Decimal,
     pub depth_quality: DepthQuality,
 }
 
@@ -139,9 +184,65 @@ pub struct RiskMetrics {
     pub volatility_risk_score: Decimal,
     pub overall_risk_score: Decimal,
     pub recommended_max_exposure: Decimal,
+    pub stress_tests: Vec<StressTestResult>,
+    /// Expected impermanent loss over the strategy's range and duration
+    pub impermanent_loss: ImpermanentLossEstimate,
+    pub impermanent_loss_risk_score: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImpermanentLossEstimate {
+    pub horizon_secs: u64,
+    /// Liquidity per unit of capital relative to a full-range position
+    pub concentration: Decimal,
+    pub expected_loss_pct: Decimal,
+    pub expected_loss_usd: Decimal,
+    /// Loss if the price ends at the worse range bound
+    pub range_exit_loss_pct: Decimal,
+}
+
+/// AERO emissions a staked position in the pool would earn
+#[derive(Debug, Clone, Serialize)]
+pub struct EmissionYield {
+    pub gauge: Address,
+    pub reward_rate_aero_per_sec: Decimal,
+    pub aero_price_usd: Decimal,
+    /// USD value of the pool liquidity staked in the gauge
+    pub staked_tvl_usd: Decimal,
+    pub apr_pct: Decimal,
+}
+
+/// Expected return of a signal's position over the strategy's duration
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExpectedReturn {
+    /// Half the spread on the expected volume, net of `pool_fee_usd`
+    pub spread_capture_usd: Decimal,
+    pub pool_fee_usd: Decimal,
+    pub emission_usd: Decimal,
+    pub impermanent_loss_usd: Decimal,
+    pub net_usd: Decimal,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub enum StressScenario {
+    EthDown10,
+    EthDown20,
+    StableDepeg,
+    GasSpike10x,
 }
 
+/// Revaluation of inventory and open plans under one shock
 #[derive(Debug, Clone, Serialize)]
+pub struct StressTestResult {
+    pub scenario: StressScenario,
+    pub exposure_eth: Decimal,
+    pub baseline_value_usd: Decimal,
+    pub stressed_value_usd: Decimal,
+    pub pnl_usd: Decimal,
+    pub pnl_pct: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum ExecutionPriority {
     Immediate,
     High,
@@ -149,3 +250,65 @@ pub enum ExecutionPriority {
     Low,
     Hold,
 }
+
+impl ExecutionPriority {
+    /// Scheduling order, higher runs first
+    pub fn rank(&self) -> u8 {
+        match self {
+            ExecutionPriority::Immediate => 4,
+            ExecutionPriority::High => 3,
+            ExecutionPriority::Medium => 2,
+            ExecutionPriority::Low => 1,
+            ExecutionPriority::Hold => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum QuoteSide {
+    Bid,
+    Ask,
+}
+
+/// A resting quote placed from a market-making signal
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulatedQuote {
+    pub id: String,
+    pub signal_id: String,
+    pub pool: String,
+    pub side: QuoteSide,
+    pub price: Decimal,
+    pub size_eth: Decimal,
+    pub placed_at: DateTime<Utc>,
+}
+
+/// A simulated quote filled by the market trading through it
+#[derive(Debug, Clone, Serialize)]
+pub struct QuoteFill {
+    pub quote: SimulatedQuote,
+    pub filled_at: DateTime<Utc>,
+    /// DEX or CEX price that crossed the quote
+    pub market_price: Decimal,
+    pub fair_value_price: Decimal,
+    /// Fill price against fair value: what the quote earned on the spread,
+    /// negative when the fill was adversely selected
+    pub spread_capture_usd: Decimal,
+}
+
+/// Quote lifecycle totals and the resulting position and P&L
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct QuoteBookReport {
+    pub open_quotes: usize,
+    pub placed: u64,
+    pub cancelled: u64,
+    pub bid_fills: u64,
+    pub ask_fills: u64,
+    pub filled_eth: Decimal,
+    pub spread_capture_usd: Decimal,
+    /// WETH bought minus sold through fills
+    pub position_eth: Decimal,
+    /// USD received minus paid through fills
+    pub cash_usd: Decimal,
+    /// Cash plus the position at the mark price
+    pub pnl_usd: Decimal,
+}
@@ -8,6 +8,12 @@ pub mod volatility;
 pub mod validation;
 pub mod pools;
 pub mod health;
+pub mod control;
+pub mod decision;
+pub mod session;
+pub mod checkpoint;
+pub mod pricing;
+pub mod dashboard;
 
 pub use addresses::*;
 pub use arbitrage::*;
@@ -17,3 +23,9 @@ pub use volatility::*;
 pub use validation::*;
 pub use pools::*;
 pub use health::*;
+pub use control::*;
+pub use decision::*;
+pub use session::*;
+pub use checkpoint::*;
+pub use pricing::*;
+pub use dashboard::*;
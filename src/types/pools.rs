@@ -1,8 +1,86 @@
 //! Pool-related types and structures
 
-use alloy::primitives::Address;
+use alloy::primitives::{Address, U256};
 use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
 use std::time::Instant;
+use crate::utils::{order_pair, scale_from_raw};
+
+/// Token symbols priced at $1
+pub const USD_STABLE_SYMBOLS: &[&str] = &["USDC", "USDbC", "USDT", "DAI"];
+
+/// USD prices of other quote assets by symbol, from `STABLE_PRICES`
+pub type StablePrices = HashMap<String, Decimal>;
+
+/// AMM curve implemented by a pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolKind {
+    /// Classic vAMM/sAMM pool exposing getReserves()
+    Basic,
+    /// Slipstream concentrated liquidity pool exposing slot0()/liquidity()
+    Concentrated { tick_spacing: i32 },
+}
+
+/// A pool's tokens as (base, quote), with on-chain decimals and symbols.
+/// Prices are quoted as quote tokens per base token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenPair {
+    pub base: Address,
+    pub quote: Address,
+    pub base_symbol: String,
+    pub quote_symbol: String,
+    pub base_decimals: i32,
+    pub quote_decimals: i32,
+    /// Binance symbol giving the base price, e.g. `ETHUSDC` or `CBETHETH`
+    pub cex_symbol: String,
+}
+
+impl TokenPair {
+    pub fn is_weth_base(&self) -> bool {
+        self.base_symbol == "WETH"
+    }
+
+    pub fn quote_is_usd(&self) -> bool {
+        USD_STABLE_SYMBOLS.contains(&self.quote_symbol.as_str())
+    }
+
+    /// WETH against a USD stable, the pairs the inventory and volatility models cover
+    pub fn is_weth_usd(&self) -> bool {
+        self.is_weth_base() && self.quote_is_usd()
+    }
+
+    /// USD value of one quote token: USD stables, WETH, or a `stable_prices` entry
+    pub fn quote_usd(&self, eth_usd: Decimal, stable_prices: &StablePrices) -> Option<Decimal> {
+        if self.quote_is_usd() {
+            Some(dec!(1))
+        } else if self.quote_symbol == "WETH" {
+            Some(eth_usd)
+        } else {
+            stable_prices.get(&self.quote_symbol).copied()
+        }
+    }
+
+    /// USD value of one unit of the CEX symbol's quote asset
+    pub fn cex_quote_usd(&self, eth_usd: Decimal) -> Option<Decimal> {
+        let symbol = self.cex_symbol.as_str();
+        if ["USDC", "USDT", "FDUSD"].iter().any(|q| symbol.ends_with(q)) {
+            Some(dec!(1))
+        } else if symbol.ends_with("ETH") {
+            Some(eth_usd)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for TokenPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.base_symbol, self.quote_symbol)
+    }
+}
 
 #[derive(Clone)]
 pub struct PoolInfo {
@@ -10,10 +88,36 @@ pub struct PoolInfo {
     pub name: String,
     pub token0: Address,
     pub token1: Address,
-    #[allow(dead_code)]
+    pub pair: TokenPair,
     pub is_stable: bool,
+    pub kind: PoolKind,
+    /// Fee rate the pool charges on swaps, e.g. 0.003 for 30 bps
+    pub fee: Decimal,
     #[allow(dead_code)]
     pub min_liquidity: Decimal,
     #[allow(dead_code)]
     pub last_update: Instant,
 }
+
+impl PoolInfo {
+    /// Scale token0/token1 reserves into (base, quote) amounts using the pair's decimals
+    pub fn base_quote_reserves(&self, reserve0: U256, reserve1: U256) -> Option<(Decimal, Decimal)> {
+        let (base_raw, quote_raw) = order_pair(self.token0, self.token1, self.pair.base, reserve0, reserve1)?;
+        Some((
+            scale_from_raw(base_raw, self.pair.base_decimals)?,
+            scale_from_raw(quote_raw, self.pair.quote_decimals)?,
+        ))
+    }
+}
+
+/// Composite pool health score, each component in 0-100
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolHealth {
+    pub pool: String,
+    pub liquidity_score: Decimal,
+    pub volume_score: Decimal,
+    pub stability_score: Decimal,
+    pub spread_score: Decimal,
+    pub age_score: Decimal,
+    pub overall_score: Decimal,
+}
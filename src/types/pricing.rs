@@ -0,0 +1,190 @@
+//! Fair-value price bases for comparing DEX prices
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Reference price that DEX prices are compared against
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceBasis {
+    /// Binance last trade price
+    #[default]
+    CexLast,
+    /// Midpoint of the Binance best bid and ask
+    CexMid,
+    /// Best bid and ask weighted by the opposite side's size
+    CexMicroprice,
+    /// Average of the CEX microprice and the DEX TWAP
+    Composite,
+    /// Time-weighted average of the pool's own recent prices
+    DexTwap,
+}
+
+impl PriceBasis {
+    pub const ALL: [PriceBasis; 5] = [
+        PriceBasis::CexLast,
+        PriceBasis::CexMid,
+        PriceBasis::CexMicroprice,
+        PriceBasis::Composite,
+        PriceBasis::DexTwap,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriceBasis::CexLast => "cex_last",
+            PriceBasis::CexMid => "cex_mid",
+            PriceBasis::CexMicroprice => "cex_microprice",
+            PriceBasis::Composite => "composite",
+            PriceBasis::DexTwap => "dex_twap",
+        }
+    }
+}
+
+impl fmt::Display for PriceBasis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PriceBasis {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter()
+            .find(|basis| basis.as_str() == s.trim().to_lowercase())
+            .ok_or_else(|| format!("unknown price basis {:?}", s))
+    }
+}
+
+/// CEX best bid/ask with sizes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BookTicker {
+    pub bid_price: Decimal,
+    pub bid_qty: Decimal,
+    pub ask_price: Decimal,
+    pub ask_qty: Decimal,
+}
+
+impl BookTicker {
+    pub fn mid(&self) -> Decimal {
+        (self.bid_price + self.ask_price) / dec!(2)
+    }
+
+    /// Leans toward the side more likely to trade next: a deep bid against a
+    /// thin ask pulls the price toward the ask
+    pub fn microprice(&self) -> Decimal {
+        let depth = self.bid_qty + self.ask_qty;
+        if depth <= dec!(0) {
+            return self.mid();
+        }
+        (self.bid_price * self.ask_qty + self.ask_price * self.bid_qty) / depth
+    }
+}
+
+/// One price level of a CEX order book
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BookLevel {
+    pub price: Decimal,
+    pub qty: Decimal,
+}
+
+/// Top levels of a CEX order book, best price first on each side
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrderBook {
+    pub bids: Vec<BookLevel>,
+    pub asks: Vec<BookLevel>,
+}
+
+impl OrderBook {
+    fn side(&self, buy: bool) -> &[BookLevel] {
+        if buy { &self.asks } else { &self.bids }
+    }
+
+    /// Base quantity on the asks (`buy`) or bids within the fetched levels
+    pub fn depth(&self, buy: bool) -> Decimal {
+        self.side(buy).iter().map(|level| level.qty).sum()
+    }
+
+    /// Average price of a market order for `qty` walking the asks (`buy`) or
+    /// bids; None when the fetched levels hold less than `qty`
+    pub fn average_fill_price(&self, buy: bool, qty: Decimal) -> Option<Decimal> {
+        if qty <= dec!(0) {
+            return None;
+        }
+        let mut remaining = qty;
+        let mut cost = dec!(0);
+        for level in self.side(buy) {
+            let filled = remaining.min(level.qty);
+            cost += filled * level.price;
+            remaining -= filled;
+            if remaining <= dec!(0) {
+                return Some(cost / qty);
+            }
+        }
+        None
+    }
+
+    /// Prices multiplied by `factor`, e.g. from ETH into USD
+    pub fn scaled(&self, factor: Decimal) -> Self {
+        let scale = |levels: &[BookLevel]| levels.iter()
+            .map(|level| BookLevel { price: level.price * factor, qty: level.qty })
+            .collect();
+        Self { bids: scale(&self.bids), asks: scale(&self.asks) }
+    }
+}
+
+/// CEX reference prices for a pool's base token, in USD
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CexQuotes {
+    pub last: Decimal,
+    /// None when the order book could not be fetched
+    pub book: Option<BookTicker>,
+}
+
+/// Every basis's price for one observation, in USD, recorded so backtests
+/// can replay the same observations on each basis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FairValueQuotes {
+    pub cex_last: Decimal,
+    pub cex_mid: Option<Decimal>,
+    pub cex_microprice: Option<Decimal>,
+    pub composite: Option<Decimal>,
+    pub dex_twap: Option<Decimal>,
+}
+
+impl FairValueQuotes {
+    pub fn new(cex: CexQuotes, dex_twap: Option<Decimal>) -> Self {
+        let cex_mid = cex.book.map(|b| b.mid());
+        let cex_microprice = cex.book.map(|b| b.microprice());
+        let composite = dex_twap.map(|twap| (cex_microprice.unwrap_or(cex.last) + twap) / dec!(2));
+        Self {
+            cex_last: cex.last,
+            cex_mid,
+            cex_microprice,
+            composite,
+            dex_twap,
+        }
+    }
+
+    pub fn price(&self, basis: PriceBasis) -> Option<Decimal> {
+        match basis {
+            PriceBasis::CexLast => Some(self.cex_last),
+            PriceBasis::CexMid => self.cex_mid,
+            PriceBasis::CexMicroprice => self.cex_microprice,
+            PriceBasis::Composite => self.composite,
+            PriceBasis::DexTwap => self.dex_twap,
+        }
+    }
+
+    /// Price on `basis`, falling back to the CEX last price while it is
+    /// unavailable (no order book, TWAP still warming up). Returns the basis used.
+    pub fn resolve(&self, basis: PriceBasis) -> (PriceBasis, Decimal) {
+        match self.price(basis) {
+            Some(price) => (basis, price),
+            None => (PriceBasis::CexLast, self.cex_last),
+        }
+    }
+}
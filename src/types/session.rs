@@ -0,0 +1,113 @@
+//! Session summary types
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use crate::{
+    portfolio::{InventoryReport, PaperPortfolio, PaperPortfolioReport},
+    storage::HistorySnapshot,
+    types::{QuoteBookReport, StressTestResult},
+};
+
+/// Shutdown artifact summarizing one bot run
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub timestamp: DateTime<Utc>,
+    pub runtime_secs: u64,
+    pub total_opportunities: u64,
+    pub profitable_opportunities: u64,
+    pub total_potential_profit_usd: Decimal,
+    pub total_market_making_signals: u64,
+    pub total_executions: u64,
+    pub successful_executions: u64,
+    pub deferred_opportunities: u64,
+    pub reevaluated_deferrals: u64,
+    pub expired_deferrals: u64,
+    pub error_counts: HashMap<String, u32>,
+    pub inventory: Option<InventoryReport>,
+    /// Paper-trading ledger at the last CEX price (with PAPER_TRADING)
+    pub paper_portfolio: Option<PaperPortfolioReport>,
+    /// Simulated market-making quotes and fills (with ENABLE_MARKET_MAKING)
+    pub market_making_quotes: Option<QuoteBookReport>,
+    /// Inventory revalued under each stress scenario at the last signal
+    pub stress_tests: Vec<StressTestResult>,
+}
+
+/// Running totals that carry over a restart
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionCounters {
+    pub total_opportunities: u64,
+    pub profitable_opportunities: u64,
+    pub total_potential_profit_usd: Decimal,
+    pub total_market_making_signals: u64,
+    pub total_executions: u64,
+    pub successful_executions: u64,
+    pub deferred_opportunities: u64,
+    pub reevaluated_deferrals: u64,
+    pub expired_deferrals: u64,
+}
+
+impl SessionCounters {
+    /// What was counted since `earlier`
+    pub fn since(&self, earlier: &SessionCounters) -> SessionCounters {
+        SessionCounters {
+            total_opportunities: self.total_opportunities.saturating_sub(earlier.total_opportunities),
+            profitable_opportunities: self.profitable_opportunities.saturating_sub(earlier.profitable_opportunities),
+            total_potential_profit_usd: self.total_potential_profit_usd - earlier.total_potential_profit_usd,
+            total_market_making_signals: self.total_market_making_signals.saturating_sub(earlier.total_market_making_signals),
+            total_executions: self.total_executions.saturating_sub(earlier.total_executions),
+            successful_executions: self.successful_executions.saturating_sub(earlier.successful_executions),
+            deferred_opportunities: self.deferred_opportunities.saturating_sub(earlier.deferred_opportunities),
+            reevaluated_deferrals: self.reevaluated_deferrals.saturating_sub(earlier.reevaluated_deferrals),
+            expired_deferrals: self.expired_deferrals.saturating_sub(earlier.expired_deferrals),
+        }
+    }
+
+    pub fn add(&mut self, other: &SessionCounters) {
+        self.total_opportunities += other.total_opportunities;
+        self.profitable_opportunities += other.profitable_opportunities;
+        self.total_potential_profit_usd += other.total_potential_profit_usd;
+        self.total_market_making_signals += other.total_market_making_signals;
+        self.total_executions += other.total_executions;
+        self.successful_executions += other.successful_executions;
+        self.deferred_opportunities += other.deferred_opportunities;
+        self.reevaluated_deferrals += other.reevaluated_deferrals;
+        self.expired_deferrals += other.expired_deferrals;
+    }
+}
+
+/// Session statistics counted on one UTC day, summed over the runs active that day
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DayStats {
+    /// Runs that counted anything on this day
+    pub sessions: u64,
+    pub runtime_secs: u64,
+    pub counters: SessionCounters,
+    pub error_counts: BTreeMap<String, u64>,
+}
+
+/// Session statistics summed over every run, with the per-day history
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LifetimeStats {
+    pub first_started_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub sessions: u64,
+    pub runtime_secs: u64,
+    pub counters: SessionCounters,
+    pub error_counts: BTreeMap<String, u64>,
+    pub days: BTreeMap<NaiveDate, DayStats>,
+}
+
+/// State saved on graceful shutdown and restored on the next start
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BotState {
+    pub saved_at: DateTime<Utc>,
+    pub counters: SessionCounters,
+    /// ETH/USD samples behind the volatility windows
+    pub volatility_history: HistorySnapshot,
+    pub last_cex_price: Option<Decimal>,
+    /// Last DEX price per pool, in USD
+    pub last_dex_prices: HashMap<String, Decimal>,
+    pub paper_portfolio: Option<PaperPortfolio>,
+}
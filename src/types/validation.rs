@@ -9,6 +9,13 @@ pub struct ValidationResult {
     pub gas_economics: bool,
     pub slippage_acceptable: bool,
     pub volatility_acceptable: bool,
+    pub pool_health_acceptable: bool,
+    /// DEX and CEX prices within the oracle bound (true when no oracle price applies)
+    pub oracle_consistent: bool,
+    /// DEX spot far from the pool's on-chain TWAP, typically a sandwich rather than a real arb
+    pub manipulation_suspected: bool,
+    /// The CEX book can fill the trade size within the fetched levels (true without depth)
+    pub cex_liquidity: bool,
     pub all_passed: bool,
     pub warnings: Vec<String>,
 }
@@ -1,17 +1,192 @@
 //! Volatility analysis types
 
 use rust_decimal::Decimal;
-use serde::Serialize;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 use super::ExecutionUrgency;
 
+/// How each timeframe's volatility is estimated from its price samples.
+/// Every estimator except `PriceStdev` works on log returns and reports
+/// annualized volatility, so the timeframes share one scale.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VolatilityEstimator {
+    /// Standard deviation of ln(p_t / p_{t-1})
+    #[default]
+    LogReturns,
+    /// Mean squared log return, without demeaning
+    Realized,
+    /// Exponentially weighted variance of log returns (`EWMA_LAMBDA`)
+    Ewma,
+    /// GARCH(1,1) variance forecast from log returns, targeting the window's variance
+    Garch,
+    /// Standard deviation of price levels as a percentage of their mean (the
+    /// original method; depends on the window's trend and price scale)
+    PriceStdev,
+}
+
+impl VolatilityEstimator {
+    pub const ALL: [VolatilityEstimator; 5] = [
+        VolatilityEstimator::LogReturns,
+        VolatilityEstimator::Realized,
+        VolatilityEstimator::Ewma,
+        VolatilityEstimator::Garch,
+        VolatilityEstimator::PriceStdev,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VolatilityEstimator::LogReturns => "log_returns",
+            VolatilityEstimator::Realized => "realized",
+            VolatilityEstimator::Ewma => "ewma",
+            VolatilityEstimator::Garch => "garch",
+            VolatilityEstimator::PriceStdev => "price_stdev",
+        }
+    }
+
+    pub fn is_return_based(&self) -> bool {
+        *self != VolatilityEstimator::PriceStdev
+    }
+
+    /// Short-term volatility (%) at which impact becomes Moderate, High and Extreme
+    pub fn impact_thresholds(&self) -> [Decimal; 3] {
+        if self.is_return_based() {
+            [dec!(50), dec!(80), dec!(120)]
+        } else {
+            [dec!(2), dec!(5), dec!(10)]
+        }
+    }
+
+    /// Impact band of a short-term volatility (%)
+    pub fn impact_of(&self, volatility: Decimal) -> VolatilityImpact {
+        let [moderate, high, extreme] = self.impact_thresholds();
+        match volatility {
+            v if v < moderate => VolatilityImpact::Low,
+            v if v < high => VolatilityImpact::Moderate,
+            v if v < extreme => VolatilityImpact::High,
+            _ => VolatilityImpact::Extreme,
+        }
+    }
+
+    /// Default `VOLATILITY_THRESHOLD`: the High impact level
+    pub fn default_threshold(&self) -> Decimal {
+        self.impact_thresholds()[1]
+    }
+
+    /// Short- and long-term volatility closer than this make a Stable trend
+    pub fn stable_band(&self) -> Decimal {
+        if self.is_return_based() { dec!(10) } else { dec!(1) }
+    }
+}
+
+impl fmt::Display for VolatilityEstimator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for VolatilityEstimator {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter()
+            .find(|estimator| estimator.as_str() == s.trim().to_lowercase())
+            .ok_or_else(|| format!("unknown volatility estimator {:?}", s))
+    }
+}
+
+/// Fewest samples a timeframe needs before it reports a volatility
+pub const MIN_VOLATILITY_SAMPLES: usize = 10;
+
+/// How much of a volatility reading is measured rather than missing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VolatilityConfidence {
+    /// The short-term window has fewer than `MIN_VOLATILITY_SAMPLES`
+    /// samples, so every timeframe reads 0%
+    #[default]
+    Low,
+    /// The short-term window is measured, but the history doesn't span the
+    /// medium-term window yet, so the longer timeframes repeat it
+    Partial,
+    Full,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct VolatilityMetrics {
     pub short_term_volatility: Decimal,  // 5 min
     pub medium_term_volatility: Decimal, // 30 min
     pub long_term_volatility: Decimal,   // 1 hour
+    /// Estimator that produced the three timeframes
+    pub estimator: VolatilityEstimator,
+    /// Samples in the short-term window
+    pub sample_count: usize,
+    pub confidence: VolatilityConfidence,
     pub volatility_trend: VolatilityTrend,
     pub impact_assessment: VolatilityImpact,
     pub recommended_adjustments: VolatilityAdjustments,
+    /// DEX and basis volatility of the pool these metrics were taken for
+    pub pool: Option<PoolVolatility>,
+}
+
+/// Short-term volatility of one pool's DEX price and of its DEX/CEX basis
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PoolVolatility {
+    pub pool: String,
+    /// Volatility (%) of the pool's DEX price
+    pub dex_volatility: Decimal,
+    /// Volatility (%) of the DEX/CEX price ratio: how much the two markets
+    /// move apart
+    pub basis_volatility: Decimal,
+    /// DEX samples in the short-term window
+    pub sample_count: usize,
+    pub confidence: VolatilityConfidence,
+}
+
+impl PoolVolatility {
+    /// Impact band of the DEX or basis volatility, whichever is higher;
+    /// Low until measured
+    pub fn impact(&self, estimator: VolatilityEstimator) -> VolatilityImpact {
+        if self.confidence == VolatilityConfidence::Low {
+            return VolatilityImpact::Low;
+        }
+        estimator.impact_of(self.dex_volatility.max(self.basis_volatility))
+    }
+}
+
+impl VolatilityMetrics {
+    /// These metrics for one pool: the impact rises to the pool's DEX or
+    /// basis volatility band when that is higher than the CEX one, and the
+    /// spread, size and urgency adjustments follow it
+    pub fn for_pool(&self, pool: Option<PoolVolatility>) -> Self {
+        let mut metrics = self.clone();
+        if let Some(pool) = &pool {
+            let impact = pool.impact(self.estimator).max(self.impact_assessment);
+            if impact != self.impact_assessment {
+                metrics.impact_assessment = impact;
+                metrics.recommended_adjustments = VolatilityAdjustments::for_impact(impact, &self.volatility_trend);
+            }
+        }
+        metrics.pool = pool;
+        metrics
+    }
+
+    /// Too few samples to measure: the 0% readings don't mean a calm market
+    pub fn is_low_confidence(&self) -> bool {
+        self.confidence == VolatilityConfidence::Low
+    }
+
+    /// Short-term volatility for risk estimates: at least the Moderate
+    /// threshold while the reading is low-confidence
+    pub fn conservative_short_term_volatility(&self) -> Decimal {
+        if self.is_low_confidence() {
+            self.short_term_volatility.max(self.estimator.impact_thresholds()[0])
+        } else {
+            self.short_term_volatility
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -22,12 +197,14 @@ pub enum VolatilityTrend {
     Volatile,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Bands from `VolatilityEstimator::impact_thresholds`: 50/80/120% annualized
+/// for return-based estimators, 2/5/10% for price dispersion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum VolatilityImpact {
-    Low,      // < 2%
-    Moderate, // 2-5%
-    High,     // 5-10%
-    Extreme,  // > 10%
+    Low,
+    Moderate,
+    High,
+    Extreme,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -36,3 +213,33 @@ pub struct VolatilityAdjustments {
     pub position_size_factor: Decimal,
     pub execution_urgency: ExecutionUrgency,
 }
+
+impl VolatilityAdjustments {
+    /// Wider spreads, smaller positions and more cautious execution for
+    /// higher impact bands
+    pub fn for_impact(impact: VolatilityImpact, trend: &VolatilityTrend) -> Self {
+        let spread_multiplier = match impact {
+            VolatilityImpact::Low => dec!(1.0),
+            VolatilityImpact::Moderate => dec!(1.5),
+            VolatilityImpact::High => dec!(2.0),
+            VolatilityImpact::Extreme => dec!(3.0),
+        };
+
+        let position_size_factor = match impact {
+            VolatilityImpact::Low => dec!(1.0),
+            VolatilityImpact::Moderate => dec!(0.8),
+            VolatilityImpact::High => dec!(0.5),
+            VolatilityImpact::Extreme => dec!(0.25),
+        };
+
+        let execution_urgency = match (&impact, trend) {
+            (VolatilityImpact::Extreme, _) => ExecutionUrgency::Cautious,
+            (VolatilityImpact::High, VolatilityTrend::Increasing) => ExecutionUrgency::Cautious,
+            (VolatilityImpact::Low, _) => ExecutionUrgency::Normal,
+            (_, VolatilityTrend::Stable) => ExecutionUrgency::Fast,
+            _ => ExecutionUrgency::Normal,
+        };
+
+        Self { spread_multiplier, position_size_factor, execution_urgency }
+    }
+}
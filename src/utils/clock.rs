@@ -0,0 +1,57 @@
+//! Monotonic timestamps labeled with wall-clock time
+//!
+//! NTP corrections and manual clock changes can step `SystemTime` forwards
+//! or backwards. A [`MonotonicClock`] reads the wall clock once when created
+//! and advances from there by `Instant`, so the times it hands out never go
+//! backwards and the gaps between them are real elapsed time.
+
+use std::time::{Duration, Instant, SystemTime};
+
+#[derive(Debug, Clone, Copy)]
+pub struct MonotonicClock {
+    started_wall: SystemTime,
+    started: Instant,
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MonotonicClock {
+    pub fn new() -> Self {
+        Self::starting_at(SystemTime::now())
+    }
+
+    /// A clock whose current time is labeled `wall`
+    pub fn starting_at(wall: SystemTime) -> Self {
+        Self { started_wall: wall, started: Instant::now() }
+    }
+
+    pub fn now(&self) -> SystemTime {
+        self.at(Instant::now())
+    }
+
+    /// The wall-clock label of `instant`
+    pub fn at(&self, instant: Instant) -> SystemTime {
+        match instant.checked_duration_since(self.started) {
+            Some(elapsed) => self.started_wall + elapsed,
+            None => self.started_wall.checked_sub(self.started - instant).unwrap_or(self.started_wall),
+        }
+    }
+
+    /// How far the system clock has moved away from this clock since it
+    /// started, in seconds (positive when the system clock is ahead)
+    pub fn wall_skew_secs(&self) -> f64 {
+        let (wall, monotonic) = (SystemTime::now(), self.now());
+        match wall.duration_since(monotonic) {
+            Ok(ahead) => ahead.as_secs_f64(),
+            Err(behind) => -behind.duration().as_secs_f64(),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
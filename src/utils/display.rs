@@ -4,8 +4,7 @@ use std::collections::HashMap;
 use std::time::Instant;
 use tracing::{info, warn, error};
 use crate::{
-    errors::CircuitBreaker,
-    types::{ArbitrageOpportunity, MarketMakingSignal, TradeExecution, ExecutionStatus, VolatilityMetrics},
+    types::{ArbitrageOpportunity, CircuitBreakerStatus, MarketMakingSignal, TradeExecution, ExecutionStatus, VolatilityMetrics},
 };
 
 pub async fn print_session_stats(
@@ -17,7 +16,7 @@ pub async fn print_session_stats(
     total_executions: u64,
     successful_executions: u64,
     error_counts: &HashMap<String, u32>,
-    circuit_breaker: &CircuitBreaker,
+    breakers: &CircuitBreakerStatus,
 ) {
     let runtime = start_time.elapsed().as_secs() / 60;
     
@@ -56,8 +55,9 @@ pub async fn print_session_stats(
     );
     
     info!("   ⚙️  SYSTEM:");
-    info!("     Circuit breaker: {}", 
-        if *circuit_breaker.is_open.read().await { "OPEN" } else { "CLOSED" }
+    let open_breakers = breakers.open_scopes();
+    info!("     Circuit breakers: {}",
+        if open_breakers.is_empty() { "all CLOSED".to_string() } else { format!("OPEN: {}", open_breakers.join(", ")) }
     );
     
     if !error_counts.is_empty() {
@@ -76,12 +76,16 @@ pub fn print_arbitrage_opportunity(opportunity: &ArbitrageOpportunity, volatilit
     warn!("📋 Strategy: {}", opportunity.direction);
     warn!("💰 Profit Analysis:");
     warn!("   DEX Price: ${:.4}", opportunity.dex_price);
-    warn!("   CEX Price: ${:.4}", opportunity.cex_price);
+    if let Some(quoted) = opportunity.quoted_dex_price {
+        warn!("   DEX Execution Price: ${:.4} ({} ETH)", quoted, opportunity.size_eth);
+    }
+    warn!("   Fair Value ({}): ${:.4}", opportunity.price_basis, opportunity.cex_price);
     warn!("   Net Profit: ${:.2}", opportunity.net_profit_usd);
     warn!("   ROI: {:.3}%", opportunity.roi_pct);
-    warn!("📊 Volatility: {:.2}% (Impact: {:?})",
+    warn!("📊 Volatility: {:.2}% (Impact: {:?}, Confidence: {:?})",
         volatility_metrics.short_term_volatility,
-        volatility_metrics.impact_assessment
+        volatility_metrics.impact_assessment,
+        volatility_metrics.confidence
     );
     warn!("✅ All validation checks passed");
 }
@@ -90,7 +94,7 @@ pub fn print_market_making_signal(signal: &MarketMakingSignal) {
     warn!("\n🎯 MARKET MAKING SIGNAL #{}", signal.id);
     warn!("📍 Pool: {}", signal.pool);
     warn!("💰 Price Analysis:");
-    warn!("   Fair Value (CEX): ${:.4}", signal.fair_value_price);
+    warn!("   Fair Value ({}): ${:.4}", signal.price_basis, signal.fair_value_price);
     warn!("   Current Pool:     ${:.4}", signal.current_pool_price);
     warn!("   Target Bid:       ${:.4}", signal.target_bid_price);
     warn!("   Target Ask:       ${:.4}", signal.target_ask_price);
@@ -119,6 +123,12 @@ pub fn print_market_making_signal(signal: &MarketMakingSignal) {
     warn!("   Volatility Risk: {:.1}/100", signal.risk_metrics.volatility_risk_score);
     warn!("   Max Recommended Exposure: {:.4} ETH", signal.risk_metrics.recommended_max_exposure);
     warn!("   1-Day VaR: ${:.2}", signal.risk_metrics.value_at_risk_1d);
+    if !signal.risk_metrics.stress_tests.is_empty() {
+        warn!("🧯 Stress Tests:");
+        for result in &signal.risk_metrics.stress_tests {
+            warn!("   {:?}: ${:.2} ({:.2}%)", result.scenario, result.pnl_usd, result.pnl_pct);
+        }
+    }
     
     warn!("🚨 Execution Priority: {:?}", signal.execution_priority);
     warn!("📝 Strategy Rationale:");
@@ -137,6 +147,16 @@ pub fn print_trade_execution(execution: &TradeExecution) {
             if let Some(tx_hash) = &execution.tx_hash {
                 warn!("   Tx Hash: {}", tx_hash);
             }
+            if let Some(dex_fill) = execution.dex_fill_price {
+                warn!("   DEX Fill: ${:.4}", dex_fill);
+            }
+            if let Some(cex_fill) = execution.cex_fill_price {
+                warn!("   CEX Fill: ${:.4} (order {})", cex_fill,
+                    execution.cex_order_id.as_deref().unwrap_or("modeled"));
+            }
+            if let Some(error) = &execution.error_message {
+                warn!("   Warning: {}", error);
+            }
             warn!("   Expected Profit: ${:.2}", execution.expected_profit_usd);
             if let Some(actual_profit) = execution.actual_profit_usd {
                 warn!("   Actual Profit: ${:.2}", actual_profit);
@@ -1,18 +1,25 @@
 //! Health monitoring utilities
 
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
 use crate::{
+    api::StatusBoard,
     config::PRICE_STALENESS_SECONDS,
-    errors::CircuitBreaker,
+    errors::CircuitBreakerRegistry,
+    execution::WALLET_BALANCES,
+    network::LATENCY,
     types::HealthStatus,
 };
 
 pub async fn run_health_check(
     dex_last_update: &Option<Instant>,
     cex_last_update: &Option<Instant>,
-    circuit_breaker: &CircuitBreaker,
+    breakers: &CircuitBreakerRegistry,
     start_time: Instant,
 ) -> HealthStatus {
+    let breaker_status = breakers.state().await;
     HealthStatus {
         dex_connection: dex_last_update
             .map(|t| t.elapsed().as_secs() < PRICE_STALENESS_SECONDS)
@@ -22,8 +29,72 @@ pub async fn run_health_check(
             .unwrap_or(false),
         last_dex_update: *dex_last_update,
         last_cex_update: *cex_last_update,
-        consecutive_errors: *circuit_breaker.consecutive_errors.read().await,
-        circuit_breaker_active: *circuit_breaker.is_open.read().await,
+        consecutive_errors: breaker_status.cex.consecutive_errors.max(breaker_status.rpc.consecutive_errors),
+        circuit_breaker_active: breaker_status.cex.open || breaker_status.rpc.open,
+        open_breakers: breaker_status.open_scopes(),
         uptime_seconds: start_time.elapsed().as_secs(),
+        latency: LATENCY.snapshot(),
+        wallet_balances: WALLET_BALANCES.snapshot(),
+    }
+}
+
+/// Check health every `interval` on its own task, independent of how long
+/// cycles take, and publish each result to `board`. Feed freshness comes
+/// from the last published status report, so a stalled loop shows up as
+/// stale feeds.
+pub fn spawn_health_monitor(
+    board: StatusBoard,
+    breakers: Arc<CircuitBreakerRegistry>,
+    start_time: Instant,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let latest = board.latest_report().await;
+            let (dex_last_update, cex_last_update) = latest.as_ref()
+                .map(|report| (report.health.last_dex_update, report.health.last_cex_update))
+                .unwrap_or_default();
+            let health = run_health_check(&dex_last_update, &cex_last_update, &breakers, start_time).await;
+            log_health(&health);
+            if let Some(report) = latest.filter(|report| !report.monitoring.error_counts.is_empty()) {
+                debug!("Error summary: {:?}", report.monitoring.error_counts);
+            }
+            board.publish_health(health);
+        }
+    })
+}
+
+fn log_health(health: &HealthStatus) {
+    info!("🏥 Health Check: DEX={}, CEX={}, Uptime={}s, Errors={}, Open breakers={}",
+        if health.dex_connection { "OK" } else { "FAIL" },
+        if health.cex_connection { "OK" } else { "FAIL" },
+        health.uptime_seconds,
+        health.consecutive_errors,
+        if health.open_breakers.is_empty() { "none".to_string() } else { health.open_breakers.join(", ") }
+    );
+
+    if let Some(balances) = &health.wallet_balances {
+        let tokens = balances.tokens.iter()
+            .map(|(symbol, balance)| format!("{} {}", balance, symbol))
+            .collect::<Vec<_>>();
+        info!("👛 Wallet {:?}: {} ETH{}", balances.wallet, balances.eth,
+            if tokens.is_empty() { String::new() } else { format!(", {}", tokens.join(", ")) });
+        if let Some(shortfall) = &balances.shortfall {
+            warn!("💸 Last execution blocked: {}", shortfall);
+        }
+    }
+
+    for (source, latency) in &health.latency {
+        info!("📶 {} latency: p50={}ms p95={}ms max={}ms over {} requests ({} failed)",
+            source,
+            latency.p50_ms.unwrap_or_default(),
+            latency.p95_ms.unwrap_or_default(),
+            latency.max_ms.unwrap_or_default(),
+            latency.count,
+            latency.errors,
+        );
     }
 }
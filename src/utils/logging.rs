@@ -46,6 +46,14 @@ pub fn setup_output_directories() -> Result<()> {
     fs::create_dir_all("output/reports")?;
     fs::create_dir_all("output/market_making")?;
     fs::create_dir_all("output/executions")?;
+    fs::create_dir_all("output/cache")?;
+    fs::create_dir_all("output/decisions")?;
+    fs::create_dir_all("output/checkpoints")?;
+    fs::create_dir_all("output/history")?;
+    fs::create_dir_all("output/spreads")?;
+    fs::create_dir_all("output/prices")?;
+    fs::create_dir_all("output/portfolio")?;
+    fs::create_dir_all("output/lifecycles")?;
     
     Ok(())
 }
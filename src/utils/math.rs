@@ -1,7 +1,10 @@
 //! Mathematical utility functions
 
+use alloy::primitives::{Address, U256};
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
+use std::str::FromStr;
+use crate::pools::TokenMetadataCache;
 
 pub fn pow10(n: i32) -> Decimal {
     match n {
@@ -23,3 +26,58 @@ pub fn pow10(n: i32) -> Decimal {
         }
     }
 }
+
+/// Order a token0/token1 value pair as (base, quote).
+///
+/// Returns `None` if `base` is neither token0 nor token1.
+pub fn order_pair<T>(token0: Address, token1: Address, base: Address, value0: T, value1: T) -> Option<(T, T)> {
+    if token0 == base {
+        Some((value0, value1))
+    } else if token1 == base {
+        Some((value1, value0))
+    } else {
+        None
+    }
+}
+
+/// Convert a raw on-chain integer amount into a human-readable decimal amount
+pub fn scale_from_raw(raw: U256, decimals: i32) -> Option<Decimal> {
+    Decimal::from_str(&raw.to_string()).ok().map(|amount| amount / pow10(decimals))
+}
+
+/// Convert a human-readable decimal amount into a raw on-chain integer amount (truncating).
+///
+/// Returns `None` if the raw amount does not fit, so callers fail instead of sending zero.
+pub fn scale_to_raw(amount: Decimal, decimals: i32) -> Option<U256> {
+    if amount <= dec!(0) {
+        return Some(U256::ZERO);
+    }
+    amount.checked_mul(pow10(decimals))?.trunc().to_u128().map(U256::from)
+}
+
+/// Invert a price (quote per base -> base per quote)
+pub fn invert_price(price: Decimal) -> Option<Decimal> {
+    if price == dec!(0) {
+        None
+    } else {
+        Some(dec!(1) / price)
+    }
+}
+
+/// Normalize token0/token1 reserves of a pool into (base, quote) human
+/// amounts. Returns `None` unless both tokens' decimals are in `tokens`.
+pub fn normalize_reserves(
+    tokens: &TokenMetadataCache,
+    token0: Address,
+    token1: Address,
+    base: Address,
+    reserve0: U256,
+    reserve1: U256,
+) -> Option<(Decimal, Decimal)> {
+    let (base_raw, quote_raw) = order_pair(token0, token1, base, reserve0, reserve1)?;
+    let quote_token = if token0 == base { token1 } else { token0 };
+    Some((
+        scale_from_raw(base_raw, tokens.get(base)?.decimals)?,
+        scale_from_raw(quote_raw, tokens.get(quote_token)?.decimals)?,
+    ))
+}
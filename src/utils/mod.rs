@@ -4,8 +4,10 @@ pub mod math;
 pub mod logging;
 pub mod health;
 pub mod display;
+pub mod clock;
 
 pub use math::*;
 pub use logging::*;
 pub use health::*;
 pub use display::*;
+pub use clock::*;
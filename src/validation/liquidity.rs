@@ -18,3 +18,15 @@ pub fn validate_liquidity(weth_reserve: Decimal, usd_reserve: Decimal) -> Result
     
     Ok(())
 }
+
+/// Liquidity check for pairs other than WETH/USD, on the USD value of the
+/// base side (a balanced pool holds as much again in the quote token)
+pub fn validate_base_liquidity_usd(base_value_usd: Decimal) -> Result<()> {
+    const MIN_BASE_LIQUIDITY_USD: Decimal = dec!(100);
+    
+    if base_value_usd < MIN_BASE_LIQUIDITY_USD {
+        return Err(anyhow::anyhow!("Insufficient base token liquidity: ${}", base_value_usd));
+    }
+    
+    Ok(())
+}
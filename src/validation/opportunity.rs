@@ -3,16 +3,11 @@
 use alloy::providers::Provider;
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
-use std::str::FromStr;
 use crate::{
     config::{CONFIG, MAX_PRICE_DEVIATION_PCT, MAX_SLIPPAGE_BPS},
-    pools::get_pool_reserves_enhanced,
-    types::{
-        ArbitrageOpportunity, PoolInfo, ValidationResult, VolatilityMetrics, VolatilityImpact,
-        WETH_MAINNET
-    },
-    utils::pow10,
-    validation::validate_liquidity,
+    pools::{estimate_price_impact_bps, get_pool_reserves_enhanced, spot_twap_divergence_pct, MIN_POOL_HEALTH_SCORE},
+    types::{ArbitrageOpportunity, OrderBook, PoolHealth, PoolInfo, ValidationResult, VolatilityMetrics, VolatilityImpact, MIN_VOLATILITY_SAMPLES},
+    validation::{validate_against_oracle, validate_base_liquidity_usd, validate_liquidity},
 };
 
 pub async fn validate_opportunity_with_volatility(
@@ -20,10 +15,28 @@ pub async fn validate_opportunity_with_volatility(
     pool_info: &PoolInfo,
     provider: &dyn Provider,
     volatility_metrics: &VolatilityMetrics,
+    pool_health: Option<&PoolHealth>,
+    oracle_price: Option<Decimal>,
+    dex_twap_price: Option<Decimal>,
+    cex_depth: Option<&OrderBook>,
 ) -> ValidationResult {
     let mut result = ValidationResult::default();
     let mut all_good = true;
 
+    // Pool health check (passes while no history has been collected)
+    result.pool_health_acceptable = pool_health
+        .map(|h| h.overall_score >= MIN_POOL_HEALTH_SCORE)
+        .unwrap_or(true);
+    if !result.pool_health_acceptable {
+        if let Some(health) = pool_health {
+            result.warnings.push(format!(
+                "Pool health too low: {:.1} (min: {})",
+                health.overall_score, MIN_POOL_HEALTH_SCORE
+            ));
+        }
+        all_good = false;
+    }
+
     // Price sanity check
     result.price_sanity = opp.price_diff_pct < MAX_PRICE_DEVIATION_PCT;
     if !result.price_sanity {
@@ -34,42 +47,79 @@ pub async fn validate_opportunity_with_volatility(
         all_good = false;
     }
 
-    // Volatility check
-    result.volatility_acceptable = volatility_metrics.short_term_volatility < CONFIG.volatility_threshold;
+    // Oracle cross-check, for WETH/USD pairs with a fresh oracle answer
+    result.oracle_consistent = true;
+    if let Some(oracle_price) = oracle_price.filter(|_| pool_info.pair.is_weth_usd()) {
+        let max_deviation_pct = CONFIG.get().max_oracle_deviation_pct;
+        if let Err(e) = validate_against_oracle(opp.dex_price, opp.cex_price, oracle_price, max_deviation_pct) {
+            result.warnings.push(e.to_string());
+            result.oracle_consistent = false;
+            all_good = false;
+        }
+    }
+
+    // Manipulation check: spot vs the pool's on-chain TWAP (zero bound disables)
+    let max_twap_deviation_pct = CONFIG.get().max_twap_deviation_pct;
+    if let Some(twap) = dex_twap_price.filter(|_| max_twap_deviation_pct > dec!(0)) {
+        let divergence_pct = spot_twap_divergence_pct(opp.dex_price, twap);
+        if divergence_pct > max_twap_deviation_pct {
+            result.warnings.push(format!(
+                "Possible manipulation: DEX spot ${:.4} is {:.2}% from its on-chain TWAP ${:.4} (max: {}%)",
+                opp.dex_price, divergence_pct, twap, max_twap_deviation_pct
+            ));
+            result.manipulation_suspected = true;
+            all_good = false;
+        }
+    }
+
+    // Volatility check (at least Moderate while too few samples to measure)
+    let short_term_volatility = volatility_metrics.conservative_short_term_volatility();
+    result.volatility_acceptable = short_term_volatility < CONFIG.get().volatility_threshold;
+    if volatility_metrics.is_low_confidence() {
+        result.warnings.push(format!(
+            "Volatility unmeasured: {} of {} short-term samples, assuming {:.2}%",
+            volatility_metrics.sample_count, MIN_VOLATILITY_SAMPLES, short_term_volatility
+        ));
+    }
     if !result.volatility_acceptable {
         result.warnings.push(format!(
             "Volatility too high: {:.2}% (threshold: {:.2}%)",
-            volatility_metrics.short_term_volatility,
-            CONFIG.volatility_threshold
+            short_term_volatility,
+            CONFIG.get().volatility_threshold
         ));
         // Don't fail entirely on high volatility, just warn
     }
 
     // Liquidity check
-    match get_pool_reserves_enhanced(provider, pool_info.address, &pool_info.name).await {
+    let mut curve_impact_bps = None;
+    match get_pool_reserves_enhanced(provider, pool_info.address, &pool_info.name, pool_info.kind).await {
         Ok((r0, r1)) => {
-            let (weth_reserve, usd_reserve) = if pool_info.token0 == WETH_MAINNET {
-                (
-                    Decimal::from_str(&r0.to_string()).unwrap_or_default() / pow10(18),
-                    Decimal::from_str(&r1.to_string()).unwrap_or_default() / pow10(6)
-                )
+            let (base_reserve, quote_reserve) = pool_info.base_quote_reserves(r0, r1).unwrap_or_default();
+            let pair = &pool_info.pair;
+
+            result.liquidity_check = if pair.is_weth_usd() {
+                validate_liquidity(base_reserve, quote_reserve).is_ok()
             } else {
-                (
-                    Decimal::from_str(&r1.to_string()).unwrap_or_default() / pow10(18),
-                    Decimal::from_str(&r0.to_string()).unwrap_or_default() / pow10(6)
-                )
+                validate_base_liquidity_usd(base_reserve * opp.dex_price).is_ok()
             };
-
-            result.liquidity_check = validate_liquidity(weth_reserve, usd_reserve).is_ok();
             if !result.liquidity_check {
                 result.warnings.push(format!(
-                    "Low liquidity: {:.4} WETH, ${:.2} USD", 
-                    weth_reserve, usd_reserve
+                    "Low liquidity: {:.4} {}, {:.2} {}", 
+                    base_reserve, pair.base_symbol, quote_reserve, pair.quote_symbol
                 ));
                 all_good = false;
             }
 
-            let trade_impact_pct = (opp.size_eth / weth_reserve) * dec!(100);
+            curve_impact_bps = estimate_price_impact_bps(
+                pool_info.is_stable,
+                opp.size_eth,
+                base_reserve,
+                quote_reserve,
+            );
+
+            let trade_impact_pct = opp.size_eth.checked_div(base_reserve)
+                .map(|ratio| ratio * dec!(100))
+                .unwrap_or(dec!(100));
             if trade_impact_pct > dec!(1) {
                 result.warnings.push(format!(
                     "Trade size is {:.2}% of pool liquidity", 
@@ -85,6 +135,21 @@ pub async fn validate_opportunity_with_volatility(
         }
     }
 
+    // CEX depth check: the hedge leg fills within the fetched book levels
+    result.cex_liquidity = true;
+    if let Some(book) = cex_depth {
+        let buy_on_cex = !opp.direction.contains("Buy on Aerodrome");
+        let depth = book.depth(buy_on_cex);
+        if depth < opp.size_eth {
+            result.warnings.push(format!(
+                "CEX book too thin: {:.4} of {} {} within fetched levels",
+                depth, opp.size_eth, pool_info.pair.base_symbol
+            ));
+            result.cex_liquidity = false;
+            all_good = false;
+        }
+    }
+
     // Gas economics check
     result.gas_economics = opp.net_profit_usd > dec!(0) && opp.roi_pct > dec!(0.01);
     if !result.gas_economics {
@@ -100,7 +165,11 @@ pub async fn validate_opportunity_with_volatility(
         VolatilityImpact::Extreme => dec!(3),
     };
     
-    let estimated_slippage_bps = (opp.size_eth * dec!(50) * volatility_slippage_factor) / dec!(1);
+    // Use the pool's own curve (x*y=k or stable-swap) when reserves are known
+    let estimated_slippage_bps = match curve_impact_bps {
+        Some(impact_bps) => impact_bps * volatility_slippage_factor,
+        None => opp.size_eth * dec!(50) * volatility_slippage_factor,
+    };
     result.slippage_acceptable = estimated_slippage_bps < Decimal::from(MAX_SLIPPAGE_BPS);
     if !result.slippage_acceptable {
         result.warnings.push(format!(
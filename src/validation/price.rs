@@ -3,6 +3,7 @@
 use anyhow::Result;
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
+use crate::{network::oracle_deviation_pct, types::TokenPair};
 
 pub fn validate_price(price: Decimal, source: &str) -> Result<()> {
     if price <= dec!(0) {
@@ -15,3 +16,35 @@ pub fn validate_price(price: Decimal, source: &str) -> Result<()> {
     
     Ok(())
 }
+
+/// Validate a pool price in quote tokens per base token. The ETH range
+/// check only applies to WETH/USD pairs; other pairs must be positive.
+pub fn validate_pair_price(price: Decimal, source: &str, pair: &TokenPair) -> Result<()> {
+    if pair.is_weth_usd() {
+        return validate_price(price, source);
+    }
+    if price <= dec!(0) {
+        return Err(anyhow::anyhow!("{} {} price is zero or negative: {}", source, pair, price));
+    }
+    Ok(())
+}
+
+/// Reject a DEX or CEX price further than `max_deviation_pct` from the
+/// oracle, which points to a poisoned or broken feed rather than an opportunity
+pub fn validate_against_oracle(
+    dex_price: Decimal,
+    cex_price: Decimal,
+    oracle_price: Decimal,
+    max_deviation_pct: Decimal,
+) -> Result<()> {
+    for (source, price) in [("DEX", dex_price), ("CEX", cex_price)] {
+        let deviation_pct = oracle_deviation_pct(price, oracle_price);
+        if deviation_pct > max_deviation_pct {
+            return Err(anyhow::anyhow!(
+                "{} price ${:.2} is {:.2}% from the oracle's ${:.2} (max: {}%)",
+                source, price, deviation_pct, oracle_price, max_deviation_pct
+            ));
+        }
+    }
+    Ok(())
+}
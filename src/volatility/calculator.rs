@@ -1,84 +1,149 @@
 //! Volatility calculator for single timeframes
 
-use std::collections::VecDeque;
-use std::time::{Duration, SystemTime};
-use tracing::warn;
+use rust_decimal::prelude::ToPrimitive;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::config::DEFAULT_EWMA_LAMBDA;
+use crate::storage::{Candle, PriceHistory};
+use crate::types::{VolatilityEstimator, MIN_VOLATILITY_SAMPLES};
 
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 3600.0;
+/// GARCH(1,1) weights on the last squared return and the last variance
+const GARCH_ALPHA: f64 = 0.1;
+const GARCH_BETA: f64 = 0.85;
+
+/// One timeframe over a shared price history
 pub struct VolatilityCalculator {
-    window: VecDeque<(SystemTime, f64)>,
     max_duration: Duration,
+    estimator: VolatilityEstimator,
+    ewma_lambda: f64,
 }
 
 impl VolatilityCalculator {
     pub fn new(max_duration_secs: u64) -> Self {
         VolatilityCalculator {
-            window: VecDeque::new(),
             max_duration: Duration::from_secs(max_duration_secs),
+            estimator: VolatilityEstimator::default(),
+            ewma_lambda: DEFAULT_EWMA_LAMBDA.to_f64().unwrap_or_default(),
         }
     }
 
-    pub fn add_value(&mut self, price: f64) {
-        let now = SystemTime::now();
-        self.window.push_back((now, price));
-
-        while let Some((timestamp, _)) = self.window.front() {
-            if let Ok(duration) = now.duration_since(*timestamp) {
-                if duration > self.max_duration {
-                    self.window.pop_front();
-                } else {
-                    break;
-                }
-            } else {
-                warn!("Encountered a timestamp in the future: {:?}", timestamp);
-                self.window.pop_front();
-            }
+    /// Estimate with `estimator`; `ewma_lambda` only applies to EWMA
+    pub fn with_estimator(mut self, estimator: VolatilityEstimator, ewma_lambda: f64) -> Self {
+        self.estimator = estimator;
+        self.ewma_lambda = ewma_lambda;
+        self
+    }
+
+    pub fn estimator(&self) -> VolatilityEstimator {
+        self.estimator
+    }
+
+    /// Samples in this timeframe ending at the latest one, including any
+    /// the history has spilled to disk
+    pub fn window(&self, history: &PriceHistory) -> Vec<(SystemTime, f64)> {
+        match history.latest() {
+            Some((latest, _)) => history.since(latest.checked_sub(self.max_duration).unwrap_or(UNIX_EPOCH)),
+            None => Vec::new(),
         }
     }
 
-    pub fn calculate_volatility(&self) -> Option<f64> {
-        if self.window.len() < 10 {
+    pub fn calculate_volatility(&self, history: &PriceHistory) -> Option<f64> {
+        let window = self.window(history);
+        if window.len() < MIN_VOLATILITY_SAMPLES {
             return None;
         }
 
-        let prices: Vec<f64> = self.window.iter().map(|(_, price)| *price).collect();
+        let prices: Vec<f64> = window.iter().map(|(_, price)| *price).collect();
         let mean: f64 = prices.iter().sum::<f64>() / prices.len() as f64;
         let variance: f64 = prices.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / prices.len() as f64;
 
         Some(variance.sqrt())
     }
 
-    pub fn calculate_volatility_percentage(&self) -> Option<f64> {
-        if let Some(volatility) = self.calculate_volatility() {
-            if self.window.len() < 10 {
-                return None;
+    /// Volatility in percent, using the configured estimator. Return-based
+    /// estimators annualize the per-sample variance by the window's mean
+    /// sample interval.
+    pub fn calculate_volatility_percentage(&self, history: &PriceHistory) -> Option<f64> {
+        let window = self.window(history);
+        if window.len() < MIN_VOLATILITY_SAMPLES {
+            return None;
+        }
+
+        let prices: Vec<f64> = window.iter().map(|(_, price)| *price).collect();
+        let returns = log_returns(&prices);
+        let (first, rest) = returns.split_first()?;
+        let count = returns.len() as f64;
+        let variance = match self.estimator {
+            VolatilityEstimator::PriceStdev => return price_stdev_percentage(&prices),
+            VolatilityEstimator::LogReturns => {
+                let mean = returns.iter().sum::<f64>() / count;
+                returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / count
             }
-            
-            let prices: Vec<f64> = self.window.iter().map(|(_, price)| *price).collect();
-            let mean: f64 = prices.iter().sum::<f64>() / prices.len() as f64;
-            
-            if mean > 0.0 {
-                Some((volatility / mean) * 100.0)
-            } else {
-                None
+            VolatilityEstimator::Realized => returns.iter().map(|r| r * r).sum::<f64>() / count,
+            VolatilityEstimator::Ewma => rest.iter()
+                .fold(first * first, |variance, r| self.ewma_lambda * variance + (1.0 - self.ewma_lambda) * r * r),
+            VolatilityEstimator::Garch => {
+                let long_run_variance = returns.iter().map(|r| r * r).sum::<f64>() / count;
+                let omega = (1.0 - GARCH_ALPHA - GARCH_BETA) * long_run_variance;
+                returns.iter()
+                    .fold(long_run_variance, |variance, r| omega + GARCH_ALPHA * r * r + GARCH_BETA * variance)
             }
-        } else {
-            None
+        };
+
+        let (start, end) = (window.first()?.0, window.last()?.0);
+        let sample_interval_secs = end.duration_since(start).ok()?.as_secs_f64() / count;
+        if sample_interval_secs <= 0.0 {
+            return None;
         }
+        Some((variance * SECONDS_PER_YEAR / sample_interval_secs).sqrt() * 100.0)
     }
 
-    pub fn sample_count(&self) -> usize {
-        self.window.len()
+    pub fn sample_count(&self, history: &PriceHistory) -> usize {
+        self.window(history).len()
     }
 
-    pub fn window_duration(&self) -> Option<Duration> {
-        if self.window.len() < 2 {
+    pub fn window_duration(&self, history: &PriceHistory) -> Option<Duration> {
+        let window = self.window(history);
+        if window.len() < 2 {
             return None;
         }
-        
-        if let (Some((first_time, _)), Some((last_time, _))) = (self.window.front(), self.window.back()) {
+
+        if let (Some((first_time, _)), Some((last_time, _))) = (window.first(), window.last()) {
             last_time.duration_since(*first_time).ok()
         } else {
             None
         }
     }
 }
+
+fn price_stdev_percentage(prices: &[f64]) -> Option<f64> {
+    let mean: f64 = prices.iter().sum::<f64>() / prices.len() as f64;
+    let variance: f64 = prices.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / prices.len() as f64;
+
+    if mean > 0.0 {
+        Some((variance.sqrt() / mean) * 100.0)
+    } else {
+        None
+    }
+}
+
+/// ln(p_t / p_{t-1}) for consecutive positive prices
+fn log_returns(prices: &[f64]) -> Vec<f64> {
+    prices.windows(2)
+        .filter(|pair| pair[0] > 0.0 && pair[1] > 0.0)
+        .map(|pair| (pair[1] / pair[0]).ln())
+        .collect()
+}
+
+/// Volatility percentage of one market's candle closes across all of them,
+/// measured the same way as the live timeframes (at least `MIN_VOLATILITY_SAMPLES` candles)
+pub fn candle_volatility_percentage(candles: &[Candle]) -> Option<f64> {
+    let span = candles.last()?.open_time.signed_duration_since(candles.first()?.open_time);
+    let window_secs = span.num_seconds().max(0) as u64 + 1;
+    let mut history = PriceHistory::in_memory(Duration::from_secs(window_secs));
+    for candle in candles {
+        let at = UNIX_EPOCH + Duration::from_millis(candle.open_time.timestamp_millis().max(0) as u64);
+        history.push(at, candle.close.to_f64()?);
+    }
+    VolatilityCalculator::new(window_secs).calculate_volatility_percentage(&history)
+}
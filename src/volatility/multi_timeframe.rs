@@ -2,103 +2,216 @@
 
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 use tokio::sync::RwLock;
 use crate::{
-    types::{VolatilityMetrics, VolatilityTrend, VolatilityImpact, VolatilityAdjustments, ExecutionUrgency},
+    storage::{HistorySnapshot, PriceHistory},
+    types::{
+        PoolVolatility, VolatilityConfidence, VolatilityEstimator, VolatilityMetrics, VolatilityTrend,
+        VolatilityImpact, VolatilityAdjustments, MIN_VOLATILITY_SAMPLES,
+    },
+    utils::MonotonicClock,
     volatility::VolatilityCalculator,
 };
 
+pub const SHORT_TERM_SECS: u64 = 300;
+pub const MEDIUM_TERM_SECS: u64 = 1800;
+/// Longest timeframe; the shared history keeps this much
+pub const LONG_TERM_SECS: u64 = 3600;
+
+/// One pool's DEX prices and DEX/CEX price ratios
+struct PoolHistory {
+    dex: PriceHistory,
+    basis: PriceHistory,
+}
+
 pub struct MultiTimeframeVolatilityCalculator {
-    short_term: Arc<RwLock<VolatilityCalculator>>,   // 5 minutes
-    medium_term: Arc<RwLock<VolatilityCalculator>>,  // 30 minutes
-    long_term: Arc<RwLock<VolatilityCalculator>>,    // 1 hour
+    history: Arc<RwLock<PriceHistory>>,
+    /// DEX price histories by pool name, kept in memory
+    pools: Arc<RwLock<HashMap<String, PoolHistory>>>,
+    /// Stamps live prices, so system clock steps don't distort the windows
+    clock: MonotonicClock,
+    short_term: VolatilityCalculator,   // 5 minutes
+    medium_term: VolatilityCalculator,  // 30 minutes
+    long_term: VolatilityCalculator,    // 1 hour
 }
 
 impl MultiTimeframeVolatilityCalculator {
+    /// Keep the whole history in memory (used when replaying recorded data)
     pub fn new() -> Self {
+        Self::with_history(PriceHistory::in_memory(Duration::from_secs(LONG_TERM_SECS)))
+    }
+
+    /// Keep at most `max_memory` samples in memory, spilling older ones to
+    /// `output/history/<series>/`
+    pub fn spilling(series: &str, max_memory: usize, retention_days: u64) -> Self {
+        Self::with_history(PriceHistory::spilling(
+            series,
+            Duration::from_secs(LONG_TERM_SECS),
+            max_memory,
+            retention_days,
+        ))
+    }
+
+    /// Estimate every timeframe with `estimator`; `ewma_lambda` only applies to EWMA
+    pub fn with_estimator(self, estimator: VolatilityEstimator, ewma_lambda: f64) -> Self {
         Self {
-            short_term: Arc::new(RwLock::new(VolatilityCalculator::new(300))),    // 5 min
-            medium_term: Arc::new(RwLock::new(VolatilityCalculator::new(1800))),  // 30 min
-            long_term: Arc::new(RwLock::new(VolatilityCalculator::new(3600))),    // 1 hour
+            short_term: self.short_term.with_estimator(estimator, ewma_lambda),
+            medium_term: self.medium_term.with_estimator(estimator, ewma_lambda),
+            long_term: self.long_term.with_estimator(estimator, ewma_lambda),
+            ..self
         }
     }
 
+    pub fn estimator(&self) -> VolatilityEstimator {
+        self.short_term.estimator()
+    }
+
+    fn with_history(history: PriceHistory) -> Self {
+        Self {
+            history: Arc::new(RwLock::new(history)),
+            pools: Arc::new(RwLock::new(HashMap::new())),
+            clock: MonotonicClock::new(),
+            short_term: VolatilityCalculator::new(SHORT_TERM_SECS),   // 5 min
+            medium_term: VolatilityCalculator::new(MEDIUM_TERM_SECS), // 30 min
+            long_term: VolatilityCalculator::new(LONG_TERM_SECS),  // 1 hour
+        }
+    }
+
+    /// Add a live price, stamped by the monotonic clock
     pub async fn add_price(&self, price: Decimal) {
+        self.add_price_at(self.clock.now(), price).await;
+    }
+
+    /// Add a price observed at `timestamp` (used when replaying recorded data)
+    pub async fn add_price_at(&self, timestamp: SystemTime, price: Decimal) {
         let price_f64 = price.to_f64().unwrap_or(0.0);
-        
-        // Update all timeframes
-        self.short_term.write().await.add_value(price_f64);
-        self.medium_term.write().await.add_value(price_f64);
-        self.long_term.write().await.add_value(price_f64);
+        self.history.write().await.push(timestamp, price_f64);
+    }
+
+    /// Add a pool's live DEX price and the CEX price it is compared with
+    pub async fn add_dex_price(&self, pool: &str, dex_price: Decimal, cex_price: Decimal) {
+        self.add_dex_price_at(pool, self.clock.now(), dex_price, cex_price).await;
+    }
+
+    /// Add a pool's DEX price observed at `timestamp`
+    pub async fn add_dex_price_at(&self, pool: &str, timestamp: SystemTime, dex_price: Decimal, cex_price: Decimal) {
+        let mut pools = self.pools.write().await;
+        let history = pools.entry(pool.to_string()).or_insert_with(|| PoolHistory {
+            dex: PriceHistory::in_memory(Duration::from_secs(LONG_TERM_SECS)),
+            basis: PriceHistory::in_memory(Duration::from_secs(LONG_TERM_SECS)),
+        });
+        history.dex.push(timestamp, dex_price.to_f64().unwrap_or(0.0));
+        if cex_price > Decimal::ZERO {
+            history.basis.push(timestamp, (dex_price / cex_price).to_f64().unwrap_or(0.0));
+        }
+    }
+
+    /// Short-term DEX and basis volatility of `pool`, None before its first price
+    pub async fn pool_volatility(&self, pool: &str) -> Option<PoolVolatility> {
+        let pools = self.pools.read().await;
+        let history = pools.get(pool)?;
+        let (sample_count, confidence) = self.confidence(&history.dex);
+        let percentage = |history: &PriceHistory| self.short_term.calculate_volatility_percentage(history)
+            .and_then(Decimal::from_f64)
+            .unwrap_or(dec!(0));
+        Some(PoolVolatility {
+            pool: pool.to_string(),
+            dex_volatility: percentage(&history.dex),
+            basis_volatility: percentage(&history.basis),
+            sample_count,
+            confidence,
+        })
     }
 
+    /// [`get_volatility_metrics`](Self::get_volatility_metrics) for one
+    /// pool, raised to its DEX or basis volatility band when higher
+    pub async fn get_pool_volatility_metrics(&self, pool: &str) -> VolatilityMetrics {
+        self.get_volatility_metrics().await.for_pool(self.pool_volatility(pool).await)
+    }
+
+    /// Short-term samples and how far the history covers the timeframes
+    fn confidence(&self, history: &PriceHistory) -> (usize, VolatilityConfidence) {
+        let sample_count = self.short_term.sample_count(history);
+        let span = self.long_term.window_duration(history).unwrap_or_default();
+        let confidence = if sample_count < MIN_VOLATILITY_SAMPLES {
+            VolatilityConfidence::Low
+        } else if span < Duration::from_secs(MEDIUM_TERM_SECS) {
+            VolatilityConfidence::Partial
+        } else {
+            VolatilityConfidence::Full
+        };
+        (sample_count, confidence)
+    }
+
+    pub async fn history_snapshot(&self) -> HistorySnapshot {
+        self.history.read().await.snapshot()
+    }
+
+    /// Resume from a snapshot saved by a previous run, minus any samples
+    /// stamped after the current time
+    pub async fn restore_history(&self, snapshot: &HistorySnapshot) {
+        self.history.write().await.restore_until(snapshot, Some(self.clock.now()));
+    }
+
+    pub fn clock(&self) -> &MonotonicClock {
+        &self.clock
+    }
+
+    /// Metrics for the current history. Until the short-term window has
+    /// `MIN_VOLATILITY_SAMPLES` samples the reading is low-confidence and
+    /// assessed as Moderate impact with an unknown (Volatile) trend rather
+    /// than as a calm market.
     pub async fn get_volatility_metrics(&self) -> VolatilityMetrics {
-        let short_vol = self.short_term.read().await.calculate_volatility_percentage()
+        let history = self.history.read().await;
+        let (sample_count, confidence) = self.confidence(&history);
+        let short_vol = self.short_term.calculate_volatility_percentage(&history)
             .map(|v| Decimal::from_f64(v).unwrap_or(dec!(0)))
             .unwrap_or(dec!(0));
             
-        let medium_vol = self.medium_term.read().await.calculate_volatility_percentage()
+        let medium_vol = self.medium_term.calculate_volatility_percentage(&history)
             .map(|v| Decimal::from_f64(v).unwrap_or(dec!(0)))
             .unwrap_or(dec!(0));
             
-        let long_vol = self.long_term.read().await.calculate_volatility_percentage()
+        let long_vol = self.long_term.calculate_volatility_percentage(&history)
             .map(|v| Decimal::from_f64(v).unwrap_or(dec!(0)))
             .unwrap_or(dec!(0));
 
+        let estimator = self.estimator();
+
         // Determine volatility trend
-        let trend = if short_vol > medium_vol * dec!(1.2) && medium_vol > long_vol * dec!(1.2) {
+        let trend = if confidence == VolatilityConfidence::Low {
+            VolatilityTrend::Volatile
+        } else if short_vol > medium_vol * dec!(1.2) && medium_vol > long_vol * dec!(1.2) {
             VolatilityTrend::Increasing
         } else if short_vol < medium_vol * dec!(0.8) && medium_vol < long_vol * dec!(0.8) {
             VolatilityTrend::Decreasing
-        } else if (short_vol - long_vol).abs() < dec!(1) {
+        } else if (short_vol - long_vol).abs() < estimator.stable_band() {
             VolatilityTrend::Stable
         } else {
             VolatilityTrend::Volatile
         };
 
         // Assess impact
-        let impact = match short_vol {
-            v if v < dec!(2) => VolatilityImpact::Low,
-            v if v < dec!(5) => VolatilityImpact::Moderate,
-            v if v < dec!(10) => VolatilityImpact::High,
-            _ => VolatilityImpact::Extreme,
-        };
-
-        // Calculate recommended adjustments
-        let spread_multiplier = match impact {
-            VolatilityImpact::Low => dec!(1.0),
-            VolatilityImpact::Moderate => dec!(1.5),
-            VolatilityImpact::High => dec!(2.0),
-            VolatilityImpact::Extreme => dec!(3.0),
-        };
-
-        let position_size_factor = match impact {
-            VolatilityImpact::Low => dec!(1.0),
-            VolatilityImpact::Moderate => dec!(0.8),
-            VolatilityImpact::High => dec!(0.5),
-            VolatilityImpact::Extreme => dec!(0.25),
-        };
-
-        let execution_urgency = match (&impact, &trend) {
-            (VolatilityImpact::Extreme, _) => ExecutionUrgency::Cautious,
-            (VolatilityImpact::High, VolatilityTrend::Increasing) => ExecutionUrgency::Cautious,
-            (VolatilityImpact::Low, _) => ExecutionUrgency::Normal,
-            (_, VolatilityTrend::Stable) => ExecutionUrgency::Fast,
-            _ => ExecutionUrgency::Normal,
+        let impact = if confidence == VolatilityConfidence::Low {
+            VolatilityImpact::Moderate
+        } else {
+            estimator.impact_of(short_vol)
         };
 
         VolatilityMetrics {
             short_term_volatility: short_vol,
             medium_term_volatility: medium_vol,
             long_term_volatility: long_vol,
-            volatility_trend: trend,
+            estimator,
+            sample_count,
+            confidence,
             impact_assessment: impact,
-            recommended_adjustments: VolatilityAdjustments {
-                spread_multiplier,
-                position_size_factor,
-                execution_urgency,
-            },
+            recommended_adjustments: VolatilityAdjustments::for_impact(impact, &trend),
+            volatility_trend: trend,
+            pool: None,
         }
     }
 }
@@ -0,0 +1,92 @@
+//! Volatility-driven cycle intervals and their bounds
+
+mod common;
+
+use aero_arb_mm_bot::config::Config;
+use aero_arb_mm_bot::network::{adaptive_poll_interval, CycleTrigger};
+use aero_arb_mm_bot::types::{
+    ExecutionUrgency, VolatilityAdjustments, VolatilityConfidence, VolatilityEstimator, VolatilityImpact, VolatilityMetrics, VolatilityTrend,
+};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::time::Duration;
+use common::load;
+
+/// Mainnet settings, so watch-only bounds don't apply
+fn mainnet(extra: &str) -> Config {
+    load(&format!("{}[networks]\nalchemy_api_key = \"test\"\n", extra)).unwrap()
+}
+
+fn volatility(short_term_volatility: Decimal, impact_assessment: VolatilityImpact, volatility_trend: VolatilityTrend) -> VolatilityMetrics {
+    VolatilityMetrics {
+        short_term_volatility,
+        medium_term_volatility: short_term_volatility,
+        long_term_volatility: short_term_volatility,
+        estimator: VolatilityEstimator::Realized,
+        sample_count: 60,
+        confidence: VolatilityConfidence::Full,
+        volatility_trend,
+        impact_assessment,
+        recommended_adjustments: VolatilityAdjustments {
+            spread_multiplier: dec!(1),
+            position_size_factor: dec!(1),
+            execution_urgency: ExecutionUrgency::Normal,
+        },
+        pool: None,
+    }
+}
+
+#[test]
+fn interval_follows_the_volatility_band() {
+    let config = mainnet("");
+    let at = |impact, trend| adaptive_poll_interval(&volatility(dec!(1), impact, trend), &config);
+
+    assert_eq!(at(VolatilityImpact::Extreme, VolatilityTrend::Volatile), Duration::from_millis(500));
+    assert_eq!(at(VolatilityImpact::High, VolatilityTrend::Volatile), Duration::from_secs(1));
+    assert_eq!(at(VolatilityImpact::Moderate, VolatilityTrend::Stable), Duration::from_secs(2));
+    assert_eq!(at(VolatilityImpact::Low, VolatilityTrend::Stable), Duration::from_secs(10));
+    assert_eq!(at(VolatilityImpact::Low, VolatilityTrend::Increasing), Duration::from_secs(6));
+
+    // No estimate yet: the configured interval
+    let warming_up = VolatilityMetrics {
+        sample_count: 3,
+        confidence: VolatilityConfidence::Low,
+        ..volatility(dec!(0), VolatilityImpact::Moderate, VolatilityTrend::Volatile)
+    };
+    assert_eq!(adaptive_poll_interval(&warming_up, &config), Duration::from_secs(2));
+    // A measured flat market backs off
+    let flat = volatility(dec!(0), VolatilityImpact::Low, VolatilityTrend::Stable);
+    assert_eq!(adaptive_poll_interval(&flat, &config), Duration::from_secs(10));
+
+    let fixed = mainnet("[monitoring]\nadaptive_polling = false\n");
+    let extreme = volatility(dec!(1), VolatilityImpact::Extreme, VolatilityTrend::Volatile);
+    assert_eq!(adaptive_poll_interval(&extreme, &fixed), Duration::from_secs(2));
+    assert_eq!(fixed.slowest_poll_interval(), Duration::from_secs(2));
+}
+
+#[test]
+fn bounds_are_validated_and_raised_in_watch_only_mode() {
+    let config = mainnet("[monitoring]\nmin_poll_interval_ms = 250\nmax_poll_interval_ms = 30000\n");
+    assert_eq!(config.min_poll_interval_ms, 250);
+    assert_eq!(config.slowest_poll_interval(), Duration::from_secs(30));
+
+    assert!(load("[monitoring]\nmin_poll_interval_ms = 0\n").is_err());
+    assert!(load("[monitoring]\nmin_poll_interval_ms = 5000\nmax_poll_interval_ms = 1000\n").is_err());
+
+    // The public RPC never polls faster than every 10 seconds
+    let watch_only = load("").unwrap();
+    assert!(watch_only.watch_only);
+    assert_eq!(watch_only.min_poll_interval_ms, 10_000);
+    assert_eq!(watch_only.max_poll_interval_ms, 10_000);
+}
+
+#[tokio::test]
+async fn interval_triggers_take_the_new_period() {
+    let mut trigger = CycleTrigger::interval(2);
+    assert!(trigger.set_poll_interval(Duration::from_millis(500)));
+    assert!(!trigger.set_poll_interval(Duration::from_millis(500)));
+    match &trigger {
+        CycleTrigger::Interval(interval) => assert_eq!(interval.period(), Duration::from_millis(500)),
+        CycleTrigger::Blocks { .. } => panic!("expected an interval trigger"),
+    }
+}
@@ -0,0 +1,131 @@
+//! DEX/CEX basis statistics and the z-score arbitrage gate
+
+mod common;
+
+use aero_arb_mm_bot::arbitrage::{
+    BasisStats, BasisTracker, DEFAULT_BASIS_WINDOW_SECS, DEFAULT_MIN_PRICE_DIFF_PCT, MIN_BASIS_SAMPLES,
+};
+use aero_arb_mm_bot::config::Config;
+use aero_arb_mm_bot::strategy::{ArbitrageStrategy, MarketState, PoolState, Strategy};
+use aero_arb_mm_bot::types::{CexQuotes, FairValueQuotes, PriceBasis, VolatilityMetrics};
+use aero_arb_mm_bot::volatility::MultiTimeframeVolatilityCalculator;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::time::{Duration, Instant};
+use common::{load, weth_usdc_pool};
+
+#[tokio::test]
+async fn the_basis_is_scored_against_its_own_history() {
+    let tracker = BasisTracker::new();
+    let window = Duration::from_secs(DEFAULT_BASIS_WINDOW_SECS);
+    let start = Instant::now();
+    // The DEX trades $3 above a moving CEX, give or take 50 cents
+    let mut stats = None;
+    for i in 0..40u64 {
+        let cex = dec!(3000) + Decimal::from((i * 7) % 11);
+        let dex = cex + if i % 2 == 0 { dec!(3.5) } else { dec!(2.5) };
+        stats = Some(tracker.record_at("WETH/USDC", dex, cex, window, start + Duration::from_secs(i)).await);
+        if (i as usize) < MIN_BASIS_SAMPLES {
+            assert!(stats.as_ref().unwrap().z_score.is_none());
+        }
+    }
+    assert!(stats.unwrap().correlation.unwrap() > 0.9);
+
+    let usual = tracker.record_at("WETH/USDC", dec!(3003.25), dec!(3000), window, start + Duration::from_secs(40)).await;
+    assert_eq!((usual.samples, usual.mean, usual.stdev), (40, dec!(3), dec!(0.5)));
+    assert!((usual.z_score.unwrap() - dec!(0.5)).abs() < dec!(0.0001));
+    assert!(!usual.signals(dec!(2)));
+    assert!(usual.signals(dec!(0)));
+
+    let wide = tracker.record_at("WETH/USDC", dec!(3010), dec!(3000), window, start + Duration::from_secs(41)).await;
+    assert!(wide.z_score.unwrap() > dec!(10));
+    assert!(wide.signals(dec!(2)));
+
+    // Samples leave the window, and pools are tracked separately
+    let later = tracker.record_at("WETH/USDC", dec!(3003), dec!(3000), window, start + window + Duration::from_secs(45)).await;
+    assert_eq!(later.samples, 0);
+    let other = tracker.record_at("WETH/USDbC", dec!(3003), dec!(3000), window, start).await;
+    assert_eq!((other.samples, other.z_score), (0, None));
+}
+
+fn basis(z_score: Option<Decimal>) -> BasisStats {
+    BasisStats { basis: dec!(-30), mean: dec!(-29), stdev: dec!(1), samples: 60, z_score, correlation: None }
+}
+
+fn market<'a>(config: &'a Config, volatility: &'a VolatilityMetrics) -> MarketState<'a> {
+    MarketState {
+        eth_usd: dec!(3000),
+        gas_cost_usd: dec!(0.05),
+        gas_baseline_usd: dec!(0.05),
+        oracle_price: None,
+        volatility,
+        config,
+    }
+}
+
+/// Opportunities the arbitrage strategy finds with the DEX 1% under the CEX
+async fn opportunities(market: &MarketState<'_>, stats: Option<BasisStats>) -> usize {
+    let pool = weth_usdc_pool();
+    let fair_values = FairValueQuotes::new(CexQuotes { last: dec!(3000), book: None }, None);
+    let state = PoolState {
+        pool: &pool,
+        dex_price: dec!(2970),
+        quote_usd: dec!(1),
+        cex_price: dec!(3000),
+        fair_values: &fair_values,
+        price_basis: PriceBasis::CexLast,
+        fair_value: dec!(3000),
+        price_diff_pct: dec!(1),
+        trade_size: dec!(1),
+        quote: None,
+        reserves: None,
+        liquidity_depth: None,
+        health: None,
+        basis: stats.as_ref(),
+    };
+    ArbitrageStrategy.evaluate(&state, market).await.unwrap().len()
+}
+
+#[tokio::test]
+async fn a_z_score_threshold_gates_arbitrage() {
+    let config = load("[strategies.arbitrage]\nbasis_z_threshold = 2\n").unwrap();
+    let volatility = MultiTimeframeVolatilityCalculator::new().get_volatility_metrics().await;
+    let market = market(&config, &volatility);
+
+    // A 1% gap this pool usually has is not an opportunity
+    assert_eq!(opportunities(&market, Some(basis(Some(dec!(-1))))).await, 0);
+    assert_eq!(opportunities(&market, Some(basis(Some(dec!(-2.5))))).await, 1);
+    // Without enough history the fixed threshold applies
+    assert_eq!(opportunities(&market, Some(basis(None))).await, 1);
+    assert_eq!(opportunities(&market, None).await, 1);
+}
+
+#[test]
+fn basis_settings_are_validated_and_reloadable() {
+    let mut config = load("").unwrap();
+    assert_eq!((config.basis_z_threshold, config.basis_window_secs), (dec!(0), DEFAULT_BASIS_WINDOW_SECS));
+    let changed = config.apply_reloadable(&load("[strategies.arbitrage]\nbasis_z_threshold = 2.5\nbasis_window_secs = 900\n").unwrap());
+    assert_eq!(changed, vec!["basis_z_threshold", "basis_window_secs"]);
+    assert!(load("[strategies.arbitrage]\nbasis_z_threshold = -1\n").is_err());
+    assert!(load("[strategies.arbitrage]\nbasis_window_secs = 0\n").is_err());
+}
+
+#[tokio::test]
+async fn the_price_difference_threshold_is_configurable_and_calibrates_to_basis_noise() {
+    let volatility = MultiTimeframeVolatilityCalculator::new().get_volatility_metrics().await;
+    let noisy = BasisStats { stdev: dec!(20), ..basis(None) };
+
+    let fixed = load("[strategies.arbitrage]\nmin_price_diff_pct = 1.5\n").unwrap();
+    assert_eq!(opportunities(&market(&fixed, &volatility), None).await, 0);
+
+    // Two standard deviations of a $20 basis is 1.33% at $3000
+    let auto = load("[strategies.arbitrage]\nauto_min_price_diff = true\n").unwrap();
+    assert_eq!(auto.min_price_diff_pct_for(Some(&noisy), dec!(3000)).round_dp(2), dec!(1.33));
+    assert_eq!(opportunities(&market(&auto, &volatility), Some(noisy.clone())).await, 0);
+    assert_eq!(opportunities(&market(&auto, &volatility), Some(basis(None))).await, 1);
+    // Too little history falls back to the configured minimum
+    let young = BasisStats { samples: MIN_BASIS_SAMPLES - 1, ..noisy };
+    assert_eq!(auto.min_price_diff_pct_for(Some(&young), dec!(3000)), DEFAULT_MIN_PRICE_DIFF_PCT);
+
+    assert!(load("[strategies.arbitrage]\nmin_price_diff_pct = -0.1\n").is_err());
+}
@@ -0,0 +1,24 @@
+//! BotError crosses threads without unsafe impls
+
+use aero_arb_mm_bot::errors::BotError;
+use rust_decimal_macros::dec;
+
+fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+#[test]
+fn bot_error_is_send_and_sync() {
+    assert_send_sync::<BotError>();
+    // Usable as the error of an anyhow chain shared between tasks
+    assert_send_sync::<anyhow::Error>();
+}
+
+#[tokio::test]
+async fn price_validation_error_moves_across_tasks() {
+    let error = BotError::PriceValidation {
+        feed: "Binance ETHUSDT".to_string(),
+        price: dec!(-1),
+        reason: "Price outside valid range".to_string(),
+    };
+    let message = tokio::spawn(async move { error.to_string() }).await.unwrap();
+    assert_eq!(message, "Price validation failed: Binance ETHUSDT price $-1 is invalid - Price outside valid range");
+}
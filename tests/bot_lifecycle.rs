@@ -0,0 +1,38 @@
+//! Embedded bot startup and handle lifecycle
+
+mod common;
+
+use aero_arb_mm_bot::config::Config;
+use aero_arb_mm_bot::{storage, Bot};
+use rust_decimal_macros::dec;
+use common::load;
+
+fn test_config() -> Config {
+    load("").unwrap()
+}
+
+#[tokio::test]
+async fn invalid_config_fails_before_connecting() {
+    let mut config = test_config();
+    config.trade_size_eth = dec!(0);
+
+    let handle = Bot::new(config).with_dry_run(true).run();
+    assert!(storage::is_dry_run());
+
+    let err = handle.wait().await.unwrap_err();
+    assert!(err.to_string().contains("Trade size out of bounds"), "{}", err);
+}
+
+#[tokio::test]
+async fn shutdown_after_exit_is_harmless() {
+    let mut config = test_config();
+    config.trade_size_eth = dec!(1000000);
+
+    let handle = Bot::new(config).with_dry_run(true).run();
+    while !handle.is_finished() {
+        tokio::task::yield_now().await;
+    }
+    handle.shutdown();
+    handle.control().resume();
+    assert!(handle.wait().await.is_err());
+}
@@ -0,0 +1,96 @@
+//! Shutdown state saved and restored across restarts
+
+use aero_arb_mm_bot::storage::{
+    load_bot_state_from, recorded_cex_history, save_bot_state_to, save_price_samples_in, HistorySnapshot, PriceHistory,
+    PriceSample, PriceSource,
+};
+use aero_arb_mm_bot::portfolio::PaperPortfolio;
+use aero_arb_mm_bot::types::{BotState, SessionCounters};
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+#[test]
+fn state_file_round_trips() {
+    let dir = std::env::temp_dir().join(format!("bot_state_{}", std::process::id()));
+    let path = dir.join("state.json");
+    assert!(load_bot_state_from(&path).unwrap().is_none());
+
+    let state = BotState {
+        saved_at: chrono::Utc::now(),
+        counters: SessionCounters {
+            total_opportunities: 12,
+            total_potential_profit_usd: dec!(3.5),
+            ..Default::default()
+        },
+        volatility_history: HistorySnapshot {
+            samples: vec![(1_700_000_000_000, 3000.0), (1_700_000_002_000, 3001.5)],
+            newest_spilled_ms: None,
+        },
+        last_cex_price: Some(dec!(3001.5)),
+        last_dex_prices: HashMap::from([("WETH/USDC".to_string(), dec!(3002))]),
+        paper_portfolio: Some(PaperPortfolio::new(dec!(1), dec!(3000), dec!(3000))),
+    };
+    save_bot_state_to(&path, &state).unwrap();
+
+    let loaded = load_bot_state_from(&path).unwrap().unwrap();
+    assert_eq!(loaded.counters.total_opportunities, 12);
+    assert_eq!(loaded.counters.total_potential_profit_usd, dec!(3.5));
+    assert_eq!(loaded.volatility_history.samples, state.volatility_history.samples);
+    assert_eq!(loaded.last_dex_prices["WETH/USDC"], dec!(3002));
+    assert_eq!(loaded.paper_portfolio.unwrap().start.value_usd(), dec!(6000));
+
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn restored_history_keeps_the_volatility_window() {
+    let window = Duration::from_secs(3600);
+    let mut history = PriceHistory::in_memory(window);
+    let start = SystemTime::now() - Duration::from_secs(100);
+    for i in 0..20u64 {
+        history.push(start + Duration::from_secs(i), 3000.0 + i as f64);
+    }
+
+    let mut restored = PriceHistory::in_memory(window);
+    restored.restore(&history.snapshot());
+    assert_eq!(restored.memory_len(), 20);
+
+    let before: Vec<f64> = history.samples().into_iter().map(|(_, p)| p).collect();
+    let after: Vec<f64> = restored.samples().into_iter().map(|(_, p)| p).collect();
+    assert_eq!(before, after);
+}
+
+#[test]
+fn recorded_prices_fill_the_gap_after_a_snapshot() {
+    let dir = std::env::temp_dir().join(format!("state_prices_{}", std::process::id()));
+    let now = chrono::Utc::now();
+    let at = |secs_ago: i64, source: PriceSource, market: &str, price| PriceSample {
+        timestamp: now - chrono::Duration::seconds(secs_ago),
+        source,
+        market: market.to_string(),
+        price,
+    };
+    save_price_samples_in(&dir, &[
+        at(7200, PriceSource::Cex, "ETHUSDT", dec!(2900)),
+        at(30, PriceSource::Cex, "ETHUSDT", dec!(3000)),
+        at(20, PriceSource::Dex, "WETH/USDC", dec!(3001)),
+        at(20, PriceSource::Cex, "BTCUSDT", dec!(90000)),
+        at(10, PriceSource::Cex, "ETHUSDT", dec!(3010)),
+    ]).unwrap();
+
+    let recorded = recorded_cex_history(&dir, "ETHUSDT", now - chrono::Duration::seconds(3600)).unwrap();
+    assert_eq!(recorded.iter().map(|(_, p)| *p).collect::<Vec<_>>(), vec![3000.0, 3010.0]);
+
+    // Only samples newer than the snapshot's newest are appended
+    let mut snapshot = HistorySnapshot {
+        samples: vec![(recorded[0].0, 3000.0)],
+        newest_spilled_ms: None,
+    };
+    assert_eq!(snapshot.append_newer(recorded.clone()), 1);
+    assert_eq!(snapshot.samples.len(), 2);
+    assert_eq!(HistorySnapshot::default().append_newer(recorded), 2);
+
+    assert!(recorded_cex_history(&dir.join("missing"), "ETHUSDT", now).unwrap().is_empty());
+    std::fs::remove_dir_all(dir).ok();
+}
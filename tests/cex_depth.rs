@@ -0,0 +1,85 @@
+//! CEX order book fills, depth-priced arbitrage and the depth setting
+
+mod common;
+
+use aero_arb_mm_bot::arbitrage::{apply_cex_depth, calculate_arbitrage, FeeModel, DEFAULT_MIN_PRICE_DIFF_PCT};
+use aero_arb_mm_bot::config::DEFAULT_CEX_DEPTH_LEVELS;
+use aero_arb_mm_bot::errors::BotError;
+use aero_arb_mm_bot::network::{FixturePriceSource, PriceSource};
+use aero_arb_mm_bot::types::{BookLevel, OrderBook};
+use rust_decimal_macros::dec;
+use common::load;
+
+fn book() -> OrderBook {
+    let level = |price, qty| BookLevel { price, qty };
+    OrderBook {
+        bids: vec![level(dec!(2999), dec!(0.5)), level(dec!(2998), dec!(1)), level(dec!(2990), dec!(2))],
+        asks: vec![level(dec!(3001), dec!(0.5)), level(dec!(3002), dec!(1))],
+    }
+}
+
+#[test]
+fn fills_walk_the_book() {
+    let book = book();
+    assert_eq!(book.depth(true), dec!(1.5));
+    assert_eq!(book.depth(false), dec!(3.5));
+
+    // Half at the touch, half one level down
+    assert_eq!(book.average_fill_price(false, dec!(1)), Some(dec!(2998.5)));
+    assert_eq!(book.average_fill_price(true, dec!(0.5)), Some(dec!(3001)));
+    // More than the fetched levels hold
+    assert_eq!(book.average_fill_price(true, dec!(2)), None);
+    assert_eq!(book.average_fill_price(true, dec!(0)), None);
+
+    let in_usd = book.scaled(dec!(2));
+    assert_eq!(in_usd.bids[0], BookLevel { price: dec!(5998), qty: dec!(0.5) });
+}
+
+#[test]
+fn depth_reprices_the_cex_leg() {
+    // Buy on Aerodrome at 2970, sell 1 WETH into the bids
+    let mut opportunity = calculate_arbitrage("WETH/USDC", dec!(2970), dec!(3000), dec!(1), None, None, dec!(0.05), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    let quoted_net = opportunity.net_profit_usd;
+    apply_cex_depth(&mut opportunity, &book());
+
+    assert_eq!(opportunity.cex_fill_price, Some(dec!(2998.5)));
+    assert_eq!(opportunity.net_profit_usd, quoted_net - dec!(1.5));
+    assert!(opportunity.roi_pct < dec!(1));
+
+    // A book too thin to fill the size leaves the quote alone
+    let mut large = calculate_arbitrage("WETH/USDC", dec!(2970), dec!(3000), dec!(10), None, None, dec!(0.05), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    let large_net = large.net_profit_usd;
+    apply_cex_depth(&mut large, &book());
+    assert_eq!(large.cex_fill_price, None);
+    assert_eq!(large.net_profit_usd, large_net);
+}
+
+#[tokio::test]
+async fn fixture_books_are_truncated_to_the_requested_levels() {
+    let source = FixturePriceSource::new().with_order_book("ETHUSDC", book());
+
+    let top = source.order_book("ETHUSDC", 1).await.unwrap();
+    assert_eq!(top.bids.len(), 1);
+    assert_eq!(top.asks.len(), 1);
+    assert_eq!(source.order_book("ETHUSDC", 20).await.unwrap(), book());
+    assert!(matches!(source.order_book("AEROUSDC", 20).await, Err(BotError::Network { .. })));
+
+    // A crossed book is rejected like a crossed ticker
+    source.set_order_book("ETHUSDC", OrderBook { asks: vec![BookLevel { price: dec!(2990), qty: dec!(1) }], ..book() });
+    assert!(matches!(source.order_book("ETHUSDC", 20).await, Err(BotError::PriceValidation { .. })));
+}
+
+#[test]
+fn depth_levels_are_configurable() {
+    assert_eq!(load("").unwrap().cex_depth_levels, DEFAULT_CEX_DEPTH_LEVELS);
+    assert_eq!(load("[networks]\ncex_depth_levels = 0\n").unwrap().cex_depth_levels, 0);
+    assert!(load("[networks]\ncex_depth_levels = 10000\n").is_err());
+}
+
+#[test]
+fn depth_levels_reload_without_a_restart() {
+    let mut config = load("").unwrap();
+    let changed = config.apply_reloadable(&load("[networks]\ncex_depth_levels = 50\n").unwrap());
+    assert_eq!(changed, vec!["cex_depth_levels"]);
+    assert_eq!(config.cex_depth_levels, 50);
+}
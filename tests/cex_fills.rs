@@ -0,0 +1,37 @@
+//! Binance testnet order responses and their commissions
+
+use aero_arb_mm_bot::execution::{parse_order_fill, CexOrderSide};
+use rust_decimal_macros::dec;
+use serde_json::json;
+
+fn response(commission: &str, asset: &str) -> serde_json::Value {
+    json!({
+        "orderId": 42,
+        "executedQty": "0.10000000",
+        "cummulativeQuoteQty": "300.00000000",
+        "fills": [{ "price": "3000.00", "qty": "0.10000000", "commission": commission, "commissionAsset": asset }],
+    })
+}
+
+#[test]
+fn buy_commission_in_base_asset_reduces_the_received_quantity() {
+    let fill = parse_order_fill(&response("0.0001", "ETH"), CexOrderSide::Buy, "ETH", "USDT").unwrap();
+    assert_eq!(fill.executed_qty, dec!(0.0999));
+    assert_eq!(fill.avg_price.round_dp(4), (dec!(300) / dec!(0.0999)).round_dp(4));
+    assert_eq!(fill.order_id.as_deref(), Some("42"));
+}
+
+#[test]
+fn quote_commission_is_folded_into_the_price() {
+    let buy = parse_order_fill(&response("0.3", "USDT"), CexOrderSide::Buy, "ETH", "USDT").unwrap();
+    assert_eq!((buy.executed_qty, buy.avg_price), (dec!(0.1), dec!(3003)));
+
+    let sell = parse_order_fill(&response("0.3", "USDT"), CexOrderSide::Sell, "ETH", "USDT").unwrap();
+    assert_eq!((sell.executed_qty, sell.avg_price), (dec!(0.1), dec!(2997)));
+}
+
+#[test]
+fn commission_in_another_asset_leaves_the_fill_unchanged() {
+    let fill = parse_order_fill(&response("0.0002", "BNB"), CexOrderSide::Buy, "ETH", "USDT").unwrap();
+    assert_eq!((fill.executed_qty, fill.avg_price), (dec!(0.1), dec!(3000)));
+}
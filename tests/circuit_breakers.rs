@@ -0,0 +1,73 @@
+//! Circuit breakers scoped to the CEX, the RPC and each pool
+
+use aero_arb_mm_bot::control::control_channel;
+use aero_arb_mm_bot::errors::{BreakerLimits, CircuitBreakerRegistry};
+use aero_arb_mm_bot::types::ControlEvent;
+
+fn registry() -> CircuitBreakerRegistry {
+    CircuitBreakerRegistry::new(
+        BreakerLimits { max_errors: 2, cooldown_secs: 60 },
+        BreakerLimits { max_errors: 5, cooldown_secs: 300 },
+        BreakerLimits { max_errors: 1, cooldown_secs: 600 },
+    )
+}
+
+#[tokio::test]
+async fn cex_errors_leave_rpc_and_pools_closed() {
+    let (control_tx, mut control_rx) = control_channel();
+    let breakers = registry().with_control(control_tx);
+
+    assert!(!breakers.cex.record_error().await);
+    assert!(breakers.cex.record_error().await);
+    assert!(!breakers.cex.can_proceed().await);
+    assert!(breakers.rpc.can_proceed().await);
+    assert!(breakers.pool("WETH/USDC").await.can_proceed().await);
+
+    let status = breakers.state().await;
+    assert!(status.cex.open);
+    assert!(status.cex.cooldown_remaining_secs.is_some_and(|secs| secs <= 60));
+    assert!(!status.rpc.open);
+    assert_eq!(status.open_scopes(), vec!["cex".to_string()]);
+
+    match control_rx.recv().await {
+        Some(ControlEvent::BreakerTripped { reason }) => assert!(reason.starts_with("cex:")),
+        other => panic!("expected BreakerTripped, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn pool_breakers_are_independent() {
+    let (control_tx, mut control_rx) = control_channel();
+    let breakers = registry().with_control(control_tx);
+
+    let broken = breakers.pool("AERO/USDC").await;
+    assert!(broken.record_error().await);
+    assert!(!breakers.pool("AERO/USDC").await.can_proceed().await);
+    assert!(breakers.pool("WETH/USDC").await.can_proceed().await);
+
+    let status = breakers.state().await;
+    assert_eq!(status.pools.len(), 2);
+    assert!(status.pools["AERO/USDC"].open);
+    assert!(!status.pools["WETH/USDC"].open);
+    assert_eq!(status.open_scopes(), vec!["pool:AERO/USDC".to_string()]);
+
+    // Pools registered after with_control report trips too
+    match control_rx.recv().await {
+        Some(ControlEvent::BreakerTripped { reason }) => assert!(reason.starts_with("pool:AERO/USDC")),
+        other => panic!("expected BreakerTripped, got {:?}", other),
+    }
+
+    broken.record_success().await;
+    assert!(breakers.state().await.open_scopes().is_empty());
+}
+
+#[test]
+fn breaker_trips_wait_for_the_running_cycle() {
+    let reason = || "test".to_string();
+    assert!(ControlEvent::KillSwitch { reason: reason() }.preempts_cycle());
+    assert!(ControlEvent::Pause { reason: reason() }.preempts_cycle());
+    assert!(!ControlEvent::BreakerTripped { reason: reason() }.preempts_cycle());
+    assert!(!ControlEvent::ExecutionHalted { reason: reason() }.preempts_cycle());
+    assert!(!ControlEvent::ReloadConfig { reason: reason() }.preempts_cycle());
+    assert!(!ControlEvent::Resume.preempts_cycle());
+}
@@ -0,0 +1,27 @@
+//! Slipstream virtual reserves across the full sqrt price range
+
+use aero_arb_mm_bot::pools::{cl_virtual_reserves, ClPoolState};
+use alloy::primitives::{U256, U512};
+
+fn state(sqrt_price_x96: U256, liquidity: u128) -> ClPoolState {
+    ClPoolState { sqrt_price_x96, tick: 0, liquidity }
+}
+
+#[test]
+fn price_one_splits_liquidity_evenly() {
+    let (reserve0, reserve1) = cl_virtual_reserves(&state(U256::from(1u8) << 96, 5_000)).unwrap();
+    assert_eq!((reserve0, reserve1), (U256::from(5_000u64), U256::from(5_000u64)));
+}
+
+#[test]
+fn extreme_prices_do_not_wrap() {
+    // Max u128 liquidity at the largest uint160 sqrt price: the product needs 288 bits
+    let sqrt_price = (U256::from(1u8) << 160) - U256::from(1u8);
+    let (_, reserve1) = cl_virtual_reserves(&state(sqrt_price, u128::MAX)).unwrap();
+    let exact = (U512::from(u128::MAX) * U512::from(sqrt_price)) >> 96;
+    assert_eq!(U512::from(reserve1), exact);
+
+    // A sqrt price past uint160 cannot come from slot0; its reserves do not fit
+    assert!(cl_virtual_reserves(&state(U256::MAX, u128::MAX)).is_err());
+    assert!(cl_virtual_reserves(&state(U256::ZERO, 1)).is_err());
+}
@@ -0,0 +1,58 @@
+//! Subcommand parsing and single-file replay loading
+
+use aero_arb_mm_bot::backtest::load_price_points_from_file;
+use aero_arb_mm_bot::cli::Command;
+use serde_json::json;
+
+fn args(list: &[&str]) -> Vec<String> {
+    list.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn no_command_runs_live_monitoring() {
+    assert_eq!(Command::parse(&[]).unwrap(), Command::Run { dry_run: false });
+    assert_eq!(Command::parse(&args(&["run"])).unwrap(), Command::Run { dry_run: false });
+    assert_eq!(Command::parse(&args(&["run", "--dry-run"])).unwrap(), Command::Run { dry_run: true });
+    assert_eq!(Command::parse(&args(&["--dry-run"])).unwrap(), Command::Run { dry_run: true });
+    assert_eq!(Command::parse(&args(&["--help"])).unwrap(), Command::Help);
+}
+
+#[test]
+fn commands_keep_their_options() {
+    let report = args(&["report", "--date", "2024-05-01"]);
+    assert_eq!(Command::parse(&report).unwrap(), Command::Report(&report[1..]));
+
+    let replay = args(&["replay", "signals.jsonl", "--min-profit", "2"]);
+    assert_eq!(
+        Command::parse(&replay).unwrap(),
+        Command::Replay { file: "signals.jsonl", args: &replay[2..] },
+    );
+
+    assert_eq!(Command::parse(&args(&["validate-config"])).unwrap(), Command::ValidateConfig);
+    assert_eq!(Command::parse(&args(&["pools", "list"])).unwrap(), Command::PoolsList);
+}
+
+#[test]
+fn malformed_commands_are_rejected() {
+    for bad in [&["pools"][..], &["pools", "drop"], &["replay"], &["replay", "--min-profit", "2"],
+                &["validate-config", "--strict"], &["run", "--fast"], &["launch"]] {
+        assert!(Command::parse(&args(bad)).is_err(), "{:?}", bad);
+    }
+}
+
+#[test]
+fn replay_loads_one_file_in_time_order() {
+    let path = std::env::temp_dir().join(format!("cli_replay_{}.jsonl", std::process::id()));
+    let records = [
+        json!({ "id": "b", "timestamp": "2024-05-01T00:01:00Z", "pool": "WETH/USDC", "dex_price": "3001", "cex_price": "3000" }),
+        json!({ "id": "a", "timestamp": "2024-05-01T00:00:00Z", "pool": "WETH/USDC", "dex_price": "2999", "cex_price": "3000" }),
+        json!({ "id": "a", "timestamp": "2024-05-01T00:00:00Z", "pool": "WETH/USDC", "dex_price": "2999", "cex_price": "3000" }),
+    ];
+    let lines: Vec<String> = records.iter().map(|r| r.to_string()).collect();
+    std::fs::write(&path, lines.join("\n") + "\nnot json\n").unwrap();
+
+    let points = load_price_points_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(points.len(), 2);
+    assert!(points[0].timestamp < points[1].timestamp);
+}
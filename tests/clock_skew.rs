@@ -0,0 +1,59 @@
+//! Volatility windows under system clock steps
+
+use aero_arb_mm_bot::storage::{HistorySnapshot, PriceHistory};
+use aero_arb_mm_bot::utils::MonotonicClock;
+use aero_arb_mm_bot::volatility::{MultiTimeframeVolatilityCalculator, VolatilityCalculator};
+use rust_decimal_macros::dec;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+fn millis(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH).unwrap().as_millis() as i64
+}
+
+#[test]
+fn the_clock_labels_instants_from_its_start() {
+    let start = UNIX_EPOCH + Duration::from_secs(1_772_409_600);
+    let clock = MonotonicClock::starting_at(start);
+    let later = Instant::now() + Duration::from_secs(90);
+    assert_eq!(clock.at(later).duration_since(start).unwrap().as_secs(), 90);
+    assert!(clock.now() >= start && clock.now() <= clock.at(later));
+
+    // Started an hour behind the system clock
+    let behind = MonotonicClock::starting_at(SystemTime::now() - Duration::from_secs(3600));
+    assert!((behind.wall_skew_secs() - 3600.0).abs() < 5.0);
+}
+
+#[test]
+fn a_backwards_step_keeps_the_window() {
+    let mut history = PriceHistory::in_memory(Duration::from_secs(300));
+    let start = SystemTime::now() - Duration::from_secs(60);
+    for i in 0..20u64 {
+        history.push(start + Duration::from_secs(i), 100.0 + i as f64);
+    }
+    // The clock stepped back ten minutes
+    history.push(start - Duration::from_secs(600), 120.0);
+    let samples = history.samples();
+    assert_eq!(samples.len(), 21);
+    assert!(samples.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    assert_eq!(history.latest(), Some((start + Duration::from_secs(19), 120.0)));
+    assert!(VolatilityCalculator::new(300).calculate_volatility_percentage(&history).is_some());
+}
+
+#[tokio::test]
+async fn samples_saved_ahead_of_the_clock_are_dropped_on_restore() {
+    let calculator = MultiTimeframeVolatilityCalculator::new();
+    let now = calculator.clock().now();
+    let snapshot = HistorySnapshot {
+        samples: vec![
+            (millis(now - Duration::from_secs(20)), 100.0),
+            (millis(now - Duration::from_secs(10)), 101.0),
+            (millis(now + Duration::from_secs(300)), 102.0),
+        ],
+        newest_spilled_ms: None,
+    };
+    calculator.restore_history(&snapshot).await;
+    calculator.add_price(dec!(103)).await;
+
+    let prices: Vec<f64> = calculator.history_snapshot().await.samples.into_iter().map(|(_, p)| p).collect();
+    assert_eq!(prices, vec![100.0, 101.0, 103.0]);
+}
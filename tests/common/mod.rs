@@ -0,0 +1,70 @@
+//! Fixtures shared by the integration tests
+
+#![allow(dead_code)]
+
+use aero_arb_mm_bot::config::{Config, ConfigError, ConfigFormat, ConfigSource};
+use aero_arb_mm_bot::types::{
+    ExecutionStatus, PoolInfo, PoolKind, TokenPair, TradeExecution, TradeType, USDC_MAINNET, WETH_MAINNET,
+};
+use alloy::primitives::address;
+use chrono::Utc;
+use rust_decimal_macros::dec;
+use std::time::Instant;
+
+/// Config from TOML contents over the defaults
+pub fn load(contents: &str) -> Result<Config, ConfigError> {
+    Config::from_source(&ConfigSource::from_contents("bot.test", contents, ConfigFormat::Toml)?)
+}
+
+/// The Aerodrome WETH/USDC volatile pool on Base
+pub fn weth_usdc_pool() -> PoolInfo {
+    PoolInfo {
+        address: address!("cDAC0d6c6C59727a65F871236188350531885C43"),
+        name: "WETH/USDC".to_string(),
+        token0: WETH_MAINNET,
+        token1: USDC_MAINNET,
+        pair: TokenPair {
+            base: WETH_MAINNET,
+            quote: USDC_MAINNET,
+            base_symbol: "WETH".to_string(),
+            quote_symbol: "USDC".to_string(),
+            base_decimals: 18,
+            quote_decimals: 6,
+            cex_symbol: "ETHUSDC".to_string(),
+        },
+        is_stable: false,
+        kind: PoolKind::Basic,
+        fee: dec!(0.003),
+        min_liquidity: dec!(1000),
+        last_update: Instant::now(),
+    }
+}
+
+/// A simulated buy-on-DEX execution with nothing measured; tests set the
+/// fields they check with struct update syntax
+pub fn simulated_execution() -> TradeExecution {
+    TradeExecution {
+        id: "execution".to_string(),
+        opportunity_id: "opportunity".to_string(),
+        timestamp: Utc::now(),
+        network: "Base Sepolia".to_string(),
+        trade_type: TradeType::BuyDexSellCex,
+        status: ExecutionStatus::Simulated,
+        tx_hash: None,
+        gas_used: None,
+        gas_price_gwei: None,
+        execution_time_ms: 0,
+        expected_profit_usd: dec!(8.5),
+        actual_profit_usd: None,
+        slippage_bps: None,
+        error_message: None,
+        dex_fill_price: None,
+        cex_fill_price: None,
+        cex_order_id: None,
+        volatility_impact: None,
+        simulated: true,
+        preflight: None,
+        swap_bounds: None,
+        min_amount_out: None,
+    }
+}
@@ -0,0 +1,136 @@
+//! Config file parsing and field validation
+
+use aero_arb_mm_bot::config::{Config, ConfigError, ConfigFormat, ConfigSource};
+use rust_decimal_macros::dec;
+
+const TOML: &str = r#"
+[pools]
+enabled = ["WETH/USDC"]
+
+[strategies.arbitrage]
+trade_size_eth = 0.25
+
+[strategies.market_making]
+base_spread_bps = 40
+
+[networks.execution]
+confirmation_tiers = ["0:1", "1000:2"]
+"#;
+
+const YAML: &str = r#"
+pools:
+  enabled: [WETH/USDC]
+strategies:
+  arbitrage:
+    trade_size_eth: 0.25
+  market_making:
+    base_spread_bps: 40
+networks:
+  execution:
+    confirmation_tiers: "0:1,1000:2"
+"#;
+
+fn load(contents: &str, format: ConfigFormat) -> Result<Config, ConfigError> {
+    let source = ConfigSource::from_contents("bot.test", contents, format)?;
+    Config::from_source(&source)
+}
+
+#[test]
+fn toml_and_yaml_nested_sections_load_the_same_config() {
+    for config in [load(TOML, ConfigFormat::Toml).unwrap(), load(YAML, ConfigFormat::Yaml).unwrap()] {
+        assert_eq!(config.trade_size_eth, dec!(0.25));
+        assert_eq!(config.base_spread_bps, 40);
+        assert_eq!(config.confirmation_tiers, vec![(dec!(0), 1), (dec!(1000), 2)]);
+        assert!(config.pool_enabled("WETH/USDC"));
+        assert!(!config.pool_enabled("CL100-WETH/USDC"));
+    }
+}
+
+#[test]
+fn unknown_field_is_named() {
+    let err = load("[strategies.arbitrage]\ntrade_size = 0.1\n", ConfigFormat::Toml).unwrap_err();
+    assert!(matches!(err, ConfigError::UnknownField { ref field, .. } if field == "strategies.arbitrage.trade_size"));
+}
+
+#[test]
+fn invalid_value_names_field_instead_of_defaulting() {
+    let err = load("[strategies.arbitrage]\ntrade_size_eth = \"0.1eth\"\n", ConfigFormat::Toml).unwrap_err();
+    assert!(matches!(err, ConfigError::InvalidValue { ref field, .. } if field == "TRADE_SIZE_ETH"));
+    assert!(err.to_string().contains("strategies.arbitrage.trade_size_eth"));
+}
+
+#[test]
+fn out_of_range_limits_are_rejected_instead_of_clamped() {
+    for (contents, field) in [
+        ("[strategies.arbitrage]\ntrade_size_eth = 50\n", "TRADE_SIZE_ETH"),
+        ("[strategies.arbitrage]\ntrade_size_eth = 0.001\n", "TRADE_SIZE_ETH"),
+        ("[strategies.arbitrage]\nmin_profit_usd = 0.01\n", "MIN_PROFIT_USD"),
+        ("[strategies.market_making]\nbase_spread_bps = 500\n", "BASE_SPREAD_BPS"),
+        ("[networks.execution]\nmax_gas_price_gwei = 0\n", "MAX_GAS_PRICE_GWEI"),
+        ("[networks.execution]\nslippage_tolerance_bps = 150\n", "SLIPPAGE_TOLERANCE_BPS"),
+    ] {
+        let err = load(contents, ConfigFormat::Toml).unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { field: ref f, .. } if f == field), "{contents}: {err}");
+    }
+
+    let config = load("[strategies.arbitrage]\ntrade_size_eth = 10\n[networks.execution]\nmax_gas_price_gwei = 200\n", ConfigFormat::Toml).unwrap();
+    assert_eq!((config.trade_size_eth, config.max_gas_price_gwei), (dec!(10), 200));
+}
+
+#[test]
+fn content_only_source_ignores_private_key_in_env() {
+    // SAFETY: no test in this binary reads PRIVATE_KEY through an env-backed source
+    unsafe { std::env::set_var("PRIVATE_KEY", "0x01") };
+    let config = load("", ConfigFormat::Toml).unwrap();
+    assert!(config.private_key.is_none());
+}
+
+#[test]
+fn unknown_pool_is_rejected() {
+    let err = load("[pools]\nenabled = [\"WETH/DAI\"]\n", ConfigFormat::Toml).unwrap_err();
+    assert!(matches!(err, ConfigError::InvalidValue { ref field, .. } if field == "POOLS"));
+}
+
+#[test]
+fn custom_pools_and_cex_symbol_overrides() {
+    let config = load(r#"
+[pools]
+enabled = ["vAMM-cbETH/WETH"]
+custom = ["vAMM-cbETH/WETH=0x44Ecc644449fC3a9858d2007CaA8CFAa4C561f91"]
+cex_symbols = ["cbETH/WETH=CBETHETH"]
+"#, ConfigFormat::Toml).unwrap();
+    assert_eq!(config.custom_pools.len(), 1);
+    assert!(config.pool_enabled("vAMM-cbETH/WETH"));
+    assert_eq!(config.cex_symbol("cbETH", "WETH"), "CBETHETH");
+    assert_eq!(config.cex_symbol("AERO", "USDC"), "AEROUSDC");
+
+    let err = load("[pools]\ncustom = [\"AERO/USDC=not-an-address\"]\n", ConfigFormat::Toml).unwrap_err();
+    assert!(matches!(err, ConfigError::InvalidValue { ref field, .. } if field == "CUSTOM_POOLS"));
+}
+
+#[test]
+fn monitor_trigger_selects_block_subscription() {
+    use aero_arb_mm_bot::network::MonitorTrigger;
+
+    let config = load("[monitoring]\ntrigger = \"blocks\"\nws_rpc_url = \"wss://example.org\"\n", ConfigFormat::Toml).unwrap();
+    assert_eq!(config.monitor_trigger, MonitorTrigger::Blocks);
+    assert_eq!(config.ws_rpc_url.as_deref(), Some("wss://example.org"));
+
+    let err = load("[monitoring]\ntrigger = \"mempool\"\n", ConfigFormat::Toml).unwrap_err();
+    assert!(matches!(err, ConfigError::InvalidValue { ref field, .. } if field == "MONITOR_TRIGGER"));
+}
+
+#[test]
+fn fee_model_uses_maker_rate_and_dex_override() {
+    use aero_arb_mm_bot::arbitrage::FeeModel;
+
+    let config = load("[fees]\nbinance_maker_bps = 2\nbinance_maker_orders = true\ndex_bps = 5\ntransfer_cost_usd = 1.5\n", ConfigFormat::Toml).unwrap();
+    let fees = FeeModel::from_config(&config, dec!(0.003));
+    assert_eq!(fees, FeeModel { cex_fee_rate: dec!(0.0002), dex_fee_rate: dec!(0.0005), transfer_cost_usd: dec!(1.5) });
+
+    let defaults = FeeModel::from_config(&load("", ConfigFormat::Toml).unwrap(), dec!(0.003));
+    assert_eq!((defaults.cex_fee_rate, defaults.dex_fee_rate), (dec!(0.001), dec!(0.003)));
+
+    let err = load("[fees]\ntransfer_cost_usd = -1\n", ConfigFormat::Toml).unwrap_err();
+    assert!(matches!(err, ConfigError::InvalidValue { ref field, .. } if field == "TRANSFER_COST_USD"));
+}
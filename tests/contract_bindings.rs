@@ -0,0 +1,54 @@
+//! Typed contract calldata and return decoding
+
+mod common;
+
+use aero_arb_mm_bot::execution::{
+    router_swap_calldata, selector, AERODROME_SWAP_SIGNATURE, UNISWAP_V2_SWAP_SIGNATURE,
+};
+use aero_arb_mm_bot::network::{IAerodromePool, IAerodromeRouter, IERC20, IUniswapV2Router};
+use aero_arb_mm_bot::types::{SwapBounds, AERODROME_POOL_FACTORY_MAINNET, USDC_MAINNET, WETH_MAINNET};
+use alloy::primitives::{address, U256};
+use alloy::sol_types::{SolCall, SolValue};
+use rust_decimal_macros::dec;
+use common::weth_usdc_pool;
+
+const BOUNDS: SwapBounds = SwapBounds { slippage_bps: 50, deadline_secs: 120 };
+
+#[test]
+fn router_selectors_match_the_policy_whitelist() {
+    assert_eq!(IAerodromeRouter::swapExactTokensForTokensCall::SELECTOR, selector(AERODROME_SWAP_SIGNATURE));
+    assert_eq!(IUniswapV2Router::swapExactTokensForTokensCall::SELECTOR, selector(UNISWAP_V2_SWAP_SIGNATURE));
+    assert_eq!(IERC20::balanceOfCall::SELECTOR, selector("balanceOf(address)"));
+    assert_eq!(IAerodromePool::getAmountOutCall::SELECTOR, selector("getAmountOut(uint256,address)"));
+}
+
+#[test]
+fn router_calldata_decodes_back_to_the_swap() {
+    let to = address!("1111111111111111111111111111111111111111");
+    let data = router_swap_calldata(&weth_usdc_pool(), true, dec!(1), dec!(3000), to, BOUNDS).unwrap();
+
+    let call = IAerodromeRouter::swapExactTokensForTokensCall::abi_decode(&data, true).unwrap();
+    assert_eq!(call.amountIn, U256::from(3_000_000_000u64));
+    assert_eq!(call.amountOutMin, U256::from(995_000_000_000_000_000u64));
+    assert_eq!(call.to, to);
+    assert_eq!(call.routes.len(), 1);
+    assert_eq!(call.routes[0].from, USDC_MAINNET);
+    assert_eq!(call.routes[0].to, WETH_MAINNET);
+    assert!(!call.routes[0].stable);
+    assert_eq!(call.routes[0].factory, AERODROME_POOL_FACTORY_MAINNET);
+}
+
+#[test]
+fn return_values_decode_into_named_fields() {
+    let output = (U256::from(10u64), U256::from(30_000u64), U256::from(1_700_000_000u64)).abi_encode_params();
+    let reserves = IAerodromePool::getReservesCall::abi_decode_returns(&output, true).unwrap();
+    assert_eq!(reserves.reserve0, U256::from(10u64));
+    assert_eq!(reserves.reserve1, U256::from(30_000u64));
+
+    let output = U256::from(6u8).abi_encode();
+    assert_eq!(IERC20::decimalsCall::abi_decode_returns(&output, true).unwrap()._0, 6);
+
+    // Values that do not fit the declared return type are rejected
+    let output = U256::from(256u16).abi_encode();
+    assert!(IERC20::decimalsCall::abi_decode_returns(&output, true).is_err());
+}
@@ -0,0 +1,89 @@
+//! Daily summary reports built from the JSONL outputs
+
+use aero_arb_mm_bot::storage::{build_daily_report, render_daily_report_markdown};
+use chrono::NaiveDate;
+use rust_decimal_macros::dec;
+use serde_json::json;
+use std::path::Path;
+
+fn write_jsonl(dir: &Path, file: &str, records: &[serde_json::Value]) {
+    let path = dir.join(file);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let lines: Vec<String> = records.iter().map(|r| r.to_string()).collect();
+    std::fs::write(path, lines.join("\n") + "\n").unwrap();
+}
+
+fn volatility(short: &str, long: &str) -> serde_json::Value {
+    json!({ "short_term_volatility": short, "long_term_volatility": long })
+}
+
+fn stress(eth_down_pnl: &str, pct: &str) -> serde_json::Value {
+    json!({ "stress_tests": [{ "scenario": "EthDown10", "pnl_usd": eth_down_pnl, "pnl_pct": pct }] })
+}
+
+#[test]
+fn report_aggregates_a_days_outputs() {
+    let dir = std::env::temp_dir().join(format!("daily_report_{}", std::process::id()));
+    let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+
+    write_jsonl(&dir, "opportunities/arbitrage_2026-03-02.jsonl", &[
+        json!({ "id": "a", "pool": "WETH/USDC", "net_profit_usd": "4.5", "volatility_assessment": volatility("2", "1") }),
+        json!({ "id": "b", "pool": "WETH/USDC", "net_profit_usd": "1.5", "volatility_assessment": volatility("4", "3") }),
+        json!({ "id": "c", "pool": "cbBTC/USDC", "net_profit_usd": "-0.5", "volatility_assessment": volatility("6", "2") }),
+    ]);
+    write_jsonl(&dir, "market_making/signals_2026-03-02.jsonl", &[
+        json!({ "pool": "WETH/USDC", "effective_spread_bps": "20", "risk_metrics": stress("-120", "-4") }),
+        json!({ "pool": "WETH/USDC", "effective_spread_bps": "30", "risk_metrics": stress("-80", "-2.5") }),
+    ]);
+    write_jsonl(&dir, "executions/trades_2026-03-02.jsonl", &[
+        json!({ "opportunity_id": "a", "status": "Success", "expected_profit_usd": "4.5", "actual_profit_usd": "4" }),
+        json!({ "opportunity_id": "b", "status": "Failed", "expected_profit_usd": "1.5", "actual_profit_usd": "-0.25" }),
+        json!({ "opportunity_id": "c", "status": "Simulated", "expected_profit_usd": "-0.5", "actual_profit_usd": "-1" }),
+    ]);
+    std::fs::create_dir_all(dir.join("decisions")).unwrap();
+    std::fs::write(
+        dir.join("decisions/decisions_2026-03-02.jsonl"),
+        "{\"outcome\":\"Queued\"}\n{\"outcome\":\"Rejected\"}\n{\"outcome\":\"Rejected\"}\nnot json\n",
+    ).unwrap();
+
+    let report = build_daily_report(&dir, date).unwrap();
+    assert_eq!(report.opportunities, 3);
+    assert_eq!(report.potential_profit_usd, dec!(5.5));
+    assert_eq!(report.best_opportunity_usd, Some(dec!(4.5)));
+    assert_eq!(report.market_making_signals, 2);
+    assert_eq!(report.mean_spread_bps, Some(dec!(25)));
+    assert_eq!((report.executions, report.successful_executions, report.failed_executions), (3, 2, 1));
+    assert_eq!(report.realized_pnl_usd, dec!(2.75));
+    assert_eq!(report.execution_success_rate_pct.unwrap().round_dp(2), dec!(66.67));
+    assert_eq!(report.best_pool.as_deref(), Some("WETH/USDC"));
+    assert_eq!(report.worst_pool.as_deref(), Some("cbBTC/USDC"));
+    assert_eq!(report.pools[0].realized_pnl_usd, dec!(3.75));
+    assert_eq!(report.decisions["Rejected"], 2);
+    assert_eq!(report.unreadable_lines, 1);
+
+    let volatility = report.volatility.as_ref().unwrap();
+    assert_eq!(volatility.samples, 3);
+    assert_eq!(volatility.mean_short_term_pct, dec!(4));
+    assert_eq!(volatility.max_short_term_pct, dec!(6));
+    assert_eq!(volatility.mean_long_term_pct, dec!(2));
+
+    let stress = &report.stress_tests[0];
+    assert_eq!((stress.scenario.as_str(), stress.samples), ("EthDown10", 2));
+    assert_eq!((stress.worst_pnl_usd, stress.worst_pnl_pct, stress.last_pnl_usd), (dec!(-120), dec!(-4), dec!(-80)));
+
+    let markdown = render_daily_report_markdown(&report);
+    assert!(markdown.starts_with("# Daily Report 2026-03-02"));
+    assert!(markdown.contains("| Best pool | WETH/USDC |"));
+    assert!(markdown.contains("| EthDown10 | 2 | $-120 (-4%) | $-80 |"));
+
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn day_without_outputs_is_empty() {
+    let dir = std::env::temp_dir().join(format!("daily_report_empty_{}", std::process::id()));
+    let report = build_daily_report(&dir, NaiveDate::from_ymd_opt(2026, 3, 3).unwrap()).unwrap();
+    assert_eq!(report.opportunities, 0);
+    assert!(report.execution_success_rate_pct.is_none());
+    assert!(report.best_pool.is_none() && report.volatility.is_none());
+}
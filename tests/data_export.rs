@@ -0,0 +1,89 @@
+//! CSV and Parquet export of the daily JSONL outputs
+
+use aero_arb_mm_bot::arbitrage::{calculate_arbitrage, FeeModel, DEFAULT_MIN_PRICE_DIFF_PCT};
+use aero_arb_mm_bot::storage::{export_dataset, ExportDataset, ExportFormat};
+use arrow::array::{Array, Float64Array, StringArray, TimestampMillisecondArray};
+use chrono::{NaiveDate, TimeZone, Utc};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use rust_decimal_macros::dec;
+use std::path::PathBuf;
+
+fn data_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("export_{}_{}", name, std::process::id()));
+    let mut opportunity = calculate_arbitrage("WETH/USDC", dec!(2990.5), dec!(3000), dec!(1), None, None, dec!(1), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    opportunity.id = "opp-1".to_string();
+    opportunity.timestamp = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+    let mut second = opportunity.clone();
+    second.id = "opp-2".to_string();
+    second.pool = "vAMM-AERO/USDC, custom".to_string();
+
+    std::fs::create_dir_all(dir.join("opportunities")).unwrap();
+    let lines = [&opportunity, &second].map(|o| serde_json::to_string(o).unwrap());
+    std::fs::write(
+        dir.join("opportunities/arbitrage_2026-03-02.jsonl"),
+        format!("{}\nnot json\n{}\n", lines[0], lines[1]),
+    ).unwrap();
+    dir
+}
+
+fn date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()
+}
+
+#[test]
+fn formats_and_datasets_parse_from_cli_names() {
+    assert_eq!("parquet".parse::<ExportFormat>(), Ok(ExportFormat::Parquet));
+    assert!("xlsx".parse::<ExportFormat>().is_err());
+    assert_eq!("signals".parse::<ExportDataset>(), Ok(ExportDataset::Signals));
+    assert_eq!(ExportDataset::Executions.to_string(), "executions");
+}
+
+#[test]
+fn csv_keeps_decimal_text_and_quotes_commas() {
+    let dir = data_dir("csv");
+    let out = dir.join("exports");
+    let export = export_dataset(ExportDataset::Opportunities, ExportFormat::Csv, &dir, date(), &out).unwrap().unwrap();
+    assert_eq!(export.rows, 2);
+    assert_eq!(export.unreadable_lines, 1);
+    assert_eq!(export.path, out.join("opportunities_2026-03-02.csv"));
+
+    let csv = std::fs::read_to_string(&export.path).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("id,timestamp,pool,direction,dex_price,cex_price,"));
+    assert!(lines[1].starts_with("opp-1,2026-03-02T09:00:00Z,WETH/USDC,"));
+    assert!(lines[1].contains(",2990.5,3000,"));
+    assert!(lines[2].starts_with("opp-2,2026-03-02T09:00:00Z,\"vAMM-AERO/USDC, custom\","));
+
+    // A day without records exports nothing
+    let missing = NaiveDate::from_ymd_opt(2026, 3, 3).unwrap();
+    assert!(export_dataset(ExportDataset::Opportunities, ExportFormat::Csv, &dir, missing, &out).unwrap().is_none());
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn parquet_columns_are_typed() {
+    let dir = data_dir("parquet");
+    let out = dir.join("exports");
+    let export = export_dataset(ExportDataset::Opportunities, ExportFormat::Parquet, &dir, date(), &out).unwrap().unwrap();
+    assert_eq!(export.path, out.join("opportunities_2026-03-02.parquet"));
+
+    let file = std::fs::File::open(&export.path).unwrap();
+    let batches: Vec<_> = ParquetRecordBatchReaderBuilder::try_new(file).unwrap().build().unwrap()
+        .collect::<Result<_, _>>().unwrap();
+    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 2);
+    let batch = &batches[0];
+
+    let column = |name: &str| batch.column(batch.schema().index_of(name).unwrap()).clone();
+    let pools = column("pool");
+    let pools = pools.as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!(pools.value(1), "vAMM-AERO/USDC, custom");
+    let prices = column("dex_price");
+    assert_eq!(prices.as_any().downcast_ref::<Float64Array>().unwrap().value(0), 2990.5);
+    let timestamps = column("timestamp");
+    let timestamps = timestamps.as_any().downcast_ref::<TimestampMillisecondArray>().unwrap();
+    assert_eq!(timestamps.value(0), Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap().timestamp_millis());
+    // No CEX book was priced
+    assert!(column("cex_fill_price").is_null(0));
+    std::fs::remove_dir_all(dir).ok();
+}
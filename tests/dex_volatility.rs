@@ -0,0 +1,55 @@
+//! Per-pool DEX and DEX/CEX basis volatility
+
+use aero_arb_mm_bot::types::{VolatilityConfidence, VolatilityImpact};
+use aero_arb_mm_bot::volatility::MultiTimeframeVolatilityCalculator;
+use rust_decimal_macros::dec;
+use std::time::{Duration, SystemTime};
+
+#[tokio::test]
+async fn a_jumpy_pool_widens_its_own_metrics_only() {
+    let calculator = MultiTimeframeVolatilityCalculator::new();
+    let start = SystemTime::now() - Duration::from_secs(20);
+    for i in 0..20u64 {
+        let at = start + Duration::from_secs(i);
+        let cex = if i % 2 == 0 { dec!(3000) } else { dec!(3000.03) };
+        calculator.add_price_at(at, cex).await;
+        // The DEX swings 1% around a CEX that barely moves
+        let jumpy = if i % 2 == 0 { dec!(3000) } else { dec!(3030) };
+        calculator.add_dex_price_at("WETH/USDC", at, jumpy, cex).await;
+        calculator.add_dex_price_at("WETH/USDbC", at, cex, cex).await;
+    }
+
+    let cex = calculator.get_volatility_metrics().await;
+    assert_eq!(cex.impact_assessment, VolatilityImpact::Low);
+    assert!(cex.pool.is_none());
+
+    let jumpy = calculator.pool_volatility("WETH/USDC").await.unwrap();
+    assert_eq!((jumpy.sample_count, jumpy.confidence), (20, VolatilityConfidence::Partial));
+    assert!(jumpy.dex_volatility > cex.short_term_volatility * dec!(10));
+    assert!(jumpy.basis_volatility > cex.short_term_volatility * dec!(10));
+    let metrics = calculator.get_pool_volatility_metrics("WETH/USDC").await;
+    assert_eq!(metrics.impact_assessment, VolatilityImpact::Extreme);
+    assert_eq!(metrics.recommended_adjustments.spread_multiplier, dec!(3.0));
+    assert_eq!(metrics.pool.as_ref(), Some(&jumpy));
+
+    // A pool tracking the CEX keeps the CEX band, with no basis volatility
+    let tracking = calculator.get_pool_volatility_metrics("WETH/USDbC").await;
+    assert_eq!(tracking.impact_assessment, VolatilityImpact::Low);
+    assert_eq!(tracking.pool.unwrap().basis_volatility, dec!(0));
+}
+
+#[tokio::test]
+async fn unmeasured_pools_leave_the_cex_metrics_alone() {
+    let calculator = MultiTimeframeVolatilityCalculator::new();
+    assert!(calculator.pool_volatility("WETH/USDC").await.is_none());
+
+    calculator.add_dex_price("WETH/USDC", dec!(3000), dec!(3000)).await;
+    calculator.add_dex_price("WETH/USDC", dec!(3300), dec!(3000)).await;
+    let pool = calculator.pool_volatility("WETH/USDC").await.unwrap();
+    assert_eq!((pool.sample_count, pool.confidence), (2, VolatilityConfidence::Low));
+
+    let cex = calculator.get_volatility_metrics().await;
+    let metrics = cex.for_pool(Some(pool));
+    assert_eq!(metrics.impact_assessment, cex.impact_assessment);
+    assert_eq!(metrics.recommended_adjustments.spread_multiplier, cex.recommended_adjustments.spread_multiplier);
+}
@@ -0,0 +1,16 @@
+//! Dry-run mode skips storage writes
+
+use aero_arb_mm_bot::storage::{is_dry_run, set_dry_run, skip_write};
+use serde_json::json;
+
+#[test]
+fn writes_are_skipped_only_in_dry_run() {
+    let record = json!({ "id": "a", "net_profit_usd": "1.5" });
+    assert!(!is_dry_run());
+    assert!(!skip_write("output/opportunities/arbitrage.jsonl", &record));
+
+    set_dry_run(true);
+    assert!(skip_write("output/opportunities/arbitrage.jsonl", &record));
+    set_dry_run(false);
+    assert!(!skip_write("output/opportunities/arbitrage.jsonl", &record));
+}
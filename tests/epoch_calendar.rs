@@ -0,0 +1,37 @@
+//! Aerodrome weekly epoch calendar
+
+use aero_arb_mm_bot::market_making::{epoch_position, epoch_start, EPOCH_DURATION_SECS};
+use aero_arb_mm_bot::types::EpochPhase;
+
+/// Thursday 2024-01-04 00:00 UTC, an epoch flip
+const FLIP: u64 = 1_704_326_400;
+const HOUR: u64 = 3600;
+
+#[test]
+fn epochs_start_on_thursdays_at_midnight_utc() {
+    assert_eq!(FLIP % EPOCH_DURATION_SECS, 0);
+    assert_eq!(epoch_start(FLIP + 3 * 24 * HOUR), FLIP);
+    assert_eq!(epoch_start(FLIP - 1), FLIP - EPOCH_DURATION_SECS);
+}
+
+#[test]
+fn phases_follow_the_flip_window() {
+    let after = epoch_position(FLIP + HOUR, 2 * HOUR);
+    assert_eq!(after.phase, EpochPhase::PostFlip);
+    assert_eq!((after.epoch_start, after.secs_since_flip), (FLIP, HOUR));
+    assert!(after.is_near_flip());
+
+    let middle = epoch_position(FLIP + 3 * 24 * HOUR, 2 * HOUR);
+    assert_eq!(middle.phase, EpochPhase::Mid);
+    assert!(!middle.is_near_flip());
+
+    let before = epoch_position(FLIP - HOUR, 2 * HOUR);
+    assert_eq!(before.phase, EpochPhase::PreFlip);
+    assert_eq!(before.secs_until_flip, HOUR);
+}
+
+#[test]
+fn zero_window_never_counts_as_near_a_flip() {
+    assert_eq!(epoch_position(FLIP, 0).phase, EpochPhase::Mid);
+    assert_eq!(epoch_position(FLIP - 1, 0).phase, EpochPhase::Mid);
+}
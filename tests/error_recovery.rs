@@ -0,0 +1,69 @@
+//! Configurable recovery strategies and error count decay
+
+mod common;
+
+use aero_arb_mm_bot::config::ConfigError;
+use aero_arb_mm_bot::errors::{BotError, ErrorRecovery, RecoveryAction, RecoveryStrategy};
+use std::time::Duration;
+use tracing::Level;
+use common::load;
+
+fn network_error() -> BotError {
+    BotError::Network { message: "timeout".to_string(), source: None, retry_count: 0 }
+}
+
+fn parse_error() -> BotError {
+    BotError::DataParsing { context: "ticker".to_string(), source: anyhow::anyhow!("bad json") }
+}
+
+#[test]
+fn strategies_parse_per_error_class() {
+    let config = load(r#"
+[recovery]
+strategies = ["network_timeout=retry:2:250", "parse_error=shutdown:corrupt feed", "invalid_price=skip:debug"]
+error_decay_secs = 60
+"#).unwrap();
+    assert_eq!(config.recovery_strategies, vec![
+        ("network_timeout".to_string(), RecoveryStrategy::Retry { max_attempts: 2, delay_ms: 250 }),
+        ("parse_error".to_string(), RecoveryStrategy::Shutdown { reason: "corrupt feed".to_string() }),
+        ("invalid_price".to_string(), RecoveryStrategy::Skip { log_level: Level::DEBUG }),
+    ]);
+    assert_eq!(config.recovery_error_decay_secs, 60);
+
+    for spec in ["network_timeout=retry:2", "unknown_class=skip", "parse_error=explode"] {
+        let err = load(&format!("[recovery]\nstrategies = \"{}\"\n", spec)).unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { ref field, .. } if field == "RECOVERY_STRATEGIES"), "{}", spec);
+    }
+}
+
+#[tokio::test]
+async fn configured_strategies_override_the_builtin_table() {
+    let config = load("[recovery]\nstrategies = \"network_timeout=retry:2:250,parse_error=shutdown:corrupt feed\"\n").unwrap();
+    let recovery = ErrorRecovery::from_config(&config);
+
+    for _ in 0..2 {
+        assert!(matches!(recovery.handle_error(&network_error(), "test").await,
+            RecoveryAction::Retry { delay } if delay == Duration::from_millis(250)));
+    }
+    assert!(matches!(recovery.handle_error(&network_error(), "test").await, RecoveryAction::Escalate));
+    assert!(matches!(recovery.handle_error(&parse_error(), "test").await,
+        RecoveryAction::Shutdown { ref reason } if reason == "corrupt feed"));
+    // Classes without an override keep the built-in strategy
+    assert_eq!(recovery.recovery_strategies["contract_error"],
+        RecoveryStrategy::Fallback { alternative_source: "backup_pool".to_string() });
+}
+
+#[tokio::test]
+async fn error_counts_reset_after_a_quiet_period() {
+    let recovery = ErrorRecovery::new()
+        .with_strategies([("network_timeout".to_string(), RecoveryStrategy::Retry { max_attempts: 1, delay_ms: 0 })])
+        .with_error_decay(Duration::from_millis(50));
+
+    assert!(matches!(recovery.handle_error(&network_error(), "test").await, RecoveryAction::Retry { .. }));
+    assert!(matches!(recovery.handle_error(&network_error(), "test").await, RecoveryAction::Escalate));
+    assert_eq!(recovery.error_count("network_timeout").await, 2);
+
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    assert_eq!(recovery.error_count("network_timeout").await, 0);
+    assert!(matches!(recovery.handle_error(&network_error(), "test").await, RecoveryAction::Retry { .. }));
+}
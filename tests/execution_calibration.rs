@@ -0,0 +1,70 @@
+//! Simulator success rates and slippage calibrated from recorded executions
+
+mod common;
+
+use aero_arb_mm_bot::execution::{ExecutionCalibration, PRIOR_WEIGHT};
+use aero_arb_mm_bot::storage::load_trade_executions_since;
+use aero_arb_mm_bot::types::{ExecutionStatus, TradeExecution, VolatilityImpact};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use common::simulated_execution;
+
+fn execution(impact: VolatilityImpact, status: ExecutionStatus, actual: Option<Decimal>, simulated: bool) -> TradeExecution {
+    TradeExecution {
+        id: uuid::Uuid::new_v4().to_string(),
+        status,
+        expected_profit_usd: dec!(10),
+        actual_profit_usd: actual,
+        volatility_impact: Some(impact),
+        simulated,
+        ..simulated_execution()
+    }
+}
+
+#[test]
+fn without_observations_the_fixed_table_applies() {
+    let calibration = ExecutionCalibration::default();
+    assert!((calibration.success_rate(VolatilityImpact::Low) - 0.95).abs() < 1e-9);
+    assert_eq!(calibration.slippage_bps(VolatilityImpact::Low), 25);
+    assert_eq!(calibration.slippage_bps(VolatilityImpact::Extreme), 175);
+}
+
+#[test]
+fn submitted_executions_move_their_band() {
+    let prior_weight = PRIOR_WEIGHT as usize;
+    // As many failures as the prior is worth halve a 95% band
+    let mut executions: Vec<TradeExecution> = (0..prior_weight)
+        .map(|_| execution(VolatilityImpact::Low, ExecutionStatus::Failed, None, false))
+        .collect();
+    // Half the expected profit is a 5000 bps shortfall
+    executions.extend((0..prior_weight).map(|_| execution(VolatilityImpact::High, ExecutionStatus::Success, Some(dec!(5)), false)));
+    // The simulator's own output never feeds back
+    executions.extend((0..100).map(|_| execution(VolatilityImpact::Moderate, ExecutionStatus::Failed, None, true)));
+
+    let calibration = ExecutionCalibration::from_executions(&executions);
+    assert!((calibration.success_rate(VolatilityImpact::Low) - 0.475).abs() < 1e-9);
+    assert_eq!(calibration.slippage_bps(VolatilityImpact::Low), 25);
+    assert_eq!(calibration.slippage_bps(VolatilityImpact::High), (100 + 5000) / 2);
+    assert!((calibration.success_rate(VolatilityImpact::High) - 0.85).abs() < 1e-9);
+    assert!((calibration.success_rate(VolatilityImpact::Moderate) - 0.85).abs() < 1e-9);
+    assert_eq!(calibration.observed(), 2 * PRIOR_WEIGHT as u64);
+}
+
+#[test]
+fn recorded_executions_load_from_the_daily_files() {
+    let dir = std::env::temp_dir().join(format!("executions_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let record = serde_json::to_string(&execution(VolatilityImpact::Low, ExecutionStatus::Success, Some(dec!(9)), false)).unwrap();
+    std::fs::write(dir.join("trades_2026-03-01.jsonl"), format!("{}\n", record)).unwrap();
+    std::fs::write(dir.join("trades_2026-03-02.jsonl"), format!("{}\nnot json\n", record)).unwrap();
+
+    let since = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+    let executions = load_trade_executions_since(&dir, since).unwrap();
+    assert_eq!(executions.len(), 1);
+    assert_eq!(executions[0].actual_profit_usd, Some(dec!(9)));
+
+    let calibration = ExecutionCalibration::from_executions(&executions);
+    assert_eq!(calibration.outcomes(VolatilityImpact::Low).shortfall_samples, 1);
+    std::fs::remove_dir_all(dir).ok();
+}
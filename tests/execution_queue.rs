@@ -0,0 +1,103 @@
+//! Execution queue priorities and scheduling order
+
+use aero_arb_mm_bot::arbitrage::{calculate_arbitrage, FeeModel, DEFAULT_MIN_PRICE_DIFF_PCT};
+use aero_arb_mm_bot::execution::{opportunity_priority, ExecutionJob, PendingExecutions};
+use aero_arb_mm_bot::types::{
+    ArbitrageOpportunity, ExecutionPriority, ExecutionUrgency, PoolInfo, PoolKind, TokenPair,
+    VolatilityAdjustments, VolatilityConfidence, VolatilityEstimator, VolatilityImpact, VolatilityMetrics, VolatilityTrend,
+    USDC_MAINNET, WETH_MAINNET,
+};
+use alloy::primitives::address;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::time::Instant;
+
+fn opportunity(net_profit_usd: Decimal) -> ArbitrageOpportunity {
+    let mut opportunity = calculate_arbitrage("WETH/USDC", dec!(2990), dec!(3000), dec!(1), None, None, dec!(1), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    opportunity.net_profit_usd = net_profit_usd;
+    opportunity
+}
+
+fn volatility(urgency: ExecutionUrgency) -> VolatilityMetrics {
+    VolatilityMetrics {
+        short_term_volatility: dec!(0.3),
+        medium_term_volatility: dec!(0.3),
+        long_term_volatility: dec!(0.3),
+        estimator: VolatilityEstimator::Realized,
+        sample_count: 60,
+        confidence: VolatilityConfidence::Full,
+        volatility_trend: VolatilityTrend::Stable,
+        impact_assessment: VolatilityImpact::Low,
+        recommended_adjustments: VolatilityAdjustments {
+            spread_multiplier: dec!(1),
+            position_size_factor: dec!(1),
+            execution_urgency: urgency,
+        },
+        pool: None,
+    }
+}
+
+fn pool(name: &str) -> PoolInfo {
+    PoolInfo {
+        address: address!("cDAC0d6c6C59727a65F871236188350531885C43"),
+        name: name.to_string(),
+        token0: WETH_MAINNET,
+        token1: USDC_MAINNET,
+        pair: TokenPair {
+            base: WETH_MAINNET,
+            quote: USDC_MAINNET,
+            base_symbol: "WETH".to_string(),
+            quote_symbol: "USDC".to_string(),
+            base_decimals: 18,
+            quote_decimals: 6,
+            cex_symbol: "ETHUSDC".to_string(),
+        },
+        is_stable: false,
+        kind: PoolKind::Basic,
+        fee: dec!(0.003),
+        min_liquidity: dec!(1000),
+        last_update: Instant::now(),
+    }
+}
+
+fn job(name: &str, priority: ExecutionPriority, net_profit_usd: Decimal) -> ExecutionJob {
+    ExecutionJob::new(opportunity(net_profit_usd), pool(name), volatility(ExecutionUrgency::Normal), priority)
+}
+
+#[test]
+fn priority_follows_urgency_then_profit_multiple() {
+    let normal = volatility(ExecutionUrgency::Normal);
+    assert_eq!(opportunity_priority(&opportunity(dec!(1)), &volatility(ExecutionUrgency::Fast), dec!(5)), ExecutionPriority::Immediate);
+    assert_eq!(opportunity_priority(&opportunity(dec!(100)), &volatility(ExecutionUrgency::Cautious), dec!(5)), ExecutionPriority::Low);
+    assert_eq!(opportunity_priority(&opportunity(dec!(25)), &normal, dec!(5)), ExecutionPriority::High);
+    assert_eq!(opportunity_priority(&opportunity(dec!(10)), &normal, dec!(5)), ExecutionPriority::Medium);
+    assert_eq!(opportunity_priority(&opportunity(dec!(6)), &normal, dec!(5)), ExecutionPriority::Low);
+    assert_eq!(opportunity_priority(&opportunity(dec!(6)), &normal, dec!(0)), ExecutionPriority::Medium);
+}
+
+#[test]
+fn rank_orders_priorities() {
+    let ranks = [
+        ExecutionPriority::Immediate,
+        ExecutionPriority::High,
+        ExecutionPriority::Medium,
+        ExecutionPriority::Low,
+        ExecutionPriority::Hold,
+    ].map(|priority| priority.rank());
+    assert!(ranks.windows(2).all(|pair| pair[0] > pair[1]));
+}
+
+#[test]
+fn pending_jobs_pop_by_priority_then_profit_then_arrival() {
+    let mut pending = PendingExecutions::new();
+    pending.push(job("low", ExecutionPriority::Low, dec!(50)));
+    pending.push(job("medium-first", ExecutionPriority::Medium, dec!(10)));
+    pending.push(job("medium-richer", ExecutionPriority::Medium, dec!(20)));
+    pending.push(job("medium-second", ExecutionPriority::Medium, dec!(10)));
+    pending.push(job("immediate", ExecutionPriority::Immediate, dec!(1)));
+    assert_eq!(pending.len(), 5);
+
+    let order: Vec<String> = std::iter::from_fn(|| pending.pop()).map(|job| job.pool.name).collect();
+    assert_eq!(order, ["immediate", "medium-richer", "medium-first", "medium-second", "low"]);
+    assert!(pending.is_empty());
+}
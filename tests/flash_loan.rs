@@ -0,0 +1,87 @@
+//! Flash-loan planning, fees and lender calldata
+
+mod common;
+
+use aero_arb_mm_bot::arbitrage::{calculate_arbitrage, FeeModel, DEFAULT_MIN_PRICE_DIFF_PCT};
+use aero_arb_mm_bot::config::ConfigError;
+use aero_arb_mm_bot::execution::{
+    check_flash_loan_profit, check_flash_loan_repayment, encode_flash_loan, flash_loan_params, plan_flash_loan, repay_amount, selector,
+    FlashLoanSource, TransactionPolicy, AAVE_FLASH_LOAN_SIGNATURE, BALANCER_FLASH_LOAN_SIGNATURE,
+};
+use aero_arb_mm_bot::types::{AAVE_V3_POOL_BASE, BALANCER_VAULT, USDC_MAINNET, WETH_MAINNET};
+use alloy::primitives::{address, U256};
+use alloy::rpc::types::eth::TransactionRequest;
+use rust_decimal_macros::dec;
+use common::{load, weth_usdc_pool};
+
+const RECEIVER: alloy::primitives::Address = address!("2222222222222222222222222222222222222222");
+
+#[test]
+fn buying_on_the_dex_borrows_the_quote_token() {
+    let opp = calculate_arbitrage("WETH/USDC", dec!(2990), dec!(3000), dec!(1), None, None, dec!(1), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    let plan = plan_flash_loan(FlashLoanSource::Aave, &opp, &weth_usdc_pool()).unwrap();
+
+    assert_eq!(plan.asset, USDC_MAINNET);
+    assert_eq!(plan.amount, U256::from(2_990_000_000u64));
+    // 5 bps of $2,990
+    assert_eq!(plan.fee_usd, dec!(1.495));
+    assert_eq!(plan.net_profit_usd, opp.net_profit_usd - dec!(1.495));
+    assert_eq!(repay_amount(&plan), U256::from(2_991_495_000u64));
+
+    let balancer = plan_flash_loan(FlashLoanSource::Balancer, &opp, &weth_usdc_pool()).unwrap();
+    assert_eq!(balancer.fee_usd, dec!(0));
+    assert_eq!(repay_amount(&balancer), balancer.amount);
+}
+
+#[test]
+fn a_dex_leg_closed_on_the_cex_cannot_repay_the_loan() {
+    for (dex, cex) in [(dec!(2990), dec!(3000)), (dec!(3010), dec!(3000))] {
+        let opp = calculate_arbitrage("WETH/USDC", dex, cex, dec!(1), None, None, dec!(1), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+        let plan = plan_flash_loan(FlashLoanSource::Balancer, &opp, &weth_usdc_pool()).unwrap();
+        assert_ne!(plan.proceeds_asset, plan.asset);
+        // Profitable with the CEX leg, but nothing on-chain returns the borrowed token
+        assert!(check_flash_loan_profit(&plan, dec!(1)).is_ok());
+        assert!(check_flash_loan_repayment(&plan).unwrap_err().to_string().contains("cannot be repaid"));
+    }
+}
+
+#[test]
+fn flash_fee_can_make_an_opportunity_unprofitable() {
+    let opp = calculate_arbitrage("WETH/USDC", dec!(2997), dec!(3000), dec!(1), None, None, dec!(1), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    let plan = plan_flash_loan(FlashLoanSource::Aave, &opp, &weth_usdc_pool()).unwrap();
+    assert!(check_flash_loan_profit(&plan, dec!(1)).is_err());
+
+    let plan = plan_flash_loan(FlashLoanSource::Balancer, &opp, &weth_usdc_pool()).unwrap();
+    assert!(check_flash_loan_profit(&plan, dec!(1)).is_ok());
+}
+
+#[test]
+fn lender_calldata_is_whitelisted_by_the_policy() {
+    let opp = calculate_arbitrage("WETH/USDC", dec!(3010), dec!(3000), dec!(1), None, None, dec!(1), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    let mut policy = TransactionPolicy::with_limits(500_000, U256::ZERO, None);
+
+    for (source, lender, signature) in [
+        (FlashLoanSource::Aave, AAVE_V3_POOL_BASE, AAVE_FLASH_LOAN_SIGNATURE),
+        (FlashLoanSource::Balancer, BALANCER_VAULT, BALANCER_FLASH_LOAN_SIGNATURE),
+    ] {
+        // Selling on the DEX borrows the WETH to sell
+        let plan = plan_flash_loan(source, &opp, &weth_usdc_pool()).unwrap();
+        assert_eq!(plan.asset, WETH_MAINNET);
+        let data = encode_flash_loan(&plan, RECEIVER, flash_loan_params(vec![0xab; 4], repay_amount(&plan)));
+        assert_eq!(data[..4], selector(signature));
+
+        let tx = TransactionRequest::default().to(lender).input(data.into()).gas_limit(450_000);
+        assert!(policy.check(&tx).is_err());
+        policy.allow(source.lender(), selector(source.signature()));
+        assert!(policy.check(&tx).is_ok());
+    }
+}
+
+#[test]
+fn flash_loan_settings_are_rejected() {
+    let err = load("[networks.execution]\nflash_loan_source = \"aave\"\n").unwrap_err();
+    assert!(matches!(err, ConfigError::InvalidValue { ref field, .. } if field == "FLASH_LOAN_SOURCE"));
+
+    let err = load("[networks.execution]\nflash_loan_receiver = \"0x2222222222222222222222222222222222222222\"\n").unwrap_err();
+    assert!(matches!(err, ConfigError::InvalidValue { ref field, .. } if field == "FLASH_LOAN_RECEIVER"));
+}
@@ -0,0 +1,47 @@
+//! Fork simulation settings and fill prices of fork-mined swaps
+
+mod common;
+
+use aero_arb_mm_bot::execution::{ForkSwap, SimulationMode};
+use rust_decimal_macros::dec;
+use common::load;
+
+#[test]
+fn simulation_mode_is_configured_by_name() {
+    let config = load("").unwrap();
+    assert_eq!(config.simulation_mode, SimulationMode::Random);
+    assert_eq!(config.anvil_fork_url, None);
+
+    let fork = load(concat!(
+        "[monitoring]\nsimulation_mode = \"Fork\"\n",
+        "[networks.execution]\nanvil_fork_url = \"http://127.0.0.1:8545\"\n",
+    )).unwrap();
+    assert_eq!(fork.simulation_mode, SimulationMode::Fork);
+    assert_eq!(fork.anvil_fork_url.as_deref(), Some("http://127.0.0.1:8545"));
+    assert_eq!(fork.simulation_mode.to_string(), "fork");
+
+    assert!(load("[monitoring]\nsimulation_mode = \"replay\"\n").is_err());
+}
+
+#[test]
+fn fill_price_is_quote_per_base() {
+    let swap = ForkSwap {
+        tx_hash: "0x01".to_string(),
+        reverted: false,
+        amount_in: dec!(3000),
+        amount_out: dec!(0.98),
+        min_amount_out: Some(dec!(0.97)),
+        gas_used: 150_000,
+        gas_price_gwei: dec!(0.01),
+    };
+    // Buying base: 3000 USDC paid for 0.98 WETH
+    assert_eq!(swap.fill_price(true).unwrap().round_dp(2), dec!(3061.22));
+
+    // Selling base: 1 WETH sold for 2950 USDC
+    let sell = ForkSwap { amount_in: dec!(1), amount_out: dec!(2950), ..swap.clone() };
+    assert_eq!(sell.fill_price(false), Some(dec!(2950)));
+
+    // Nothing received, no fill price
+    let empty = ForkSwap { amount_out: dec!(0), ..swap };
+    assert_eq!(empty.fill_price(true), None);
+}
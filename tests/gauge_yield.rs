@@ -0,0 +1,74 @@
+//! Gauge emission APR and market-making expected return
+
+use aero_arb_mm_bot::market_making::expected_return;
+use aero_arb_mm_bot::pools::{emission_apr_pct, emission_return_usd, GaugeRewards, SECONDS_PER_YEAR};
+use aero_arb_mm_bot::types::{LiquidityStrategy, RangeBounds, RiskLevel, StrategyType};
+use alloy::primitives::Address;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::time::Duration;
+
+fn rewards(period_finish: u64) -> GaugeRewards {
+    GaugeRewards {
+        gauge: Address::repeat_byte(0x11),
+        reward_rate_aero_per_sec: dec!(0.1),
+        staked_fraction: dec!(0.5),
+        aero_price_usd: dec!(1),
+        period_finish,
+    }
+}
+
+#[test]
+fn apr_is_annual_emissions_over_staked_tvl() {
+    let annual = Decimal::from(SECONDS_PER_YEAR);
+    assert_eq!(emission_apr_pct(dec!(1), dec!(2), annual * dec!(4)), dec!(50));
+    assert_eq!(emission_apr_pct(dec!(1), dec!(2), dec!(0)), dec!(0));
+
+    assert_eq!(emission_return_usd(dec!(1000), dec!(50), Duration::from_secs(SECONDS_PER_YEAR)), dec!(500));
+    assert_eq!(emission_return_usd(dec!(1000), dec!(50), Duration::ZERO), dec!(0));
+}
+
+#[test]
+fn only_the_staked_share_earns_and_emissions_stop_after_the_period() {
+    let tvl = Decimal::from(SECONDS_PER_YEAR) * dec!(0.2);
+    let live = rewards(2_000).emission_yield(tvl, 1_000);
+    assert_eq!(live.staked_tvl_usd, tvl / dec!(2));
+    // 0.1 AERO/s for a year on a tenth of a year's seconds in USD: 100%
+    assert_eq!(live.apr_pct, dec!(100));
+
+    let ended = rewards(2_000).emission_yield(tvl, 3_000);
+    assert_eq!((ended.reward_rate_aero_per_sec, ended.apr_pct), (dec!(0), dec!(0)));
+}
+
+#[test]
+fn expected_return_nets_spread_and_emissions_against_loss() {
+    let strategy = LiquidityStrategy {
+        strategy_type: StrategyType::WideSpread,
+        bid_size_eth: dec!(0.5),
+        ask_size_eth: dec!(0.5),
+        range_bounds: RangeBounds {
+            lower_bound: dec!(2850),
+            upper_bound: dec!(3150),
+            confidence_interval: dec!(0.95),
+        },
+        duration_estimate: Duration::from_secs(86_400),
+        expected_daily_volume: dec!(5),
+        risk_level: RiskLevel::Moderate,
+    };
+
+    let without = expected_return(&strategy, dec!(3000), 20, dec!(0), dec!(1500), dec!(0), dec!(3));
+    // 5 ETH at $3000 earning half of 20 bps
+    assert_eq!(without.spread_capture_usd, dec!(15));
+    assert_eq!(without.emission_usd, dec!(0));
+    assert_eq!(without.net_usd, dec!(12));
+
+    let with = expected_return(&strategy, dec!(3000), 20, dec!(0), dec!(1500), dec!(36.5), dec!(3));
+    assert_eq!(with.emission_usd, dec!(1.5));
+    assert_eq!(with.net_usd, dec!(13.5));
+
+    // A 5 bps pool fee on the $15000 volume comes out of the spread
+    let with_fee = expected_return(&strategy, dec!(3000), 20, dec!(0.0005), dec!(1500), dec!(0), dec!(3));
+    assert_eq!(with_fee.pool_fee_usd, dec!(7.5));
+    assert_eq!(with_fee.spread_capture_usd, dec!(7.5));
+    assert_eq!(with_fee.net_usd, dec!(4.5));
+}
@@ -0,0 +1,23 @@
+//! The running bot's config is the process-wide CONFIG. Kept in its own
+//! test binary so no other bot replaces CONFIG concurrently.
+
+mod common;
+
+use aero_arb_mm_bot::config::Config;
+use aero_arb_mm_bot::{Bot, CONFIG};
+use rust_decimal_macros::dec;
+use common::load;
+
+#[tokio::test]
+async fn running_installs_the_bot_config_globally() {
+    let mut config = load("").unwrap();
+    config.instance_id = "embedded-bot".to_string();
+    config.trade_size_eth = dec!(0);
+
+    let handle = Bot::new(config).with_dry_run(true).run();
+    assert_eq!(CONFIG.get().instance_id, "embedded-bot");
+    assert!(handle.wait().await.is_err());
+
+    // Built-in defaults ignore the environment and are always valid
+    assert!(Config::defaults().config_file.is_none());
+}
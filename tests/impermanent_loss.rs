@@ -0,0 +1,68 @@
+//! Concentrated-range impermanent loss estimates
+
+use aero_arb_mm_bot::market_making::{
+    concentrated_loss, concentration, estimate_impermanent_loss, full_range_loss,
+};
+use aero_arb_mm_bot::types::RangeBounds;
+use rust_decimal_macros::dec;
+use std::time::Duration;
+
+fn range(lower: f64, upper: f64) -> RangeBounds {
+    RangeBounds {
+        lower_bound: rust_decimal::Decimal::try_from(lower).unwrap(),
+        upper_bound: rust_decimal::Decimal::try_from(upper).unwrap(),
+        confidence_interval: dec!(0.95),
+    }
+}
+
+#[test]
+fn full_range_loss_matches_the_closed_form() {
+    assert_eq!(full_range_loss(1.0), 0.0);
+    // Price quadruples: 1 - 2 * 2 / 5
+    assert!((full_range_loss(4.0) - 0.2).abs() < 1e-12);
+    assert!((full_range_loss(0.25) - 0.2).abs() < 1e-12);
+}
+
+#[test]
+fn narrower_ranges_concentrate_and_amplify_loss() {
+    let wide = range(2000.0, 4500.0);
+    let narrow = range(2850.0, 3150.0);
+    assert!(concentration(&narrow, 3000.0) > concentration(&wide, 3000.0));
+    assert!(concentration(&wide, 3000.0) > 1.0);
+
+    assert_eq!(concentrated_loss(&narrow, 3000.0, 3000.0), 0.0);
+    let moved = 3030.0;
+    assert!(concentrated_loss(&narrow, 3000.0, moved) > concentrated_loss(&wide, 3000.0, moved));
+    assert!(concentrated_loss(&wide, 3000.0, moved) > full_range_loss(moved / 3000.0));
+}
+
+#[test]
+fn loss_stops_growing_once_the_price_leaves_the_range() {
+    let bounds = range(2850.0, 3150.0);
+    let at_bound = concentrated_loss(&bounds, 3000.0, 3150.0);
+    let beyond = concentrated_loss(&bounds, 3000.0, 3300.0);
+    // Past the upper bound the position is all quote token, so it only lags the held ETH further
+    assert!(beyond >= at_bound);
+    assert!(at_bound > 0.0);
+}
+
+#[test]
+fn expected_loss_grows_with_horizon_and_volatility() {
+    let bounds = range(2850.0, 3150.0);
+    let estimate = |vol, secs| {
+        estimate_impermanent_loss(&bounds, dec!(3000), vol, Duration::from_secs(secs), dec!(3000))
+    };
+
+    let tight = estimate(dec!(3), 300);
+    let wide = estimate(dec!(3), 3600);
+    let volatile = estimate(dec!(6), 3600);
+    assert!(tight.expected_loss_pct > dec!(0));
+    assert!(wide.expected_loss_pct > tight.expected_loss_pct);
+    assert!(volatile.expected_loss_pct > wide.expected_loss_pct);
+    assert!(wide.expected_loss_pct < wide.range_exit_loss_pct);
+    assert_eq!(wide.expected_loss_usd, dec!(3000) * wide.expected_loss_pct / dec!(100));
+    assert_eq!(wide.horizon_secs, 3600);
+
+    let calm = estimate(dec!(0), 3600);
+    assert_eq!((calm.expected_loss_pct, calm.expected_loss_usd), (dec!(0), dec!(0)));
+}
@@ -0,0 +1,69 @@
+//! Per-source latency histograms and the DEX/CEX price skew budget
+
+mod common;
+
+use aero_arb_mm_bot::config::DEFAULT_MAX_PRICE_SKEW_MS;
+use aero_arb_mm_bot::network::{LatencyHistogram, LatencyRecorder, LATENCY_BUCKETS_MS};
+use std::time::Duration;
+use common::load;
+
+#[test]
+fn quantiles_come_from_bucket_bounds() {
+    let mut histogram = LatencyHistogram::default();
+    assert_eq!(histogram.quantile_ms(0.5), None);
+
+    for ms in [5, 8, 40, 45, 90, 120, 180, 300, 700, 3000] {
+        histogram.record(Duration::from_millis(ms), true);
+    }
+    histogram.record(Duration::from_millis(20_000), false);
+
+    assert_eq!(histogram.count(), 11);
+    assert_eq!(histogram.quantile_ms(0.5), Some(250));
+    assert_eq!(histogram.quantile_ms(0.9), Some(5000));
+    // The overflow bucket reports the slowest request
+    assert_eq!(histogram.quantile_ms(1.0), Some(20_000));
+
+    let summary = histogram.summary();
+    assert_eq!(summary.errors, 1);
+    assert_eq!(summary.max_ms, Some(20_000));
+    assert_eq!(summary.mean_ms, Some(24_488 / 11));
+    assert_eq!(summary.buckets.len(), LATENCY_BUCKETS_MS.len() + 1);
+    assert_eq!(summary.buckets[0].le_ms, Some(10));
+    assert_eq!(summary.buckets[0].count, 2);
+    assert_eq!(summary.buckets.last().unwrap().le_ms, None);
+}
+
+#[test]
+fn quantiles_never_exceed_the_slowest_request() {
+    let mut histogram = LatencyHistogram::default();
+    histogram.record(Duration::from_millis(120), true);
+    assert_eq!(histogram.quantile_ms(0.5), Some(120));
+}
+
+#[tokio::test]
+async fn timed_requests_are_recorded_per_source() {
+    let recorder = LatencyRecorder::default();
+    let ok: Result<u32, String> = recorder.timed("binance", async { Ok(1) }).await;
+    assert_eq!(ok, Ok(1));
+    let failed: Result<u32, String> = recorder.timed("binance", async { Err("timeout".to_string()) }).await;
+    assert!(failed.is_err());
+    recorder.record("rpc", Duration::from_millis(30), true);
+
+    let snapshot = recorder.snapshot();
+    assert_eq!(snapshot.keys().collect::<Vec<_>>(), ["binance", "rpc"]);
+    assert_eq!(snapshot["binance"].count, 2);
+    assert_eq!(snapshot["binance"].errors, 1);
+    assert_eq!(snapshot["rpc"].p50_ms, Some(30));
+}
+
+#[test]
+fn price_skew_budget_is_configurable_and_reloadable() {
+    let mut config = load("").unwrap();
+    assert_eq!(config.max_price_skew_ms, DEFAULT_MAX_PRICE_SKEW_MS);
+
+    let changed = config.apply_reloadable(&load("[strategies]\nmax_price_skew_ms = 0\n").unwrap());
+    assert_eq!(changed, vec!["max_price_skew_ms"]);
+    assert_eq!(config.max_price_skew_ms, 0);
+
+    assert!(load("[strategies]\nmax_price_skew_ms = -5\n").is_err());
+}
@@ -0,0 +1,66 @@
+//! File lease leader election: one holder at a time under the lock file
+
+mod common;
+
+use aero_arb_mm_bot::control::LeaderElection;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use common::load;
+
+fn lease_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("leader_{}_{}", name, std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join("leader.lease")
+}
+
+fn replica(path: &PathBuf, id: &str, lease: Duration) -> LeaderElection {
+    LeaderElection::new(path.clone(), id.to_string(), lease)
+}
+
+#[test]
+fn one_replica_leads_until_it_releases() {
+    let path = lease_path("release");
+    let mut a = replica(&path, "a", Duration::from_secs(60));
+    let mut b = replica(&path, "b", Duration::from_secs(60));
+
+    assert!(a.try_acquire());
+    assert!(!b.try_acquire());
+    // Renewal keeps the lease
+    assert!(a.try_acquire() && a.flag().is_leader());
+
+    a.release();
+    assert!(!a.is_leader());
+    assert!(b.try_acquire());
+    assert!(!a.try_acquire());
+}
+
+#[test]
+fn a_held_lock_keeps_others_off_an_expired_lease() {
+    let path = lease_path("lock");
+    let mut a = replica(&path, "a", Duration::from_millis(1));
+    let mut b = replica(&path, "b", Duration::from_secs(60));
+    assert!(a.try_acquire());
+    std::thread::sleep(Duration::from_millis(5));
+
+    // Another replica is mid-update: the expired lease is not taken
+    let lock = path.with_extension("lock");
+    std::fs::write(&lock, "").unwrap();
+    assert!(!b.try_acquire());
+
+    // A lock older than a lease was left by a crashed replica
+    let old = SystemTime::now() - Duration::from_secs(120);
+    std::fs::File::options().write(true).open(&lock).unwrap().set_modified(old).unwrap();
+    assert!(b.try_acquire());
+    assert!(!lock.exists());
+}
+
+#[test]
+fn leases_outlast_the_longest_execution() {
+    let config = load("[leader]\nlease_path = \"/tmp/leader.lease\"\nlease_secs = 5\n").unwrap();
+    let election = LeaderElection::from_config(&config).unwrap();
+    assert!(election.lease_duration() > config.longest_execution());
+    assert!(election.lease_duration() >= config.slowest_poll_interval() * 3);
+
+    assert!(LeaderElection::from_config(&load("").unwrap()).is_none());
+}
@@ -0,0 +1,44 @@
+//! On-chain TWAP from cumulative reserves and spot divergence
+
+mod common;
+
+use aero_arb_mm_bot::pools::{
+    average_reserves, observation_twap_price, spot_twap_divergence_pct, CumulativeObservation,
+};
+use alloy::primitives::U256;
+use rust_decimal_macros::dec;
+use common::weth_usdc_pool;
+
+fn observation(timestamp: u64, reserve0_cumulative: U256, reserve1_cumulative: U256) -> CumulativeObservation {
+    CumulativeObservation { timestamp, reserve0_cumulative, reserve1_cumulative }
+}
+
+#[test]
+fn cumulative_reserves_average_over_the_span() {
+    let weth = U256::from(10u64).pow(U256::from(18));
+    let usdc = U256::from(1_000_000u64);
+    let start = observation(1_000, U256::ZERO, U256::ZERO);
+    // 10 WETH / 30,000 USDC for 20 minutes, then 20 WETH / 80,000 USDC for 10
+    let end = observation(
+        2_800,
+        weth * U256::from(10 * 1_200 + 20 * 600),
+        usdc * U256::from(30_000 * 1_200 + 80_000 * 600),
+    );
+
+    let (reserve0, reserve1) = average_reserves(&start, &end).unwrap();
+    assert_eq!(reserve0, weth * U256::from(40) / U256::from(3));
+    assert_eq!(reserve1, usdc * U256::from(140_000) / U256::from(3));
+
+    let twap = observation_twap_price(&weth_usdc_pool(), &start, &end).unwrap();
+    assert!((twap - dec!(3500)).abs() < dec!(0.001));
+
+    // No elapsed time, no average
+    assert!(average_reserves(&end, &end).is_none());
+}
+
+#[test]
+fn sharp_spot_moves_diverge_from_the_twap() {
+    assert_eq!(spot_twap_divergence_pct(dec!(3030), dec!(3000)), dec!(1));
+    assert_eq!(spot_twap_divergence_pct(dec!(2850), dec!(3000)), dec!(5));
+    assert!(spot_twap_divergence_pct(dec!(2850), dec!(3000)) > dec!(2));
+}
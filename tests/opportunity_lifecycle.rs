@@ -0,0 +1,144 @@
+//! Opportunity state machine, lifecycle tracking across cycles and the
+//! fill-rate summary
+
+mod common;
+
+use aero_arb_mm_bot::arbitrage::{
+    calculate_arbitrage, FeeModel, LifecycleTracker, OpportunitySighting, DEFAULT_MIN_PRICE_DIFF_PCT,
+};
+use aero_arb_mm_bot::execution::ExecutionResult;
+use aero_arb_mm_bot::storage::{
+    build_lifecycle_report, load_opportunity_lifecycles, save_opportunity_lifecycles_in, summarize_lifecycles,
+};
+use aero_arb_mm_bot::types::{
+    ExecutionStatus, LifecycleEnd, OpportunityStage, TradeExecution,
+};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use common::simulated_execution;
+
+const BUY_DEX: &str = "Buy on Aerodrome, Sell on Binance";
+
+fn at(secs: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap() + Duration::seconds(secs)
+}
+
+fn sighting(id: &str, secs: i64, net_profit_usd: Decimal, stage: OpportunityStage, queued: bool) -> OpportunitySighting {
+    OpportunitySighting {
+        opportunity_id: id.to_string(),
+        direction: BUY_DEX.to_string(),
+        timestamp: at(secs),
+        net_profit_usd,
+        stage,
+        queued,
+    }
+}
+
+fn execution(status: ExecutionStatus, actual_profit_usd: Option<Decimal>) -> TradeExecution {
+    TradeExecution {
+        opportunity_id: "b".to_string(),
+        timestamp: at(5),
+        status,
+        expected_profit_usd: dec!(3),
+        actual_profit_usd,
+        ..simulated_execution()
+    }
+}
+
+#[test]
+fn stages_only_advance_one_step_at_a_time() {
+    let mut opportunity = calculate_arbitrage("WETH/USDC", dec!(2990), dec!(3000), dec!(1), None, None, dec!(1), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    assert_eq!(opportunity.stage, OpportunityStage::Detected);
+    assert!(!opportunity.advance(OpportunityStage::Executed));
+    assert!(opportunity.advance(OpportunityStage::Validated));
+    assert!(!opportunity.advance(OpportunityStage::Validated));
+    assert!(opportunity.advance(OpportunityStage::Executed));
+    assert!(opportunity.advance(OpportunityStage::Settled));
+    assert_eq!(OpportunityStage::Settled.next(), None);
+}
+
+#[test]
+fn a_spread_seen_across_cycles_is_one_lifecycle_until_it_closes() {
+    let mut tracker = LifecycleTracker::new();
+    assert!(tracker.observe("WETH/USDC", &[sighting("a", 0, dec!(1), OpportunityStage::Detected, false)], at(0)).is_empty());
+    assert!(tracker.observe("WETH/USDC", &[sighting("b", 2, dec!(4), OpportunityStage::Validated, false)], at(2)).is_empty());
+    assert!(tracker.observe("WETH/USDC", &[sighting("c", 4, dec!(2), OpportunityStage::Validated, false)], at(4)).is_empty());
+    // Another pool's cycle leaves this one open
+    assert!(tracker.observe("CL100-WETH/USDC", &[], at(5)).is_empty());
+    assert_eq!(tracker.len(), 1);
+
+    let closed = tracker.observe("WETH/USDC", &[], at(6));
+    assert_eq!(closed.len(), 1);
+    let lifecycle = &closed[0];
+    assert_eq!(lifecycle.id, "a");
+    assert_eq!(lifecycle.stage, OpportunityStage::Validated);
+    assert_eq!(lifecycle.end, LifecycleEnd::Closed);
+    assert_eq!(lifecycle.validated_at, Some(at(2)));
+    assert_eq!(lifecycle.sightings, 3);
+    assert_eq!(lifecycle.persisted_ms, 4000);
+    assert_eq!(lifecycle.ended_at, at(6));
+    assert_eq!((lifecycle.detected_net_profit_usd, lifecycle.peak_net_profit_usd, lifecycle.last_net_profit_usd),
+        (dec!(1), dec!(4), dec!(2)));
+    assert!(tracker.is_empty());
+}
+
+#[test]
+fn queued_opportunities_wait_for_their_execution_result() {
+    let mut tracker = LifecycleTracker::new();
+    tracker.observe("WETH/USDC", &[sighting("a", 0, dec!(4), OpportunityStage::Detected, false)], at(0));
+    tracker.observe("WETH/USDC", &[sighting("b", 2, dec!(4), OpportunityStage::Validated, true)], at(2));
+    // The spread closes while the execution is still queued
+    assert!(tracker.observe("WETH/USDC", &[], at(4)).is_empty());
+
+    let result = ExecutionResult::Completed(Box::new(execution(ExecutionStatus::Simulated, Some(dec!(-0.5)))));
+    assert!(tracker.record_execution("unknown", &result, at(6)).is_none());
+    let settled = tracker.record_execution("b", &result, at(6)).unwrap();
+    assert_eq!(settled.stage, OpportunityStage::Settled);
+    assert_eq!(settled.end, LifecycleEnd::Settled);
+    assert_eq!(settled.executed_at, Some(at(5)));
+    assert_eq!(settled.settled_at, Some(at(6)));
+    assert_eq!(settled.execution_id.as_deref(), Some("execution"));
+    assert_eq!(settled.execution_profit_usd, Some(dec!(-0.5)));
+    assert_eq!(settled.profitable_at_execution, Some(false));
+    assert!(tracker.is_empty());
+
+    // An expired job ends at the validated stage; a spread still open afterwards starts over
+    tracker.observe("WETH/USDC", &[sighting("d", 8, dec!(4), OpportunityStage::Validated, true)], at(8));
+    let expired = tracker.record_execution("d", &ExecutionResult::Expired, at(9)).unwrap();
+    assert_eq!((expired.stage, expired.end), (OpportunityStage::Validated, LifecycleEnd::Expired));
+    tracker.observe("WETH/USDC", &[sighting("e", 10, dec!(4), OpportunityStage::Detected, false)], at(10));
+    let interrupted = tracker.drain(at(11));
+    assert_eq!(interrupted.len(), 1);
+    assert_eq!((interrupted[0].id.as_str(), interrupted[0].end), ("e", LifecycleEnd::Interrupted));
+}
+
+#[test]
+fn fill_rate_and_decay_summarize_recorded_lifecycles() {
+    let mut tracker = LifecycleTracker::new();
+    let mut lifecycles = Vec::new();
+    tracker.observe("WETH/USDC", &[sighting("a", 0, dec!(4), OpportunityStage::Validated, true)], at(0));
+    let result = ExecutionResult::Completed(Box::new(execution(ExecutionStatus::Success, Some(dec!(3)))));
+    lifecycles.extend(tracker.record_execution("a", &result, at(1)));
+    tracker.observe("WETH/USDC", &[sighting("b", 2, dec!(2), OpportunityStage::Validated, false)], at(2));
+    tracker.observe("WETH/USDC", &[sighting("c", 4, dec!(2), OpportunityStage::Validated, false)], at(4));
+    lifecycles.extend(tracker.observe("WETH/USDC", &[], at(6)));
+    tracker.observe("CL100-WETH/USDC", &[sighting("d", 6, dec!(0.1), OpportunityStage::Detected, false)], at(6));
+    lifecycles.extend(tracker.observe("CL100-WETH/USDC", &[], at(8)));
+
+    let summary = summarize_lifecycles(&lifecycles);
+    assert_eq!((summary.lifecycles, summary.validated, summary.executed, summary.settled), (3, 2, 1, 1));
+    assert_eq!(summary.profitable_at_execution, 1);
+    assert_eq!(summary.fill_rate_pct, Some(dec!(50)));
+    assert_eq!(summary.mean_persisted_ms, Some(Decimal::from(2000) / Decimal::from(3)));
+    assert_eq!(summary.mean_profit_decay_usd, Some(dec!(1)));
+
+    let report = build_lifecycle_report(&lifecycles);
+    assert_eq!(report.pools.keys().collect::<Vec<_>>(), ["CL100-WETH/USDC", "WETH/USDC"]);
+    assert_eq!(report.pools["CL100-WETH/USDC"].fill_rate_pct, None);
+
+    let dir = std::env::temp_dir().join(format!("lifecycles_{}", std::process::id()));
+    save_opportunity_lifecycles_in(&dir, &lifecycles).unwrap();
+    assert_eq!(load_opportunity_lifecycles(&dir).unwrap(), lifecycles);
+    std::fs::remove_dir_all(dir).ok();
+}
@@ -0,0 +1,68 @@
+//! Oracle price parsing and the DEX/CEX sanity bound
+
+mod common;
+
+use aero_arb_mm_bot::network::{oracle_deviation_pct, OraclePrice, StablePriceOracle, StablePriceSource};
+use aero_arb_mm_bot::validation::validate_against_oracle;
+use alloy::primitives::address;
+use chrono::{Duration, TimeZone, Utc};
+use rust_decimal_macros::dec;
+use common::load;
+
+#[test]
+fn aggregator_answers_scale_by_feed_decimals() {
+    let price = OraclePrice::from_answer(3_012_345_678_90, 8, 1_700_000_000).unwrap();
+    assert_eq!(price.price, dec!(3012.3456789));
+    assert_eq!(price.updated_at, Utc.timestamp_opt(1_700_000_000, 0).unwrap());
+
+    assert!(OraclePrice::from_answer(0, 8, 1_700_000_000).is_none());
+    assert!(OraclePrice::from_answer(-5, 8, 1_700_000_000).is_none());
+}
+
+#[test]
+fn stale_answers_are_not_fresh() {
+    let price = OraclePrice::from_answer(300_000_000_000, 8, 1_700_000_000).unwrap();
+    let max_age = Duration::hours(1);
+    assert!(price.is_fresh(max_age, price.updated_at + Duration::minutes(30)));
+    assert!(!price.is_fresh(max_age, price.updated_at + Duration::minutes(61)));
+}
+
+#[test]
+fn prices_far_from_the_oracle_are_rejected() {
+    assert_eq!(oracle_deviation_pct(dec!(3090), dec!(3000)), dec!(3));
+    assert_eq!(oracle_deviation_pct(dec!(2910), dec!(3000)), dec!(3));
+
+    // Normal spread around the oracle
+    assert!(validate_against_oracle(dec!(2990), dec!(3005), dec!(3000), dec!(3)).is_ok());
+
+    // A poisoned CEX feed
+    let err = validate_against_oracle(dec!(2995), dec!(3600), dec!(3000), dec!(3)).unwrap_err();
+    assert!(err.to_string().starts_with("CEX price"));
+
+    // Both feeds agree with each other but not with the oracle
+    let err = validate_against_oracle(dec!(1500), dec!(1502), dec!(3000), dec!(3)).unwrap_err();
+    assert!(err.to_string().starts_with("DEX price"));
+}
+
+#[tokio::test]
+async fn stable_prices_come_from_pegs_and_fresh_feeds() {
+    let config = load(r#"
+[oracle]
+stable_prices = ["DOLA=1", "eUSD=0x0000000000000000000000000000000000000e05"]
+"#).unwrap();
+    assert_eq!(config.stable_prices["DOLA"], StablePriceSource::Fixed(dec!(1)));
+    assert_eq!(
+        config.stable_prices["eUSD"],
+        StablePriceSource::Feed(address!("0000000000000000000000000000000000000e05")),
+    );
+
+    // Feed-backed assets have no price until a read succeeds
+    let prices = StablePriceOracle::from_config(&config).prices().await;
+    assert_eq!(prices.get("DOLA"), Some(&dec!(1)));
+    assert_eq!(prices.get("eUSD"), None);
+
+    for bad in ["DOLA=0", "DOLA=-1", "DOLA=0xnotanaddress", "DOLA"] {
+        let contents = format!("[oracle]\nstable_prices = [\"{}\"]\n", bad);
+        assert!(load(&contents).is_err(), "{} should be rejected", bad);
+    }
+}
@@ -0,0 +1,113 @@
+//! Compression and expiry of old logs and daily outputs
+
+mod common;
+
+use aero_arb_mm_bot::config::DEFAULT_OUTPUT_COMPRESS_AFTER_DAYS;
+use aero_arb_mm_bot::storage::{sweep_dirs, RetentionPolicy};
+use flate2::read::GzDecoder;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use common::load;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("retention_{}_{}", name, std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn now() -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(1_772_409_600)
+}
+
+/// A file of `len` bytes last written `age_days` before `now()`
+fn write_aged(path: &Path, len: usize, age_days: u64) {
+    std::fs::write(path, "x".repeat(len)).unwrap();
+    let modified = now() - Duration::from_secs(age_days * 24 * 60 * 60);
+    File::options().write(true).open(path).unwrap().set_modified(modified).unwrap();
+}
+
+fn policy(compress_after_days: u64, retention_days: u64, max_total_mb: u64) -> RetentionPolicy {
+    RetentionPolicy { compress_after_days, retention_days, max_total_mb }
+}
+
+#[test]
+fn old_files_are_gzipped_and_expired_files_deleted() {
+    let dir = temp_dir("age");
+    write_aged(&dir.join("arbitrage_today.jsonl"), 100, 0);
+    write_aged(&dir.join("arbitrage_old.jsonl"), 10_000, 10);
+    write_aged(&dir.join("arbitrage_expired.jsonl"), 100, 40);
+    write_aged(&dir.join("arbitrage_expired.jsonl.gz"), 100, 40);
+
+    let sweep = sweep_dirs(&[dir.clone(), dir.join("missing")], &policy(7, 30, 0), now()).unwrap();
+    assert_eq!((sweep.compressed, sweep.deleted), (1, 2));
+    assert!(dir.join("arbitrage_today.jsonl").exists());
+    assert!(!dir.join("arbitrage_old.jsonl").exists());
+    assert!(!dir.join("arbitrage_expired.jsonl").exists());
+
+    let gz_path = dir.join("arbitrage_old.jsonl.gz");
+    let mut contents = String::new();
+    GzDecoder::new(File::open(&gz_path).unwrap()).read_to_string(&mut contents).unwrap();
+    assert_eq!(contents.len(), 10_000);
+    // The compressed file keeps its age, so it expires on schedule
+    let modified = std::fs::metadata(&gz_path).unwrap().modified().unwrap();
+    assert_eq!(modified, now() - Duration::from_secs(10 * 24 * 60 * 60));
+
+    // Already compressed files are left alone on the next sweep
+    let again = sweep_dirs(std::slice::from_ref(&dir), &policy(7, 30, 0), now()).unwrap();
+    assert_eq!((again.compressed, again.deleted), (0, 0));
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn size_cap_deletes_the_oldest_files_but_spares_recent_ones() {
+    let dir = temp_dir("size");
+    let mb = 1024 * 1024;
+    write_aged(&dir.join("prices_1.csv"), mb, 5);
+    write_aged(&dir.join("prices_2.csv"), mb, 4);
+    write_aged(&dir.join("prices_3.csv"), mb, 3);
+    write_aged(&dir.join("prices_4.csv"), 2 * mb, 0);
+
+    let sweep = sweep_dirs(std::slice::from_ref(&dir), &policy(0, 0, 3), now()).unwrap();
+    assert_eq!((sweep.compressed, sweep.deleted), (0, 2));
+    assert_eq!(sweep.freed_bytes, 2 * mb as u64);
+    assert_eq!(sweep.total_bytes, 3 * mb as u64);
+    assert!(!dir.join("prices_1.csv").exists());
+    assert!(!dir.join("prices_2.csv").exists());
+    assert!(dir.join("prices_3.csv").exists());
+
+    // Over the cap with only files written today: nothing to delete
+    let sweep = sweep_dirs(std::slice::from_ref(&dir), &policy(0, 0, 1), now()).unwrap();
+    assert_eq!(sweep.deleted, 1);
+    assert!(dir.join("prices_4.csv").exists());
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn a_disabled_policy_touches_nothing() {
+    let dir = temp_dir("disabled");
+    write_aged(&dir.join("decisions_old.jsonl"), 100, 400);
+    assert!(!policy(0, 0, 0).is_enabled());
+    let sweep = sweep_dirs(std::slice::from_ref(&dir), &policy(0, 0, 0), now()).unwrap();
+    assert_eq!(sweep, Default::default());
+    assert!(dir.join("decisions_old.jsonl").exists());
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn retention_settings_are_configurable_and_reloadable() {
+    let mut config = load("").unwrap();
+    assert_eq!(
+        RetentionPolicy::from_config(&config),
+        policy(DEFAULT_OUTPUT_COMPRESS_AFTER_DAYS, 0, 0),
+    );
+
+    let reloaded = load("[monitoring]\noutput_retention_days = 90\noutput_max_size_mb = 2048\n").unwrap();
+    let changed = config.apply_reloadable(&reloaded);
+    assert_eq!(changed, vec!["output_retention_days", "output_max_size_mb"]);
+    assert_eq!(RetentionPolicy::from_config(&config), policy(DEFAULT_OUTPUT_COMPRESS_AFTER_DAYS, 90, 2048));
+
+    assert!(load("[monitoring]\noutput_retention_days = -1\n").is_err());
+}
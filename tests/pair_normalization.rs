@@ -0,0 +1,73 @@
+//! Property-based tests for token pair normalization helpers
+
+use aero_arb_mm_bot::pools::{TokenMetadata, TokenMetadataCache};
+use aero_arb_mm_bot::types::{USDC_MAINNET, WETH_MAINNET};
+use aero_arb_mm_bot::utils::{invert_price, normalize_reserves, order_pair, scale_from_raw, scale_to_raw};
+use alloy::primitives::U256;
+use proptest::prelude::*;
+use rust_decimal::Decimal;
+
+proptest! {
+    #[test]
+    fn order_pair_is_symmetric(a in any::<u64>(), b in any::<u64>()) {
+        let forward = order_pair(WETH_MAINNET, USDC_MAINNET, WETH_MAINNET, a, b);
+        let reversed = order_pair(USDC_MAINNET, WETH_MAINNET, WETH_MAINNET, b, a);
+        prop_assert_eq!(forward, Some((a, b)));
+        prop_assert_eq!(forward, reversed);
+    }
+
+    #[test]
+    fn scaling_round_trips(raw in 0u64..u64::MAX, decimals in 0i32..=18) {
+        let amount = scale_from_raw(U256::from(raw), decimals).unwrap();
+        prop_assert_eq!(scale_to_raw(amount, decimals), Some(U256::from(raw)));
+    }
+
+    #[test]
+    fn inversion_round_trips(price in 1u32..1_000_000u32) {
+        let price = Decimal::from(price);
+        let inverted = invert_price(price).unwrap();
+        let back = invert_price(inverted).unwrap();
+        prop_assert!((back - price).abs() < Decimal::new(1, 6));
+    }
+
+    #[test]
+    fn reserves_normalize_independent_of_token_order(weth_raw in 1u64..u64::MAX, usd_raw in 1u64..u64::MAX) {
+        let (weth, usd) = (U256::from(weth_raw), U256::from(usd_raw));
+        let tokens = weth_usdc_metadata();
+        let a = normalize_reserves(&tokens, WETH_MAINNET, USDC_MAINNET, WETH_MAINNET, weth, usd);
+        let b = normalize_reserves(&tokens, USDC_MAINNET, WETH_MAINNET, WETH_MAINNET, usd, weth);
+        prop_assert!(a.is_some());
+        prop_assert_eq!(a, b);
+    }
+}
+
+fn weth_usdc_metadata() -> TokenMetadataCache {
+    let tokens = TokenMetadataCache::new();
+    tokens.insert(WETH_MAINNET, TokenMetadata { symbol: "WETH".to_string(), decimals: 18 });
+    tokens.insert(USDC_MAINNET, TokenMetadata { symbol: "USDC".to_string(), decimals: 6 });
+    tokens
+}
+
+#[test]
+fn reserves_of_unknown_tokens_are_not_guessed() {
+    let tokens = weth_usdc_metadata();
+    let dai = alloy::primitives::address!("50c5725949A6F0c72E6C4a641F24049A917DB0Cb");
+    assert_eq!(normalize_reserves(&tokens, WETH_MAINNET, dai, WETH_MAINNET, U256::from(1), U256::from(1)), None);
+
+    // An 18-decimal stable scales by its own decimals, not USDC's
+    tokens.insert(dai, TokenMetadata { symbol: "DAI".to_string(), decimals: 18 });
+    let raw = U256::from(3_000u64) * U256::from(10u64).pow(U256::from(18));
+    let (_, dai_amount) = normalize_reserves(&tokens, WETH_MAINNET, dai, WETH_MAINNET, U256::from(1), raw).unwrap();
+    assert_eq!(dai_amount, Decimal::from(3_000));
+}
+
+#[test]
+fn zero_price_has_no_inverse() {
+    assert_eq!(invert_price(Decimal::ZERO), None);
+}
+
+#[test]
+fn amounts_too_large_to_scale_are_not_zeroed() {
+    assert_eq!(scale_to_raw(Decimal::MAX, 18), None);
+    assert_eq!(scale_to_raw(Decimal::from(-1), 18), Some(U256::ZERO));
+}
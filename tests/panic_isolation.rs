@@ -0,0 +1,80 @@
+//! Caught cycle and pool panics and the MAX_PANICS shutdown threshold
+
+mod common;
+
+use aero_arb_mm_bot::bot::cycle::finish_pool_task;
+use aero_arb_mm_bot::bot::session::{panic_limit_reached, record_panic, PoolTally};
+use aero_arb_mm_bot::config::DEFAULT_MAX_PANICS;
+use aero_arb_mm_bot::errors::{BreakerLimits, CircuitBreakerRegistry};
+use aero_arb_mm_bot::types::MonitoringState;
+use futures::FutureExt;
+use std::collections::HashMap;
+use std::panic::AssertUnwindSafe;
+use tokio::task::JoinSet;
+use common::load;
+
+async fn pool_cycle(fail: bool) -> anyhow::Result<()> {
+    tokio::task::yield_now().await;
+    if fail {
+        panic!("reserve index {} out of range", 3);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn panics_are_counted_as_an_error_class_until_the_limit() {
+    let config = load("[recovery]\nmax_panics = 2\n").unwrap();
+    let mut state = MonitoringState::new();
+
+    let caught = AssertUnwindSafe(pool_cycle(true)).catch_unwind().await.unwrap_err();
+    record_panic(&mut state, "pool WETH/USDC", caught.as_ref());
+    assert_eq!((state.panics, state.error_counts["panic"]), (1, 1));
+    assert!(!panic_limit_reached(&state, &config));
+
+    // A cycle that finishes is untouched by the wrapper
+    assert!(AssertUnwindSafe(pool_cycle(false)).catch_unwind().await.unwrap().is_ok());
+
+    let caught = AssertUnwindSafe(async { panic!("static message") }).catch_unwind().await.unwrap_err();
+    record_panic(&mut state, "monitoring cycle", caught.as_ref());
+    assert!(panic_limit_reached(&state, &config));
+    assert_eq!(state.snapshot().panics, 2);
+}
+
+#[tokio::test]
+async fn a_panicking_pool_task_fails_only_that_pool() {
+    let limits = BreakerLimits { max_errors: 1, cooldown_secs: 600 };
+    let breakers = CircuitBreakerRegistry::new(limits, limits, limits);
+    let mut state = MonitoringState::new();
+    let mut running = JoinSet::new();
+    let mut pool_tasks = HashMap::new();
+    for (pool, fail) in [("WETH/USDC", true), ("cbETH/WETH", false)] {
+        let task = running.spawn(async move { (PoolTally::default(), pool_cycle(fail).await) });
+        pool_tasks.insert(task.id(), pool.to_string());
+    }
+
+    let mut finished = HashMap::new();
+    while let Some(joined) = running.join_next_with_id().await {
+        let pool = match &joined {
+            Ok((id, _)) => pool_tasks[id].clone(),
+            Err(e) => pool_tasks[&e.id()].clone(),
+        };
+        finished.insert(pool, finish_pool_task(joined, &mut pool_tasks, &breakers, &mut state).await);
+    }
+
+    assert_eq!(finished, HashMap::from([("WETH/USDC".to_string(), false), ("cbETH/WETH".to_string(), true)]));
+    assert!(pool_tasks.is_empty());
+    assert_eq!((state.panics, state.error_counts["panic"]), (1, 1));
+    assert!(!breakers.pool("WETH/USDC").await.can_proceed().await);
+    assert!(breakers.pool("cbETH/WETH").await.can_proceed().await);
+}
+
+#[test]
+fn max_panics_defaults_and_zero_never_shuts_down() {
+    assert_eq!(load("").unwrap().max_panics, DEFAULT_MAX_PANICS);
+
+    let config = load("[recovery]\nmax_panics = 0\n").unwrap();
+    let mut state = MonitoringState::new();
+    state.panics = 1_000;
+    assert!(!panic_limit_reached(&state, &config));
+    assert!(load("[recovery]\nmax_panics = -1\n").is_err());
+}
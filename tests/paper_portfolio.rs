@@ -0,0 +1,80 @@
+//! Paper-trading ledger booked from execution fills
+
+mod common;
+
+use aero_arb_mm_bot::arbitrage::{calculate_arbitrage, FeeModel, DEFAULT_MIN_PRICE_DIFF_PCT};
+use aero_arb_mm_bot::portfolio::PaperPortfolio;
+use aero_arb_mm_bot::storage::build_daily_report;
+use aero_arb_mm_bot::types::{ArbitrageOpportunity, ExecutionStatus, TradeExecution};
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde_json::json;
+use common::simulated_execution;
+
+fn opportunity() -> ArbitrageOpportunity {
+    let fees = FeeModel { transfer_cost_usd: dec!(0.5), ..FeeModel::zero() };
+    calculate_arbitrage("WETH/USDC", dec!(2990), dec!(3000), dec!(1), None, None, dec!(1), &fees, DEFAULT_MIN_PRICE_DIFF_PCT).unwrap()
+}
+
+fn execution(status: ExecutionStatus, dex_fill: Option<Decimal>, cex_fill: Option<Decimal>) -> TradeExecution {
+    TradeExecution { status, dex_fill_price: dex_fill, cex_fill_price: cex_fill, ..simulated_execution() }
+}
+
+#[test]
+fn round_trips_realize_cash_and_open_legs_stay_unrealized() {
+    let opportunity = opportunity();
+    let mut paper = PaperPortfolio::new(dec!(1), dec!(3000), dec!(3000));
+
+    assert!(!paper.apply_execution(&execution(ExecutionStatus::Failed, None, None), &opportunity));
+    assert!(paper.apply_execution(&execution(ExecutionStatus::Simulated, Some(dec!(2992)), Some(dec!(2999))), &opportunity));
+    // Bought at 2992, sold at 2999, minus $1 gas and $0.50 transfer
+    assert_eq!((paper.weth, paper.usdc), (dec!(1), dec!(3005.5)));
+    assert_eq!(paper.realized_pnl_usd, dec!(5.5));
+    assert_eq!(paper.fees_usd, dec!(1.5));
+
+    // The CEX leg failed: the bought WETH stays open and only gas is realized
+    assert!(paper.apply_execution(&execution(ExecutionStatus::Success, Some(dec!(2992)), None), &opportunity));
+    assert_eq!((paper.weth, paper.usdc), (dec!(2), dec!(12.5)));
+    let report = paper.report(dec!(2900));
+    assert_eq!(report.realized_pnl_usd, dec!(4.5));
+    assert_eq!(report.total_pnl_usd, dec!(5812.5) - dec!(6000));
+    assert_eq!(report.unrealized_pnl_usd, report.total_pnl_usd - dec!(4.5));
+    assert_eq!(report.trades, 2);
+}
+
+#[test]
+fn marks_track_drawdown_and_sharpe() {
+    let mut paper = PaperPortfolio::new(dec!(1), dec!(0), dec!(3000));
+    let start = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+    for (i, price) in [dec!(3000), dec!(3030), dec!(2970), dec!(3000), dec!(3060)].into_iter().enumerate() {
+        paper.mark(price, start + Duration::seconds(2 * i as i64));
+    }
+    let report = paper.report(dec!(3060));
+    assert_eq!(report.max_drawdown_usd, dec!(60));
+    assert_eq!(report.drawdown_usd, dec!(0));
+    assert!(report.sharpe_ratio.unwrap() > 0.0);
+
+    let flat = PaperPortfolio::new(dec!(0), dec!(100), dec!(3000));
+    assert!(flat.report(dec!(3000)).sharpe_ratio.is_none());
+}
+
+#[test]
+fn daily_report_takes_the_days_last_snapshot() {
+    let dir = std::env::temp_dir().join(format!("paper_report_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("portfolio")).unwrap();
+    let snapshot = |value: &str, pnl: &str| json!({
+        "value_usd": value, "total_pnl_usd": pnl, "realized_pnl_usd": "2", "unrealized_pnl_usd": pnl,
+        "max_drawdown_usd": "10", "sharpe_ratio": 1.5, "trades": 3,
+    }).to_string();
+    std::fs::write(
+        dir.join("portfolio/paper_2026-03-02.jsonl"),
+        format!("{}\n{}\n", snapshot("6000", "0"), snapshot("6012", "12")),
+    ).unwrap();
+
+    let report = build_daily_report(&dir, NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()).unwrap();
+    let paper = report.paper_portfolio.unwrap();
+    assert_eq!((paper.value_usd, paper.total_pnl_usd, paper.trades), (dec!(6012), dec!(12), 3));
+    assert_eq!(paper.sharpe_ratio, Some(1.5));
+    std::fs::remove_dir_all(dir).ok();
+}
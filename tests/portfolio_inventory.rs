@@ -0,0 +1,23 @@
+//! Inventory from tracked venue balances
+
+use aero_arb_mm_bot::portfolio::{PortfolioSnapshot, VenueBalances};
+use rust_decimal_macros::dec;
+
+#[test]
+fn testnet_cex_balances_are_not_inventory() {
+    let cex_testnet = Some(VenueBalances { weth: dec!(1), usd: dec!(10000) });
+    let snapshot = PortfolioSnapshot {
+        timestamp: chrono::Utc::now(),
+        wallet: Some(VenueBalances { weth: dec!(2), usd: dec!(3000) }),
+        cex_testnet: cex_testnet.clone(),
+    };
+    let inventory = snapshot.inventory().unwrap();
+    assert_eq!((inventory.weth, inventory.usd), (dec!(2), dec!(3000)));
+
+    // Reported per venue
+    let json = serde_json::to_value(&snapshot).unwrap();
+    assert_eq!(json["cex_testnet"]["usd"], "10000");
+
+    let testnet_only = PortfolioSnapshot { timestamp: chrono::Utc::now(), wallet: None, cex_testnet };
+    assert!(testnet_only.inventory().is_none());
+}
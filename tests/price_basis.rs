@@ -0,0 +1,35 @@
+//! Fair-value price bases and the DEX TWAP
+
+use aero_arb_mm_bot::pools::TwapTracker;
+use aero_arb_mm_bot::types::{BookTicker, CexQuotes, FairValueQuotes, PriceBasis};
+use rust_decimal_macros::dec;
+use std::time::{Duration, Instant};
+
+#[test]
+fn microprice_leans_toward_the_thin_side() {
+    let book = BookTicker { bid_price: dec!(3000), bid_qty: dec!(30), ask_price: dec!(3001), ask_qty: dec!(10) };
+    assert_eq!(book.mid(), dec!(3000.5));
+    assert_eq!(book.microprice(), dec!(3000.75));
+}
+
+#[test]
+fn unavailable_basis_falls_back_to_cex_last() {
+    let quotes = FairValueQuotes::new(CexQuotes { last: dec!(3000), book: None }, None);
+    assert_eq!(quotes.resolve(PriceBasis::CexMicroprice), (PriceBasis::CexLast, dec!(3000)));
+    assert_eq!(quotes.resolve(PriceBasis::Composite), (PriceBasis::CexLast, dec!(3000)));
+    assert_eq!("dex_twap".parse::<PriceBasis>(), Ok(PriceBasis::DexTwap));
+    assert!("vwap".parse::<PriceBasis>().is_err());
+}
+
+#[tokio::test]
+async fn twap_weights_prices_by_how_long_they_held() {
+    let twaps = TwapTracker::new();
+    let window = Duration::from_secs(300);
+    let start = Instant::now();
+
+    assert_eq!(twaps.record_at("pool", dec!(100), window, start).await, None);
+    twaps.record_at("pool", dec!(200), window, start + Duration::from_secs(30)).await;
+    // 100 held for 30s, 200 for 10s
+    let twap = twaps.record_at("pool", dec!(300), window, start + Duration::from_secs(40)).await;
+    assert_eq!(twap, Some(dec!(125)));
+}
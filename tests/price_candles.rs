@@ -0,0 +1,92 @@
+//! Recorded price stream and OHLC candle building
+
+use aero_arb_mm_bot::storage::{
+    build_candles, load_price_samples, save_price_samples_in, CandleInterval, PriceSample, PriceSource,
+};
+use aero_arb_mm_bot::volatility::candle_volatility_percentage;
+use chrono::{TimeZone, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+fn sample(minute: u32, second: u32, source: PriceSource, market: &str, price: Decimal) -> PriceSample {
+    PriceSample {
+        timestamp: Utc.with_ymd_and_hms(2026, 3, 2, 9, minute, second).unwrap(),
+        source,
+        market: market.to_string(),
+        price,
+    }
+}
+
+#[test]
+fn samples_round_trip_through_the_csv_stream() {
+    let dir = std::env::temp_dir().join(format!("prices_{}", std::process::id()));
+    let samples = vec![
+        sample(0, 1, PriceSource::Cex, "ETHUSDT", dec!(3000.25)),
+        sample(0, 1, PriceSource::Dex, "WETH/USDC", dec!(3001.5)),
+    ];
+    save_price_samples_in(&dir, &samples[..1]).unwrap();
+    save_price_samples_in(&dir, &samples[1..]).unwrap();
+
+    assert_eq!(load_price_samples(&dir).unwrap(), samples);
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn a_batch_across_midnight_lands_in_each_days_file() {
+    let dir = std::env::temp_dir().join(format!("prices_midnight_{}", std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+    let at = |day: u32, hour: u32, minute: u32| PriceSample {
+        timestamp: Utc.with_ymd_and_hms(2026, 3, day, hour, minute, 0).unwrap(),
+        source: PriceSource::Cex,
+        market: "ETHUSDT".to_string(),
+        price: dec!(3000),
+    };
+    let samples = vec![at(1, 23, 59), at(2, 0, 1)];
+    save_price_samples_in(&dir, &samples).unwrap();
+
+    let rows = |day: &str| std::fs::read_to_string(dir.join(format!("prices_{}.csv", day))).unwrap().lines().count();
+    // Header plus one sample each
+    assert_eq!((rows("2026-03-01"), rows("2026-03-02")), (2, 2));
+    assert_eq!(load_price_samples(&dir).unwrap(), samples);
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn candles_track_open_high_low_close_per_market() {
+    let samples = vec![
+        sample(0, 10, PriceSource::Dex, "WETH/USDC", dec!(3000)),
+        sample(0, 50, PriceSource::Dex, "WETH/USDC", dec!(2990)),
+        sample(0, 20, PriceSource::Dex, "WETH/USDC", dec!(3010)),
+        sample(1, 5, PriceSource::Dex, "WETH/USDC", dec!(3005)),
+        sample(0, 30, PriceSource::Cex, "ETHUSDT", dec!(3002)),
+    ];
+
+    let minutes = build_candles(&samples, CandleInterval::OneMinute);
+    assert_eq!(minutes.len(), 3);
+    let cex = &minutes[0];
+    assert_eq!((cex.source, cex.samples), (PriceSource::Cex, 1));
+    let first = &minutes[1];
+    assert_eq!(first.open_time, Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap());
+    assert_eq!((first.open, first.high, first.low, first.close), (dec!(3000), dec!(3010), dec!(2990), dec!(2990)));
+    assert_eq!(first.samples, 3);
+    assert_eq!(minutes[2].open, dec!(3005));
+
+    let five = build_candles(&samples, CandleInterval::FiveMinutes);
+    assert_eq!(five.len(), 2);
+    assert_eq!((five[1].open, five[1].close, five[1].samples), (dec!(3000), dec!(3005), 4));
+}
+
+#[test]
+fn intervals_parse_and_volatility_needs_ten_candles() {
+    assert_eq!("5m".parse::<CandleInterval>().unwrap(), CandleInterval::FiveMinutes);
+    assert!("2m".parse::<CandleInterval>().is_err());
+
+    let samples: Vec<PriceSample> = (0..12)
+        .map(|minute| sample(minute, 0, PriceSource::Dex, "WETH/USDC", dec!(3000) + Decimal::from(minute % 2 * 30)))
+        .collect();
+    let candles = build_candles(&samples, CandleInterval::OneMinute);
+    assert!(candle_volatility_percentage(&candles[..9]).is_none());
+    // ±1% a minute, annualized
+    let volatility = candle_volatility_percentage(&candles).unwrap();
+    assert!(volatility > 700.0 && volatility < 730.0, "volatility {}", volatility);
+}
@@ -0,0 +1,35 @@
+//! Bounded price history with spill-to-disk read-back
+
+use aero_arb_mm_bot::storage::PriceHistory;
+use std::time::{Duration, SystemTime};
+
+#[test]
+fn spilled_samples_read_back_in_order() {
+    let dir = std::env::temp_dir().join(format!("price_history_{}", std::process::id()));
+    let mut history = PriceHistory::spilling_to(dir.clone(), Duration::from_secs(3600), 10, 30);
+    let start = SystemTime::now() - Duration::from_secs(100);
+
+    for i in 0..50u64 {
+        history.push(start + Duration::from_secs(i), i as f64);
+    }
+    assert!(history.memory_len() <= 10);
+
+    let prices: Vec<f64> = history.samples().into_iter().map(|(_, price)| price).collect();
+    assert_eq!(prices, (0..50).map(|i| i as f64).collect::<Vec<_>>());
+
+    let recent = history.since(start + Duration::from_secs(45));
+    assert_eq!(recent.len(), 5);
+
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn samples_outside_the_window_are_dropped() {
+    let mut history = PriceHistory::in_memory(Duration::from_secs(60));
+    let start = SystemTime::now();
+    for i in 0..10u64 {
+        history.push(start + Duration::from_secs(i * 30), i as f64);
+    }
+    let prices: Vec<f64> = history.samples().into_iter().map(|(_, price)| price).collect();
+    assert_eq!(prices, vec![7.0, 8.0, 9.0]);
+}
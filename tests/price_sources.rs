@@ -0,0 +1,70 @@
+//! CEX price source selection, Coinbase symbol mapping and the fixture source
+
+mod common;
+
+use aero_arb_mm_bot::errors::BotError;
+use aero_arb_mm_bot::network::{coinbase_product_id, cex_price_source, CexSource, FixturePriceSource, PriceSource};
+use aero_arb_mm_bot::types::BookTicker;
+use rust_decimal_macros::dec;
+use common::load;
+
+#[test]
+fn sources_are_configured_by_name() {
+    assert_eq!(load("").unwrap().cex_source, CexSource::Binance);
+    let coinbase = load("[networks]\ncex_source = \"Coinbase\"\n").unwrap();
+    assert_eq!(coinbase.cex_source, CexSource::Coinbase);
+    assert_eq!(cex_price_source(&coinbase).unwrap().name(), "Coinbase");
+
+    assert!(load("[networks]\ncex_source = \"kraken\"\n").is_err());
+    // The fixture source needs its file
+    assert!(load("[networks]\ncex_source = \"fixture\"\n").is_err());
+}
+
+#[test]
+fn binance_symbols_map_to_coinbase_products() {
+    assert_eq!(coinbase_product_id("ETHUSDC").as_deref(), Some("ETH-USD"));
+    assert_eq!(coinbase_product_id("ETHUSDT").as_deref(), Some("ETH-USDT"));
+    assert_eq!(coinbase_product_id("AEROUSD").as_deref(), Some("AERO-USD"));
+    assert_eq!(coinbase_product_id("CBETHETH").as_deref(), Some("CBETH-ETH"));
+    assert_eq!(coinbase_product_id("cbethbtc").as_deref(), Some("CBETH-BTC"));
+    assert_eq!(coinbase_product_id("USDC"), None);
+    assert_eq!(coinbase_product_id("ETHJPY"), None);
+}
+
+#[tokio::test]
+async fn fixture_prices_pass_the_usual_checks() {
+    let book = BookTicker { bid_price: dec!(2999.5), bid_qty: dec!(4), ask_price: dec!(3000.5), ask_qty: dec!(3) };
+    let source = FixturePriceSource::new()
+        .with_price("ETHUSDC", dec!(3000))
+        .with_book("ETHUSDC", book)
+        .with_price("AEROUSDC", dec!(0));
+
+    assert_eq!(source.price("ETHUSDC").await.unwrap(), dec!(3000));
+    assert_eq!(source.book_ticker("ETHUSDC").await.unwrap(), book);
+    assert!(matches!(source.price("CBETHETH").await, Err(BotError::Network { .. })));
+    assert!(matches!(source.price("AEROUSDC").await, Err(BotError::PriceValidation { .. })));
+
+    // The ETH/USD reference is range-checked, and prices can move while running
+    source.set_price("ETHUSDC", dec!(50));
+    assert!(matches!(source.price("ETHUSDC").await, Err(BotError::PriceValidation { .. })));
+    source.set_book("ETHUSDC", BookTicker { bid_price: dec!(3001), ..book });
+    assert!(source.book_ticker("ETHUSDC").await.is_err());
+}
+
+#[tokio::test]
+async fn fixture_file_is_loaded_from_config() {
+    let path = std::env::temp_dir().join(format!("cex_fixture_{}.json", std::process::id()));
+    std::fs::write(&path, r#"{"prices": {"ETHUSDC": "3000", "AEROUSDC": 1.25}}"#).unwrap();
+
+    let config = load(&format!(
+        "[networks]\ncex_source = \"fixture\"\ncex_fixture_path = {:?}\n",
+        path.to_str().unwrap(),
+    )).unwrap();
+    let source = cex_price_source(&config).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(source.name(), "Fixture");
+    assert_eq!(source.price("ETHUSDC").await.unwrap(), dec!(3000));
+    assert_eq!(source.price("AEROUSDC").await.unwrap(), dec!(1.25));
+    assert!(source.book_ticker("ETHUSDC").await.is_err());
+}
@@ -0,0 +1,35 @@
+//! Submission modes and private endpoint request bodies
+
+use aero_arb_mm_bot::execution::{
+    bundle_request, bundle_retarget, raw_transaction_request, SubmissionMode, BUNDLE_RETARGET_BLOCKS,
+};
+
+#[test]
+fn submission_modes_parse() {
+    assert_eq!("private".parse::<SubmissionMode>(), Ok(SubmissionMode::Private));
+    assert_eq!(" Bundle ".parse::<SubmissionMode>(), Ok(SubmissionMode::Bundle));
+    assert_eq!(SubmissionMode::default(), SubmissionMode::Public);
+    assert!("flashbots".parse::<SubmissionMode>().is_err());
+}
+
+#[test]
+fn raw_transactions_are_hex_encoded() {
+    let body = raw_transaction_request(&[0x02, 0xf8, 0x6b]);
+    assert_eq!(body["method"], "eth_sendRawTransaction");
+    assert_eq!(body["params"][0], "0x02f86b");
+}
+
+#[test]
+fn bundles_target_a_single_block() {
+    let body = bundle_request(&[0x02, 0xf8], 24_000_001);
+    assert_eq!(body["method"], "eth_sendBundle");
+    assert_eq!(body["params"][0]["txs"][0], "0x02f8");
+    assert_eq!(body["params"][0]["blockNumber"], "0x16e3601");
+}
+
+#[test]
+fn missed_bundles_are_retargeted_then_sent_privately() {
+    assert_eq!(bundle_retarget(100, 0), Some(101));
+    assert_eq!(bundle_retarget(103, BUNDLE_RETARGET_BLOCKS), Some(104));
+    assert_eq!(bundle_retarget(104, BUNDLE_RETARGET_BLOCKS + 1), None);
+}
@@ -0,0 +1,80 @@
+//! Realized P&L attributed to pools, directions, hours and volatility regimes
+
+mod common;
+
+use aero_arb_mm_bot::arbitrage::{calculate_arbitrage, FeeModel, DEFAULT_MIN_PRICE_DIFF_PCT};
+use aero_arb_mm_bot::storage::build_profit_attribution;
+use aero_arb_mm_bot::types::{TradeExecution, TradeType, VolatilityImpact};
+use chrono::{NaiveDate, TimeZone, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::path::Path;
+use common::simulated_execution;
+
+fn execution(
+    opportunity_id: &str,
+    hour: u32,
+    trade_type: TradeType,
+    volatility_impact: Option<VolatilityImpact>,
+    actual_profit_usd: Decimal,
+) -> TradeExecution {
+    TradeExecution {
+        id: format!("execution-{}-{}", opportunity_id, hour),
+        opportunity_id: opportunity_id.to_string(),
+        timestamp: Utc.with_ymd_and_hms(2026, 3, 2, hour, 30, 0).unwrap(),
+        trade_type,
+        expected_profit_usd: dec!(2),
+        actual_profit_usd: Some(actual_profit_usd),
+        volatility_impact,
+        ..simulated_execution()
+    }
+}
+
+fn write_jsonl<T: serde::Serialize>(path: &Path, records: &[T]) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let lines: Vec<String> = records.iter().map(|r| serde_json::to_string(r).unwrap()).collect();
+    std::fs::write(path, lines.join("\n") + "\n").unwrap();
+}
+
+#[test]
+fn executions_are_attributed_by_pool_direction_hour_and_regime() {
+    let dir = std::env::temp_dir().join(format!("attribution_{}", std::process::id()));
+    let mut weth = calculate_arbitrage("WETH/USDC", dec!(2990), dec!(3000), dec!(1), None, None, dec!(1), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    weth.id = "weth".to_string();
+    let mut cl = calculate_arbitrage("CL100-WETH/USDC", dec!(3010), dec!(3000), dec!(1), None, None, dec!(1), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    cl.id = "cl".to_string();
+    // The CL opportunity was detected the day before its execution
+    write_jsonl(&dir.join("opportunities/arbitrage_2026-03-01.jsonl"), &[&cl]);
+    write_jsonl(&dir.join("opportunities/arbitrage_2026-03-02.jsonl"), &[&weth]);
+    write_jsonl(&dir.join("executions/trades_2026-03-02.jsonl"), &[
+        execution("weth", 9, TradeType::BuyDexSellCex, Some(VolatilityImpact::Low), dec!(3)),
+        execution("weth", 14, TradeType::BuyDexSellCex, Some(VolatilityImpact::High), dec!(-1)),
+        execution("cl", 9, TradeType::BuyCexSellDex, Some(VolatilityImpact::Low), dec!(1)),
+        execution("missing", 22, TradeType::BuyCexSellDex, None, dec!(0.5)),
+    ]);
+    // Before --since
+    write_jsonl(&dir.join("executions/trades_2026-03-01.jsonl"), &[
+        execution("cl", 8, TradeType::BuyCexSellDex, None, dec!(100)),
+    ]);
+
+    let report = build_profit_attribution(&dir, NaiveDate::from_ymd_opt(2026, 3, 2)).unwrap();
+    assert_eq!(report.executions, 4);
+    assert_eq!(report.realized_pnl_usd, dec!(3.5));
+    assert_eq!(report.unattributed_executions, 1);
+
+    let pools: Vec<_> = report.by_pool.iter().map(|b| (b.key.as_str(), b.executions, b.realized_pnl_usd)).collect();
+    assert_eq!(pools, [("WETH/USDC", 2, dec!(2)), ("CL100-WETH/USDC", 1, dec!(1)), ("unknown", 1, dec!(0.5))]);
+    let weth_pool = &report.by_pool[0];
+    assert_eq!((weth_pool.winning_executions, weth_pool.win_rate_pct, weth_pool.mean_pnl_usd), (1, dec!(50), dec!(1)));
+
+    let hours: Vec<_> = report.by_hour.iter().map(|b| (b.key.as_str(), b.realized_pnl_usd)).collect();
+    assert_eq!(hours, [("09", dec!(4)), ("14", dec!(-1)), ("22", dec!(0.5))]);
+    let directions: Vec<_> = report.by_direction.iter().map(|b| (b.key.as_str(), b.realized_pnl_usd)).collect();
+    assert_eq!(directions, [("BuyDexSellCex", dec!(2)), ("BuyCexSellDex", dec!(1.5))]);
+    let regimes: Vec<_> = report.by_volatility.iter().map(|b| (b.key.as_str(), b.realized_pnl_usd)).collect();
+    assert_eq!(regimes, [("Low", dec!(4)), ("unknown", dec!(0.5)), ("High", dec!(-1))]);
+
+    let all_days = build_profit_attribution(&dir, None).unwrap();
+    assert_eq!(all_days.executions, 5);
+    std::fs::remove_dir_all(dir).ok();
+}
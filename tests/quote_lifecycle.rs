@@ -0,0 +1,71 @@
+//! Simulated market-making quote placement, fills and spread capture
+
+use aero_arb_mm_bot::market_making::{fill_probability, SimulatedOrderBook};
+use aero_arb_mm_bot::types::QuoteSide;
+use rust_decimal_macros::dec;
+
+const POOL: &str = "WETH/USDC";
+
+fn always() -> f64 {
+    0.0
+}
+
+fn never() -> f64 {
+    1.0
+}
+
+#[test]
+fn fill_chance_rises_from_the_touch_to_certain() {
+    assert_eq!(fill_probability(dec!(-1)), 0.0);
+    assert_eq!(fill_probability(dec!(0)), 0.5);
+    assert_eq!(fill_probability(dec!(5)), 0.75);
+    assert_eq!(fill_probability(dec!(25)), 1.0);
+}
+
+#[test]
+fn quotes_fill_only_when_the_market_crosses_them() {
+    let mut book = SimulatedOrderBook::new();
+    book.quote(POOL, "signal", dec!(2990), dec!(3010), dec!(0.5));
+
+    // Inside the spread: nothing fills however lucky the roll
+    assert!(book.match_prices(POOL, dec!(2995), dec!(3000), always).is_empty());
+    assert!(book.match_prices("other", dec!(2900), dec!(2900), always).is_empty());
+
+    // The DEX trades down through the bid; the ask stays open
+    let fills = book.match_prices(POOL, dec!(2985), dec!(3000), always);
+    assert_eq!(fills.len(), 1);
+    assert_eq!(fills[0].quote.side, QuoteSide::Bid);
+    assert_eq!(fills[0].market_price, dec!(2985));
+    assert_eq!(fills[0].spread_capture_usd, dec!(5));
+
+    // A filled quote does not fill twice, and an unlucky roll leaves the ask resting
+    assert!(book.match_prices(POOL, dec!(2980), dec!(3020), never).is_empty());
+    let fills = book.match_prices(POOL, dec!(3000), dec!(3020), always);
+    assert_eq!(fills.len(), 1);
+    assert_eq!(fills[0].quote.side, QuoteSide::Ask);
+    // Fair value moved above the ask: adversely selected
+    assert_eq!(fills[0].spread_capture_usd, dec!(-5));
+
+    let report = book.report(dec!(3000));
+    assert_eq!((report.bid_fills, report.ask_fills, report.open_quotes), (1, 1, 0));
+    assert_eq!(report.position_eth, dec!(0));
+    assert_eq!(report.cash_usd, dec!(10));
+    assert_eq!(report.pnl_usd, dec!(10));
+    assert_eq!(report.spread_capture_usd, dec!(0));
+}
+
+#[test]
+fn new_quotes_replace_and_zero_size_cancels() {
+    let mut book = SimulatedOrderBook::new();
+    book.quote(POOL, "first", dec!(2990), dec!(3010), dec!(1));
+    book.quote(POOL, "second", dec!(2995), dec!(3005), dec!(1));
+    assert_eq!(book.report(dec!(3000)).open_quotes, 2);
+
+    let fills = book.match_prices(POOL, dec!(2994), dec!(3000), always);
+    assert_eq!(fills[0].quote.signal_id, "second");
+    assert_eq!(book.report(dec!(2994)).pnl_usd, dec!(-1));
+
+    book.quote(POOL, "hold", dec!(2995), dec!(3005), dec!(0));
+    let report = book.report(dec!(3000));
+    assert_eq!((report.placed, report.cancelled, report.open_quotes), (4, 3, 0));
+}
@@ -0,0 +1,50 @@
+//! Token-bucket request budgets
+
+use aero_arb_mm_bot::network::RateLimiter;
+use std::time::{Duration, Instant};
+
+#[test]
+fn burst_is_capped_at_one_second_of_budget() {
+    let limiter = RateLimiter::per_second(5);
+    for _ in 0..5 {
+        assert!(limiter.try_acquire());
+    }
+    assert!(!limiter.try_acquire());
+
+    // Clones draw from the same bucket
+    assert!(!limiter.clone().try_acquire());
+}
+
+#[test]
+fn zero_budget_is_unlimited() {
+    let limiter = RateLimiter::per_second(0);
+    assert!((0..1000).all(|_| limiter.try_acquire()));
+}
+
+#[test]
+fn a_new_rate_applies_to_every_clone() {
+    let limiter = RateLimiter::per_second(1);
+    let shared = limiter.clone();
+    assert!(limiter.try_acquire());
+    assert!(!shared.try_acquire());
+
+    // A restarted bot resets the process-wide limiters to its own config
+    limiter.set_rate(3);
+    assert!((0..3).all(|_| shared.try_acquire()));
+    assert!(!shared.try_acquire());
+    limiter.set_rate(0);
+    assert!((0..1000).all(|_| shared.try_acquire()));
+}
+
+#[tokio::test]
+async fn acquire_waits_for_a_refill() {
+    let limiter = RateLimiter::per_second(20);
+    while limiter.try_acquire() {}
+
+    let started = Instant::now();
+    for _ in 0..4 {
+        limiter.acquire().await;
+    }
+    // 4 tokens at 20/s take ~200ms
+    assert!(started.elapsed() >= Duration::from_millis(150));
+}
@@ -0,0 +1,74 @@
+//! Fills reconciler acknowledgments: rejections are retried, never recorded
+
+mod common;
+
+use aero_arb_mm_bot::network::{ReconciliationAck, ReconciliationClient, ReconciliationRejected};
+use aero_arb_mm_bot::storage::save_reconciliation_ack;
+use chrono::Utc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use common::{load, simulated_execution};
+
+/// Reconciler answering every request with `ack`, counting the requests
+async fn reconciler(ack: serde_json::Value) -> (String, Arc<AtomicU32>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/fills", listener.local_addr().unwrap());
+    let requests = Arc::new(AtomicU32::new(0));
+    let counter = requests.clone();
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+            let body = ack.to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(), body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    });
+    (url, requests)
+}
+
+#[tokio::test]
+async fn rejected_acks_are_retried_then_reported() {
+    let (url, requests) = reconciler(serde_json::json!({
+        "execution_id": "execution", "accepted": false, "message": "unknown venue",
+    })).await;
+    let config = load(&format!("[reconciler]\nurl = \"{}\"\n", url)).unwrap();
+    let client = ReconciliationClient::from_config(&config).unwrap().unwrap();
+
+    let err = client.reconcile(&simulated_execution()).await.unwrap_err();
+    let cause = std::error::Error::source(&err).unwrap();
+    let rejected = cause.downcast_ref::<ReconciliationRejected>().unwrap();
+    assert_eq!(rejected.0.message.as_deref(), Some("unknown venue"));
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn accepted_acks_are_returned() {
+    let (url, requests) = reconciler(serde_json::json!({
+        "execution_id": "execution", "accepted": true, "reference": "r-1",
+    })).await;
+    let config = load(&format!("[reconciler]\nurl = \"{}\"\n", url)).unwrap();
+    let client = ReconciliationClient::from_config(&config).unwrap().unwrap();
+
+    let ack = client.reconcile(&simulated_execution()).await.unwrap();
+    assert_eq!(ack.reference.as_deref(), Some("r-1"));
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn rejected_acks_are_not_saved() {
+    let ack = ReconciliationAck {
+        execution_id: "execution".to_string(),
+        accepted: false,
+        reference: None,
+        message: None,
+        received_at: Utc::now(),
+    };
+    assert!(save_reconciliation_ack(&ack).is_err());
+}
@@ -0,0 +1,57 @@
+//! Per-cycle pool reserves cache
+
+use aero_arb_mm_bot::pools::{CacheStats, PoolStateCache};
+use alloy::primitives::{address, Address, U256};
+use std::time::Duration;
+
+const POOL: Address = address!("cDAC0d6c6C59727a65F871236188350531885C43");
+const OTHER: Address = address!("1111111111111111111111111111111111111111");
+
+fn reserves(r0: u64, r1: u64) -> (U256, U256) {
+    (U256::from(r0), U256::from(r1))
+}
+
+#[test]
+fn serves_reserves_until_invalidated() {
+    let cache = PoolStateCache::new(Duration::from_secs(60));
+    assert_eq!(cache.get(POOL), None);
+
+    cache.insert(POOL, reserves(10, 30_000));
+    assert_eq!(cache.get(POOL), Some(reserves(10, 30_000)));
+    assert_eq!(cache.get(OTHER), None);
+
+    cache.invalidate(POOL);
+    assert_eq!(cache.get(POOL), None);
+    assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 3 });
+}
+
+#[test]
+fn a_new_block_clears_entries_but_an_old_one_does_not() {
+    let cache = PoolStateCache::new(Duration::from_secs(60));
+    cache.advance_block(100);
+    cache.insert(POOL, reserves(1, 2));
+
+    cache.advance_block(100);
+    cache.advance_block(99);
+    assert_eq!(cache.get(POOL), Some(reserves(1, 2)));
+
+    cache.advance_block(101);
+    assert_eq!(cache.get(POOL), None);
+}
+
+#[test]
+fn entries_expire_after_the_ttl() {
+    let cache = PoolStateCache::new(Duration::from_millis(20));
+    cache.insert(POOL, reserves(1, 2));
+    std::thread::sleep(Duration::from_millis(40));
+    assert_eq!(cache.get(POOL), None);
+}
+
+#[test]
+fn zero_ttl_disables_the_cache() {
+    let cache = PoolStateCache::new(Duration::ZERO);
+    cache.insert(POOL, reserves(1, 2));
+    assert!(!cache.is_enabled());
+    assert_eq!(cache.get(POOL), None);
+    assert_eq!(cache.stats(), CacheStats::default());
+}
@@ -0,0 +1,174 @@
+//! Global loss, exposure and execution-rate limits
+
+mod common;
+
+use aero_arb_mm_bot::arbitrage::{calculate_arbitrage, FeeModel, DEFAULT_MIN_PRICE_DIFF_PCT};
+use aero_arb_mm_bot::control::control_channel;
+use aero_arb_mm_bot::risk::{RiskLimits, RiskManager};
+use aero_arb_mm_bot::types::{
+    ArbitrageOpportunity, ControlEvent, ExecutionStatus, SwapPreflight, TradeExecution,
+};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use common::simulated_execution;
+
+fn limits() -> RiskLimits {
+    RiskLimits {
+        max_daily_loss_usd: dec!(10),
+        max_open_exposure_eth: dec!(1.5),
+        max_executions_per_hour: 3,
+    }
+}
+
+fn opportunity() -> ArbitrageOpportunity {
+    calculate_arbitrage("WETH/USDC", dec!(2990), dec!(3000), dec!(1), None, None, dec!(1), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap()
+}
+
+fn execution(actual_profit_usd: Option<Decimal>, cex_fill: Option<Decimal>) -> TradeExecution {
+    TradeExecution {
+        status: ExecutionStatus::Success,
+        expected_profit_usd: dec!(9),
+        actual_profit_usd,
+        dex_fill_price: Some(dec!(2990)),
+        cex_fill_price: cex_fill,
+        ..simulated_execution()
+    }
+}
+
+#[tokio::test]
+async fn daily_loss_halts_execution_and_market_making() {
+    let (control_tx, mut control_rx) = control_channel();
+    let risk = RiskManager::new(limits()).with_control(control_tx);
+    let opp = opportunity();
+
+    assert!(risk.check_execution(&opp).await.is_ok());
+    risk.record_execution(&execution(Some(dec!(-6)), Some(dec!(2984))), &opp).await;
+    assert!(!risk.is_halted().await);
+    risk.record_execution(&execution(Some(dec!(-4)), Some(dec!(2986))), &opp).await;
+
+    let status = risk.status().await;
+    assert_eq!(status.daily_pnl_usd, dec!(-10));
+    assert!(status.halted.unwrap().contains("Daily loss"));
+    assert!(risk.check_execution(&opp).await.is_err());
+    assert_eq!(risk.market_making_allowance(dec!(0.5)).await, dec!(0));
+
+    match control_rx.recv().await {
+        Some(ControlEvent::ExecutionHalted { reason }) => assert!(reason.contains("Daily loss")),
+        other => panic!("expected ExecutionHalted, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn unhedged_legs_count_towards_open_exposure() {
+    let risk = RiskManager::new(limits());
+    let opp = opportunity();
+
+    // CEX leg failed: the WETH bought on the DEX stays open
+    risk.record_execution(&execution(None, None), &opp).await;
+    let status = risk.status().await;
+    assert_eq!(status.open_exposure_eth, dec!(1));
+    assert!(status.halted.is_none());
+
+    // Another failed hedge could take exposure to 2 ETH
+    assert!(risk.check_execution(&opp).await.unwrap_err().contains("exposure"));
+    assert_eq!(risk.market_making_allowance(dec!(2)).await, dec!(0.5));
+    assert_eq!(risk.market_making_allowance(dec!(0.2)).await, dec!(0.2));
+}
+
+/// A sent mainnet DEX leg filled at `dex_fill`, with no CEX order placed
+fn unhedged_mainnet_fill(dex_fill: Decimal) -> TradeExecution {
+    TradeExecution {
+        network: "Base Mainnet".to_string(),
+        tx_hash: Some("0xabc".to_string()),
+        dex_fill_price: Some(dex_fill),
+        // A modeled CEX price must not pass for a hedge
+        cex_fill_price: Some(dec!(2997)),
+        actual_profit_usd: Some(dec!(7)),
+        simulated: false,
+        preflight: Some(SwapPreflight { amount_out: Some(dec!(1)), revert_reason: None }),
+        ..execution(None, None)
+    }
+}
+
+#[tokio::test]
+async fn real_fills_without_a_cex_order_stay_open_and_book_only_realized_pnl() {
+    let risk = RiskManager::new(limits());
+    let opp = opportunity();
+    let quoted = opp.quoted_dex_price.unwrap_or(opp.dex_price);
+
+    // Filled 2 USD above the quote: gas plus the slippage, not the modeled profit
+    risk.record_execution(&unhedged_mainnet_fill(quoted + dec!(2)), &opp).await;
+    let status = risk.status().await;
+    assert_eq!(status.open_exposure_eth, dec!(1));
+    assert_eq!(status.daily_pnl_usd, dec!(-2) - opp.gas_cost_usd);
+
+    // A reverted preflight sent nothing and costs nothing
+    let reverted = TradeExecution {
+        status: ExecutionStatus::Failed,
+        tx_hash: None,
+        preflight: Some(SwapPreflight { amount_out: None, revert_reason: Some("expired".to_string()) }),
+        ..unhedged_mainnet_fill(quoted)
+    };
+    risk.record_execution(&reverted, &opp).await;
+    let status = risk.status().await;
+    assert_eq!(status.open_exposure_eth, dec!(1));
+    assert_eq!(status.daily_pnl_usd, dec!(-2) - opp.gas_cost_usd);
+
+    // A real hedge order closes the leg and books its two-leg result
+    let hedged = TradeExecution { cex_order_id: Some("42".to_string()), ..unhedged_mainnet_fill(quoted) };
+    risk.record_execution(&hedged, &opp).await;
+    let status = risk.status().await;
+    assert_eq!(status.open_exposure_eth, dec!(1));
+    assert_eq!(status.daily_pnl_usd, dec!(5) - opp.gas_cost_usd);
+}
+
+#[tokio::test]
+async fn executions_per_hour_are_capped() {
+    let risk = RiskManager::new(limits());
+    let opp = opportunity();
+
+    for _ in 0..3 {
+        assert!(risk.check_execution(&opp).await.is_ok());
+        risk.record_execution(&execution(Some(dec!(1)), Some(dec!(3000))), &opp).await;
+    }
+    let status = risk.status().await;
+    assert_eq!(status.executions_last_hour, 3);
+    assert!(status.halted.is_none());
+    assert!(risk.check_execution(&opp).await.unwrap_err().contains("rate limit"));
+    // The rate limit skips trades without halting market making
+    assert_eq!(risk.market_making_allowance(dec!(0.5)).await, dec!(0.5));
+}
+
+#[tokio::test]
+async fn manual_halt_until_resumed() {
+    let risk = RiskManager::new(limits());
+    let opp = opportunity();
+
+    risk.halt("operator request").await;
+    assert_eq!(risk.check_execution(&opp).await.unwrap_err(), "operator request");
+    risk.resume().await;
+    assert!(risk.check_execution(&opp).await.is_ok());
+}
+
+#[tokio::test]
+async fn admitted_trades_hold_their_budget_until_dropped() {
+    let risk = RiskManager::new(limits());
+    let opp = opportunity();
+
+    // Two concurrent 1 ETH trades cannot both fit under 1.5 ETH of exposure
+    let first = risk.check_execution(&opp).await.unwrap();
+    assert!(risk.check_execution(&opp).await.unwrap_err().contains("exposure"));
+    assert!(risk.can_execute(&opp).await.is_err());
+    assert_eq!(risk.market_making_allowance(dec!(1)).await, dec!(0.5));
+    drop(first);
+    assert!(risk.can_execute(&opp).await.is_ok());
+
+    // Reserved rate slots count against the hourly cap
+    let small = ArbitrageOpportunity { size_eth: dec!(0.1), ..opportunity() };
+    let held: Vec<_> = futures::future::join_all((0..3).map(|_| risk.check_execution(&small))).await;
+    assert!(held.iter().all(Result::is_ok));
+    assert!(risk.check_execution(&small).await.unwrap_err().contains("rate limit"));
+    drop(held);
+    assert!(risk.check_execution(&small).await.is_ok());
+    assert_eq!(risk.status().await.executions_last_hour, 0);
+}
@@ -0,0 +1,82 @@
+//! Session statistics aggregated across restarts and per UTC day
+
+use aero_arb_mm_bot::storage::{
+    build_daily_report, load_lifetime_stats_from, render_daily_report_markdown, save_lifetime_stats_to,
+    SessionStatsRecorder,
+};
+use aero_arb_mm_bot::types::SessionCounters;
+use chrono::{NaiveDate, TimeZone, Utc};
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+
+fn counters(total_opportunities: u64, total_executions: u64) -> SessionCounters {
+    SessionCounters {
+        total_opportunities,
+        total_potential_profit_usd: rust_decimal::Decimal::from(total_opportunities) / dec!(2),
+        total_executions,
+        ..Default::default()
+    }
+}
+
+fn day(d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+}
+
+#[test]
+fn folds_only_what_each_run_added() {
+    let start = Utc.with_ymd_and_hms(2026, 3, 2, 22, 0, 0).unwrap();
+    let mut recorder = SessionStatsRecorder::new(Default::default(), &counters(0, 0), start);
+    let mut errors = HashMap::from([("cex_price".to_string(), 2)]);
+    recorder.record(day(2), &counters(10, 1), &errors, 600);
+    errors.insert("cex_price".to_string(), 3);
+    recorder.record(day(2), &counters(14, 2), &errors, 900);
+    // The run crosses midnight
+    recorder.record(day(3), &counters(20, 2), &errors, 1500);
+
+    let lifetime = recorder.lifetime().clone();
+    assert_eq!((lifetime.sessions, lifetime.runtime_secs), (1, 1500));
+    assert_eq!(lifetime.first_started_at, Some(start));
+    assert_eq!(lifetime.counters, counters(20, 2));
+    assert_eq!(lifetime.error_counts["cex_price"], 3);
+    let monday = recorder.day(day(2)).unwrap();
+    assert_eq!((monday.sessions, monday.runtime_secs, monday.counters.total_opportunities), (1, 900, 14));
+    assert_eq!(monday.error_counts["cex_price"], 3);
+    let tuesday = recorder.day(day(3)).unwrap();
+    assert_eq!((tuesday.sessions, tuesday.runtime_secs, tuesday.counters.total_opportunities), (1, 600, 6));
+    assert!(tuesday.error_counts.is_empty());
+
+    // The next run resumes its counters from the saved state and counts on top
+    let restart = Utc.with_ymd_and_hms(2026, 3, 3, 8, 0, 0).unwrap();
+    let mut recorder = SessionStatsRecorder::new(lifetime, &counters(20, 2), restart);
+    recorder.record(day(3), &counters(25, 4), &HashMap::new(), 60);
+    let lifetime = recorder.lifetime();
+    assert_eq!((lifetime.sessions, lifetime.runtime_secs), (2, 1560));
+    assert_eq!(lifetime.counters, counters(25, 4));
+    assert_eq!(lifetime.first_started_at, Some(start));
+    let tuesday = recorder.day(day(3)).unwrap();
+    assert_eq!((tuesday.sessions, tuesday.counters.total_opportunities, tuesday.counters.total_executions), (2, 11, 2));
+}
+
+#[test]
+fn lifetime_stats_feed_the_daily_report() {
+    let dir = std::env::temp_dir().join(format!("session_stats_{}", std::process::id()));
+    let path = dir.join("stats/lifetime.json");
+    assert!(load_lifetime_stats_from(&path).unwrap().is_none());
+
+    let start = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+    let mut recorder = SessionStatsRecorder::new(Default::default(), &counters(0, 0), start);
+    recorder.record(day(2), &counters(8, 3), &HashMap::from([("rpc".to_string(), 1)]), 7200);
+    save_lifetime_stats_to(&path, recorder.lifetime()).unwrap();
+    assert_eq!(load_lifetime_stats_from(&path).unwrap().as_ref(), Some(recorder.lifetime()));
+
+    let report = build_daily_report(&dir, day(2)).unwrap();
+    let sessions = report.sessions.as_ref().unwrap();
+    assert_eq!((sessions.sessions, sessions.runtime_secs, sessions.counters.total_executions), (1, 7200, 3));
+    let markdown = render_daily_report_markdown(&report);
+    assert!(markdown.contains("| Runtime | 2.0 h |"));
+    assert!(markdown.contains("| Errors | 1 |"));
+
+    // A day the bot didn't run
+    assert!(build_daily_report(&dir, day(4)).unwrap().sessions.is_none());
+    std::fs::remove_dir_all(dir).ok();
+}
@@ -0,0 +1,67 @@
+//! Calldata whitelist enforced before signing
+
+use aero_arb_mm_bot::execution::{selector, TransactionPolicy, AERODROME_SWAP_SIGNATURE};
+use aero_arb_mm_bot::network::IERC20;
+use aero_arb_mm_bot::types::{AERODROME_ROUTER_MAINNET, USDC_MAINNET, WETH_MAINNET};
+use alloy::primitives::{address, U256};
+use alloy::rpc::types::eth::TransactionRequest;
+use alloy::sol_types::SolCall;
+
+const WALLET: alloy::primitives::Address = address!("1111111111111111111111111111111111111111");
+
+fn policy() -> TransactionPolicy {
+    let mut policy = TransactionPolicy::with_limits(500_000, U256::ZERO, Some(WALLET));
+    policy.allow(AERODROME_ROUTER_MAINNET, selector(AERODROME_SWAP_SIGNATURE));
+    policy
+}
+
+fn call(to: alloy::primitives::Address, data: Vec<u8>) -> TransactionRequest {
+    TransactionRequest::default().to(to).input(data.into()).gas_limit(300_000)
+}
+
+#[test]
+fn whitelisted_router_swap_passes() {
+    let mut data = selector(AERODROME_SWAP_SIGNATURE).to_vec();
+    data.extend_from_slice(&[0u8; 64]);
+    assert!(policy().check(&call(AERODROME_ROUTER_MAINNET, data)).is_ok());
+}
+
+#[test]
+fn unlisted_contracts_and_selectors_are_rejected() {
+    let approve = selector("approve(address,uint256)").to_vec();
+    assert!(policy().check(&call(AERODROME_ROUTER_MAINNET, approve.clone())).is_err());
+    assert!(policy().check(&call(USDC_MAINNET, approve)).is_err());
+}
+
+#[test]
+fn value_gas_and_transfers_are_bounded() {
+    let swap = selector(AERODROME_SWAP_SIGNATURE).to_vec();
+    let with_value = call(AERODROME_ROUTER_MAINNET, swap.clone()).value(U256::from(1));
+    assert!(policy().check(&with_value).is_err());
+    let over_gas = call(AERODROME_ROUTER_MAINNET, swap.clone()).gas_limit(2_000_000);
+    assert!(policy().check(&over_gas).is_err());
+    let no_gas = TransactionRequest::default().to(AERODROME_ROUTER_MAINNET).input(swap.into());
+    assert!(policy().check(&no_gas).is_err());
+
+    let cancellation = TransactionRequest::default().to(WALLET).value(U256::ZERO).gas_limit(21_000);
+    assert!(policy().check(&cancellation).is_ok());
+    let transfer_out = TransactionRequest::default().to(USDC_MAINNET).gas_limit(21_000);
+    assert!(policy().check(&transfer_out).is_err());
+}
+
+#[test]
+fn approvals_need_a_pair_token_and_a_whitelisted_spender() {
+    let mut policy = policy();
+    policy.allow_approvals_to(AERODROME_ROUTER_MAINNET);
+    let approve = |spender| IERC20::approveCall { spender, amount: U256::from(1) }.abi_encode();
+    let arbitrary = address!("2222222222222222222222222222222222222222");
+
+    // Whitelisted spender, but no pool traded yet
+    assert!(policy.check(&call(USDC_MAINNET, approve(AERODROME_ROUTER_MAINNET))).is_err());
+
+    policy.allow_approvals_on(USDC_MAINNET);
+    assert!(policy.check(&call(USDC_MAINNET, approve(AERODROME_ROUTER_MAINNET))).is_ok());
+    assert!(policy.check(&call(USDC_MAINNET, approve(arbitrary))).is_err());
+    assert!(policy.check(&call(WETH_MAINNET, approve(AERODROME_ROUTER_MAINNET))).is_err());
+    assert!(policy.check(&call(arbitrary, approve(AERODROME_ROUTER_MAINNET))).is_err());
+}
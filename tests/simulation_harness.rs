@@ -0,0 +1,221 @@
+//! Pool, arbitrage, validation and simulated execution paths against the
+//! scripted mock chain. Run with `cargo test --features test-support`.
+
+mod common;
+
+use aero_arb_mm_bot::arbitrage::{calculate_arbitrage, FeeModel, DEFAULT_MIN_PRICE_DIFF_PCT};
+use aero_arb_mm_bot::config::Config;
+use aero_arb_mm_bot::errors::BotError;
+use aero_arb_mm_bot::execution::{
+    check_wallet_balances, create_simulated_execution, simulation_rng, ExecutionCalibration, WALLET_BALANCES,
+};
+use aero_arb_mm_bot::network::{call_contract, IAerodromePool, IERC20};
+use aero_arb_mm_bot::pools::{calculate_pool_price_safe, get_pool_info_internal, VOLATILE_POOL_FEE};
+use aero_arb_mm_bot::test_support::{seeded_rng, MockChain, DEFAULT_TEST_SEED};
+use aero_arb_mm_bot::types::{
+    ExecutionUrgency, PoolInfo, VolatilityAdjustments, VolatilityConfidence, VolatilityEstimator, VolatilityImpact,
+    VolatilityMetrics, VolatilityTrend, WETH_MAINNET,
+};
+use aero_arb_mm_bot::validation::validate_opportunity_with_volatility;
+use alloy::primitives::{address, Address, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::eth::TransactionRequest;
+use alloy::sol_types::SolCall;
+use rust_decimal_macros::dec;
+use std::time::Instant;
+use common::{load, weth_usdc_pool};
+
+/// Pool reserves are cached process-wide by address, so each test uses its own pool
+fn pool_at(address: Address) -> PoolInfo {
+    PoolInfo { address, ..weth_usdc_pool() }
+}
+
+/// `weth` WETH against `usdc` USDC, in raw token units
+fn reserves(weth: u64, usdc: u64) -> (U256, U256) {
+    (U256::from(weth) * U256::from(10).pow(U256::from(18)), U256::from(usdc) * U256::from(1_000_000))
+}
+
+fn volatility(impact_assessment: VolatilityImpact) -> VolatilityMetrics {
+    VolatilityMetrics {
+        short_term_volatility: dec!(0.3),
+        medium_term_volatility: dec!(0.3),
+        long_term_volatility: dec!(0.3),
+        estimator: VolatilityEstimator::Realized,
+        sample_count: 60,
+        confidence: VolatilityConfidence::Full,
+        volatility_trend: VolatilityTrend::Stable,
+        impact_assessment,
+        recommended_adjustments: VolatilityAdjustments {
+            spread_multiplier: dec!(1),
+            position_size_factor: dec!(1),
+            execution_urgency: ExecutionUrgency::Normal,
+        },
+        pool: None,
+    }
+}
+
+fn test_config() -> Config {
+    load("").unwrap()
+}
+
+#[tokio::test]
+async fn pool_info_and_price_come_from_scripted_state() {
+    let chain = MockChain::new();
+    // A custom fee set on the factory, not the 30 bps volatile default
+    let pool = PoolInfo { fee: dec!(0.0042), ..pool_at(address!("00000000000000000000000000000000000a0001")) };
+    let (weth, usdc) = reserves(100, 297_000);
+    chain.set_basic_pool(&pool, weth, usdc);
+    let provider = chain.provider();
+
+    let info = get_pool_info_internal(&provider, "WETH/USDC", pool.address, false, None, &test_config()).await.unwrap();
+    assert_eq!(info.pair.base, WETH_MAINNET);
+    assert_eq!(info.pair.quote_decimals, 6);
+    assert!(!info.is_stable);
+    assert_eq!(info.fee, dec!(0.0042));
+
+    // Without a readable factory the pool type's default applies
+    chain.revert_call::<IAerodromePool::factoryCall>(pool.address, "no factory");
+    let info = get_pool_info_internal(&provider, "WETH/USDC", pool.address, false, None, &test_config()).await.unwrap();
+    assert_eq!(info.fee, VOLATILE_POOL_FEE);
+
+    let dex_price = calculate_pool_price_safe(&provider, &pool).await.unwrap();
+    assert_eq!(dex_price, dec!(2970));
+
+    let opportunity = calculate_arbitrage(&pool.name, dex_price, dec!(3000), dec!(1), None, None, dec!(0.05), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT)
+        .expect("a 1% discount is an opportunity");
+    assert!(opportunity.direction.starts_with("Buy on Aerodrome"));
+    assert_eq!(opportunity.net_profit_usd, dec!(29.95));
+}
+
+#[tokio::test]
+async fn validation_reads_scripted_liquidity() {
+    let chain = MockChain::new();
+    let deep = pool_at(address!("00000000000000000000000000000000000a0002"));
+    let shallow = pool_at(address!("00000000000000000000000000000000000a0003"));
+    let (weth, usdc) = reserves(1_000, 2_970_000);
+    chain.set_basic_pool(&deep, weth, usdc);
+    chain.set_reserves(shallow.address, U256::from(10).pow(U256::from(16)), U256::from(29_700_000));
+    let provider = chain.provider();
+
+    let opportunity = calculate_arbitrage(&deep.name, dec!(2970), dec!(3000), dec!(1), None, None, dec!(0.05), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    let volatility = volatility(VolatilityImpact::Low);
+
+    let deep_result = validate_opportunity_with_volatility(&opportunity, &deep, &provider, &volatility, None, None, None, None).await;
+    assert!(deep_result.liquidity_check, "{:?}", deep_result.warnings);
+
+    let shallow_result = validate_opportunity_with_volatility(&opportunity, &shallow, &provider, &volatility, None, None, None, None).await;
+    assert!(!shallow_result.liquidity_check);
+    assert!(!shallow_result.all_passed);
+}
+
+#[tokio::test]
+async fn unscripted_and_reverted_calls_fail() {
+    let chain = MockChain::new();
+    let pool = address!("00000000000000000000000000000000000a0004");
+    chain.set_block_number(1_000);
+    let provider = chain.provider();
+
+    assert_eq!(provider.get_block_number().await.unwrap(), 1_000);
+    assert_eq!(chain.advance_block(), 1_001);
+    assert_eq!(provider.get_block_number().await.unwrap(), 1_001);
+
+    let err = call_contract(&provider, pool, IAerodromePool::getReservesCall {}).await.unwrap_err();
+    assert!(format!("{:#}", err).contains("no scripted result"), "{:#}", err);
+
+    chain.revert_call::<IAerodromePool::getReservesCall>(pool, "paused");
+    let err = call_contract(&provider, pool, IAerodromePool::getReservesCall {}).await.unwrap_err();
+    assert!(format!("{:#}", err).contains("paused"), "{:#}", err);
+
+    assert_eq!(chain.requests(), ["eth_blockNumber", "eth_blockNumber", "eth_call", "eth_call"]);
+}
+
+#[tokio::test]
+async fn configured_seed_replays_simulated_executions() {
+    let config = load("[monitoring]\nsimulation_seed = 7\n").unwrap();
+    assert_eq!(config.simulation_seed, Some(7));
+    assert_eq!(test_config().simulation_seed, None);
+
+    let opportunity = calculate_arbitrage("WETH/USDC", dec!(2970), dec!(3000), dec!(1), None, None, dec!(0.05), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    let volatility = volatility(VolatilityImpact::Extreme);
+    let calibration = ExecutionCalibration::default();
+
+    let mut tx_hashes = Vec::new();
+    for _ in 0..2 {
+        let (mut rng, seed) = simulation_rng(config.simulation_seed);
+        assert_eq!(seed, 7);
+        let execution = create_simulated_execution(
+            "replay".to_string(),
+            &opportunity,
+            &volatility,
+            &calibration,
+            Instant::now(),
+            &mut rng,
+        ).await.unwrap();
+        tx_hashes.push(execution.tx_hash);
+    }
+    assert_eq!(tx_hashes[0], tx_hashes[1]);
+
+    // Unseeded runs report the seed they drew
+    let (_, drawn) = simulation_rng(None);
+    let (_, other) = simulation_rng(None);
+    assert_ne!(drawn, other);
+}
+
+#[tokio::test]
+async fn seeded_simulations_repeat_exactly() {
+    let opportunity = calculate_arbitrage("WETH/USDC", dec!(2970), dec!(3000), dec!(1), None, None, dec!(0.05), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    // Extreme volatility has the lowest success rate, so a run mixes fills and failures
+    let volatility = volatility(VolatilityImpact::Extreme);
+    let calibration = ExecutionCalibration::default();
+
+    let mut outcomes = Vec::new();
+    for _ in 0..2 {
+        let mut rng = seeded_rng(DEFAULT_TEST_SEED);
+        let mut run = Vec::new();
+        for i in 0..8 {
+            let execution = create_simulated_execution(
+                i.to_string(),
+                &opportunity,
+                &volatility,
+                &calibration,
+                Instant::now(),
+                &mut rng,
+            ).await.unwrap();
+            run.push((execution.tx_hash, execution.actual_profit_usd, execution.slippage_bps));
+        }
+        outcomes.push(run);
+    }
+    assert_eq!(outcomes[0], outcomes[1]);
+}
+
+#[tokio::test]
+async fn unfunded_wallets_fail_before_sending() {
+    let chain = MockChain::new();
+    let provider = chain.provider();
+    let wallet = address!("00000000000000000000000000000000000000aa");
+    let token = address!("0000000000000000000000000000000000000b01");
+    chain.set_call::<IERC20::symbolCall>(token, IERC20::symbolCall::abi_encode_returns(&("USDC".to_string(),)));
+    chain.set_call::<IERC20::decimalsCall>(token, IERC20::decimalsCall::abi_encode_returns(&(6u8,)));
+    chain.set_call::<IERC20::balanceOfCall>(token, IERC20::balanceOfCall::abi_encode_returns(&(U256::from(50_000_000u64),)));
+    // 0.001 ETH against 0.0003 ETH of worst-case gas
+    chain.set_response("eth_getBalance", serde_json::json!("0x38d7ea4c68000"));
+    let tx = TransactionRequest::default().gas_limit(300_000).max_fee_per_gas(1_000_000_000);
+
+    check_wallet_balances(&provider, wallet, &tx, Some((token, U256::from(40_000_000u64)))).await.unwrap();
+    let balances = WALLET_BALANCES.snapshot().unwrap();
+    assert_eq!((balances.eth, balances.tokens["USDC"]), (dec!(0.001), dec!(50)));
+    assert_eq!(balances.shortfall, None);
+
+    let short_of = |e: anyhow::Error| match e.downcast::<BotError>() {
+        Ok(BotError::InsufficientBalance { asset, required, available, .. }) => (asset, required, available),
+        other => panic!("expected InsufficientBalance, got {:?}", other),
+    };
+    let err = check_wallet_balances(&provider, wallet, &tx, Some((token, U256::from(60_000_000u64)))).await.unwrap_err();
+    assert_eq!(short_of(err), ("USDC".to_string(), dec!(60), dec!(50)));
+    assert!(WALLET_BALANCES.snapshot().unwrap().shortfall.is_some());
+
+    // 0.003 ETH of gas at a 10 gwei max fee
+    let tx = tx.max_fee_per_gas(10_000_000_000);
+    let err = check_wallet_balances(&provider, wallet, &tx, None).await.unwrap_err();
+    assert_eq!(short_of(err), ("ETH".to_string(), dec!(0.003), dec!(0.001)));
+}
@@ -0,0 +1,47 @@
+//! Reserve-based execution pricing for arbitrage P&L
+
+mod common;
+
+use aero_arb_mm_bot::arbitrage::{calculate_arbitrage, FeeModel, DEFAULT_MIN_PRICE_DIFF_PCT};
+use aero_arb_mm_bot::pools::{PoolReserves, VOLATILE_POOL_FEE};
+use rust_decimal_macros::dec;
+use common::weth_usdc_pool;
+
+#[test]
+fn small_trades_pay_only_the_pool_fee() {
+    let reserves = PoolReserves::new(&weth_usdc_pool(), dec!(1000), dec!(3000000), dec!(1));
+    let quote = reserves.quote(dec!(0.001)).unwrap();
+    let fee_adjusted = dec!(3000) * (dec!(1) - VOLATILE_POOL_FEE);
+    assert!((quote.sell_price - fee_adjusted).abs() < dec!(0.01));
+    assert!(quote.buy_price > dec!(3000));
+}
+
+#[test]
+fn gross_profit_reflects_price_impact_for_the_trade_size() {
+    let reserves = PoolReserves::new(&weth_usdc_pool(), dec!(1000), dec!(3000000), dec!(1));
+    let no_fees = FeeModel::zero();
+    let mid_only = calculate_arbitrage("WETH/USDC", dec!(3000), dec!(3100), dec!(10), None, None, dec!(0), &no_fees, DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    let curve = calculate_arbitrage("WETH/USDC", dec!(3000), dec!(3100), dec!(10), None, Some(&reserves), dec!(0), &no_fees, DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+
+    assert_eq!(mid_only.gross_profit_usd, dec!(1000));
+    // Buying 10 WETH out of 1000 moves the price ~1% plus the 0.3% fee
+    let buy_price = curve.quoted_dex_price.unwrap();
+    assert!(buy_price > dec!(3035) && buy_price < dec!(3045));
+    assert!(curve.gross_profit_usd < dec!(650) && curve.gross_profit_usd > dec!(550));
+}
+
+#[test]
+fn net_profit_pays_cex_fee_transfer_cost_and_unquoted_dex_fee() {
+    let fees = FeeModel { cex_fee_rate: dec!(0.001), dex_fee_rate: dec!(0.003), transfer_cost_usd: dec!(5) };
+    let mid_only = calculate_arbitrage("WETH/USDC", dec!(3000), dec!(3100), dec!(10), None, None, dec!(2), &fees, DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    assert_eq!(mid_only.cex_fee_usd, dec!(31));
+    assert_eq!(mid_only.dex_fee_usd, dec!(90));
+    assert_eq!(mid_only.net_profit_usd, dec!(1000) - dec!(2) - dec!(31) - dec!(90) - dec!(5));
+    assert_eq!(mid_only.roi_pct, mid_only.net_profit_usd / dec!(31000) * dec!(100));
+
+    // The reserve quote already charges the pool fee
+    let reserves = PoolReserves::new(&weth_usdc_pool(), dec!(1000), dec!(3000000), dec!(1));
+    let curve = calculate_arbitrage("WETH/USDC", dec!(3000), dec!(3100), dec!(10), None, Some(&reserves), dec!(0), &fees, DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    assert_eq!(curve.dex_fee_usd, dec!(0));
+    assert_eq!(curve.net_profit_usd, curve.gross_profit_usd - dec!(36));
+}
@@ -0,0 +1,53 @@
+//! Spread observation stream and hour-of-day × pool aggregation
+
+use aero_arb_mm_bot::storage::{
+    build_spread_heatmap, load_spread_observations, save_spread_observations_in, SpreadObservation,
+};
+use chrono::{TimeZone, Utc};
+use rust_decimal_macros::dec;
+
+fn observation(hour: u32, pool: &str, spread_bps: rust_decimal::Decimal, edge_bps: Option<rust_decimal::Decimal>) -> SpreadObservation {
+    SpreadObservation {
+        timestamp: Utc.with_ymd_and_hms(2026, 3, 2, hour, 15, 0).unwrap(),
+        pool: pool.to_string(),
+        spread_bps,
+        edge_bps,
+        liquidity_usd: Some(dec!(1000000)),
+        health_score: None,
+    }
+}
+
+#[test]
+fn observations_round_trip_through_the_csv_stream() {
+    let dir = std::env::temp_dir().join(format!("spreads_{}", std::process::id()));
+    let observations = vec![
+        observation(9, "WETH/USDC", dec!(-12.5), Some(dec!(-20.25))),
+        observation(9, "WETH/USDC", dec!(3), None),
+    ];
+    save_spread_observations_in(&dir, &observations[..1]).unwrap();
+    save_spread_observations_in(&dir, &observations[1..]).unwrap();
+
+    assert_eq!(load_spread_observations(&dir).unwrap(), observations);
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn heatmap_groups_by_pool_and_hour() {
+    let heatmap = build_spread_heatmap(&[
+        observation(9, "WETH/USDC", dec!(-10), Some(dec!(5))),
+        observation(9, "WETH/USDC", dec!(20), Some(dec!(-15))),
+        observation(14, "WETH/USDC", dec!(4), None),
+        observation(9, "vAMM-WETH/USDbC", dec!(2), None),
+    ]);
+
+    assert_eq!(heatmap.observations, 4);
+    assert_eq!(heatmap.cells.len(), 3);
+    let morning = heatmap.cells.iter().find(|c| c.pool == "WETH/USDC" && c.hour == 9).unwrap();
+    assert_eq!(morning.samples, 2);
+    assert_eq!(morning.mean_abs_spread_bps, dec!(15));
+    assert_eq!(morning.max_abs_spread_bps, dec!(20));
+    assert_eq!(morning.mean_edge_bps, Some(dec!(-5)));
+    assert_eq!(morning.positive_edge_pct, Some(dec!(50)));
+    let afternoon = heatmap.cells.iter().find(|c| c.hour == 14).unwrap();
+    assert_eq!(afternoon.mean_edge_bps, None);
+}
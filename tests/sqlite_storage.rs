@@ -0,0 +1,77 @@
+//! SQLite timestamps compare chronologically as text, and the process-wide
+//! database survives a bot restart
+
+use aero_arb_mm_bot::arbitrage::{calculate_arbitrage, FeeModel, DEFAULT_MIN_PRICE_DIFF_PCT};
+use aero_arb_mm_bot::storage::{close_database, database, init_database, StorageDb};
+use aero_arb_mm_bot::types::ArbitrageOpportunity;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+
+fn opportunity(id: &str, timestamp: DateTime<Utc>) -> ArbitrageOpportunity {
+    let mut opp = calculate_arbitrage("WETH/USDC", dec!(2990), dec!(3000), dec!(1), None, None, dec!(1), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    opp.id = id.to_string();
+    opp.timestamp = timestamp;
+    opp
+}
+
+fn db_path(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("sqlite_{}_{}.db", name, std::process::id()));
+    std::fs::remove_file(&path).ok();
+    path.to_string_lossy().into_owned()
+}
+
+#[test]
+fn opportunities_since_compares_times_not_strings() {
+    let path = db_path("since");
+    let db = StorageDb::open(&path).unwrap();
+    let noon = Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap();
+    // A whole second and sub-second times format differently with to_rfc3339
+    db.insert_opportunity(&opportunity("whole", noon)).unwrap();
+    db.insert_opportunity(&opportunity("later", noon + Duration::milliseconds(250))).unwrap();
+    db.insert_opportunity(&opportunity("earlier", noon - Duration::microseconds(1500))).unwrap();
+
+    let ids = |since| db.opportunities_since(since, dec!(0)).unwrap().into_iter().map(|r| r.id).collect::<Vec<_>>();
+    assert_eq!(ids(noon), ["later", "whole"]);
+    assert_eq!(ids(noon + Duration::milliseconds(1)), ["later"]);
+    assert_eq!(ids(noon - Duration::seconds(1))[2], "earlier");
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn timestamps_from_earlier_versions_are_normalized() {
+    let path = db_path("legacy");
+    let noon = Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap();
+    StorageDb::open(&path).unwrap()
+        .insert_opportunity(&opportunity("legacy", noon + Duration::microseconds(123_456))).unwrap();
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    conn.execute("UPDATE opportunities SET timestamp = '2026-03-02T12:00:00.123456+00:00'", []).unwrap();
+    drop(conn);
+
+    let db = StorageDb::open(&path).unwrap();
+    let rows = db.opportunities_since(noon + Duration::milliseconds(123), dec!(0)).unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].timestamp, noon + Duration::milliseconds(123));
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn reopening_the_database_keeps_or_replaces_it() {
+    let (first, second) = (db_path("first"), db_path("second"));
+    init_database(&first).unwrap();
+    let db = database().unwrap();
+
+    // A second run in the same process opens the same path again
+    init_database(&first).unwrap();
+    assert!(Arc::ptr_eq(&db, &database().unwrap()));
+    init_database(&second).unwrap();
+    assert!(!Arc::ptr_eq(&db, &database().unwrap()));
+
+    close_database();
+    assert!(database().is_none());
+    for path in [first, second] {
+        std::fs::remove_file(path).ok();
+    }
+}
@@ -0,0 +1,80 @@
+//! Property-based tests for the Solidly stable-swap (sAMM) curve
+
+use aero_arb_mm_bot::pools::{estimate_price_impact_bps, stable_amount_out, stable_spot_price, STABLE_POOL_FEE};
+use proptest::prelude::*;
+use rust_decimal::prelude::*;
+
+fn invariant(x: f64, y: f64) -> f64 {
+    x * x * x * y + x * y * y * y
+}
+
+proptest! {
+    #[test]
+    fn newton_solve_keeps_the_invariant(
+        reserve_x in 1_000u64..1_000_000_000,
+        ratio in 1u64..=100,
+        amount_pct in 1u64..=100,
+    ) {
+        // Reserves up to 100:1 apart, trades up to the whole input reserve
+        let (x, y) = (reserve_x as f64, (reserve_x * ratio) as f64);
+        let amount_in = x * amount_pct as f64 / 100.0;
+        let out = stable_amount_out(
+            Decimal::from_f64(amount_in).unwrap(),
+            Decimal::from_f64(x).unwrap(),
+            Decimal::from_f64(y).unwrap(),
+            Decimal::ZERO,
+        ).unwrap().to_f64().unwrap();
+
+        prop_assert!(out > 0.0 && out < y);
+        let (before, after) = (invariant(x, y), invariant(x + amount_in, y - out));
+        prop_assert!(((after - before) / before).abs() < 1e-9, "k moved from {} to {}", before, after);
+    }
+
+    #[test]
+    fn output_never_beats_the_spot_price(reserve in 1_000u64..1_000_000_000, amount_pct in 1u64..=100) {
+        let reserve = Decimal::from(reserve);
+        let amount_in = reserve * Decimal::from(amount_pct) / Decimal::from(100);
+        let spot = stable_spot_price(reserve, reserve).unwrap();
+        let out = stable_amount_out(amount_in, reserve, reserve, Decimal::ZERO).unwrap();
+        let with_fee = stable_amount_out(amount_in, reserve, reserve, STABLE_POOL_FEE).unwrap();
+        prop_assert!(out <= amount_in * spot);
+        prop_assert!(with_fee < out);
+    }
+
+    #[test]
+    fn balanced_reserves_price_at_par(reserve in 1u64..u64::MAX) {
+        let reserve = Decimal::from(reserve);
+        let price = stable_spot_price(reserve, reserve).unwrap();
+        prop_assert!((price - Decimal::ONE).abs() < Decimal::new(1, 9), "spot {}", price);
+    }
+
+    #[test]
+    fn price_impact_grows_with_size(reserve in 1_000_000u64..1_000_000_000, amount_bps in 1u64..=5_000) {
+        let reserve = Decimal::from(reserve);
+        let amount_in = reserve * Decimal::from(amount_bps) / Decimal::from(10_000);
+        let impact = |is_stable: bool, amount: Decimal| {
+            estimate_price_impact_bps(is_stable, amount, reserve, reserve).unwrap()
+        };
+
+        let stable = impact(true, amount_in);
+        prop_assert!(stable >= Decimal::ZERO);
+        prop_assert!(impact(true, amount_in * Decimal::TWO) >= stable);
+        // The flat middle of the stable curve slips less than x*y=k
+        prop_assert!(stable <= impact(false, amount_in));
+    }
+}
+
+#[test]
+fn stable_curve_prices_unbalanced_reserves_off_the_ratio() {
+    // 2:1 reserves: x*y=k would say 2, the stable curve stays much closer to par
+    let price = stable_spot_price(Decimal::from(1_000_000), Decimal::from(2_000_000)).unwrap();
+    assert!(price > Decimal::ONE && price < Decimal::TWO);
+}
+
+#[test]
+fn empty_reserves_have_no_price_or_impact() {
+    assert_eq!(stable_spot_price(Decimal::ZERO, Decimal::ONE), None);
+    assert_eq!(stable_amount_out(Decimal::ONE, Decimal::ZERO, Decimal::ONE, STABLE_POOL_FEE), None);
+    assert_eq!(estimate_price_impact_bps(true, Decimal::ONE, Decimal::ZERO, Decimal::ONE), None);
+    assert_eq!(estimate_price_impact_bps(false, Decimal::ONE, Decimal::ZERO, Decimal::ONE), None);
+}
@@ -0,0 +1,73 @@
+//! When the periodic session statistics are printed
+
+mod common;
+
+use aero_arb_mm_bot::bot::session::{mark_statistics_printed, should_print_statistics};
+use aero_arb_mm_bot::config::DEFAULT_STATS_INTERVAL_SECS;
+use aero_arb_mm_bot::types::{BotState, MonitoringState, SessionCounters};
+use std::time::{Duration, Instant};
+use common::load;
+
+#[test]
+fn quiet_sessions_print_on_the_interval() {
+    let config = load("").unwrap();
+    let start = Instant::now();
+    let mut state = MonitoringState::new();
+    let interval = Duration::from_secs(DEFAULT_STATS_INTERVAL_SECS);
+
+    assert!(!should_print_statistics(&state, &config, start, start + interval - Duration::from_secs(1)));
+    assert!(should_print_statistics(&state, &config, start, start + interval));
+    mark_statistics_printed(&mut state, start + interval);
+    assert!(!should_print_statistics(&state, &config, start, start + interval + Duration::from_secs(60)));
+    assert!(should_print_statistics(&state, &config, start, start + interval * 2));
+
+    let counts_only = load("[monitoring]\nstats_interval_secs = 0\n").unwrap();
+    assert!(!should_print_statistics(&MonitoringState::new(), &counts_only, start, start + interval * 100));
+}
+
+#[test]
+fn counts_print_when_they_pass_a_threshold_multiple() {
+    let config = load("[monitoring]\nstats_every_opportunities = 10\nstats_every_executions = 0\n").unwrap();
+    let start = Instant::now();
+    let mut state = MonitoringState::new();
+
+    state.total_opportunities = 9;
+    state.total_executions = 500;
+    assert!(!should_print_statistics(&state, &config, start, start));
+    // Several opportunities in one cycle step over the multiple itself
+    state.total_opportunities = 12;
+    assert!(should_print_statistics(&state, &config, start, start));
+    mark_statistics_printed(&mut state, start);
+    state.total_opportunities = 19;
+    assert!(!should_print_statistics(&state, &config, start, start));
+    state.total_market_making_signals = 25;
+    assert!(should_print_statistics(&state, &config, start, start));
+}
+
+#[test]
+fn restored_counts_are_not_reported_again() {
+    let config = load("").unwrap();
+    let start = Instant::now();
+    let mut state = MonitoringState::new();
+    let saved = BotState {
+        saved_at: chrono::Utc::now(),
+        counters: SessionCounters { total_opportunities: 120, ..Default::default() },
+        volatility_history: Default::default(),
+        last_cex_price: None,
+        last_dex_prices: Default::default(),
+        paper_portfolio: None,
+    };
+    state.restore(&saved);
+    assert!(!should_print_statistics(&state, &config, start, start));
+    state.total_opportunities = 150;
+    assert!(should_print_statistics(&state, &config, start, start));
+}
+
+#[test]
+fn cadence_is_configurable_and_reloadable() {
+    let mut config = load("").unwrap();
+    let changed = config.apply_reloadable(&load("[monitoring]\nstats_interval_secs = 60\nstats_every_signals = 0\n").unwrap());
+    assert_eq!(changed, vec!["stats_interval_secs", "stats_every_signals"]);
+    assert_eq!((config.stats_interval_secs, config.stats_every_signals), (60, 0));
+    assert!(load("[monitoring]\nstats_every_executions = -1\n").is_err());
+}
@@ -0,0 +1,176 @@
+//! Health and status HTTP endpoints
+
+use aero_arb_mm_bot::api::{spawn_status_server, StatusBoard};
+use aero_arb_mm_bot::types::{
+    CircuitBreakerState, CircuitBreakerStatus, HealthStatus, MonitoringState, RecentActivity, SessionSummary, StatusReport,
+};
+use aero_arb_mm_bot::errors::{BreakerLimits, CircuitBreakerRegistry};
+use aero_arb_mm_bot::utils::spawn_health_monitor;
+use aero_arb_mm_bot::volatility::MultiTimeframeVolatilityCalculator;
+use rust_decimal_macros::dec;
+use std::collections::{BTreeMap, HashMap};
+
+async fn report(healthy: bool) -> StatusReport {
+    StatusReport {
+        timestamp: chrono::Utc::now(),
+        paused: false,
+        is_leader: true,
+        health: HealthStatus {
+            dex_connection: healthy,
+            cex_connection: true,
+            last_dex_update: None,
+            last_cex_update: None,
+            consecutive_errors: 0,
+            circuit_breaker_active: false,
+            open_breakers: Vec::new(),
+            uptime_seconds: 42,
+            latency: BTreeMap::new(),
+            wallet_balances: None,
+        },
+        volatility: MultiTimeframeVolatilityCalculator::new().get_volatility_metrics().await,
+        circuit_breakers: CircuitBreakerStatus {
+            cex: CircuitBreakerState { open: false, consecutive_errors: 0, cooldown_remaining_secs: None },
+            rpc: CircuitBreakerState { open: false, consecutive_errors: 0, cooldown_remaining_secs: None },
+            pools: BTreeMap::new(),
+        },
+        session: SessionSummary {
+            timestamp: chrono::Utc::now(),
+            runtime_secs: 42,
+            total_opportunities: 3,
+            profitable_opportunities: 1,
+            total_potential_profit_usd: dec!(1.25),
+            total_market_making_signals: 5,
+            total_executions: 0,
+            successful_executions: 0,
+            deferred_opportunities: 0,
+            reevaluated_deferrals: 0,
+            expired_deferrals: 0,
+            error_counts: HashMap::new(),
+            inventory: None,
+            paper_portfolio: None,
+            market_making_quotes: None,
+            stress_tests: Vec::new(),
+        },
+        monitoring: MonitoringState::new().snapshot(),
+    }
+}
+
+#[tokio::test]
+async fn serves_health_and_status() {
+    let board = StatusBoard::new();
+    let addr = spawn_status_server("127.0.0.1:0", board.clone()).await.unwrap();
+    let client = reqwest::Client::new();
+    let get = |path: &str| client.get(format!("http://{}{}", addr, path)).send();
+
+    // Nothing published before the first cycle
+    assert_eq!(get("/health").await.unwrap().status(), 503);
+
+    board.publish(report(true).await).await;
+    let health = get("/health").await.unwrap();
+    assert_eq!(health.status(), 200);
+    let health: serde_json::Value = health.json().await.unwrap();
+    assert_eq!(health["uptime_seconds"], 42);
+
+    let status: serde_json::Value = get("/status").await.unwrap().json().await.unwrap();
+    assert_eq!(status["session"]["total_opportunities"], 3);
+    assert_eq!(status["circuit_breakers"]["cex"]["open"], false);
+    assert!(status["volatility"].is_object());
+
+    board.publish(report(false).await).await;
+    assert_eq!(get("/health").await.unwrap().status(), 503);
+    assert_eq!(get("/status").await.unwrap().status(), 200);
+    assert_eq!(get("/metrics").await.unwrap().status(), 404);
+}
+
+#[tokio::test]
+async fn dashboard_streams_reports_with_recent_activity() {
+    let board = StatusBoard::new();
+    let addr = spawn_status_server("127.0.0.1:0", board.clone()).await.unwrap();
+    let client = reqwest::Client::new();
+
+    let page = client.get(format!("http://{}/", addr)).send().await.unwrap();
+    assert!(page.headers()["content-type"].to_str().unwrap().starts_with("text/html"));
+    assert!(page.text().await.unwrap().contains("EventSource(\"/events\")"));
+
+    let mut activity = RecentActivity::default();
+    let report = report(true).await;
+    activity.record_volatility(Some(dec!(3000)), &report.volatility);
+    board.publish_activity(activity).await;
+
+    let mut events = client.get(format!("http://{}/events", addr)).send().await.unwrap();
+    assert_eq!(events.headers()["content-type"], "text/event-stream");
+    board.publish(report).await;
+
+    let mut event = String::new();
+    while !event.ends_with("\n\n") {
+        let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), events.chunk()).await
+            .unwrap().unwrap().unwrap();
+        event.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+    let data: serde_json::Value = serde_json::from_str(event.trim().strip_prefix("data: ").unwrap()).unwrap();
+    assert_eq!(data["status"]["session"]["total_opportunities"], 3);
+    assert_eq!(data["activity"]["volatility"][0]["eth_usd"], "3000");
+}
+
+#[tokio::test]
+async fn monitoring_snapshot_is_served_as_taken() {
+    let board = StatusBoard::new();
+    let addr = spawn_status_server("127.0.0.1:0", board.clone()).await.unwrap();
+
+    let mut state = MonitoringState::new();
+    state.total_opportunities = 7;
+    state.error_counts.insert("cex_price".to_string(), 2);
+    state.last_dex_prices.insert("WETH/USDC".to_string(), dec!(2999.5));
+    state.cex_last_update = Some(std::time::Instant::now());
+    let snapshot = state.snapshot();
+    // Later changes don't reach a snapshot already taken
+    state.total_opportunities += 1;
+    assert_eq!(snapshot.counters.total_opportunities, 7);
+    assert!(snapshot.is_leader && snapshot.dex_update_age_ms.is_none());
+
+    let mut report = report(true).await;
+    report.monitoring = snapshot;
+    board.publish(report).await;
+    let url = format!("http://{}/monitoring", addr);
+    let monitoring: serde_json::Value = reqwest::get(url).await.unwrap().json().await.unwrap();
+    assert_eq!(monitoring["counters"]["total_opportunities"], 7);
+    assert_eq!(monitoring["error_counts"]["cex_price"], 2);
+    assert_eq!(monitoring["last_dex_prices"]["WETH/USDC"], "2999.5");
+    assert!(monitoring["cex_update_age_ms"].is_u64());
+}
+
+async fn next_health(health: &mut tokio::sync::watch::Receiver<Option<HealthStatus>>) -> HealthStatus {
+    tokio::time::timeout(std::time::Duration::from_secs(5), health.changed()).await.unwrap().unwrap();
+    health.borrow_and_update().clone().unwrap()
+}
+
+#[tokio::test]
+async fn health_is_checked_on_its_own_interval() {
+    let board = StatusBoard::new();
+    let limits = BreakerLimits { max_errors: 5, cooldown_secs: 60 };
+    let breakers = std::sync::Arc::new(CircuitBreakerRegistry::new(limits, limits, limits));
+    let mut health = board.subscribe_health();
+    let monitor = spawn_health_monitor(board.clone(), breakers, std::time::Instant::now(), std::time::Duration::from_millis(20));
+
+    // Checked before any cycle completes: no feed has updated yet
+    let first = next_health(&mut health).await;
+    assert!(!first.dex_connection && !first.is_healthy());
+    assert!(board.latest().await.is_none());
+
+    // Feed updates from the last published report reach the next check
+    let mut published = report(true).await;
+    published.health.last_dex_update = Some(std::time::Instant::now());
+    published.health.last_cex_update = Some(std::time::Instant::now());
+    board.publish(published).await;
+    // A check already underway may still see the old report
+    let checked = loop {
+        let checked = next_health(&mut health).await;
+        if checked.dex_connection {
+            break checked;
+        }
+    };
+    assert!(checked.cex_connection);
+    assert!(board.health().unwrap().is_healthy());
+    assert!(board.latest().await.unwrap().health.is_healthy());
+    monitor.abort();
+}
@@ -0,0 +1,158 @@
+//! Strategy registration, evaluation order and the built-in arbitrage strategy
+
+mod common;
+
+use aero_arb_mm_bot::config::Config;
+use aero_arb_mm_bot::strategy::{
+    Action, ArbitrageStrategy, MarketState, PoolState, Strategies, Strategy, StrategyFuture,
+};
+use aero_arb_mm_bot::types::{
+    CexQuotes, ExecutionUrgency, FairValueQuotes, PoolInfo, PriceBasis, VolatilityAdjustments, VolatilityConfidence,
+    VolatilityEstimator, VolatilityImpact, VolatilityMetrics, VolatilityTrend,
+};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use common::{load, weth_usdc_pool};
+
+fn volatility() -> VolatilityMetrics {
+    VolatilityMetrics {
+        short_term_volatility: dec!(0.3),
+        medium_term_volatility: dec!(0.3),
+        long_term_volatility: dec!(0.3),
+        estimator: VolatilityEstimator::Realized,
+        sample_count: 60,
+        confidence: VolatilityConfidence::Full,
+        volatility_trend: VolatilityTrend::Stable,
+        impact_assessment: VolatilityImpact::Low,
+        recommended_adjustments: VolatilityAdjustments {
+            spread_multiplier: dec!(1),
+            position_size_factor: dec!(1),
+            execution_urgency: ExecutionUrgency::Normal,
+        },
+        pool: None,
+    }
+}
+
+fn test_config() -> Config {
+    load("").unwrap()
+}
+
+fn pool_state<'a>(pool: &'a PoolInfo, fair_values: &'a FairValueQuotes, dex_price: Decimal, trade_size: Decimal) -> PoolState<'a> {
+    PoolState {
+        pool,
+        dex_price,
+        quote_usd: dec!(1),
+        cex_price: fair_values.cex_last,
+        fair_values,
+        price_basis: PriceBasis::CexLast,
+        fair_value: fair_values.cex_last,
+        price_diff_pct: (dex_price - fair_values.cex_last).abs() / fair_values.cex_last * dec!(100),
+        trade_size,
+        quote: None,
+        reserves: None,
+        liquidity_depth: None,
+        health: None,
+        basis: None,
+    }
+}
+
+/// Echoes the arbitrage opportunities of the built-in strategy at a fixed size
+struct FixedSize(Decimal);
+
+impl Strategy for FixedSize {
+    fn name(&self) -> &str {
+        "fixed_size"
+    }
+
+    fn evaluate<'a>(&'a self, pool: &'a PoolState<'a>, market: &'a MarketState<'a>) -> StrategyFuture<'a> {
+        Box::pin(async move {
+            let resized = PoolState { trade_size: self.0, ..*pool };
+            ArbitrageStrategy.evaluate(&resized, market).await
+        })
+    }
+}
+
+struct Failing;
+
+impl Strategy for Failing {
+    fn name(&self) -> &str {
+        "failing"
+    }
+
+    fn evaluate<'a>(&'a self, _pool: &'a PoolState<'a>, _market: &'a MarketState<'a>) -> StrategyFuture<'a> {
+        Box::pin(async { Err(anyhow::anyhow!("no data")) })
+    }
+}
+
+struct Disabled;
+
+impl Strategy for Disabled {
+    fn name(&self) -> &str {
+        "disabled"
+    }
+
+    fn enabled(&self, _config: &Config) -> bool {
+        false
+    }
+
+    fn evaluate<'a>(&'a self, _pool: &'a PoolState<'a>, _market: &'a MarketState<'a>) -> StrategyFuture<'a> {
+        panic!("disabled strategies are not evaluated")
+    }
+}
+
+fn sizes(actions: &[Action]) -> Vec<Decimal> {
+    actions.iter().map(|action| match action {
+        Action::Arbitrage(opportunity) => opportunity.size_eth,
+        other => panic!("unexpected action {:?}", other),
+    }).collect()
+}
+
+#[tokio::test]
+async fn strategies_run_in_registration_order() {
+    let pool = weth_usdc_pool();
+    let fair_values = FairValueQuotes::new(CexQuotes { last: dec!(3000), book: None }, None);
+    let volatility = volatility();
+    let config = test_config();
+    let market = MarketState {
+        eth_usd: dec!(3000),
+        gas_cost_usd: dec!(0.05),
+        gas_baseline_usd: dec!(0.05),
+        oracle_price: None,
+        volatility: &volatility,
+        config: &config,
+    };
+    let strategies = Strategies::new()
+        .with_strategy(ArbitrageStrategy)
+        .with_strategies(Strategies::new().with_strategy(Failing).with_strategy(Disabled).with_strategy(FixedSize(dec!(2))));
+    assert_eq!(strategies.names(), ["arbitrage", "failing", "disabled", "fixed_size"]);
+
+    let actions = strategies.evaluate(&pool_state(&pool, &fair_values, dec!(2970), dec!(0.5)), &market).await;
+    assert_eq!(sizes(&actions), [dec!(0.5), dec!(2)]);
+}
+
+#[tokio::test]
+async fn arbitrage_strategy_skips_zero_size_and_flat_prices() {
+    let pool = weth_usdc_pool();
+    let fair_values = FairValueQuotes::new(CexQuotes { last: dec!(3000), book: None }, None);
+    let volatility = volatility();
+    let config = test_config();
+    let market = MarketState {
+        eth_usd: dec!(3000),
+        gas_cost_usd: dec!(0.05),
+        gas_baseline_usd: dec!(0.05),
+        oracle_price: None,
+        volatility: &volatility,
+        config: &config,
+    };
+
+    let sized_out = ArbitrageStrategy.evaluate(&pool_state(&pool, &fair_values, dec!(2970), dec!(0)), &market).await.unwrap();
+    assert!(sized_out.is_empty());
+
+    let flat = ArbitrageStrategy.evaluate(&pool_state(&pool, &fair_values, dec!(3000), dec!(1)), &market).await.unwrap();
+    assert!(flat.is_empty());
+
+    let wide = ArbitrageStrategy.evaluate(&pool_state(&pool, &fair_values, dec!(2970), dec!(1)), &market).await.unwrap();
+    let [Action::Arbitrage(opportunity)] = wide.as_slice() else { panic!("expected one opportunity") };
+    assert_eq!(opportunity.price_basis, PriceBasis::CexLast);
+    assert_eq!(opportunity.fair_values.as_ref(), Some(&fair_values));
+}
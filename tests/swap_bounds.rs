@@ -0,0 +1,79 @@
+//! Volatility-scaled swap minimum output and deadline
+
+mod common;
+
+use aero_arb_mm_bot::arbitrage::{calculate_arbitrage, FeeModel, DEFAULT_MIN_PRICE_DIFF_PCT};
+use aero_arb_mm_bot::execution::{
+    router_swap_calldata, router_swap_input, router_swap_min_out, router_swap_output, sepolia_swap_calldata, swap_bounds, swap_deadline,
+};
+use aero_arb_mm_bot::types::{SwapBounds, VolatilityImpact, USDC_SEPOLIA, WETH_SEPOLIA};
+use alloy::primitives::{address, U256};
+use rust_decimal_macros::dec;
+use std::time::SystemTime;
+use common::weth_usdc_pool;
+
+#[test]
+fn bounds_widen_with_volatility() {
+    let bands = [
+        VolatilityImpact::Low,
+        VolatilityImpact::Moderate,
+        VolatilityImpact::High,
+        VolatilityImpact::Extreme,
+    ].map(|impact| swap_bounds(40, impact));
+
+    assert_eq!(bands[0], SwapBounds { slippage_bps: 20, deadline_secs: 60 });
+    assert_eq!(bands[1], SwapBounds { slippage_bps: 40, deadline_secs: 120 });
+    assert_eq!(bands[2], SwapBounds { slippage_bps: 60, deadline_secs: 300 });
+    assert_eq!(bands[3], SwapBounds { slippage_bps: 80, deadline_secs: 600 });
+}
+
+#[test]
+fn slippage_stays_within_the_cap_and_above_zero() {
+    assert_eq!(swap_bounds(100, VolatilityImpact::Extreme).slippage_bps, 100);
+    assert_eq!(swap_bounds(1, VolatilityImpact::Low).slippage_bps, 1);
+}
+
+#[test]
+fn calldata_carries_the_bounds() {
+    let to = address!("1111111111111111111111111111111111111111");
+    let pool = weth_usdc_pool();
+    let calm = router_swap_calldata(&pool, false, dec!(1), dec!(3000), to, swap_bounds(50, VolatilityImpact::Low)).unwrap();
+    let wild = router_swap_calldata(&pool, false, dec!(1), dec!(3000), to, swap_bounds(50, VolatilityImpact::Extreme)).unwrap();
+
+    // Selling 1 WETH at $3,000: 0.25% and 1% below
+    assert_eq!(router_swap_min_out(&calm), Some(U256::from(2_992_500_000u64)));
+    assert_eq!(router_swap_min_out(&wild), Some(U256::from(2_970_000_000u64)));
+    assert_eq!(router_swap_min_out(&[0u8; 4]), None);
+
+    // An amount too large to encode fails instead of sending zero
+    let huge = dec!(10_000_000_000_000_000_000_000);
+    for buy_base in [true, false] {
+        assert!(router_swap_calldata(&pool, buy_base, huge, dec!(3000), to, swap_bounds(50, VolatilityImpact::Low)).is_err());
+    }
+}
+
+#[test]
+fn sepolia_calldata_spends_the_input_token() {
+    let to = address!("1111111111111111111111111111111111111111");
+    let bounds = SwapBounds { slippage_bps: 50, deadline_secs: 60 };
+
+    // Buying 1 WETH at $2,990 spends USDC and expects WETH back
+    let buy = calculate_arbitrage("WETH/USDC", dec!(2990), dec!(3000), dec!(1), None, None, dec!(1), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    let calldata = sepolia_swap_calldata(&buy, to, bounds, 18, 6).unwrap();
+    assert_eq!(router_swap_input(&calldata), Some((USDC_SEPOLIA, U256::from(2_990_000_000u64))));
+    assert_eq!(router_swap_output(&calldata), Some((WETH_SEPOLIA, to)));
+    assert_eq!(router_swap_min_out(&calldata), Some(U256::from(995_000_000_000_000_000u64)));
+
+    // Selling 1 WETH at $3,010 spends WETH and expects USDC back
+    let sell = calculate_arbitrage("WETH/USDC", dec!(3010), dec!(3000), dec!(1), None, None, dec!(1), &FeeModel::zero(), DEFAULT_MIN_PRICE_DIFF_PCT).unwrap();
+    let calldata = sepolia_swap_calldata(&sell, to, bounds, 18, 6).unwrap();
+    assert_eq!(router_swap_input(&calldata), Some((WETH_SEPOLIA, U256::from(1_000_000_000_000_000_000u64))));
+    assert_eq!(router_swap_min_out(&calldata), Some(U256::from(2_994_950_000u64)));
+}
+
+#[test]
+fn deadline_is_relative_to_now() {
+    let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
+    let deadline = swap_deadline(&SwapBounds { slippage_bps: 50, deadline_secs: 60 }).unwrap();
+    assert!(deadline >= U256::from(now + 60) && deadline <= U256::from(now + 61));
+}
@@ -0,0 +1,51 @@
+//! Router output decoding and preflight results on executions
+
+use aero_arb_mm_bot::execution::decode_amount_out;
+use aero_arb_mm_bot::types::{SwapPreflight, TradeExecution};
+use alloy::primitives::U256;
+use alloy::sol_types::SolValue;
+use rust_decimal_macros::dec;
+
+#[test]
+fn amount_out_is_the_last_router_amount() {
+    let output = vec![U256::from(3_000_000_000u64), U256::from(999_000_000_000_000_000u64)].abi_encode();
+    assert_eq!(decode_amount_out(&output), Some(U256::from(999_000_000_000_000_000u64)));
+
+    assert_eq!(decode_amount_out(&[]), None);
+    assert_eq!(decode_amount_out(&Vec::<U256>::new().abi_encode()), None);
+}
+
+#[test]
+fn executions_without_a_preflight_still_load() {
+    let json = r#"{
+        "id": "execution",
+        "opportunity_id": "opportunity",
+        "timestamp": "2025-01-01T00:00:00Z",
+        "network": "Base Sepolia",
+        "trade_type": "BuyDexSellCex",
+        "status": "Failed",
+        "tx_hash": null,
+        "gas_used": null,
+        "gas_price_gwei": null,
+        "execution_time_ms": 12,
+        "expected_profit_usd": "4.5",
+        "actual_profit_usd": null,
+        "slippage_bps": null,
+        "error_message": "Preflight simulation reverted",
+        "dex_fill_price": null,
+        "cex_fill_price": null,
+        "cex_order_id": null,
+        "volatility_impact": null
+    }"#;
+    let mut execution: TradeExecution = serde_json::from_str(json).unwrap();
+    assert!(execution.preflight.is_none());
+
+    execution.preflight = Some(SwapPreflight {
+        amount_out: Some(dec!(0.999)),
+        revert_reason: None,
+    });
+    let reloaded: TradeExecution = serde_json::from_str(&serde_json::to_string(&execution).unwrap()).unwrap();
+    let preflight = reloaded.preflight.unwrap();
+    assert!(!preflight.reverted());
+    assert_eq!(preflight.amount_out, Some(dec!(0.999)));
+}
@@ -0,0 +1,131 @@
+//! Router allowance caching, approval amounts and the approve() whitelist
+
+mod common;
+
+use aero_arb_mm_bot::execution::{
+    approval_amount, approve_calldata, received_amount, router_swap_calldata, router_swap_input, router_swap_output,
+    AllowanceCache, TransactionPolicy,
+};
+use aero_arb_mm_bot::types::{
+    SwapBounds, AERODROME_ROUTER_MAINNET, UNISWAP_V2_ROUTER_SEPOLIA, USDC_MAINNET, WETH_MAINNET,
+};
+use alloy::primitives::{address, Address, U256};
+use alloy::rpc::types::eth::TransactionRequest;
+use rust_decimal_macros::dec;
+use common::{load, weth_usdc_pool};
+
+const BOUNDS: SwapBounds = SwapBounds { slippage_bps: 50, deadline_secs: 120 };
+
+#[test]
+fn swap_input_is_read_from_router_calldata() {
+    let to = address!("1111111111111111111111111111111111111111");
+    let buy = router_swap_calldata(&weth_usdc_pool(), true, dec!(1), dec!(3000), to, BOUNDS).unwrap();
+    assert_eq!(router_swap_input(&buy), Some((USDC_MAINNET, U256::from(3_000_000_000u64))));
+
+    let sell = router_swap_calldata(&weth_usdc_pool(), false, dec!(0.5), dec!(3000), to, BOUNDS).unwrap();
+    assert_eq!(router_swap_input(&sell), Some((WETH_MAINNET, U256::from(500_000_000_000_000_000u64))));
+
+    // Approvals and other calls are not swaps
+    assert_eq!(router_swap_input(&approve_calldata(AERODROME_ROUTER_MAINNET, U256::from(1))), None);
+
+    assert_eq!(router_swap_output(&buy), Some((WETH_MAINNET, to)));
+    assert_eq!(router_swap_output(&sell), Some((USDC_MAINNET, to)));
+}
+
+#[test]
+fn received_amounts_come_from_transfer_logs() {
+    let wallet = address!("1111111111111111111111111111111111111111");
+    let pool = address!("cDAC0d6c6C59727a65F871236188350531885C43");
+    let transfer_topic = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+    let word = |address: Address| format!("0x000000000000000000000000{}", alloy::primitives::hex::encode(address));
+    let log = |token: Address, to: Address, value: u64| serde_json::json!({
+        "address": token,
+        "topics": [transfer_topic, word(pool), word(to)],
+        "data": format!("0x{:064x}", value),
+        "blockNumber": "0x1",
+        "transactionHash": format!("0x{}", "ab".repeat(32)),
+        "transactionIndex": "0x0",
+        "blockHash": format!("0x{}", "cd".repeat(32)),
+        "logIndex": "0x0",
+        "removed": false,
+    });
+    let receipt: alloy::rpc::types::eth::TransactionReceipt = serde_json::from_value(serde_json::json!({
+        "type": "0x2",
+        "status": "0x1",
+        "cumulativeGasUsed": "0x5208",
+        "logs": [
+            log(WETH_MAINNET, wallet, 400_000_000_000_000_000),
+            log(WETH_MAINNET, pool, 7),
+            log(USDC_MAINNET, wallet, 9),
+        ],
+        "logsBloom": format!("0x{}", "00".repeat(256)),
+        "transactionHash": format!("0x{}", "ab".repeat(32)),
+        "transactionIndex": "0x0",
+        "blockHash": format!("0x{}", "cd".repeat(32)),
+        "blockNumber": "0x1",
+        "gasUsed": "0x5208",
+        "effectiveGasPrice": "0x3b9aca00",
+        "from": wallet,
+        "to": AERODROME_ROUTER_MAINNET,
+        "contractAddress": null,
+    })).unwrap();
+
+    // Only the output token's transfers to the recipient count
+    assert_eq!(received_amount(&receipt, WETH_MAINNET, wallet), U256::from(400_000_000_000_000_000u64));
+    assert_eq!(received_amount(&receipt, USDC_MAINNET, pool), U256::ZERO);
+}
+
+#[test]
+fn approvals_are_exact_unless_infinite() {
+    let required = U256::from(3_000_000_000u64);
+    assert_eq!(approval_amount(required, false), required);
+    assert_eq!(approval_amount(required, true), U256::MAX);
+}
+
+#[tokio::test]
+async fn cached_allowances_are_drawn_down_by_swaps() {
+    let cache = AllowanceCache::new();
+    let router = AERODROME_ROUTER_MAINNET;
+    assert!(!cache.covers(USDC_MAINNET, router, U256::from(1)).await);
+
+    cache.record(USDC_MAINNET, router, U256::from(100)).await;
+    assert!(cache.covers(USDC_MAINNET, router, U256::from(100)).await);
+    assert!(!cache.covers(WETH_MAINNET, router, U256::from(1)).await);
+    assert!(!cache.covers(USDC_MAINNET, UNISWAP_V2_ROUTER_SEPOLIA, U256::from(1)).await);
+
+    cache.spend(USDC_MAINNET, router, U256::from(60)).await;
+    assert!(cache.covers(USDC_MAINNET, router, U256::from(40)).await);
+    assert!(!cache.covers(USDC_MAINNET, router, U256::from(41)).await);
+
+    // Infinite approvals are not decremented
+    cache.record(WETH_MAINNET, router, U256::MAX).await;
+    cache.spend(WETH_MAINNET, router, U256::from(1_000)).await;
+    assert!(cache.covers(WETH_MAINNET, router, U256::MAX).await);
+
+    cache.invalidate(USDC_MAINNET, router).await;
+    assert!(!cache.covers(USDC_MAINNET, router, U256::from(1)).await);
+}
+
+#[test]
+fn only_router_spenders_can_be_approved() {
+    let config = load("").unwrap();
+    let policy = TransactionPolicy::new(&config, None);
+    // Mainnet pair tokens become approvable once their pool is traded
+    policy.allow_pair_approvals(&weth_usdc_pool().pair);
+    let approve = |spender: Address| {
+        TransactionRequest::default()
+            .to(USDC_MAINNET)
+            .input(approve_calldata(spender, U256::MAX).into())
+            .gas_limit(100_000)
+    };
+
+    assert!(policy.check(&approve(AERODROME_ROUTER_MAINNET)).is_ok());
+    assert!(policy.check(&approve(UNISWAP_V2_ROUTER_SEPOLIA)).is_ok());
+    assert!(policy.check(&approve(address!("2222222222222222222222222222222222222222"))).is_err());
+}
+
+#[test]
+fn infinite_approvals_default_off() {
+    assert!(!load("").unwrap().infinite_approvals);
+    assert!(load("[networks.execution]\ninfinite_approvals = true\n").unwrap().infinite_approvals);
+}
@@ -0,0 +1,89 @@
+//! Pair naming, CEX symbol resolution and reserve scaling for arbitrary token pairs
+
+use aero_arb_mm_bot::pools::{default_cex_symbol, pair_symbols};
+use aero_arb_mm_bot::types::{PoolInfo, PoolKind, StablePrices, TokenPair, USDC_MAINNET};
+use alloy::primitives::{address, U256};
+use rust_decimal_macros::dec;
+use std::time::Instant;
+
+const AERO: alloy::primitives::Address = address!("940181a94A35A4569E4529A3CDfB74e38FD98631");
+
+fn aero_usdc_pool() -> PoolInfo {
+    PoolInfo {
+        address: address!("6cDcb1C4A4D1C3C6d054b27AC5B77e89eAFb971d"),
+        name: "vAMM-AERO/USDC".to_string(),
+        // USDC sorts before AERO here, so the base is token1
+        token0: USDC_MAINNET,
+        token1: AERO,
+        pair: TokenPair {
+            base: AERO,
+            quote: USDC_MAINNET,
+            base_symbol: "AERO".to_string(),
+            quote_symbol: "USDC".to_string(),
+            base_decimals: 18,
+            quote_decimals: 6,
+            cex_symbol: "AEROUSDC".to_string(),
+        },
+        is_stable: false,
+        kind: PoolKind::Basic,
+        fee: dec!(0.003),
+        min_liquidity: dec!(1000),
+        last_update: Instant::now(),
+    }
+}
+
+#[test]
+fn pair_symbols_strip_pool_prefixes_and_suffixes() {
+    assert_eq!(pair_symbols("vAMM-AERO/USDC"), Some(("AERO", "USDC")));
+    assert_eq!(pair_symbols("CL100-WETH/USDC"), Some(("WETH", "USDC")));
+    assert_eq!(pair_symbols("WETH/USDC-Sepolia"), Some(("WETH", "USDC")));
+    assert_eq!(pair_symbols("cbETH/WETH"), Some(("cbETH", "WETH")));
+    assert_eq!(pair_symbols("AERO"), None);
+}
+
+#[test]
+fn default_cex_symbol_maps_wrapped_assets() {
+    assert_eq!(default_cex_symbol("WETH", "USDbC"), "ETHUSDC");
+    assert_eq!(default_cex_symbol("AERO", "USDC"), "AEROUSDC");
+    assert_eq!(default_cex_symbol("cbETH", "WETH"), "CBETHETH");
+}
+
+#[test]
+fn reserves_scale_with_each_token_decimals() {
+    let pool = aero_usdc_pool();
+    let usdc = U256::from(1_200_000_000u64); // 1,200 USDC
+    let aero = U256::from(1_000u64) * U256::from(10u64).pow(U256::from(18)); // 1,000 AERO
+    assert_eq!(pool.base_quote_reserves(usdc, aero), Some((dec!(1000), dec!(1200))));
+}
+
+#[test]
+fn quote_and_cex_prices_convert_to_usd() {
+    let stables = StablePrices::new();
+    let mut pair = aero_usdc_pool().pair;
+    assert!(!pair.is_weth_usd());
+    assert_eq!(pair.quote_usd(dec!(3000), &stables), Some(dec!(1)));
+
+    pair.quote_symbol = "WETH".to_string();
+    pair.cex_symbol = "AEROETH".to_string();
+    assert_eq!(pair.quote_usd(dec!(3000), &stables), Some(dec!(3000)));
+    assert_eq!(pair.cex_quote_usd(dec!(3000)), Some(dec!(3000)));
+
+    pair.quote_symbol = "DEGEN".to_string();
+    pair.cex_symbol = "AEROBTC".to_string();
+    assert_eq!(pair.quote_usd(dec!(3000), &stables), None);
+    assert_eq!(pair.cex_quote_usd(dec!(3000)), None);
+}
+
+#[test]
+fn configured_stable_quotes_convert_to_usd() {
+    let mut pair = aero_usdc_pool().pair;
+    pair.quote_symbol = "DOLA".to_string();
+    assert_eq!(pair.quote_usd(dec!(3000), &StablePrices::new()), None);
+
+    let stables = StablePrices::from([("DOLA".to_string(), dec!(0.998))]);
+    assert_eq!(pair.quote_usd(dec!(3000), &stables), Some(dec!(0.998)));
+    // Configured prices never override the built-in stables
+    pair.quote_symbol = "USDC".to_string();
+    let stables = StablePrices::from([("USDC".to_string(), dec!(0.5))]);
+    assert_eq!(pair.quote_usd(dec!(3000), &stables), Some(dec!(1)));
+}
@@ -0,0 +1,46 @@
+//! Sample counts and confidence of volatility readings
+
+use aero_arb_mm_bot::types::{VolatilityConfidence, VolatilityImpact, VolatilityTrend, MIN_VOLATILITY_SAMPLES};
+use aero_arb_mm_bot::volatility::MultiTimeframeVolatilityCalculator;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::time::{Duration, SystemTime};
+
+/// A calm market: one sample every `step_secs` alternating by 0.001%
+async fn calm(samples: u64, step_secs: u64) -> MultiTimeframeVolatilityCalculator {
+    let calculator = MultiTimeframeVolatilityCalculator::new();
+    let start = SystemTime::now() - Duration::from_secs(samples * step_secs);
+    for i in 0..samples {
+        let price = if i % 2 == 0 { dec!(3000) } else { dec!(3000.03) };
+        calculator.add_price_at(start + Duration::from_secs(i * step_secs), price).await;
+    }
+    calculator
+}
+
+#[tokio::test]
+async fn too_few_samples_read_as_moderate_not_calm() {
+    let metrics = calm(MIN_VOLATILITY_SAMPLES as u64 - 1, 1).await.get_volatility_metrics().await;
+    assert_eq!(metrics.sample_count, MIN_VOLATILITY_SAMPLES - 1);
+    assert_eq!(metrics.confidence, VolatilityConfidence::Low);
+    assert!(metrics.is_low_confidence());
+    assert_eq!(metrics.short_term_volatility, Decimal::ZERO);
+    assert_eq!(metrics.impact_assessment, VolatilityImpact::Moderate);
+    assert!(matches!(metrics.volatility_trend, VolatilityTrend::Volatile));
+    assert_eq!(metrics.recommended_adjustments.spread_multiplier, dec!(1.5));
+    // Risk estimates assume the Moderate threshold
+    assert_eq!(metrics.conservative_short_term_volatility(), metrics.estimator.impact_thresholds()[0]);
+}
+
+#[tokio::test]
+async fn confidence_grows_with_the_history() {
+    let warming_up = calm(60, 1).await.get_volatility_metrics().await;
+    assert_eq!((warming_up.sample_count, warming_up.confidence), (60, VolatilityConfidence::Partial));
+    assert_eq!(warming_up.impact_assessment, VolatilityImpact::Low);
+
+    // 40 minutes of samples, 5 minutes of them in the short-term window
+    let full = calm(240, 10).await.get_volatility_metrics().await;
+    assert_eq!(full.confidence, VolatilityConfidence::Full);
+    assert_eq!(full.sample_count, 31);
+    assert!(!full.is_low_confidence());
+    assert_eq!(full.conservative_short_term_volatility(), full.short_term_volatility);
+}
@@ -0,0 +1,74 @@
+//! Log-return, realized, EWMA, GARCH and price-level volatility estimators
+
+use aero_arb_mm_bot::storage::PriceHistory;
+use aero_arb_mm_bot::types::{VolatilityEstimator, VolatilityImpact};
+use aero_arb_mm_bot::volatility::{MultiTimeframeVolatilityCalculator, VolatilityCalculator};
+use std::time::{Duration, SystemTime};
+
+fn history(prices: &[f64]) -> PriceHistory {
+    let mut history = PriceHistory::in_memory(Duration::from_secs(3600));
+    let start = SystemTime::now() - Duration::from_secs(prices.len() as u64);
+    for (i, price) in prices.iter().enumerate() {
+        history.push(start + Duration::from_secs(i as u64), *price);
+    }
+    history
+}
+
+fn estimate(estimator: VolatilityEstimator, prices: &[f64]) -> f64 {
+    VolatilityCalculator::new(300)
+        .with_estimator(estimator, 0.94)
+        .calculate_volatility_percentage(&history(prices))
+        .unwrap()
+}
+
+#[test]
+fn return_estimators_annualize_constant_variance() {
+    // One sample per second alternating by 1%
+    let prices: Vec<f64> = (0..20).map(|i| if i % 2 == 0 { 100.0 } else { 101.0 }).collect();
+    let per_sample = (1.01f64).ln();
+    let expected = per_sample * (365.0f64 * 24.0 * 3600.0).sqrt() * 100.0;
+
+    assert!((estimate(VolatilityEstimator::PriceStdev, &prices) - 0.4975).abs() < 0.001);
+    for estimator in [VolatilityEstimator::Realized, VolatilityEstimator::Ewma, VolatilityEstimator::Garch] {
+        let volatility = estimate(estimator, &prices);
+        assert!((volatility - expected).abs() / expected < 1e-9, "{} gave {}", estimator, volatility);
+    }
+    // Demeaning removes the one extra up move
+    let log_returns = estimate(VolatilityEstimator::LogReturns, &prices);
+    assert!(log_returns < expected && log_returns > expected * 0.99);
+
+    // Returns do not depend on the price level
+    let scaled: Vec<f64> = prices.iter().map(|p| p * 1000.0).collect();
+    assert!((estimate(VolatilityEstimator::LogReturns, &scaled) - log_returns).abs() / log_returns < 1e-9);
+}
+
+#[test]
+fn a_steady_trend_is_not_volatile_in_returns() {
+    let prices: Vec<f64> = (0..30).map(|i| 3000.0 * 1.003f64.powi(i)).collect();
+    assert!(estimate(VolatilityEstimator::LogReturns, &prices) < 1.0);
+    assert!(estimate(VolatilityEstimator::PriceStdev, &prices) > 2.0);
+}
+
+#[test]
+fn ewma_weights_a_late_move_above_realized() {
+    let mut prices = vec![100.0; 19];
+    prices.push(102.0);
+    assert!(estimate(VolatilityEstimator::Ewma, &prices) > estimate(VolatilityEstimator::Realized, &prices));
+    assert!(VolatilityCalculator::new(300).calculate_volatility_percentage(&history(&prices[..9])).is_none());
+}
+
+#[tokio::test]
+async fn metrics_report_their_estimator() {
+    assert_eq!("garch".parse::<VolatilityEstimator>().unwrap(), VolatilityEstimator::Garch);
+    assert!("stdev".parse::<VolatilityEstimator>().is_err());
+
+    let calculator = MultiTimeframeVolatilityCalculator::new().with_estimator(VolatilityEstimator::Ewma, 0.9);
+    assert_eq!(calculator.get_volatility_metrics().await.estimator, VolatilityEstimator::Ewma);
+
+    let metrics = MultiTimeframeVolatilityCalculator::new().get_volatility_metrics().await;
+    assert_eq!(metrics.estimator, VolatilityEstimator::LogReturns);
+    // Nothing measured yet reads as Moderate, not calm
+    assert!(matches!(metrics.impact_assessment, VolatilityImpact::Moderate));
+    assert_eq!(VolatilityEstimator::LogReturns.default_threshold(), rust_decimal_macros::dec!(80));
+    assert_eq!(VolatilityEstimator::PriceStdev.default_threshold(), rust_decimal_macros::dec!(5));
+}